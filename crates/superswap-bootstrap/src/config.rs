@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use anyhow::{ensure, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+/// Bootstrap configuration, loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct BootstrapConfig {
+    pub rpc_url: String,
+    /// Funded keypair that deploys, becomes the admin and mint authority, and pays for setup
+    pub keypair_path: PathBuf,
+    /// Keypair registered as the Across handler; generated when the file does not exist
+    pub handler_keypair_path: PathBuf,
+    #[serde(with = "pubkey_string", default = "default_program_id")]
+    pub program_id: Pubkey,
+    /// Program binary to deploy when the program is not on the cluster yet
+    #[serde(default)]
+    pub program: Option<ProgramDeployment>,
+    /// Deploys `mock-jupiter` and routes the program through it instead of `jupiter_program`
+    #[serde(default)]
+    pub mock_jupiter: Option<ProgramDeployment>,
+    #[serde(with = "pubkey_string", default = "default_jupiter_program")]
+    pub jupiter_program: Pubkey,
+    #[serde(with = "pubkey_string", default = "default_across_spoke_pool")]
+    pub across_spoke_pool: Pubkey,
+    #[serde(with = "pubkey_string", default = "default_cctp_token_messenger_minter")]
+    pub cctp_token_messenger_minter: Pubkey,
+    /// Inbound fee, in basis points
+    #[serde(default = "default_fee_bps")]
+    pub fee_bps: u16,
+    /// Outbound fee, in basis points
+    #[serde(default)]
+    pub outbound_fee_bps: u16,
+    /// Test USDC the handler is topped up to, standing in for bridged funds
+    #[serde(default = "default_handler_usdc")]
+    pub handler_usdc: u64,
+    /// Lamports the handler is topped up to, for fees and order accounts
+    #[serde(default = "default_handler_lamports")]
+    pub handler_lamports: u64,
+    /// Lamports the rent vault is topped up to, for recipient token accounts
+    #[serde(default = "default_rent_vault_lamports")]
+    pub rent_vault_lamports: u64,
+    /// Output mints to create, each given to the mock Jupiter pool when it is deployed
+    #[serde(default = "default_output_mints")]
+    pub output_mints: Vec<TestMint>,
+    /// Output tokens of each mint the mock Jupiter pool starts with
+    #[serde(default = "default_pool_liquidity")]
+    pub pool_liquidity: u64,
+    /// Where the environment file is written
+    #[serde(default = "default_env_file")]
+    pub env_file: PathBuf,
+}
+
+/// A program binary and the keypair of the address it deploys to
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ProgramDeployment {
+    pub so_path: PathBuf,
+    pub keypair_path: PathBuf,
+}
+
+/// A test output mint
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct TestMint {
+    /// Names the mint's variable in the environment file
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl BootstrapConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        let config: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse config {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(self.fee_bps <= 10_000, "fee_bps cannot exceed 10000");
+        ensure!(self.outbound_fee_bps <= 10_000, "outbound_fee_bps cannot exceed 10000");
+        ensure!(
+            self.keypair_path != self.handler_keypair_path,
+            "the handler must be a different keypair from the payer"
+        );
+        let mut symbols = HashSet::new();
+        for mint in &self.output_mints {
+            ensure!(
+                !mint.symbol.is_empty() && mint.symbol.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                "output mint symbol {:?} must be letters, digits and underscores",
+                mint.symbol
+            );
+            ensure!(
+                symbols.insert(mint.symbol.to_ascii_uppercase()),
+                "output mint symbol {} is listed twice",
+                mint.symbol
+            );
+        }
+        Ok(())
+    }
+
+    /// The Jupiter program the config routes through
+    pub fn routed_jupiter_program(&self) -> Pubkey {
+        if self.mock_jupiter.is_some() {
+            mock_jupiter::ID
+        } else {
+            self.jupiter_program
+        }
+    }
+}
+
+fn default_program_id() -> Pubkey {
+    superswap_sol::ID
+}
+
+fn default_jupiter_program() -> Pubkey {
+    pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4")
+}
+
+fn default_across_spoke_pool() -> Pubkey {
+    pubkey!("DLv3NggMiSaef97YCkew5xKUHDh13tVGZ7tydt3ZeAru")
+}
+
+fn default_cctp_token_messenger_minter() -> Pubkey {
+    pubkey!("CCTPiPYPc6AsJuwueEnWgSgucamXDZwBd53dQ11YiKX3")
+}
+
+fn default_fee_bps() -> u16 {
+    10
+}
+
+fn default_handler_usdc() -> u64 {
+    1_000_000_000_000
+}
+
+fn default_handler_lamports() -> u64 {
+    2_000_000_000
+}
+
+fn default_rent_vault_lamports() -> u64 {
+    1_000_000_000
+}
+
+fn default_output_mints() -> Vec<TestMint> {
+    vec![TestMint {
+        symbol: "TEST".to_string(),
+        decimals: 9,
+    }]
+}
+
+fn default_pool_liquidity() -> u64 {
+    1_000_000_000_000_000
+}
+
+fn default_env_file() -> PathBuf {
+    PathBuf::from("superswap.env")
+}
+
+mod pubkey_string {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Pubkey::from_str(&raw).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_parses() {
+        let raw = include_str!("../bootstrap.example.json");
+        let config: BootstrapConfig = serde_json::from_str(raw).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.program_id, superswap_sol::ID);
+        assert_eq!(config.routed_jupiter_program(), mock_jupiter::ID);
+        assert_eq!(config.output_mints.len(), 2);
+    }
+
+    #[test]
+    fn duplicate_symbols_are_rejected() {
+        let raw = r#"{"rpc_url": "http://127.0.0.1:8899", "keypair_path": "payer.json",
+            "handler_keypair_path": "handler.json",
+            "output_mints": [{"symbol": "test", "decimals": 9}, {"symbol": "TEST", "decimals": 6}]}"#;
+        let config: BootstrapConfig = serde_json::from_str(raw).unwrap();
+        assert!(config.validate().is_err());
+        assert_eq!(config.routed_jupiter_program(), default_jupiter_program());
+    }
+}
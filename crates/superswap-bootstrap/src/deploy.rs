@@ -0,0 +1,58 @@
+use std::path::Path;
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use log::info;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use tokio::process::Command;
+use crate::config::ProgramDeployment;
+
+/// Deploys `deployment` to `program_id` unless an executable program is already there
+///
+/// Deployment goes through `solana program deploy`, which handles buffer
+/// writes and retries, so the Solana CLI must be on the `PATH`.
+pub async fn ensure_deployed(
+    rpc: &RpcClient,
+    rpc_url: &str,
+    payer_keypair_path: &Path,
+    name: &str,
+    program_id: &Pubkey,
+    deployment: Option<&ProgramDeployment>,
+) -> Result<()> {
+    let account = rpc.get_account_with_commitment(program_id, rpc.commitment()).await?.value;
+    if account.is_some_and(|account| account.executable) {
+        info!("{} already deployed at {}", name, program_id);
+        return Ok(());
+    }
+    let Some(deployment) = deployment else {
+        bail!("{} is not deployed at {} and the config has no binary to deploy", name, program_id);
+    };
+
+    let program_keypair = read_keypair_file(&deployment.keypair_path)
+        .map_err(|err| anyhow!("failed to read keypair {}: {}", deployment.keypair_path.display(), err))?;
+    ensure!(
+        program_keypair.pubkey() == *program_id,
+        "{} keypair {} is for {}, not {}",
+        name,
+        deployment.keypair_path.display(),
+        program_keypair.pubkey(),
+        program_id
+    );
+
+    info!("deploying {} from {} to {}", name, deployment.so_path.display(), program_id);
+    let status = Command::new("solana")
+        .arg("program")
+        .arg("deploy")
+        .arg("--url")
+        .arg(rpc_url)
+        .arg("--keypair")
+        .arg(payer_keypair_path)
+        .arg("--program-id")
+        .arg(&deployment.keypair_path)
+        .arg(&deployment.so_path)
+        .status()
+        .await
+        .context("failed to run `solana program deploy`; is the Solana CLI installed?")?;
+    ensure!(status.success(), "deploying {} failed with {}", name, status);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+use std::path::PathBuf;
+use solana_sdk::pubkey::Pubkey;
+use superswap_pda::{find_config, find_rent_vault, insurance_usdc_account, program_usdc_account, wallet_token_account};
+
+/// What an integrator needs to point clients at a bootstrapped environment
+pub struct EnvFile {
+    pub rpc_url: String,
+    pub program_id: Pubkey,
+    /// Admin and mint authority of every test mint
+    pub admin: Pubkey,
+    pub admin_keypair_path: PathBuf,
+    pub usdc_mint: Pubkey,
+    pub across_handler: Pubkey,
+    pub handler_keypair_path: PathBuf,
+    pub fee_recipient: Pubkey,
+    pub jupiter_program: Pubkey,
+    /// Output mints by symbol
+    pub output_mints: Vec<(String, Pubkey)>,
+    /// Authority of the mock Jupiter pool, when the environment routes through the mock
+    pub mock_jupiter_pool: Option<Pubkey>,
+}
+
+impl fmt::Display for EnvFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# SuperSwap environment written by superswap-bootstrap")?;
+        // Read by superswap-cli
+        writeln!(f, "SUPERSWAP_RPC_URL={}", self.rpc_url)?;
+        writeln!(f, "SUPERSWAP_KEYPAIR={}", self.admin_keypair_path.display())?;
+
+        writeln!(f, "SUPERSWAP_PROGRAM_ID={}", self.program_id)?;
+        writeln!(f, "SUPERSWAP_CONFIG={}", find_config(&self.program_id).0)?;
+        writeln!(f, "SUPERSWAP_RENT_VAULT={}", find_rent_vault(&self.program_id).0)?;
+        writeln!(f, "SUPERSWAP_ADMIN={}", self.admin)?;
+        writeln!(f, "SUPERSWAP_USDC_MINT={}", self.usdc_mint)?;
+        writeln!(
+            f,
+            "SUPERSWAP_PROGRAM_USDC_ACCOUNT={}",
+            program_usdc_account(&self.program_id, &self.usdc_mint)
+        )?;
+        writeln!(
+            f,
+            "SUPERSWAP_INSURANCE_USDC_ACCOUNT={}",
+            insurance_usdc_account(&self.program_id, &self.usdc_mint)
+        )?;
+        writeln!(f, "SUPERSWAP_ACROSS_HANDLER={}", self.across_handler)?;
+        writeln!(f, "SUPERSWAP_ACROSS_HANDLER_KEYPAIR={}", self.handler_keypair_path.display())?;
+        writeln!(
+            f,
+            "SUPERSWAP_HANDLER_USDC_ACCOUNT={}",
+            wallet_token_account(&self.across_handler, &self.usdc_mint)
+        )?;
+        writeln!(f, "SUPERSWAP_FEE_RECIPIENT={}", self.fee_recipient)?;
+        writeln!(f, "SUPERSWAP_JUPITER_PROGRAM={}", self.jupiter_program)?;
+        if let Some(pool) = self.mock_jupiter_pool {
+            writeln!(f, "SUPERSWAP_MOCK_JUPITER_POOL={}", pool)?;
+        }
+        for (symbol, mint) in &self.output_mints {
+            writeln!(f, "SUPERSWAP_MINT_{}={}", symbol.to_ascii_uppercase(), mint)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_one_variable_per_line() {
+        let env = EnvFile {
+            rpc_url: "http://127.0.0.1:8899".to_string(),
+            program_id: superswap_sol::ID,
+            admin: Pubkey::new_unique(),
+            admin_keypair_path: PathBuf::from("payer.json"),
+            usdc_mint: Pubkey::new_unique(),
+            across_handler: Pubkey::new_unique(),
+            handler_keypair_path: PathBuf::from("handler.json"),
+            fee_recipient: Pubkey::new_unique(),
+            jupiter_program: mock_jupiter::ID,
+            output_mints: vec![("bonk".to_string(), Pubkey::new_unique())],
+            mock_jupiter_pool: None,
+        };
+        let rendered = env.to_string();
+
+        assert!(rendered.contains(&format!("SUPERSWAP_CONFIG={}\n", find_config(&superswap_sol::ID).0)));
+        assert!(rendered.contains(&format!("SUPERSWAP_MINT_BONK={}\n", env.output_mints[0].1)));
+        assert!(rendered.contains("SUPERSWAP_KEYPAIR=payer.json\n"));
+        assert!(!rendered.contains("SUPERSWAP_MOCK_JUPITER_POOL"));
+        assert!(rendered
+            .lines()
+            .all(|line| line.starts_with('#') || line.split_once('=').is_some_and(|(key, _)| key.starts_with("SUPERSWAP_"))));
+    }
+}
@@ -0,0 +1,322 @@
+//! Devnet and localnet bootstrap for SuperSwap
+//!
+//! Deploys the program (and `mock-jupiter` when configured) if it is not on
+//! the cluster yet, initializes its config with a test USDC mint and a test
+//! Across handler, creates the fee, handler and program token accounts, funds
+//! the handler and rent vault, creates test output mints stocked in the mock
+//! Jupiter pool, and writes an environment file integrators can source.
+//! Re-running tops balances back up and skips what is already in place, but
+//! always creates fresh output mints.
+
+mod config;
+mod deploy;
+mod env_file;
+
+use std::path::{Path, PathBuf};
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token;
+use anyhow::{anyhow, ensure, Context, Result};
+use clap::Parser;
+use log::info;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, write_keypair_file, Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use superswap_pda::{find_config, find_insurance_vault, find_rent_vault, wallet_token_account};
+use superswap_sol::state::{Config, InitializeParams};
+use crate::config::BootstrapConfig;
+use crate::deploy::ensure_deployed;
+use crate::env_file::EnvFile;
+
+/// Decimals of the test USDC mint, matching mainnet USDC
+const USDC_DECIMALS: u8 = 6;
+
+#[derive(Parser)]
+#[command(name = "superswap-bootstrap", about = "Deploys and seeds a devnet or localnet SuperSwap environment")]
+struct Args {
+    /// Path to the bootstrap's JSON config
+    #[arg(long, env = "SUPERSWAP_BOOTSTRAP_CONFIG")]
+    config: PathBuf,
+}
+
+struct Bootstrap {
+    rpc: RpcClient,
+    payer: Keypair,
+    config: BootstrapConfig,
+}
+
+impl Bootstrap {
+    async fn run(&self, handler: &Keypair) -> Result<EnvFile> {
+        let config = &self.config;
+        ensure_deployed(
+            &self.rpc,
+            &config.rpc_url,
+            &config.keypair_path,
+            "superswap_sol",
+            &config.program_id,
+            config.program.as_ref(),
+        )
+        .await?;
+        if config.mock_jupiter.is_some() {
+            ensure_deployed(
+                &self.rpc,
+                &config.rpc_url,
+                &config.keypair_path,
+                "mock_jupiter",
+                &mock_jupiter::ID,
+                config.mock_jupiter.as_ref(),
+            )
+            .await?;
+        }
+
+        let program_config = match self.fetch_program_config().await? {
+            Some(program_config) => program_config,
+            None => self.initialize(handler).await?,
+        };
+        ensure!(
+            program_config.is_across_handler(&handler.pubkey()),
+            "program's Across handler is {}, and the configured handler {} is not registered either; update it with superswap-cli",
+            program_config.across_handler,
+            handler.pubkey()
+        );
+        ensure!(
+            program_config.jupiter_program == config.routed_jupiter_program(),
+            "program routes through {}, not {}; update it with superswap-cli",
+            program_config.jupiter_program,
+            config.routed_jupiter_program()
+        );
+        let usdc_mint = program_config.usdc_mint;
+        self.ensure_mintable(&usdc_mint).await?;
+
+        // Vaults: the fee recipient's, the handler's, the program's own and the insurance fund's USDC accounts
+        let payer = self.payer.pubkey();
+        let owners = [
+            program_config.fee_recipient,
+            handler.pubkey(),
+            find_config(&config.program_id).0,
+            find_insurance_vault(&config.program_id).0,
+        ];
+        let create_accounts = owners
+            .iter()
+            .map(|owner| create_associated_token_account_idempotent(&payer, owner, &usdc_mint, &spl_token::ID))
+            .collect::<Vec<_>>();
+        self.send(&create_accounts, &[]).await?;
+        let handler_usdc = wallet_token_account(&handler.pubkey(), &usdc_mint);
+        let handler_usdc_balance: u64 = self.rpc.get_token_account_balance(&handler_usdc).await?.amount.parse()?;
+        if handler_usdc_balance < config.handler_usdc {
+            let amount = config.handler_usdc - handler_usdc_balance;
+            self.send(&[mint_to(&usdc_mint, &handler_usdc, &payer, amount)?], &[]).await?;
+            info!("minted {} test USDC to handler {}", amount, handler.pubkey());
+        }
+
+        self.fund(&program_config, handler).await?;
+
+        let mut output_mints = Vec::new();
+        for mint in &config.output_mints {
+            let address = self.create_mint(mint.decimals).await?;
+            info!("created output mint {} ({}) with {} decimals", mint.symbol, address, mint.decimals);
+            output_mints.push((mint.symbol.clone(), address));
+        }
+        let mock_jupiter_pool = if config.mock_jupiter.is_some() {
+            Some(self.stock_mock_pool(&usdc_mint, &output_mints).await?)
+        } else {
+            None
+        };
+
+        Ok(EnvFile {
+            rpc_url: config.rpc_url.clone(),
+            program_id: config.program_id,
+            admin: program_config.admin,
+            admin_keypair_path: config.keypair_path.clone(),
+            usdc_mint,
+            across_handler: handler.pubkey(),
+            handler_keypair_path: config.handler_keypair_path.clone(),
+            fee_recipient: program_config.fee_recipient,
+            jupiter_program: program_config.jupiter_program,
+            output_mints,
+            mock_jupiter_pool,
+        })
+    }
+
+    /// Initializes the program with a new test USDC mint and the test handler
+    async fn initialize(&self, handler: &Keypair) -> Result<Config> {
+        let config = &self.config;
+        let usdc_mint = self.create_mint(USDC_DECIMALS).await?;
+        let initialize = superswap_sdk::initialize(
+            &config.program_id,
+            &self.payer.pubkey(),
+            InitializeParams {
+                across_handler: handler.pubkey(),
+                jupiter_program: config.routed_jupiter_program(),
+                across_spoke_pool: config.across_spoke_pool,
+                cctp_token_messenger_minter: config.cctp_token_messenger_minter,
+                usdc_mint,
+                fee_recipient: self.payer.pubkey(),
+                fee_bps: config.fee_bps,
+                outbound_fee_bps: config.outbound_fee_bps,
+                min_outbound_amount: 0,
+                max_outbound_amount: 0,
+            },
+        );
+        self.send(&[initialize], &[]).await?;
+        info!("initialized program {} with test USDC mint {}", config.program_id, usdc_mint);
+
+        self.fetch_program_config()
+            .await?
+            .ok_or_else(|| anyhow!("program config missing after initialize"))
+    }
+
+    /// Tops the handler and, when the payer is admin, the rent vault up to their configured balances
+    async fn fund(&self, program_config: &Config, handler: &Keypair) -> Result<()> {
+        let config = &self.config;
+        let payer = self.payer.pubkey();
+        let mut funding = Vec::new();
+        let handler_balance = self.rpc.get_balance(&handler.pubkey()).await?;
+        if handler_balance < config.handler_lamports {
+            funding.push(system_instruction::transfer(
+                &payer,
+                &handler.pubkey(),
+                config.handler_lamports - handler_balance,
+            ));
+        }
+        if program_config.admin == payer {
+            let rent_vault_balance = self.rpc.get_balance(&find_rent_vault(&config.program_id).0).await?;
+            if rent_vault_balance < config.rent_vault_lamports {
+                funding.push(superswap_sdk::fund_rent_vault(
+                    &config.program_id,
+                    &payer,
+                    config.rent_vault_lamports - rent_vault_balance,
+                ));
+            }
+        } else {
+            info!("payer is not the admin; leaving the rent vault unfunded");
+        }
+        if !funding.is_empty() {
+            self.send(&funding, &[]).await?;
+        }
+        Ok(())
+    }
+
+    /// Gives the mock Jupiter pool USDC and output accounts, stocks the output side
+    /// and resets it to paying exactly the quote; returns the pool authority
+    async fn stock_mock_pool(&self, usdc_mint: &Pubkey, output_mints: &[(String, Pubkey)]) -> Result<Pubkey> {
+        let payer = self.payer.pubkey();
+        let pool_authority = mock_jupiter::client::pool_authority();
+        let mut setup = vec![create_associated_token_account_idempotent(
+            &payer,
+            &pool_authority,
+            usdc_mint,
+            &spl_token::ID,
+        )];
+        setup.push(mock_jupiter::client::configure(&payer, 10_000, false));
+        self.send(&setup, &[]).await?;
+
+        for (symbol, mint) in output_mints {
+            let pool_output = wallet_token_account(&pool_authority, mint);
+            let stock = [
+                create_associated_token_account_idempotent(&payer, &pool_authority, mint, &spl_token::ID),
+                mint_to(mint, &pool_output, &payer, self.config.pool_liquidity)?,
+            ];
+            self.send(&stock, &[]).await?;
+            info!("stocked mock Jupiter pool with {} {}", self.config.pool_liquidity, symbol);
+        }
+        Ok(pool_authority)
+    }
+
+    async fn fetch_program_config(&self) -> Result<Option<Config>> {
+        let address = find_config(&self.config.program_id).0;
+        let account = self.rpc.get_account_with_commitment(&address, self.rpc.commitment()).await?.value;
+        match account {
+            Some(account) => Ok(Some(Config::try_deserialize(&mut account.data.as_slice())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fails unless the payer can mint `mint`, as it must to hand out test tokens
+    async fn ensure_mintable(&self, mint: &Pubkey) -> Result<()> {
+        let account = self.rpc.get_account(mint).await?;
+        let mint_state = spl_token::state::Mint::unpack(&account.data)?;
+        ensure!(
+            mint_state.mint_authority == COption::Some(self.payer.pubkey()),
+            "USDC mint {} is not mintable by the payer; bootstrap only seeds test mints",
+            mint
+        );
+        Ok(())
+    }
+
+    async fn create_mint(&self, decimals: u8) -> Result<Pubkey> {
+        let mint = Keypair::new();
+        let payer = self.payer.pubkey();
+        let rent = self
+            .rpc
+            .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+            .await?;
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint(&spl_token::ID, &mint.pubkey(), &payer, None, decimals)?,
+        ];
+        self.send(&instructions, &[&mint]).await?;
+        Ok(mint.pubkey())
+    }
+
+    async fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<()> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&self.payer.pubkey()), &all_signers, blockhash);
+        self.rpc.send_and_confirm_transaction(&transaction).await?;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    let config = BootstrapConfig::load(&args.config)?;
+    let payer = read_keypair(&config.keypair_path)?;
+    let handler = load_or_create_handler(&config.handler_keypair_path)?;
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+
+    let bootstrap = Bootstrap { rpc, payer, config };
+    let env_file = bootstrap.run(&handler).await?;
+    let rendered = env_file.to_string();
+    let path = &bootstrap.config.env_file;
+    std::fs::write(path, &rendered).with_context(|| format!("failed to write {}", path.display()))?;
+    info!("wrote {}", path.display());
+    print!("{}", rendered);
+    Ok(())
+}
+
+fn mint_to(mint: &Pubkey, account: &Pubkey, authority: &Pubkey, amount: u64) -> Result<Instruction> {
+    Ok(spl_token::instruction::mint_to(&spl_token::ID, mint, account, authority, &[], amount)?)
+}
+
+/// Reads the handler keypair, generating and saving one on the first run
+fn load_or_create_handler(path: &Path) -> Result<Keypair> {
+    if path.exists() {
+        return read_keypair(path);
+    }
+    let handler = Keypair::new();
+    write_keypair_file(&handler, path).map_err(|err| anyhow!("failed to write keypair {}: {}", path.display(), err))?;
+    info!("generated handler keypair {} at {}", handler.pubkey(), path.display());
+    Ok(handler)
+}
+
+fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
@@ -0,0 +1,961 @@
+//! Admin CLI for the SuperSwap program
+//!
+//! Every command builds its instruction with `superswap-sdk`, signs it with
+//! the admin key (a keypair file or `usb://ledger`) and sends it, or only
+//! simulates it with `--simulate`.
+
+mod signer;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+use superswap_pda::{
+    find_admin_log, find_config, find_pending_recovery, find_rent_vault, find_swap_order, insurance_usdc_account, program_usdc_account,
+    wallet_token_account,
+};
+use superswap_quote::{JupiterClient, QuoteRequest, SwapAccounts};
+use superswap_sol::state::{
+    AdminLog, Config, InitializeParams, LamportSource, MintConfigParams, OrderStatus, OutboundRateLimit, PayInsuranceParams, PendingRecovery, QueueLamportRecoveryParams, QueueRecoveryParams, RecoverFundsParams,
+    ProposeConfigUpdateParams, RecoveryLimits, SurplusPolicy, SwapOrder, UpdateConfigParams,
+};
+use crate::signer::{signer_from_path, DEFAULT_KEYPAIR_PATH};
+
+#[derive(Parser)]
+#[command(name = "superswap-cli", about = "Operate the SuperSwap program")]
+struct Cli {
+    /// JSON RPC endpoint
+    #[arg(long, short = 'u', env = "SUPERSWAP_RPC_URL", default_value = "http://localhost:8899")]
+    url: String,
+
+    /// Admin signer: a keypair file or a `usb://ledger` URL
+    #[arg(long, short = 'k', env = "SUPERSWAP_KEYPAIR", default_value = DEFAULT_KEYPAIR_PATH)]
+    keypair: String,
+
+    /// SuperSwap program id
+    #[arg(long, default_value_t = superswap_sol::ID)]
+    program_id: Pubkey,
+
+    /// Simulate the transaction and print its logs instead of sending it
+    #[arg(long)]
+    simulate: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the program configuration
+    ShowConfig,
+    /// Quote the Jupiter route an inbound fill of `amount` USDC would take
+    Quote(QuoteArgs),
+    /// Create the program configuration, with the signer as admin
+    Initialize(Box<InitializeArgs>),
+    /// Update configuration fields; omitted fields are left unchanged
+    UpdateConfig(Box<UpdateConfigArgs>),
+    /// Take over as admin after being proposed with `update-config --admin`;
+    /// run with the pending admin's keypair
+    AcceptAdmin,
+    /// Propose a change to routing-critical addresses, applicable after the config's update delay
+    ProposeConfigUpdate(ProposeConfigUpdateArgs),
+    /// Apply the proposed config update once its delay has passed
+    ApplyConfigUpdate,
+    /// Discard the proposed config update
+    CancelConfigUpdate,
+    /// Stop accepting an additional Across handler, effective immediately
+    RemoveAcrossHandler {
+        handler: Pubkey,
+    },
+    /// Migrate a V1 or version 2 config account to the current layout in place
+    MigrateConfig,
+    /// Create the admin log of a config initialized before the log existed
+    CreateAdminLog,
+    /// Print the privileged actions recorded in the admin log, oldest first
+    ShowAdminLog,
+    /// Check that the USDC vault and DCA escrows cover every outstanding order;
+    /// with `--simulate`, exits non-zero when they don't, for monitoring
+    AssertSolvency,
+    /// Pause the program
+    Pause,
+    /// Unpause the program
+    Unpause,
+    /// Reject new fills while refunds, claims and closures keep working
+    EnterRefundsOnly,
+    /// Resume accepting fills after refunds-only mode
+    LeaveRefundsOnly,
+    /// Reject fills into a destination mint, e.g. after a depeg or pool exploit
+    DisableMint {
+        mint: Pubkey,
+    },
+    /// Accept fills into a previously disabled destination mint again
+    EnableMint {
+        mint: Pubkey,
+    },
+    /// Set a fee, order cap and slippage bound for fills into a destination mint
+    CreateMintConfig(MintConfigArgs),
+    /// Replace a destination mint's overrides
+    UpdateMintConfig(MintConfigArgs),
+    /// Return a destination mint to the config's settings
+    DeleteMintConfig {
+        mint: Pubkey,
+    },
+    /// Start tracking daily fill volume, across all mints or into `--mint`;
+    /// create the tracker before setting the matching daily volume cap
+    CreateVolumeTracker {
+        #[arg(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Manage the hook, spend, vault and donation allowlists
+    #[command(subcommand)]
+    Allowlist(AllowlistCommand),
+    /// Withdraw protocol USDC held by the config PDA
+    WithdrawFees {
+        /// Amount in USDC base units
+        amount: u64,
+        /// Destination USDC token account (default: the signer's USDC ATA)
+        #[arg(long)]
+        destination: Option<Pubkey>,
+    },
+    /// Move tokens out of a config-owned token account
+    RecoverFunds {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        source: Pubkey,
+        #[arg(long)]
+        destination: Pubkey,
+        /// Amount in token base units
+        amount: u64,
+    },
+    /// Queue a recovery above the timelock threshold, executable once the guard's delay passes
+    QueueRecovery {
+        /// Id of the queued recovery, unique among queued recoveries
+        id: u64,
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        source: Pubkey,
+        #[arg(long)]
+        destination: Pubkey,
+        /// Amount in token base units
+        amount: u64,
+    },
+    /// Move stray lamports above a program-owned account's rent-exempt minimum
+    RecoverLamports {
+        #[arg(long)]
+        source: LamportSourceArg,
+        /// Order id, for an order source
+        #[arg(long)]
+        order_id: Option<u64>,
+        #[arg(long)]
+        destination: Pubkey,
+        /// Amount in lamports
+        amount: u64,
+    },
+    /// Queue a lamport recovery above the timelock threshold
+    QueueLamportRecovery {
+        /// Id of the queued recovery, unique among queued recoveries
+        id: u64,
+        #[arg(long)]
+        source: LamportSourceArg,
+        /// Order id, for an order source
+        #[arg(long)]
+        order_id: Option<u64>,
+        #[arg(long)]
+        destination: Pubkey,
+        /// Amount in lamports
+        amount: u64,
+    },
+    /// Execute a queued recovery whose delay has passed
+    ExecuteRecovery {
+        id: u64,
+    },
+    /// Drop a queued recovery
+    CancelRecovery {
+        id: u64,
+    },
+    /// Set the recovery timelock and cap; looser limits apply only after the current delay
+    SetRecoveryLimits {
+        /// Recoveries above this must be queued (0 = no timelock)
+        #[arg(long)]
+        timelock_threshold: u64,
+        #[arg(long, default_value_t = RecoveryLimits::DEFAULT_DELAY_SECONDS)]
+        delay_seconds: i64,
+        #[arg(long, default_value_t = OutboundRateLimit::DEFAULT_PERIOD_SECONDS)]
+        period_seconds: i64,
+        /// Maximum recovered per period (0 = no cap)
+        #[arg(long)]
+        period_cap: u64,
+    },
+    /// Create the insurance fund's USDC account; needed before setting an insurance fee share
+    CreateInsuranceFund,
+    /// Compensate a user affected by an incident from the insurance fund
+    PayInsurance {
+        /// Incident the payout compensates, recorded in the event
+        #[arg(long)]
+        incident_id: u64,
+        /// Destination USDC token account
+        #[arg(long)]
+        destination: Pubkey,
+        /// Amount in USDC base units
+        amount: u64,
+    },
+    /// Approve the insurance claim filed against an order
+    ApproveClaim {
+        order_id: u64,
+    },
+    /// Top up the rent vault
+    FundRentVault {
+        lamports: u64,
+    },
+    /// Withdraw surplus lamports from the rent vault to the signer
+    WithdrawRentVault {
+        lamports: u64,
+    },
+}
+
+#[derive(Args)]
+struct QuoteArgs {
+    /// Bridged USDC in base units, before the protocol fee
+    amount: u64,
+    #[arg(long)]
+    output_mint: Pubkey,
+    /// Recipient whose output ATA the route delivers to
+    #[arg(long)]
+    recipient: Pubkey,
+    #[arg(long, default_value_t = 50)]
+    slippage_bps: u16,
+    #[arg(long, env = "SUPERSWAP_JUPITER_API_URL", default_value = superswap_quote::DEFAULT_JUPITER_API_URL)]
+    jupiter_api_url: String,
+}
+
+#[derive(Args)]
+struct InitializeArgs {
+    #[arg(long)]
+    across_handler: Pubkey,
+    #[arg(long)]
+    jupiter_program: Pubkey,
+    #[arg(long)]
+    across_spoke_pool: Pubkey,
+    #[arg(long)]
+    cctp_token_messenger_minter: Pubkey,
+    #[arg(long)]
+    usdc_mint: Pubkey,
+    #[arg(long)]
+    fee_recipient: Pubkey,
+    #[arg(long)]
+    fee_bps: u16,
+    #[arg(long)]
+    outbound_fee_bps: u16,
+    #[arg(long)]
+    min_outbound_amount: u64,
+    /// 0 = no cap
+    #[arg(long, default_value_t = 0)]
+    max_outbound_amount: u64,
+}
+
+#[derive(Args)]
+struct ProposeConfigUpdateArgs {
+    #[arg(long)]
+    across_handler: Option<Pubkey>,
+    #[arg(long)]
+    jupiter_program: Option<Pubkey>,
+    #[arg(long)]
+    fee_recipient: Option<Pubkey>,
+    /// Shorter update delay; raising it goes through `update-config`
+    #[arg(long)]
+    config_update_delay_seconds: Option<i64>,
+    /// Accept this handler alongside the current one
+    #[arg(long)]
+    add_across_handler: Option<Pubkey>,
+}
+
+#[derive(Args)]
+struct MintConfigArgs {
+    mint: Pubkey,
+    /// Reject fills into the mint while the overrides stay in place
+    #[arg(long)]
+    disabled: bool,
+    /// Fee charged instead of the config's
+    #[arg(long)]
+    fee_bps: Option<u16>,
+    /// Largest order into the mint in USDC base units (0 = no cap)
+    #[arg(long, default_value_t = 0)]
+    max_order_usdc: u64,
+    /// Furthest the output may fall below the route's quote (0 = unchecked)
+    #[arg(long, default_value_t = 0)]
+    max_slippage_bps: u16,
+    /// Most USDC filled into the mint per UTC day (0 = no cap); needs the mint's volume tracker
+    #[arg(long, default_value_t = 0)]
+    daily_volume_cap: u64,
+}
+
+#[derive(Args)]
+struct UpdateConfigArgs {
+    /// Propose a new admin, who takes over with `accept-admin`
+    #[arg(long)]
+    admin: Option<Pubkey>,
+    #[arg(long)]
+    across_spoke_pool: Option<Pubkey>,
+    #[arg(long)]
+    cctp_token_messenger_minter: Option<Pubkey>,
+    #[arg(long)]
+    fee_bps: Option<u16>,
+    #[arg(long)]
+    outbound_fee_bps: Option<u16>,
+    #[arg(long)]
+    min_outbound_amount: Option<u64>,
+    #[arg(long)]
+    max_outbound_amount: Option<u64>,
+    #[arg(long)]
+    across_fee_bps_estimate: Option<u16>,
+    #[arg(long)]
+    cctp_fee_bps_estimate: Option<u16>,
+    #[arg(long)]
+    onward_adapter: Option<Pubkey>,
+    #[arg(long)]
+    jito_stake_pool: Option<Pubkey>,
+    #[arg(long)]
+    marinade_state: Option<Pubkey>,
+    #[arg(long)]
+    kamino_lending_market: Option<Pubkey>,
+    #[arg(long)]
+    solend_lending_market: Option<Pubkey>,
+    #[arg(long)]
+    max_gas_top_up_usdc: Option<u64>,
+    #[arg(long)]
+    max_donation_bps: Option<u16>,
+    #[arg(long)]
+    refund_bounty_bps: Option<u16>,
+    #[arg(long)]
+    min_order_usdc: Option<u64>,
+    #[arg(long)]
+    deliver_dust_as_usdc: Option<bool>,
+    #[arg(long)]
+    max_open_orders_per_recipient: Option<u16>,
+    #[arg(long)]
+    max_price_impact_bps: Option<u16>,
+    #[arg(long)]
+    order_ttl_seconds: Option<u32>,
+    #[arg(long)]
+    escrow_shortfall_tolerance: Option<u64>,
+    #[arg(long)]
+    across_paused: Option<bool>,
+    #[arg(long)]
+    cctp_paused: Option<bool>,
+    #[arg(long)]
+    insurance_fee_share_bps: Option<u16>,
+    #[arg(long)]
+    max_insurance_claim_usdc: Option<u64>,
+    #[arg(long)]
+    fallback_output_mint: Option<Pubkey>,
+    #[arg(long)]
+    rent_treasury: Option<Pubkey>,
+    #[arg(long)]
+    min_retention_seconds: Option<u32>,
+    #[arg(long)]
+    operator_fill_cost_usdc: Option<u64>,
+    #[arg(long)]
+    guardian: Option<Pubkey>,
+    #[arg(long)]
+    max_order_usdc: Option<u64>,
+    #[arg(long)]
+    enabled_adapters: Option<u8>,
+    #[arg(long)]
+    max_relayer_tip_usdc: Option<u64>,
+    #[arg(long)]
+    surplus_share_bps: Option<u16>,
+    #[arg(long)]
+    max_gas_drop_lamports: Option<u64>,
+    #[arg(long)]
+    config_update_delay_seconds: Option<i64>,
+    #[arg(long)]
+    operator: Option<Pubkey>,
+    #[arg(long)]
+    daily_volume_cap: Option<u64>,
+    #[arg(long)]
+    surplus_policy: Option<SurplusPolicyArg>,
+}
+
+#[derive(Subcommand)]
+enum AllowlistCommand {
+    /// Allowlist an address
+    Add { list: Allowlist, address: Pubkey },
+    /// Remove an address from an allowlist
+    Remove { list: Allowlist, address: Pubkey },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Allowlist {
+    /// Post-swap hook programs
+    Hook,
+    /// Spend programs for post-swap spend actions
+    Spend,
+    /// 4626-style vault programs for vault deposit actions
+    Vault,
+    /// Delegates that approve-delegate actions may grant an allowance to
+    Delegate,
+    /// Donation split recipients
+    Donation,
+    /// Payer operators whose fill costs are settled from fees
+    Operator,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SurplusPolicyArg {
+    /// The recipient keeps all output above the quote
+    Recipient,
+    /// The fee recipient receives the surplus share in its account for the mint
+    Treasury,
+    /// The fill's payer receives the surplus share as a rebate
+    PayerRebate,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LamportSourceArg {
+    Config,
+    OutboundRateLimit,
+    RecoveryGuard,
+    AdminLog,
+    OrderArchive,
+    /// Swap order given by `--order-id`
+    SwapOrder,
+    /// Outbound order given by `--order-id`
+    OutboundOrder,
+}
+
+impl LamportSourceArg {
+    fn with_order_id(self, order_id: Option<u64>) -> Result<LamportSource> {
+        let order_id = || order_id.context("--order-id is required for an order source");
+        Ok(match self {
+            Self::Config => LamportSource::Config,
+            Self::OutboundRateLimit => LamportSource::OutboundRateLimit,
+            Self::RecoveryGuard => LamportSource::RecoveryGuard,
+            Self::AdminLog => LamportSource::AdminLog,
+            Self::OrderArchive => LamportSource::OrderArchive,
+            Self::SwapOrder => LamportSource::SwapOrder { order_id: order_id()? },
+            Self::OutboundOrder => LamportSource::OutboundOrder { order_id: order_id()? },
+        })
+    }
+}
+
+impl From<SurplusPolicyArg> for SurplusPolicy {
+    fn from(policy: SurplusPolicyArg) -> Self {
+        match policy {
+            SurplusPolicyArg::Recipient => SurplusPolicy::Recipient,
+            SurplusPolicyArg::Treasury => SurplusPolicy::Treasury,
+            SurplusPolicyArg::PayerRebate => SurplusPolicy::PayerRebate,
+        }
+    }
+}
+
+impl From<InitializeArgs> for InitializeParams {
+    fn from(args: InitializeArgs) -> Self {
+        Self {
+            across_handler: args.across_handler,
+            jupiter_program: args.jupiter_program,
+            across_spoke_pool: args.across_spoke_pool,
+            cctp_token_messenger_minter: args.cctp_token_messenger_minter,
+            usdc_mint: args.usdc_mint,
+            fee_recipient: args.fee_recipient,
+            fee_bps: args.fee_bps,
+            outbound_fee_bps: args.outbound_fee_bps,
+            min_outbound_amount: args.min_outbound_amount,
+            max_outbound_amount: args.max_outbound_amount,
+        }
+    }
+}
+
+impl From<MintConfigArgs> for MintConfigParams {
+    fn from(args: MintConfigArgs) -> Self {
+        Self {
+            mint: args.mint,
+            enabled: !args.disabled,
+            fee_bps: args.fee_bps,
+            max_order_usdc: args.max_order_usdc,
+            max_slippage_bps: args.max_slippage_bps,
+            daily_volume_cap: args.daily_volume_cap,
+        }
+    }
+}
+
+impl From<ProposeConfigUpdateArgs> for ProposeConfigUpdateParams {
+    fn from(args: ProposeConfigUpdateArgs) -> Self {
+        Self {
+            new_across_handler: args.across_handler,
+            new_jupiter_program: args.jupiter_program,
+            new_fee_recipient: args.fee_recipient,
+            new_config_update_delay_seconds: args.config_update_delay_seconds,
+            add_across_handler: args.add_across_handler,
+        }
+    }
+}
+
+impl From<UpdateConfigArgs> for UpdateConfigParams {
+    fn from(args: UpdateConfigArgs) -> Self {
+        Self {
+            new_admin: args.admin,
+            new_across_spoke_pool: args.across_spoke_pool,
+            new_cctp_token_messenger_minter: args.cctp_token_messenger_minter,
+            new_fee_bps: args.fee_bps,
+            new_outbound_fee_bps: args.outbound_fee_bps,
+            new_min_outbound_amount: args.min_outbound_amount,
+            new_max_outbound_amount: args.max_outbound_amount,
+            new_across_fee_bps_estimate: args.across_fee_bps_estimate,
+            new_cctp_fee_bps_estimate: args.cctp_fee_bps_estimate,
+            new_onward_adapter: args.onward_adapter,
+            new_jito_stake_pool: args.jito_stake_pool,
+            new_marinade_state: args.marinade_state,
+            new_kamino_lending_market: args.kamino_lending_market,
+            new_solend_lending_market: args.solend_lending_market,
+            new_max_gas_top_up_usdc: args.max_gas_top_up_usdc,
+            new_max_donation_bps: args.max_donation_bps,
+            new_refund_bounty_bps: args.refund_bounty_bps,
+            new_min_order_usdc: args.min_order_usdc,
+            new_deliver_dust_as_usdc: args.deliver_dust_as_usdc,
+            new_max_open_orders_per_recipient: args.max_open_orders_per_recipient,
+            new_max_price_impact_bps: args.max_price_impact_bps,
+            new_order_ttl_seconds: args.order_ttl_seconds,
+            new_escrow_shortfall_tolerance: args.escrow_shortfall_tolerance,
+            new_across_paused: args.across_paused,
+            new_cctp_paused: args.cctp_paused,
+            new_insurance_fee_share_bps: args.insurance_fee_share_bps,
+            new_max_insurance_claim_usdc: args.max_insurance_claim_usdc,
+            new_fallback_output_mint: args.fallback_output_mint,
+            new_rent_treasury: args.rent_treasury,
+            new_min_retention_seconds: args.min_retention_seconds,
+            new_operator_fill_cost_usdc: args.operator_fill_cost_usdc,
+            new_guardian: args.guardian,
+            new_max_order_usdc: args.max_order_usdc,
+            new_enabled_adapters: args.enabled_adapters,
+            new_max_relayer_tip_usdc: args.max_relayer_tip_usdc,
+            new_surplus_share_bps: args.surplus_share_bps,
+            new_max_gas_drop_lamports: args.max_gas_drop_lamports,
+            new_config_update_delay_seconds: args.config_update_delay_seconds,
+            new_operator: args.operator,
+            new_daily_volume_cap: args.daily_volume_cap,
+            new_surplus_policy: args.surplus_policy.map(SurplusPolicy::from),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    let program_id = cli.program_id;
+
+    match cli.command {
+        Command::ShowConfig => return show_config(&rpc, &program_id),
+        Command::ShowAdminLog => return show_admin_log(&rpc, &program_id),
+        Command::Quote(args) => return quote(&rpc, &program_id, args),
+        _ => {}
+    }
+
+    let signer = signer_from_path(&cli.keypair, "admin")?;
+    let admin = signer.pubkey();
+
+    let instruction = match cli.command {
+        Command::ShowConfig | Command::ShowAdminLog | Command::Quote(_) => unreachable!("handled above"),
+        Command::Initialize(args) => superswap_sdk::initialize(&program_id, &admin, (*args).into()),
+        Command::UpdateConfig(args) => superswap_sdk::update_config(&program_id, &admin, (*args).into()),
+        Command::AcceptAdmin => superswap_sdk::accept_admin(&program_id, &admin),
+        Command::ProposeConfigUpdate(args) => superswap_sdk::propose_config_update(&program_id, &admin, args.into()),
+        Command::ApplyConfigUpdate => superswap_sdk::apply_config_update(&program_id, &admin),
+        Command::CancelConfigUpdate => superswap_sdk::cancel_config_update(&program_id, &admin),
+        Command::RemoveAcrossHandler { handler } => superswap_sdk::remove_across_handler(&program_id, &admin, &handler),
+        Command::MigrateConfig => superswap_sdk::migrate_config(&program_id, &admin),
+        Command::CreateAdminLog => superswap_sdk::create_admin_log(&program_id, &admin),
+        Command::AssertSolvency => {
+            let config = fetch_config(&rpc, &program_id)?;
+            let (owed_orders, dca_orders) = fetch_outstanding_orders(&rpc, &program_id)?;
+            println!("Checking {} owed and {} DCA orders", owed_orders.len(), dca_orders.len());
+            superswap_sdk::assert_solvency(&program_id, &config.usdc_mint, &owed_orders, &dca_orders)
+        }
+        Command::Pause => superswap_sdk::pause(&program_id, &admin),
+        Command::Unpause => superswap_sdk::unpause(&program_id, &admin),
+        Command::EnterRefundsOnly => superswap_sdk::set_refunds_only(&program_id, &admin, true),
+        Command::LeaveRefundsOnly => superswap_sdk::set_refunds_only(&program_id, &admin, false),
+        Command::DisableMint { mint } => superswap_sdk::set_destination_mint(&program_id, &admin, &mint, true),
+        Command::EnableMint { mint } => superswap_sdk::set_destination_mint(&program_id, &admin, &mint, false),
+        Command::CreateMintConfig(args) => superswap_sdk::create_mint_config(&program_id, &admin, args.into()),
+        Command::UpdateMintConfig(args) => superswap_sdk::update_mint_config(&program_id, &admin, args.into()),
+        Command::DeleteMintConfig { mint } => superswap_sdk::delete_mint_config(&program_id, &admin, &mint),
+        Command::CreateVolumeTracker { mint } => {
+            superswap_sdk::create_volume_tracker(&program_id, &admin, &mint.unwrap_or_default())
+        }
+        Command::Allowlist(command) => allowlist_instruction(&program_id, &admin, command),
+        Command::WithdrawFees { amount, destination } => {
+            let usdc_mint = fetch_config(&rpc, &program_id)?.usdc_mint;
+            superswap_sdk::recover_funds(
+                &program_id,
+                &admin,
+                &program_usdc_account(&program_id, &usdc_mint),
+                &destination.unwrap_or_else(|| wallet_token_account(&admin, &usdc_mint)),
+                RecoverFundsParams {
+                    token_mint: usdc_mint,
+                    amount,
+                },
+            )
+        }
+        Command::RecoverFunds {
+            mint,
+            source,
+            destination,
+            amount,
+        } => superswap_sdk::recover_funds(
+            &program_id,
+            &admin,
+            &source,
+            &destination,
+            RecoverFundsParams {
+                token_mint: mint,
+                amount,
+            },
+        ),
+        Command::QueueRecovery {
+            id,
+            mint,
+            source,
+            destination,
+            amount,
+        } => superswap_sdk::queue_recovery(
+            &program_id,
+            &admin,
+            &source,
+            &destination,
+            QueueRecoveryParams {
+                recovery_id: id,
+                token_mint: mint,
+                amount,
+            },
+        ),
+        Command::RecoverLamports {
+            source,
+            order_id,
+            destination,
+            amount,
+        } => superswap_sdk::recover_lamports(&program_id, &admin, source.with_order_id(order_id)?, &destination, amount),
+        Command::QueueLamportRecovery {
+            id,
+            source,
+            order_id,
+            destination,
+            amount,
+        } => superswap_sdk::queue_lamport_recovery(
+            &program_id,
+            &admin,
+            &destination,
+            QueueLamportRecoveryParams {
+                recovery_id: id,
+                source: source.with_order_id(order_id)?,
+                amount,
+            },
+        ),
+        Command::ExecuteRecovery { id } => {
+            let pending = fetch_pending_recovery(&rpc, &program_id, id)?;
+            let execute = if pending.is_lamports() {
+                superswap_sdk::execute_lamport_recovery
+            } else {
+                superswap_sdk::execute_recovery
+            };
+            execute(
+                &program_id,
+                &admin,
+                id,
+                &pending.source_token_account,
+                &pending.destination_token_account,
+            )
+        }
+        Command::CancelRecovery { id } => superswap_sdk::cancel_recovery(&program_id, &admin, id),
+        Command::SetRecoveryLimits {
+            timelock_threshold,
+            delay_seconds,
+            period_seconds,
+            period_cap,
+        } => superswap_sdk::set_recovery_limits(
+            &program_id,
+            &admin,
+            RecoveryLimits {
+                timelock_threshold,
+                delay_seconds,
+                period_seconds,
+                period_cap,
+            },
+        ),
+        Command::CreateInsuranceFund => {
+            let usdc_mint = fetch_config(&rpc, &program_id)?.usdc_mint;
+            superswap_sdk::create_insurance_fund(&program_id, &admin, &usdc_mint)
+        }
+        Command::PayInsurance {
+            incident_id,
+            destination,
+            amount,
+        } => {
+            let usdc_mint = fetch_config(&rpc, &program_id)?.usdc_mint;
+            superswap_sdk::pay_insurance(
+                &program_id,
+                &admin,
+                &usdc_mint,
+                &destination,
+                PayInsuranceParams { incident_id, amount },
+            )
+        }
+        Command::ApproveClaim { order_id } => superswap_sdk::approve_insurance_claim(&program_id, &admin, order_id),
+        Command::FundRentVault { lamports } => superswap_sdk::fund_rent_vault(&program_id, &admin, lamports),
+        Command::WithdrawRentVault { lamports } => superswap_sdk::withdraw_rent_vault(&program_id, &admin, lamports),
+    };
+
+    send(&rpc, signer.as_ref(), instruction, cli.simulate)
+}
+
+fn allowlist_instruction(program_id: &Pubkey, admin: &Pubkey, command: AllowlistCommand) -> Instruction {
+    match command {
+        AllowlistCommand::Add { list, address } => match list {
+            Allowlist::Hook => superswap_sdk::add_hook_program(program_id, admin, &address),
+            Allowlist::Spend => superswap_sdk::add_spend_program(program_id, admin, &address),
+            Allowlist::Vault => superswap_sdk::add_vault_program(program_id, admin, &address),
+            Allowlist::Delegate => superswap_sdk::add_approved_delegate(program_id, admin, &address),
+            Allowlist::Donation => superswap_sdk::add_donation_recipient(program_id, admin, &address),
+            Allowlist::Operator => superswap_sdk::add_payer_operator(program_id, admin, &address),
+        },
+        AllowlistCommand::Remove { list, address } => match list {
+            Allowlist::Hook => superswap_sdk::remove_hook_program(program_id, admin, &address),
+            Allowlist::Spend => superswap_sdk::remove_spend_program(program_id, admin, &address),
+            Allowlist::Vault => superswap_sdk::remove_vault_program(program_id, admin, &address),
+            Allowlist::Delegate => superswap_sdk::remove_approved_delegate(program_id, admin, &address),
+            Allowlist::Donation => superswap_sdk::remove_donation_recipient(program_id, admin, &address),
+            Allowlist::Operator => superswap_sdk::remove_payer_operator(program_id, admin, &address),
+        },
+    }
+}
+
+fn send(rpc: &RpcClient, signer: &dyn Signer, instruction: Instruction, simulate: bool) -> Result<()> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer.pubkey()),
+        &[signer],
+        blockhash,
+    );
+
+    if simulate {
+        let result = rpc.simulate_transaction(&transaction)?.value;
+        for line in result.logs.unwrap_or_default() {
+            println!("{}", line);
+        }
+        return match result.err {
+            Some(err) => Err(anyhow::anyhow!("simulation failed: {}", err)),
+            None => Ok(()),
+        };
+    }
+
+    let signature = rpc
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .context("transaction failed")?;
+    println!("{}", signature);
+    Ok(())
+}
+
+fn fetch_config(rpc: &RpcClient, program_id: &Pubkey) -> Result<Config> {
+    let address = find_config(program_id).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("program config {} not found", address))?;
+    Ok(Config::try_deserialize(&mut account.data.as_slice())?)
+}
+
+/// The primary instance's swap orders owed from the USDC vault, and its active DCA orders
+fn fetch_outstanding_orders(rpc: &RpcClient, program_id: &Pubkey) -> Result<(Vec<Pubkey>, Vec<Pubkey>)> {
+    let filters = vec![
+        RpcFilterType::DataSize(SwapOrder::LEN as u64),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &SwapOrder::DISCRIMINATOR)),
+    ];
+    let accounts = rpc.get_program_accounts_with_config(
+        program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    let mut owed_orders = Vec::new();
+    let mut dca_orders = Vec::new();
+    for (address, account) in accounts {
+        let order = SwapOrder::try_deserialize(&mut account.data.as_slice())
+            .with_context(|| format!("malformed swap order {}", address))?;
+        if address != find_swap_order(program_id, order.order_id).0 {
+            continue;
+        }
+        if order.is_vault_liability() {
+            owed_orders.push(address);
+        } else if order.status == OrderStatus::DcaActive {
+            dca_orders.push(address);
+        }
+    }
+    Ok((owed_orders, dca_orders))
+}
+
+fn fetch_pending_recovery(rpc: &RpcClient, program_id: &Pubkey, recovery_id: u64) -> Result<PendingRecovery> {
+    let address = find_pending_recovery(program_id, recovery_id).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("queued recovery {} not found", recovery_id))?;
+    Ok(PendingRecovery::try_deserialize(&mut account.data.as_slice())?)
+}
+
+fn show_config(rpc: &RpcClient, program_id: &Pubkey) -> Result<()> {
+    let config = fetch_config(rpc, program_id)?;
+    let rent_vault = find_rent_vault(program_id).0;
+    let protocol_usdc = program_usdc_account(program_id, &config.usdc_mint);
+
+    println!("Config:                      {}", find_config(program_id).0);
+    println!("Admin:                       {}", config.admin);
+    println!("Pending admin:               {}", config.pending_admin);
+    println!("Paused:                      {}", config.is_paused);
+    println!("Refunds only:                {}", config.refunds_only);
+    println!("Across handler:              {}", config.across_handler);
+    for handler in config.registered_additional_across_handlers() {
+        println!("Additional Across handler:   {}", handler);
+    }
+    println!("Jupiter program:             {}", config.jupiter_program);
+    println!("Across spoke pool:           {}", config.across_spoke_pool);
+    println!("CCTP token messenger minter: {}", config.cctp_token_messenger_minter);
+    println!("USDC mint:                   {}", config.usdc_mint);
+    println!("Fee recipient:               {}", config.fee_recipient);
+    println!("Fee (bps):                   {}", config.fee_bps);
+    println!("Outbound fee (bps):          {}", config.outbound_fee_bps);
+    println!("Outbound amount range:       {}..={}", config.min_outbound_amount, config.max_outbound_amount);
+    println!("Onward adapter:              {}", config.onward_adapter);
+    println!("Max gas top-up (USDC):       {}", config.max_gas_top_up_usdc);
+    println!("Max donation (bps):          {}", config.max_donation_bps);
+    println!("Refund bounty (bps):         {}", config.refund_bounty_bps);
+    println!("Min order (USDC):            {}", config.min_order_usdc);
+    println!("Deliver dust as USDC:        {}", config.deliver_dust_as_usdc);
+    println!("Max open orders/recipient:   {}", config.max_open_orders_per_recipient);
+    println!("Max price impact (bps):      {}", config.max_price_impact_bps);
+    println!("Order TTL (seconds):         {}", config.order_ttl_seconds);
+    println!("Escrow shortfall tolerance:  {}", config.escrow_shortfall_tolerance);
+    println!("Across paused:               {}", config.across_paused);
+    println!("CCTP paused:                 {}", config.cctp_paused);
+    println!("Insurance fee share (bps):   {}", config.insurance_fee_share_bps);
+    println!("Max insurance claim:         {}", config.max_insurance_claim_usdc);
+    println!("Fallback output mint:        {}", config.fallback_output_mint);
+    println!("Rent treasury:               {}", config.rent_treasury);
+    println!("Min retention (s):           {}", config.min_retention_seconds);
+    println!("Operator fill cost (USDC):   {}", config.operator_fill_cost_usdc);
+    println!("Config version:              {}", config.version);
+    println!("Guardian:                    {}", config.guardian);
+    println!("Max order (USDC):            {}", config.max_order_usdc);
+    println!("Enabled adapters:            {}", config.enabled_adapters);
+    println!("Max relayer tip (USDC):      {}", config.max_relayer_tip_usdc);
+    println!("Surplus share (bps):         {}", config.surplus_share_bps);
+    println!("Max gas drop (lamports):     {}", config.max_gas_drop_lamports);
+    println!("Config update delay (s):     {}", config.config_update_delay_seconds);
+    println!("Operator:                    {}", config.operator);
+    println!("Daily volume cap (USDC):     {}", config.daily_volume_cap);
+    println!("Surplus policy:              {:?}", config.surplus_policy);
+    println!("Rent vault:                  {} ({} lamports)", rent_vault, rpc.get_balance(&rent_vault)?);
+    match rpc.get_token_account_balance(&protocol_usdc) {
+        Ok(balance) => println!("Protocol USDC:               {} ({})", protocol_usdc, balance.ui_amount_string),
+        Err(_) => println!("Protocol USDC:               {} (not created)", protocol_usdc),
+    }
+    let insurance_usdc = insurance_usdc_account(program_id, &config.usdc_mint);
+    match rpc.get_token_account_balance(&insurance_usdc) {
+        Ok(balance) => println!("Insurance fund:              {} ({})", insurance_usdc, balance.ui_amount_string),
+        Err(_) => println!("Insurance fund:              {} (not created)", insurance_usdc),
+    }
+    Ok(())
+}
+
+fn show_admin_log(rpc: &RpcClient, program_id: &Pubkey) -> Result<()> {
+    let address = find_admin_log(program_id).0;
+    let account = rpc
+        .get_account(&address)
+        .context("admin log not found; create it with create-admin-log")?;
+    let admin_log = AdminLog::try_deserialize(&mut account.data.as_slice())?;
+
+    println!("Admin log: {} ({} actions recorded)", address, admin_log.count);
+    for entry in admin_log.chronological() {
+        print!("{} {:?} by {}", entry.timestamp, entry.action, entry.actor);
+        if entry.subject != Pubkey::default() {
+            print!(" on {}", entry.subject);
+        }
+        let field = String::from_utf8_lossy(&entry.field);
+        let field = field.trim_end_matches('\0');
+        if !field.is_empty() {
+            print!(" [{}]", field);
+        }
+        if entry.old_value != entry.new_value {
+            print!(": {} -> {}", log_value(&entry.old_value), log_value(&entry.new_value));
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Renders a logged value as an integer when it fits in eight bytes, else as an address
+fn log_value(value: &[u8; 32]) -> String {
+    if value[8..].iter().all(|byte| *byte == 0) {
+        u64::from_le_bytes(value[..8].try_into().unwrap()).to_string()
+    } else {
+        Pubkey::new_from_array(*value).to_string()
+    }
+}
+
+fn quote(rpc: &RpcClient, program_id: &Pubkey, args: QuoteArgs) -> Result<()> {
+    let config = fetch_config(rpc, program_id)?;
+    let fee = superswap_sol::utils::calculate_fee(args.amount, config.fee_bps)
+        .map_err(|err| anyhow::anyhow!("fee calculation failed: {}", err))?;
+    let request = QuoteRequest {
+        input_mint: config.usdc_mint,
+        output_mint: args.output_mint,
+        amount: args.amount - fee,
+        slippage_bps: args.slippage_bps,
+        max_accounts: None,
+    };
+    // Inbound fills swap from the program's USDC account, owned by the config PDA
+    let accounts = SwapAccounts {
+        jupiter_program: config.jupiter_program,
+        swap_authority: find_config(program_id).0,
+        destination_token_account: wallet_token_account(&args.recipient, &args.output_mint),
+    };
+
+    let client = JupiterClient::new(&args.jupiter_api_url);
+    let route = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(client.route(&request, &accounts))?;
+
+    println!("Swapped USDC:    {} (fee {})", request.amount, fee);
+    println!("Quoted output:   {}", route.out_amount);
+    println!("Minimum output:  {}", route.min_out_amount);
+    println!("Swap data:       {} bytes", route.swap_data.len());
+    println!("Route accounts:  {}", route.accounts.len());
+    for table in &route.lookup_tables {
+        println!("Lookup table:    {}", table);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+}
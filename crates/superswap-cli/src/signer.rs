@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+use anyhow::{anyhow, Context, Result};
+use solana_remote_wallet::locator::Locator;
+use solana_remote_wallet::remote_keypair::generate_remote_keypair;
+use solana_remote_wallet::remote_wallet::maybe_wallet_manager;
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use uriparse::URIReference;
+
+/// Default keypair path of the Solana CLI
+pub const DEFAULT_KEYPAIR_PATH: &str = "~/.config/solana/id.json";
+
+/// Loads a signer from a keypair file or a `usb://ledger[?key=<account>/<change>]` URL
+///
+/// Ledger signing needs the binary built with the `ledger` feature.
+pub fn signer_from_path(path: &str, keypair_name: &str) -> Result<Box<dyn Signer>> {
+    if path.starts_with("usb://") {
+        return ledger_signer(path, keypair_name);
+    }
+
+    let path = expand_home(path);
+    let keypair = read_keypair_file(&path)
+        .map_err(|err| anyhow!("failed to read {} keypair {}: {}", keypair_name, path.display(), err))?;
+    Ok(Box::new(keypair))
+}
+
+fn ledger_signer(url: &str, keypair_name: &str) -> Result<Box<dyn Signer>> {
+    let uri = URIReference::try_from(url).with_context(|| format!("invalid wallet URL {}", url))?;
+    let locator = Locator::new_from_uri(&uri)?;
+    let derivation_path = DerivationPath::from_uri_key_query(&uri)?.unwrap_or_default();
+    let wallet_manager = maybe_wallet_manager()?
+        .ok_or_else(|| anyhow!("no hardware wallet found for {}", url))?;
+    let keypair = generate_remote_keypair(locator, derivation_path, &wallet_manager, true, keypair_name)?;
+    Ok(Box::new(keypair))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
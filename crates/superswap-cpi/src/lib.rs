@@ -0,0 +1,89 @@
+//! CPI bindings for on-chain programs composing with SuperSwap
+//!
+//! Wraps the Anchor-generated `cpi` module of the program in one builder per
+//! entry point a composer is expected to call. Each builder checks that the
+//! program account really is SuperSwap, signs with the caller's PDA seeds and
+//! forwards the route accounts as remaining accounts.
+//!
+//! The inbound entry points (`process_bridge_and_swap`, `place_limit_order`,
+//! `create_dca_order`) require `across_handler` to sign as the instance's
+//! configured handler, so a composer calling them passes its own PDA there
+//! and has that PDA set as the handler of the instance it fills for.
+//! `swap_and_bridge` only needs the composer's PDA as the paying `user`.
+//!
+//! The account structs are re-exported as [`accounts`], the parameter types
+//! as [`types`] and the PDA helpers as [`pda`].
+
+use anchor_lang::prelude::*;
+use superswap_sol::state::{
+    CreateDcaOrderParams, FillResult, PlaceLimitOrderParams, ProcessBridgeAndSwapParams, SwapAndBridgeParams,
+};
+
+pub use superswap_pda as pda;
+pub use superswap_sol::cpi::accounts;
+pub use superswap_sol::state as types;
+pub use superswap_sol::ID;
+
+/// Invokes `process_bridge_and_swap`, returning the fill's fee and delivered output
+///
+/// `remaining_accounts` are the Jupiter route accounts followed by the gas
+/// top-up route and post-swap hook accounts, as for a direct call.
+pub fn process_bridge_and_swap<'info>(
+    superswap_program: AccountInfo<'info>,
+    accounts: accounts::ProcessBridgeAndSwap<'info>,
+    remaining_accounts: Vec<AccountInfo<'info>>,
+    params: ProcessBridgeAndSwapParams,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<FillResult> {
+    check_program(&superswap_program)?;
+    let ctx = CpiContext::new_with_signer(superswap_program, accounts, signer_seeds)
+        .with_remaining_accounts(remaining_accounts);
+    Ok(superswap_sol::cpi::process_bridge_and_swap(ctx, params)?.get())
+}
+
+/// Invokes `place_limit_order`, registering an order filled once its price is reachable
+pub fn place_limit_order<'info>(
+    superswap_program: AccountInfo<'info>,
+    accounts: accounts::PlaceLimitOrder<'info>,
+    remaining_accounts: Vec<AccountInfo<'info>>,
+    params: PlaceLimitOrderParams,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    check_program(&superswap_program)?;
+    let ctx = CpiContext::new_with_signer(superswap_program, accounts, signer_seeds)
+        .with_remaining_accounts(remaining_accounts);
+    superswap_sol::cpi::place_limit_order(ctx, params)
+}
+
+/// Invokes `create_dca_order`, registering an order executed in tranches
+pub fn create_dca_order<'info>(
+    superswap_program: AccountInfo<'info>,
+    accounts: accounts::CreateDcaOrder<'info>,
+    params: CreateDcaOrderParams,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    check_program(&superswap_program)?;
+    let ctx = CpiContext::new_with_signer(superswap_program, accounts, signer_seeds);
+    superswap_sol::cpi::create_dca_order(ctx, params)
+}
+
+/// Invokes `swap_and_bridge`, swapping the composer's input to USDC and bridging it out through Across
+///
+/// `remaining_accounts` are the Jupiter route accounts.
+pub fn swap_and_bridge<'info>(
+    superswap_program: AccountInfo<'info>,
+    accounts: accounts::SwapAndBridge<'info>,
+    remaining_accounts: Vec<AccountInfo<'info>>,
+    params: SwapAndBridgeParams,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    check_program(&superswap_program)?;
+    let ctx = CpiContext::new_with_signer(superswap_program, accounts, signer_seeds)
+        .with_remaining_accounts(remaining_accounts);
+    superswap_sol::cpi::swap_and_bridge(ctx, params)
+}
+
+fn check_program(program: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*program.key, ID, ErrorCode::InvalidProgramId);
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+use anyhow::{ensure, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Cranker configuration, loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct CrankerConfig {
+    pub rpc_url: String,
+    /// Keypair of the keeper, which signs and pays for refunds
+    pub keypair_path: PathBuf,
+    #[serde(with = "pubkey_string", default = "default_program_id")]
+    pub program_id: Pubkey,
+    /// Wallet whose USDC account collects refund bounties (defaults to the keeper)
+    #[serde(with = "optional_pubkey_string", default)]
+    pub bounty_wallet: Option<Pubkey>,
+    /// Refund transactions in flight at once
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// Priority fee attached to refunds, in micro-lamports per compute unit
+    #[serde(default)]
+    pub compute_unit_price: u64,
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+impl CrankerConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        let config: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse config {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(self.concurrency > 0, "concurrency must be positive");
+        Ok(())
+    }
+}
+
+fn default_program_id() -> Pubkey {
+    superswap_sol::ID
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+fn default_poll_interval_ms() -> u64 {
+    30_000
+}
+
+mod pubkey_string {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Pubkey::from_str(&raw).map_err(D::Error::custom)
+    }
+}
+
+mod optional_pubkey_string {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+        let raw = Option::<String>::deserialize(deserializer)?;
+        raw.map(|raw| Pubkey::from_str(&raw).map_err(D::Error::custom)).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_parses() {
+        let raw = include_str!("../cranker.example.json");
+        let config: CrankerConfig = serde_json::from_str(raw).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.program_id, superswap_sol::ID);
+        assert_eq!(config.bounty_wallet, Some(Pubkey::default()));
+    }
+
+    #[test]
+    fn zero_concurrency_is_rejected() {
+        let raw = r#"{"rpc_url": "http://localhost:8899", "keypair_path": "keeper.json", "concurrency": 0}"#;
+        let config: CrankerConfig = serde_json::from_str(raw).unwrap();
+        assert!(config.bounty_wallet.is_none());
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use superswap_sdk::OrderRef;
+use superswap_sol::state::{LimitOrder, OrderStatus, SwapOrder};
+
+/// What the keeper can do with one limit order record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Candidate {
+    /// Expired or past the order TTL, with its Jupiter order closed; refundable by the keeper
+    Refund,
+    /// Not expired yet, or placed without an expiry, and within the order TTL
+    NotExpired,
+    /// The swap order already left `LimitPlaced`
+    Settled,
+    /// Expired but the Jupiter order is still open; Jupiter's own keepers close
+    /// expired orders, after which it is retried
+    JupiterOrderOpen,
+}
+
+/// Classifies a limit order by whether an expired-order refund can land now
+///
+/// An order past `order_ttl_seconds` is refundable whatever its own expiry.
+pub fn classify(
+    limit_order: &LimitOrder,
+    swap_order: Option<&SwapOrder>,
+    jupiter_order_open: bool,
+    now: i64,
+    order_ttl_seconds: u32,
+) -> Candidate {
+    let past_ttl = swap_order.is_some_and(|swap_order| swap_order.is_past_ttl(now, order_ttl_seconds));
+    if !limit_order.is_expired(now) && !past_ttl {
+        return Candidate::NotExpired;
+    }
+    match swap_order {
+        Some(swap_order) if swap_order.status == OrderStatus::LimitPlaced => {}
+        _ => return Candidate::Settled,
+    }
+    if jupiter_order_open {
+        return Candidate::JupiterOrderOpen;
+    }
+    Candidate::Refund
+}
+
+/// The fields `cancel_limit_order` derives its accounts from
+pub fn order_ref(swap_order: &SwapOrder) -> OrderRef {
+    OrderRef {
+        order_id: swap_order.order_id,
+        recipient: swap_order.recipient,
+        destination_mint: swap_order.destination_mint,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn limit_order(expired_at: i64) -> LimitOrder {
+        LimitOrder {
+            swap_order: Pubkey::new_unique(),
+            jupiter_order: Pubkey::new_unique(),
+            making_amount: 1_000_000,
+            taking_amount: 5_000,
+            expired_at,
+            bump: 255,
+        }
+    }
+
+    fn swap_order(status: OrderStatus) -> SwapOrder {
+        SwapOrder {
+            order_id: 7,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000_000,
+            min_output_amount: 0,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 100,
+            created_at: 50,
+            status,
+            bump: 255,
+            rent_payer: Pubkey::new_unique(),
+            surplus: 0,
+        }
+    }
+
+    #[test]
+    fn only_expired_placed_orders_with_closed_jupiter_order_are_refunded() {
+        let placed = swap_order(OrderStatus::LimitPlaced);
+        assert_eq!(classify(&limit_order(100), Some(&placed), false, 100, 0), Candidate::Refund);
+        assert_eq!(classify(&limit_order(100), Some(&placed), false, 99, 0), Candidate::NotExpired);
+        assert_eq!(classify(&limit_order(0), Some(&placed), false, i64::MAX, 0), Candidate::NotExpired);
+        assert_eq!(classify(&limit_order(100), Some(&placed), true, 100, 0), Candidate::JupiterOrderOpen);
+    }
+
+    #[test]
+    fn settled_or_missing_orders_are_skipped() {
+        let completed = swap_order(OrderStatus::Completed);
+        assert_eq!(classify(&limit_order(100), Some(&completed), false, 200, 0), Candidate::Settled);
+        assert_eq!(classify(&limit_order(100), None, false, 200, 0), Candidate::Settled);
+    }
+
+    #[test]
+    fn orders_past_the_ttl_are_refunded_before_their_expiry() {
+        let placed = swap_order(OrderStatus::LimitPlaced);
+        assert_eq!(classify(&limit_order(0), Some(&placed), false, 80, 30), Candidate::Refund);
+        assert_eq!(classify(&limit_order(1_000), Some(&placed), false, 79, 30), Candidate::NotExpired);
+        assert_eq!(classify(&limit_order(0), None, false, 80, 30), Candidate::NotExpired);
+    }
+}
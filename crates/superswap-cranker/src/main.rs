@@ -0,0 +1,290 @@
+//! Refund cranker for SuperSwap
+//!
+//! Scans the program's limit order records for orders past their expiry whose
+//! Jupiter order has been closed, and submits `cancel_limit_order` for each as
+//! a keeper, returning the escrowed USDC and any fills to the recipient. Once
+//! an order has expired the cancellation is permissionless; the keeper earns
+//! the configured refund bounty into the bounty wallet's USDC account.
+
+mod config;
+mod crank;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use futures::stream::{self, StreamExt};
+use log::{debug, error, info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use superswap_pda::{find_config, wallet_token_account};
+use superswap_sdk::{cancel_limit_order, OrderRef};
+use superswap_sol::state::{Config, LimitOrder, SwapOrder};
+use crate::config::CrankerConfig;
+use crate::crank::{classify, order_ref, Candidate};
+
+/// Accounts fetched per `getMultipleAccounts` call
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Parser)]
+#[command(name = "superswap-cranker", about = "Refunds expired SuperSwap orders for the refund bounty")]
+struct Args {
+    /// Path to the cranker's JSON config
+    #[arg(long, env = "SUPERSWAP_CRANKER_CONFIG")]
+    config: PathBuf,
+
+    /// Run a single pass, then exit non-zero if any refund failed
+    #[arg(long)]
+    once: bool,
+}
+
+/// Signs and submits refunds as the keeper
+struct Keeper {
+    rpc: RpcClient,
+    keeper: Keypair,
+    config: CrankerConfig,
+    program_config: Config,
+    bounty_wallet: Pubkey,
+    bounty_account: Pubkey,
+}
+
+impl Keeper {
+    async fn new(config: CrankerConfig, keeper: Keypair) -> Result<Self> {
+        let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+        let program_config = fetch_program_config(&rpc, &config.program_id).await?;
+        if program_config.is_across_handler(&keeper.pubkey()) {
+            warn!("keeper {} is an Across handler, which earns no refund bounty", keeper.pubkey());
+        }
+        let bounty_wallet = config.bounty_wallet.unwrap_or_else(|| keeper.pubkey());
+        let bounty_account = wallet_token_account(&bounty_wallet, &program_config.usdc_mint);
+
+        Ok(Self {
+            rpc,
+            keeper,
+            config,
+            program_config,
+            bounty_wallet,
+            bounty_account,
+        })
+    }
+
+    /// Creates the bounty wallet's USDC account, which refunds must pass to collect the bounty
+    async fn ensure_bounty_account(&self) -> Result<()> {
+        let existing = self
+            .rpc
+            .get_account_with_commitment(&self.bounty_account, self.rpc.commitment())
+            .await?
+            .value;
+        if existing.is_some() {
+            return Ok(());
+        }
+
+        let create = create_associated_token_account_idempotent(
+            &self.keeper.pubkey(),
+            &self.bounty_wallet,
+            &self.program_config.usdc_mint,
+            &anchor_spl::token::ID,
+        );
+        let signature = self.send(vec![create]).await?;
+        info!("created bounty account {} ({})", self.bounty_account, signature);
+        Ok(())
+    }
+
+    /// Expired orders the keeper can refund now
+    async fn refundable(&self) -> Result<Vec<OrderRef>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        // With a TTL any order may be stale, which only its swap order tells
+        let order_ttl_seconds = self.program_config.order_ttl_seconds;
+        let limit_orders: Vec<LimitOrder> = self
+            .limit_orders()
+            .await?
+            .into_iter()
+            .filter(|limit_order| order_ttl_seconds > 0 || limit_order.is_expired(now))
+            .collect();
+
+        let mut refundable = Vec::new();
+        for chunk in limit_orders.chunks(MAX_MULTIPLE_ACCOUNTS / 2) {
+            // Each order's swap order and Jupiter order are fetched together
+            let addresses: Vec<Pubkey> = chunk
+                .iter()
+                .flat_map(|limit_order| [limit_order.swap_order, limit_order.jupiter_order])
+                .collect();
+            let accounts = self.rpc.get_multiple_accounts(&addresses).await?;
+
+            for (limit_order, pair) in chunk.iter().zip(accounts.chunks(2)) {
+                let swap_order = match &pair[0] {
+                    Some(account) => Some(
+                        SwapOrder::try_deserialize(&mut account.data.as_slice())
+                            .with_context(|| format!("malformed swap order {}", limit_order.swap_order))?,
+                    ),
+                    None => None,
+                };
+                let jupiter_order_open = pair[1].as_ref().is_some_and(|account| !account.data.is_empty());
+
+                match classify(limit_order, swap_order.as_ref(), jupiter_order_open, now, order_ttl_seconds) {
+                    Candidate::Refund => refundable.extend(swap_order.as_ref().map(order_ref)),
+                    Candidate::JupiterOrderOpen => debug!(
+                        "swap order {} expired but Jupiter order {} is still open",
+                        limit_order.swap_order, limit_order.jupiter_order
+                    ),
+                    Candidate::NotExpired | Candidate::Settled => {}
+                }
+            }
+        }
+        Ok(refundable)
+    }
+
+    /// Every limit order record owned by the program
+    async fn limit_orders(&self) -> Result<Vec<LimitOrder>> {
+        let filters = vec![
+            RpcFilterType::DataSize(LimitOrder::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &LimitOrder::DISCRIMINATOR)),
+        ];
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(
+                &self.config.program_id,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                        commitment: Some(self.rpc.commitment()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(address, account)| {
+                LimitOrder::try_deserialize(&mut account.data.as_slice())
+                    .with_context(|| format!("malformed limit order {}", address))
+            })
+            .collect()
+    }
+
+    /// Cancels one expired limit order, collecting the bounty
+    async fn refund(&self, order: &OrderRef) -> Result<Signature> {
+        let keeper = self.keeper.pubkey();
+        let cancel = cancel_limit_order(
+            &self.config.program_id,
+            order,
+            &self.program_config.usdc_mint,
+            &keeper,
+            &keeper,
+            Some(self.bounty_account),
+            &[],
+        );
+        self.send(vec![cancel]).await
+    }
+
+    async fn send(&self, mut instructions: Vec<Instruction>) -> Result<Signature> {
+        if self.config.compute_unit_price > 0 {
+            instructions.insert(
+                0,
+                ComputeBudgetInstruction::set_compute_unit_price(self.config.compute_unit_price),
+            );
+        }
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.keeper.pubkey()),
+            &[&self.keeper],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction).await?)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    let config = CrankerConfig::load(&args.config)?;
+    let keeper = read_keypair_file(&config.keypair_path)
+        .map_err(|err| anyhow!("failed to read keypair {}: {}", config.keypair_path.display(), err))?;
+    let poll_interval = Duration::from_millis(config.poll_interval_ms);
+    let concurrency = config.concurrency;
+
+    let keeper = Arc::new(Keeper::new(config, keeper).await?);
+    keeper.ensure_bounty_account().await?;
+    info!(
+        "keeper {} collecting {} bps bounties into {}",
+        keeper.keeper.pubkey(),
+        keeper.program_config.refund_bounty_bps,
+        keeper.bounty_account
+    );
+
+    loop {
+        let failed = match keeper.refundable().await {
+            Ok(orders) => crank(&keeper, orders, concurrency).await,
+            Err(err) => {
+                error!("scan failed: {:#}", err);
+                if args.once {
+                    return Err(err);
+                }
+                0
+            }
+        };
+
+        if args.once {
+            if failed > 0 {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Submits refunds with at most `concurrency` in flight, returning how many failed
+async fn crank(keeper: &Arc<Keeper>, orders: Vec<OrderRef>, concurrency: usize) -> usize {
+    if orders.is_empty() {
+        return 0;
+    }
+    info!("refunding {} expired orders", orders.len());
+
+    let results: Vec<bool> = stream::iter(orders)
+        .map(|order| {
+            let keeper = Arc::clone(keeper);
+            async move {
+                match keeper.refund(&order).await {
+                    Ok(signature) => {
+                        info!("refunded order {} ({})", order.order_id, signature);
+                        true
+                    }
+                    Err(err) => {
+                        warn!("refund of order {} failed: {:#}", order.order_id, err);
+                        false
+                    }
+                }
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+
+    results.into_iter().filter(|refunded| !refunded).count()
+}
+
+async fn fetch_program_config(rpc: &RpcClient, program_id: &Pubkey) -> Result<Config> {
+    let address = find_config(program_id).0;
+    let account = rpc
+        .get_account(&address)
+        .await
+        .with_context(|| format!("program config {} not found", address))?;
+    Ok(Config::try_deserialize(&mut account.data.as_slice())?)
+}
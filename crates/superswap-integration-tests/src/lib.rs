@@ -0,0 +1,8 @@
+//! End-to-end tests for the SuperSwap program
+//!
+//! The tests in `tests/` run the program natively under `solana-program-test`
+//! next to `mock-jupiter`, a Jupiter v6 stand-in with configurable output
+//! and failures, and
+//! `mock-across`, a spoke pool whose handler PDA signs as the Across handler,
+//! and drive it through the instruction builders of `superswap-sdk`. The
+//! shared harness lives in `tests/common`.
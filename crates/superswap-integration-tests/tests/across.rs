@@ -0,0 +1,114 @@
+//! The inbound path as Across drives it: a spoke pool fill delivers USDC and
+//! the order message to the handler, which then fills the order
+
+mod common;
+
+use anchor_lang::AnchorSerialize;
+use common::{quoted_route, TestEnv, FEE_BPS};
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address;
+use superswap_pda::{find_config, find_swap_order, program_usdc_account};
+use superswap_sdk::{FillAccounts, OrderMessage};
+use superswap_sol::state::{OrderStatus, SwapOrder};
+
+const BRIDGED_USDC: u64 = 2_000_000;
+
+fn order_message(env: &TestEnv, order_id: u64, deadline: i64) -> OrderMessage {
+    OrderMessage {
+        order_id,
+        recipient: Keypair::new().pubkey(),
+        usdc_amount: BRIDGED_USDC,
+        min_output_amount: 0,
+        destination_mint: env.output_mint,
+        deadline,
+        jupiter_swap_data: Vec::new(),
+        relayer_tip: None,
+        allow_off_curve_recipient: None,
+    }
+}
+
+/// The handler's fill of a delivered order, signed by the mock handler PDA and
+/// swapped through a mock route
+fn handler_fill(env: &TestEnv, message: OrderMessage) -> solana_sdk::instruction::Instruction {
+    let swap = env.mock_swap(
+        &find_config(&env.program_id).0,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &get_associated_token_address(&message.recipient, &env.output_mint),
+        BRIDGED_USDC - BRIDGED_USDC * FEE_BPS as u64 / 10_000,
+        1_000,
+    );
+    let route = quoted_route(&swap, swap.route());
+    let accounts = FillAccounts {
+        across_handler: env.across_handler,
+        payer: env.payer(),
+        source_usdc_account: env.handler_usdc_account(),
+        usdc_mint: env.usdc_mint,
+        fee_recipient: env.fee_recipient,
+        jupiter_program: mock_jupiter::ID,
+        recipient_preferences: None,
+        recipient_program: None,
+        fallback_output_mint: None,
+        staged_route: false,
+        route_template: false,
+        surplus_vault: false,
+        payer_surplus_account: false,
+        fee_recipient_surplus_account: false,
+        token_2022_destination: false,
+        global_volume_tracker: false,
+        mint_volume_tracker: false,
+    };
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &accounts,
+        message.into_params(BRIDGED_USDC, route.swap_data),
+        &route.accounts,
+    );
+    env.as_mock_handler(fill)
+}
+
+#[tokio::test]
+async fn relay_fill_and_handler_fill_complete_the_order() {
+    let mut env = TestEnv::with_mock_across(BRIDGED_USDC).await;
+    let now = env.now().await;
+    let message = order_message(&env, 11, now + 600);
+
+    let relay = env.fill_relay(message.try_to_vec().unwrap(), BRIDGED_USDC, now as u32 + 600);
+    let fill = handler_fill(&env, message.clone());
+    env.process(&[relay, fill], &[]).await.expect("relay and fill");
+
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(0));
+    assert_eq!(
+        env.token_balance(&env.fee_recipient_usdc_account()).await,
+        Some(BRIDGED_USDC * FEE_BPS as u64 / 10_000)
+    );
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 11).0)
+        .await
+        .expect("order account");
+    assert_eq!(swap_order.recipient, message.recipient);
+    assert!(swap_order.status == OrderStatus::Completed);
+}
+
+#[tokio::test]
+async fn expired_order_refunds_relayed_usdc_to_the_recipient() {
+    let mut env = TestEnv::with_mock_across(BRIDGED_USDC).await;
+    let now = env.now().await;
+    let message = order_message(&env, 12, now - 1);
+
+    let relay = env.fill_relay(message.try_to_vec().unwrap(), BRIDGED_USDC, now as u32 + 600);
+    env.process(&[relay], &[]).await.expect("relay");
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(BRIDGED_USDC));
+
+    let fill = handler_fill(&env, message.clone());
+    env.process(&[fill], &[]).await.expect("late fill");
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(0));
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&message.recipient, &env.usdc_mint)).await,
+        Some(BRIDGED_USDC)
+    );
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 12).0)
+        .await
+        .expect("order account");
+    assert!(swap_order.status == OrderStatus::Refunded);
+}
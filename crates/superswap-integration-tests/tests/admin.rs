@@ -0,0 +1,511 @@
+//! Admin roles, handover and timelocked config updates: a proposed admin only
+//! takes over once it accepts, routing-critical addresses and extra Across
+//! handlers only change after a delay, the guardian can stop the program
+//! but not restart it, each instance's admin only reaches its own accounts,
+//! stray lamports leave only through the recovery guard and above rent exemption,
+//! and an older config migrates in place with its settings
+
+mod common;
+
+use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+use common::{custom_error, TestEnv, RENT_VAULT_FUNDING};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::{system_instruction, system_program};
+use superswap_pda::{
+    find_config, find_hook_program, find_instance_admin_log, find_instance_config, find_instance_hook_program,
+    find_instance_outbound_rate_limit, find_instance_pending_recovery, find_instance_recovery_guard,
+    find_instance_rent_vault, find_outbound_rate_limit, find_pending_config_update, find_pending_recovery,
+    find_rent_vault,
+};
+use superswap_sol::error::SuperSwapError;
+use superswap_sol::state::{
+    Config, ConfigV2, HookProgram, HookProgramParams, LamportSource, OutboundRateLimit, PendingRecovery,
+    ProposeConfigUpdateParams, QueueLamportRecoveryParams, RecoverLamportsParams, RecoveryGuard, RecoveryLimits,
+    RentVault, SurplusPolicy, UpdateConfigParams,
+};
+
+#[tokio::test]
+async fn proposed_admin_takes_over_only_after_accepting() {
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    let new_admin = Keypair::new();
+    let config_address = find_config(&env.program_id).0;
+
+    let propose = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_admin: Some(new_admin.pubkey()),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[propose], &[]).await.expect("propose admin");
+    let config: Config = env.anchor_account(&config_address).await.expect("config");
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.pending_admin, new_admin.pubkey());
+
+    // Only the proposed key can accept
+    let stranger = Keypair::new();
+    let result = env
+        .process(&[superswap_sdk::accept_admin(&env.program_id, &stranger.pubkey())], &[&stranger])
+        .await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::Unauthorized)));
+
+    env.process(&[superswap_sdk::accept_admin(&env.program_id, &new_admin.pubkey())], &[&new_admin])
+        .await
+        .expect("accept admin");
+    let config: Config = env.anchor_account(&config_address).await.expect("config");
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Pubkey::default());
+}
+
+#[tokio::test]
+async fn config_update_waits_out_the_delay_and_can_be_cancelled() {
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    let config_address = find_config(&env.program_id).0;
+    let new_fee_recipient = Pubkey::new_unique();
+
+    let set_delay = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_config_update_delay_seconds: Some(86_400),
+            ..UpdateConfigParams::default()
+        },
+    );
+    let propose = superswap_sdk::propose_config_update(
+        &env.program_id,
+        &admin,
+        ProposeConfigUpdateParams {
+            new_fee_recipient: Some(new_fee_recipient),
+            ..ProposeConfigUpdateParams::default()
+        },
+    );
+    env.process(&[set_delay, propose], &[]).await.expect("propose update");
+
+    let result = env.process(&[superswap_sdk::apply_config_update(&env.program_id, &admin)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::ConfigUpdateTimelockActive)));
+    let config: Config = env.anchor_account(&config_address).await.expect("config");
+    assert_ne!(config.fee_recipient, new_fee_recipient);
+
+    // The delay cannot be shortened without going through the timelock
+    let shorten = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_config_update_delay_seconds: Some(0),
+            ..UpdateConfigParams::default()
+        },
+    );
+    let result = env.process(&[shorten], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidConfigUpdateDelay)));
+
+    env.process(&[superswap_sdk::cancel_config_update(&env.program_id, &admin)], &[])
+        .await
+        .expect("cancel update");
+    let pending = find_pending_config_update(&env.program_id).0;
+    assert!(env.context.banks_client.get_account(pending).await.expect("get account").is_none());
+}
+
+#[tokio::test]
+async fn additional_across_handler_is_added_through_the_timelock_and_removed_at_once() {
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    let config_address = find_config(&env.program_id).0;
+    let handler = Pubkey::new_unique();
+
+    let program_id = env.program_id;
+    let propose = |handler| {
+        superswap_sdk::propose_config_update(
+            &program_id,
+            &admin,
+            ProposeConfigUpdateParams {
+                add_across_handler: Some(handler),
+                ..ProposeConfigUpdateParams::default()
+            },
+        )
+    };
+    let add = [propose(handler), superswap_sdk::apply_config_update(&env.program_id, &admin)];
+    env.process(&add, &[]).await.expect("add handler");
+    let config: Config = env.anchor_account(&config_address).await.expect("config");
+    assert!(config.is_across_handler(&handler));
+    assert!(config.is_across_handler(&config.across_handler));
+
+    let result = env.process(&[propose(handler)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::AcrossHandlerAlreadyRegistered)));
+
+    let remove = superswap_sdk::remove_across_handler(&env.program_id, &admin, &handler);
+    env.process(&[remove.clone()], &[]).await.expect("remove handler");
+    let config: Config = env.anchor_account(&config_address).await.expect("config");
+    assert!(!config.is_across_handler(&handler));
+
+    let result = env.process(&[remove], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::AcrossHandlerNotRegistered)));
+}
+
+#[tokio::test]
+async fn guardian_can_pause_but_not_unpause() {
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    let guardian = Keypair::new();
+
+    let assign = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_guardian: Some(guardian.pubkey()),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[assign], &[]).await.expect("assign guardian");
+
+    env.process(&[superswap_sdk::pause(&env.program_id, &guardian.pubkey())], &[&guardian])
+        .await
+        .expect("guardian pauses");
+    let result = env
+        .process(&[superswap_sdk::unpause(&env.program_id, &guardian.pubkey())], &[&guardian])
+        .await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::Unauthorized)));
+
+    env.process(&[superswap_sdk::unpause(&env.program_id, &admin)], &[]).await.expect("admin unpauses");
+    let config: Config = env.anchor_account(&find_config(&env.program_id).0).await.expect("config");
+    assert!(!config.is_paused);
+}
+
+#[tokio::test]
+async fn version_2_config_migrates_in_place_with_its_settings() {
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    let config_address = find_config(&env.program_id).0;
+
+    // Rewrite the config in the version 2 layout: it ended at `vault_authority`,
+    // followed by the pause flag and bump, and was only rent-exempt at that size
+    let mut config: Config = env.anchor_account(&config_address).await.expect("config");
+    config.version = 2;
+    config.guardian = Pubkey::new_unique();
+    config.max_order_usdc = 5_000_000_000;
+    config.is_paused = true;
+    let mut current = Vec::new();
+    config.try_serialize(&mut current).expect("serialize config");
+    let mut data = current[..ConfigV2::LEN - 2].to_vec();
+    data.extend_from_slice(&current[Config::LEN - 2..]);
+    let rent = env.context.banks_client.get_rent().await.expect("rent");
+    let mut account = env
+        .context
+        .banks_client
+        .get_account(config_address)
+        .await
+        .expect("get account")
+        .expect("config account");
+    account.lamports = rent.minimum_balance(ConfigV2::LEN);
+    account.data = data;
+    env.context.set_account(&config_address, &account.into());
+
+    let outsider = Keypair::new();
+    let result = env
+        .process(&[superswap_sdk::migrate_config(&env.program_id, &outsider.pubkey())], &[&outsider])
+        .await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::Unauthorized)));
+
+    env.process(&[superswap_sdk::migrate_config(&env.program_id, &admin)], &[]).await.expect("migrate config");
+    let account = env
+        .context
+        .banks_client
+        .get_account(config_address)
+        .await
+        .expect("get account")
+        .expect("config account");
+    assert_eq!(account.data.len(), Config::LEN);
+    assert!(account.lamports >= rent.minimum_balance(Config::LEN));
+
+    let migrated: Config = env.anchor_account(&config_address).await.expect("migrated config");
+    assert_eq!(migrated.version, Config::VERSION);
+    assert_eq!((migrated.admin, migrated.guardian), (config.admin, config.guardian));
+    assert_eq!((migrated.usdc_mint, migrated.fee_recipient), (config.usdc_mint, config.fee_recipient));
+    assert_eq!((migrated.fee_bps, migrated.max_order_usdc), (config.fee_bps, config.max_order_usdc));
+    assert_eq!(migrated.vault_authority, config.vault_authority);
+    assert_eq!((migrated.is_paused, migrated.bump), (true, config.bump));
+    // Settings version 3 added start out inert
+    assert!(!migrated.refunds_only);
+    assert_eq!(migrated.surplus_policy, SurplusPolicy::Recipient);
+    assert_eq!(migrated.instance_id, 0);
+
+    // A migrated config is in the current layout and cannot be migrated again
+    env.advance_clock(1).await;
+    let result = env.process(&[superswap_sdk::migrate_config(&env.program_id, &admin)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::ConfigNotMigratable)));
+}
+
+#[tokio::test]
+async fn instance_admin_manages_only_its_own_allowlist() {
+    const INSTANCE_ID: u16 = 7;
+
+    let mut env = TestEnv::new(0).await;
+    let primary_admin = env.payer();
+    let instance_admin = env.create_instance(INSTANCE_ID).await;
+
+    // The instance starts with its own rent vault, rate limit and recovery guard, and like the
+    // primary config leaves all positive slippage with the recipient
+    let rent_vault: RentVault = env
+        .anchor_account(&find_instance_rent_vault(&env.program_id, INSTANCE_ID).0)
+        .await
+        .expect("instance rent vault");
+    assert_eq!(rent_vault.total_deposited, 0);
+    let primary_rent_vault: RentVault =
+        env.anchor_account(&find_rent_vault(&env.program_id).0).await.expect("primary rent vault");
+    assert_eq!(primary_rent_vault.total_deposited, RENT_VAULT_FUNDING);
+    let outbound_rate_limit = find_instance_outbound_rate_limit(&env.program_id, INSTANCE_ID).0;
+    assert!(env.anchor_account::<OutboundRateLimit>(&outbound_rate_limit).await.is_some());
+    let recovery_guard = find_instance_recovery_guard(&env.program_id, INSTANCE_ID).0;
+    assert!(env.anchor_account::<RecoveryGuard>(&recovery_guard).await.is_some());
+    let instance_config = find_instance_config(&env.program_id, INSTANCE_ID).0;
+    for config_address in [find_config(&env.program_id).0, instance_config] {
+        let config: Config = env.anchor_account(&config_address).await.expect("config");
+        assert_eq!(config.surplus_policy, SurplusPolicy::Recipient);
+        assert_eq!(config.surplus_share_bps, 0);
+    }
+
+    let hook = Pubkey::new_unique();
+    let instance_entry = find_instance_hook_program(&env.program_id, INSTANCE_ID, &hook).0;
+    let add_to_instance = |program_id: Pubkey, admin: Pubkey| Instruction {
+        program_id,
+        accounts: superswap_sol::accounts::AddHookProgram {
+            config: instance_config,
+            admin_log: find_instance_admin_log(&program_id, INSTANCE_ID).0,
+            hook_program: instance_entry,
+            admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: superswap_sol::instruction::AddHookProgram {
+            params: HookProgramParams { program_id: hook },
+        }
+        .data(),
+    };
+
+    // Neither admin reaches the other instance's allowlist
+    let result = env.process(&[add_to_instance(env.program_id, primary_admin)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::Unauthorized)));
+    let add_to_primary = superswap_sdk::add_hook_program(&env.program_id, &instance_admin.pubkey(), &hook);
+    let result = env.process(&[add_to_primary], &[&instance_admin]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::Unauthorized)));
+
+    // The instance's entry leaves the primary allowlist untouched
+    let add = add_to_instance(env.program_id, instance_admin.pubkey());
+    env.process(&[add], &[&instance_admin]).await.expect("instance admin adds a hook");
+    let entry: HookProgram = env.anchor_account(&instance_entry).await.expect("instance entry");
+    assert_eq!(entry.program_id, hook);
+    assert!(env
+        .anchor_account::<HookProgram>(&find_hook_program(&env.program_id, &hook).0)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn lamport_recovery_above_the_threshold_waits_out_the_delay_and_can_be_cancelled() {
+    const STRAY: u64 = 5_000_000;
+
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    // Any program-owned account other than the rent vault can hold stray lamports
+    let source = find_outbound_rate_limit(&env.program_id).0;
+    let destination = Pubkey::new_unique();
+    let rent_exempt = env.lamports(&source).await;
+
+    let limits = superswap_sdk::set_recovery_limits(
+        &env.program_id,
+        &admin,
+        RecoveryLimits {
+            timelock_threshold: 1_000_000,
+            ..RecoveryLimits::DISABLED
+        },
+    );
+    let strand = system_instruction::transfer(&admin, &source, STRAY);
+    env.process(&[limits, strand], &[]).await.expect("strand lamports");
+
+    let recover =
+        superswap_sdk::recover_lamports(&env.program_id, &admin, LamportSource::OutboundRateLimit, &destination, STRAY);
+    let result = env.process(&[recover], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RecoveryRequiresTimelock)));
+
+    let program_id = env.program_id;
+    let queue = move |recovery_id: u64| {
+        superswap_sdk::queue_lamport_recovery(
+            &program_id,
+            &admin,
+            &destination,
+            QueueLamportRecoveryParams {
+                recovery_id,
+                source: LamportSource::OutboundRateLimit,
+                amount: STRAY,
+            },
+        )
+    };
+    let execute = move |recovery_id: u64| {
+        superswap_sdk::execute_lamport_recovery(&program_id, &admin, recovery_id, &source, &destination)
+    };
+
+    env.process(&[queue(1)], &[]).await.expect("queue recovery");
+    let pending = find_pending_recovery(&env.program_id, 1).0;
+    let queued: PendingRecovery = env.anchor_account(&pending).await.expect("pending recovery");
+    assert_eq!(queued.amount, STRAY);
+
+    let result = env.process(&[execute(1)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RecoveryTimelockActive)));
+    assert_eq!(env.lamports(&source).await, rent_exempt + STRAY);
+
+    env.advance_clock(RecoveryLimits::DEFAULT_DELAY_SECONDS).await;
+    env.process(&[execute(1)], &[]).await.expect("execute recovery");
+    assert_eq!(env.lamports(&destination).await, STRAY);
+    assert_eq!(env.lamports(&source).await, rent_exempt);
+    assert!(env.anchor_account::<PendingRecovery>(&pending).await.is_none());
+
+    // A cancelled recovery can no longer be executed, even once its delay is over
+    let strand = system_instruction::transfer(&admin, &source, STRAY);
+    env.process(&[strand, queue(2)], &[]).await.expect("queue second recovery");
+    env.process(&[superswap_sdk::cancel_recovery(&env.program_id, &admin, 2)], &[])
+        .await
+        .expect("cancel recovery");
+    assert!(env
+        .anchor_account::<PendingRecovery>(&find_pending_recovery(&env.program_id, 2).0)
+        .await
+        .is_none());
+
+    env.advance_clock(RecoveryLimits::DEFAULT_DELAY_SECONDS).await;
+    assert!(env.process(&[execute(2)], &[]).await.is_err());
+    assert_eq!(env.lamports(&source).await, rent_exempt + STRAY);
+    assert_eq!(env.lamports(&destination).await, STRAY);
+}
+
+#[tokio::test]
+async fn lamport_recovery_leaves_the_source_rent_exempt() {
+    const STRAY: u64 = 2_000_000;
+
+    let mut env = TestEnv::new(0).await;
+    let admin = env.payer();
+    let source = find_outbound_rate_limit(&env.program_id).0;
+    let destination = Pubkey::new_unique();
+    let rent_exempt = env.lamports(&source).await;
+
+    let strand = system_instruction::transfer(&admin, &source, STRAY);
+    env.process(&[strand], &[]).await.expect("strand lamports");
+
+    // Only what the source holds above its rent-exempt minimum can leave
+    let program_id = env.program_id;
+    let recover = move |source: &Pubkey, amount: u64| {
+        recover_lamports(program_id, 0, admin, source, LamportSource::OutboundRateLimit, destination, amount)
+    };
+    let result = env.process(&[recover(&source, STRAY + 1)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InsufficientStrayLamports)));
+
+    env.process(&[recover(&source, STRAY)], &[]).await.expect("recover lamports");
+    assert_eq!(env.lamports(&source).await, rent_exempt);
+    assert_eq!(env.lamports(&destination).await, STRAY);
+
+    let result = env.process(&[recover(&source, 1)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InsufficientStrayLamports)));
+
+    // The rent vault's lamports are accounted for, and other programs' accounts are not ours to drain
+    let result = env.process(&[recover(&find_rent_vault(&env.program_id).0, 1)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidLamportSource)));
+    let result = env.process(&[recover(&destination, 1)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidLamportSource)));
+}
+
+#[tokio::test]
+async fn instance_admin_recovers_lamports_only_from_its_own_instance() {
+    const INSTANCE_ID: u16 = 7;
+    const STRAY: u64 = 2_000_000;
+
+    let mut env = TestEnv::new(0).await;
+    let primary_admin = env.payer();
+    let instance_admin = env.create_instance(INSTANCE_ID).await;
+    let primary_source = find_outbound_rate_limit(&env.program_id).0;
+    let instance_source = find_instance_outbound_rate_limit(&env.program_id, INSTANCE_ID).0;
+    let destination = Pubkey::new_unique();
+    let rent_exempt = env.lamports(&primary_source).await;
+
+    let strand_primary = system_instruction::transfer(&primary_admin, &primary_source, STRAY);
+    let strand_instance = system_instruction::transfer(&primary_admin, &instance_source, STRAY);
+    env.process(&[strand_primary, strand_instance], &[]).await.expect("strand lamports");
+
+    let program_id = env.program_id;
+    let recover = move |instance_id: u16, admin: Pubkey, source: &Pubkey| {
+        recover_lamports(program_id, instance_id, admin, source, LamportSource::OutboundRateLimit, destination, STRAY)
+    };
+
+    // Neither admin can name the other instance's account as its own
+    let steal = recover(INSTANCE_ID, instance_admin.pubkey(), &primary_source);
+    let result = env.process(&[steal], &[&instance_admin]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidLamportSource)));
+    let result = env.process(&[recover(0, primary_admin, &instance_source)], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidLamportSource)));
+
+    // Nor queue a recovery from it to run once the delay is over
+    let queue = Instruction {
+        program_id,
+        accounts: superswap_sol::accounts::QueueLamportRecovery {
+            config: find_instance_config(&program_id, INSTANCE_ID).0,
+            admin_log: find_instance_admin_log(&program_id, INSTANCE_ID).0,
+            recovery_guard: find_instance_recovery_guard(&program_id, INSTANCE_ID).0,
+            pending_recovery: find_instance_pending_recovery(&program_id, INSTANCE_ID, 1).0,
+            admin: instance_admin.pubkey(),
+            source: primary_source,
+            destination,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: superswap_sol::instruction::QueueLamportRecovery {
+            params: QueueLamportRecoveryParams {
+                recovery_id: 1,
+                source: LamportSource::OutboundRateLimit,
+                amount: STRAY,
+            },
+        }
+        .data(),
+    };
+    let result = env.process(&[queue], &[&instance_admin]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidLamportSource)));
+    assert_eq!(env.lamports(&primary_source).await, rent_exempt + STRAY);
+
+    // Each admin still reaches its own instance's account
+    let own = recover(INSTANCE_ID, instance_admin.pubkey(), &instance_source);
+    env.process(&[own], &[&instance_admin]).await.expect("instance admin recovers its own lamports");
+    env.process(&[recover(0, primary_admin, &primary_source)], &[])
+        .await
+        .expect("primary admin recovers its own lamports");
+    assert_eq!(env.lamports(&primary_source).await, rent_exempt);
+    assert_eq!(env.lamports(&instance_source).await, rent_exempt);
+    assert_eq!(env.lamports(&destination).await, 2 * STRAY);
+}
+
+/// Builds a `recover_lamports` instruction for any instance, with `source` passed as given
+fn recover_lamports(
+    program_id: Pubkey,
+    instance_id: u16,
+    admin: Pubkey,
+    source: &Pubkey,
+    named: LamportSource,
+    destination: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: superswap_sol::accounts::RecoverLamports {
+            config: find_instance_config(&program_id, instance_id).0,
+            admin_log: find_instance_admin_log(&program_id, instance_id).0,
+            recovery_guard: find_instance_recovery_guard(&program_id, instance_id).0,
+            admin,
+            source: *source,
+            destination,
+        }
+        .to_account_metas(None),
+        data: superswap_sol::instruction::RecoverLamports {
+            params: RecoverLamportsParams { source: named, amount },
+        }
+        .data(),
+    }
+}
@@ -0,0 +1,508 @@
+//! Test environment: a configured program, funded rent vault, USDC and output
+//! mints, a mock Jupiter pool that pays the quote a test asks for unless told to
+//! short it or fail, and a mock Across spoke pool whose handler PDA can stand in
+//! for the handler keypair
+
+// Each test binary uses a different subset of the harness
+#![allow(dead_code)]
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022;
+use mock_jupiter::client::MockSwap;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::sysvar::clock::Clock;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
+use spl_token_2022::extension::transfer_fee::{self, TransferFeeAmount};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use superswap_quote::JupiterRoute;
+use superswap_sol::state::{CreateInstanceParams, InitializeParams};
+
+/// Inbound fee configured for every test, in basis points
+pub const FEE_BPS: u16 = 10;
+
+/// Lamports the rent vault starts with
+pub const RENT_VAULT_FUNDING: u64 = 1_000_000_000;
+
+/// Output tokens the mock Jupiter pool starts with
+pub const POOL_OUTPUT: u64 = 1_000_000_000_000;
+
+/// Slippage every mock route is quoted with, in basis points
+pub const MOCK_SLIPPAGE_BPS: u16 = 50;
+
+// Anchor's entrypoints tie the accounts slice and its items to one lifetime
+fn superswap_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    superswap_sol::entry(program_id, accounts, data)
+}
+
+fn mock_across_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_across::entry(program_id, accounts, data)
+}
+
+fn mock_jupiter_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_jupiter::entry(program_id, accounts, data)
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub program_id: Pubkey,
+    pub handler: Keypair,
+    /// The program's Across handler: `handler`, or the mock Across handler PDA
+    pub across_handler: Pubkey,
+    pub mint_authority: Keypair,
+    pub usdc_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub pool_authority: Pubkey,
+}
+
+impl TestEnv {
+    /// Starts a validator with the program initialized, the rent vault funded
+    /// and `handler_usdc` USDC in the Across handler's ATA
+    pub async fn new(handler_usdc: u64) -> Self {
+        let mut env = Self::start(false).await;
+        let (usdc_mint, handler_usdc_account) = (env.usdc_mint, env.handler_usdc_account());
+        env.mint_to(&usdc_mint, &handler_usdc_account, handler_usdc).await;
+        env
+    }
+
+    /// Starts a validator whose Across handler is the mock spoke pool's handler
+    /// PDA, with `relayer_usdc` USDC in the payer's ATA to fill relays from
+    pub async fn with_mock_across(relayer_usdc: u64) -> Self {
+        let mut env = Self::start(true).await;
+        let (usdc_mint, payer) = (env.usdc_mint, env.payer());
+        let relayer_usdc_account = env.create_ata(&payer, &usdc_mint).await;
+        env.mint_to(&usdc_mint, &relayer_usdc_account, relayer_usdc).await;
+        env
+    }
+
+    async fn start(mock_across_handler: bool) -> Self {
+        let program_id = superswap_sol::ID;
+        let mut program_test = ProgramTest::new("superswap_sol", program_id, processor!(superswap_entry));
+        program_test.add_program("mock_jupiter", mock_jupiter::ID, processor!(mock_jupiter_entry));
+        program_test.add_program("mock_across", mock_across::ID, processor!(mock_across_entry));
+        program_test.prefer_bpf(false);
+
+        let context = program_test.start_with_context().await;
+        let handler = Keypair::new();
+        let across_handler = if mock_across_handler {
+            Pubkey::find_program_address(&[mock_across::HANDLER_SEED], &mock_across::ID).0
+        } else {
+            handler.pubkey()
+        };
+        let mut env = Self {
+            context,
+            program_id,
+            handler,
+            across_handler,
+            mint_authority: Keypair::new(),
+            usdc_mint: Pubkey::default(),
+            output_mint: Pubkey::default(),
+            fee_recipient: Pubkey::new_unique(),
+            pool_authority: mock_jupiter::client::pool_authority(),
+        };
+
+        env.usdc_mint = env.create_mint(6).await;
+        env.output_mint = env.create_mint(9).await;
+
+        let admin = env.payer();
+        let initialize = superswap_sdk::initialize(
+            &program_id,
+            &admin,
+            InitializeParams {
+                across_handler: env.across_handler,
+                jupiter_program: mock_jupiter::ID,
+                across_spoke_pool: Pubkey::new_unique(),
+                cctp_token_messenger_minter: Pubkey::new_unique(),
+                usdc_mint: env.usdc_mint,
+                fee_recipient: env.fee_recipient,
+                fee_bps: FEE_BPS,
+                outbound_fee_bps: 0,
+                min_outbound_amount: 0,
+                max_outbound_amount: 0,
+            },
+        );
+        let fund = superswap_sdk::fund_rent_vault(&program_id, &admin, RENT_VAULT_FUNDING);
+        // The context payer pays for the fills the handler signs
+        let operator = superswap_sdk::add_payer_operator(&program_id, &admin, &admin);
+        env.process(&[initialize, fund, operator], &[]).await.expect("initialize");
+
+        // Token accounts every flow touches
+        let (usdc_mint, output_mint, handler, fee_recipient, pool) = (
+            env.usdc_mint,
+            env.output_mint,
+            env.across_handler,
+            env.fee_recipient,
+            env.pool_authority,
+        );
+        env.create_ata(&handler, &usdc_mint).await;
+        env.create_ata(&fee_recipient, &usdc_mint).await;
+        env.create_ata(&pool, &usdc_mint).await;
+        let pool_output = env.create_ata(&pool, &output_mint).await;
+        env.mint_to(&output_mint, &pool_output, POOL_OUTPUT).await;
+
+        env
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub fn handler_usdc_account(&self) -> Pubkey {
+        get_associated_token_address(&self.across_handler, &self.usdc_mint)
+    }
+
+    pub fn fee_recipient_usdc_account(&self) -> Pubkey {
+        get_associated_token_address(&self.fee_recipient, &self.usdc_mint)
+    }
+
+    /// Sends instructions paid for by the context payer, with `signers` as extra signers
+    pub async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let mut all_signers: Vec<&Keypair> = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&self.context.payer.pubkey()), &all_signers, blockhash);
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .expect("clock")
+            .unix_timestamp
+    }
+
+    /// Moves the bank clock `seconds` forward and waits for a fresh blockhash, so a
+    /// transaction retried after the jump is not rejected as already processed
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.context.banks_client.get_sysvar::<Clock>().await.expect("clock");
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+        self.context.get_new_latest_blockhash().await.expect("new blockhash");
+    }
+
+    /// Token balance of `address`, or `None` when the account does not exist
+    pub async fn token_balance(&mut self, address: &Pubkey) -> Option<u64> {
+        let account = self.context.banks_client.get_account(*address).await.expect("get account")?;
+        Some(spl_token::state::Account::unpack(&account.data).expect("token account").amount)
+    }
+
+    /// Overwrites the balance of the token account at `address`, standing in for a drain
+    /// the program never saw
+    pub async fn set_token_balance(&mut self, address: &Pubkey, amount: u64) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .expect("get account")
+            .expect("token account");
+        let mut state = spl_token::state::Account::unpack(&account.data).expect("token account");
+        state.amount = amount;
+        spl_token::state::Account::pack(state, &mut account.data).expect("pack token account");
+        self.context.set_account(address, &account.into());
+    }
+
+    /// Balance of the Token-2022 account at `address` and the transfer fees withheld in
+    /// it, or `None` when the account does not exist
+    pub async fn token_2022_balance(&mut self, address: &Pubkey) -> Option<(u64, u64)> {
+        let account = self.context.banks_client.get_account(*address).await.expect("get account")?;
+        let state =
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).expect("token account");
+        let withheld = state
+            .get_extension::<TransferFeeAmount>()
+            .map_or(0, |fee_amount| u64::from(fee_amount.withheld_amount));
+        Some((state.base.amount, withheld))
+    }
+
+    /// Lamports held by `address`, 0 when the account does not exist
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context.banks_client.get_balance(*address).await.expect("get balance")
+    }
+
+    /// Deserialized Anchor account at `address`, or `None` when it does not exist
+    pub async fn anchor_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self.context.banks_client.get_account(*address).await.expect("get account")?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).expect("anchor account"))
+    }
+
+    /// Mock Jupiter swap of USDC for output, quoted at `out_amount`
+    ///
+    /// `authority` owns `source` and signs through the calling program.
+    pub fn mock_swap(
+        &self,
+        authority: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        in_amount: u64,
+        out_amount: u64,
+    ) -> MockSwap {
+        MockSwap {
+            authority: *authority,
+            source: *source,
+            destination: *destination,
+            input_mint: self.usdc_mint,
+            output_mint: self.output_mint,
+            output_token_program: spl_token::ID,
+            in_amount,
+            quoted_out_amount: out_amount,
+            slippage_bps: MOCK_SLIPPAGE_BPS,
+        }
+    }
+
+    /// Sets the share of the quote the mock pool pays, and whether it fails every swap
+    pub async fn configure_mock_jupiter(&mut self, output_bps: u16, fail: bool) {
+        let configure = mock_jupiter::client::configure(&self.payer(), output_bps, fail);
+        self.process(&[configure], &[]).await.expect("configure mock Jupiter");
+    }
+
+    /// Creates deployment instance `instance_id` with its own funded admin, who is returned
+    pub async fn create_instance(&mut self, instance_id: u16) -> Keypair {
+        let admin = Keypair::new();
+        let create = superswap_sdk::create_instance(
+            &self.program_id,
+            &self.payer(),
+            CreateInstanceParams {
+                instance_id,
+                admin: admin.pubkey(),
+                config: InitializeParams {
+                    across_handler: Pubkey::new_unique(),
+                    jupiter_program: Pubkey::new_unique(),
+                    across_spoke_pool: Pubkey::new_unique(),
+                    cctp_token_messenger_minter: Pubkey::new_unique(),
+                    usdc_mint: self.usdc_mint,
+                    fee_recipient: Pubkey::new_unique(),
+                    fee_bps: 20,
+                    outbound_fee_bps: 0,
+                    min_outbound_amount: 0,
+                    max_outbound_amount: 0,
+                },
+            },
+        );
+        let fund = system_instruction::transfer(&self.payer(), &admin.pubkey(), 1_000_000_000);
+        self.process(&[create, fund], &[]).await.expect("create instance");
+        admin
+    }
+
+    /// Disables or re-enables fills into `mint`, signed by the admin
+    pub async fn set_mint_disabled(&mut self, mint: &Pubkey, disabled: bool) {
+        let set = superswap_sdk::set_destination_mint(&self.program_id, &self.payer(), mint, disabled);
+        self.process(&[set], &[]).await.expect("set destination mint");
+    }
+
+    /// Mock spoke pool fill paying `output_amount` USDC from the payer to the Across handler
+    pub fn fill_relay(&self, message: Vec<u8>, output_amount: u64, fill_deadline: u32) -> Instruction {
+        let relay_data = mock_across::RelayData {
+            depositor: Pubkey::new_unique(),
+            recipient: self.across_handler,
+            exclusive_relayer: Pubkey::default(),
+            input_token: Pubkey::new_unique(),
+            output_token: self.usdc_mint,
+            input_amount: [0; 32],
+            output_amount,
+            origin_chain_id: 8453,
+            deposit_id: [0; 32],
+            fill_deadline,
+            exclusivity_deadline: 0,
+            message,
+        };
+        Instruction {
+            program_id: mock_across::ID,
+            accounts: mock_across::accounts::FillRelay {
+                relayer: self.payer(),
+                relayer_token_account: get_associated_token_address(&self.payer(), &self.usdc_mint),
+                recipient_token_account: self.handler_usdc_account(),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: mock_across::instruction::FillRelay {
+                relay_hash: [7; 32],
+                relay_data: Some(relay_data),
+            }
+            .data(),
+        }
+    }
+
+    /// Wraps a program instruction so the mock Across handler PDA signs it
+    pub fn as_mock_handler(&self, instruction: Instruction) -> Instruction {
+        let mut accounts = mock_across::accounts::ExecuteAsHandler {
+            handler: self.across_handler,
+            target_program: instruction.program_id,
+        }
+        .to_account_metas(None);
+        accounts.extend(instruction.accounts.into_iter().map(|meta| AccountMeta {
+            is_signer: meta.is_signer && meta.pubkey != self.across_handler,
+            ..meta
+        }));
+        Instruction {
+            program_id: mock_across::ID,
+            accounts,
+            data: mock_across::instruction::ExecuteAsHandler { data: instruction.data }.data(),
+        }
+    }
+
+    async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        let instructions = [
+            system_instruction::create_account(
+                &self.payer(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.mint_authority.pubkey(),
+                Some(&self.mint_authority.pubkey()),
+                decimals,
+            )
+            .expect("initialize mint"),
+        ];
+        self.process(&instructions, &[&mint]).await.expect("create mint");
+        mint.pubkey()
+    }
+
+    pub async fn create_ata(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let create = create_associated_token_account_idempotent(&self.payer(), owner, mint, &spl_token::ID);
+        self.process(&[create], &[]).await.expect("create ATA");
+        get_associated_token_address(owner, mint)
+    }
+
+    /// Funds the mock pool with `lamports` of WSOL, for routes paying out native SOL
+    pub async fn fund_wsol_pool(&mut self, lamports: u64) {
+        let native_mint = spl_token::native_mint::ID;
+        let pool = self.pool_authority;
+        let pool_wsol = self.create_ata(&pool, &native_mint).await;
+        let fund = [
+            system_instruction::transfer(&self.payer(), &pool_wsol, lamports),
+            spl_token::instruction::sync_native(&spl_token::ID, &pool_wsol).expect("sync native"),
+        ];
+        self.process(&fund, &[]).await.expect("fund WSOL pool");
+    }
+
+    /// Creates a Token-2022 mint charging `fee_bps` of every transfer, with no cap on the fee
+    pub async fn create_transfer_fee_mint(&mut self, decimals: u8, fee_bps: u16) -> Pubkey {
+        let mint = Keypair::new();
+        let authority = self.mint_authority.pubkey();
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .expect("mint length");
+        let instructions = [
+            system_instruction::create_account(
+                &self.payer(),
+                &mint.pubkey(),
+                rent.minimum_balance(len),
+                len as u64,
+                &spl_token_2022::ID,
+            ),
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                &spl_token_2022::ID,
+                &mint.pubkey(),
+                Some(&authority),
+                Some(&authority),
+                fee_bps,
+                u64::MAX,
+            )
+            .expect("initialize transfer fee"),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::ID,
+                &mint.pubkey(),
+                &authority,
+                None,
+                decimals,
+            )
+            .expect("initialize mint"),
+        ];
+        self.process(&instructions, &[&mint]).await.expect("create transfer fee mint");
+        mint.pubkey()
+    }
+
+    /// Funds the mock pool with `amount` of Token-2022 `mint`, for routes paying it out
+    pub async fn fund_token_2022_pool(&mut self, mint: &Pubkey, amount: u64) {
+        let pool = self.pool_authority;
+        let create = create_associated_token_account_idempotent(&self.payer(), &pool, mint, &spl_token_2022::ID);
+        let pool_output = get_associated_token_address_with_program_id(&pool, mint, &spl_token_2022::ID);
+        let mint_to = spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            mint,
+            &pool_output,
+            &self.mint_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .expect("mint to");
+        let authority = self.mint_authority.insecure_clone();
+        self.process(&[create, mint_to], &[&authority]).await.expect("fund Token-2022 pool");
+    }
+
+    /// Freezes a token account of one of the test mints
+    pub async fn freeze(&mut self, account: &Pubkey, mint: &Pubkey) {
+        let freeze = spl_token::instruction::freeze_account(
+            &spl_token::ID,
+            account,
+            mint,
+            &self.mint_authority.pubkey(),
+            &[],
+        )
+        .expect("freeze account");
+        let authority = self.mint_authority.insecure_clone();
+        self.process(&[freeze], &[&authority]).await.expect("freeze");
+    }
+
+    pub async fn mint_to(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+        let mint_to = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            mint,
+            account,
+            &self.mint_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .expect("mint to");
+        let authority = self.mint_authority.insecure_clone();
+        self.process(&[mint_to], &[&authority]).await.expect("mint");
+    }
+}
+
+/// Compiles a mock swap instruction as the quote API would return it
+pub fn quoted_route(swap: &MockSwap, instruction: Instruction) -> JupiterRoute {
+    JupiterRoute::from_instruction(
+        instruction,
+        &swap.authority,
+        Vec::new(),
+        swap.quoted_out_amount,
+        swap.min_out_amount(),
+    )
+}
+
+/// The program error code a failed transaction ended with
+pub fn custom_error(result: Result<(), BanksClientError>) -> Option<u32> {
+    let err = match result.err()? {
+        BanksClientError::TransactionError(err) => err,
+        BanksClientError::SimulationError { err, .. } => err,
+        _ => return None,
+    };
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
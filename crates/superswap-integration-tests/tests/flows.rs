@@ -0,0 +1,1968 @@
+//! Inbound flows end to end: bridged USDC in, swap through (mock) Jupiter,
+//! output delivered, and failures leaving funds where they were
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022;
+use common::{custom_error, quoted_route, TestEnv, FEE_BPS};
+use mock_jupiter::client::MockSwap;
+use mock_jupiter::MockJupiterError;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
+use superswap_pda::{
+    escrow_token_account, find_basket_plan, find_config, find_dca_order, find_escrow, find_instance_config,
+    find_instance_recovery_guard, find_insurance_claim, find_insurance_vault, find_order_archive, find_order_extension,
+    find_recipient_orders, find_rent_vault, find_route_staging, find_swap_order, find_volume_tracker,
+    insurance_usdc_account, program_usdc_account,
+};
+use superswap_quote::JupiterRoute;
+use superswap_sdk::{FillAccounts, OrderCreationAccounts, OrderRef};
+use superswap_sol::error::SuperSwapError;
+use superswap_sol::state::{
+    BasketLegParams, BasketPlan, Config, CreateBasketPlanParams, CreateDcaOrderParams, CreateOrderExtensionParams,
+    DcaOrder, ExecuteBasketLegParams, ExecutePostSwapActionParams, GasTopUp, InsuranceClaim, InsuranceClaimParams,
+    InsuranceClaimStatus, MintConfigParams, OrderArchive, OrderStatus, OrderTombstone, PostSwapAction,
+    ProcessBridgeAndSwapParams, RegisterRouteTemplateParams, RouteStaging, SurplusPolicy, SwapOrder, TemplateAccount,
+    UpdateConfigParams, VolumeTracker,
+};
+
+const BRIDGED_USDC: u64 = 1_000_000;
+
+fn fee(amount: u64) -> u64 {
+    amount * FEE_BPS as u64 / 10_000
+}
+
+fn fill_accounts(env: &TestEnv) -> FillAccounts {
+    FillAccounts {
+        across_handler: env.handler.pubkey(),
+        payer: env.payer(),
+        source_usdc_account: env.handler_usdc_account(),
+        usdc_mint: env.usdc_mint,
+        fee_recipient: env.fee_recipient,
+        jupiter_program: mock_jupiter::ID,
+        recipient_preferences: None,
+        recipient_program: None,
+        fallback_output_mint: None,
+        staged_route: false,
+        route_template: false,
+        surplus_vault: false,
+        payer_surplus_account: false,
+        fee_recipient_surplus_account: false,
+        token_2022_destination: false,
+        global_volume_tracker: false,
+        mint_volume_tracker: false,
+    }
+}
+
+fn fill_params(env: &TestEnv, order_id: u64, recipient: Pubkey, deadline: i64) -> ProcessBridgeAndSwapParams {
+    ProcessBridgeAndSwapParams {
+        order_id,
+        recipient,
+        usdc_amount: BRIDGED_USDC,
+        min_output_amount: 0,
+        destination_mint: env.output_mint,
+        deadline,
+        jupiter_swap_data: Vec::new(),
+        post_swap_hook: None,
+        gas_top_up: None,
+        donation: None,
+        relayer_tip: 0,
+        allow_off_curve_recipient: false,
+        refund_failed_swap: false,
+        gas_drop_lamports: 0,
+    }
+}
+
+/// Mock route swapping a fill's USDC from the program's vault into the recipient's ATA, quoted at `output`
+fn fill_route(env: &TestEnv, recipient: &Pubkey, output: u64) -> JupiterRoute {
+    let swap = env.mock_swap(
+        &find_config(&env.program_id).0,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &get_associated_token_address(recipient, &env.output_mint),
+        BRIDGED_USDC - fee(BRIDGED_USDC),
+        output,
+    );
+    quoted_route(&swap, swap.route())
+}
+
+/// Creates a single-tranche DCA order and returns its handle
+async fn create_dca_order(env: &mut TestEnv, order_id: u64, min_output: u64) -> OrderRef {
+    create_dca_order_for(env, order_id, Keypair::new().pubkey(), min_output).await
+}
+
+/// Creates a single-tranche DCA order delivering to `recipient` and returns its handle
+async fn create_dca_order_for(env: &mut TestEnv, order_id: u64, recipient: Pubkey, min_output: u64) -> OrderRef {
+    let order = OrderRef {
+        order_id,
+        recipient,
+        destination_mint: env.output_mint,
+    };
+    let create = superswap_sdk::create_dca_order(
+        &env.program_id,
+        &OrderCreationAccounts {
+            across_handler: env.handler.pubkey(),
+            payer: env.payer(),
+            source_usdc_account: env.handler_usdc_account(),
+            usdc_mint: env.usdc_mint,
+            fee_recipient: env.fee_recipient,
+        },
+        CreateDcaOrderParams {
+            order_id,
+            recipient: order.recipient,
+            usdc_amount: BRIDGED_USDC,
+            destination_mint: env.output_mint,
+            tranche_count: 1,
+            interval_seconds: 0,
+            min_output_per_tranche: min_output,
+        },
+    );
+    let handler = env.handler.insecure_clone();
+    env.process(&[create], &[&handler]).await.expect("create DCA order");
+    order
+}
+
+/// Files an insurance claim on a fresh DCA order that cannot be refunded: its
+/// schedule is past the order TTL and its recipient's USDC account is frozen.
+/// Returns the order and the account the claim pays out to
+async fn file_insurance_claim(env: &mut TestEnv, order_id: u64) -> (OrderRef, Pubkey) {
+    const ORDER_TTL_SECONDS: u32 = 60;
+
+    let admin = env.payer();
+    let usdc_mint = env.usdc_mint;
+    let recipient = Keypair::new();
+    let enable = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_order_ttl_seconds: Some(ORDER_TTL_SECONDS),
+            new_max_insurance_claim_usdc: Some(BRIDGED_USDC),
+            ..UpdateConfigParams::default()
+        },
+    );
+    let fund = system_instruction::transfer(&admin, &recipient.pubkey(), 1_000_000_000);
+    env.process(&[enable, fund], &[]).await.expect("enable insurance claims");
+    let recipient_usdc = env.create_ata(&recipient.pubkey(), &usdc_mint).await;
+    env.freeze(&recipient_usdc, &usdc_mint).await;
+
+    let order = create_dca_order_for(env, order_id, recipient.pubkey(), 500).await;
+    env.advance_clock(ORDER_TTL_SECONDS as i64).await;
+    let payout = env.create_ata(&Pubkey::new_unique(), &usdc_mint).await;
+    let file = superswap_sdk::file_insurance_claim(&env.program_id, &order, &usdc_mint, &payout);
+    env.process(&[file], &[&recipient]).await.expect("file insurance claim");
+    (order, payout)
+}
+
+/// Mock swap of the order's next tranche, quoted at `output`
+fn tranche_swap(env: &TestEnv, order: &OrderRef, output: u64) -> MockSwap {
+    let swap_order = find_swap_order(&env.program_id, order.order_id).0;
+    let escrow = find_escrow(&env.program_id, &swap_order).0;
+    env.mock_swap(
+        &escrow,
+        &get_associated_token_address(&escrow, &env.usdc_mint),
+        &get_associated_token_address(&order.recipient, &order.destination_mint),
+        BRIDGED_USDC - fee(BRIDGED_USDC),
+        output,
+    )
+}
+
+/// Executes the order's next tranche through `route`
+async fn execute_route(env: &mut TestEnv, order: &OrderRef, route: JupiterRoute) -> Result<(), BanksClientError> {
+    let execute = superswap_sdk::execute_dca_tranche(
+        &env.program_id,
+        order,
+        &env.usdc_mint,
+        &mock_jupiter::ID,
+        &env.payer(),
+        route.swap_data,
+        &route.accounts,
+    );
+    env.process(&[execute], &[]).await
+}
+
+/// Executes the order's next tranche through a mock `route` quoted at `output`
+async fn execute_tranche(env: &mut TestEnv, order: &OrderRef, output: u64) -> Result<(), BanksClientError> {
+    let swap = tranche_swap(env, order, output);
+    execute_route(env, order, quoted_route(&swap, swap.route())).await
+}
+
+/// Asserts the order's tranche never ran: all of its USDC is still in escrow
+async fn assert_escrowed(env: &mut TestEnv, order: &OrderRef) {
+    let swap_order_key = find_swap_order(&env.program_id, order.order_id).0;
+    let escrow = find_escrow(&env.program_id, &swap_order_key).0;
+    let scheduled = BRIDGED_USDC - fee(BRIDGED_USDC);
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&escrow, &env.usdc_mint)).await,
+        Some(scheduled)
+    );
+    let dca_order: DcaOrder = env
+        .anchor_account(&find_dca_order(&env.program_id, &swap_order_key).0)
+        .await
+        .expect("DCA order account");
+    assert_eq!(dca_order.remaining_amount, scheduled);
+    assert!(dca_order.fills.is_empty());
+}
+
+#[tokio::test]
+async fn inbound_fill_takes_fee_and_completes_order() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    let route = fill_route(&env, &recipient, 777);
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            min_output_amount: 500,
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, 1, recipient, deadline)
+        },
+        &route.accounts,
+    );
+    let handler = env.handler.insecure_clone();
+    env.process(&[fill], &[&handler]).await.expect("fill");
+
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(0));
+    assert_eq!(env.token_balance(&env.fee_recipient_usdc_account()).await, Some(fee(BRIDGED_USDC)));
+    // The recipient's output account is created from the rent vault and receives the swap's output
+    let destination = get_associated_token_address(&recipient, &env.output_mint);
+    assert_eq!(env.token_balance(&destination).await, Some(777));
+
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 1).0)
+        .await
+        .expect("order account");
+    assert_eq!(swap_order.recipient, recipient);
+    assert_eq!(swap_order.usdc_amount, BRIDGED_USDC);
+    assert!(swap_order.status == OrderStatus::Completed);
+}
+
+#[tokio::test]
+async fn inbound_fill_with_route_minimum_below_order_fails_and_leaves_order_unfilled() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    let route = fill_route(&env, &recipient, 499);
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            min_output_amount: 500,
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, 6, recipient, deadline)
+        },
+        &route.accounts,
+    );
+    let handler = env.handler.insecure_clone();
+    let result = env.process(&[fill], &[&handler]).await;
+
+    // The route's own minimum is below the order's, so it is rejected before the swap
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidSwapCalldata)));
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(BRIDGED_USDC));
+    assert!(env
+        .anchor_account::<SwapOrder>(&find_swap_order(&env.program_id, 6).0)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn routes_delivering_anywhere_but_the_recipient_are_rejected() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let handler = env.handler.insecure_clone();
+    // The relayer points the route's output at its own account
+    let relayer_output = get_associated_token_address(&handler.pubkey(), &env.output_mint);
+
+    let swap = env.mock_swap(
+        &find_config(&env.program_id).0,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &relayer_output,
+        BRIDGED_USDC - fee(BRIDGED_USDC),
+        777,
+    );
+    let route = quoted_route(&swap, swap.route());
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, 35, recipient, deadline)
+        },
+        &route.accounts,
+    );
+    let result = env.process(&[fill], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RouteAccountMismatch)));
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(BRIDGED_USDC));
+
+    // A DCA tranche's route is held to the escrow and the recipient the same way
+    let order = create_dca_order(&mut env, 36, 500).await;
+    let swap_order = find_swap_order(&env.program_id, order.order_id).0;
+    let escrow = find_escrow(&env.program_id, &swap_order).0;
+    let swap = env.mock_swap(
+        &escrow,
+        &get_associated_token_address(&escrow, &env.usdc_mint),
+        &relayer_output,
+        BRIDGED_USDC - fee(BRIDGED_USDC),
+        777,
+    );
+    let result = execute_route(&mut env, &order, quoted_route(&swap, swap.route())).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RouteAccountMismatch)));
+    assert_escrowed(&mut env, &order).await;
+}
+
+#[tokio::test]
+async fn off_curve_recipient_is_filled_only_when_the_order_allows_it() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let handler = env.handler.insecure_clone();
+    let deadline = env.now().await + 600;
+    // A PDA has no private key, so output sent to it is lost unless a program owns it
+    let recipient = Pubkey::find_program_address(&[b"vault"], &Pubkey::new_unique()).0;
+
+    let route = fill_route(&env, &recipient, 777);
+    let fill = |allow_off_curve_recipient| {
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(&env),
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data.clone(),
+                allow_off_curve_recipient,
+                ..fill_params(&env, 42, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    };
+    let (rejected, allowed) = (fill(false), fill(true));
+
+    let result = env.process(&[rejected], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::OffCurveRecipient)));
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(BRIDGED_USDC));
+
+    env.process(&[allowed], &[&handler]).await.expect("flagged fill");
+    let destination = get_associated_token_address(&recipient, &env.output_mint);
+    assert_eq!(env.token_balance(&destination).await, Some(777));
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 42).0)
+        .await
+        .expect("order account");
+    assert!(swap_order.status == OrderStatus::Completed);
+}
+
+#[tokio::test]
+async fn staged_route_fills_only_once_finalized() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let payer = env.payer();
+    let route_staging = find_route_staging(&env.program_id, &payer, 38).0;
+
+    let route = fill_route(&env, &recipient, 777);
+    let mut upload = superswap_sdk::upload_route_chunks(&env.program_id, &payer, 38, &route.swap_data);
+    let finalize = upload.pop().expect("finalize_route");
+    env.process(&upload, &[]).await.expect("upload route");
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &FillAccounts {
+            staged_route: true,
+            ..fill_accounts(&env)
+        },
+        fill_params(&env, 38, recipient, deadline),
+        &route.accounts,
+    );
+    let handler = env.handler.insecure_clone();
+
+    // A route still being uploaded cannot be filled from
+    let result = env.process(&[fill.clone()], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RouteNotFinalized)));
+
+    // Once finalized it takes no more chunks
+    env.process(&[finalize], &[]).await.expect("finalize route");
+    env.advance_clock(1).await;
+    let result = env.process(&upload, &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RouteAlreadyFinalized)));
+
+    env.process(&[fill], &[&handler]).await.expect("staged fill");
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.output_mint)).await,
+        Some(777)
+    );
+    // The fill closes the staging account back to the payer
+    assert!(env.anchor_account::<RouteStaging>(&route_staging).await.is_none());
+}
+
+#[tokio::test]
+async fn templated_fill_must_match_the_registered_route() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    // The template pins every route account but the recipient's, which varies by order
+    let template_route = fill_route(&env, &Pubkey::new_unique(), 777);
+    let order_specific = template_route.accounts[3].pubkey;
+    let register = superswap_sdk::register_route_template(
+        &env.program_id,
+        &env.payer(),
+        RegisterRouteTemplateParams {
+            input_mint: env.usdc_mint,
+            output_mint: env.output_mint,
+            discriminator: mock_jupiter::ROUTE_DISCRIMINATOR,
+            accounts: template_route
+                .accounts
+                .iter()
+                .map(|meta| TemplateAccount {
+                    pubkey: if meta.pubkey == order_specific { Pubkey::default() } else { meta.pubkey },
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+        },
+    );
+    env.process(&[register], &[]).await.expect("register route template");
+
+    let accounts = FillAccounts {
+        route_template: true,
+        ..fill_accounts(&env)
+    };
+    let templated_fill = |route: JupiterRoute| {
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &accounts,
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(&env, 39, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    };
+    // The same swap through another Jupiter instruction does not match
+    let swap = env.mock_swap(
+        &find_config(&env.program_id).0,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &get_associated_token_address(&recipient, &env.output_mint),
+        BRIDGED_USDC - fee(BRIDGED_USDC),
+        777,
+    );
+    let mismatched_fill = templated_fill(quoted_route(&swap, swap.shared_accounts_route()));
+    let fill = templated_fill(fill_route(&env, &recipient, 777));
+    let handler = env.handler.insecure_clone();
+
+    let result = env.process(&[mismatched_fill], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RouteTemplateMismatch)));
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(BRIDGED_USDC));
+
+    env.process(&[fill], &[&handler]).await.expect("templated fill");
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.output_mint)).await,
+        Some(777)
+    );
+}
+
+#[tokio::test]
+async fn relayer_tip_is_paid_to_the_payer_out_of_the_swapped_usdc() {
+    const MAX_TIP: u64 = 5_000;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let (admin, usdc_mint) = (env.payer(), env.usdc_mint);
+    let set_cap = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_max_relayer_tip_usdc: Some(MAX_TIP),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[set_cap], &[]).await.expect("set relayer tip cap");
+    let relayer_usdc = env.create_ata(&admin, &usdc_mint).await;
+
+    // The tip leaves the program's vault before the swap, so the route swaps what remains
+    let swap = env.mock_swap(
+        &find_config(&env.program_id).0,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &get_associated_token_address(&recipient, &env.output_mint),
+        BRIDGED_USDC - fee(BRIDGED_USDC) - MAX_TIP,
+        777,
+    );
+    let route = quoted_route(&swap, swap.route());
+    let [over_cap_fill, fill] = [MAX_TIP + 1, MAX_TIP].map(|relayer_tip| {
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(&env),
+            ProcessBridgeAndSwapParams {
+                relayer_tip,
+                jupiter_swap_data: route.swap_data.clone(),
+                ..fill_params(&env, 40, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    });
+    let handler = env.handler.insecure_clone();
+
+    let result = env.process(&[over_cap_fill], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidRelayerTip)));
+    assert_eq!(env.token_balance(&relayer_usdc).await, Some(0));
+
+    env.process(&[fill], &[&handler]).await.expect("tipped fill");
+    assert_eq!(env.token_balance(&relayer_usdc).await, Some(MAX_TIP));
+    assert_eq!(env.token_balance(&env.fee_recipient_usdc_account()).await, Some(fee(BRIDGED_USDC)));
+    assert_eq!(
+        env.token_balance(&program_usdc_account(&env.program_id, &env.usdc_mint)).await,
+        Some(0)
+    );
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.output_mint)).await,
+        Some(777)
+    );
+}
+
+#[tokio::test]
+async fn refund_fill_after_failed_swap_returns_usdc_to_recipient() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            refund_failed_swap: true,
+            ..fill_params(&env, 7, recipient, deadline)
+        },
+        &[],
+    );
+    let handler = env.handler.insecure_clone();
+    env.process(&[fill], &[&handler]).await.expect("refund fill");
+
+    // Nothing is swapped and no fee is taken
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.usdc_mint)).await,
+        Some(BRIDGED_USDC)
+    );
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 7).0)
+        .await
+        .expect("order account");
+    assert!(swap_order.status == OrderStatus::Refunded);
+}
+
+#[tokio::test]
+async fn usdc_order_passes_through_without_a_swap() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            destination_mint: env.usdc_mint,
+            ..fill_params(&env, 8, recipient, deadline)
+        },
+        &[],
+    );
+    let handler = env.handler.insecure_clone();
+    env.process(&[fill], &[&handler]).await.expect("passthrough fill");
+
+    assert_eq!(env.token_balance(&env.fee_recipient_usdc_account()).await, Some(fee(BRIDGED_USDC)));
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.usdc_mint)).await,
+        Some(BRIDGED_USDC - fee(BRIDGED_USDC))
+    );
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 8).0)
+        .await
+        .expect("order account");
+    assert!(swap_order.status == OrderStatus::Completed);
+}
+
+#[tokio::test]
+async fn token_2022_output_is_checked_net_of_its_transfer_fee() {
+    const TRANSFER_FEE_BPS: u16 = 100;
+    const QUOTED_OUTPUT: u64 = 1_000_000;
+    const TRANSFER_FEE: u64 = QUOTED_OUTPUT * TRANSFER_FEE_BPS as u64 / 10_000;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let output_mint = env.create_transfer_fee_mint(9, TRANSFER_FEE_BPS).await;
+    env.fund_token_2022_pool(&output_mint, QUOTED_OUTPUT).await;
+    let pool_output =
+        get_associated_token_address_with_program_id(&env.pool_authority, &output_mint, &spl_token_2022::ID);
+    let recipient_output = get_associated_token_address_with_program_id(&recipient, &output_mint, &spl_token_2022::ID);
+
+    let swap = MockSwap {
+        output_mint,
+        output_token_program: spl_token_2022::ID,
+        ..env.mock_swap(
+            &find_config(&env.program_id).0,
+            &program_usdc_account(&env.program_id, &env.usdc_mint),
+            &recipient_output,
+            BRIDGED_USDC - fee(BRIDGED_USDC),
+            QUOTED_OUTPUT,
+        )
+    };
+    let route = quoted_route(&swap, swap.route());
+    let accounts = FillAccounts {
+        token_2022_destination: true,
+        ..fill_accounts(&env)
+    };
+    // The route's own minimum clears the quote's slippage, but not the transfer fee
+    let [strict_fill, fill] = [swap.min_out_amount(), QUOTED_OUTPUT - TRANSFER_FEE].map(|min_output_amount| {
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &accounts,
+            ProcessBridgeAndSwapParams {
+                min_output_amount,
+                destination_mint: output_mint,
+                jupiter_swap_data: route.swap_data.clone(),
+                ..fill_params(&env, 37, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    });
+    let handler = env.handler.insecure_clone();
+
+    let result = env.process(&[strict_fill], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InsufficientOutputAmount)));
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(BRIDGED_USDC));
+
+    env.process(&[fill], &[&handler]).await.expect("Token-2022 fill");
+
+    // The pool paid the whole quote; the recipient keeps it net of the fee the mint withholds
+    assert_eq!(env.token_2022_balance(&pool_output).await, Some((0, 0)));
+    assert_eq!(
+        env.token_2022_balance(&recipient_output).await,
+        Some((QUOTED_OUTPUT - TRANSFER_FEE, TRANSFER_FEE))
+    );
+    // The protocol fee is taken in USDC, untouched by the output's transfer fee
+    assert_eq!(env.token_balance(&env.fee_recipient_usdc_account()).await, Some(fee(BRIDGED_USDC)));
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 37).0)
+        .await
+        .expect("order account");
+    assert!(swap_order.status == OrderStatus::Completed);
+    assert_eq!(swap_order.surplus, 0);
+}
+
+#[tokio::test]
+async fn expired_fill_refunds_usdc_to_recipient() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let deadline = env.now().await - 1;
+    let recipient = Keypair::new().pubkey();
+
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        fill_params(&env, 2, recipient, deadline),
+        &[],
+    );
+    let handler = env.handler.insecure_clone();
+    env.process(&[fill], &[&handler]).await.expect("late fill");
+
+    // The whole bridged amount is refunded, fee included
+    assert_eq!(env.token_balance(&env.handler_usdc_account()).await, Some(0));
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.usdc_mint)).await,
+        Some(BRIDGED_USDC)
+    );
+    let swap_order: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 2).0)
+        .await
+        .expect("order account");
+    assert!(swap_order.status == OrderStatus::Refunded);
+}
+
+#[tokio::test]
+async fn dca_tranche_swaps_through_jupiter_and_delivers() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 3, 500).await;
+
+    execute_tranche(&mut env, &order, 777).await.expect("tranche");
+
+    let swap_order_key = find_swap_order(&env.program_id, order.order_id).0;
+    let recipient_output = get_associated_token_address(&order.recipient, &order.destination_mint);
+    assert_eq!(env.token_balance(&recipient_output).await, Some(777));
+    assert_eq!(env.token_balance(&env.fee_recipient_usdc_account()).await, Some(fee(BRIDGED_USDC)));
+
+    // Completing the schedule closes the escrow's USDC account
+    let escrow = find_escrow(&env.program_id, &swap_order_key).0;
+    assert_eq!(env.token_balance(&get_associated_token_address(&escrow, &env.usdc_mint)).await, None);
+    let swap_order: SwapOrder = env.anchor_account(&swap_order_key).await.expect("order account");
+    assert!(swap_order.status == OrderStatus::Completed);
+}
+
+#[tokio::test]
+async fn dca_tranche_below_minimum_fails_and_keeps_usdc_escrowed() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 4, 500).await;
+
+    let result = execute_tranche(&mut env, &order, 499).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InsufficientOutputAmount)));
+    assert_escrowed(&mut env, &order).await;
+}
+
+#[tokio::test]
+async fn dca_tranche_swaps_through_shared_accounts_route() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 5, 500).await;
+
+    let swap = tranche_swap(&env, &order, 777);
+    execute_route(&mut env, &order, quoted_route(&swap, swap.shared_accounts_route()))
+        .await
+        .expect("tranche");
+
+    let recipient_output = get_associated_token_address(&order.recipient, &order.destination_mint);
+    assert_eq!(env.token_balance(&recipient_output).await, Some(777));
+}
+
+#[tokio::test]
+async fn dca_tranche_shorted_within_slippage_still_meets_program_minimum() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 6, 500).await;
+    // 0.2% short: within the route's slippage, so Jupiter pays and the program checks
+    env.configure_mock_jupiter(9_980, false).await;
+
+    let result = execute_tranche(&mut env, &order, 500).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InsufficientOutputAmount)));
+    assert_escrowed(&mut env, &order).await;
+
+    execute_tranche(&mut env, &order, 1_000).await.expect("tranche");
+    let recipient_output = get_associated_token_address(&order.recipient, &order.destination_mint);
+    assert_eq!(env.token_balance(&recipient_output).await, Some(998));
+}
+
+#[tokio::test]
+async fn dca_tranche_shorted_beyond_slippage_reverts_in_jupiter() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 7, 500).await;
+    env.configure_mock_jupiter(9_900, false).await;
+
+    let result = execute_tranche(&mut env, &order, 1_000).await;
+    assert_eq!(
+        custom_error(result),
+        Some(u32::from(MockJupiterError::SlippageToleranceExceeded))
+    );
+    assert_escrowed(&mut env, &order).await;
+}
+
+#[tokio::test]
+async fn dca_tranche_keeps_usdc_escrowed_when_jupiter_fails() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 8, 500).await;
+    env.configure_mock_jupiter(10_000, true).await;
+
+    let result = execute_tranche(&mut env, &order, 777).await;
+    assert_eq!(custom_error(result), Some(u32::from(MockJupiterError::InjectedFailure)));
+    assert_escrowed(&mut env, &order).await;
+}
+
+#[tokio::test]
+async fn disabled_mint_holds_tranches_until_reenabled() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let order = create_dca_order(&mut env, 9, 500).await;
+    let output_mint = env.output_mint;
+    env.set_mint_disabled(&output_mint, true).await;
+
+    let result = execute_tranche(&mut env, &order, 777).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::DestinationMintDisabled)));
+    assert_escrowed(&mut env, &order).await;
+
+    env.set_mint_disabled(&output_mint, false).await;
+    execute_tranche(&mut env, &order, 777).await.expect("tranche");
+    let recipient_output = get_associated_token_address(&order.recipient, &order.destination_mint);
+    assert_eq!(env.token_balance(&recipient_output).await, Some(777));
+}
+
+#[tokio::test]
+async fn stale_dca_order_is_expired_by_any_keeper_for_the_bounty() {
+    const ORDER_TTL_SECONDS: u32 = 60;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let admin = env.payer();
+    let usdc_mint = env.usdc_mint;
+    let set_ttl = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_order_ttl_seconds: Some(ORDER_TTL_SECONDS),
+            new_refund_bounty_bps: Some(Config::MAX_REFUND_BOUNTY_BPS),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[set_ttl], &[]).await.expect("set order TTL");
+    let order = create_dca_order(&mut env, 34, 500).await;
+    let keeper = Keypair::new();
+    let keeper_usdc = env.create_ata(&keeper.pubkey(), &usdc_mint).await;
+    let keeper_key = keeper.pubkey();
+    let expire = |env: &TestEnv| {
+        superswap_sdk::expire_and_refund(&env.program_id, &order, &usdc_mint, &keeper_key, &admin, Some(keeper_usdc))
+    };
+
+    let attempt = expire(&env);
+    let result = env.process(&[attempt], &[&keeper]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::OrderNotExpired)));
+    assert_escrowed(&mut env, &order).await;
+
+    env.advance_clock(ORDER_TTL_SECONDS as i64).await;
+    let attempt = expire(&env);
+    env.process(&[attempt], &[&keeper]).await.expect("expire order");
+
+    let scheduled = BRIDGED_USDC - fee(BRIDGED_USDC);
+    let bounty = scheduled * Config::MAX_REFUND_BOUNTY_BPS as u64 / 10_000;
+    let recipient_usdc = get_associated_token_address(&order.recipient, &usdc_mint);
+    assert_eq!(env.token_balance(&recipient_usdc).await, Some(scheduled - bounty));
+    assert_eq!(env.token_balance(&keeper_usdc).await, Some(bounty));
+    let swap_order_key = find_swap_order(&env.program_id, order.order_id).0;
+    let escrow = find_escrow(&env.program_id, &swap_order_key).0;
+    assert_eq!(env.token_balance(&get_associated_token_address(&escrow, &usdc_mint)).await, None);
+    let swap_order: SwapOrder = env.anchor_account(&swap_order_key).await.expect("swap order");
+    assert_eq!(swap_order.status, OrderStatus::Expired);
+}
+
+#[tokio::test]
+async fn mint_config_overrides_fee_and_bounds_slippage_and_order_size() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let mint_fee = BRIDGED_USDC * 50 / 10_000;
+    let mint_config = MintConfigParams {
+        mint: env.output_mint,
+        enabled: true,
+        fee_bps: Some(50),
+        max_order_usdc: BRIDGED_USDC - 1,
+        max_slippage_bps: 10,
+        daily_volume_cap: 0,
+    };
+    let create = superswap_sdk::create_mint_config(&env.program_id, &admin, mint_config.clone());
+    env.process(&[create], &[]).await.expect("create mint config");
+
+    let swap = env.mock_swap(
+        &find_config(&env.program_id).0,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &get_associated_token_address(&recipient, &env.output_mint),
+        BRIDGED_USDC - mint_fee,
+        1_000,
+    );
+    let fill = |env: &TestEnv| {
+        let route = quoted_route(&swap, swap.route());
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(env),
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(env, 12, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    };
+
+    let attempt = fill(&env);
+    let result = env.process(&[attempt], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::OrderAboveMaximum)));
+
+    let raise_cap = superswap_sdk::update_mint_config(
+        &env.program_id,
+        &admin,
+        MintConfigParams { max_order_usdc: 0, ..mint_config },
+    );
+    env.process(&[raise_cap], &[]).await.expect("update mint config");
+
+    // 0.2% short of the quote: within the route's slippage but past the mint's bound
+    env.configure_mock_jupiter(9_980, false).await;
+    let attempt = fill(&env);
+    let result = env.process(&[attempt], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::PriceImpactExceeded)));
+
+    env.configure_mock_jupiter(10_000, false).await;
+    let attempt = fill(&env);
+    env.process(&[attempt], &[&handler]).await.expect("fill");
+    assert_eq!(env.token_balance(&env.fee_recipient_usdc_account()).await, Some(mint_fee));
+
+    let delete = superswap_sdk::delete_mint_config(&env.program_id, &admin, &env.output_mint);
+    env.process(&[delete], &[]).await.expect("delete mint config");
+}
+
+#[tokio::test]
+async fn config_price_impact_bound_applies_to_fills_without_a_mint_config() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let set_bound = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_max_price_impact_bps: Some(10),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[set_bound], &[]).await.expect("set price impact bound");
+
+    let fill = |env: &TestEnv| {
+        let route = fill_route(env, &recipient, 1_000);
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(env),
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(env, 33, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    };
+
+    // 0.2% short of the quote: within the route's slippage but past the config's bound
+    env.configure_mock_jupiter(9_980, false).await;
+    let attempt = fill(&env);
+    let result = env.process(&[attempt], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::PriceImpactExceeded)));
+
+    env.configure_mock_jupiter(10_000, false).await;
+    let attempt = fill(&env);
+    env.process(&[attempt], &[&handler]).await.expect("fill");
+    let recipient_output = get_associated_token_address(&recipient, &env.output_mint);
+    assert_eq!(env.token_balance(&recipient_output).await, Some(1_000));
+}
+
+#[tokio::test]
+async fn daily_volume_cap_rejects_fills_past_the_days_volume() {
+    let mut env = TestEnv::new(2 * BRIDGED_USDC).await;
+    let deadline = env.now().await + 600;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+
+    let create = superswap_sdk::create_volume_tracker(&env.program_id, &admin, &Pubkey::default());
+    let set_cap = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_daily_volume_cap: Some(BRIDGED_USDC + BRIDGED_USDC / 2),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[create, set_cap], &[]).await.expect("set daily volume cap");
+
+    let accounts = FillAccounts {
+        global_volume_tracker: true,
+        ..fill_accounts(&env)
+    };
+    // Without its tracker the cap cannot be checked, so the fill is rejected
+    let untracked = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        fill_params(&env, 13, Keypair::new().pubkey(), deadline),
+        &[],
+    );
+    let result = env.process(&[untracked], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::MissingVolumeTracker)));
+
+    for (order_id, expected) in [(13, None), (14, Some(SuperSwapError::DailyVolumeCapExceeded))] {
+        let recipient = Keypair::new().pubkey();
+        let route = fill_route(&env, &recipient, 777);
+        let fill = superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &accounts,
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(&env, order_id, recipient, deadline)
+            },
+            &route.accounts,
+        );
+        let result = env.process(&[fill], &[&handler]).await;
+        assert_eq!(custom_error(result), expected.map(u32::from));
+    }
+
+    let tracker: VolumeTracker = env
+        .anchor_account(&find_volume_tracker(&env.program_id, &Pubkey::default()).0)
+        .await
+        .expect("volume tracker");
+    assert_eq!(tracker.volume, BRIDGED_USDC);
+}
+
+#[tokio::test]
+async fn order_size_bounds_reject_fills_but_still_refund_late_orders() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+
+    // Inverted bounds are refused
+    let inverted = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_min_order_usdc: Some(BRIDGED_USDC),
+            new_max_order_usdc: Some(BRIDGED_USDC - 1),
+            ..UpdateConfigParams::default()
+        },
+    );
+    let result = env.process(&[inverted], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidOrderSizeRange)));
+
+    let deadline = env.now().await + 600;
+    for (min, max, expected) in [
+        (BRIDGED_USDC + 1, 0, SuperSwapError::OrderBelowMinimum),
+        (0, BRIDGED_USDC - 1, SuperSwapError::OrderAboveMaximum),
+    ] {
+        let bounds = superswap_sdk::update_config(
+            &env.program_id,
+            &admin,
+            UpdateConfigParams {
+                new_min_order_usdc: Some(min),
+                new_max_order_usdc: Some(max),
+                ..UpdateConfigParams::default()
+            },
+        );
+        env.process(&[bounds], &[]).await.expect("set order size bounds");
+
+        let fill = superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(&env),
+            fill_params(&env, 15, Keypair::new().pubkey(), deadline),
+            &[],
+        );
+        let result = env.process(&[fill], &[&handler]).await;
+        assert_eq!(custom_error(result), Some(u32::from(expected)));
+    }
+
+    // A late oversized order is refunded rather than stranded with the handler
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await - 1;
+    let late = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        fill_params(&env, 15, recipient, deadline),
+        &[],
+    );
+    env.process(&[late], &[&handler]).await.expect("late fill");
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.usdc_mint)).await,
+        Some(BRIDGED_USDC)
+    );
+}
+
+#[tokio::test]
+async fn surplus_policy_sends_the_share_above_the_quote_where_configured() {
+    let mut env = TestEnv::new(3 * BRIDGED_USDC).await;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let config = find_config(&env.program_id).0;
+    let (output_mint, fee_recipient) = (env.output_mint, env.fee_recipient);
+    let surplus_vault = env.create_ata(&config, &output_mint).await;
+    let payer_surplus_account = env.create_ata(&admin, &output_mint).await;
+    let fee_surplus_account = env.create_ata(&fee_recipient, &output_mint).await;
+    let deadline = env.now().await + 600;
+
+    // The pool pays 10% over a quote of 1,000, half of which is the share
+    env.configure_mock_jupiter(11_000, false).await;
+    let swap = env.mock_swap(
+        &config,
+        &program_usdc_account(&env.program_id, &env.usdc_mint),
+        &surplus_vault,
+        BRIDGED_USDC - fee(BRIDGED_USDC),
+        1_000,
+    );
+    let fill = |env: &TestEnv, order_id: u64, recipient: Pubkey, policy: SurplusPolicy, share_account: bool| {
+        // Only a capturing policy stages the output in the vault
+        let captures = policy != SurplusPolicy::Recipient;
+        let route = if captures {
+            quoted_route(&swap, swap.route())
+        } else {
+            fill_route(env, &recipient, 1_000)
+        };
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &FillAccounts {
+                surplus_vault: captures,
+                payer_surplus_account: share_account && policy == SurplusPolicy::PayerRebate,
+                fee_recipient_surplus_account: share_account && policy == SurplusPolicy::Treasury,
+                ..fill_accounts(env)
+            },
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(env, order_id, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    };
+
+    for (order_id, policy, recipient_output, fee_share, rebate) in [
+        (16, SurplusPolicy::Recipient, 1_100, 0, 0),
+        (17, SurplusPolicy::Treasury, 1_050, 50, 0),
+        (18, SurplusPolicy::PayerRebate, 1_050, 0, 50),
+    ] {
+        let set_policy = superswap_sdk::update_config(
+            &env.program_id,
+            &admin,
+            UpdateConfigParams {
+                new_surplus_share_bps: Some(5_000),
+                new_surplus_policy: Some(policy),
+                ..UpdateConfigParams::default()
+            },
+        );
+        env.process(&[set_policy], &[]).await.expect("set surplus policy");
+
+        let recipient = Keypair::new().pubkey();
+        if policy == SurplusPolicy::Recipient {
+            // Nothing is captured, so the vault cannot be slipped into the fill either
+            let route = quoted_route(&swap, swap.route());
+            let attempt = superswap_sdk::process_bridge_and_swap(
+                &env.program_id,
+                &FillAccounts {
+                    surplus_vault: true,
+                    ..fill_accounts(&env)
+                },
+                ProcessBridgeAndSwapParams {
+                    jupiter_swap_data: route.swap_data,
+                    ..fill_params(&env, order_id, recipient, deadline)
+                },
+                &route.accounts,
+            );
+            let result = env.process(&[attempt], &[&handler]).await;
+            assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidInstructionData)));
+        }
+        let missing_share_account = match policy {
+            SurplusPolicy::Recipient => None,
+            SurplusPolicy::Treasury => Some(SuperSwapError::MissingSurplusFeeAccount),
+            SurplusPolicy::PayerRebate => Some(SuperSwapError::MissingSurplusRebateAccount),
+        };
+        if let Some(error) = missing_share_account {
+            // The share has nowhere to go without its account
+            let attempt = fill(&env, order_id, recipient, policy, false);
+            let result = env.process(&[attempt], &[&handler]).await;
+            assert_eq!(custom_error(result), Some(u32::from(error)));
+        }
+
+        let fee_before = env.token_balance(&fee_surplus_account).await.unwrap_or(0);
+        let rebate_before = env.token_balance(&payer_surplus_account).await.unwrap_or(0);
+        let attempt = fill(&env, order_id, recipient, policy, true);
+        env.process(&[attempt], &[&handler]).await.expect("fill");
+
+        assert_eq!(
+            env.token_balance(&get_associated_token_address(&recipient, &env.output_mint)).await,
+            Some(recipient_output)
+        );
+        // The vault only stages the output; the share leaves it with the fill
+        assert_eq!(env.token_balance(&surplus_vault).await, Some(0));
+        assert_eq!(env.token_balance(&fee_surplus_account).await, Some(fee_before + fee_share));
+        assert_eq!(env.token_balance(&payer_surplus_account).await, Some(rebate_before + rebate));
+        let swap_order: SwapOrder = env
+            .anchor_account(&find_swap_order(&env.program_id, order_id).0)
+            .await
+            .expect("order account");
+        assert_eq!(swap_order.surplus, 100);
+    }
+
+    // While the config captures surplus, a route cannot skip the vault
+    let recipient = Keypair::new().pubkey();
+    let route = fill_route(&env, &recipient, 1_000);
+    let attempt = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, 31, recipient, deadline)
+        },
+        &route.accounts,
+    );
+    let result = env.process(&[attempt], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::MissingSurplusVault)));
+}
+
+#[tokio::test]
+async fn refunds_only_mode_still_refunds_late_and_failed_fills() {
+    let mut env = TestEnv::new(3 * BRIDGED_USDC).await;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let enter = superswap_sdk::set_refunds_only(&env.program_id, &admin, true);
+    env.process(&[enter], &[]).await.expect("enter refunds-only mode");
+
+    let now = env.now().await;
+    let deadline = now + 600;
+    let swap = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        fill_params(&env, 19, Keypair::new().pubkey(), deadline),
+        &[],
+    );
+    let result = env.process(&[swap], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RefundsOnly)));
+
+    let late_recipient = Keypair::new().pubkey();
+    let failed_recipient = Keypair::new().pubkey();
+    let late = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        fill_params(&env, 20, late_recipient, now - 1),
+        &[],
+    );
+    let refund = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            refund_failed_swap: true,
+            ..fill_params(&env, 21, failed_recipient, deadline)
+        },
+        &[],
+    );
+    env.process(&[late, refund], &[&handler]).await.expect("refunds");
+
+    for (order_id, recipient) in [(20, late_recipient), (21, failed_recipient)] {
+        assert_eq!(
+            env.token_balance(&get_associated_token_address(&recipient, &env.usdc_mint)).await,
+            Some(BRIDGED_USDC)
+        );
+        let swap_order: SwapOrder = env
+            .anchor_account(&find_swap_order(&env.program_id, order_id).0)
+            .await
+            .expect("order account");
+        assert!(swap_order.status == OrderStatus::Refunded);
+    }
+}
+
+#[tokio::test]
+async fn escrow_shortfall_trips_the_solvency_breaker_but_leaves_refunds_open() {
+    const TOLERANCE: u64 = 10;
+
+    let mut env = TestEnv::new(2 * BRIDGED_USDC).await;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let tolerate = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_escrow_shortfall_tolerance: Some(TOLERANCE),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[tolerate], &[]).await.expect("set shortfall tolerance");
+
+    // Something outside the program's accounting takes USDC from the escrow
+    let order = create_dca_order(&mut env, 44, 500).await;
+    let escrow = find_escrow(&env.program_id, &find_swap_order(&env.program_id, 44).0).0;
+    let escrow_usdc = get_associated_token_address(&escrow, &env.usdc_mint);
+    let left = BRIDGED_USDC - fee(BRIDGED_USDC) - TOLERANCE - 1;
+    env.set_token_balance(&escrow_usdc, left).await;
+
+    // The tranche succeeds without swapping so the switch to refunds-only sticks
+    execute_tranche(&mut env, &order, 777).await.expect("tranche trips the breaker");
+    let config: Config = env.anchor_account(&find_config(&env.program_id).0).await.expect("config");
+    assert!(config.refunds_only);
+    assert_eq!(env.token_balance(&escrow_usdc).await, Some(left));
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&order.recipient, &env.output_mint)).await,
+        None
+    );
+
+    let deadline = env.now().await + 600;
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        fill_params(&env, 45, Keypair::new().pubkey(), deadline),
+        &[],
+    );
+    let result = env.process(&[fill], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RefundsOnly)));
+
+    // The recipient still gets back whatever the escrow holds
+    let cancel =
+        superswap_sdk::cancel_dca_order(&env.program_id, &order, &env.usdc_mint, &handler.pubkey(), &admin, None);
+    env.process(&[cancel], &[&handler]).await.expect("refund DCA order");
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&order.recipient, &env.usdc_mint)).await,
+        Some(left)
+    );
+    assert_eq!(env.token_balance(&escrow_usdc).await, None);
+}
+
+#[tokio::test]
+async fn gas_top_up_route_must_deliver_into_the_escrow() {
+    const TOP_UP_USDC: u64 = 50_000;
+    const TOP_UP_LAMPORTS: u64 = 2_000_000;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let enable = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_max_gas_top_up_usdc: Some(TOP_UP_USDC),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[enable], &[]).await.expect("enable gas top-ups");
+    env.fund_wsol_pool(TOP_UP_LAMPORTS).await;
+
+    let native_mint = spl_token::native_mint::ID;
+    let config = find_config(&env.program_id).0;
+    let program_usdc = program_usdc_account(&env.program_id, &env.usdc_mint);
+    let escrow_wsol = escrow_token_account(&env.program_id, &find_swap_order(&env.program_id, 22).0, &native_mint);
+    let attacker_wsol = env.create_ata(&Pubkey::new_unique(), &native_mint).await;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    let main_swap = env.mock_swap(
+        &config,
+        &program_usdc,
+        &get_associated_token_address(&recipient, &env.output_mint),
+        BRIDGED_USDC - fee(BRIDGED_USDC) - TOP_UP_USDC,
+        777,
+    );
+    let main_route = quoted_route(&main_swap, main_swap.route());
+    let fill = |env: &TestEnv, destination: Pubkey| {
+        let top_up_swap = MockSwap {
+            output_mint: native_mint,
+            ..env.mock_swap(&config, &program_usdc, &destination, TOP_UP_USDC, TOP_UP_LAMPORTS)
+        };
+        let top_up_route = quoted_route(&top_up_swap, top_up_swap.route());
+        let route_accounts = [top_up_route.accounts.clone(), main_route.accounts.clone()].concat();
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(env),
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: main_route.swap_data.clone(),
+                gas_top_up: Some(GasTopUp {
+                    usdc_amount: TOP_UP_USDC,
+                    min_lamports_out: 0,
+                    jupiter_swap_data: top_up_route.swap_data,
+                    jupiter_accounts_len: top_up_route.accounts.len() as u8,
+                }),
+                ..fill_params(env, 22, recipient, deadline)
+            },
+            &route_accounts,
+        )
+    };
+
+    // The config PDA signs the top-up route, so it cannot pay out anywhere else
+    let attempt = fill(&env, attacker_wsol);
+    let result = env.process(&[attempt], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::RouteAccountMismatch)));
+    assert_eq!(env.token_balance(&attacker_wsol).await, Some(0));
+
+    let attempt = fill(&env, escrow_wsol);
+    env.process(&[attempt], &[&handler]).await.expect("fill with gas top-up");
+    assert_eq!(env.lamports(&recipient).await, TOP_UP_LAMPORTS);
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.output_mint)).await,
+        Some(777)
+    );
+}
+
+#[tokio::test]
+async fn gas_drop_funds_only_fresh_recipients_within_the_cap() {
+    const GAS_DROP: u64 = 1_000_000;
+    const RELAYER_TIP: u64 = 5_000;
+
+    let mut env = TestEnv::new(2 * BRIDGED_USDC).await;
+    let admin = env.payer();
+    let handler = env.handler.insecure_clone();
+    let enable = superswap_sdk::update_config(
+        &env.program_id,
+        &admin,
+        UpdateConfigParams {
+            new_max_gas_drop_lamports: Some(2 * GAS_DROP),
+            new_max_relayer_tip_usdc: Some(RELAYER_TIP),
+            ..UpdateConfigParams::default()
+        },
+    );
+    env.process(&[enable], &[]).await.expect("enable gas drops");
+    let usdc_mint = env.usdc_mint;
+    let payer_usdc = env.create_ata(&admin, &usdc_mint).await;
+    let deadline = env.now().await + 600;
+    let fill = |env: &TestEnv, order_id: u64, recipient: Pubkey, gas_drop_lamports: u64| {
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(env),
+            ProcessBridgeAndSwapParams {
+                destination_mint: env.usdc_mint,
+                relayer_tip: RELAYER_TIP,
+                gas_drop_lamports,
+                ..fill_params(env, order_id, recipient, deadline)
+            },
+            &[],
+        )
+    };
+
+    // Above the cap, or too little to leave the recipient rent-exempt
+    let recipient = Keypair::new().pubkey();
+    for gas_drop_lamports in [2 * GAS_DROP + 1, 100] {
+        let attempt = fill(&env, 23, recipient, gas_drop_lamports);
+        let result = env.process(&[attempt], &[&handler]).await;
+        assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidGasDrop)));
+    }
+
+    // The payer funds the drop and is reimbursed through the relayer tip
+    let payer_lamports = env.lamports(&admin).await;
+    let attempt = fill(&env, 23, recipient, GAS_DROP);
+    env.process(&[attempt], &[&handler]).await.expect("fill with gas drop");
+    assert_eq!(env.lamports(&recipient).await, GAS_DROP);
+    assert!(env.lamports(&admin).await <= payer_lamports - GAS_DROP);
+    assert_eq!(env.token_balance(&payer_usdc).await, Some(RELAYER_TIP));
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&recipient, &env.usdc_mint)).await,
+        Some(BRIDGED_USDC - fee(BRIDGED_USDC) - RELAYER_TIP)
+    );
+
+    // A recipient that already holds SOL gets no drop
+    let funded_recipient = Keypair::new().pubkey();
+    let fund = system_instruction::transfer(&admin, &funded_recipient, 5 * GAS_DROP);
+    env.process(&[fund], &[]).await.expect("fund recipient");
+    let attempt = fill(&env, 24, funded_recipient, GAS_DROP);
+    env.process(&[attempt], &[&handler]).await.expect("fill with gas drop");
+    assert_eq!(env.lamports(&funded_recipient).await, 5 * GAS_DROP);
+    assert_eq!(env.token_balance(&payer_usdc).await, Some(2 * RELAYER_TIP));
+}
+
+#[tokio::test]
+async fn wsol_output_is_unwrapped_to_native_sol() {
+    const OUTPUT_LAMPORTS: u64 = 3_000_000;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    env.fund_wsol_pool(OUTPUT_LAMPORTS).await;
+    let native_mint = spl_token::native_mint::ID;
+    let swap_order = find_swap_order(&env.program_id, 25).0;
+    let escrow_wsol = escrow_token_account(&env.program_id, &swap_order, &native_mint);
+    let rent_vault = find_rent_vault(&env.program_id).0;
+    let recipient = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+
+    let swap = MockSwap {
+        output_mint: native_mint,
+        ..env.mock_swap(
+            &find_config(&env.program_id).0,
+            &program_usdc_account(&env.program_id, &env.usdc_mint),
+            &escrow_wsol,
+            BRIDGED_USDC - fee(BRIDGED_USDC),
+            OUTPUT_LAMPORTS,
+        )
+    };
+    let route = quoted_route(&swap, swap.route());
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            destination_mint: native_mint,
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, 25, recipient, deadline)
+        },
+        &route.accounts,
+    );
+    let handler = env.handler.insecure_clone();
+    let vault_before = env.lamports(&rent_vault).await;
+    env.process(&[fill], &[&handler]).await.expect("fill into native SOL");
+
+    // The recipient holds native SOL and no WSOL account
+    assert_eq!(env.lamports(&recipient).await, OUTPUT_LAMPORTS);
+    assert_eq!(env.token_balance(&get_associated_token_address(&recipient, &native_mint)).await, None);
+    // The escrow's WSOL account is closed and its rent is back in the vault, which
+    // is only out the order account and the recipient's USDC account
+    assert_eq!(env.lamports(&escrow_wsol).await, 0);
+    assert_eq!(env.lamports(&find_escrow(&env.program_id, &swap_order).0).await, 0);
+    let order_rent = env.lamports(&swap_order).await;
+    let recipient_usdc_rent = env.lamports(&get_associated_token_address(&recipient, &env.usdc_mint)).await;
+    assert_eq!(env.lamports(&rent_vault).await, vault_before - order_rent - recipient_usdc_rent);
+}
+
+#[tokio::test]
+async fn basket_order_delivers_each_leg_its_weighted_share() {
+    const OUTPUT_LAMPORTS: u64 = 600_000;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    env.fund_wsol_pool(OUTPUT_LAMPORTS).await;
+    let native_mint = spl_token::native_mint::ID;
+    let swap_order = find_swap_order(&env.program_id, 41).0;
+    let escrow = find_escrow(&env.program_id, &swap_order).0;
+    let escrow_usdc = get_associated_token_address(&escrow, &env.usdc_mint);
+    let beneficiary = Keypair::new().pubkey();
+    let deadline = env.now().await + 600;
+    let handler = env.handler.insecure_clone();
+
+    // The order is filled as USDC into its own escrow, then split 60/40
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            destination_mint: env.usdc_mint,
+            ..fill_params(&env, 41, escrow, deadline)
+        },
+        &[],
+    );
+    let create_plan = superswap_sdk::create_basket_plan(
+        &env.program_id,
+        &handler.pubkey(),
+        &env.payer(),
+        CreateBasketPlanParams {
+            order_id: 41,
+            beneficiary,
+            legs: vec![
+                BasketLegParams {
+                    mint: env.output_mint,
+                    weight_bps: 6_000,
+                    min_output_amount: 700,
+                },
+                BasketLegParams {
+                    mint: native_mint,
+                    weight_bps: 4_000,
+                    min_output_amount: 500_000,
+                },
+            ],
+        },
+    );
+    env.process(&[fill, create_plan], &[&handler]).await.expect("basket fill");
+    let escrowed = BRIDGED_USDC - fee(BRIDGED_USDC);
+    let first_leg_usdc = escrowed * 6_000 / 10_000;
+
+    let execute_leg = |env: &TestEnv, leg_index: u8, swap: MockSwap| {
+        let route = quoted_route(&swap, swap.route());
+        superswap_sdk::execute_basket_leg(
+            &env.program_id,
+            41,
+            &env.usdc_mint,
+            &swap.output_mint,
+            &beneficiary,
+            &mock_jupiter::ID,
+            &handler.pubkey(),
+            &env.payer(),
+            ExecuteBasketLegParams {
+                order_id: 41,
+                leg_index,
+                jupiter_swap_data: route.swap_data,
+            },
+            &route.accounts,
+        )
+    };
+    let leg_swap = |env: &TestEnv, mint: Pubkey, in_amount: u64, output: u64| MockSwap {
+        output_mint: mint,
+        ..env.mock_swap(
+            &escrow,
+            &escrow_usdc,
+            &get_associated_token_address(&beneficiary, &mint),
+            in_amount,
+            output,
+        )
+    };
+
+    let first_leg = execute_leg(&env, 0, leg_swap(&env, env.output_mint, first_leg_usdc, 777));
+    env.process(&[first_leg.clone()], &[&handler]).await.expect("first leg");
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&beneficiary, &env.output_mint)).await,
+        Some(777)
+    );
+    assert_eq!(env.token_balance(&escrow_usdc).await, Some(escrowed - first_leg_usdc));
+    env.advance_clock(1).await;
+    let result = env.process(&[first_leg], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::BasketLegAlreadyExecuted)));
+
+    // A leg short of its own minimum fails alone and can be retried with a better route
+    let last_leg_usdc = escrowed - first_leg_usdc;
+    let short_leg = execute_leg(&env, 1, leg_swap(&env, native_mint, last_leg_usdc, 400_000));
+    let result = env.process(&[short_leg], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InsufficientOutputAmount)));
+    let last_leg = execute_leg(&env, 1, leg_swap(&env, native_mint, last_leg_usdc, OUTPUT_LAMPORTS));
+    env.process(&[last_leg], &[&handler]).await.expect("last leg");
+
+    let beneficiary_wsol = get_associated_token_address(&beneficiary, &native_mint);
+    assert_eq!(env.token_balance(&beneficiary_wsol).await, Some(OUTPUT_LAMPORTS));
+    // The emptied escrow USDC account is closed once every leg has run
+    assert_eq!(env.token_balance(&escrow_usdc).await, None);
+    let plan: BasketPlan = env
+        .anchor_account(&find_basket_plan(&env.program_id, &swap_order).0)
+        .await
+        .expect("basket plan");
+    assert_eq!(plan.usdc_amount, escrowed);
+    assert_eq!(
+        plan.legs.iter().map(|leg| (leg.delivered, leg.executed)).collect::<Vec<_>>(),
+        [(777, true), (OUTPUT_LAMPORTS, true)]
+    );
+}
+
+#[tokio::test]
+async fn approved_delegate_pulls_only_its_allowance_until_revoked() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let handler = env.handler.insecure_clone();
+    let admin = env.payer();
+    let deadline = env.now().await + 600;
+    let delegate = Keypair::new();
+    let beneficiary = Keypair::new();
+    let order = OrderRef {
+        order_id: 43,
+        recipient: find_escrow(&env.program_id, &find_swap_order(&env.program_id, 43).0).0,
+        destination_mint: env.output_mint,
+    };
+    let action = PostSwapAction::ApproveDelegate {
+        delegate: delegate.pubkey(),
+        amount: 300,
+        beneficiary: beneficiary.pubkey(),
+    };
+
+    let route = fill_route(&env, &order.recipient, 777);
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, order.order_id, order.recipient, deadline)
+        },
+        &route.accounts,
+    );
+    let extend = superswap_sdk::create_order_extension(
+        &env.program_id,
+        &handler.pubkey(),
+        &env.payer(),
+        CreateOrderExtensionParams {
+            order_id: order.order_id,
+            origin_chain_id: 1,
+            evm_origin: [7; 20],
+            route_hash: [0; 32],
+            integrator_id: 0,
+            onward_destination: None,
+            post_swap_action: Some(action),
+            claimant: None,
+        },
+    );
+    env.process(&[fill, extend], &[&handler]).await.expect("fill into escrow");
+
+    let beneficiary_output = get_associated_token_address(&beneficiary.pubkey(), &env.output_mint);
+    let execute = |env: &TestEnv| {
+        superswap_sdk::execute_post_swap_action(
+            &env.program_id,
+            &order,
+            &action,
+            &beneficiary.pubkey(),
+            &delegate.pubkey(),
+            &handler.pubkey(),
+            &env.payer(),
+            ExecutePostSwapActionParams {
+                order_id: order.order_id,
+                liquidity: None,
+                spend_data: None,
+                min_vault_shares: None,
+            },
+            &[AccountMeta::new_readonly(env.output_mint, false), AccountMeta::new(beneficiary_output, false)],
+        )
+    };
+
+    // A delegate missing from the allowlist has no entry to pass
+    let attempt = execute(&env);
+    let result = env.process(&[attempt], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(ErrorCode::AccountNotInitialized.into()));
+
+    let allow = superswap_sdk::add_approved_delegate(&env.program_id, &admin, &delegate.pubkey());
+    env.process(&[allow], &[]).await.expect("allowlist delegate");
+    env.advance_clock(1).await;
+    let approve = execute(&env);
+    env.process(&[approve], &[&handler]).await.expect("approve delegate");
+    let escrow_output = get_associated_token_address(&order.recipient, &env.output_mint);
+    assert_eq!(env.token_balance(&beneficiary_output).await, Some(477));
+    assert_eq!(env.token_balance(&escrow_output).await, Some(300));
+
+    // The delegate pulls from the escrow up to its allowance and no further
+    let output_mint = env.output_mint;
+    let delegate_output = env.create_ata(&delegate.pubkey(), &output_mint).await;
+    let pull = |amount| {
+        spl_token::instruction::transfer(
+            &spl_token::ID,
+            &escrow_output,
+            &delegate_output,
+            &delegate.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()
+    };
+    let (pulled, overdrawn) = (pull(100), pull(201));
+    env.process(&[pulled], &[&delegate]).await.expect("delegate pull");
+    assert!(env.process(&[overdrawn], &[&delegate]).await.is_err());
+    assert_eq!(env.token_balance(&delegate_output).await, Some(100));
+
+    // Running the action again would restore the allowance already pulled from
+    env.advance_clock(1).await;
+    let again = execute(&env);
+    let result = env.process(&[again], &[&handler]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::DelegateAlreadyApproved)));
+
+    // The beneficiary revokes and reclaims what the delegate left; the delegate cannot
+    let revoke = |signer: &Keypair| {
+        superswap_sdk::revoke_delegate(&env.program_id, &order, &signer.pubkey(), &beneficiary_output)
+    };
+    let (stolen, revoked) = (revoke(&delegate), revoke(&beneficiary));
+    let result = env.process(&[stolen], &[&delegate]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::InvalidRecipient)));
+    env.process(&[revoked], &[&beneficiary]).await.expect("revoke delegate");
+    assert_eq!(env.token_balance(&beneficiary_output).await, Some(677));
+    assert_eq!(env.token_balance(&escrow_output).await, None);
+}
+
+#[tokio::test]
+async fn close_order_waits_for_the_escrow_and_leaves_a_tombstone() {
+    let mut env = TestEnv::new(3 * BRIDGED_USDC).await;
+    let handler = env.handler.insecure_clone();
+    let closer = env.payer();
+    let rent_vault = find_rent_vault(&env.program_id).0;
+    let deadline = env.now().await + 600;
+    let fill = |env: &TestEnv, order_id: u64, recipient: Pubkey| {
+        let route = fill_route(env, &recipient, 777);
+        superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(env),
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(env, order_id, recipient, deadline)
+            },
+            &route.accounts,
+        )
+    };
+    let close = |env: &TestEnv, order_id: u64, recipient: Pubkey| {
+        let order = OrderRef {
+            order_id,
+            recipient,
+            destination_mint: env.output_mint,
+        };
+        superswap_sdk::close_order(&env.program_id, &order, &spl_token::ID, &closer, None, None)
+    };
+
+    // Order 27's output waits in its escrow for a claimant, so it cannot be closed yet
+    let escrow = find_escrow(&env.program_id, &find_swap_order(&env.program_id, 27).0).0;
+    let escrowed = fill(&env, 27, escrow);
+    env.process(&[escrowed], &[&handler]).await.expect("fill into escrow");
+    let attempt = close(&env, 27, escrow);
+    let result = env.process(&[attempt], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::EscrowNotEmpty)));
+
+    // Order 26 delivered straight to its recipient, so its rent and its extension's
+    // go back to the vault
+    let recipient = Keypair::new().pubkey();
+    let settled = fill(&env, 26, recipient);
+    let extend = superswap_sdk::create_order_extension(
+        &env.program_id,
+        &env.handler.pubkey(),
+        &closer,
+        CreateOrderExtensionParams {
+            order_id: 26,
+            origin_chain_id: 1,
+            evm_origin: [7; 20],
+            route_hash: [0; 32],
+            integrator_id: 0,
+            onward_destination: None,
+            post_swap_action: None,
+            claimant: None,
+        },
+    );
+    env.process(&[settled, extend], &[&handler]).await.expect("fill with extension");
+    let swap_order = find_swap_order(&env.program_id, 26).0;
+    let order_extension = find_order_extension(&env.program_id, &swap_order).0;
+    let order_rent = env.lamports(&swap_order).await;
+    let extension_rent = env.lamports(&order_extension).await;
+    assert!(extension_rent > 0);
+    let vault_before = env.lamports(&rent_vault).await;
+    let attempt = close(&env, 26, recipient);
+    env.process(&[attempt], &[]).await.expect("close order");
+
+    let tombstone: OrderTombstone = env.anchor_account(&swap_order).await.expect("tombstone");
+    assert_eq!(tombstone.order_id, 26);
+    let tombstone_rent = env.lamports(&swap_order).await;
+    assert!(tombstone_rent < order_rent);
+    assert_eq!(env.lamports(&order_extension).await, 0);
+    assert_eq!(
+        env.lamports(&rent_vault).await,
+        vault_before + order_rent - tombstone_rent + extension_rent
+    );
+
+    // The tombstone keeps the order's PDA taken, so the same order cannot be filled twice
+    let replay_recipient = Keypair::new().pubkey();
+    let replay = fill(&env, 26, replay_recipient);
+    assert!(env.process(&[replay], &[&handler]).await.is_err());
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&replay_recipient, &env.output_mint)).await,
+        None
+    );
+}
+
+#[tokio::test]
+async fn close_orders_batch_refuses_orders_with_unclaimed_escrows() {
+    let mut env = TestEnv::new(2 * BRIDGED_USDC).await;
+    let handler = env.handler.insecure_clone();
+    let admin = env.payer();
+    let deadline = env.now().await + 600;
+    let escrow = find_escrow(&env.program_id, &find_swap_order(&env.program_id, 29).0).0;
+    let orders = [(28, Keypair::new().pubkey()), (29, escrow)].map(|(order_id, recipient)| OrderRef {
+        order_id,
+        recipient,
+        destination_mint: env.output_mint,
+    });
+    for order in orders {
+        let route = fill_route(&env, &order.recipient, 777);
+        let fill = superswap_sdk::process_bridge_and_swap(
+            &env.program_id,
+            &fill_accounts(&env),
+            ProcessBridgeAndSwapParams {
+                jupiter_swap_data: route.swap_data,
+                ..fill_params(&env, order.order_id, order.recipient, deadline)
+            },
+            &route.accounts,
+        );
+        env.process(&[fill], &[&handler]).await.expect("fill");
+    }
+
+    // Order 29's output still waits in its escrow, which fails the whole batch
+    let both = orders.map(|order| (order, spl_token::ID));
+    let attempt = superswap_sdk::close_orders_batch(&env.program_id, &admin, &both, &[], None);
+    let result = env.process(&[attempt], &[]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::EscrowNotEmpty)));
+
+    let close = superswap_sdk::close_orders_batch(&env.program_id, &admin, &both[..1], &[], None);
+    env.process(&[close], &[]).await.expect("close settled order");
+    let tombstone: OrderTombstone = env
+        .anchor_account(&find_swap_order(&env.program_id, 28).0)
+        .await
+        .expect("tombstone");
+    assert_eq!(tombstone.order_id, 28);
+    let escrowed: SwapOrder = env
+        .anchor_account(&find_swap_order(&env.program_id, 29).0)
+        .await
+        .expect("order account");
+    assert!(escrowed.status == OrderStatus::Completed);
+}
+
+#[tokio::test]
+async fn compress_orders_archives_into_the_instance_archive() {
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let handler = env.handler.insecure_clone();
+    let admin = env.payer();
+    let deadline = env.now().await + 600;
+    let order = OrderRef {
+        order_id: 30,
+        recipient: Keypair::new().pubkey(),
+        destination_mint: env.output_mint,
+    };
+    let route = fill_route(&env, &order.recipient, 777);
+    let fill = superswap_sdk::process_bridge_and_swap(
+        &env.program_id,
+        &fill_accounts(&env),
+        ProcessBridgeAndSwapParams {
+            jupiter_swap_data: route.swap_data,
+            ..fill_params(&env, order.order_id, order.recipient, deadline)
+        },
+        &route.accounts,
+    );
+    env.process(&[fill], &[&handler]).await.expect("fill");
+
+    let compress = superswap_sdk::compress_orders(&env.program_id, &admin, &[(order, spl_token::ID)], &[], None);
+    env.process(&[compress], &[]).await.expect("compress order");
+
+    let archive: OrderArchive = env
+        .anchor_account(&find_order_archive(&env.program_id).0)
+        .await
+        .expect("order archive");
+    assert_eq!(archive.next_index, 1);
+    let tombstone: OrderTombstone = env
+        .anchor_account(&find_swap_order(&env.program_id, 30).0)
+        .await
+        .expect("tombstone");
+    assert_eq!(tombstone.order_id, 30);
+}
+
+#[tokio::test]
+async fn instance_admin_cannot_approve_claims_on_primary_orders() {
+    const INSTANCE_ID: u16 = 7;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let (order, _) = file_insurance_claim(&mut env, 32).await;
+    let instance_admin = env.create_instance(INSTANCE_ID).await;
+    let swap_order = find_swap_order(&env.program_id, order.order_id).0;
+    let insurance_claim = find_insurance_claim(&env.program_id, &swap_order).0;
+
+    // Under the instance's config the order id derives the instance's own order
+    let approve = Instruction {
+        program_id: env.program_id,
+        accounts: superswap_sol::accounts::ApproveInsuranceClaim {
+            config: find_instance_config(&env.program_id, INSTANCE_ID).0,
+            swap_order,
+            insurance_claim,
+            admin: instance_admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: superswap_sol::instruction::ApproveInsuranceClaim {
+            params: InsuranceClaimParams { order_id: order.order_id },
+        }
+        .data(),
+    };
+    let result = env.process(&[approve], &[&instance_admin]).await;
+    assert_eq!(custom_error(result), Some(ErrorCode::ConstraintSeeds.into()));
+    let claim: InsuranceClaim = env.anchor_account(&insurance_claim).await.expect("insurance claim");
+    assert!(claim.status == InsuranceClaimStatus::Filed);
+
+    let approve = superswap_sdk::approve_insurance_claim(&env.program_id, &env.payer(), order.order_id);
+    env.process(&[approve], &[]).await.expect("approve claim");
+    let claim: InsuranceClaim = env.anchor_account(&insurance_claim).await.expect("insurance claim");
+    assert!(claim.status == InsuranceClaimStatus::Approved);
+}
+
+#[tokio::test]
+async fn approved_claim_is_paid_only_by_its_instances_admin() {
+    const INSTANCE_ID: u16 = 7;
+
+    let mut env = TestEnv::new(BRIDGED_USDC).await;
+    let admin = env.payer();
+    let usdc_mint = env.usdc_mint;
+    let (order, payout) = file_insurance_claim(&mut env, 46).await;
+    let instance_admin = env.create_instance(INSTANCE_ID).await;
+    let approve = superswap_sdk::approve_insurance_claim(&env.program_id, &admin, order.order_id);
+    env.process(&[approve], &[]).await.expect("approve claim");
+    env.create_ata(&find_insurance_vault(&env.program_id).0, &usdc_mint).await;
+    let swap_order = find_swap_order(&env.program_id, order.order_id).0;
+    let insurance_claim = find_insurance_claim(&env.program_id, &swap_order).0;
+
+    let outsider = Keypair::new();
+    let pay = superswap_sdk::pay_insurance_claim(&env.program_id, &outsider.pubkey(), &order, &usdc_mint, &payout);
+    let result = env.process(&[pay], &[&outsider]).await;
+    assert_eq!(custom_error(result), Some(u32::from(SuperSwapError::Unauthorized)));
+
+    // Under the instance's config the order id derives the instance's own order
+    let escrow = find_escrow(&env.program_id, &swap_order).0;
+    let pay = Instruction {
+        program_id: env.program_id,
+        accounts: superswap_sol::accounts::PayInsuranceClaim {
+            config: find_instance_config(&env.program_id, INSTANCE_ID).0,
+            recovery_guard: find_instance_recovery_guard(&env.program_id, INSTANCE_ID).0,
+            swap_order,
+            dca_order: find_dca_order(&env.program_id, &swap_order).0,
+            insurance_claim,
+            recipient_orders: find_recipient_orders(&env.program_id, &order.recipient).0,
+            escrow,
+            escrow_usdc_account: get_associated_token_address(&escrow, &usdc_mint),
+            insurance_vault: find_insurance_vault(&env.program_id).0,
+            insurance_usdc_account: insurance_usdc_account(&env.program_id, &usdc_mint),
+            payout_token_account: payout,
+            admin: instance_admin.pubkey(),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: superswap_sol::instruction::PayInsuranceClaim {
+            params: InsuranceClaimParams { order_id: order.order_id },
+        }
+        .data(),
+    };
+    let result = env.process(&[pay], &[&instance_admin]).await;
+    assert_eq!(custom_error(result), Some(ErrorCode::ConstraintSeeds.into()));
+    let claim: InsuranceClaim = env.anchor_account(&insurance_claim).await.expect("insurance claim");
+    assert!(claim.status == InsuranceClaimStatus::Approved);
+
+    let pay = superswap_sdk::pay_insurance_claim(&env.program_id, &admin, &order, &usdc_mint, &payout);
+    env.process(&[pay], &[]).await.expect("pay claim");
+    assert_eq!(env.token_balance(&payout).await, Some(BRIDGED_USDC - fee(BRIDGED_USDC)));
+    let claim: InsuranceClaim = env.anchor_account(&insurance_claim).await.expect("insurance claim");
+    assert!(claim.status == InsuranceClaimStatus::Paid);
+    let swap_order: SwapOrder = env.anchor_account(&swap_order).await.expect("order account");
+    assert!(swap_order.status == OrderStatus::Refunded);
+}
@@ -0,0 +1,136 @@
+use std::path::{Path, PathBuf};
+use anyhow::{ensure, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Load-test configuration, loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct LoadTestConfig {
+    pub rpc_url: String,
+    /// Funded keypair that pays for setup, initializes the program and mints the test tokens
+    pub keypair_path: PathBuf,
+    /// Keypair registered as the Across handler, which signs every fill
+    pub handler_keypair_path: PathBuf,
+    #[serde(with = "pubkey_string", default = "default_program_id")]
+    pub program_id: Pubkey,
+    /// Simulated orders sent in the run
+    #[serde(default = "default_orders")]
+    pub orders: usize,
+    /// Fills in flight at once
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// USDC bridged in for each order
+    #[serde(default = "default_usdc_per_order")]
+    pub usdc_per_order: u64,
+    /// Output the mock Jupiter route pays for each order
+    #[serde(default = "default_output_per_order")]
+    pub output_per_order: u64,
+    /// Share of orders sent past their deadline, in basis points, to exercise the late-fill refund path
+    #[serde(default)]
+    pub expired_order_bps: u16,
+    #[serde(default = "default_compute_unit_limit")]
+    pub compute_unit_limit: u32,
+    /// Priority fee attached to fills, in micro-lamports per compute unit
+    #[serde(default)]
+    pub compute_unit_price: u64,
+    /// How long a sent fill may take to confirm before it counts as timed out
+    #[serde(default = "default_confirm_timeout_ms")]
+    pub confirm_timeout_ms: u64,
+}
+
+impl LoadTestConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        let config: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse config {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(self.orders > 0, "orders must be positive");
+        ensure!(self.concurrency > 0, "concurrency must be positive");
+        ensure!(self.usdc_per_order > 0, "usdc_per_order must be positive");
+        ensure!(self.output_per_order > 0, "output_per_order must be positive");
+        ensure!(self.expired_order_bps <= 10_000, "expired_order_bps cannot exceed 10000");
+        ensure!(
+            self.keypair_path != self.handler_keypair_path,
+            "the handler must be a different keypair from the payer"
+        );
+        Ok(())
+    }
+
+    /// Whether order `index` is sent already expired, spreading them evenly over the run
+    pub fn is_expired_order(&self, index: usize) -> bool {
+        let bps = self.expired_order_bps as usize;
+        (index + 1) * bps / 10_000 > index * bps / 10_000
+    }
+}
+
+fn default_program_id() -> Pubkey {
+    superswap_sol::ID
+}
+
+fn default_orders() -> usize {
+    500
+}
+
+fn default_concurrency() -> usize {
+    100
+}
+
+fn default_usdc_per_order() -> u64 {
+    1_000_000
+}
+
+fn default_output_per_order() -> u64 {
+    1_000_000
+}
+
+fn default_compute_unit_limit() -> u32 {
+    400_000
+}
+
+fn default_confirm_timeout_ms() -> u64 {
+    60_000
+}
+
+mod pubkey_string {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Pubkey::from_str(&raw).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_parses() {
+        let raw = include_str!("../loadtest.example.json");
+        let config: LoadTestConfig = serde_json::from_str(raw).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.program_id, superswap_sol::ID);
+        assert_eq!(config.orders, 500);
+    }
+
+    #[test]
+    fn expired_orders_are_spread_at_the_configured_rate() {
+        let raw = r#"{"rpc_url": "http://127.0.0.1:8899", "keypair_path": "payer.json",
+            "handler_keypair_path": "handler.json", "orders": 1000, "expired_order_bps": 500}"#;
+        let mut config: LoadTestConfig = serde_json::from_str(raw).unwrap();
+        let expired = (0..config.orders).filter(|&index| config.is_expired_order(index)).count();
+        assert_eq!(expired, 50);
+        assert!(!config.is_expired_order(0));
+
+        config.expired_order_bps = 0;
+        assert!((0..config.orders).all(|index| !config.is_expired_order(index)));
+    }
+}
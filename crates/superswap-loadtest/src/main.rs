@@ -0,0 +1,260 @@
+//! Load-testing harness for SuperSwap
+//!
+//! Prepares a local validator running the program next to `mock-jupiter`,
+//! acts as the bridge by minting every order's USDC to a test Across handler,
+//! then fires `process_bridge_and_swap` fills for hundreds of simulated orders
+//! with a bounded number in flight. Each fill carries a mock Jupiter route, so
+//! its size and accounts match a routed fill. The run ends with fill latency,
+//! compute-unit distribution, throughput and failures by error.
+
+mod config;
+mod report;
+mod setup;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use futures::stream::{self, StreamExt};
+use log::{info, warn};
+use mock_jupiter::client::MockSwap;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcTransactionConfig};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+use superswap_pda::{find_config, program_usdc_account, wallet_token_account};
+use superswap_quote::JupiterRoute;
+use superswap_sdk::{process_bridge_and_swap, FillAccounts, FillTransactionBuilder};
+use superswap_sol::state::ProcessBridgeAndSwapParams;
+use crate::config::LoadTestConfig;
+use crate::report::{Outcome, Report, Sample};
+use crate::setup::Environment;
+
+/// How often an unconfirmed fill's status is polled
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Parser)]
+#[command(name = "superswap-loadtest", about = "Fires concurrent simulated fills at a local validator")]
+struct Args {
+    /// Path to the load test's JSON config
+    #[arg(long, env = "SUPERSWAP_LOADTEST_CONFIG")]
+    config: PathBuf,
+}
+
+/// Builds, sends and measures fills as the Across handler
+struct Harness {
+    rpc: RpcClient,
+    handler: Keypair,
+    config: LoadTestConfig,
+    env: Environment,
+    /// First order id of the run, so repeated runs never collide
+    first_order_id: u64,
+}
+
+impl Harness {
+    /// Sends the fill for order `index` and waits for its outcome
+    async fn run_order(&self, index: usize) -> Sample {
+        let started = Instant::now();
+        match self.fill(index, &started).await {
+            Ok(sample) => sample,
+            Err(err) => Sample {
+                latency: started.elapsed(),
+                compute_units: None,
+                outcome: Outcome::Failed(format!("{:#}", err)),
+            },
+        }
+    }
+
+    async fn fill(&self, index: usize, started: &Instant) -> Result<Sample> {
+        let transaction = self.build_fill(index).await?;
+        // Failures should land, so their compute usage shows up as it would on a live cluster
+        let signature = self
+            .rpc
+            .send_transaction_with_config(
+                &transaction,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    max_retries: Some(0),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let deadline = *started + Duration::from_millis(self.config.confirm_timeout_ms);
+        loop {
+            let status = self.rpc.get_signature_statuses(&[signature]).await?.value.remove(0);
+            if let Some(status) = status.filter(|status| status.satisfies_commitment(CommitmentConfig::confirmed())) {
+                let latency = started.elapsed();
+                let outcome = match status.err {
+                    None => Outcome::Filled,
+                    Some(err) => Outcome::Failed(format!("{:?}", err)),
+                };
+                return Ok(Sample {
+                    latency,
+                    compute_units: self.compute_units(&signature).await,
+                    outcome,
+                });
+            }
+            if Instant::now() >= deadline {
+                return Ok(Sample {
+                    latency: started.elapsed(),
+                    compute_units: None,
+                    outcome: Outcome::TimedOut,
+                });
+            }
+            tokio::time::sleep(CONFIRM_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn build_fill(&self, index: usize) -> Result<VersionedTransaction> {
+        let program_id = self.config.program_id;
+        let program_config = &self.env.program_config;
+        let recipient = Pubkey::new_unique();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let deadline = if self.config.is_expired_order(index) { now - 3600 } else { now + 600 };
+
+        let route = self.mock_route(&recipient);
+        let accounts = FillAccounts {
+            across_handler: self.handler.pubkey(),
+            payer: self.handler.pubkey(),
+            source_usdc_account: wallet_token_account(&self.handler.pubkey(), &program_config.usdc_mint),
+            usdc_mint: program_config.usdc_mint,
+            fee_recipient: program_config.fee_recipient,
+            jupiter_program: mock_jupiter::ID,
+            recipient_preferences: None,
+            recipient_program: None,
+            fallback_output_mint: None,
+            staged_route: false,
+            route_template: false,
+            surplus_vault: false,
+            payer_surplus_account: false,
+            fee_recipient_surplus_account: false,
+            token_2022_destination: false,
+            global_volume_tracker: false,
+            mint_volume_tracker: false,
+        };
+        let fill = process_bridge_and_swap(
+            &program_id,
+            &accounts,
+            ProcessBridgeAndSwapParams {
+                order_id: self.first_order_id + index as u64,
+                recipient,
+                usdc_amount: self.config.usdc_per_order,
+                min_output_amount: route.min_out_amount,
+                destination_mint: self.env.output_mint,
+                deadline,
+                jupiter_swap_data: route.swap_data,
+                post_swap_hook: None,
+                gas_top_up: None,
+                donation: None,
+                relayer_tip: 0,
+                allow_off_curve_recipient: false,
+                refund_failed_swap: false,
+                gas_drop_lamports: 0,
+            },
+            &route.accounts,
+        );
+
+        let message = FillTransactionBuilder::new(self.handler.pubkey())
+            .compute_unit_limit(self.config.compute_unit_limit)
+            .compute_unit_price(self.config.compute_unit_price)
+            .instruction(fill)
+            .compile(self.rpc.get_latest_blockhash().await?)?;
+        Ok(VersionedTransaction::try_new(message, &[&self.handler])?)
+    }
+
+    /// Mock Jupiter route swapping the order's USDC for the configured output
+    fn mock_route(&self, recipient: &Pubkey) -> JupiterRoute {
+        let program_id = self.config.program_id;
+        let usdc_mint = self.env.program_config.usdc_mint;
+        let swap_authority = find_config(&program_id).0;
+        let swap = MockSwap {
+            authority: swap_authority,
+            source: program_usdc_account(&program_id, &usdc_mint),
+            destination: wallet_token_account(recipient, &self.env.output_mint),
+            input_mint: usdc_mint,
+            output_mint: self.env.output_mint,
+            output_token_program: anchor_spl::token::ID,
+            in_amount: self.config.usdc_per_order,
+            quoted_out_amount: self.config.output_per_order,
+            slippage_bps: 0,
+        };
+        JupiterRoute::from_instruction(
+            swap.route(),
+            &swap_authority,
+            Vec::new(),
+            swap.quoted_out_amount,
+            swap.min_out_amount(),
+        )
+    }
+
+    /// Compute units a confirmed transaction consumed
+    async fn compute_units(&self, signature: &Signature) -> Option<u64> {
+        let transaction = self
+            .rpc
+            .get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await;
+        match transaction {
+            Ok(transaction) => match transaction.transaction.meta?.compute_units_consumed {
+                OptionSerializer::Some(units) => Some(units),
+                _ => None,
+            },
+            Err(err) => {
+                warn!("failed to fetch transaction {}: {}", signature, err);
+                None
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    let config = LoadTestConfig::load(&args.config)?;
+    let payer = read_keypair(&config.keypair_path)?;
+    let handler = read_keypair(&config.handler_keypair_path)?;
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+
+    let env = Environment::prepare(&rpc, &payer, &handler, &config).await?;
+    let first_order_id = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64 * 1_000_000;
+    let (orders, concurrency) = (config.orders, config.concurrency);
+    let harness = Arc::new(Harness {
+        rpc,
+        handler,
+        config,
+        env,
+        first_order_id,
+    });
+
+    info!("sending {} fills, {} in flight", orders, concurrency);
+    let started = Instant::now();
+    let samples: Vec<Sample> = stream::iter(0..orders)
+        .map(|index| {
+            let harness = Arc::clone(&harness);
+            async move { harness.run_order(index).await }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+
+    print!("{}", Report::new(&samples, started.elapsed()));
+    Ok(())
+}
+
+fn read_keypair(path: &std::path::Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// What became of one simulated order's fill
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Filled,
+    /// The fill landed or was rejected with this error
+    Failed(String),
+    /// The fill did not confirm within the timeout
+    TimedOut,
+}
+
+/// Measurements of one fill
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// Send to confirmation
+    pub latency: Duration,
+    /// Compute units the transaction consumed, when it landed
+    pub compute_units: Option<u64>,
+    pub outcome: Outcome,
+}
+
+/// Minimum, nearest-rank percentiles and maximum of a set of measurements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    pub min: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<u64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_unstable();
+        Some(Self {
+            min: values[0],
+            p50: percentile(&values, 50),
+            p90: percentile(&values, 90),
+            p99: percentile(&values, 99),
+            max: values[values.len() - 1],
+        })
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty values
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Summary of a load-test run
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub orders: usize,
+    pub filled: usize,
+    pub timed_out: usize,
+    /// Failed fills by error
+    pub failures: BTreeMap<String, usize>,
+    pub elapsed: Duration,
+    /// Latency of filled orders, in milliseconds
+    pub latency_ms: Option<Distribution>,
+    /// Compute units of every fill that landed
+    pub compute_units: Option<Distribution>,
+}
+
+impl Report {
+    pub fn new(samples: &[Sample], elapsed: Duration) -> Self {
+        let mut failures = BTreeMap::new();
+        let mut timed_out = 0;
+        let mut latencies = Vec::new();
+        for sample in samples {
+            match &sample.outcome {
+                Outcome::Filled => latencies.push(sample.latency.as_millis() as u64),
+                Outcome::Failed(err) => *failures.entry(err.clone()).or_insert(0) += 1,
+                Outcome::TimedOut => timed_out += 1,
+            }
+        }
+
+        Self {
+            orders: samples.len(),
+            filled: latencies.len(),
+            timed_out,
+            failures,
+            elapsed,
+            latency_ms: Distribution::new(latencies),
+            compute_units: Distribution::new(samples.iter().filter_map(|sample| sample.compute_units).collect()),
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failures.values().sum()
+    }
+
+    /// Share of orders that failed or timed out, in percent
+    pub fn failure_rate(&self) -> f64 {
+        if self.orders == 0 {
+            return 0.0;
+        }
+        (self.failed() + self.timed_out) as f64 * 100.0 / self.orders as f64
+    }
+
+    /// Filled orders per second over the whole run
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.filled as f64 / secs
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "orders:       {}", self.orders)?;
+        writeln!(f, "filled:       {}", self.filled)?;
+        writeln!(f, "failed:       {}", self.failed())?;
+        writeln!(f, "timed out:    {}", self.timed_out)?;
+        writeln!(f, "failure rate: {:.2}%", self.failure_rate())?;
+        writeln!(
+            f,
+            "throughput:   {:.1} fills/s over {:.1}s",
+            self.throughput(),
+            self.elapsed.as_secs_f64()
+        )?;
+        write_distribution(f, "latency (ms)", self.latency_ms.as_ref())?;
+        write_distribution(f, "compute units", self.compute_units.as_ref())?;
+        for (err, count) in &self.failures {
+            writeln!(f, "  {:>6} x {}", count, err)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_distribution(f: &mut fmt::Formatter<'_>, name: &str, distribution: Option<&Distribution>) -> fmt::Result {
+    match distribution {
+        Some(d) => writeln!(
+            f,
+            "{}: min {} / p50 {} / p90 {} / p99 {} / max {}",
+            name, d.min, d.p50, d.p90, d.p99, d.max
+        ),
+        None => writeln!(f, "{}: no samples", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(latency_ms: u64, compute_units: Option<u64>, outcome: Outcome) -> Sample {
+        Sample {
+            latency: Duration::from_millis(latency_ms),
+            compute_units,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let distribution = Distribution::new((1..=200).rev().collect()).unwrap();
+        assert_eq!(
+            distribution,
+            Distribution {
+                min: 1,
+                p50: 100,
+                p90: 180,
+                p99: 198,
+                max: 200,
+            }
+        );
+        assert_eq!(Distribution::new(vec![7]).unwrap().p99, 7);
+        assert!(Distribution::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn report_counts_outcomes() {
+        let expired = "InstructionError(0, Custom(6003))".to_string();
+        let samples = vec![
+            sample(400, Some(60_000), Outcome::Filled),
+            sample(800, Some(62_000), Outcome::Filled),
+            sample(500, Some(9_000), Outcome::Failed(expired.clone())),
+            sample(60_000, None, Outcome::TimedOut),
+        ];
+        let report = Report::new(&samples, Duration::from_secs(2));
+
+        assert_eq!((report.orders, report.filled, report.failed(), report.timed_out), (4, 2, 1, 1));
+        assert_eq!(report.failures[&expired], 1);
+        assert_eq!(report.failure_rate(), 50.0);
+        assert_eq!(report.throughput(), 1.0);
+        assert_eq!(report.latency_ms.unwrap().max, 800);
+        assert_eq!(report.compute_units.unwrap().min, 9_000);
+    }
+}
@@ -0,0 +1,203 @@
+//! Prepares a local validator for a run: the program configured with the
+//! harness's handler and the mock Jupiter, test mints, a funded mock pool and
+//! the handler holding every order's bridged USDC
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token;
+use anyhow::{anyhow, ensure, Result};
+use log::info;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use superswap_pda::{find_config, wallet_token_account};
+use superswap_sol::state::{Config, InitializeParams, SwapOrder};
+use crate::config::LoadTestConfig;
+
+/// Inbound fee the harness initializes the program with, in basis points
+const FEE_BPS: u16 = 10;
+
+/// Lamports the handler keeps per order for the base transaction fee
+const FEE_LAMPORTS_PER_ORDER: u64 = 10_000;
+
+/// Accounts every fill in the run uses
+pub struct Environment {
+    pub program_config: Config,
+    pub output_mint: Pubkey,
+}
+
+impl Environment {
+    /// Prepares the program and funds `config.orders` fills
+    ///
+    /// The program is initialized on first use; later runs reuse it, provided
+    /// it was set up by the harness with the same payer and handler.
+    pub async fn prepare(rpc: &RpcClient, payer: &Keypair, handler: &Keypair, config: &LoadTestConfig) -> Result<Self> {
+        let mock_jupiter = rpc.get_account(&mock_jupiter::ID).await.ok();
+        ensure!(
+            mock_jupiter.is_some_and(|account| account.executable),
+            "mock Jupiter {} is not deployed; start the validator with `anchor localnet`",
+            mock_jupiter::ID
+        );
+
+        let program_config = match fetch_program_config(rpc, &config.program_id).await? {
+            Some(program_config) => program_config,
+            None => initialize(rpc, payer, handler, config).await?,
+        };
+        ensure!(
+            program_config.across_handler == handler.pubkey(),
+            "the program's Across handler is {}, not {}",
+            program_config.across_handler,
+            handler.pubkey()
+        );
+        ensure!(
+            program_config.jupiter_program == mock_jupiter::ID,
+            "the program routes through {}, not the mock Jupiter; load tests only run against a local validator",
+            program_config.jupiter_program
+        );
+        let usdc_mint = rpc.get_account(&program_config.usdc_mint).await?;
+        let usdc_mint = spl_token::state::Mint::unpack(&usdc_mint.data)?;
+        ensure!(
+            usdc_mint.mint_authority == COption::Some(payer.pubkey()),
+            "USDC mint {} is not mintable by the payer",
+            program_config.usdc_mint
+        );
+
+        let orders = config.orders as u64;
+        let pool_authority = mock_jupiter::client::pool_authority();
+        let output_mint = create_mint(rpc, payer, 9).await?;
+
+        // Mock pool: USDC in, a fresh output mint out, paying exactly the quote
+        let pool_output = wallet_token_account(&pool_authority, &output_mint);
+        let handler_usdc = wallet_token_account(&handler.pubkey(), &program_config.usdc_mint);
+        let mut create_accounts = [
+            (pool_authority, program_config.usdc_mint),
+            (pool_authority, output_mint),
+            (program_config.fee_recipient, program_config.usdc_mint),
+            (handler.pubkey(), program_config.usdc_mint),
+        ]
+        .iter()
+        .map(|(owner, mint)| create_associated_token_account_idempotent(&payer.pubkey(), owner, mint, &spl_token::ID))
+        .collect::<Vec<_>>();
+        create_accounts.push(mock_jupiter::client::configure(&payer.pubkey(), 10_000, false));
+        send(rpc, payer, &create_accounts, &[]).await?;
+
+        // The mock bridge: every order's USDC lands with the handler up front, as Across would deliver it
+        let mint_tokens = [
+            mint_to(&output_mint, &pool_output, &payer.pubkey(), orders * config.output_per_order)?,
+            mint_to(&program_config.usdc_mint, &handler_usdc, &payer.pubkey(), orders * config.usdc_per_order)?,
+        ];
+        send(rpc, payer, &mint_tokens, &[]).await?;
+
+        // The handler pays for order accounts; the rent vault for recipient token accounts
+        let order_rent = rpc.get_minimum_balance_for_rent_exemption(SwapOrder::LEN).await?;
+        let priority_fee = config.compute_unit_price * config.compute_unit_limit as u64 / 1_000_000;
+        let handler_needs = orders * (order_rent + FEE_LAMPORTS_PER_ORDER + priority_fee);
+        let handler_balance = rpc.get_balance(&handler.pubkey()).await?;
+        let mut funding = Vec::new();
+        if handler_balance < handler_needs {
+            funding.push(system_instruction::transfer(
+                &payer.pubkey(),
+                &handler.pubkey(),
+                handler_needs - handler_balance,
+            ));
+        }
+        if program_config.admin == payer.pubkey() {
+            let token_account_rent = rpc
+                .get_minimum_balance_for_rent_exemption(spl_token::state::Account::LEN)
+                .await?;
+            funding.push(superswap_sdk::fund_rent_vault(
+                &config.program_id,
+                &payer.pubkey(),
+                orders * token_account_rent,
+            ));
+        }
+        if !funding.is_empty() {
+            send(rpc, payer, &funding, &[]).await?;
+        }
+
+        info!(
+            "funded {} orders: USDC {}, output mint {}, handler {}",
+            orders,
+            program_config.usdc_mint,
+            output_mint,
+            handler.pubkey()
+        );
+        Ok(Self {
+            program_config,
+            output_mint,
+        })
+    }
+}
+
+/// Initializes the program with the harness's handler, a new USDC mint and the mock Jupiter
+async fn initialize(rpc: &RpcClient, payer: &Keypair, handler: &Keypair, config: &LoadTestConfig) -> Result<Config> {
+    let usdc_mint = create_mint(rpc, payer, 6).await?;
+    let initialize = superswap_sdk::initialize(
+        &config.program_id,
+        &payer.pubkey(),
+        InitializeParams {
+            across_handler: handler.pubkey(),
+            jupiter_program: mock_jupiter::ID,
+            across_spoke_pool: Pubkey::new_unique(),
+            cctp_token_messenger_minter: Pubkey::new_unique(),
+            usdc_mint,
+            fee_recipient: payer.pubkey(),
+            fee_bps: FEE_BPS,
+            outbound_fee_bps: 0,
+            min_outbound_amount: 0,
+            max_outbound_amount: 0,
+        },
+    );
+    send(rpc, payer, &[initialize], &[]).await?;
+    info!("initialized program {} with USDC mint {}", config.program_id, usdc_mint);
+
+    fetch_program_config(rpc, &config.program_id)
+        .await?
+        .ok_or_else(|| anyhow!("program config missing after initialize"))
+}
+
+async fn fetch_program_config(rpc: &RpcClient, program_id: &Pubkey) -> Result<Option<Config>> {
+    let address = find_config(program_id).0;
+    let account = rpc.get_account_with_commitment(&address, rpc.commitment()).await?.value;
+    match account {
+        Some(account) => Ok(Some(Config::try_deserialize(&mut account.data.as_slice())?)),
+        None => Ok(None),
+    }
+}
+
+async fn create_mint(rpc: &RpcClient, payer: &Keypair, decimals: u8) -> Result<Pubkey> {
+    let mint = Keypair::new();
+    let rent = rpc
+        .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+        .await?;
+    let instructions = [
+        system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            rent,
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint(&spl_token::ID, &mint.pubkey(), &payer.pubkey(), None, decimals)?,
+    ];
+    send(rpc, payer, &instructions, &[&mint]).await?;
+    Ok(mint.pubkey())
+}
+
+fn mint_to(mint: &Pubkey, account: &Pubkey, authority: &Pubkey, amount: u64) -> Result<Instruction> {
+    Ok(spl_token::instruction::mint_to(&spl_token::ID, mint, account, authority, &[], amount)?)
+}
+
+async fn send(rpc: &RpcClient, payer: &Keypair, instructions: &[Instruction], signers: &[&Keypair]) -> Result<()> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash);
+    rpc.send_and_confirm_transaction(&transaction).await?;
+    Ok(())
+}
@@ -0,0 +1,658 @@
+//! PDA seeds and address derivation helpers for the SuperSwap program
+//!
+//! Shared by the on-chain program, relayers and tests so every component
+//! derives the same addresses from the same seeds.
+
+use solana_program::pubkey::Pubkey;
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
+
+/// Seed for the config PDA, followed by the instance seed (see [`instance_seed`])
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Seed for the rent vault PDA, followed by the instance seed
+pub const RENT_VAULT_SEED: &[u8] = b"rent_vault";
+
+/// Seed for the outbound rate limit PDA, followed by the instance seed
+pub const OUTBOUND_RATE_LIMIT_SEED: &[u8] = b"outbound_rate_limit";
+
+/// Seed for the PDA that signs delivery callbacks to recipient programs
+pub const CALLBACK_AUTHORITY_SEED: &[u8] = b"callback_authority";
+
+/// Seed prefix for hook allowlist PDAs (followed by the instance seed and the hook program id)
+pub const HOOK_PROGRAM_SEED: &[u8] = b"hook_program";
+
+/// Seed prefix for vault program allowlist PDAs (followed by the instance seed and the vault program id)
+pub const VAULT_PROGRAM_SEED: &[u8] = b"vault_program";
+
+/// Seed prefix for approved delegate allowlist PDAs (followed by the instance seed and the delegate address)
+pub const APPROVED_DELEGATE_SEED: &[u8] = b"approved_delegate";
+
+/// Seed prefix for donation recipient allowlist PDAs (followed by the instance seed and the recipient address)
+pub const DONATION_RECIPIENT_SEED: &[u8] = b"donation_recipient";
+
+/// Seed prefix for spend program allowlist PDAs (followed by the instance seed and the spend program id)
+pub const SPEND_PROGRAM_SEED: &[u8] = b"spend_program";
+
+/// Seed prefix for destination mint entry PDAs (followed by the instance seed and the mint address)
+pub const DESTINATION_MINT_SEED: &[u8] = b"destination_mint";
+
+/// Seed prefix for basket plan PDAs (followed by the swap order address)
+pub const BASKET_PLAN_SEED: &[u8] = b"basket_plan";
+
+/// Seed prefix for split plan PDAs (followed by the swap order address)
+pub const SPLIT_PLAN_SEED: &[u8] = b"split_plan";
+
+/// Seed prefix for stream plan PDAs (followed by the swap order address)
+pub const STREAM_PLAN_SEED: &[u8] = b"stream_plan";
+
+/// Seed prefix for swap order PDAs (followed by the instance seed and the little-endian order id)
+pub const SWAP_ORDER_SEED: &[u8] = b"swap_order";
+
+/// Seed prefix for outbound order PDAs (followed by the instance seed and the little-endian order id)
+pub const OUTBOUND_ORDER_SEED: &[u8] = b"outbound_order";
+
+/// Seed prefix for order extension PDAs (followed by the swap order address)
+pub const ORDER_EXTENSION_SEED: &[u8] = b"order_extension";
+
+/// Seed prefix for per-order escrow authority PDAs (followed by the swap order address)
+pub const ESCROW_SEED: &[u8] = b"escrow";
+
+/// Seed prefix for limit order PDAs (followed by the swap order address)
+pub const LIMIT_ORDER_SEED: &[u8] = b"limit_order";
+
+/// Seed prefix for DCA schedule PDAs (followed by the swap order address)
+pub const DCA_ORDER_SEED: &[u8] = b"dca_order";
+
+/// Seed prefix for recipient preferences PDAs (followed by the recipient address)
+pub const RECIPIENT_PREFERENCES_SEED: &[u8] = b"recipient_preferences";
+
+/// Seed prefix for recipient open order count PDAs (followed by the recipient address)
+pub const RECIPIENT_ORDERS_SEED: &[u8] = b"recipient_orders";
+
+/// Seed for the recovery guard PDA, followed by the instance seed
+pub const RECOVERY_GUARD_SEED: &[u8] = b"recovery_guard";
+
+/// Seed prefix for queued recovery PDAs (followed by the instance seed and the recovery id)
+pub const PENDING_RECOVERY_SEED: &[u8] = b"pending_recovery";
+
+/// Seed for the insurance vault PDA, owner of the insurance fund's USDC, followed by the instance seed
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+
+/// Seed prefix for insurance claim PDAs (followed by the swap order address)
+pub const INSURANCE_CLAIM_SEED: &[u8] = b"insurance_claim";
+
+/// Seed for the order archive PDA, the Merkle commitment to compressed orders, followed by the instance seed
+pub const ORDER_ARCHIVE_SEED: &[u8] = b"order_archive";
+
+/// Seed prefix for EVM claim PDAs (followed by the instance seed and the 20-byte EVM address)
+pub const EVM_CLAIM_SEED: &[u8] = b"evm_claim";
+
+/// Seed prefix for payer operator ledger PDAs (followed by the instance seed and the operator address)
+pub const PAYER_LEDGER_SEED: &[u8] = b"payer_ledger";
+
+/// Seed for the admin audit log PDA, followed by the instance seed
+pub const ADMIN_LOG_SEED: &[u8] = b"admin_log";
+
+/// Seed prefix for route staging PDAs (followed by the instance seed, uploader and order id)
+pub const ROUTE_STAGING_SEED: &[u8] = b"route_staging";
+
+/// Seed prefix for route template PDAs (followed by the instance seed, input mint and output mint)
+pub const ROUTE_TEMPLATE_SEED: &[u8] = b"route_template";
+
+/// Seed for the pending config update PDA, followed by the instance seed
+pub const PENDING_CONFIG_UPDATE_SEED: &[u8] = b"pending_config_update";
+
+/// Seed prefix for per-mint config PDAs (followed by the instance seed and the destination mint)
+pub const MINT_CONFIG_SEED: &[u8] = b"mint_config";
+
+/// Seed prefix for daily volume tracker PDAs (followed by the instance seed and
+/// the destination mint, or the default pubkey for the instance-wide tracker)
+pub const VOLUME_TRACKER_SEED: &[u8] = b"volume_tracker";
+
+/// Seed scoping the config and order PDAs to a deployment instance
+///
+/// The primary instance (0) has an empty seed, so its addresses are the
+/// unscoped ones; any other instance adds its id in little-endian bytes.
+pub fn instance_seed(instance_id: u16) -> Vec<u8> {
+    if instance_id == 0 {
+        Vec::new()
+    } else {
+        instance_id.to_le_bytes().to_vec()
+    }
+}
+
+/// Derives the primary instance's config PDA
+pub fn find_config(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_config(program_id, 0)
+}
+
+/// Derives the config PDA of a deployment instance
+pub fn find_instance_config(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the primary instance's rent vault PDA
+pub fn find_rent_vault(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_rent_vault(program_id, 0)
+}
+
+/// Derives the rent vault PDA of a deployment instance
+pub fn find_instance_rent_vault(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RENT_VAULT_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the primary instance's recovery guard PDA
+pub fn find_recovery_guard(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_recovery_guard(program_id, 0)
+}
+
+/// Derives the recovery guard PDA of a deployment instance
+pub fn find_instance_recovery_guard(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECOVERY_GUARD_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the primary instance's insurance vault PDA
+pub fn find_insurance_vault(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_insurance_vault(program_id, 0)
+}
+
+/// Derives the insurance vault PDA of a deployment instance
+pub fn find_instance_insurance_vault(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_VAULT_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the primary instance's outbound rate limit PDA
+pub fn find_outbound_rate_limit(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_outbound_rate_limit(program_id, 0)
+}
+
+/// Derives the outbound rate limit PDA of a deployment instance
+pub fn find_instance_outbound_rate_limit(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OUTBOUND_RATE_LIMIT_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the delivery callback authority PDA
+pub fn find_callback_authority(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CALLBACK_AUTHORITY_SEED], program_id)
+}
+
+/// Derives the primary instance's allowlist entry PDA for a hook program
+pub fn find_hook_program(program_id: &Pubkey, hook_program: &Pubkey) -> (Pubkey, u8) {
+    find_instance_hook_program(program_id, 0, hook_program)
+}
+
+/// Derives the allowlist entry PDA for a hook program within a deployment instance
+pub fn find_instance_hook_program(program_id: &Pubkey, instance_id: u16, hook_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HOOK_PROGRAM_SEED, &instance_seed(instance_id), hook_program.as_ref()], program_id)
+}
+
+/// Derives the primary instance's allowlist entry PDA for a vault program
+pub fn find_vault_program(program_id: &Pubkey, vault_program: &Pubkey) -> (Pubkey, u8) {
+    find_instance_vault_program(program_id, 0, vault_program)
+}
+
+/// Derives the allowlist entry PDA for a vault program within a deployment instance
+pub fn find_instance_vault_program(program_id: &Pubkey, instance_id: u16, vault_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_PROGRAM_SEED, &instance_seed(instance_id), vault_program.as_ref()], program_id)
+}
+
+/// Derives the primary instance's allowlist entry PDA for an approved delegate
+pub fn find_approved_delegate(program_id: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    find_instance_approved_delegate(program_id, 0, delegate)
+}
+
+/// Derives the allowlist entry PDA for an approved delegate within a deployment instance
+pub fn find_instance_approved_delegate(program_id: &Pubkey, instance_id: u16, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[APPROVED_DELEGATE_SEED, &instance_seed(instance_id), delegate.as_ref()], program_id)
+}
+
+/// Derives the primary instance's allowlist entry PDA for a donation recipient
+pub fn find_donation_recipient(program_id: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    find_instance_donation_recipient(program_id, 0, recipient)
+}
+
+/// Derives the allowlist entry PDA for a donation recipient within a deployment instance
+pub fn find_instance_donation_recipient(program_id: &Pubkey, instance_id: u16, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DONATION_RECIPIENT_SEED, &instance_seed(instance_id), recipient.as_ref()], program_id)
+}
+
+/// Derives the primary instance's allowlist entry PDA for a spend program
+pub fn find_spend_program(program_id: &Pubkey, spend_program: &Pubkey) -> (Pubkey, u8) {
+    find_instance_spend_program(program_id, 0, spend_program)
+}
+
+/// Derives the allowlist entry PDA for a spend program within a deployment instance
+pub fn find_instance_spend_program(program_id: &Pubkey, instance_id: u16, spend_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPEND_PROGRAM_SEED, &instance_seed(instance_id), spend_program.as_ref()], program_id)
+}
+
+/// Derives the primary instance's entry PDA for a destination mint
+pub fn find_destination_mint(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    find_instance_destination_mint(program_id, 0, mint)
+}
+
+/// Derives the entry PDA for a destination mint within a deployment instance
+pub fn find_instance_destination_mint(program_id: &Pubkey, instance_id: u16, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DESTINATION_MINT_SEED, &instance_seed(instance_id), mint.as_ref()], program_id)
+}
+
+/// Derives the basket plan PDA for a swap order
+pub fn find_basket_plan(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BASKET_PLAN_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the split plan PDA for a swap order
+pub fn find_split_plan(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPLIT_PLAN_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the stream plan PDA for a swap order
+pub fn find_stream_plan(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STREAM_PLAN_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the primary instance's swap order PDA for an order id
+pub fn find_swap_order(program_id: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    find_instance_swap_order(program_id, 0, order_id)
+}
+
+/// Derives the swap order PDA for an order id within a deployment instance
+pub fn find_instance_swap_order(program_id: &Pubkey, instance_id: u16, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SWAP_ORDER_SEED, &instance_seed(instance_id), &order_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Derives the primary instance's outbound order PDA for an order id
+pub fn find_outbound_order(program_id: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    find_instance_outbound_order(program_id, 0, order_id)
+}
+
+/// Derives the outbound order PDA for an order id within a deployment instance
+pub fn find_instance_outbound_order(program_id: &Pubkey, instance_id: u16, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OUTBOUND_ORDER_SEED, &instance_seed(instance_id), &order_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Derives the primary instance's queued recovery PDA for a recovery id
+pub fn find_pending_recovery(program_id: &Pubkey, recovery_id: u64) -> (Pubkey, u8) {
+    find_instance_pending_recovery(program_id, 0, recovery_id)
+}
+
+/// Derives the queued recovery PDA for a recovery id within a deployment instance
+pub fn find_instance_pending_recovery(program_id: &Pubkey, instance_id: u16, recovery_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_RECOVERY_SEED, &instance_seed(instance_id), &recovery_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Derives the insurance claim PDA for a swap order
+pub fn find_insurance_claim(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_CLAIM_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the primary instance's order archive PDA
+pub fn find_order_archive(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_order_archive(program_id, 0)
+}
+
+/// Derives the order archive PDA of a deployment instance
+pub fn find_instance_order_archive(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORDER_ARCHIVE_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the primary instance's claim PDA that holds funds for an EVM address
+/// until it names a Solana wallet
+pub fn find_evm_claim(program_id: &Pubkey, evm_address: &[u8; 20]) -> (Pubkey, u8) {
+    find_instance_evm_claim(program_id, 0, evm_address)
+}
+
+/// Derives the claim PDA for an EVM address within a deployment instance
+pub fn find_instance_evm_claim(program_id: &Pubkey, instance_id: u16, evm_address: &[u8; 20]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVM_CLAIM_SEED, &instance_seed(instance_id), evm_address.as_ref()], program_id)
+}
+
+/// Derives the primary instance's reimbursement ledger PDA of a registered payer operator
+pub fn find_payer_ledger(program_id: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    find_instance_payer_ledger(program_id, 0, operator)
+}
+
+/// Derives the reimbursement ledger PDA of a registered payer operator within a deployment instance
+pub fn find_instance_payer_ledger(program_id: &Pubkey, instance_id: u16, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAYER_LEDGER_SEED, &instance_seed(instance_id), operator.as_ref()], program_id)
+}
+
+/// Derives the primary instance's admin audit log PDA
+pub fn find_admin_log(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_admin_log(program_id, 0)
+}
+
+/// Derives the admin audit log PDA of a deployment instance
+pub fn find_instance_admin_log(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_LOG_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the PDA holding the primary instance's timelocked config update while it waits out the delay
+pub fn find_pending_config_update(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_instance_pending_config_update(program_id, 0)
+}
+
+/// Derives the pending config update PDA of a deployment instance
+pub fn find_instance_pending_config_update(program_id: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_CONFIG_UPDATE_SEED, &instance_seed(instance_id)], program_id)
+}
+
+/// Derives the PDA staging an oversized Jupiter route uploaded by `authority` for a primary instance order
+pub fn find_route_staging(program_id: &Pubkey, authority: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    find_instance_route_staging(program_id, 0, authority, order_id)
+}
+
+/// Derives the route staging PDA for an order within a deployment instance
+pub fn find_instance_route_staging(program_id: &Pubkey, instance_id: u16, authority: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ROUTE_STAGING_SEED, &instance_seed(instance_id), authority.as_ref(), &order_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Derives the PDA of the route template registered for a pair on the primary instance
+pub fn find_route_template(program_id: &Pubkey, input_mint: &Pubkey, output_mint: &Pubkey) -> (Pubkey, u8) {
+    find_instance_route_template(program_id, 0, input_mint, output_mint)
+}
+
+/// Derives the route template PDA for a pair within a deployment instance
+pub fn find_instance_route_template(
+    program_id: &Pubkey,
+    instance_id: u16,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ROUTE_TEMPLATE_SEED, &instance_seed(instance_id), input_mint.as_ref(), output_mint.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives the PDA of the overrides the primary instance applies to fills into `mint`
+pub fn find_mint_config(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    find_instance_mint_config(program_id, 0, mint)
+}
+
+/// Derives the per-mint config PDA for `mint` within a deployment instance
+pub fn find_instance_mint_config(program_id: &Pubkey, instance_id: u16, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_CONFIG_SEED, &instance_seed(instance_id), mint.as_ref()], program_id)
+}
+
+/// Derives the PDA tracking the primary instance's daily fill volume into `mint`,
+/// or across all mints when `mint` is the default pubkey
+pub fn find_volume_tracker(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    find_instance_volume_tracker(program_id, 0, mint)
+}
+
+/// Derives the daily volume tracker PDA for `mint` within a deployment instance
+pub fn find_instance_volume_tracker(program_id: &Pubkey, instance_id: u16, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOLUME_TRACKER_SEED, &instance_seed(instance_id), mint.as_ref()], program_id)
+}
+
+/// Derives the order extension PDA for a swap order
+pub fn find_order_extension(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORDER_EXTENSION_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the escrow authority PDA for a swap order
+pub fn find_escrow(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ESCROW_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the limit order PDA for a swap order
+pub fn find_limit_order(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LIMIT_ORDER_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the DCA schedule PDA for a swap order
+pub fn find_dca_order(program_id: &Pubkey, swap_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DCA_ORDER_SEED, swap_order.as_ref()], program_id)
+}
+
+/// Derives the preferences PDA for a recipient
+pub fn find_recipient_preferences(program_id: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECIPIENT_PREFERENCES_SEED, recipient.as_ref()], program_id)
+}
+
+/// Derives the open order count PDA for a recipient
+pub fn find_recipient_orders(program_id: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECIPIENT_ORDERS_SEED, recipient.as_ref()], program_id)
+}
+
+/// Program's USDC token account (ATA owned by the config PDA)
+pub fn program_usdc_account(program_id: &Pubkey, usdc_mint: &Pubkey) -> Pubkey {
+    let (config, _) = find_config(program_id);
+    get_associated_token_address(&config, usdc_mint)
+}
+
+/// USDC token account of a deployment instance (ATA owned by its config PDA)
+pub fn instance_program_usdc_account(program_id: &Pubkey, instance_id: u16, usdc_mint: &Pubkey) -> Pubkey {
+    let (config, _) = find_instance_config(program_id, instance_id);
+    get_associated_token_address(&config, usdc_mint)
+}
+
+/// Primary instance's insurance fund USDC account (ATA owned by the insurance vault PDA)
+pub fn insurance_usdc_account(program_id: &Pubkey, usdc_mint: &Pubkey) -> Pubkey {
+    instance_insurance_usdc_account(program_id, 0, usdc_mint)
+}
+
+/// Insurance fund USDC account of a deployment instance (ATA owned by its insurance vault PDA)
+pub fn instance_insurance_usdc_account(program_id: &Pubkey, instance_id: u16, usdc_mint: &Pubkey) -> Pubkey {
+    let (insurance_vault, _) = find_instance_insurance_vault(program_id, instance_id);
+    get_associated_token_address(&insurance_vault, usdc_mint)
+}
+
+/// Escrow token account for a swap order (ATA owned by the escrow PDA)
+pub fn escrow_token_account(program_id: &Pubkey, swap_order: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let (escrow, _) = find_escrow(program_id, swap_order);
+    get_associated_token_address(&escrow, mint)
+}
+
+/// Escrow token account for a swap order and a mint owned by `token_program`, e.g. Token-2022
+pub fn escrow_token_account_with_program(program_id: &Pubkey, swap_order: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    let (escrow, _) = find_escrow(program_id, swap_order);
+    get_associated_token_address_with_program_id(&escrow, mint, token_program)
+}
+
+/// Escrow token account for an outbound order (ATA owned by the outbound order PDA)
+pub fn outbound_escrow_account(program_id: &Pubkey, order_id: u64, usdc_mint: &Pubkey) -> Pubkey {
+    let (outbound_order, _) = find_outbound_order(program_id, order_id);
+    get_associated_token_address(&outbound_order, usdc_mint)
+}
+
+/// Expected ATA for a wallet (recipient, fee recipient) and mint
+pub fn wallet_token_account(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(wallet, mint)
+}
+
+/// Expected ATA for a wallet and a mint owned by `token_program`, e.g. Token-2022
+pub fn wallet_token_account_with_program(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, token_program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Address the program derives from its `seeds = [...]` constraint
+    fn program_pda(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    #[test]
+    fn primary_instance_seed_is_empty() {
+        assert!(instance_seed(0).is_empty());
+    }
+
+    #[test]
+    fn other_instances_seed_with_their_id_as_u16_le() {
+        assert_eq!(instance_seed(7), vec![7, 0]);
+        assert_eq!(instance_seed(0x0102), vec![0x02, 0x01]);
+        // `create_instance` seeds the new accounts with `instance_id.to_le_bytes()`
+        assert_eq!(instance_seed(u16::MAX), u16::MAX.to_le_bytes());
+    }
+
+    #[test]
+    fn instance_scoped_helpers_match_the_program_seeds() {
+        let program_id = Pubkey::new_unique();
+        let (key, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = 42u64.to_le_bytes();
+        let evm_address = [7u8; 20];
+
+        for instance_id in [0, 7] {
+            let scope = instance_seed(instance_id);
+            let cases = [
+                (find_instance_config(&program_id, instance_id), vec![CONFIG_SEED, &scope]),
+                (find_instance_rent_vault(&program_id, instance_id), vec![RENT_VAULT_SEED, &scope]),
+                (find_instance_recovery_guard(&program_id, instance_id), vec![RECOVERY_GUARD_SEED, &scope]),
+                (find_instance_insurance_vault(&program_id, instance_id), vec![INSURANCE_VAULT_SEED, &scope]),
+                (
+                    find_instance_outbound_rate_limit(&program_id, instance_id),
+                    vec![OUTBOUND_RATE_LIMIT_SEED, &scope],
+                ),
+                (find_instance_order_archive(&program_id, instance_id), vec![ORDER_ARCHIVE_SEED, &scope]),
+                (find_instance_admin_log(&program_id, instance_id), vec![ADMIN_LOG_SEED, &scope]),
+                (
+                    find_instance_pending_config_update(&program_id, instance_id),
+                    vec![PENDING_CONFIG_UPDATE_SEED, &scope],
+                ),
+                (
+                    find_instance_hook_program(&program_id, instance_id, &key),
+                    vec![HOOK_PROGRAM_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_vault_program(&program_id, instance_id, &key),
+                    vec![VAULT_PROGRAM_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_approved_delegate(&program_id, instance_id, &key),
+                    vec![APPROVED_DELEGATE_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_donation_recipient(&program_id, instance_id, &key),
+                    vec![DONATION_RECIPIENT_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_spend_program(&program_id, instance_id, &key),
+                    vec![SPEND_PROGRAM_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_destination_mint(&program_id, instance_id, &key),
+                    vec![DESTINATION_MINT_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_mint_config(&program_id, instance_id, &key),
+                    vec![MINT_CONFIG_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_volume_tracker(&program_id, instance_id, &key),
+                    vec![VOLUME_TRACKER_SEED, &scope, key.as_ref()],
+                ),
+                (
+                    find_instance_payer_ledger(&program_id, instance_id, &key),
+                    vec![PAYER_LEDGER_SEED, &scope, key.as_ref()],
+                ),
+                (find_instance_swap_order(&program_id, instance_id, 42), vec![SWAP_ORDER_SEED, &scope, &id]),
+                (
+                    find_instance_outbound_order(&program_id, instance_id, 42),
+                    vec![OUTBOUND_ORDER_SEED, &scope, &id],
+                ),
+                (
+                    find_instance_pending_recovery(&program_id, instance_id, 42),
+                    vec![PENDING_RECOVERY_SEED, &scope, &id],
+                ),
+                (
+                    find_instance_evm_claim(&program_id, instance_id, &evm_address),
+                    vec![EVM_CLAIM_SEED, &scope, &evm_address],
+                ),
+                (
+                    find_instance_route_staging(&program_id, instance_id, &key, 42),
+                    vec![ROUTE_STAGING_SEED, &scope, key.as_ref(), &id],
+                ),
+                (
+                    find_instance_route_template(&program_id, instance_id, &key, &other),
+                    vec![ROUTE_TEMPLATE_SEED, &scope, key.as_ref(), other.as_ref()],
+                ),
+            ];
+            for (derived, seeds) in cases {
+                assert_eq!(derived, program_pda(&seeds, &program_id), "instance {instance_id}, seeds {seeds:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn primary_helpers_derive_the_unscoped_addresses() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let id = 42u64.to_le_bytes();
+
+        assert_eq!(find_config(&program_id), program_pda(&[CONFIG_SEED], &program_id));
+        assert_eq!(find_rent_vault(&program_id), program_pda(&[RENT_VAULT_SEED], &program_id));
+        assert_eq!(find_recovery_guard(&program_id), program_pda(&[RECOVERY_GUARD_SEED], &program_id));
+        assert_eq!(find_admin_log(&program_id), program_pda(&[ADMIN_LOG_SEED], &program_id));
+        assert_eq!(find_swap_order(&program_id, 42), program_pda(&[SWAP_ORDER_SEED, &id], &program_id));
+        assert_eq!(
+            find_approved_delegate(&program_id, &key),
+            program_pda(&[APPROVED_DELEGATE_SEED, key.as_ref()], &program_id)
+        );
+        assert_eq!(find_callback_authority(&program_id), program_pda(&[CALLBACK_AUTHORITY_SEED], &program_id));
+    }
+
+    #[test]
+    fn order_and_recipient_helpers_match_the_program_seeds() {
+        let program_id = Pubkey::new_unique();
+        let (swap_order, _) = find_swap_order(&program_id, 42);
+        let recipient = Pubkey::new_unique();
+
+        let cases = [
+            (find_escrow(&program_id, &swap_order), ESCROW_SEED, swap_order),
+            (find_dca_order(&program_id, &swap_order), DCA_ORDER_SEED, swap_order),
+            (find_limit_order(&program_id, &swap_order), LIMIT_ORDER_SEED, swap_order),
+            (find_order_extension(&program_id, &swap_order), ORDER_EXTENSION_SEED, swap_order),
+            (find_insurance_claim(&program_id, &swap_order), INSURANCE_CLAIM_SEED, swap_order),
+            (find_basket_plan(&program_id, &swap_order), BASKET_PLAN_SEED, swap_order),
+            (find_split_plan(&program_id, &swap_order), SPLIT_PLAN_SEED, swap_order),
+            (find_stream_plan(&program_id, &swap_order), STREAM_PLAN_SEED, swap_order),
+            (find_recipient_preferences(&program_id, &recipient), RECIPIENT_PREFERENCES_SEED, recipient),
+            (find_recipient_orders(&program_id, &recipient), RECIPIENT_ORDERS_SEED, recipient),
+        ];
+        for (derived, seed, key) in cases {
+            assert_eq!(derived, program_pda(&[seed, key.as_ref()], &program_id));
+        }
+    }
+
+    #[test]
+    fn token_accounts_are_owned_by_the_program_pdas() {
+        let program_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (swap_order, _) = find_swap_order(&program_id, 42);
+        let (escrow, _) = find_escrow(&program_id, &swap_order);
+
+        assert_eq!(
+            program_usdc_account(&program_id, &mint),
+            get_associated_token_address(&find_config(&program_id).0, &mint)
+        );
+        assert_eq!(
+            instance_program_usdc_account(&program_id, 7, &mint),
+            get_associated_token_address(&find_instance_config(&program_id, 7).0, &mint)
+        );
+        assert_eq!(
+            instance_insurance_usdc_account(&program_id, 7, &mint),
+            get_associated_token_address(&find_instance_insurance_vault(&program_id, 7).0, &mint)
+        );
+        assert_eq!(escrow_token_account(&program_id, &swap_order, &mint), get_associated_token_address(&escrow, &mint));
+        assert_eq!(
+            outbound_escrow_account(&program_id, 42, &mint),
+            get_associated_token_address(&find_outbound_order(&program_id, 42).0, &mint)
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use std::str::FromStr;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use crate::error::QuoteError;
+use crate::route::JupiterRoute;
+
+/// Public Jupiter swap API
+pub const DEFAULT_JUPITER_API_URL: &str = "https://quote-api.jup.ag/v6";
+
+/// Exact-in quote request
+#[derive(Debug, Clone)]
+pub struct QuoteRequest {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Input amount in base units
+    pub amount: u64,
+    pub slippage_bps: u16,
+    /// Caps the route's account count so it still fits next to the program's accounts
+    pub max_accounts: Option<u8>,
+}
+
+/// A Jupiter quote, kept verbatim for the swap-instructions request
+#[derive(Debug, Clone)]
+pub struct Quote {
+    /// Quoted output before slippage
+    pub out_amount: u64,
+    /// Output the route reverts below, after slippage
+    pub min_out_amount: u64,
+    response: Value,
+}
+
+/// Where the swap takes its input from and delivers its output to
+#[derive(Debug, Clone, Copy)]
+pub struct SwapAccounts {
+    /// Jupiter program the SuperSwap config allows
+    pub jupiter_program: Pubkey,
+    /// PDA holding the input: the program config for inbound fills, an
+    /// order's escrow for DCA tranches
+    pub swap_authority: Pubkey,
+    /// Token account receiving the output
+    pub destination_token_account: Pubkey,
+}
+
+/// Client for the Jupiter quote and swap-instructions API
+pub struct JupiterClient {
+    http: reqwest::Client,
+    api_url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructionsResponse {
+    swap_instruction: ApiInstruction,
+    #[serde(default)]
+    address_lookup_table_addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiInstruction {
+    program_id: String,
+    accounts: Vec<ApiAccountMeta>,
+    data: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiAccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructionsRequest<'a> {
+    quote_response: &'a Value,
+    user_public_key: String,
+    destination_token_account: String,
+    wrap_and_unwrap_sol: bool,
+    use_shared_accounts: bool,
+}
+
+impl JupiterClient {
+    pub fn new(api_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Quotes an exact-in swap
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<Quote, QuoteError> {
+        let mut query = vec![
+            ("inputMint", request.input_mint.to_string()),
+            ("outputMint", request.output_mint.to_string()),
+            ("amount", request.amount.to_string()),
+            ("slippageBps", request.slippage_bps.to_string()),
+            ("swapMode", "ExactIn".to_string()),
+        ];
+        if let Some(max_accounts) = request.max_accounts {
+            query.push(("maxAccounts", max_accounts.to_string()));
+        }
+
+        let response: Value = self
+            .http
+            .get(format!("{}/quote", self.api_url))
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Quote::from_response(response)
+    }
+
+    /// Compiles a quote into swap data and route accounts for the program
+    pub async fn swap_instructions(&self, quote: &Quote, accounts: &SwapAccounts) -> Result<JupiterRoute, QuoteError> {
+        let response: SwapInstructionsResponse = self
+            .http
+            .post(format!("{}/swap-instructions", self.api_url))
+            .json(&SwapInstructionsRequest {
+                quote_response: &quote.response,
+                user_public_key: accounts.swap_authority.to_string(),
+                destination_token_account: accounts.destination_token_account.to_string(),
+                wrap_and_unwrap_sol: false,
+                use_shared_accounts: true,
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        compile_route(accounts, response, quote)
+    }
+
+    /// Quotes an exact-in swap and compiles it for the program to execute
+    pub async fn route(&self, request: &QuoteRequest, accounts: &SwapAccounts) -> Result<JupiterRoute, QuoteError> {
+        let quote = self.quote(request).await?;
+        self.swap_instructions(&quote, accounts).await
+    }
+}
+
+impl Quote {
+    fn from_response(response: Value) -> Result<Self, QuoteError> {
+        Ok(Self {
+            out_amount: amount_field(&response, "outAmount")?,
+            min_out_amount: amount_field(&response, "otherAmountThreshold")?,
+            response,
+        })
+    }
+}
+
+/// Jupiter encodes amounts as decimal strings
+fn amount_field(response: &Value, field: &'static str) -> Result<u64, QuoteError> {
+    let value = response
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or(QuoteError::MissingField(field))?;
+    value.parse().map_err(|_| QuoteError::InvalidField {
+        field,
+        value: value.to_string(),
+    })
+}
+
+fn parse_pubkey(field: &'static str, value: &str) -> Result<Pubkey, QuoteError> {
+    Pubkey::from_str(value).map_err(|_| QuoteError::InvalidField {
+        field,
+        value: value.to_string(),
+    })
+}
+
+fn compile_route(
+    accounts: &SwapAccounts,
+    response: SwapInstructionsResponse,
+    quote: &Quote,
+) -> Result<JupiterRoute, QuoteError> {
+    let api_instruction = response.swap_instruction;
+    let program_id = parse_pubkey("programId", &api_instruction.program_id)?;
+    if program_id != accounts.jupiter_program {
+        return Err(QuoteError::UnexpectedProgram {
+            expected: accounts.jupiter_program,
+            actual: program_id,
+        });
+    }
+
+    let instruction = Instruction {
+        program_id,
+        accounts: api_instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                Ok(AccountMeta {
+                    pubkey: parse_pubkey("account", &meta.pubkey)?,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+            })
+            .collect::<Result<_, QuoteError>>()?,
+        data: BASE64
+            .decode(&api_instruction.data)
+            .map_err(|_| QuoteError::InvalidField {
+                field: "data",
+                value: api_instruction.data.clone(),
+            })?,
+    };
+
+    let lookup_tables = response
+        .address_lookup_table_addresses
+        .iter()
+        .map(|address| parse_pubkey("addressLookupTableAddresses", address))
+        .collect::<Result<_, _>>()?;
+
+    Ok(JupiterRoute::from_instruction(
+        instruction,
+        &accounts.swap_authority,
+        lookup_tables,
+        quote.out_amount,
+        quote.min_out_amount,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn quote() -> Quote {
+        Quote::from_response(json!({ "outAmount": "1000", "otherAmountThreshold": "995" })).unwrap()
+    }
+
+    fn swap_accounts() -> SwapAccounts {
+        SwapAccounts {
+            jupiter_program: Pubkey::new_unique(),
+            swap_authority: Pubkey::new_unique(),
+            destination_token_account: Pubkey::new_unique(),
+        }
+    }
+
+    fn response(program_id: &Pubkey, authority: &Pubkey, lookup_table: &Pubkey) -> SwapInstructionsResponse {
+        serde_json::from_value(json!({
+            "swapInstruction": {
+                "programId": program_id.to_string(),
+                "accounts": [
+                    { "pubkey": authority.to_string(), "isSigner": true, "isWritable": false },
+                    { "pubkey": Pubkey::new_unique().to_string(), "isSigner": false, "isWritable": true },
+                ],
+                "data": BASE64.encode([7u8, 8, 9]),
+            },
+            "addressLookupTableAddresses": [lookup_table.to_string()],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn route_carries_quote_amounts_and_lookup_tables() {
+        let accounts = swap_accounts();
+        let lookup_table = Pubkey::new_unique();
+        let response = response(&accounts.jupiter_program, &accounts.swap_authority, &lookup_table);
+
+        let route = compile_route(&accounts, response, &quote()).unwrap();
+        assert!(!route.accounts[0].is_signer);
+        assert_eq!(route.swap_data, vec![7, 8, 9]);
+        assert_eq!(route.lookup_tables, vec![lookup_table]);
+        assert_eq!((route.out_amount, route.min_out_amount), (1000, 995));
+    }
+
+    #[test]
+    fn route_for_another_program_is_rejected() {
+        let accounts = swap_accounts();
+        let response = response(&Pubkey::new_unique(), &accounts.swap_authority, &Pubkey::new_unique());
+
+        assert!(matches!(
+            compile_route(&accounts, response, &quote()),
+            Err(QuoteError::UnexpectedProgram { .. })
+        ));
+    }
+
+    #[test]
+    fn quote_without_amounts_is_rejected() {
+        assert!(matches!(
+            Quote::from_response(json!({ "outAmount": "1000" })),
+            Err(QuoteError::MissingField("otherAmountThreshold"))
+        ));
+        assert!(matches!(
+            Quote::from_response(json!({ "outAmount": "lots", "otherAmountThreshold": "1" })),
+            Err(QuoteError::InvalidField { field: "outAmount", .. })
+        ));
+    }
+}
@@ -0,0 +1,17 @@
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum QuoteError {
+    #[error("Jupiter API request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Jupiter response is missing {0}")]
+    MissingField(&'static str),
+
+    #[error("Jupiter response has an invalid {field}: {value}")]
+    InvalidField { field: &'static str, value: String },
+
+    #[error("Jupiter returned a route for {actual}, but the program expects {expected}")]
+    UnexpectedProgram { expected: Pubkey, actual: Pubkey },
+}
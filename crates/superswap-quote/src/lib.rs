@@ -0,0 +1,15 @@
+//! Jupiter quotes compiled for the SuperSwap program
+//!
+//! Wraps Jupiter's `/quote` and `/swap-instructions` API and turns the
+//! response into what SuperSwap instructions take: `jupiter_swap_data`, the
+//! route's remaining accounts and the lookup tables they live in. Shared by
+//! the relayer, the CLI and the integration tests, which build the same
+//! [`JupiterRoute`] from a mock pool instead of the API.
+
+pub mod client;
+pub mod error;
+pub mod route;
+
+pub use client::*;
+pub use error::QuoteError;
+pub use route::JupiterRoute;
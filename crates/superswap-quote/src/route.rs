@@ -0,0 +1,76 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+
+/// A quoted route compiled for CPI from the program
+///
+/// `swap_data` is passed as `jupiter_swap_data` and `accounts` as the
+/// instruction's remaining accounts; `lookup_tables` must be resolved and
+/// attached to the v0 transaction carrying it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JupiterRoute {
+    /// Jupiter instruction data
+    pub swap_data: Vec<u8>,
+    /// Route accounts, in Jupiter's order
+    pub accounts: Vec<AccountMeta>,
+    /// Lookup tables the route's accounts live in
+    pub lookup_tables: Vec<Pubkey>,
+    /// Quoted output before slippage
+    pub out_amount: u64,
+    /// Output the route reverts below, after slippage
+    pub min_out_amount: u64,
+}
+
+impl JupiterRoute {
+    /// Compiles a swap instruction for the program to execute on behalf of `swap_authority`
+    ///
+    /// The swap authority is a PDA that signs through the program, so it is
+    /// never left marked as a transaction signer.
+    pub fn from_instruction(
+        instruction: Instruction,
+        swap_authority: &Pubkey,
+        lookup_tables: Vec<Pubkey>,
+        out_amount: u64,
+        min_out_amount: u64,
+    ) -> Self {
+        let accounts = instruction
+            .accounts
+            .into_iter()
+            .map(|meta| AccountMeta {
+                is_signer: meta.is_signer && meta.pubkey != *swap_authority,
+                ..meta
+            })
+            .collect();
+
+        Self {
+            swap_data: instruction.data,
+            accounts,
+            lookup_tables,
+            out_amount,
+            min_out_amount,
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_authority_is_not_a_transaction_signer() {
+        let authority = Pubkey::new_unique();
+        let instruction = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ],
+            data: vec![7, 8, 9],
+        };
+
+        let route = JupiterRoute::from_instruction(instruction, &authority, Vec::new(), 10, 9);
+        assert!(!route.accounts[0].is_signer);
+        assert!(route.accounts[1].is_writable);
+        assert_eq!(route.swap_data, vec![7, 8, 9]);
+    }
+}
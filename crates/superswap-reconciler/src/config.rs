@@ -0,0 +1,123 @@
+use std::path::Path;
+use anyhow::{ensure, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use superswap_sdk::EvmAddress;
+
+/// Across chain id of Solana mainnet
+pub const SOLANA_CHAIN_ID: u64 = 34268394551451;
+
+/// Reconciler configuration, loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ReconcilerConfig {
+    /// Solana JSON RPC endpoint
+    pub rpc_url: String,
+    #[serde(with = "pubkey_string", default = "default_program_id")]
+    pub program_id: Pubkey,
+    /// Across chain id deposits to SuperSwap are destined for
+    #[serde(default = "default_destination_chain_id")]
+    pub destination_chain_id: u64,
+    /// How long after a deposit its order account must exist before it is flagged
+    #[serde(default = "default_fill_timeout_secs")]
+    pub fill_timeout_secs: u64,
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    pub chains: Vec<EvmChainConfig>,
+}
+
+/// An origin chain whose spoke pool is scanned for deposits
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct EvmChainConfig {
+    pub name: String,
+    pub rpc_url: String,
+    #[serde(with = "evm_address_string")]
+    pub spoke_pool: EvmAddress,
+    /// First block scanned
+    pub start_block: u64,
+    /// Blocks behind the head considered final
+    #[serde(default)]
+    pub confirmations: u64,
+    /// Largest block range the RPC accepts in one `eth_getLogs`
+    #[serde(default = "default_max_block_range")]
+    pub max_block_range: u64,
+}
+
+impl ReconcilerConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        let config: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse config {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(!self.chains.is_empty(), "no origin chains configured");
+        for chain in &self.chains {
+            ensure!(chain.max_block_range > 0, "{}: max_block_range must be positive", chain.name);
+        }
+        Ok(())
+    }
+}
+
+fn default_program_id() -> Pubkey {
+    superswap_sol::ID
+}
+
+fn default_destination_chain_id() -> u64 {
+    SOLANA_CHAIN_ID
+}
+
+fn default_fill_timeout_secs() -> u64 {
+    30 * 60
+}
+
+fn default_poll_interval_ms() -> u64 {
+    15_000
+}
+
+fn default_max_block_range() -> u64 {
+    2_000
+}
+
+mod pubkey_string {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Pubkey::from_str(&raw).map_err(D::Error::custom)
+    }
+}
+
+mod evm_address_string {
+    use serde::{de::Error, Deserialize, Deserializer};
+    use superswap_sdk::{parse_evm_address, EvmAddress};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<EvmAddress, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        parse_evm_address(&raw).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use superswap_sdk::parse_evm_address;
+
+    #[test]
+    fn example_config_parses() {
+        let raw = include_str!("../reconciler.example.json");
+        let config: ReconcilerConfig = serde_json::from_str(raw).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.destination_chain_id, SOLANA_CHAIN_ID);
+        assert_eq!(
+            config.chains[0].spoke_pool,
+            parse_evm_address("0x5c7BCd6E7De5423a257D81B442095A1a6ced35C5").unwrap()
+        );
+    }
+}
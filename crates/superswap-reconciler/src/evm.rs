@@ -0,0 +1,249 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::keccak;
+use solana_sdk::pubkey::Pubkey;
+use superswap_sdk::EvmAddress;
+
+/// Across V3 spoke pool deposit event
+pub const FUNDS_DEPOSITED_EVENT: &str =
+    "FundsDeposited(bytes32,bytes32,uint256,uint256,uint256,uint256,uint32,uint32,uint32,bytes32,bytes32,bytes32,bytes)";
+
+// Non-indexed fields, one 32-byte word each: inputToken, outputToken,
+// inputAmount, outputAmount, quoteTimestamp, fillDeadline,
+// exclusivityDeadline, recipient, exclusiveRelayer, message offset
+const DATA_OUTPUT_TOKEN: usize = 1;
+const DATA_OUTPUT_AMOUNT: usize = 3;
+const DATA_QUOTE_TIMESTAMP: usize = 4;
+const DATA_FILL_DEADLINE: usize = 5;
+const DATA_RECIPIENT: usize = 7;
+const DATA_MESSAGE_OFFSET: usize = 9;
+
+/// A deposit into an origin chain spoke pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deposit {
+    pub transaction_hash: String,
+    pub block_number: u64,
+    /// Spoke pool deposit id, as a hex word
+    pub deposit_id: String,
+    pub output_token: Pubkey,
+    /// USDC the depositor expects on Solana, before the relayer fee is known
+    pub output_amount: u64,
+    /// Quote time of the deposit, within minutes of its inclusion
+    pub quote_timestamp: u32,
+    /// Across refunds the depositor on the origin chain if unfilled by then
+    pub fill_deadline: u32,
+    pub recipient: Pubkey,
+    pub message: Vec<u8>,
+}
+
+/// Minimal JSON RPC client for an origin chain
+pub struct EvmClient {
+    http: reqwest::Client,
+    rpc_url: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub topics: Vec<String>,
+    pub data: String,
+    pub block_number: String,
+    pub transaction_hash: String,
+}
+
+impl EvmClient {
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            rpc_url: rpc_url.to_string(),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let response: RpcResponse<T> = self
+            .http
+            .post(&self.rpc_url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("malformed {} response", method))?;
+        if let Some(error) = response.error {
+            return Err(anyhow!("{} failed ({}): {}", method, error.code, error.message));
+        }
+        response.result.ok_or_else(|| anyhow!("{} returned no result", method))
+    }
+
+    pub async fn block_number(&self) -> Result<u64> {
+        let raw: String = self.call("eth_blockNumber", json!([])).await?;
+        parse_quantity(&raw)
+    }
+
+    /// Deposits from `spoke_pool` to `destination_chain_id` within `from..=to`
+    pub async fn deposits(
+        &self,
+        spoke_pool: &EvmAddress,
+        destination_chain_id: u64,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<Deposit>> {
+        let logs: Vec<Log> = self
+            .call(
+                "eth_getLogs",
+                json!([{
+                    "address": format!("0x{}", encode_hex(spoke_pool)),
+                    "fromBlock": format!("{:#x}", from),
+                    "toBlock": format!("{:#x}", to),
+                    "topics": [event_topic(), uint_topic(destination_chain_id)],
+                }]),
+            )
+            .await?;
+        logs.iter().map(decode_deposit).collect()
+    }
+}
+
+/// Topic of the `FundsDeposited` event
+pub fn event_topic() -> String {
+    format!("0x{}", encode_hex(&keccak::hash(FUNDS_DEPOSITED_EVENT.as_bytes()).to_bytes()))
+}
+
+fn uint_topic(value: u64) -> String {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    format!("0x{}", encode_hex(&word))
+}
+
+pub fn decode_deposit(log: &Log) -> Result<Deposit> {
+    let deposit_id = log
+        .topics
+        .get(2)
+        .ok_or_else(|| anyhow!("deposit log {} has no deposit id topic", log.transaction_hash))?;
+    let data = decode_hex(&log.data)?;
+    let word = |index: usize| -> Result<&[u8]> {
+        data.get(index * 32..(index + 1) * 32)
+            .ok_or_else(|| anyhow!("truncated deposit log {}", log.transaction_hash))
+    };
+
+    let message_offset = read_u64(word(DATA_MESSAGE_OFFSET)?)? as usize;
+    let message_len = data
+        .get(message_offset..message_offset.saturating_add(32))
+        .ok_or_else(|| anyhow!("truncated deposit message in {}", log.transaction_hash))
+        .and_then(read_u64)? as usize;
+    let message_start = message_offset + 32;
+    let message = data
+        .get(message_start..message_start.saturating_add(message_len))
+        .ok_or_else(|| anyhow!("truncated deposit message in {}", log.transaction_hash))?
+        .to_vec();
+
+    Ok(Deposit {
+        transaction_hash: log.transaction_hash.clone(),
+        block_number: parse_quantity(&log.block_number)?,
+        deposit_id: deposit_id.clone(),
+        output_token: Pubkey::try_from(word(DATA_OUTPUT_TOKEN)?)?,
+        output_amount: read_u64(word(DATA_OUTPUT_AMOUNT)?)?,
+        quote_timestamp: read_u64(word(DATA_QUOTE_TIMESTAMP)?)? as u32,
+        fill_deadline: read_u64(word(DATA_FILL_DEADLINE)?)? as u32,
+        recipient: Pubkey::try_from(word(DATA_RECIPIENT)?)?,
+        message,
+    })
+}
+
+/// Reads a big-endian uint256 word that must fit in a u64
+fn read_u64(word: &[u8]) -> Result<u64> {
+    ensure!(word[..24].iter().all(|byte| *byte == 0), "value exceeds u64");
+    Ok(u64::from_be_bytes(word[24..32].try_into()?))
+}
+
+fn parse_quantity(raw: &str) -> Result<u64> {
+    let digits = raw.strip_prefix("0x").ok_or_else(|| anyhow!("invalid quantity {}", raw))?;
+    Ok(u64::from_str_radix(digits, 16)?)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(raw: &str) -> Result<Vec<u8>> {
+    let raw = raw.strip_prefix("0x").unwrap_or(raw);
+    raw.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            ensure!(pair.len() == 2, "odd-length hex string");
+            Ok(u8::from_str_radix(std::str::from_utf8(pair)?, 16)?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn word(value: u64) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    /// ABI-encodes a deposit log the way the spoke pool emits it
+    pub(crate) fn deposit_log(recipient: &Pubkey, output_token: &Pubkey, quote_timestamp: u32, message: &[u8]) -> Log {
+        let mut data = Vec::new();
+        data.extend_from_slice(&[0u8; 32]);
+        data.extend_from_slice(output_token.as_ref());
+        data.extend_from_slice(&word(1_000_000));
+        data.extend_from_slice(&word(999_000));
+        data.extend_from_slice(&word(quote_timestamp as u64));
+        data.extend_from_slice(&word(quote_timestamp as u64 + 3_600));
+        data.extend_from_slice(&word(0));
+        data.extend_from_slice(recipient.as_ref());
+        data.extend_from_slice(&[0u8; 32]);
+        data.extend_from_slice(&word(10 * 32));
+        data.extend_from_slice(&word(message.len() as u64));
+        data.extend_from_slice(message);
+        data.resize(data.len().div_ceil(32) * 32, 0);
+
+        Log {
+            topics: vec![event_topic(), uint_topic(34268394551451), uint_topic(77), uint_topic(0)],
+            data: format!("0x{}", encode_hex(&data)),
+            block_number: "0x10".to_string(),
+            transaction_hash: "0xabc".to_string(),
+        }
+    }
+
+    #[test]
+    fn deposit_log_decodes() {
+        let recipient = Pubkey::new_unique();
+        let usdc = Pubkey::new_unique();
+        let deposit = decode_deposit(&deposit_log(&recipient, &usdc, 1_700_000_000, &[1, 2, 3])).unwrap();
+
+        assert_eq!(deposit.recipient, recipient);
+        assert_eq!(deposit.output_token, usdc);
+        assert_eq!(deposit.output_amount, 999_000);
+        assert_eq!(deposit.quote_timestamp, 1_700_000_000);
+        assert_eq!(deposit.fill_deadline, 1_700_003_600);
+        assert_eq!(deposit.block_number, 16);
+        assert_eq!(deposit.message, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn truncated_deposit_log_is_rejected() {
+        let mut log = deposit_log(&Pubkey::new_unique(), &Pubkey::new_unique(), 0, &[1, 2, 3]);
+        log.data.truncate(2 + 64 * 9);
+        assert!(decode_deposit(&log).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Cross-chain reconciler for SuperSwap
+//!
+//! Scans the Across spoke pools of the configured origin chains for deposits
+//! to Solana carrying a SuperSwap order for the program's Across handler, and
+//! checks each against its `SwapOrder` account. Deposits still without an
+//! order account after the fill timeout are flagged until they fill; past the
+//! order's deadline a fill refunds the USDC to the recipient instead of swapping.
+
+mod config;
+mod evm;
+mod reconcile;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anchor_lang::AccountDeserialize;
+use anyhow::{Context, Result};
+use clap::Parser;
+use log::{error, info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use superswap_pda::{find_config, find_swap_order};
+use superswap_sdk::{to_checksum_address, OrderMessage};
+use superswap_sol::state::{Config, OrderStatus, SwapOrder};
+use crate::config::{EvmChainConfig, ReconcilerConfig};
+use crate::evm::{Deposit, EvmClient};
+use crate::reconcile::{classify, Finding};
+
+/// Accounts fetched per `getMultipleAccounts` call
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Parser)]
+#[command(name = "superswap-reconciler", about = "Flags SuperSwap deposits with no fill on Solana")]
+struct Args {
+    /// Path to the reconciler's JSON config
+    #[arg(long, env = "SUPERSWAP_RECONCILER_CONFIG")]
+    config: PathBuf,
+
+    /// Scan up to the current head once, then exit non-zero if any deposit is flagged
+    #[arg(long)]
+    once: bool,
+}
+
+/// Walks one origin chain's spoke pool logs up to its confirmed head
+struct ChainScanner {
+    config: EvmChainConfig,
+    client: EvmClient,
+    next_block: u64,
+}
+
+/// A SuperSwap deposit not yet matched with its order account
+struct Outstanding {
+    chain: String,
+    deposit: Deposit,
+    flagged: bool,
+}
+
+impl ChainScanner {
+    fn new(config: EvmChainConfig) -> Self {
+        Self {
+            client: EvmClient::new(&config.rpc_url),
+            next_block: config.start_block,
+            config,
+        }
+    }
+
+    async fn scan(&mut self, destination_chain_id: u64) -> Result<Vec<Deposit>> {
+        let head = self.client.block_number().await?.saturating_sub(self.config.confirmations);
+        let mut deposits = Vec::new();
+        while self.next_block <= head {
+            let to = head.min(self.next_block + self.config.max_block_range - 1);
+            deposits.extend(
+                self.client
+                    .deposits(&self.config.spoke_pool, destination_chain_id, self.next_block, to)
+                    .await?,
+            );
+            self.next_block = to + 1;
+        }
+        Ok(deposits)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    let config = ReconcilerConfig::load(&args.config)?;
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+    let program_config = fetch_program_config(&rpc, &config.program_id).await?;
+
+    let mut scanners: Vec<ChainScanner> = config.chains.iter().cloned().map(ChainScanner::new).collect();
+    for scanner in &scanners {
+        info!(
+            "scanning {} spoke pool {} from block {}",
+            scanner.config.name,
+            to_checksum_address(&scanner.config.spoke_pool),
+            scanner.next_block
+        );
+    }
+
+    // Keyed by chain and deposit id, which together identify an Across deposit
+    let mut outstanding: BTreeMap<(String, String), Outstanding> = BTreeMap::new();
+    loop {
+        for scanner in scanners.iter_mut() {
+            match scanner.scan(config.destination_chain_id).await {
+                Ok(deposits) => {
+                    for deposit in deposits {
+                        // Only deposits the relayer is expected to fill are reconciled
+                        if !program_config.is_across_handler(&deposit.recipient)
+                            || deposit.output_token != program_config.usdc_mint
+                            || deposit.message.is_empty()
+                        {
+                            continue;
+                        }
+                        let key = (scanner.config.name.clone(), deposit.deposit_id.clone());
+                        outstanding.entry(key).or_insert(Outstanding {
+                            chain: scanner.config.name.clone(),
+                            deposit,
+                            flagged: false,
+                        });
+                    }
+                }
+                Err(err) => warn!("{} scan failed: {:#}", scanner.config.name, err),
+            }
+        }
+
+        let flagged = match reconcile(&rpc, &config, &mut outstanding).await {
+            Ok(flagged) => flagged,
+            Err(err) => {
+                error!("reconciliation failed: {:#}", err);
+                if args.once {
+                    return Err(err);
+                }
+                0
+            }
+        };
+
+        if args.once {
+            info!("{} deposits outstanding, {} flagged", outstanding.len(), flagged);
+            if flagged > 0 {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(config.poll_interval_ms)).await;
+    }
+}
+
+/// Matches outstanding deposits with their order accounts, returning how many are flagged
+async fn reconcile(
+    rpc: &RpcClient,
+    config: &ReconcilerConfig,
+    outstanding: &mut BTreeMap<(String, String), Outstanding>,
+) -> Result<usize> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let keys: Vec<(String, String)> = outstanding.keys().cloned().collect();
+    let order_ids: Vec<Option<u64>> = keys
+        .iter()
+        .map(|key| OrderMessage::decode(&outstanding[key].deposit.message).ok().map(|message| message.order_id))
+        .collect();
+    let statuses = order_statuses(rpc, &config.program_id, &order_ids).await?;
+
+    let mut flagged = 0;
+    for (key, status) in keys.into_iter().zip(statuses) {
+        let Some(entry) = outstanding.get_mut(&key) else {
+            continue;
+        };
+        let deposit = &entry.deposit;
+        let finding = classify(deposit, status, now, config.fill_timeout_secs);
+        if finding.is_flagged() {
+            flagged += 1;
+        }
+        match finding {
+            Finding::Filled { order_id, status } => {
+                if entry.flagged {
+                    info!("order {} from {} deposit {} is now filled ({:?})", order_id, entry.chain, deposit.transaction_hash, status);
+                }
+                outstanding.remove(&key);
+            }
+            Finding::Awaiting { .. } => {}
+            Finding::Unfilled { order_id, age_secs, past_deadline } => {
+                if !entry.flagged {
+                    warn!(
+                        "UNFILLED order {}: {} deposit {} ({} USDC) has no order account after {}s{}",
+                        order_id,
+                        entry.chain,
+                        deposit.transaction_hash,
+                        deposit.output_amount,
+                        age_secs,
+                        if past_deadline { "; past its deadline, filling it refunds the recipient" } else { "" }
+                    );
+                    entry.flagged = true;
+                }
+            }
+            Finding::Undecodable { reason } => {
+                warn!(
+                    "UNDECODABLE {} deposit {} ({} USDC) to the handler: {}",
+                    entry.chain, deposit.transaction_hash, deposit.output_amount, reason
+                );
+                outstanding.remove(&key);
+            }
+        }
+    }
+    Ok(flagged)
+}
+
+/// Status of each order's `SwapOrder` account, `None` where it does not exist
+async fn order_statuses(rpc: &RpcClient, program_id: &Pubkey, order_ids: &[Option<u64>]) -> Result<Vec<Option<OrderStatus>>> {
+    let mut statuses = vec![None; order_ids.len()];
+    let known: Vec<(usize, Pubkey)> = order_ids
+        .iter()
+        .enumerate()
+        .filter_map(|(index, order_id)| order_id.map(|order_id| (index, find_swap_order(program_id, order_id).0)))
+        .collect();
+
+    for chunk in known.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let addresses: Vec<Pubkey> = chunk.iter().map(|(_, address)| *address).collect();
+        let accounts = rpc.get_multiple_accounts(&addresses).await?;
+        for ((index, address), account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account {
+                let order = SwapOrder::try_deserialize(&mut account.data.as_slice())
+                    .with_context(|| format!("malformed swap order {}", address))?;
+                statuses[*index] = Some(order.status);
+            }
+        }
+    }
+    Ok(statuses)
+}
+
+async fn fetch_program_config(rpc: &RpcClient, program_id: &Pubkey) -> Result<Config> {
+    let address = find_config(program_id).0;
+    let account = rpc
+        .get_account(&address)
+        .await
+        .with_context(|| format!("program config {} not found", address))?;
+    Ok(Config::try_deserialize(&mut account.data.as_slice())?)
+}
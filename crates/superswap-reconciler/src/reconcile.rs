@@ -0,0 +1,113 @@
+use superswap_sdk::OrderMessage;
+use superswap_sol::state::OrderStatus;
+use crate::evm::Deposit;
+
+/// Where a SuperSwap deposit stands on Solana
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    /// The order account exists; the fill landed
+    Filled { order_id: u64, status: OrderStatus },
+    /// No order account yet, but the deposit is younger than the fill timeout
+    Awaiting { order_id: u64 },
+    /// No order account after the fill timeout
+    ///
+    /// Past the order's deadline the fill no longer swaps: it refunds the
+    /// bridged USDC to the recipient, so the handler should still submit it.
+    Unfilled { order_id: u64, age_secs: u64, past_deadline: bool },
+    /// A deposit to the handler whose message is not a SuperSwap order
+    Undecodable { reason: String },
+}
+
+impl Finding {
+    /// Whether an operator needs to look at the deposit
+    pub fn is_flagged(&self) -> bool {
+        matches!(self, Finding::Unfilled { .. } | Finding::Undecodable { .. })
+    }
+}
+
+/// Classifies a deposit against its order account
+///
+/// `order_status` is `None` when the order's `SwapOrder` account does not
+/// exist. The deposit's age is measured from its quote timestamp.
+pub fn classify(deposit: &Deposit, order_status: Option<OrderStatus>, now: i64, fill_timeout_secs: u64) -> Finding {
+    let message = match OrderMessage::decode(&deposit.message) {
+        Ok(message) => message,
+        Err(err) => return Finding::Undecodable { reason: err.to_string() },
+    };
+
+    if let Some(status) = order_status {
+        return Finding::Filled {
+            order_id: message.order_id,
+            status,
+        };
+    }
+
+    let age_secs = now.saturating_sub(deposit.quote_timestamp as i64).max(0) as u64;
+    if age_secs < fill_timeout_secs {
+        return Finding::Awaiting {
+            order_id: message.order_id,
+        };
+    }
+
+    Finding::Unfilled {
+        order_id: message.order_id,
+        age_secs,
+        past_deadline: now > message.deadline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorSerialize;
+    use solana_sdk::pubkey::Pubkey;
+    use crate::evm::decode_deposit;
+    use crate::evm::tests::deposit_log;
+
+    const QUOTED_AT: u32 = 1_700_000_000;
+    const TIMEOUT: u64 = 1_800;
+
+    fn deposit(deadline: i64) -> Deposit {
+        let message = OrderMessage {
+            order_id: 9,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000_000,
+            min_output_amount: 1,
+            destination_mint: Pubkey::new_unique(),
+            deadline,
+            jupiter_swap_data: Vec::new(),
+            relayer_tip: None,
+            allow_off_curve_recipient: None,
+        };
+        let log = deposit_log(&Pubkey::new_unique(), &Pubkey::new_unique(), QUOTED_AT, &message.try_to_vec().unwrap());
+        decode_deposit(&log).unwrap()
+    }
+
+    #[test]
+    fn existing_order_is_filled() {
+        let finding = classify(&deposit(0), Some(OrderStatus::Completed), i64::MAX, TIMEOUT);
+        assert_eq!(finding, Finding::Filled { order_id: 9, status: OrderStatus::Completed });
+        assert!(!finding.is_flagged());
+    }
+
+    #[test]
+    fn missing_order_is_flagged_only_after_the_timeout() {
+        let deadline = QUOTED_AT as i64 + 3_600;
+        let young = classify(&deposit(deadline), None, QUOTED_AT as i64 + 60, TIMEOUT);
+        assert_eq!(young, Finding::Awaiting { order_id: 9 });
+
+        let stale = classify(&deposit(deadline), None, QUOTED_AT as i64 + 1_800, TIMEOUT);
+        assert_eq!(stale, Finding::Unfilled { order_id: 9, age_secs: 1_800, past_deadline: false });
+        assert!(stale.is_flagged());
+
+        let expired = classify(&deposit(deadline), None, deadline + 1, TIMEOUT);
+        assert!(matches!(expired, Finding::Unfilled { past_deadline: true, .. }));
+    }
+
+    #[test]
+    fn foreign_message_is_flagged() {
+        let mut foreign = deposit(0);
+        foreign.message = vec![1, 2, 3];
+        assert!(classify(&foreign, None, 0, TIMEOUT).is_flagged());
+    }
+}
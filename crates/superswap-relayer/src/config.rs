@@ -0,0 +1,288 @@
+use std::path::{Path, PathBuf};
+use anyhow::{ensure, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Relayer configuration, loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RelayerConfig {
+    pub rpc_url: String,
+    /// Key of the program's Across handler, which signs and pays for fills
+    pub signer: SignerConfig,
+    #[serde(with = "pubkey_string", default = "default_program_id")]
+    pub program_id: Pubkey,
+    #[serde(default = "default_jupiter_api_url")]
+    pub jupiter_api_url: String,
+    /// Slippage tolerance for fresh Jupiter quotes; the order's minimum output still binds
+    #[serde(default = "default_slippage_bps")]
+    pub slippage_bps: u16,
+    /// Lookup tables holding the program's static accounts
+    #[serde(with = "pubkey_strings", default)]
+    pub lookup_tables: Vec<Pubkey>,
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    #[serde(default)]
+    pub priority_fee: PriorityFeeConfig,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    /// Watch the spoke pool recorded in the program config for Across fills
+    #[serde(default = "default_watch_across")]
+    pub watch_across: bool,
+    pub cctp: Option<CctpSourceConfig>,
+}
+
+/// Where the Across handler's key lives
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SignerConfig {
+    /// Keypair file on the relayer host
+    Keypair { path: PathBuf },
+    /// Ed25519 key held in AWS KMS
+    AwsKms(KmsSignerConfig),
+    /// Raw signing through a Fireblocks-style custody API
+    Fireblocks(FireblocksSignerConfig),
+}
+
+/// AWS KMS key signing with `ED25519_SHA_512`; credentials come from the
+/// standard `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`/`AWS_SESSION_TOKEN` variables
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct KmsSignerConfig {
+    pub region: String,
+    /// Key id, ARN or alias
+    pub key_id: String,
+    /// Solana address of the key, checked against every signature
+    #[serde(with = "pubkey_string")]
+    pub pubkey: Pubkey,
+    /// Overrides the regional endpoint, e.g. for a VPC endpoint
+    pub endpoint: Option<String>,
+}
+
+/// Vault account signing raw messages through a Fireblocks-style API
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct FireblocksSignerConfig {
+    #[serde(default = "default_fireblocks_api_url")]
+    pub api_url: String,
+    pub api_key: String,
+    /// PKCS#8 PEM file with the API user's RSA secret key
+    pub secret_key_path: PathBuf,
+    pub vault_account_id: String,
+    #[serde(default = "default_fireblocks_asset_id")]
+    pub asset_id: String,
+    /// Solana address of the vault account, checked against every signature
+    #[serde(with = "pubkey_string")]
+    pub pubkey: Pubkey,
+    /// Interval between polls for the signing request's approval
+    #[serde(default = "default_fireblocks_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// How long a signing request may wait for approval before the fill attempt fails
+    #[serde(default = "default_fireblocks_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+/// Priority fee policy, in micro-lamports per compute unit
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PriorityFeeConfig {
+    /// Percentile of recent fees paid on the fill's writable accounts
+    pub percentile: u8,
+    pub min_micro_lamports: u64,
+    pub max_micro_lamports: u64,
+    /// Fee multiplier applied on each retry, in percent
+    pub escalation_percent: u64,
+    /// Upper bound on the compute unit limit, and the limit fills are simulated with
+    pub compute_unit_limit: u32,
+    /// Margin added to the simulated compute units when sizing the fill's limit, in percent
+    #[serde(default = "default_compute_unit_headroom_percent")]
+    pub compute_unit_headroom_percent: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            min_micro_lamports: 1_000,
+            max_micro_lamports: 2_000_000,
+            escalation_percent: 150,
+            compute_unit_limit: 1_200_000,
+            compute_unit_headroom_percent: default_compute_unit_headroom_percent(),
+        }
+    }
+}
+
+/// Submission retry policy
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// How long to wait for a sent transaction to confirm before resending
+    pub confirm_timeout_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 6,
+            base_delay_ms: 500,
+            max_delay_ms: 15_000,
+            confirm_timeout_ms: 30_000,
+        }
+    }
+}
+
+/// Polls Circle attestations for CCTP burns minting to the Across handler
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct CctpSourceConfig {
+    #[serde(default = "default_iris_api_url")]
+    pub iris_api_url: String,
+    /// Directory the frontend drops burn intents into, one JSON file per burn
+    pub pending_dir: PathBuf,
+    /// Token messenger's fee recipient USDC account, credited by fast-transfer mints
+    #[serde(with = "pubkey_string")]
+    pub fee_recipient_token_account: Pubkey,
+}
+
+impl RelayerConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        let config: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse config {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.watch_across || self.cctp.is_some(),
+            "`watch_across` is disabled and `cctp` is not configured"
+        );
+        let fees = &self.priority_fee;
+        ensure!(
+            (1..=100).contains(&fees.percentile),
+            "priority_fee.percentile must be within 1..=100"
+        );
+        ensure!(
+            fees.min_micro_lamports <= fees.max_micro_lamports,
+            "priority_fee.min_micro_lamports exceeds max_micro_lamports"
+        );
+        ensure!(self.retry.max_attempts > 0, "retry.max_attempts must be positive");
+        if let SignerConfig::Fireblocks(fireblocks) = &self.signer {
+            ensure!(fireblocks.poll_interval_ms > 0, "signer.poll_interval_ms must be positive");
+        }
+        Ok(())
+    }
+}
+
+fn default_program_id() -> Pubkey {
+    superswap_sol::ID
+}
+
+fn default_jupiter_api_url() -> String {
+    superswap_quote::DEFAULT_JUPITER_API_URL.to_string()
+}
+
+fn default_slippage_bps() -> u16 {
+    50
+}
+
+fn default_watch_across() -> bool {
+    true
+}
+
+fn default_iris_api_url() -> String {
+    "https://iris-api.circle.com".to_string()
+}
+
+fn default_poll_interval_ms() -> u64 {
+    2_000
+}
+
+fn default_fireblocks_api_url() -> String {
+    "https://api.fireblocks.io".to_string()
+}
+
+fn default_fireblocks_asset_id() -> String {
+    "SOL".to_string()
+}
+
+fn default_fireblocks_poll_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_fireblocks_timeout_ms() -> u64 {
+    120_000
+}
+
+fn default_compute_unit_headroom_percent() -> u64 {
+    20
+}
+
+mod pubkey_string {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Pubkey::from_str(&raw).map_err(D::Error::custom)
+    }
+}
+
+mod pubkey_strings {
+    use std::str::FromStr;
+    use serde::{de::Error, Deserialize, Deserializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Pubkey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|raw| Pubkey::from_str(raw).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_parses() {
+        let raw = include_str!("../relayer.example.json");
+        let config: RelayerConfig = serde_json::from_str(raw).unwrap();
+        config.validate().unwrap();
+        assert!(matches!(config.signer, SignerConfig::Keypair { .. }));
+    }
+
+    #[test]
+    fn remote_signers_parse() {
+        let kms: SignerConfig = serde_json::from_str(
+            r#"{"type": "aws_kms", "region": "us-east-1", "key_id": "alias/across-handler",
+                "pubkey": "11111111111111111111111111111111"}"#,
+        )
+        .unwrap();
+        assert!(matches!(kms, SignerConfig::AwsKms(KmsSignerConfig { endpoint: None, .. })));
+
+        let fireblocks: SignerConfig = serde_json::from_str(
+            r#"{"type": "fireblocks", "api_key": "key", "secret_key_path": "fireblocks.pem",
+                "vault_account_id": "0", "pubkey": "11111111111111111111111111111111"}"#,
+        )
+        .unwrap();
+        let SignerConfig::Fireblocks(fireblocks) = fireblocks else {
+            panic!("expected a Fireblocks signer");
+        };
+        assert_eq!(fireblocks.asset_id, "SOL");
+        assert_eq!(fireblocks.api_url, "https://api.fireblocks.io");
+
+        let unknown = serde_json::from_str::<SignerConfig>(
+            r#"{"type": "aws_kms", "region": "us-east-1", "key_id": "k",
+                "pubkey": "11111111111111111111111111111111", "secret": "x"}"#,
+        );
+        assert!(unknown.is_err());
+    }
+}
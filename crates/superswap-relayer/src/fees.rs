@@ -0,0 +1,81 @@
+use anyhow::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use crate::config::PriorityFeeConfig;
+
+/// Priority fee for a fill, in micro-lamports per compute unit
+///
+/// Starts at the configured percentile of fees recently paid on the fill's
+/// writable accounts and escalates on every retry, within the configured bounds.
+pub async fn priority_fee(
+    rpc: &RpcClient,
+    config: &PriorityFeeConfig,
+    writable_accounts: &[Pubkey],
+    attempt: u32,
+) -> Result<u64> {
+    let recent: Vec<u64> = rpc
+        .get_recent_prioritization_fees(writable_accounts)
+        .await?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+
+    Ok(escalate(config, percentile(recent, config.percentile), attempt))
+}
+
+/// Compute unit limit for a fill that simulated at `units_consumed`
+///
+/// The priority fee is paid per unit of the requested limit, so a fill asks for
+/// what it used plus the configured headroom rather than the configured maximum.
+/// Without a simulated figure the configured limit applies.
+pub fn compute_unit_limit(config: &PriorityFeeConfig, units_consumed: Option<u64>) -> u32 {
+    let Some(units) = units_consumed else {
+        return config.compute_unit_limit;
+    };
+    let limit = units.saturating_mul(100 + config.compute_unit_headroom_percent) / 100;
+    limit.min(config.compute_unit_limit as u64) as u32
+}
+
+/// The `pct`th percentile of `fees`, or zero without samples
+pub fn percentile(mut fees: Vec<u64>, pct: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() * pct as usize).div_ceil(100).max(1);
+    fees[rank - 1]
+}
+
+/// Applies the per-attempt escalation and the configured bounds to a base fee
+pub fn escalate(config: &PriorityFeeConfig, base: u64, attempt: u32) -> u64 {
+    let mut fee = base.max(config.min_micro_lamports);
+    for _ in 0..attempt {
+        fee = fee.saturating_mul(config.escalation_percent) / 100;
+    }
+    fee.clamp(config.min_micro_lamports, config.max_micro_lamports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_escalates_within_bounds() {
+        let config = PriorityFeeConfig::default();
+        let base = percentile(vec![5_000, 1_000, 20_000, 10_000], 75);
+        assert_eq!(base, 10_000);
+
+        assert_eq!(escalate(&config, base, 0), 10_000);
+        assert_eq!(escalate(&config, base, 2), 22_500);
+        assert_eq!(escalate(&config, 0, 0), config.min_micro_lamports);
+        assert_eq!(escalate(&config, base, 40), config.max_micro_lamports);
+    }
+
+    #[test]
+    fn compute_unit_limit_fits_simulation() {
+        let config = PriorityFeeConfig::default();
+        assert_eq!(compute_unit_limit(&config, Some(250_000)), 300_000);
+        assert_eq!(compute_unit_limit(&config, Some(1_100_000)), config.compute_unit_limit);
+        assert_eq!(compute_unit_limit(&config, None), config.compute_unit_limit);
+    }
+}
@@ -0,0 +1,412 @@
+use std::time::Duration;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_interface::TokenAccount;
+use anyhow::{anyhow, Context, Result};
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
+use superswap_pda::{find_config, find_destination_mint, find_mint_config, find_recipient_preferences, find_swap_order, find_volume_tracker, wallet_token_account, wallet_token_account_with_program};
+use superswap_quote::{JupiterClient, JupiterRoute, QuoteRequest, SwapAccounts};
+use superswap_sdk::{process_bridge_and_swap, simulate_fill, FillAccounts, FillPreferences, FillTransactionBuilder};
+use superswap_sol::state::{Config, DestinationMint, MintConfig, RecipientPreferences, SurplusPolicy};
+use crate::config::RelayerConfig;
+use crate::fees::{compute_unit_limit, priority_fee};
+use crate::signer::{sign_transaction, RemoteSigner};
+use crate::source::InboundOrder;
+
+/// What became of an inbound order
+#[derive(Debug)]
+pub enum FillOutcome {
+    Filled(Signature),
+    /// The order account already exists, filled by this or another relayer
+    AlreadyFilled,
+    /// The fresh quote cannot meet the order's minimum output; retried later
+    BelowMinimum { quoted: u64 },
+    /// The fill fails in simulation, so it is not sent; retried with a fresh quote later
+    SimulationFailed { err: TransactionError, logs: Vec<String> },
+}
+
+/// Quotes, builds and submits fills as the program's Across handler
+pub struct Filler {
+    rpc: RpcClient,
+    signer: Box<dyn RemoteSigner>,
+    config: RelayerConfig,
+    program_config: Config,
+    jupiter: JupiterClient,
+    program_lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl Filler {
+    pub async fn new(config: RelayerConfig, signer: Box<dyn RemoteSigner>) -> Result<Self> {
+        let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+        let program_config = fetch_program_config(&rpc, &config.program_id).await?;
+        if !program_config.is_across_handler(&signer.pubkey()) {
+            return Err(anyhow!(
+                "signer {} is not one of the program's Across handlers, led by {}",
+                signer.pubkey(),
+                program_config.across_handler
+            ));
+        }
+        let program_lookup_tables = fetch_lookup_tables(&rpc, &config.lookup_tables).await?;
+
+        Ok(Self {
+            jupiter: JupiterClient::new(&config.jupiter_api_url),
+            rpc,
+            signer,
+            config,
+            program_config,
+            program_lookup_tables,
+        })
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn program_config(&self) -> &Config {
+        &self.program_config
+    }
+
+    pub fn handler(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    /// Fills an order, retrying submission with backoff and escalating priority fees
+    pub async fn fill(&self, order: &InboundOrder) -> Result<FillOutcome> {
+        let message = &order.message;
+        let program_id = self.config.program_id;
+        let swap_order = find_swap_order(&program_id, message.order_id).0;
+
+        if self.account_exists(&swap_order).await? {
+            return Ok(FillOutcome::AlreadyFilled);
+        }
+        // A late order is still filled: the program refunds its USDC to the recipient
+        let now = self.rpc.get_block_time(self.rpc.get_slot().await?).await?;
+        let expired = !message.is_live(now);
+
+        // A disabled destination mint is filled with the config's fallback asset instead
+        let fallback_output_mint = self.fallback_output_mint(message.destination_mint).await?;
+        let output_mint = fallback_output_mint.unwrap_or(message.destination_mint);
+        let preferences = self.fill_preferences(message.recipient, output_mint).await?;
+        let token_2022_destination = self.is_token_2022_mint(&output_mint).await?;
+        let destination_token_program = if token_2022_destination {
+            anchor_spl::token_2022::ID
+        } else {
+            spl_token::ID
+        };
+        let destination_account = preferences
+            .as_ref()
+            .and_then(|preferences| preferences.destination_account)
+            .unwrap_or_else(|| wallet_token_account_with_program(&message.recipient, &output_mint, &destination_token_program));
+
+        // The requested mint's fee override only applies when that mint is delivered
+        let mint_config = match fallback_output_mint {
+            Some(_) => None,
+            None => self.mint_config(&message.destination_mint).await?,
+        };
+        let fee_bps = mint_config
+            .and_then(|mint_config| mint_config.fee_bps)
+            .unwrap_or(self.program_config.fee_bps);
+        let fee = superswap_sol::utils::calculate_fee(order.delivered_usdc, fee_bps)
+            .map_err(|err| anyhow!("fee calculation failed: {}", err))?;
+        let swap_amount = order.delivered_usdc - fee;
+        let swapped = !expired && output_mint != self.program_config.usdc_mint;
+
+        // A config capturing surplus takes a swap's output into its vault for the mint first,
+        // except for WSOL delivered as native SOL
+        let unwrap_sol = output_mint == spl_token::native_mint::ID
+            && preferences
+                .as_ref()
+                .map(|preferences| preferences.auto_unwrap_sol)
+                .unwrap_or(true);
+        let capture_surplus = swapped && !unwrap_sol && self.program_config.captures_surplus();
+        let route_destination = if capture_surplus {
+            wallet_token_account_with_program(&find_config(&program_id).0, &output_mint, &destination_token_program)
+        } else {
+            destination_account
+        };
+        let route = if !swapped {
+            // USDC is delivered as is, without a swap
+            JupiterRoute {
+                swap_data: Vec::new(),
+                accounts: Vec::new(),
+                lookup_tables: Vec::new(),
+                out_amount: swap_amount,
+                min_out_amount: swap_amount,
+            }
+        } else {
+            self.jupiter
+                .route(
+                    &QuoteRequest {
+                        input_mint: self.program_config.usdc_mint,
+                        output_mint,
+                        amount: swap_amount,
+                        slippage_bps: self.config.slippage_bps,
+                        max_accounts: None,
+                    },
+                    &SwapAccounts {
+                        jupiter_program: self.program_config.jupiter_program,
+                        swap_authority: find_config(&program_id).0,
+                        destination_token_account: route_destination,
+                    },
+                )
+                .await?
+        };
+        // The order's minimum is quoted in its own mint, not the fallback's
+        if !expired && fallback_output_mint.is_none() && route.out_amount < message.min_output_amount {
+            return Ok(FillOutcome::BelowMinimum { quoted: route.out_amount });
+        }
+
+        // Volume trackers are passed whenever they exist, so a cap set after startup still holds
+        let global_volume_tracker = self
+            .account_exists(&find_volume_tracker(&program_id, &Pubkey::default()).0)
+            .await?;
+        let mint_volume_tracker = fallback_output_mint.is_none()
+            && self
+                .account_exists(&find_volume_tracker(&program_id, &message.destination_mint).0)
+                .await?;
+
+        let accounts = FillAccounts {
+            across_handler: self.handler(),
+            payer: self.handler(),
+            source_usdc_account: wallet_token_account(&self.handler(), &self.program_config.usdc_mint),
+            usdc_mint: self.program_config.usdc_mint,
+            fee_recipient: self.program_config.fee_recipient,
+            jupiter_program: self.program_config.jupiter_program,
+            recipient_preferences: preferences,
+            recipient_program: None,
+            fallback_output_mint,
+            staged_route: false,
+            route_template: false,
+            surplus_vault: capture_surplus,
+            payer_surplus_account: capture_surplus
+                && self.program_config.surplus_policy == SurplusPolicy::PayerRebate,
+            fee_recipient_surplus_account: capture_surplus
+                && self.program_config.surplus_policy == SurplusPolicy::Treasury,
+            token_2022_destination,
+            global_volume_tracker,
+            mint_volume_tracker,
+        };
+        let fill = process_bridge_and_swap(
+            &program_id,
+            &accounts,
+            message.clone().into_params(order.delivered_usdc, route.swap_data),
+            &route.accounts,
+        );
+        let writable: Vec<Pubkey> = fill
+            .accounts
+            .iter()
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+
+        let mut builder = FillTransactionBuilder::new(self.handler())
+            .program_lookup_tables(self.program_lookup_tables.clone())
+            .route_lookup_tables(fetch_lookup_tables(&self.rpc, &route.lookup_tables).await?)
+            .compute_unit_limit(self.config.priority_fee.compute_unit_limit);
+        for instruction in &order.pre_instructions {
+            builder = builder.instruction(instruction.clone());
+        }
+        let builder = builder.instruction(fill);
+
+        // Dry-run the route first: a failed fill still costs fees and priority fees
+        let simulation = simulate_fill(&self.rpc, &builder, &program_id).await?;
+        if let Some(err) = simulation.err {
+            return Ok(FillOutcome::SimulationFailed { err, logs: simulation.logs });
+        }
+        if let Some(result) = &simulation.result {
+            info!(
+                "order {} simulates: fee {}, {} USDC swapped, {} delivered, {} CU",
+                message.order_id,
+                result.fee_amount,
+                result.swap_amount,
+                result.amount_delivered,
+                simulation.units_consumed.unwrap_or_default()
+            );
+        }
+        // Priority fees are paid on the requested limit, so ask for what the fill uses
+        let units = compute_unit_limit(&self.config.priority_fee, simulation.units_consumed);
+        let builder = builder.compute_unit_limit(units);
+
+        let retry = &self.config.retry;
+        for attempt in 0..retry.max_attempts {
+            let micro_lamports = priority_fee(&self.rpc, &self.config.priority_fee, &writable, attempt).await?;
+            let result = self
+                .submit(builder.clone().compute_unit_price(micro_lamports))
+                .await;
+
+            match result {
+                Ok(signature) => return Ok(FillOutcome::Filled(signature)),
+                Err(err) => {
+                    // A timed-out attempt may still have landed
+                    if self.account_exists(&swap_order).await? {
+                        return Ok(FillOutcome::AlreadyFilled);
+                    }
+                    let delay = backoff(retry.base_delay_ms, retry.max_delay_ms, attempt);
+                    warn!(
+                        "fill attempt {} for order {} failed: {:#}; retrying in {:?}",
+                        attempt + 1,
+                        message.order_id,
+                        err,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+
+        Err(anyhow!(
+            "order {} not filled after {} attempts",
+            message.order_id,
+            retry.max_attempts
+        ))
+    }
+
+    /// Sends the transaction once and waits for it to confirm or for the blockhash window to pass
+    async fn submit(&self, builder: FillTransactionBuilder) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let transaction = sign_transaction(self.signer.as_ref(), builder.compile(blockhash)?).await?;
+        let signature = self
+            .rpc
+            .send_transaction_with_config(
+                &transaction,
+                RpcSendTransactionConfig {
+                    preflight_commitment: Some(CommitmentConfig::confirmed().commitment),
+                    max_retries: Some(0),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        info!("sent fill {}", signature);
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(self.config.retry.confirm_timeout_ms);
+        while tokio::time::Instant::now() < deadline {
+            if let Some(status) = self.rpc.get_signature_status(&signature).await? {
+                return status.map(|_| signature).context("fill transaction failed");
+            }
+            if !self.rpc.is_blockhash_valid(&blockhash, CommitmentConfig::processed()).await? {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        Err(anyhow!("fill {} did not confirm", signature))
+    }
+
+    /// The recipient's registered preferences, when the preferences PDA exists
+    async fn fill_preferences(&self, recipient: Pubkey, destination_mint: Pubkey) -> Result<Option<FillPreferences>> {
+        let address = find_recipient_preferences(&self.config.program_id, &recipient).0;
+        let Some(account) = self.rpc.get_account_with_commitment(&address, self.rpc.commitment()).await?.value else {
+            return Ok(None);
+        };
+        let preferences = RecipientPreferences::try_deserialize(&mut account.data.as_slice())?;
+
+        // A preferred account only applies when it holds the order's destination mint
+        let mut destination_account = None;
+        if preferences.preferred_destination_account != Pubkey::default() {
+            let token_account = self.rpc.get_account(&preferences.preferred_destination_account).await?;
+            let token_account = TokenAccount::try_deserialize(&mut token_account.data.as_slice())?;
+            if token_account.mint == destination_mint {
+                destination_account = Some(preferences.preferred_destination_account);
+            }
+        }
+
+        Ok(Some(FillPreferences {
+            destination_account,
+            refund_address: (preferences.refund_address != Pubkey::default()).then_some(preferences.refund_address),
+            auto_unwrap_sol: preferences.auto_unwrap_sol,
+        }))
+    }
+
+    /// The config's fallback mint when an operator has disabled `destination_mint`
+    async fn fallback_output_mint(&self, destination_mint: Pubkey) -> Result<Option<Pubkey>> {
+        let fallback = self.program_config.fallback_output_mint;
+        if fallback == Pubkey::default() {
+            return Ok(None);
+        }
+        let mint_disabled = self
+            .mint_config(&destination_mint)
+            .await?
+            .is_some_and(|mint_config| !mint_config.enabled);
+        let address = find_destination_mint(&self.config.program_id, &destination_mint).0;
+        let Some(account) = self.rpc.get_account_with_commitment(&address, self.rpc.commitment()).await?.value else {
+            return Ok(mint_disabled.then_some(fallback));
+        };
+        let entry = DestinationMint::try_deserialize(&mut account.data.as_slice())?;
+        Ok((entry.disabled || mint_disabled).then_some(fallback))
+    }
+
+    /// The admin's overrides for fills into `mint`, if it has any
+    async fn mint_config(&self, mint: &Pubkey) -> Result<Option<MintConfig>> {
+        let address = find_mint_config(&self.config.program_id, mint).0;
+        let Some(account) = self.rpc.get_account_with_commitment(&address, self.rpc.commitment()).await?.value else {
+            return Ok(None);
+        };
+        Ok(Some(MintConfig::try_deserialize(&mut account.data.as_slice())?))
+    }
+
+    /// Whether `mint` belongs to the Token-2022 program rather than SPL Token
+    async fn is_token_2022_mint(&self, mint: &Pubkey) -> Result<bool> {
+        let account = self
+            .rpc
+            .get_account(mint)
+            .await
+            .with_context(|| format!("mint {} not found", mint))?;
+        Ok(account.owner == anchor_spl::token_2022::ID)
+    }
+
+    async fn account_exists(&self, address: &Pubkey) -> Result<bool> {
+        Ok(self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())
+            .await?
+            .value
+            .is_some())
+    }
+}
+
+/// Exponential backoff for the `attempt`th retry, capped at `max_ms`
+pub fn backoff(base_ms: u64, max_ms: u64, attempt: u32) -> Duration {
+    let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+    Duration::from_millis(base_ms.saturating_mul(factor).min(max_ms))
+}
+
+async fn fetch_program_config(rpc: &RpcClient, program_id: &Pubkey) -> Result<Config> {
+    let address = find_config(program_id).0;
+    let account = rpc
+        .get_account(&address)
+        .await
+        .with_context(|| format!("program config {} not found", address))?;
+    Ok(Config::try_deserialize(&mut account.data.as_slice())?)
+}
+
+async fn fetch_lookup_tables(rpc: &RpcClient, addresses: &[Pubkey]) -> Result<Vec<AddressLookupTableAccount>> {
+    let mut tables = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        let account = rpc.get_account(address).await?;
+        let table = AddressLookupTable::deserialize(&account.data)?;
+        tables.push(AddressLookupTableAccount {
+            key: *address,
+            addresses: table.addresses.to_vec(),
+        });
+    }
+    Ok(tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(backoff(500, 15_000, 0), Duration::from_millis(500));
+        assert_eq!(backoff(500, 15_000, 3), Duration::from_millis(4_000));
+        assert_eq!(backoff(500, 15_000, 64), Duration::from_millis(15_000));
+    }
+}
@@ -0,0 +1,124 @@
+//! Reference relayer for SuperSwap
+//!
+//! Watches Across spoke pool fills and CCTP attestations delivering USDC to
+//! the program's Across handler, quotes a fresh Jupiter route for each order
+//! and submits `process_bridge_and_swap` as the handler, retrying with
+//! backoff and escalating priority fees until the order lands or expires.
+
+mod config;
+mod fees;
+mod fill;
+mod signer;
+mod source;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use anyhow::Result;
+use clap::Parser;
+use log::{debug, error, info, warn};
+use crate::config::RelayerConfig;
+use crate::fill::{FillOutcome, Filler};
+use crate::source::{AcrossSource, CctpSource, InboundOrder, Origin};
+
+#[derive(Parser)]
+#[command(name = "superswap-relayer", about = "Fills SuperSwap orders bridged to Solana")]
+struct Args {
+    /// Path to the relayer's JSON config
+    #[arg(long, env = "SUPERSWAP_RELAYER_CONFIG")]
+    config: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    let config = RelayerConfig::load(&args.config)?;
+    let signer = signer::from_config(&config.signer)?;
+    let poll_interval = Duration::from_millis(config.poll_interval_ms);
+
+    let filler = Filler::new(config.clone(), signer).await?;
+    let mut across = None;
+    if config.watch_across {
+        let program_config = filler.program_config();
+        let mut source = AcrossSource::new(
+            program_config.across_spoke_pool,
+            filler.handler(),
+            program_config.usdc_mint,
+        );
+        source.start(filler.rpc()).await?;
+        across = Some(source);
+    }
+    let mut cctp = config.cctp.as_ref().map(|cctp| {
+        CctpSource::new(
+            &cctp.iris_api_url,
+            cctp.pending_dir.clone(),
+            filler.handler(),
+            filler.program_config().usdc_mint,
+            cctp.fee_recipient_token_account,
+        )
+    });
+
+    info!("relaying as {}", filler.handler());
+
+    // Orders awaiting a successful fill, keyed by order id so re-observed orders are not duplicated
+    let mut pending: BTreeMap<u64, InboundOrder> = BTreeMap::new();
+    loop {
+        if let Some(across) = across.as_mut() {
+            match across.poll(filler.rpc()).await {
+                Ok(orders) => enqueue(&mut pending, orders),
+                Err(err) => warn!("Across poll failed: {:#}", err),
+            }
+        }
+        if let Some(cctp) = cctp.as_mut() {
+            match cctp.poll().await {
+                Ok(orders) => enqueue(&mut pending, orders),
+                Err(err) => warn!("CCTP poll failed: {:#}", err),
+            }
+        }
+
+        let mut settled = Vec::new();
+        for (order_id, order) in &pending {
+            match filler.fill(order).await {
+                Ok(FillOutcome::Filled(signature)) => {
+                    info!("filled order {} from {}: {}", order_id, order.origin, signature);
+                    settled.push(*order_id);
+                }
+                Ok(FillOutcome::AlreadyFilled) => {
+                    info!("order {} is already filled", order_id);
+                    settled.push(*order_id);
+                }
+                Ok(FillOutcome::BelowMinimum { quoted }) => warn!(
+                    "order {} quotes {} below its minimum {}, waiting for a better route",
+                    order_id, quoted, order.message.min_output_amount
+                ),
+                Ok(FillOutcome::SimulationFailed { err, logs }) => {
+                    warn!("order {} fails in simulation: {}; retrying with a fresh quote", order_id, err);
+                    for line in logs {
+                        debug!("  {}", line);
+                    }
+                }
+                Err(err) => error!("failed to fill order {}: {:#}", order_id, err),
+            }
+        }
+        for order_id in settled {
+            let Some(order) = pending.remove(&order_id) else {
+                continue;
+            };
+            if let (Some(cctp), Origin::Cctp { intent, .. }) = (cctp.as_ref(), &order.origin) {
+                if let Err(err) = cctp.complete(intent) {
+                    warn!("{:#}", err);
+                }
+            }
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+fn enqueue(pending: &mut BTreeMap<u64, InboundOrder>, orders: Vec<InboundOrder>) {
+    for order in orders {
+        pending.entry(order.message.order_id).or_insert(order);
+    }
+}
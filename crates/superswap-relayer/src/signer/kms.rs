@@ -0,0 +1,193 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use crate::config::KmsSignerConfig;
+use super::{to_hex, RemoteSigner};
+
+const SERVICE: &str = "kms";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+const SIGN_TARGET: &str = "TrentService.Sign";
+
+/// Static AWS credentials read from the environment
+struct Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl Credentials {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            access_key_id: std::env::var("AWS_ACCESS_KEY_ID").context("AWS_ACCESS_KEY_ID is not set")?,
+            secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY")
+                .context("AWS_SECRET_ACCESS_KEY is not set")?,
+            session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SignResponse {
+    signature: String,
+}
+
+/// Signs with an Ed25519 key that never leaves AWS KMS
+pub struct KmsSigner {
+    client: reqwest::Client,
+    config: KmsSignerConfig,
+    endpoint: String,
+    credentials: Credentials,
+}
+
+impl KmsSigner {
+    pub fn new(config: KmsSignerConfig) -> Result<Self> {
+        let endpoint = config
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://kms.{}.amazonaws.com", config.region));
+        Ok(Self {
+            client: reqwest::Client::new(),
+            credentials: Credentials::from_env()?,
+            endpoint,
+            config,
+        })
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for KmsSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.config.pubkey
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let body = json!({
+            "KeyId": self.config.key_id,
+            "Message": STANDARD.encode(message),
+            "MessageType": "RAW",
+            "SigningAlgorithm": "ED25519_SHA_512",
+        })
+        .to_string();
+
+        let host = self
+            .endpoint
+            .split("://")
+            .nth(1)
+            .unwrap_or(&self.endpoint)
+            .trim_end_matches('/')
+            .to_string();
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let authorization = authorization(&self.credentials, &self.config.region, &host, &amz_date, &body);
+
+        let mut request = self
+            .client
+            .post(&self.endpoint)
+            .header("content-type", CONTENT_TYPE)
+            .header("x-amz-date", &amz_date)
+            .header("x-amz-target", SIGN_TARGET)
+            .header("authorization", authorization);
+        if let Some(token) = &self.credentials.session_token {
+            request = request.header("x-amz-security-token", token);
+        }
+        let response = request.body(body).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("KMS Sign returned {}: {}", status, text));
+        }
+
+        let signature = STANDARD.decode(response.json::<SignResponse>().await?.signature)?;
+        Signature::try_from(signature.as_slice()).map_err(|_| anyhow!("KMS returned a {}-byte signature", signature.len()))
+    }
+}
+
+/// AWS Signature Version 4 `Authorization` header for a KMS JSON request to `/`
+fn authorization(credentials: &Credentials, region: &str, host: &str, amz_date: &str, body: &str) -> String {
+    let date = &amz_date[..8];
+    let mut headers = vec![
+        ("content-type", CONTENT_TYPE.to_string()),
+        ("host", host.to_string()),
+        ("x-amz-date", amz_date.to_string()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    headers.push(("x-amz-target", SIGN_TARGET.to_string()));
+
+    let canonical_headers: String = headers.iter().map(|(name, value)| format!("{}:{}\n", name, value)).collect();
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+        "POST\n/\n\n{}\n{}\n{}",
+        canonical_headers,
+        signed_headers,
+        to_hex(&Sha256::digest(body.as_bytes()))
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, SERVICE);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        to_hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+    let key = signing_key(&credentials.secret_access_key, date, region, SERVICE);
+    let signature = to_hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+/// SigV4 key derived from the secret for one day, region and service
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let key = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_key_matches_aws_example() {
+        // Example from the AWS "Deriving the signing key" documentation
+        let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+        assert_eq!(
+            to_hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn authorization_signs_session_token() {
+        let mut credentials = Credentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "secret".to_string(),
+            session_token: None,
+        };
+        let header = authorization(&credentials, "us-east-1", "kms.us-east-1.amazonaws.com", "20260101T000000Z", "{}");
+        assert!(header.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260101/us-east-1/kms/aws4_request"));
+        assert!(header.contains("SignedHeaders=content-type;host;x-amz-date;x-amz-target,"));
+
+        credentials.session_token = Some("token".to_string());
+        let header = authorization(&credentials, "us-east-1", "kms.us-east-1.amazonaws.com", "20260101T000000Z", "{}");
+        assert!(header.contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target,"));
+    }
+}
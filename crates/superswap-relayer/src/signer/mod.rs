@@ -0,0 +1,142 @@
+//! Signers for the Across handler: a local keypair, or a remote key that never
+//! leaves AWS KMS or a Fireblocks-style custody API
+
+mod fireblocks;
+mod kms;
+
+pub use fireblocks::FireblocksSigner;
+pub use kms::KmsSigner;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use superswap_sdk::check_transaction_size;
+use crate::config::SignerConfig;
+
+/// Produces Ed25519 signatures for one Solana key
+#[async_trait]
+pub trait RemoteSigner: Send + Sync {
+    fn pubkey(&self) -> Pubkey;
+
+    /// Signs a serialized transaction message
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature>;
+}
+
+/// Signs with a keypair file on the relayer host
+pub struct LocalSigner {
+    keypair: Keypair,
+}
+
+impl LocalSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for LocalSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        Ok(self.keypair.sign_message(message))
+    }
+}
+
+/// Builds the signer described by the config
+pub fn from_config(config: &SignerConfig) -> Result<Box<dyn RemoteSigner>> {
+    Ok(match config {
+        SignerConfig::Keypair { path } => {
+            let keypair = read_keypair_file(path)
+                .map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))?;
+            Box::new(LocalSigner::new(keypair))
+        }
+        SignerConfig::AwsKms(config) => Box::new(KmsSigner::new(config.clone())?),
+        SignerConfig::Fireblocks(config) => Box::new(FireblocksSigner::new(config.clone())?),
+    })
+}
+
+/// Signs a message whose only required signer is `signer`, checking the
+/// returned signature before it is sent
+pub async fn sign_transaction(signer: &dyn RemoteSigner, message: VersionedMessage) -> Result<VersionedTransaction> {
+    let pubkey = signer.pubkey();
+    let required = message.header().num_required_signatures as usize;
+    ensure!(
+        required == 1 && message.static_account_keys().first() == Some(&pubkey),
+        "transaction needs signers other than {}",
+        pubkey
+    );
+
+    let data = message.serialize();
+    let signature = signer.sign_message(&data).await.context("signing failed")?;
+    ensure!(
+        signature.verify(pubkey.as_ref(), &data),
+        "signer returned an invalid signature for {}",
+        pubkey
+    );
+
+    let transaction = VersionedTransaction {
+        signatures: vec![signature],
+        message,
+    };
+    check_transaction_size(&transaction)?;
+    Ok(transaction)
+}
+
+/// Lowercase hex encoding, as remote signing APIs expect
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(raw: &str) -> Result<Vec<u8>> {
+    let raw = raw.strip_prefix("0x").unwrap_or(raw);
+    raw.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            ensure!(pair.len() == 2, "odd-length hex string");
+            Ok(u8::from_str_radix(std::str::from_utf8(pair)?, 16)?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::message::Message;
+    use solana_sdk::system_instruction;
+
+    #[tokio::test]
+    async fn local_signer_signs_fee_payer_transaction() {
+        let keypair = Keypair::new();
+        let payer = keypair.pubkey();
+        let signer = LocalSigner::new(keypair);
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(&[transfer], Some(&payer), &Hash::default()));
+
+        let transaction = sign_transaction(&signer, message).await.unwrap();
+        assert!(transaction.verify_with_results().into_iter().all(|valid| valid));
+    }
+
+    #[tokio::test]
+    async fn other_fee_payer_is_rejected() {
+        let signer = LocalSigner::new(Keypair::new());
+        let payer = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(&[transfer], Some(&payer), &Hash::default()));
+
+        assert!(sign_transaction(&signer, message).await.is_err());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let bytes = [0u8, 1, 0xab, 0xff];
+        assert_eq!(to_hex(&bytes), "0001abff");
+        assert_eq!(from_hex("0x0001ABff").unwrap(), bytes);
+        assert!(from_hex("abc").is_err());
+    }
+}
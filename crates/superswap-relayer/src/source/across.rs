@@ -0,0 +1,239 @@
+use anchor_lang::prelude::{borsh, AnchorDeserialize};
+use anchor_lang::solana_program::hash::hash;
+use anyhow::{anyhow, Result};
+use log::{debug, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+use std::str::FromStr;
+use superswap_sdk::OrderMessage;
+use super::{InboundOrder, Origin};
+
+/// Relay data of an Across SVM spoke pool fill
+#[derive(Debug, Clone, AnchorDeserialize)]
+struct RelayData {
+    _depositor: Pubkey,
+    recipient: Pubkey,
+    _exclusive_relayer: Pubkey,
+    _input_token: Pubkey,
+    output_token: Pubkey,
+    _input_amount: [u8; 32],
+    output_amount: u64,
+    _origin_chain_id: u64,
+    _deposit_id: [u8; 32],
+    _fill_deadline: u32,
+    _exclusivity_deadline: u32,
+    message: Vec<u8>,
+}
+
+/// Arguments of the spoke pool `fill_relay` instruction
+///
+/// Relay data is omitted when the filler staged it in an instruction
+/// params account, in which case the fill cannot be decoded here.
+#[derive(Debug, Clone, AnchorDeserialize)]
+struct FillRelayArgs {
+    _relay_hash: [u8; 32],
+    relay_data: Option<RelayData>,
+}
+
+/// Watches the Across spoke pool for fills delivering SuperSwap orders to the handler
+///
+/// Only top-level `fill_relay` instructions are decoded; fills made through
+/// another program's CPI are not observed.
+pub struct AcrossSource {
+    spoke_pool: Pubkey,
+    across_handler: Pubkey,
+    usdc_mint: Pubkey,
+    last_seen: Option<Signature>,
+}
+
+impl AcrossSource {
+    pub fn new(spoke_pool: Pubkey, across_handler: Pubkey, usdc_mint: Pubkey) -> Self {
+        Self {
+            spoke_pool,
+            across_handler,
+            usdc_mint,
+            last_seen: None,
+        }
+    }
+
+    /// Starts watching from the spoke pool's latest transaction
+    pub async fn start(&mut self, rpc: &RpcClient) -> Result<()> {
+        let latest = rpc
+            .get_signatures_for_address_with_config(
+                &self.spoke_pool,
+                GetConfirmedSignaturesForAddress2Config {
+                    limit: Some(1),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        self.last_seen = latest
+            .first()
+            .map(|status| Signature::from_str(&status.signature))
+            .transpose()?;
+        Ok(())
+    }
+
+    /// Orders delivered by spoke pool fills since the previous poll, oldest first
+    pub async fn poll(&mut self, rpc: &RpcClient) -> Result<Vec<InboundOrder>> {
+        let statuses = rpc
+            .get_signatures_for_address_with_config(
+                &self.spoke_pool,
+                GetConfirmedSignaturesForAddress2Config {
+                    until: self.last_seen,
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let mut orders = Vec::new();
+        for status in statuses.iter().rev() {
+            let signature = Signature::from_str(&status.signature)?;
+            if status.err.is_none() {
+                match self.fetch_orders(rpc, &signature).await {
+                    Ok(found) => orders.extend(found),
+                    // Leave the cursor here so the transaction is retried next poll
+                    Err(err) => {
+                        warn!("failed to fetch spoke pool transaction {}: {:#}", signature, err);
+                        break;
+                    }
+                }
+            }
+            self.last_seen = Some(signature);
+        }
+        Ok(orders)
+    }
+
+    async fn fetch_orders(&self, rpc: &RpcClient, signature: &Signature) -> Result<Vec<InboundOrder>> {
+        let transaction = rpc
+            .get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        let versioned = transaction
+            .transaction
+            .transaction
+            .decode()
+            .ok_or_else(|| anyhow!("undecodable transaction {}", signature))?;
+
+        // Program ids are always static keys; lookup table keys only matter for accounts
+        let mut keys = versioned.message.static_account_keys().to_vec();
+        if let Some(meta) = transaction.transaction.meta {
+            if let OptionSerializer::Some(loaded) = meta.loaded_addresses {
+                for address in loaded.writable.iter().chain(loaded.readonly.iter()) {
+                    keys.push(Pubkey::from_str(address)?);
+                }
+            }
+        }
+
+        let discriminator = fill_relay_discriminator();
+        let mut orders = Vec::new();
+        for instruction in versioned.message.instructions() {
+            let program_id = keys.get(instruction.program_id_index as usize);
+            if program_id != Some(&self.spoke_pool) || !instruction.data.starts_with(&discriminator) {
+                continue;
+            }
+            if let Some(order) = self.decode_fill(&instruction.data[8..], signature) {
+                orders.push(order);
+            }
+        }
+        Ok(orders)
+    }
+
+    fn decode_fill(&self, mut data: &[u8], signature: &Signature) -> Option<InboundOrder> {
+        let args = FillRelayArgs::deserialize(&mut data).ok()?;
+        let Some(relay_data) = args.relay_data else {
+            debug!("fill {} staged its relay data, skipping", signature);
+            return None;
+        };
+        if relay_data.recipient != self.across_handler
+            || relay_data.output_token != self.usdc_mint
+            || relay_data.message.is_empty()
+        {
+            return None;
+        }
+
+        match OrderMessage::decode(&relay_data.message) {
+            Ok(message) => Some(InboundOrder {
+                message,
+                delivered_usdc: relay_data.output_amount,
+                pre_instructions: Vec::new(),
+                origin: Origin::Across { signature: *signature },
+            }),
+            Err(err) => {
+                warn!("fill {} to the handler carries no order: {:#}", signature, err);
+                None
+            }
+        }
+    }
+}
+
+fn fill_relay_discriminator() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(b"global:fill_relay").to_bytes()[..8]);
+    discriminator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AnchorSerialize, InstructionData};
+
+    #[test]
+    fn mock_spoke_pool_fill_decodes_to_order() {
+        let handler = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let message = OrderMessage {
+            order_id: 9,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000_000,
+            min_output_amount: 0,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 1_800_000_000,
+            jupiter_swap_data: Vec::new(),
+            relayer_tip: None,
+            allow_off_curve_recipient: None,
+        };
+        let data = mock_across::instruction::FillRelay {
+            relay_hash: [1; 32],
+            relay_data: Some(mock_across::RelayData {
+                depositor: Pubkey::new_unique(),
+                recipient: handler,
+                exclusive_relayer: Pubkey::default(),
+                input_token: Pubkey::new_unique(),
+                output_token: usdc_mint,
+                input_amount: [0; 32],
+                output_amount: 990_000,
+                origin_chain_id: 8453,
+                deposit_id: [0; 32],
+                fill_deadline: u32::MAX,
+                exclusivity_deadline: 0,
+                message: message.try_to_vec().unwrap(),
+            }),
+        }
+        .data();
+        assert!(data.starts_with(&fill_relay_discriminator()));
+
+        let source = AcrossSource::new(mock_across::ID, handler, usdc_mint);
+        let order = source.decode_fill(&data[8..], &Signature::default()).unwrap();
+        assert_eq!(order.message, message);
+        assert_eq!(order.delivered_usdc, 990_000);
+
+        // Fills to anyone but the handler carry no order for this relayer
+        let other = AcrossSource::new(mock_across::ID, Pubkey::new_unique(), usdc_mint);
+        assert!(other.decode_fill(&data[8..], &Signature::default()).is_none());
+    }
+}
@@ -0,0 +1,271 @@
+use std::path::{Path, PathBuf};
+use anchor_lang::prelude::{borsh, AnchorSerialize};
+use anchor_lang::solana_program::hash::hash;
+use anyhow::{anyhow, ensure, Context, Result};
+use log::{debug, warn};
+use serde::Deserialize;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use superswap_sdk::OrderMessage;
+use super::{InboundOrder, Origin};
+
+/// CCTP V2 message transmitter program
+pub const MESSAGE_TRANSMITTER_PROGRAM_ID: Pubkey = pubkey!("CCTPV2Sm4AdWt5296sk4P66VBZ7bEhcARwFaaS9YPbeC");
+
+/// CCTP V2 token messenger minter program
+pub const TOKEN_MESSENGER_MINTER_PROGRAM_ID: Pubkey = pubkey!("CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe");
+
+// Message header: version, source_domain, destination_domain, nonce, sender,
+// recipient, destination_caller, min_finality_threshold, finality_threshold_executed
+const HEADER_SOURCE_DOMAIN: usize = 4;
+const HEADER_NONCE: usize = 12;
+const HEADER_BODY: usize = 148;
+
+// Burn message body: version, burn_token, mint_recipient, amount, message_sender,
+// max_fee, fee_executed, expiration_block, hook_data
+const BODY_BURN_TOKEN: usize = 4;
+const BODY_MINT_RECIPIENT: usize = 36;
+const BODY_AMOUNT: usize = 68;
+const BODY_FEE_EXECUTED: usize = 164;
+const BODY_HOOK_DATA: usize = 228;
+
+/// A burn queued by the frontend, awaiting Circle's attestation
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+struct BurnIntent {
+    source_domain: u32,
+    transaction_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    #[serde(default)]
+    messages: Vec<AttestedMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AttestedMessage {
+    message: String,
+    attestation: String,
+    status: String,
+}
+
+/// Decoded fields of an attested CCTP V2 burn message
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BurnMessage {
+    source_domain: u32,
+    nonce: [u8; 32],
+    burn_token: [u8; 32],
+    mint_recipient: Pubkey,
+    minted: u64,
+    hook_data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize)]
+struct ReceiveMessageArgs {
+    message: Vec<u8>,
+    attestation: Vec<u8>,
+}
+
+/// Polls Circle's attestation service for CCTP burns carrying SuperSwap orders
+///
+/// EVM burns are not observable from Solana, so the frontend that submitted
+/// the burn drops a `{source_domain, transaction_hash}` JSON intent into the
+/// pending directory. The order message travels as the burn's hook data, and
+/// the fill transaction mints the USDC to the handler before filling.
+pub struct CctpSource {
+    http: reqwest::Client,
+    iris_api_url: String,
+    pending_dir: PathBuf,
+    across_handler: Pubkey,
+    handler_usdc_account: Pubkey,
+    usdc_mint: Pubkey,
+    fee_recipient_token_account: Pubkey,
+}
+
+impl CctpSource {
+    pub fn new(
+        iris_api_url: &str,
+        pending_dir: PathBuf,
+        across_handler: Pubkey,
+        usdc_mint: Pubkey,
+        fee_recipient_token_account: Pubkey,
+    ) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            iris_api_url: iris_api_url.trim_end_matches('/').to_string(),
+            pending_dir,
+            across_handler,
+            handler_usdc_account: superswap_pda::wallet_token_account(&across_handler, &usdc_mint),
+            usdc_mint,
+            fee_recipient_token_account,
+        }
+    }
+
+    /// Orders whose burns have been attested since the previous poll
+    pub async fn poll(&mut self) -> Result<Vec<InboundOrder>> {
+        let mut orders = Vec::new();
+        for entry in std::fs::read_dir(&self.pending_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            match self.check_intent(&path).await {
+                Ok(Some(order)) => orders.push(order),
+                Ok(None) => {}
+                Err(err) => warn!("skipping CCTP intent {}: {:#}", path.display(), err),
+            }
+        }
+        Ok(orders)
+    }
+
+    /// Drops a filled or unfillable order's intent from the pending directory
+    pub fn complete(&self, intent: &Path) -> Result<()> {
+        std::fs::remove_file(intent)
+            .with_context(|| format!("failed to remove CCTP intent {}", intent.display()))
+    }
+
+    async fn check_intent(&self, path: &Path) -> Result<Option<InboundOrder>> {
+        let intent: BurnIntent = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let response: MessagesResponse = self
+            .http
+            .get(format!("{}/v2/messages/{}", self.iris_api_url, intent.source_domain))
+            .query(&[("transactionHash", &intent.transaction_hash)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let Some(attested) = response.messages.into_iter().find(|message| message.status == "complete") else {
+            debug!("burn {} not attested yet", intent.transaction_hash);
+            return Ok(None);
+        };
+        let message = decode_hex(&attested.message)?;
+        let attestation = decode_hex(&attested.attestation)?;
+        let burn = parse_burn_message(&message)?;
+        ensure!(
+            burn.mint_recipient == self.handler_usdc_account,
+            "burn mints to {}, not the handler's USDC account",
+            burn.mint_recipient
+        );
+
+        Ok(Some(InboundOrder {
+            message: OrderMessage::decode(&burn.hook_data)?,
+            delivered_usdc: burn.minted,
+            pre_instructions: vec![self.receive_message(&burn, message, attestation)],
+            origin: Origin::Cctp {
+                source_domain: intent.source_domain,
+                transaction_hash: intent.transaction_hash,
+                intent: path.to_path_buf(),
+            },
+        }))
+    }
+
+    /// Builds the message transmitter `receive_message` minting the burn to the handler
+    fn receive_message(&self, burn: &BurnMessage, message: Vec<u8>, attestation: Vec<u8>) -> Instruction {
+        let transmitter = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &MESSAGE_TRANSMITTER_PROGRAM_ID).0;
+        let minter = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &TOKEN_MESSENGER_MINTER_PROGRAM_ID).0;
+        let domain = burn.source_domain.to_string();
+
+        let accounts = vec![
+            AccountMeta::new(self.across_handler, true),
+            AccountMeta::new_readonly(self.across_handler, true),
+            AccountMeta::new_readonly(
+                transmitter(&[b"message_transmitter_authority", TOKEN_MESSENGER_MINTER_PROGRAM_ID.as_ref()]),
+                false,
+            ),
+            AccountMeta::new_readonly(transmitter(&[b"message_transmitter"]), false),
+            AccountMeta::new(transmitter(&[b"used_nonce", &burn.nonce]), false),
+            AccountMeta::new_readonly(TOKEN_MESSENGER_MINTER_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(transmitter(&[b"__event_authority"]), false),
+            AccountMeta::new_readonly(MESSAGE_TRANSMITTER_PROGRAM_ID, false),
+            // Token messenger minter accounts for handle_receive_finalized_message
+            AccountMeta::new_readonly(minter(&[b"token_messenger"]), false),
+            AccountMeta::new_readonly(minter(&[b"remote_token_messenger", domain.as_bytes()]), false),
+            AccountMeta::new(minter(&[b"token_minter"]), false),
+            AccountMeta::new(minter(&[b"local_token", self.usdc_mint.as_ref()]), false),
+            AccountMeta::new_readonly(minter(&[b"token_pair", domain.as_bytes(), &burn.burn_token]), false),
+            AccountMeta::new(self.fee_recipient_token_account, false),
+            AccountMeta::new(self.handler_usdc_account, false),
+            AccountMeta::new(minter(&[b"custody", self.usdc_mint.as_ref()]), false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(minter(&[b"__event_authority"]), false),
+            AccountMeta::new_readonly(TOKEN_MESSENGER_MINTER_PROGRAM_ID, false),
+        ];
+
+        let mut data = hash(b"global:receive_message").to_bytes()[..8].to_vec();
+        ReceiveMessageArgs { message, attestation }
+            .serialize(&mut data)
+            .expect("serializing into a Vec cannot fail");
+
+        Instruction {
+            program_id: MESSAGE_TRANSMITTER_PROGRAM_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+fn decode_hex(raw: &str) -> Result<Vec<u8>> {
+    let raw = raw.strip_prefix("0x").unwrap_or(raw);
+    raw.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            ensure!(pair.len() == 2, "odd-length hex string");
+            Ok(u8::from_str_radix(std::str::from_utf8(pair)?, 16)?)
+        })
+        .collect()
+}
+
+/// Reads a big-endian uint256 that must fit in a u64
+fn read_amount(word: &[u8]) -> Result<u64> {
+    ensure!(word[..24].iter().all(|byte| *byte == 0), "amount exceeds u64");
+    Ok(u64::from_be_bytes(word[24..32].try_into()?))
+}
+
+fn parse_burn_message(message: &[u8]) -> Result<BurnMessage> {
+    ensure!(message.len() >= HEADER_BODY + BODY_HOOK_DATA, "truncated CCTP burn message");
+    let body = &message[HEADER_BODY..];
+    let amount = read_amount(&body[BODY_AMOUNT..BODY_AMOUNT + 32])?;
+    let fee_executed = read_amount(&body[BODY_FEE_EXECUTED..BODY_FEE_EXECUTED + 32])?;
+
+    Ok(BurnMessage {
+        source_domain: u32::from_be_bytes(message[HEADER_SOURCE_DOMAIN..HEADER_SOURCE_DOMAIN + 4].try_into()?),
+        nonce: message[HEADER_NONCE..HEADER_NONCE + 32].try_into()?,
+        burn_token: body[BODY_BURN_TOKEN..BODY_BURN_TOKEN + 32].try_into()?,
+        mint_recipient: Pubkey::try_from(&body[BODY_MINT_RECIPIENT..BODY_MINT_RECIPIENT + 32])
+            .map_err(|_| anyhow!("invalid mint recipient"))?,
+        minted: amount
+            .checked_sub(fee_executed)
+            .ok_or_else(|| anyhow!("CCTP fee exceeds the burn amount"))?,
+        hook_data: body[BODY_HOOK_DATA..].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burn_message_yields_minted_amount_and_hook_data() {
+        let recipient = Pubkey::new_unique();
+        let mut message = vec![0u8; HEADER_BODY + BODY_HOOK_DATA];
+        message[HEADER_SOURCE_DOMAIN..HEADER_SOURCE_DOMAIN + 4].copy_from_slice(&6u32.to_be_bytes());
+        let body = &mut message[HEADER_BODY..];
+        body[BODY_MINT_RECIPIENT..BODY_MINT_RECIPIENT + 32].copy_from_slice(recipient.as_ref());
+        body[BODY_AMOUNT + 24..BODY_AMOUNT + 32].copy_from_slice(&1_000_000u64.to_be_bytes());
+        body[BODY_FEE_EXECUTED + 24..BODY_FEE_EXECUTED + 32].copy_from_slice(&100u64.to_be_bytes());
+        message.extend_from_slice(&[1, 2, 3]);
+
+        let burn = parse_burn_message(&message).unwrap();
+        assert_eq!(burn.source_domain, 6);
+        assert_eq!(burn.mint_recipient, recipient);
+        assert_eq!(burn.minted, 999_900);
+        assert_eq!(burn.hook_data, vec![1, 2, 3]);
+        assert_eq!(decode_hex("0x0aff").unwrap(), vec![10, 255]);
+    }
+}
@@ -0,0 +1,45 @@
+//! Sources of inbound orders: bridge deliveries to the Across handler that
+//! carry a SuperSwap order message
+
+mod across;
+mod cctp;
+
+pub use across::AcrossSource;
+pub use cctp::CctpSource;
+
+use std::path::PathBuf;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signature;
+use superswap_sdk::OrderMessage;
+
+/// A bridged order ready to be filled
+#[derive(Debug, Clone)]
+pub struct InboundOrder {
+    pub message: OrderMessage,
+    /// USDC delivered to the Across handler, net of bridge fees
+    pub delivered_usdc: u64,
+    /// Instructions that must land in the fill transaction before the fill,
+    /// e.g. the CCTP mint of the bridged USDC
+    pub pre_instructions: Vec<Instruction>,
+    pub origin: Origin,
+}
+
+/// Where an inbound order was observed
+#[derive(Debug, Clone)]
+pub enum Origin {
+    /// Across spoke pool fill transaction
+    Across { signature: Signature },
+    /// Attested CCTP burn, with the intent file it was queued through
+    Cctp { source_domain: u32, transaction_hash: String, intent: PathBuf },
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::Across { signature } => write!(f, "across fill {}", signature),
+            Origin::Cctp { source_domain, transaction_hash, .. } => {
+                write!(f, "cctp burn {} (domain {})", transaction_hash, source_domain)
+            }
+        }
+    }
+}
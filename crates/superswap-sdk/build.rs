@@ -0,0 +1,119 @@
+//! Generates event and account decoders from the program's Anchor IDL
+//!
+//! Every event and account listed in `idl/superswap_sol.json` becomes a
+//! variant of `ProgramEvent` or `ProgramAccount`, keyed by the IDL's
+//! discriminator and wrapping the program's own type. Regenerate the IDL
+//! with `scripts/sync-idl.sh` after changing the program.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use serde_json::Value;
+
+const IDL_PATH: &str = "idl/superswap_sol.json";
+
+struct Entry {
+    name: String,
+    discriminator: Vec<u8>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", IDL_PATH);
+
+    let idl: Value = serde_json::from_str(&fs::read_to_string(IDL_PATH).expect("failed to read the IDL"))
+        .expect("the IDL is not valid JSON");
+    let events = entries(&idl, "events");
+    let accounts = entries(&idl, "accounts");
+
+    let mut out = String::new();
+    decoder(&mut out, "ProgramEvent", "A program event, decoded from `emit!` log data", "events", &events);
+    decoder(&mut out, "ProgramAccount", "A program-owned account, decoded from its data", "state", &accounts);
+
+    let path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("idl_decoders.rs");
+    fs::write(path, out).expect("failed to write the generated decoders");
+}
+
+fn entries(idl: &Value, section: &str) -> Vec<Entry> {
+    idl[section]
+        .as_array()
+        .unwrap_or_else(|| panic!("the IDL has no `{}` section", section))
+        .iter()
+        .map(|entry| {
+            let name = entry["name"].as_str().expect("IDL entry without a name");
+            let discriminator = entry["discriminator"]
+                .as_array()
+                .unwrap_or_else(|| panic!("{} has no discriminator", name))
+                .iter()
+                .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()).expect("invalid discriminator byte"))
+                .collect::<Vec<u8>>();
+            assert_eq!(discriminator.len(), 8, "{} has a {}-byte discriminator", name, discriminator.len());
+            Entry {
+                name: name.rsplit("::").next().unwrap().to_string(),
+                discriminator,
+            }
+        })
+        .collect()
+}
+
+/// Writes the enum, its `decode` and its IDL discriminator table
+///
+/// Each IDL discriminator is asserted against the type's own at compile time,
+/// so a stale IDL fails the build rather than silently mis-decoding.
+fn decoder(out: &mut String, enum_name: &str, doc: &str, module: &str, entries: &[Entry]) {
+    for entry in entries {
+        writeln!(
+            out,
+            "const _: () = assert!(matches!(<superswap_sol::{}::{} as anchor_lang::Discriminator>::DISCRIMINATOR, {:?}), \"IDL discriminator of {} is stale\");",
+            module, entry.name, entry.discriminator, entry.name
+        )
+        .unwrap();
+    }
+    writeln!(out).unwrap();
+
+    writeln!(out, "/// {}", doc).unwrap();
+    writeln!(out, "#[allow(clippy::large_enum_variant)]").unwrap();
+    writeln!(out, "pub enum {} {{", enum_name).unwrap();
+    for entry in entries {
+        writeln!(out, "    {0}(superswap_sol::{1}::{0}),", entry.name, module).unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    writeln!(out, "impl {} {{", enum_name).unwrap();
+    writeln!(out, "    /// Discriminators as recorded in the IDL, by type name").unwrap();
+    writeln!(out, "    pub const IDL_DISCRIMINATORS: &'static [(&'static str, [u8; 8])] = &[").unwrap();
+    for entry in entries {
+        writeln!(out, "        (\"{}\", {:?}),", entry.name, entry.discriminator).unwrap();
+    }
+    writeln!(out, "    ];\n").unwrap();
+
+    writeln!(out, "    /// Decodes discriminator-prefixed data; `Ok(None)` for data of another type").unwrap();
+    writeln!(out, "    pub fn decode(data: &[u8]) -> Result<Option<Self>, crate::error::SdkError> {{").unwrap();
+    writeln!(out, "        if data.len() < 8 {{").unwrap();
+    writeln!(out, "            return Ok(None);").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "        let (discriminator, mut body) = data.split_at(8);").unwrap();
+    writeln!(out, "        let decoded = match discriminator {{").unwrap();
+    for entry in entries {
+        writeln!(
+            out,
+            "            {:?} => Self::{}(anchor_lang::AnchorDeserialize::deserialize(&mut body).map_err(|err| crate::error::SdkError::InvalidProgramData(format!(\"{}: {{}}\", err)))?),",
+            entry.discriminator, entry.name, entry.name
+        )
+        .unwrap();
+    }
+    writeln!(out, "            _ => return Ok(None),").unwrap();
+    writeln!(out, "        }};").unwrap();
+    writeln!(out, "        Ok(Some(decoded))").unwrap();
+    writeln!(out, "    }}\n").unwrap();
+
+    writeln!(out, "    /// Name of the wrapped type").unwrap();
+    writeln!(out, "    pub fn name(&self) -> &'static str {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for entry in entries {
+        writeln!(out, "            Self::{0}(_) => \"{0}\",", entry.name).unwrap();
+    }
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}\n").unwrap();
+}
@@ -0,0 +1,107 @@
+//! Event and account decoders generated from the program's IDL
+//!
+//! `ProgramEvent` and `ProgramAccount` are written by the build script from
+//! `idl/superswap_sol.json`, so indexers and bots see a new event or account
+//! as soon as the IDL is regenerated, and a stale IDL fails to compile.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use solana_sdk::pubkey::Pubkey;
+use crate::error::SdkError;
+
+include!(concat!(env!("OUT_DIR"), "/idl_decoders.rs"));
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Decodes the events `program_id` emitted in a transaction's logs, in order
+///
+/// Only `Program data:` lines logged while `program_id` is the innermost
+/// running program are considered, so events of programs it invokes (or that
+/// invoke it) are skipped.
+pub fn decode_events(program_id: &Pubkey, logs: &[String]) -> Result<Vec<ProgramEvent>, SdkError> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let data = BASE64
+                .decode(data)
+                .map_err(|err| SdkError::InvalidProgramData(err.to_string()))?;
+            events.extend(ProgramEvent::decode(&data)?);
+            continue;
+        }
+
+        let mut words = log.split_whitespace();
+        if words.next() != Some("Program") {
+            continue;
+        }
+        match (words.next(), words.next()) {
+            (Some(program), Some("invoke")) => stack.push(program),
+            (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Event};
+    use superswap_sol::events::OutboundSettled;
+    use superswap_sol::state::RentVault;
+
+    fn program_data(event: &impl Event) -> String {
+        format!("{}{}", PROGRAM_DATA, BASE64.encode(event.data()))
+    }
+
+    #[test]
+    fn events_of_invoked_programs_are_skipped() {
+        let program_id = superswap_sol::ID;
+        let other = Pubkey::new_unique();
+        let settled = OutboundSettled {
+            order_id: 7,
+            destination_tx_hash: [1; 32],
+        };
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("Program {} invoke [2]", other),
+            program_data(&settled),
+            format!("Program {} success", other),
+            program_data(&settled),
+            format!("Program {} success", program_id),
+            program_data(&settled),
+        ];
+
+        let events = decode_events(&program_id, &logs).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ProgramEvent::OutboundSettled(event) => assert_eq!(event.order_id, 7),
+            other => panic!("decoded {}", other.name()),
+        }
+    }
+
+    #[test]
+    fn accounts_decode_by_discriminator() {
+        let vault = RentVault {
+            total_deposited: 5,
+            total_subsidized: 3,
+            bump: 254,
+        };
+        let mut data = Vec::new();
+        vault.try_serialize(&mut data).unwrap();
+
+        match ProgramAccount::decode(&data).unwrap() {
+            Some(ProgramAccount::RentVault(decoded)) => assert_eq!(decoded.total_subsidized, 3),
+            _ => panic!("rent vault not decoded"),
+        }
+        assert!(ProgramAccount::decode(&[0; 16]).unwrap().is_none());
+        assert!(ProgramAccount::decode(&data[..8]).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use solana_sdk::message::CompileError;
+use solana_sdk::signer::SignerError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SdkError {
+    #[error("failed to compile v0 message: {0}")]
+    Compile(#[from] CompileError),
+
+    #[error("failed to sign transaction: {0}")]
+    Signer(#[from] SignerError),
+
+    #[error("transaction is {size} bytes, exceeding the {max} byte limit")]
+    TransactionTooLarge { size: usize, max: usize },
+
+    #[error("failed to serialize transaction: {0}")]
+    Serialization(String),
+
+    #[error("invalid EVM address: {0}")]
+    InvalidEvmAddress(String),
+
+    #[error("EVM address fails EIP-55 checksum: {0}")]
+    InvalidEvmChecksum(String),
+
+    #[error("malformed SuperSwap order message: {0}")]
+    InvalidOrderMessage(String),
+
+    #[error("RPC request failed: {0}")]
+    Rpc(String),
+
+    #[error("invalid return data: {0}")]
+    InvalidReturnData(String),
+
+    #[error("malformed program event or account: {0}")]
+    InvalidProgramData(String),
+
+    #[error("nonce account cannot be used: {0}")]
+    InvalidNonceAccount(String),
+}
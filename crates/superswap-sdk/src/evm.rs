@@ -0,0 +1,92 @@
+use solana_sdk::keccak;
+use crate::error::SdkError;
+
+/// A 20-byte EVM address
+pub type EvmAddress = [u8; 20];
+
+/// Parses a hex EVM address, with or without the `0x` prefix
+///
+/// All-lowercase and all-uppercase addresses are accepted as is. Mixed-case
+/// addresses must carry a valid EIP-55 checksum, which catches most typos.
+pub fn parse_evm_address(input: &str) -> Result<EvmAddress, SdkError> {
+    let hex = input.strip_prefix("0x").unwrap_or(input);
+    if hex.len() != 40 || !hex.is_ascii() {
+        return Err(SdkError::InvalidEvmAddress(input.to_string()));
+    }
+
+    let mut address = [0u8; 20];
+    for (i, byte) in address.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+            .map_err(|_| SdkError::InvalidEvmAddress(input.to_string()))?;
+    }
+
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && to_checksum_address(&address)[2..] != *hex {
+        return Err(SdkError::InvalidEvmChecksum(input.to_string()));
+    }
+
+    Ok(address)
+}
+
+/// Formats an EVM address with its EIP-55 checksum
+pub fn to_checksum_address(address: &EvmAddress) -> String {
+    let lower: String = address.iter().map(|byte| format!("{byte:02x}")).collect();
+    let hash = keccak::hash(lower.as_bytes()).to_bytes();
+
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    format!("0x{checksummed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn test_checksum_round_trip() {
+        for expected in CHECKSUMMED {
+            let address = parse_evm_address(expected).unwrap();
+            assert_eq!(to_checksum_address(&address), expected);
+        }
+    }
+
+    #[test]
+    fn test_single_case_accepted() {
+        let lower = CHECKSUMMED[0].to_lowercase();
+        assert!(parse_evm_address(&lower).is_ok());
+        assert!(parse_evm_address(&lower[2..]).is_ok());
+    }
+
+    #[test]
+    fn test_bad_checksum_rejected() {
+        let bad = CHECKSUMMED[0].replace("aA", "Aa");
+        assert!(matches!(parse_evm_address(&bad), Err(SdkError::InvalidEvmChecksum(_))));
+    }
+
+    #[test]
+    fn test_bad_length_rejected() {
+        assert!(matches!(
+            parse_evm_address("0x1234"),
+            Err(SdkError::InvalidEvmAddress(_))
+        ));
+    }
+}
@@ -0,0 +1,1014 @@
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use superswap_pda::{
+    escrow_token_account, find_admin_log, find_instance_admin_log, find_approved_delegate, find_config, find_dca_order, find_destination_mint, find_instance_config, find_instance_outbound_rate_limit, find_instance_recovery_guard, find_instance_rent_vault, find_donation_recipient, find_hook_program,
+    find_insurance_claim, find_insurance_vault, find_order_archive, find_outbound_rate_limit, find_pending_config_update, find_pending_recovery, find_recovery_guard, find_rent_vault,
+    find_mint_config, find_payer_ledger, find_route_template, find_spend_program, find_swap_order, find_vault_program, find_volume_tracker, insurance_usdc_account, program_usdc_account,
+    wallet_token_account,
+};
+use superswap_sol::state::{
+    ApprovedDelegateParams, CreateInstanceParams, CreateVolumeTrackerParams, DeleteMintConfigParams, DonationRecipientParams, FundRentVaultParams, HookProgramParams, InitializeParams,
+    InsuranceClaimParams, LamportSource, MintConfigParams, PayInsuranceParams, PayerOperatorParams, PendingRecoveryParams, ProposeConfigUpdateParams, QueueLamportRecoveryParams, QueueRecoveryParams, RebalanceTreasuryParams, RecoverFundsParams,
+    RecoverLamportsParams, RecoveryLimits, RegisterRouteTemplateParams, RemoveAcrossHandlerParams, RemoveRouteTemplateParams, SetDestinationMintParams, SetOutboundRateLimitParams,
+    SetRecoveryLimitsParams, SetRefundsOnlyParams, SpendProgramParams, UpdateConfigParams, VaultProgramParams,
+    WithdrawRentVaultParams,
+};
+use superswap_sol::{accounts, instruction};
+use super::{build, order_close_accounts, OrderRef};
+
+/// Builds an `initialize` instruction creating the config, rent vault, rate limit, recovery guard and admin log PDAs
+pub fn initialize(program_id: &Pubkey, admin: &Pubkey, params: InitializeParams) -> Instruction {
+    build(
+        program_id,
+        accounts::Initialize {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            rent_vault: find_rent_vault(program_id).0,
+            outbound_rate_limit: find_outbound_rate_limit(program_id).0,
+            recovery_guard: find_recovery_guard(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize { params },
+        &[],
+    )
+}
+
+/// Builds a `create_instance` instruction creating the config of instance `params.instance_id`
+///
+/// Signed by the primary instance's admin, who pays for the new config and the
+/// instance's rent vault, outbound rate limit and recovery guard
+pub fn create_instance(program_id: &Pubkey, admin: &Pubkey, params: CreateInstanceParams) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateInstance {
+            primary_config: find_config(program_id).0,
+            config: find_instance_config(program_id, params.instance_id).0,
+            admin_log: find_instance_admin_log(program_id, params.instance_id).0,
+            rent_vault: find_instance_rent_vault(program_id, params.instance_id).0,
+            outbound_rate_limit: find_instance_outbound_rate_limit(program_id, params.instance_id).0,
+            recovery_guard: find_instance_recovery_guard(program_id, params.instance_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::CreateInstance { params },
+        &[],
+    )
+}
+
+/// Builds an `update_config` instruction
+pub fn update_config(program_id: &Pubkey, admin: &Pubkey, params: UpdateConfigParams) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdateConfig {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            admin: *admin,
+        },
+        instruction::UpdateConfig { params },
+        &[],
+    )
+}
+
+/// Builds an `accept_admin` instruction, signed by the pending admin to take over the config
+pub fn accept_admin(program_id: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AcceptAdmin {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            new_admin: *new_admin,
+        },
+        instruction::AcceptAdmin {},
+        &[],
+    )
+}
+
+/// Builds a `propose_config_update` instruction queueing a timelocked change to the
+/// across handler, Jupiter program, fee recipient or update delay, or an additional handler
+pub fn propose_config_update(program_id: &Pubkey, admin: &Pubkey, params: ProposeConfigUpdateParams) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::ProposeConfigUpdate {
+            config,
+            admin_log: primary_admin_log(program_id),
+            pending_config_update: find_pending_config_update(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::ProposeConfigUpdate { params },
+        &[],
+    )
+}
+
+/// Builds an `apply_config_update` instruction applying the proposed update once its delay has passed
+pub fn apply_config_update(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::ApplyConfigUpdate {
+            config,
+            admin_log: primary_admin_log(program_id),
+            pending_config_update: find_pending_config_update(program_id).0,
+            admin: *admin,
+        },
+        instruction::ApplyConfigUpdate {},
+        &[],
+    )
+}
+
+/// Builds a `cancel_config_update` instruction discarding the proposed update
+pub fn cancel_config_update(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::CancelConfigUpdate {
+            config,
+            admin_log: primary_admin_log(program_id),
+            pending_config_update: find_pending_config_update(program_id).0,
+            admin: *admin,
+        },
+        instruction::CancelConfigUpdate {},
+        &[],
+    )
+}
+
+/// Builds a `create_mint_config` instruction setting a destination mint's overrides
+pub fn create_mint_config(program_id: &Pubkey, admin: &Pubkey, params: MintConfigParams) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::CreateMintConfig {
+            config,
+            admin_log: primary_admin_log(program_id),
+            mint_config: find_mint_config(program_id, &params.mint).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMintConfig { params },
+        &[],
+    )
+}
+
+/// Builds an `update_mint_config` instruction replacing a destination mint's overrides
+pub fn update_mint_config(program_id: &Pubkey, admin: &Pubkey, params: MintConfigParams) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::UpdateMintConfig {
+            config,
+            admin_log: primary_admin_log(program_id),
+            mint_config: find_mint_config(program_id, &params.mint).0,
+            admin: *admin,
+        },
+        instruction::UpdateMintConfig { params },
+        &[],
+    )
+}
+
+/// Builds a `delete_mint_config` instruction
+pub fn delete_mint_config(program_id: &Pubkey, admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::DeleteMintConfig {
+            config,
+            admin_log: primary_admin_log(program_id),
+            mint_config: find_mint_config(program_id, mint).0,
+            admin: *admin,
+        },
+        instruction::DeleteMintConfig {
+            params: DeleteMintConfigParams { mint: *mint },
+        },
+        &[],
+    )
+}
+
+/// Builds a `create_volume_tracker` instruction for `mint`, or for all mints
+/// when `mint` is the default pubkey
+pub fn create_volume_tracker(program_id: &Pubkey, admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::CreateVolumeTracker {
+            config,
+            admin_log: primary_admin_log(program_id),
+            volume_tracker: find_volume_tracker(program_id, mint).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::CreateVolumeTracker {
+            params: CreateVolumeTrackerParams { mint: *mint },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_across_handler` instruction revoking an additional Across handler
+pub fn remove_across_handler(program_id: &Pubkey, admin: &Pubkey, handler: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemoveAcrossHandler {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            admin: *admin,
+        },
+        instruction::RemoveAcrossHandler {
+            params: RemoveAcrossHandlerParams { handler: *handler },
+        },
+        &[],
+    )
+}
+
+/// Builds a `create_admin_log` instruction creating the admin log of a config that predates it
+pub fn create_admin_log(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateAdminLog {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::CreateAdminLog {},
+        &[],
+    )
+}
+
+/// Builds a `register_route_template` instruction vetting the route shape for a pair
+pub fn register_route_template(program_id: &Pubkey, admin: &Pubkey, params: RegisterRouteTemplateParams) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::RegisterRouteTemplate {
+            config,
+            admin_log: primary_admin_log(program_id),
+            route_template: find_route_template(program_id, &params.input_mint, &params.output_mint).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterRouteTemplate { params },
+        &[],
+    )
+}
+
+/// Builds a `remove_route_template` instruction
+pub fn remove_route_template(program_id: &Pubkey, admin: &Pubkey, input_mint: &Pubkey, output_mint: &Pubkey) -> Instruction {
+    let config = find_config(program_id).0;
+    build(
+        program_id,
+        accounts::RemoveRouteTemplate {
+            config,
+            admin_log: primary_admin_log(program_id),
+            route_template: find_route_template(program_id, input_mint, output_mint).0,
+            admin: *admin,
+        },
+        instruction::RemoveRouteTemplate {
+            params: RemoveRouteTemplateParams {
+                input_mint: *input_mint,
+                output_mint: *output_mint,
+            },
+        },
+        &[],
+    )
+}
+
+/// Builds a `migrate_config` instruction upgrading an older config account in place
+pub fn migrate_config(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::MigrateConfig {
+            config: find_config(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateConfig {},
+        &[],
+    )
+}
+
+/// Builds a `pause` instruction
+pub fn pause(program_id: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::Pause {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            authority: *authority,
+        },
+        instruction::Pause {},
+        &[],
+    )
+}
+
+/// Builds an `unpause` instruction
+pub fn unpause(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::Unpause {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            admin: *admin,
+        },
+        instruction::Unpause {},
+        &[],
+    )
+}
+
+/// Builds a `set_refunds_only` instruction entering or leaving refunds-only mode
+///
+/// The guardian may only enter the mode; leaving it takes the admin.
+pub fn set_refunds_only(program_id: &Pubkey, authority: &Pubkey, enabled: bool) -> Instruction {
+    build(
+        program_id,
+        accounts::SetRefundsOnly {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            authority: *authority,
+        },
+        instruction::SetRefundsOnly {
+            params: SetRefundsOnlyParams { enabled },
+        },
+        &[],
+    )
+}
+
+/// Builds a `set_destination_mint` instruction disabling or re-enabling fills into `mint`
+pub fn set_destination_mint(program_id: &Pubkey, admin: &Pubkey, mint: &Pubkey, disabled: bool) -> Instruction {
+    build(
+        program_id,
+        accounts::SetDestinationMint {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            destination_mint: find_destination_mint(program_id, mint).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::SetDestinationMint {
+            params: SetDestinationMintParams {
+                mint: *mint,
+                disabled,
+            },
+        },
+        &[],
+    )
+}
+
+/// Builds an `add_hook_program` instruction
+pub fn add_hook_program(program_id: &Pubkey, admin: &Pubkey, hook_program: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AddHookProgram {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            hook_program: find_hook_program(program_id, hook_program).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddHookProgram {
+            params: HookProgramParams { program_id: *hook_program },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_hook_program` instruction
+pub fn remove_hook_program(program_id: &Pubkey, admin: &Pubkey, hook_program: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemoveHookProgram {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            hook_program: find_hook_program(program_id, hook_program).0,
+            admin: *admin,
+        },
+        instruction::RemoveHookProgram {
+            params: HookProgramParams { program_id: *hook_program },
+        },
+        &[],
+    )
+}
+
+/// Builds an `add_spend_program` instruction
+pub fn add_spend_program(program_id: &Pubkey, admin: &Pubkey, spend_program: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AddSpendProgram {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            spend_program: find_spend_program(program_id, spend_program).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddSpendProgram {
+            params: SpendProgramParams { program_id: *spend_program },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_spend_program` instruction
+pub fn remove_spend_program(program_id: &Pubkey, admin: &Pubkey, spend_program: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemoveSpendProgram {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            spend_program: find_spend_program(program_id, spend_program).0,
+            admin: *admin,
+        },
+        instruction::RemoveSpendProgram {
+            params: SpendProgramParams { program_id: *spend_program },
+        },
+        &[],
+    )
+}
+
+/// Builds an `add_vault_program` instruction
+pub fn add_vault_program(program_id: &Pubkey, admin: &Pubkey, vault_program: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AddVaultProgram {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            vault_program: find_vault_program(program_id, vault_program).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddVaultProgram {
+            params: VaultProgramParams { program_id: *vault_program },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_vault_program` instruction
+pub fn remove_vault_program(program_id: &Pubkey, admin: &Pubkey, vault_program: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemoveVaultProgram {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            vault_program: find_vault_program(program_id, vault_program).0,
+            admin: *admin,
+        },
+        instruction::RemoveVaultProgram {
+            params: VaultProgramParams { program_id: *vault_program },
+        },
+        &[],
+    )
+}
+
+/// Builds an `add_approved_delegate` instruction
+pub fn add_approved_delegate(program_id: &Pubkey, admin: &Pubkey, delegate: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AddApprovedDelegate {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            approved_delegate: find_approved_delegate(program_id, delegate).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddApprovedDelegate {
+            params: ApprovedDelegateParams { delegate: *delegate },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_approved_delegate` instruction
+pub fn remove_approved_delegate(program_id: &Pubkey, admin: &Pubkey, delegate: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemoveApprovedDelegate {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            approved_delegate: find_approved_delegate(program_id, delegate).0,
+            admin: *admin,
+        },
+        instruction::RemoveApprovedDelegate {
+            params: ApprovedDelegateParams { delegate: *delegate },
+        },
+        &[],
+    )
+}
+
+/// Builds an `add_donation_recipient` instruction
+pub fn add_donation_recipient(program_id: &Pubkey, admin: &Pubkey, recipient: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AddDonationRecipient {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            donation_recipient: find_donation_recipient(program_id, recipient).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddDonationRecipient {
+            params: DonationRecipientParams { recipient: *recipient },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_donation_recipient` instruction
+pub fn remove_donation_recipient(program_id: &Pubkey, admin: &Pubkey, recipient: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemoveDonationRecipient {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            donation_recipient: find_donation_recipient(program_id, recipient).0,
+            admin: *admin,
+        },
+        instruction::RemoveDonationRecipient {
+            params: DonationRecipientParams { recipient: *recipient },
+        },
+        &[],
+    )
+}
+
+/// Builds an `add_payer_operator` instruction
+pub fn add_payer_operator(program_id: &Pubkey, admin: &Pubkey, operator: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::AddPayerOperator {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            payer_ledger: find_payer_ledger(program_id, operator).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddPayerOperator {
+            params: PayerOperatorParams { operator: *operator },
+        },
+        &[],
+    )
+}
+
+/// Builds a `remove_payer_operator` instruction
+pub fn remove_payer_operator(program_id: &Pubkey, admin: &Pubkey, operator: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::RemovePayerOperator {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            payer_ledger: find_payer_ledger(program_id, operator).0,
+            admin: *admin,
+        },
+        instruction::RemovePayerOperator {
+            params: PayerOperatorParams { operator: *operator },
+        },
+        &[],
+    )
+}
+
+/// Builds a `settle_payer_ledger` instruction paying `operator` from the fee recipient's USDC ATA
+pub fn settle_payer_ledger(
+    program_id: &Pubkey,
+    fee_recipient: &Pubkey,
+    operator: &Pubkey,
+    usdc_mint: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SettlePayerLedger {
+            config: find_config(program_id).0,
+            payer_ledger: find_payer_ledger(program_id, operator).0,
+            fee_recipient_account: wallet_token_account(fee_recipient, usdc_mint),
+            operator_usdc_account: wallet_token_account(operator, usdc_mint),
+            fee_recipient: *fee_recipient,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SettlePayerLedger {},
+        &[],
+    )
+}
+
+/// Builds a `fund_rent_vault` instruction
+pub fn fund_rent_vault(program_id: &Pubkey, authority: &Pubkey, amount: u64) -> Instruction {
+    build(
+        program_id,
+        accounts::FundRentVault {
+            config: find_config(program_id).0,
+            rent_vault: find_rent_vault(program_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::FundRentVault {
+            params: FundRentVaultParams { amount },
+        },
+        &[],
+    )
+}
+
+/// Builds a `withdraw_rent_vault` instruction
+pub fn withdraw_rent_vault(program_id: &Pubkey, admin: &Pubkey, amount: u64) -> Instruction {
+    build(
+        program_id,
+        accounts::WithdrawRentVault {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            rent_vault: find_rent_vault(program_id).0,
+            admin: *admin,
+        },
+        instruction::WithdrawRentVault {
+            params: WithdrawRentVaultParams { amount },
+        },
+        &[],
+    )
+}
+
+/// Builds a `set_outbound_rate_limit` instruction
+pub fn set_outbound_rate_limit(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    params: SetOutboundRateLimitParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SetOutboundRateLimit {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            outbound_rate_limit: find_outbound_rate_limit(program_id).0,
+            admin: *admin,
+        },
+        instruction::SetOutboundRateLimit { params },
+        &[],
+    )
+}
+
+/// Builds a `recover_funds` instruction moving tokens out of a program-owned account
+pub fn recover_funds(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    source_token_account: &Pubkey,
+    destination_token_account: &Pubkey,
+    params: RecoverFundsParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RecoverFunds {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            admin: *admin,
+            source_token_account: *source_token_account,
+            destination_token_account: *destination_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::RecoverFunds { params },
+        &[],
+    )
+}
+
+/// Builds a `queue_recovery` instruction for a recovery above the timelock threshold
+pub fn queue_recovery(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    source_token_account: &Pubkey,
+    destination_token_account: &Pubkey,
+    params: QueueRecoveryParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::QueueRecovery {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            pending_recovery: find_pending_recovery(program_id, params.recovery_id).0,
+            admin: *admin,
+            source_token_account: *source_token_account,
+            destination_token_account: *destination_token_account,
+            system_program: system_program::ID,
+        },
+        instruction::QueueRecovery { params },
+        &[],
+    )
+}
+
+/// Builds an `execute_recovery` instruction; the token accounts must be the ones queued
+pub fn execute_recovery(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    recovery_id: u64,
+    source_token_account: &Pubkey,
+    destination_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::ExecuteRecovery {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            pending_recovery: find_pending_recovery(program_id, recovery_id).0,
+            admin: *admin,
+            source_token_account: *source_token_account,
+            destination_token_account: *destination_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ExecuteRecovery {
+            params: PendingRecoveryParams { recovery_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `cancel_recovery` instruction
+pub fn cancel_recovery(program_id: &Pubkey, admin: &Pubkey, recovery_id: u64) -> Instruction {
+    build(
+        program_id,
+        accounts::CancelRecovery {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            pending_recovery: find_pending_recovery(program_id, recovery_id).0,
+            admin: *admin,
+        },
+        instruction::CancelRecovery {
+            params: PendingRecoveryParams { recovery_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `recover_lamports` instruction moving stray lamports out of a program-owned account
+pub fn recover_lamports(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    source: LamportSource,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RecoverLamports {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            admin: *admin,
+            source: source.address(program_id, 0),
+            destination: *destination,
+        },
+        instruction::RecoverLamports {
+            params: RecoverLamportsParams { source, amount },
+        },
+        &[],
+    )
+}
+
+/// Builds a `queue_lamport_recovery` instruction for a lamport recovery above the timelock threshold
+pub fn queue_lamport_recovery(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    destination: &Pubkey,
+    params: QueueLamportRecoveryParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::QueueLamportRecovery {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            pending_recovery: find_pending_recovery(program_id, params.recovery_id).0,
+            admin: *admin,
+            source: params.source.address(program_id, 0),
+            destination: *destination,
+            system_program: system_program::ID,
+        },
+        instruction::QueueLamportRecovery { params },
+        &[],
+    )
+}
+
+/// Builds an `execute_lamport_recovery` instruction; the accounts must be the ones queued
+pub fn execute_lamport_recovery(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    recovery_id: u64,
+    source: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::ExecuteLamportRecovery {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            pending_recovery: find_pending_recovery(program_id, recovery_id).0,
+            admin: *admin,
+            source: *source,
+            destination: *destination,
+        },
+        instruction::ExecuteLamportRecovery {
+            params: PendingRecoveryParams { recovery_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `set_recovery_limits` instruction
+pub fn set_recovery_limits(program_id: &Pubkey, admin: &Pubkey, limits: RecoveryLimits) -> Instruction {
+    build(
+        program_id,
+        accounts::SetRecoveryLimits {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            recovery_guard: find_recovery_guard(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::SetRecoveryLimits {
+            params: SetRecoveryLimitsParams { limits },
+        },
+        &[],
+    )
+}
+
+/// Builds an instruction creating the insurance fund's USDC account, paid for by `payer`
+///
+/// Must land before `insurance_fee_share_bps` is raised above zero, since fee
+/// transfers into a missing account fail.
+pub fn create_insurance_fund(program_id: &Pubkey, payer: &Pubkey, usdc_mint: &Pubkey) -> Instruction {
+    create_associated_token_account_idempotent(
+        payer,
+        &find_insurance_vault(program_id).0,
+        usdc_mint,
+        &anchor_spl::token::ID,
+    )
+}
+
+/// Builds a `pay_insurance` instruction paying `params.amount` USDC from the insurance fund
+pub fn pay_insurance(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    usdc_mint: &Pubkey,
+    destination_token_account: &Pubkey,
+    params: PayInsuranceParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::PayInsurance {
+            config: find_config(program_id).0,
+            recovery_guard: find_recovery_guard(program_id).0,
+            insurance_vault: find_insurance_vault(program_id).0,
+            insurance_usdc_account: insurance_usdc_account(program_id, usdc_mint),
+            destination_token_account: *destination_token_account,
+            admin: *admin,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::PayInsurance { params },
+        &[],
+    )
+}
+
+/// Builds an `approve_insurance_claim` instruction for the claim filed against `order_id`
+pub fn approve_insurance_claim(program_id: &Pubkey, admin: &Pubkey, order_id: u64) -> Instruction {
+    let swap_order = find_swap_order(program_id, order_id).0;
+    build(
+        program_id,
+        accounts::ApproveInsuranceClaim {
+            config: find_config(program_id).0,
+            swap_order,
+            insurance_claim: find_insurance_claim(program_id, &swap_order).0,
+            admin: *admin,
+        },
+        instruction::ApproveInsuranceClaim {
+            params: InsuranceClaimParams { order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `rebalance_treasury` instruction
+///
+/// `bridge_accounts` are the Across deposit or CCTP `deposit_for_burn` accounts.
+pub fn rebalance_treasury(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    usdc_mint: &Pubkey,
+    bridge_program: &Pubkey,
+    params: RebalanceTreasuryParams,
+    bridge_accounts: &[AccountMeta],
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RebalanceTreasury {
+            config: find_config(program_id).0,
+            admin_log: primary_admin_log(program_id),
+            admin: *admin,
+            usdc_mint: *usdc_mint,
+            program_usdc_account: program_usdc_account(program_id, usdc_mint),
+            bridge_program: *bridge_program,
+        },
+        instruction::RebalanceTreasury { params },
+        bridge_accounts,
+    )
+}
+
+/// Builds a `close_orders_batch` instruction closing the given swap orders
+///
+/// Each order comes with the token program owning its destination mint;
+/// `rent_payers` are the payers recorded on orders whose rent the rent vault
+/// did not fund; `rent_treasury` must be the config's rent treasury when one is set.
+pub fn close_orders_batch(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    swap_orders: &[(OrderRef, Pubkey)],
+    rent_payers: &[Pubkey],
+    rent_treasury: Option<Pubkey>,
+) -> Instruction {
+    let orders: Vec<AccountMeta> = swap_orders
+        .iter()
+        .flat_map(|(order, token_program)| order_close_accounts(program_id, order, token_program))
+        .chain(rent_payers.iter().map(|payer| AccountMeta::new(*payer, false)))
+        .collect();
+
+    build(
+        program_id,
+        accounts::CloseOrdersBatch {
+            config: find_config(program_id).0,
+            authority: *authority,
+            rent_vault: find_rent_vault(program_id).0,
+            rent_treasury,
+        },
+        instruction::CloseOrdersBatch {},
+        &orders,
+    )
+}
+
+/// Builds a `compress_orders` instruction closing the given swap orders into the order archive
+///
+/// Each order comes with the token program owning its destination mint;
+/// `rent_payers` are the payers recorded on orders whose rent the rent vault
+/// did not fund; `rent_treasury` must be the config's rent treasury when one is set.
+pub fn compress_orders(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    swap_orders: &[(OrderRef, Pubkey)],
+    rent_payers: &[Pubkey],
+    rent_treasury: Option<Pubkey>,
+) -> Instruction {
+    let orders: Vec<AccountMeta> = swap_orders
+        .iter()
+        .flat_map(|(order, token_program)| order_close_accounts(program_id, order, token_program))
+        .chain(rent_payers.iter().map(|payer| AccountMeta::new(*payer, false)))
+        .collect();
+    let config = find_config(program_id).0;
+
+    build(
+        program_id,
+        accounts::CompressOrders {
+            config,
+            authority: *authority,
+            order_archive: find_order_archive(program_id).0,
+            rent_vault: find_rent_vault(program_id).0,
+            rent_treasury,
+            system_program: system_program::ID,
+        },
+        instruction::CompressOrders {},
+        &orders,
+    )
+}
+
+/// Builds an `assert_solvency` instruction checking the USDC vault and DCA escrows
+///
+/// `owed_orders` are the swap orders still Pending or Failed, whose USDC the
+/// vault must cover; `dca_orders` are the swap orders of active DCA schedules,
+/// each checked against its own escrow.
+pub fn assert_solvency(
+    program_id: &Pubkey,
+    usdc_mint: &Pubkey,
+    owed_orders: &[Pubkey],
+    dca_orders: &[Pubkey],
+) -> Instruction {
+    let mut orders: Vec<AccountMeta> = owed_orders
+        .iter()
+        .map(|order| AccountMeta::new_readonly(*order, false))
+        .collect();
+    for order in dca_orders {
+        orders.extend([
+            AccountMeta::new_readonly(*order, false),
+            AccountMeta::new_readonly(find_dca_order(program_id, order).0, false),
+            AccountMeta::new_readonly(escrow_token_account(program_id, order, usdc_mint), false),
+        ]);
+    }
+
+    build(
+        program_id,
+        accounts::AssertSolvency {
+            config: find_config(program_id).0,
+            program_usdc_account: program_usdc_account(program_id, usdc_mint),
+        },
+        instruction::AssertSolvency {},
+        &orders,
+    )
+}
+
+/// Admin log of the primary config, written by every privileged instruction
+fn primary_admin_log(program_id: &Pubkey) -> Pubkey {
+    find_admin_log(program_id).0
+}
@@ -0,0 +1,283 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use superswap_pda::{
+    escrow_token_account, find_callback_authority, find_config, find_destination_mint,
+    find_donation_recipient, find_escrow, find_hook_program, find_mint_config, find_recipient_preferences, find_rent_vault, find_route_staging, find_route_template, find_swap_order, find_volume_tracker,
+    insurance_usdc_account, program_usdc_account, wallet_token_account, wallet_token_account_with_program,
+};
+use superswap_sol::state::{FinalizeRouteParams, ProcessBridgeAndSwapParams, UploadRouteChunkParams};
+use super::{build, payer_ledger};
+
+/// Accounts needed to fill an inbound order that are not derivable from the order itself
+#[derive(Debug, Clone)]
+pub struct FillAccounts {
+    pub across_handler: Pubkey,
+    pub payer: Pubkey,
+    pub source_usdc_account: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub jupiter_program: Pubkey,
+    /// Recipient's registered preferences, when the preferences PDA exists
+    pub recipient_preferences: Option<FillPreferences>,
+    /// Program owning a program-owned recipient, notified once the output is delivered
+    pub recipient_program: Option<Pubkey>,
+    /// Config's fallback mint, delivered instead of the order's destination mint once it is disabled
+    pub fallback_output_mint: Option<Pubkey>,
+    /// Whether the payer staged the route with `upload_route_chunks`, leaving
+    /// the params' `jupiter_swap_data` empty
+    pub staged_route: bool,
+    /// Whether the route follows the template registered for the USDC to
+    /// delivered mint pair, which the program then checks it against
+    pub route_template: bool,
+    /// Whether to pass the config's vault for the delivered mint, so the
+    /// protocol's share of any output above the route's quote is captured
+    pub surplus_vault: bool,
+    /// Whether to pass the payer's account for the delivered mint, required
+    /// for a captured surplus under `SurplusPolicy::PayerRebate`
+    pub payer_surplus_account: bool,
+    /// Whether to pass the fee recipient's account for the delivered mint,
+    /// required for a captured surplus under `SurplusPolicy::Treasury`
+    pub fee_recipient_surplus_account: bool,
+    /// Whether the delivered mint is a Token-2022 mint, whose accounts derive
+    /// under that program and which is then passed to the fill
+    pub token_2022_destination: bool,
+    /// Whether to pass the config-wide volume tracker, required while the
+    /// config sets a daily volume cap
+    pub global_volume_tracker: bool,
+    /// Whether to pass the requested mint's volume tracker, required while
+    /// its mint config sets a daily volume cap
+    pub mint_volume_tracker: bool,
+}
+
+/// The parts of a recipient's on-chain preferences that change the fill accounts
+#[derive(Debug, Clone, Default)]
+pub struct FillPreferences {
+    /// Preferred destination token account, when its mint matches the order's
+    pub destination_account: Option<Pubkey>,
+    /// USDC token account refunds go to instead of the recipient's ATA
+    pub refund_address: Option<Pubkey>,
+    /// Whether WSOL output is delivered as native SOL; without registered
+    /// preferences it always is
+    pub auto_unwrap_sol: bool,
+}
+
+/// Builds a `process_bridge_and_swap` instruction
+///
+/// `route_accounts` are the Jupiter route accounts, appended as remaining accounts;
+/// when the params carry a gas top-up, its route accounts go at the start of the
+/// slice, and when they carry a post-swap hook, its accounts go at the end.
+pub fn process_bridge_and_swap(
+    program_id: &Pubkey,
+    accounts: &FillAccounts,
+    params: ProcessBridgeAndSwapParams,
+    route_accounts: &[AccountMeta],
+) -> Instruction {
+    let (config, _) = find_config(program_id);
+    let (rent_vault, _) = find_rent_vault(program_id);
+    let (swap_order, _) = find_swap_order(program_id, params.order_id);
+    let hook_program = params.post_swap_hook.as_ref().map(|hook| hook.program_id);
+    let hook_program_entry = hook_program.map(|hook| find_hook_program(program_id, &hook).0);
+    let donation_recipient = params.donation.as_ref().map(|donation| donation.recipient);
+    let native_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let preferences = accounts.recipient_preferences.as_ref();
+    let delivered_mint = accounts.fallback_output_mint.unwrap_or(params.destination_mint);
+    let unwrap_sol = delivered_mint == native_mint
+        && preferences.map(|preferences| preferences.auto_unwrap_sol).unwrap_or(true);
+    let escrow_wsol = params.gas_top_up.is_some() || unwrap_sol;
+    let destination_token_program = if accounts.token_2022_destination {
+        anchor_spl::token_2022::ID
+    } else {
+        anchor_spl::token::ID
+    };
+
+    let mut account_metas = superswap_sol::accounts::ProcessBridgeAndSwap {
+        config,
+        swap_order,
+        across_handler: accounts.across_handler,
+        recipient: params.recipient,
+        usdc_mint: accounts.usdc_mint,
+        source_usdc_account: accounts.source_usdc_account,
+        program_usdc_account: program_usdc_account(program_id, &accounts.usdc_mint),
+        destination_mint: delivered_mint,
+        destination_mint_entry: find_destination_mint(program_id, &params.destination_mint).0,
+        mint_config: find_mint_config(program_id, &params.destination_mint).0,
+        recipient_destination_account: preferences
+            .and_then(|preferences| preferences.destination_account)
+            .unwrap_or_else(|| {
+                wallet_token_account_with_program(&params.recipient, &delivered_mint, &destination_token_program)
+            }),
+        recipient_usdc_account: preferences
+            .and_then(|preferences| preferences.refund_address)
+            .unwrap_or_else(|| wallet_token_account(&params.recipient, &accounts.usdc_mint)),
+        fee_recipient_account: wallet_token_account(&accounts.fee_recipient, &accounts.usdc_mint),
+        insurance_usdc_account: insurance_usdc_account(program_id, &accounts.usdc_mint),
+        fee_recipient: accounts.fee_recipient,
+        jupiter_program: accounts.jupiter_program,
+        rent_vault,
+        hook_program,
+        hook_program_entry,
+        donation_recipient_entry: donation_recipient
+            .map(|recipient| find_donation_recipient(program_id, &recipient).0),
+        donation_usdc_account: donation_recipient
+            .map(|recipient| wallet_token_account(&recipient, &accounts.usdc_mint)),
+        recipient_preferences: preferences
+            .map(|_| find_recipient_preferences(program_id, &params.recipient).0),
+        gas_escrow: escrow_wsol.then(|| find_escrow(program_id, &swap_order).0),
+        gas_wsol_account: escrow_wsol
+            .then(|| escrow_token_account(program_id, &swap_order, &native_mint)),
+        recipient_program: accounts.recipient_program,
+        callback_authority: accounts
+            .recipient_program
+            .map(|_| find_callback_authority(program_id).0),
+        native_mint: escrow_wsol.then_some(native_mint),
+        payer_ledger: payer_ledger(program_id, &accounts.payer, &accounts.across_handler),
+        payer: accounts.payer,
+        route_staging: accounts
+            .staged_route
+            .then(|| find_route_staging(program_id, &accounts.payer, params.order_id).0),
+        route_template: accounts
+            .route_template
+            .then(|| find_route_template(program_id, &accounts.usdc_mint, &delivered_mint).0),
+        relayer_usdc_account: (params.relayer_tip > 0)
+            .then(|| wallet_token_account(&accounts.payer, &accounts.usdc_mint)),
+        surplus_vault: accounts
+            .surplus_vault
+            .then(|| wallet_token_account_with_program(&config, &delivered_mint, &destination_token_program)),
+        payer_surplus_account: accounts
+            .payer_surplus_account
+            .then(|| wallet_token_account_with_program(&accounts.payer, &delivered_mint, &destination_token_program)),
+        fee_recipient_surplus_account: accounts
+            .fee_recipient_surplus_account
+            .then(|| wallet_token_account_with_program(&accounts.fee_recipient, &delivered_mint, &destination_token_program)),
+        destination_token_program: accounts.token_2022_destination.then_some(destination_token_program),
+        global_volume_tracker: accounts
+            .global_volume_tracker
+            .then(|| find_volume_tracker(program_id, &Pubkey::default()).0),
+        mint_volume_tracker: accounts
+            .mint_volume_tracker
+            .then(|| find_volume_tracker(program_id, &params.destination_mint).0),
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: system_program::ID,
+        rent: solana_sdk::sysvar::rent::ID,
+    }
+    .to_account_metas(None);
+    account_metas.extend_from_slice(route_accounts);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: account_metas,
+        data: superswap_sol::instruction::ProcessBridgeAndSwap { params }.data(),
+    }
+}
+
+/// Route bytes carried by each `upload_route_chunk`, leaving room in a legacy transaction
+pub const ROUTE_CHUNK_LEN: usize = 900;
+
+/// Builds the `upload_route_chunk` instructions staging `swap_data` for an order,
+/// followed by the `finalize_route` that locks it
+///
+/// Each instruction fits in its own transaction; send them in order, then fill
+/// with `FillAccounts::staged_route` set and empty `jupiter_swap_data`.
+pub fn upload_route_chunks(program_id: &Pubkey, authority: &Pubkey, order_id: u64, swap_data: &[u8]) -> Vec<Instruction> {
+    let config = find_config(program_id).0;
+    let route_staging = find_route_staging(program_id, authority, order_id).0;
+    let total_len = swap_data.len() as u32;
+
+    let mut instructions: Vec<Instruction> = swap_data
+        .chunks(ROUTE_CHUNK_LEN)
+        .enumerate()
+        .map(|(index, chunk)| {
+            build(
+                program_id,
+                superswap_sol::accounts::UploadRouteChunk {
+                    config,
+                    route_staging,
+                    authority: *authority,
+                    system_program: system_program::ID,
+                },
+                superswap_sol::instruction::UploadRouteChunk {
+                    params: UploadRouteChunkParams {
+                        order_id,
+                        total_len,
+                        offset: (index * ROUTE_CHUNK_LEN) as u32,
+                        data: chunk.to_vec(),
+                    },
+                },
+                &[],
+            )
+        })
+        .collect();
+    instructions.push(build(
+        program_id,
+        superswap_sol::accounts::FinalizeRoute {
+            config,
+            route_staging,
+            authority: *authority,
+        },
+        superswap_sol::instruction::FinalizeRoute {
+            params: FinalizeRouteParams { order_id },
+        },
+        &[],
+    ));
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_2022_fill_derives_destination_under_token_2022() {
+        let program_id = superswap_sol::ID;
+        let recipient = Pubkey::new_unique();
+        let destination_mint = Pubkey::new_unique();
+        let accounts = FillAccounts {
+            across_handler: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            source_usdc_account: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            jupiter_program: Pubkey::new_unique(),
+            recipient_preferences: None,
+            recipient_program: None,
+            fallback_output_mint: None,
+            staged_route: false,
+            route_template: false,
+            surplus_vault: false,
+            payer_surplus_account: false,
+            fee_recipient_surplus_account: false,
+            token_2022_destination: true,
+            global_volume_tracker: false,
+            mint_volume_tracker: false,
+        };
+        let params = ProcessBridgeAndSwapParams {
+            order_id: 1,
+            recipient,
+            usdc_amount: 1_000_000,
+            min_output_amount: 0,
+            destination_mint,
+            deadline: 0,
+            jupiter_swap_data: Vec::new(),
+            post_swap_hook: None,
+            gas_top_up: None,
+            donation: None,
+            relayer_tip: 0,
+            allow_off_curve_recipient: false,
+            refund_failed_swap: false,
+            gas_drop_lamports: 0,
+        };
+        let ix = process_bridge_and_swap(&program_id, &accounts, params, &[]);
+
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert!(keys.contains(&anchor_spl::token_2022::ID));
+        assert!(keys.contains(&wallet_token_account_with_program(
+            &recipient,
+            &destination_mint,
+            &anchor_spl::token_2022::ID
+        )));
+        assert!(!keys.contains(&wallet_token_account(&recipient, &destination_mint)));
+    }
+}
@@ -0,0 +1,43 @@
+//! Typed builders for every SuperSwap instruction
+//!
+//! Account metas come from the program's own Anchor account structs and data
+//! from its instruction structs, so builders track the program as it changes.
+//! Accounts that a builder can derive (PDAs, ATAs, escrows) are derived;
+//! everything else is taken as an argument.
+
+mod admin;
+mod inbound;
+mod orders;
+mod outbound;
+
+pub use admin::*;
+pub use inbound::*;
+pub use orders::*;
+pub use outbound::*;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use superswap_pda::find_payer_ledger;
+
+/// Ledger a fill's payer is reimbursed through; the Across handler paying its own fills has none
+fn payer_ledger(program_id: &Pubkey, payer: &Pubkey, across_handler: &Pubkey) -> Option<Pubkey> {
+    (payer != across_handler).then(|| find_payer_ledger(program_id, payer).0)
+}
+
+/// Builds an instruction from Anchor accounts and data, appending `remaining_accounts`
+fn build(
+    program_id: &Pubkey,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
@@ -0,0 +1,784 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use superswap_pda::{
+    escrow_token_account, escrow_token_account_with_program, find_approved_delegate, find_basket_plan, find_config, find_dca_order, find_destination_mint, find_escrow, find_evm_claim,
+    find_insurance_claim, find_insurance_vault, find_limit_order, find_order_extension, find_recipient_orders, find_recipient_preferences, find_rent_vault,
+    find_spend_program, find_split_plan, find_stream_plan, find_swap_order, find_vault_program,
+    find_recovery_guard, insurance_usdc_account, wallet_token_account,
+};
+use superswap_sol::state::{
+    CancelDcaOrderParams, CancelLimitOrderParams, ClaimEvmFundsParams, ClaimOutputParams, CloseOrderParams, CreateBasketPlanParams,
+    CreateDcaOrderParams, CreateOrderExtensionParams, CreateSplitPlanParams, CreateStreamPlanParams,
+    ExecuteBasketLegParams, ExecuteDcaTrancheParams, ExecutePostSwapActionParams, ExpireAndRefundParams,
+    ForwardOnwardParams,
+    InsuranceClaimParams,
+    PlaceLimitOrderParams, PostSwapAction, RevokeDelegateParams, SetRecipientPreferencesParams,
+};
+use superswap_sol::utils::limit_order::JUPITER_LIMIT_ORDER_PROGRAM_ID;
+use superswap_sol::{accounts, instruction};
+use super::{build, payer_ledger};
+
+/// An existing order's fields that follow-up instructions derive their accounts from
+#[derive(Debug, Clone, Copy)]
+pub struct OrderRef {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub destination_mint: Pubkey,
+}
+
+impl OrderRef {
+    fn swap_order(&self, program_id: &Pubkey) -> Pubkey {
+        find_swap_order(program_id, self.order_id).0
+    }
+}
+
+/// Accounts shared by the Across handler's order-creating instructions
+#[derive(Debug, Clone)]
+pub struct OrderCreationAccounts {
+    pub across_handler: Pubkey,
+    pub payer: Pubkey,
+    pub source_usdc_account: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+}
+
+/// Builds a `create_order_extension` instruction
+pub fn create_order_extension(
+    program_id: &Pubkey,
+    across_handler: &Pubkey,
+    payer: &Pubkey,
+    params: CreateOrderExtensionParams,
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, params.order_id).0;
+    build(
+        program_id,
+        accounts::CreateOrderExtension {
+            config: find_config(program_id).0,
+            swap_order,
+            order_extension: find_order_extension(program_id, &swap_order).0,
+            across_handler: *across_handler,
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateOrderExtension { params },
+        &[],
+    )
+}
+
+/// Builds a `create_stream_plan` instruction
+pub fn create_stream_plan(
+    program_id: &Pubkey,
+    across_handler: &Pubkey,
+    payer: &Pubkey,
+    params: CreateStreamPlanParams,
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, params.order_id).0;
+    build(
+        program_id,
+        accounts::CreateStreamPlan {
+            config: find_config(program_id).0,
+            swap_order,
+            stream_plan: find_stream_plan(program_id, &swap_order).0,
+            across_handler: *across_handler,
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateStreamPlan { params },
+        &[],
+    )
+}
+
+/// Builds a `create_split_plan` instruction
+pub fn create_split_plan(
+    program_id: &Pubkey,
+    across_handler: &Pubkey,
+    payer: &Pubkey,
+    params: CreateSplitPlanParams,
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, params.order_id).0;
+    build(
+        program_id,
+        accounts::CreateSplitPlan {
+            config: find_config(program_id).0,
+            swap_order,
+            split_plan: find_split_plan(program_id, &swap_order).0,
+            across_handler: *across_handler,
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSplitPlan { params },
+        &[],
+    )
+}
+
+/// Builds a `create_basket_plan` instruction
+pub fn create_basket_plan(
+    program_id: &Pubkey,
+    across_handler: &Pubkey,
+    payer: &Pubkey,
+    params: CreateBasketPlanParams,
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, params.order_id).0;
+    build(
+        program_id,
+        accounts::CreateBasketPlan {
+            config: find_config(program_id).0,
+            swap_order,
+            basket_plan: find_basket_plan(program_id, &swap_order).0,
+            across_handler: *across_handler,
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateBasketPlan { params },
+        &[],
+    )
+}
+
+/// Builds an `execute_basket_leg` instruction
+///
+/// `route_accounts` are the Jupiter route swapping the leg's USDC from the
+/// escrow into the beneficiary's ATA for `leg_mint`.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_basket_leg(
+    program_id: &Pubkey,
+    order_id: u64,
+    usdc_mint: &Pubkey,
+    leg_mint: &Pubkey,
+    beneficiary: &Pubkey,
+    jupiter_program: &Pubkey,
+    across_handler: &Pubkey,
+    payer: &Pubkey,
+    params: ExecuteBasketLegParams,
+    route_accounts: &[AccountMeta],
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, order_id).0;
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::ExecuteBasketLeg {
+            config: find_config(program_id).0,
+            swap_order,
+            basket_plan: find_basket_plan(program_id, &swap_order).0,
+            escrow,
+            escrow_usdc_account: wallet_token_account(&escrow, usdc_mint),
+            leg_mint: *leg_mint,
+            beneficiary: *beneficiary,
+            beneficiary_token_account: wallet_token_account(beneficiary, leg_mint),
+            jupiter_program: *jupiter_program,
+            rent_vault: find_rent_vault(program_id).0,
+            across_handler: *across_handler,
+            payer: *payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteBasketLeg { params },
+        route_accounts,
+    )
+}
+
+/// Builds a `forward_onward` instruction
+///
+/// `adapter_accounts` are the onward adapter's `TransferRemote` accounts.
+pub fn forward_onward(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    across_handler: &Pubkey,
+    onward_adapter: &Pubkey,
+    adapter_accounts: &[AccountMeta],
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    build(
+        program_id,
+        accounts::ForwardOnward {
+            config: find_config(program_id).0,
+            swap_order,
+            order_extension: find_order_extension(program_id, &swap_order).0,
+            escrow: find_escrow(program_id, &swap_order).0,
+            escrow_token_account: escrow_token_account(program_id, &swap_order, &order.destination_mint),
+            across_handler: *across_handler,
+            onward_adapter: *onward_adapter,
+        },
+        instruction::ForwardOnward {
+            params: ForwardOnwardParams { order_id: order.order_id },
+        },
+        adapter_accounts,
+    )
+}
+
+/// Builds an `execute_post_swap_action` instruction
+///
+/// The allowlist entry or plan the action needs is derived from `action`;
+/// `action_accounts` are the accounts of the action's CPI, in the order the
+/// program documents for each action.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_post_swap_action(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    action: &PostSwapAction,
+    beneficiary: &Pubkey,
+    action_program: &Pubkey,
+    across_handler: &Pubkey,
+    payer: &Pubkey,
+    params: ExecutePostSwapActionParams,
+    action_accounts: &[AccountMeta],
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::ExecutePostSwapAction {
+            config: find_config(program_id).0,
+            swap_order,
+            order_extension: find_order_extension(program_id, &swap_order).0,
+            escrow,
+            escrow_token_account: wallet_token_account(&escrow, &order.destination_mint),
+            beneficiary: *beneficiary,
+            rent_vault: find_rent_vault(program_id).0,
+            across_handler: *across_handler,
+            payer: *payer,
+            action_program: *action_program,
+            spend_program_entry: matches!(action, PostSwapAction::Spend { .. })
+                .then(|| find_spend_program(program_id, action_program).0),
+            vault_program_entry: matches!(action, PostSwapAction::DepositVault { .. })
+                .then(|| find_vault_program(program_id, action_program).0),
+            stream_plan: matches!(action, PostSwapAction::Stream)
+                .then(|| find_stream_plan(program_id, &swap_order).0),
+            split_plan: matches!(action, PostSwapAction::Split)
+                .then(|| find_split_plan(program_id, &swap_order).0),
+            approved_delegate_entry: matches!(action, PostSwapAction::ApproveDelegate { .. })
+                .then(|| find_approved_delegate(program_id, action_program).0),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecutePostSwapAction { params },
+        action_accounts,
+    )
+}
+
+/// Builds a `claim_output` instruction delivering the escrowed output to `destination_token_account`
+pub fn claim_output(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    claimant: &Pubkey,
+    destination_token_account: &Pubkey,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    build(
+        program_id,
+        accounts::ClaimOutput {
+            config: find_config(program_id).0,
+            swap_order,
+            order_extension: find_order_extension(program_id, &swap_order).0,
+            escrow: find_escrow(program_id, &swap_order).0,
+            escrow_token_account: escrow_token_account(program_id, &swap_order, &order.destination_mint),
+            destination_token_account: *destination_token_account,
+            claimant: *claimant,
+            rent_vault: find_rent_vault(program_id).0,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimOutput {
+            params: ClaimOutputParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `revoke_delegate` instruction returning what the delegate left to `beneficiary_token_account`
+pub fn revoke_delegate(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    beneficiary: &Pubkey,
+    beneficiary_token_account: &Pubkey,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    build(
+        program_id,
+        accounts::RevokeDelegate {
+            config: find_config(program_id).0,
+            swap_order,
+            order_extension: find_order_extension(program_id, &swap_order).0,
+            escrow: find_escrow(program_id, &swap_order).0,
+            escrow_token_account: escrow_token_account(program_id, &swap_order, &order.destination_mint),
+            beneficiary_token_account: *beneficiary_token_account,
+            beneficiary: *beneficiary,
+            rent_vault: find_rent_vault(program_id).0,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RevokeDelegate {
+            params: RevokeDelegateParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `claim_evm_funds` instruction releasing the EVM claim PDA's `mint` balance to `destination`
+///
+/// `params.signature` is the EVM key's `personal_sign` over
+/// `superswap_sol::utils::evm::claim_message(program_id, destination)`.
+pub fn claim_evm_funds(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    params: ClaimEvmFundsParams,
+) -> Instruction {
+    let evm_claim = find_evm_claim(program_id, &params.evm_address).0;
+    build(
+        program_id,
+        accounts::ClaimEvmFunds {
+            config: find_config(program_id).0,
+            evm_claim,
+            mint: *mint,
+            claim_token_account: wallet_token_account(&evm_claim, mint),
+            destination: *destination,
+            destination_token_account: wallet_token_account(destination, mint),
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimEvmFunds { params },
+        &[],
+    )
+}
+
+/// Builds a `place_limit_order` instruction
+///
+/// `order_accounts` are the Jupiter Limit Order `initialize_order` accounts.
+pub fn place_limit_order(
+    program_id: &Pubkey,
+    accounts: &OrderCreationAccounts,
+    params: PlaceLimitOrderParams,
+    order_accounts: &[AccountMeta],
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, params.order_id).0;
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::PlaceLimitOrder {
+            config: find_config(program_id).0,
+            swap_order,
+            limit_order: find_limit_order(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &params.recipient).0,
+            escrow,
+            across_handler: accounts.across_handler,
+            recipient: params.recipient,
+            usdc_mint: accounts.usdc_mint,
+            source_usdc_account: accounts.source_usdc_account,
+            escrow_usdc_account: wallet_token_account(&escrow, &accounts.usdc_mint),
+            destination_mint: params.destination_mint,
+            destination_mint_entry: find_destination_mint(program_id, &params.destination_mint).0,
+            escrow_output_account: wallet_token_account(&escrow, &params.destination_mint),
+            fee_recipient_account: wallet_token_account(&accounts.fee_recipient, &accounts.usdc_mint),
+            insurance_usdc_account: insurance_usdc_account(program_id, &accounts.usdc_mint),
+            rent_vault: find_rent_vault(program_id).0,
+            limit_order_program: JUPITER_LIMIT_ORDER_PROGRAM_ID,
+            payer_ledger: payer_ledger(program_id, &accounts.payer, &accounts.across_handler),
+            payer: accounts.payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::PlaceLimitOrder { params },
+        order_accounts,
+    )
+}
+
+/// Builds a `cancel_limit_order` instruction
+///
+/// `cancel_accounts` are the Jupiter Limit Order `cancel_order` accounts, or
+/// empty once the Jupiter order has been fully filled and closed. A keeper
+/// cancelling an expired order passes `keeper_usdc_account` to collect the
+/// refund bounty.
+pub fn cancel_limit_order(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    usdc_mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    keeper_usdc_account: Option<Pubkey>,
+    cancel_accounts: &[AccountMeta],
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::CancelLimitOrder {
+            config: find_config(program_id).0,
+            swap_order,
+            limit_order: find_limit_order(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &order.recipient).0,
+            escrow,
+            escrow_usdc_account: wallet_token_account(&escrow, usdc_mint),
+            escrow_output_account: wallet_token_account(&escrow, &order.destination_mint),
+            recipient: order.recipient,
+            usdc_mint: *usdc_mint,
+            destination_mint: order.destination_mint,
+            recipient_usdc_account: wallet_token_account(&order.recipient, usdc_mint),
+            recipient_destination_account: wallet_token_account(&order.recipient, &order.destination_mint),
+            authority: *authority,
+            keeper_usdc_account,
+            rent_vault: find_rent_vault(program_id).0,
+            limit_order_program: JUPITER_LIMIT_ORDER_PROGRAM_ID,
+            payer: *payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CancelLimitOrder {
+            params: CancelLimitOrderParams { order_id: order.order_id },
+        },
+        cancel_accounts,
+    )
+}
+
+/// Builds a `close_order` instruction closing a settled order
+///
+/// `destination_token_program` owns the order's destination mint; `rent_payer` is
+/// the payer recorded on the order when the rent vault did not fund its rent;
+/// `rent_treasury` must be the config's rent treasury when one is set.
+pub fn close_order(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    destination_token_program: &Pubkey,
+    closer: &Pubkey,
+    rent_payer: Option<Pubkey>,
+    rent_treasury: Option<Pubkey>,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    build(
+        program_id,
+        accounts::CloseOrder {
+            config: find_config(program_id).0,
+            swap_order,
+            order_extension: find_order_extension(program_id, &swap_order).0,
+            destination_mint: order.destination_mint,
+            escrow_token_account: escrow_token_account_with_program(
+                program_id,
+                &swap_order,
+                &order.destination_mint,
+                destination_token_program,
+            ),
+            rent_vault: find_rent_vault(program_id).0,
+            rent_treasury,
+            rent_payer,
+            closer: *closer,
+        },
+        instruction::CloseOrder {
+            params: CloseOrderParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Remaining accounts closing `order` in a batch: the order, its extension PDA, its
+/// destination mint and its escrow's account for that mint, derived under
+/// `destination_token_program`
+pub(super) fn order_close_accounts(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    destination_token_program: &Pubkey,
+) -> [AccountMeta; 4] {
+    let swap_order = order.swap_order(program_id);
+    [
+        AccountMeta::new(swap_order, false),
+        AccountMeta::new(find_order_extension(program_id, &swap_order).0, false),
+        AccountMeta::new_readonly(order.destination_mint, false),
+        AccountMeta::new_readonly(
+            escrow_token_account_with_program(program_id, &swap_order, &order.destination_mint, destination_token_program),
+            false,
+        ),
+    ]
+}
+
+/// Builds a `create_dca_order` instruction
+pub fn create_dca_order(
+    program_id: &Pubkey,
+    accounts: &OrderCreationAccounts,
+    params: CreateDcaOrderParams,
+) -> Instruction {
+    let swap_order = find_swap_order(program_id, params.order_id).0;
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::CreateDcaOrder {
+            config: find_config(program_id).0,
+            swap_order,
+            dca_order: find_dca_order(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &params.recipient).0,
+            escrow,
+            across_handler: accounts.across_handler,
+            recipient: params.recipient,
+            usdc_mint: accounts.usdc_mint,
+            source_usdc_account: accounts.source_usdc_account,
+            escrow_usdc_account: wallet_token_account(&escrow, &accounts.usdc_mint),
+            destination_mint: params.destination_mint,
+            destination_mint_entry: find_destination_mint(program_id, &params.destination_mint).0,
+            recipient_destination_account: wallet_token_account(&params.recipient, &params.destination_mint),
+            fee_recipient_account: wallet_token_account(&accounts.fee_recipient, &accounts.usdc_mint),
+            insurance_usdc_account: insurance_usdc_account(program_id, &accounts.usdc_mint),
+            rent_vault: find_rent_vault(program_id).0,
+            payer_ledger: payer_ledger(program_id, &accounts.payer, &accounts.across_handler),
+            payer: accounts.payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateDcaOrder { params },
+        &[],
+    )
+}
+
+/// Builds an `execute_dca_tranche` instruction
+///
+/// `route_accounts` are the Jupiter route accounts for the tranche swap.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_dca_tranche(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    usdc_mint: &Pubkey,
+    jupiter_program: &Pubkey,
+    keeper: &Pubkey,
+    jupiter_swap_data: Vec<u8>,
+    route_accounts: &[AccountMeta],
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::ExecuteDcaTranche {
+            config: find_config(program_id).0,
+            swap_order,
+            dca_order: find_dca_order(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &order.recipient).0,
+            destination_mint_entry: find_destination_mint(program_id, &order.destination_mint).0,
+            escrow,
+            escrow_usdc_account: wallet_token_account(&escrow, usdc_mint),
+            recipient_destination_account: wallet_token_account(&order.recipient, &order.destination_mint),
+            jupiter_program: *jupiter_program,
+            rent_vault: find_rent_vault(program_id).0,
+            keeper: *keeper,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteDcaTranche {
+            params: ExecuteDcaTrancheParams {
+                order_id: order.order_id,
+                jupiter_swap_data,
+            },
+        },
+        route_accounts,
+    )
+}
+
+/// Builds a `cancel_dca_order` instruction
+///
+/// A keeper cancelling an order past the TTL passes `keeper_usdc_account` to
+/// collect the refund bounty.
+pub fn cancel_dca_order(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    usdc_mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    keeper_usdc_account: Option<Pubkey>,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::CancelDcaOrder {
+            config: find_config(program_id).0,
+            swap_order,
+            dca_order: find_dca_order(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &order.recipient).0,
+            escrow,
+            escrow_usdc_account: wallet_token_account(&escrow, usdc_mint),
+            recipient: order.recipient,
+            usdc_mint: *usdc_mint,
+            recipient_usdc_account: wallet_token_account(&order.recipient, usdc_mint),
+            authority: *authority,
+            keeper_usdc_account,
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CancelDcaOrder {
+            params: CancelDcaOrderParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds an `expire_and_refund` instruction refunding a DCA order past the TTL
+///
+/// Any signer may submit it; passing `keeper_usdc_account` collects the refund bounty.
+pub fn expire_and_refund(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    usdc_mint: &Pubkey,
+    keeper: &Pubkey,
+    payer: &Pubkey,
+    keeper_usdc_account: Option<Pubkey>,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::ExpireAndRefund {
+            config: find_config(program_id).0,
+            swap_order,
+            dca_order: find_dca_order(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &order.recipient).0,
+            escrow,
+            escrow_usdc_account: wallet_token_account(&escrow, usdc_mint),
+            recipient: order.recipient,
+            usdc_mint: *usdc_mint,
+            recipient_usdc_account: wallet_token_account(&order.recipient, usdc_mint),
+            keeper: *keeper,
+            keeper_usdc_account,
+            rent_vault: find_rent_vault(program_id).0,
+            payer: *payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExpireAndRefund {
+            params: ExpireAndRefundParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `file_insurance_claim` instruction signed by the order's recipient
+pub fn file_insurance_claim(
+    program_id: &Pubkey,
+    order: &OrderRef,
+    usdc_mint: &Pubkey,
+    payout_token_account: &Pubkey,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    build(
+        program_id,
+        accounts::FileInsuranceClaim {
+            config: find_config(program_id).0,
+            swap_order,
+            dca_order: find_dca_order(program_id, &swap_order).0,
+            insurance_claim: find_insurance_claim(program_id, &swap_order).0,
+            recipient: order.recipient,
+            recipient_usdc_account: wallet_token_account(&order.recipient, usdc_mint),
+            payout_token_account: *payout_token_account,
+            system_program: system_program::ID,
+        },
+        instruction::FileInsuranceClaim {
+            params: InsuranceClaimParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `pay_insurance_claim` instruction settling an approved claim, signed by the admin
+pub fn pay_insurance_claim(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    order: &OrderRef,
+    usdc_mint: &Pubkey,
+    payout_token_account: &Pubkey,
+) -> Instruction {
+    let swap_order = order.swap_order(program_id);
+    let escrow = find_escrow(program_id, &swap_order).0;
+    build(
+        program_id,
+        accounts::PayInsuranceClaim {
+            config: find_config(program_id).0,
+            recovery_guard: find_recovery_guard(program_id).0,
+            swap_order,
+            dca_order: find_dca_order(program_id, &swap_order).0,
+            insurance_claim: find_insurance_claim(program_id, &swap_order).0,
+            recipient_orders: find_recipient_orders(program_id, &order.recipient).0,
+            escrow,
+            escrow_usdc_account: wallet_token_account(&escrow, usdc_mint),
+            insurance_vault: find_insurance_vault(program_id).0,
+            insurance_usdc_account: insurance_usdc_account(program_id, usdc_mint),
+            payout_token_account: *payout_token_account,
+            admin: *admin,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::PayInsuranceClaim {
+            params: InsuranceClaimParams { order_id: order.order_id },
+        },
+        &[],
+    )
+}
+
+/// Builds a `set_recipient_preferences` instruction signed by the recipient
+pub fn set_recipient_preferences(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    params: SetRecipientPreferencesParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SetRecipientPreferences {
+            recipient_preferences: find_recipient_preferences(program_id, owner).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::SetRecipientPreferences { params },
+        &[],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_swap_action_derives_only_its_own_entry() {
+        let program_id = superswap_sol::ID;
+        let spend_program = Pubkey::new_unique();
+        let order = OrderRef {
+            order_id: 9,
+            recipient: Pubkey::new_unique(),
+            destination_mint: Pubkey::new_unique(),
+        };
+        let action = PostSwapAction::Spend {
+            program: spend_program,
+            beneficiary: order.recipient,
+        };
+        let params = ExecutePostSwapActionParams {
+            order_id: order.order_id,
+            liquidity: None,
+            spend_data: Some(vec![1]),
+            min_vault_shares: None,
+        };
+        let ix = execute_post_swap_action(
+            &program_id,
+            &order,
+            &action,
+            &order.recipient,
+            &spend_program,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            params,
+            &[],
+        );
+
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert!(keys.contains(&find_spend_program(&program_id, &spend_program).0));
+        assert!(!keys.contains(&find_vault_program(&program_id, &spend_program).0));
+        assert!(!keys.contains(&find_split_plan(&program_id, &order.swap_order(&program_id)).0));
+    }
+}
@@ -0,0 +1,158 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use superswap_pda::{
+    find_config, find_outbound_order, find_outbound_rate_limit, insurance_usdc_account,
+    outbound_escrow_account, program_usdc_account, wallet_token_account,
+};
+use superswap_sol::state::{
+    QuoteOutboundParams, RefundOutboundParams, SettleOutboundOrderParams, SwapAndBridgeParams,
+    SwapAndBurnParams,
+};
+use superswap_sol::{accounts, instruction};
+use super::build;
+
+/// Accounts shared by the outbound swap instructions that are not derivable from the order
+#[derive(Debug, Clone)]
+pub struct OutboundAccounts {
+    pub user: Pubkey,
+    pub input_mint: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub jupiter_program: Pubkey,
+}
+
+/// Builds a `swap_and_bridge` instruction
+///
+/// `route_accounts` are the Jupiter route accounts followed by the Across
+/// deposit accounts, appended as remaining accounts.
+pub fn swap_and_bridge(
+    program_id: &Pubkey,
+    accounts: &OutboundAccounts,
+    across_spoke_pool: &Pubkey,
+    params: SwapAndBridgeParams,
+    route_accounts: &[AccountMeta],
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SwapAndBridge {
+            config: find_config(program_id).0,
+            outbound_order: find_outbound_order(program_id, params.order_id).0,
+            outbound_rate_limit: find_outbound_rate_limit(program_id).0,
+            user: accounts.user,
+            input_mint: accounts.input_mint,
+            usdc_mint: accounts.usdc_mint,
+            program_usdc_account: program_usdc_account(program_id, &accounts.usdc_mint),
+            fee_recipient_account: wallet_token_account(&accounts.fee_recipient, &accounts.usdc_mint),
+            insurance_usdc_account: insurance_usdc_account(program_id, &accounts.usdc_mint),
+            outbound_escrow: outbound_escrow_account(program_id, params.order_id, &accounts.usdc_mint),
+            jupiter_program: accounts.jupiter_program,
+            across_spoke_pool: *across_spoke_pool,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SwapAndBridge { params },
+        route_accounts,
+    )
+}
+
+/// Builds a `swap_and_burn` instruction
+///
+/// `route_accounts` are the Jupiter route accounts followed by the CCTP
+/// `deposit_for_burn` accounts, appended as remaining accounts.
+pub fn swap_and_burn(
+    program_id: &Pubkey,
+    accounts: &OutboundAccounts,
+    token_messenger_minter: &Pubkey,
+    params: SwapAndBurnParams,
+    route_accounts: &[AccountMeta],
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SwapAndBurn {
+            config: find_config(program_id).0,
+            outbound_order: find_outbound_order(program_id, params.order_id).0,
+            outbound_rate_limit: find_outbound_rate_limit(program_id).0,
+            user: accounts.user,
+            input_mint: accounts.input_mint,
+            usdc_mint: accounts.usdc_mint,
+            program_usdc_account: program_usdc_account(program_id, &accounts.usdc_mint),
+            fee_recipient_account: wallet_token_account(&accounts.fee_recipient, &accounts.usdc_mint),
+            insurance_usdc_account: insurance_usdc_account(program_id, &accounts.usdc_mint),
+            outbound_escrow: outbound_escrow_account(program_id, params.order_id, &accounts.usdc_mint),
+            jupiter_program: accounts.jupiter_program,
+            token_messenger_minter: *token_messenger_minter,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SwapAndBurn { params },
+        route_accounts,
+    )
+}
+
+/// Builds a `quote_outbound` instruction, meant for simulation
+pub fn quote_outbound(program_id: &Pubkey, params: QuoteOutboundParams) -> Instruction {
+    build(
+        program_id,
+        accounts::QuoteOutbound {
+            config: find_config(program_id).0,
+        },
+        instruction::QuoteOutbound { params },
+        &[],
+    )
+}
+
+/// Builds a `get_config` instruction, meant for simulation; see [`crate::decode_config_snapshot`]
+pub fn get_config(program_id: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::GetConfig {
+            config: find_config(program_id).0,
+        },
+        instruction::GetConfig {},
+        &[],
+    )
+}
+
+/// Builds a `settle_outbound_order` instruction
+pub fn settle_outbound_order(
+    program_id: &Pubkey,
+    across_handler: &Pubkey,
+    params: SettleOutboundOrderParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SettleOutboundOrder {
+            config: find_config(program_id).0,
+            outbound_order: find_outbound_order(program_id, params.order_id).0,
+            across_handler: *across_handler,
+        },
+        instruction::SettleOutboundOrder { params },
+        &[],
+    )
+}
+
+/// Builds a `refund_outbound` instruction returning the escrowed USDC to the sender's ATA
+pub fn refund_outbound(
+    program_id: &Pubkey,
+    sender: &Pubkey,
+    usdc_mint: &Pubkey,
+    params: RefundOutboundParams,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RefundOutbound {
+            config: find_config(program_id).0,
+            outbound_order: find_outbound_order(program_id, params.order_id).0,
+            sender: *sender,
+            usdc_mint: *usdc_mint,
+            outbound_escrow: outbound_escrow_account(program_id, params.order_id, usdc_mint),
+            sender_usdc_account: wallet_token_account(sender, usdc_mint),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::RefundOutbound { params },
+        &[],
+    )
+}
@@ -0,0 +1,29 @@
+//! Client SDK for the SuperSwap program
+//!
+//! Builds program instructions from the program's own Anchor types and
+//! assembles them into versioned transactions for relayers and tooling,
+//! optionally against a durable nonce for slow signing ceremonies.
+//! With the `rpc` feature, fills can be dry-run with [`simulate_fill`].
+//! Every program instruction has a builder in [`instructions`]; the PDA
+//! helpers and on-chain types they take are re-exported as [`pda`] and
+//! [`types`]. Events and accounts are decoded by [`ProgramEvent`] and
+//! [`ProgramAccount`], generated from the program's IDL at build time.
+
+pub mod decode;
+pub mod error;
+pub mod evm;
+pub mod instructions;
+pub mod message;
+pub mod simulation;
+pub mod transaction;
+
+pub use decode::*;
+pub use error::SdkError;
+pub use evm::*;
+pub use instructions::*;
+pub use message::OrderMessage;
+pub use simulation::*;
+pub use transaction::*;
+
+pub use superswap_pda as pda;
+pub use superswap_sol::state as types;
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, Pubkey};
+use superswap_sol::state::ProcessBridgeAndSwapParams;
+use crate::error::SdkError;
+
+/// SuperSwap order message embedded in the bridge transfer by the EVM side
+///
+/// Borsh-encoded, see `ACROSS_INTEGRATION.md`. The embedded Jupiter route is
+/// only a hint: it was quoted when the user deposited, so relayers quote a
+/// fresh route at fill time.
+///
+/// The relayer tip and the off-curve flag are optional trailing fields, so
+/// messages encoded before them still decode, with no tip and the flag unset.
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct OrderMessage {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+    pub min_output_amount: u64,
+    pub destination_mint: Pubkey,
+    pub deadline: i64,
+    pub jupiter_swap_data: Vec<u8>,
+    /// USDC the user pays the filling relayer for faster execution
+    pub relayer_tip: Option<u64>,
+    /// Allows `recipient` to be an off-curve address, such as a PDA-based treasury
+    pub allow_off_curve_recipient: Option<bool>,
+}
+
+impl OrderMessage {
+    /// Decodes a message, ignoring any bytes appended after it
+    pub fn decode(data: &[u8]) -> Result<Self, SdkError> {
+        let mut slice = data;
+        let invalid = |err: std::io::Error| SdkError::InvalidOrderMessage(err.to_string());
+        Ok(Self {
+            order_id: u64::deserialize(&mut slice).map_err(invalid)?,
+            recipient: Pubkey::deserialize(&mut slice).map_err(invalid)?,
+            usdc_amount: u64::deserialize(&mut slice).map_err(invalid)?,
+            min_output_amount: u64::deserialize(&mut slice).map_err(invalid)?,
+            destination_mint: Pubkey::deserialize(&mut slice).map_err(invalid)?,
+            deadline: i64::deserialize(&mut slice).map_err(invalid)?,
+            jupiter_swap_data: Vec::deserialize(&mut slice).map_err(invalid)?,
+            relayer_tip: if slice.is_empty() {
+                None
+            } else {
+                Option::deserialize(&mut slice).map_err(invalid)?
+            },
+            allow_off_curve_recipient: if slice.is_empty() {
+                None
+            } else {
+                Option::deserialize(&mut slice).map_err(invalid)?
+            },
+        })
+    }
+
+    /// Whether the order can still be filled at `now`
+    pub fn is_live(&self, now: i64) -> bool {
+        now < self.deadline
+    }
+
+    /// Fill parameters for the delivered amount and a freshly quoted route
+    pub fn into_params(self, delivered_usdc: u64, jupiter_swap_data: Vec<u8>) -> ProcessBridgeAndSwapParams {
+        ProcessBridgeAndSwapParams {
+            order_id: self.order_id,
+            recipient: self.recipient,
+            usdc_amount: delivered_usdc,
+            min_output_amount: self.min_output_amount,
+            destination_mint: self.destination_mint,
+            deadline: self.deadline,
+            jupiter_swap_data,
+            post_swap_hook: None,
+            gas_top_up: None,
+            donation: None,
+            relayer_tip: self.relayer_tip.unwrap_or(0),
+            allow_off_curve_recipient: self.allow_off_curve_recipient.unwrap_or(false),
+            refund_failed_swap: false,
+            gas_drop_lamports: 0,
+        }
+    }
+
+    /// Fill parameters settling the order with a full USDC refund after its swap failed
+    pub fn into_refund_params(self, delivered_usdc: u64) -> ProcessBridgeAndSwapParams {
+        ProcessBridgeAndSwapParams {
+            refund_failed_swap: true,
+            ..self.into_params(delivered_usdc, Vec::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_round_trips_with_trailing_bytes() {
+        let message = OrderMessage {
+            order_id: 42,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000_000,
+            min_output_amount: 950_000,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 1_800_000_000,
+            jupiter_swap_data: vec![1, 2, 3],
+            relayer_tip: Some(25_000),
+            allow_off_curve_recipient: Some(true),
+        };
+        let mut data = message.try_to_vec().unwrap();
+        data.extend_from_slice(&[0xff; 4]);
+
+        assert_eq!(OrderMessage::decode(&data).unwrap(), message);
+        assert!(OrderMessage::decode(&data[..10]).is_err());
+    }
+
+    #[test]
+    fn message_without_tip_decodes_untipped() {
+        let message = OrderMessage {
+            order_id: 42,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000_000,
+            min_output_amount: 950_000,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 1_800_000_000,
+            jupiter_swap_data: vec![1, 2, 3],
+            relayer_tip: None,
+            allow_off_curve_recipient: None,
+        };
+        let data = message.try_to_vec().unwrap();
+
+        // Encoded before the tip and the flag existed, the message ends after the route
+        assert_eq!(OrderMessage::decode(&data[..data.len() - 2]).unwrap(), message);
+        assert_eq!(OrderMessage::decode(&data[..data.len() - 1]).unwrap(), message);
+        assert_eq!(OrderMessage::decode(&data).unwrap(), message);
+        let params = message.into_params(1_000_000, Vec::new());
+        assert_eq!(params.relayer_tip, 0);
+        assert!(!params.allow_off_curve_recipient);
+        assert!(!params.refund_failed_swap);
+    }
+}
@@ -0,0 +1,141 @@
+use anchor_lang::AnchorDeserialize;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::TransactionError;
+use superswap_sol::state::{Config, FillResult};
+use crate::error::SdkError;
+
+/// Outcome of a simulated fill transaction
+#[derive(Debug, Clone)]
+pub struct FillSimulation {
+    /// Why the fill would fail, if it would
+    pub err: Option<TransactionError>,
+    /// Fee and delivered output the program returned; `None` when the fill failed
+    pub result: Option<FillResult>,
+    /// Compute units the whole transaction consumed
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+}
+
+impl FillSimulation {
+    /// Whether the fill would land and meet its minimum output
+    pub fn is_ok(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+/// Decodes the `FillResult` that `process_bridge_and_swap` sets as return data
+///
+/// Return data set by any other program (a post-swap hook, say) is ignored.
+pub fn decode_fill_result(
+    program_id: &Pubkey,
+    return_program_id: &str,
+    return_data_base64: &str,
+) -> Result<Option<FillResult>, SdkError> {
+    decode_return_data(program_id, return_program_id, return_data_base64)
+}
+
+/// Decodes the `Config` snapshot that `get_config` sets as return data
+///
+/// The snapshot is the account's data without its discriminator.
+pub fn decode_config_snapshot(
+    program_id: &Pubkey,
+    return_program_id: &str,
+    return_data_base64: &str,
+) -> Result<Option<Config>, SdkError> {
+    decode_return_data(program_id, return_program_id, return_data_base64)
+}
+
+fn decode_return_data<T: AnchorDeserialize>(
+    program_id: &Pubkey,
+    return_program_id: &str,
+    return_data_base64: &str,
+) -> Result<Option<T>, SdkError> {
+    if return_program_id != program_id.to_string() {
+        return Ok(None);
+    }
+    let data = BASE64
+        .decode(return_data_base64)
+        .map_err(|err| SdkError::InvalidReturnData(err.to_string()))?;
+    let value = T::deserialize(&mut data.as_slice())
+        .map_err(|err| SdkError::InvalidReturnData(err.to_string()))?;
+    Ok(Some(value))
+}
+
+/// Dry-runs a fill transaction and reports its expected output, fee and compute usage
+///
+/// Signatures are not verified and the blockhash is replaced by the
+/// cluster's, so relayers can validate a freshly quoted route before
+/// paying for a fill that would fail. The transaction must still fit in a
+/// packet.
+#[cfg(feature = "rpc")]
+pub async fn simulate_fill(
+    rpc: &solana_client::nonblocking::rpc_client::RpcClient,
+    builder: &crate::transaction::FillTransactionBuilder,
+    program_id: &Pubkey,
+) -> Result<FillSimulation, SdkError> {
+    use solana_client::rpc_config::RpcSimulateTransactionConfig;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::Signature;
+    use solana_sdk::transaction::VersionedTransaction;
+
+    let message = builder.compile(Hash::default())?;
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
+    };
+    crate::transaction::check_transaction_size(&transaction)?;
+
+    let simulation = rpc
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(rpc.commitment()),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|err| SdkError::Rpc(err.to_string()))?
+        .value;
+
+    let result = match (&simulation.err, &simulation.return_data) {
+        (None, Some(return_data)) => decode_fill_result(program_id, &return_data.program_id, &return_data.data.0)?,
+        _ => None,
+    };
+
+    Ok(FillSimulation {
+        err: simulation.err,
+        result,
+        units_consumed: simulation.units_consumed,
+        logs: simulation.logs.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorSerialize;
+
+    #[test]
+    fn fill_result_is_decoded_only_from_the_program() {
+        let program_id = Pubkey::new_unique();
+        let result = FillResult {
+            order_id: 7,
+            fee_amount: 1_000,
+            swap_amount: 999_000,
+            relayer_tip: 0,
+            amount_delivered: 42,
+        };
+        let encoded = BASE64.encode(result.try_to_vec().unwrap());
+
+        let decoded = decode_fill_result(&program_id, &program_id.to_string(), &encoded).unwrap();
+        assert_eq!(decoded, Some(result));
+
+        let hook = Pubkey::new_unique().to_string();
+        assert_eq!(decode_fill_result(&program_id, &hook, &encoded).unwrap(), None);
+        assert!(decode_fill_result(&program_id, &program_id.to_string(), "AAAA").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::nonce::state::{State as NonceState, Versions as NonceVersions};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::VersionedTransaction;
+use crate::error::SdkError;
+
+/// Assembles fill transactions as v0 messages
+///
+/// Legacy transactions cannot fit a realistic Jupiter route plus the
+/// program's own accounts, so every account available in the program's
+/// lookup tables or the route's lookup tables is referenced through them.
+///
+/// The builder is not specific to fills: refunds and other instructions are
+/// assembled the same way.
+#[derive(Debug, Clone)]
+pub struct FillTransactionBuilder {
+    payer: Pubkey,
+    durable_nonce: Option<DurableNonce>,
+    instructions: Vec<Instruction>,
+    program_lookup_tables: Vec<AddressLookupTableAccount>,
+    route_lookup_tables: Vec<AddressLookupTableAccount>,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+}
+
+impl FillTransactionBuilder {
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            durable_nonce: None,
+            instructions: Vec::new(),
+            program_lookup_tables: Vec::new(),
+            route_lookup_tables: Vec::new(),
+            compute_unit_limit: None,
+            compute_unit_price: None,
+        }
+    }
+
+    /// Builds against a durable nonce instead of a recent blockhash
+    ///
+    /// The transaction then stays valid until the nonce is advanced, which suits
+    /// signing ceremonies (multisig, MPC) that outlast a blockhash. Pass the
+    /// nonce account's stored value (see [`nonce_blockhash`]) wherever a
+    /// recent blockhash is expected; `nonce_authority` must sign.
+    pub fn durable_nonce(mut self, nonce_account: Pubkey, nonce_authority: Pubkey) -> Self {
+        self.durable_nonce = Some(DurableNonce {
+            nonce_account,
+            nonce_authority,
+        });
+        self
+    }
+
+    /// Appends an instruction to the transaction
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Lookup tables maintained for the program's static accounts
+    pub fn program_lookup_tables(mut self, tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.program_lookup_tables = tables;
+        self
+    }
+
+    /// Lookup tables returned by Jupiter for the route
+    pub fn route_lookup_tables(mut self, tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.route_lookup_tables = tables;
+        self
+    }
+
+    /// Sets the compute unit limit instruction
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Sets the compute unit price (priority fee) in micro-lamports
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// All lookup tables referenced by the message, deduplicated by address
+    fn lookup_tables(&self) -> Vec<AddressLookupTableAccount> {
+        let mut tables: Vec<AddressLookupTableAccount> = Vec::new();
+        for table in self
+            .program_lookup_tables
+            .iter()
+            .chain(self.route_lookup_tables.iter())
+        {
+            if !tables.iter().any(|existing| existing.key == table.key) {
+                tables.push(table.clone());
+            }
+        }
+        tables
+    }
+
+    /// Compiles the v0 message
+    ///
+    /// With a durable nonce, `recent_blockhash` is the nonce account's stored value.
+    pub fn compile(&self, recent_blockhash: Hash) -> Result<VersionedMessage, SdkError> {
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 3);
+        // The runtime only treats a transaction as nonced when the advance comes first
+        if let Some(nonce) = &self.durable_nonce {
+            instructions.push(system_instruction::advance_nonce_account(
+                &nonce.nonce_account,
+                &nonce.nonce_authority,
+            ));
+        }
+        if let Some(units) = self.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports));
+        }
+        instructions.extend(self.instructions.iter().cloned());
+
+        let message = v0::Message::try_compile(
+            &self.payer,
+            &instructions,
+            &self.lookup_tables(),
+            recent_blockhash,
+        )?;
+
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// Compiles and signs the transaction, rejecting it if it exceeds the packet size
+    pub fn build<T: Signer>(
+        &self,
+        recent_blockhash: Hash,
+        signers: &[&T],
+    ) -> Result<VersionedTransaction, SdkError> {
+        let message = self.compile(recent_blockhash)?;
+        let transaction = VersionedTransaction::try_new(message, signers)?;
+        check_transaction_size(&transaction)?;
+        Ok(transaction)
+    }
+}
+
+/// Nonce account a transaction is built against, see [`FillTransactionBuilder::durable_nonce`]
+#[derive(Debug, Clone, Copy)]
+struct DurableNonce {
+    nonce_account: Pubkey,
+    nonce_authority: Pubkey,
+}
+
+/// Reads the value a durable nonce transaction must use as its blockhash from a nonce account's data
+pub fn nonce_blockhash(nonce_account_data: &[u8]) -> Result<Hash, SdkError> {
+    let versions: NonceVersions = bincode::deserialize(nonce_account_data)
+        .map_err(|err| SdkError::InvalidNonceAccount(err.to_string()))?;
+    match versions {
+        NonceVersions::Current(state) => match *state {
+            NonceState::Initialized(data) => Ok(data.blockhash()),
+            NonceState::Uninitialized => Err(SdkError::InvalidNonceAccount("not initialized".to_string())),
+        },
+        // Legacy nonces must be advanced once before they can be used
+        NonceVersions::Legacy(_) => Err(SdkError::InvalidNonceAccount("legacy nonce must be advanced".to_string())),
+    }
+}
+
+/// Fetches a nonce account and returns its stored value, see [`nonce_blockhash`]
+#[cfg(feature = "rpc")]
+pub async fn fetch_nonce_blockhash(
+    rpc: &solana_client::nonblocking::rpc_client::RpcClient,
+    nonce_account: &Pubkey,
+) -> Result<Hash, SdkError> {
+    let data = rpc
+        .get_account_data(nonce_account)
+        .await
+        .map_err(|err| SdkError::Rpc(err.to_string()))?;
+    nonce_blockhash(&data)
+}
+
+/// Ensures a serialized transaction fits in a single packet
+pub fn check_transaction_size(transaction: &VersionedTransaction) -> Result<(), SdkError> {
+    let size = bincode_size(transaction)?;
+    if size > PACKET_DATA_SIZE {
+        return Err(SdkError::TransactionTooLarge {
+            size,
+            max: PACKET_DATA_SIZE,
+        });
+    }
+    Ok(())
+}
+
+fn bincode_size(transaction: &VersionedTransaction) -> Result<usize, SdkError> {
+    bincode::serialized_size(transaction)
+        .map(|size| size as usize)
+        .map_err(|err| SdkError::Serialization(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::nonce::state::{Data, DurableNonce as NonceValue};
+    use solana_sdk::system_program;
+
+    #[test]
+    fn durable_nonce_advance_comes_first() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let message = FillTransactionBuilder::new(payer)
+            .durable_nonce(nonce_account, payer)
+            .compute_unit_limit(400_000)
+            .instruction(Instruction::new_with_bytes(Pubkey::new_unique(), &[], Vec::new()))
+            .compile(Hash::new_unique())
+            .unwrap();
+
+        let first = &message.instructions()[0];
+        let keys = message.static_account_keys();
+        assert_eq!(keys[first.program_id_index as usize], system_program::ID);
+        assert_eq!(keys[first.accounts[0] as usize], nonce_account);
+        assert_eq!(message.instructions().len(), 3);
+    }
+
+    #[test]
+    fn reads_initialized_nonce() {
+        let value = NonceValue::from_blockhash(&Hash::new_unique());
+        let state = NonceState::Initialized(Data::new(Pubkey::new_unique(), value, 5_000));
+        let data = bincode::serialize(&NonceVersions::new(state)).unwrap();
+        assert_eq!(nonce_blockhash(&data).unwrap(), *value.as_hash());
+
+        let data = bincode::serialize(&NonceVersions::new(NonceState::Uninitialized)).unwrap();
+        assert!(nonce_blockhash(&data).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Across spoke pool and handler stand-in
+//!
+//! `fill_relay` takes the spoke pool's real instruction name and relay data
+//! layout, so a fill delivers USDC and an order message exactly as relayers
+//! and indexers see it on mainnet. `execute_as_handler` invokes any program
+//! with this program's handler PDA as a signer; tests register that PDA as
+//! SuperSwap's `across_handler` to drive the inbound path without a keypair.
+//! Never deploy this outside test validators.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+declare_id!("BcUFramauntHAWWTVQwWGHV6NtwxuVKyftX9veWaThwi");
+
+/// Seed of the PDA that signs as the Across handler
+pub const HANDLER_SEED: &[u8] = b"handler";
+
+#[program]
+pub mod mock_across {
+    use super::*;
+
+    /// Pays the relay's output from the relayer to the recipient and logs the fill
+    pub fn fill_relay(ctx: Context<FillRelay>, relay_hash: [u8; 32], relay_data: Option<RelayData>) -> Result<()> {
+        // The real spoke pool can also read relay data staged in a separate account
+        let relay_data = relay_data.ok_or(MockAcrossError::RelayDataRequired)?;
+        let recipient_token_account = &ctx.accounts.recipient_token_account;
+        require!(
+            recipient_token_account.owner == relay_data.recipient
+                && recipient_token_account.mint == relay_data.output_token,
+            MockAcrossError::InvalidRecipientAccount
+        );
+        require!(
+            Clock::get()?.unix_timestamp <= relay_data.fill_deadline as i64,
+            MockAcrossError::ExpiredFillDeadline
+        );
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.relayer_token_account.to_account_info(),
+                    to: recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.relayer.to_account_info(),
+                },
+            ),
+            relay_data.output_amount,
+        )?;
+
+        emit!(FilledRelay {
+            relay_hash,
+            relayer: ctx.accounts.relayer.key(),
+            depositor: relay_data.depositor,
+            recipient: relay_data.recipient,
+            output_token: relay_data.output_token,
+            output_amount: relay_data.output_amount,
+            origin_chain_id: relay_data.origin_chain_id,
+            deposit_id: relay_data.deposit_id,
+            message: relay_data.message,
+        });
+        Ok(())
+    }
+
+    /// Invokes `target_program` with `data` and the remaining accounts, signed by the handler PDA
+    pub fn execute_as_handler<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteAsHandler<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let handler = ctx.accounts.handler.key();
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == handler,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.handler.to_account_info());
+
+        let seeds = &[HANDLER_SEED, &[ctx.bumps.handler]];
+        invoke_signed(
+            &Instruction {
+                program_id: ctx.accounts.target_program.key(),
+                accounts,
+                data,
+            },
+            &account_infos,
+            &[&seeds[..]],
+        )?;
+        Ok(())
+    }
+}
+
+/// Relay data of a spoke pool fill, in the Across SVM layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayData {
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub exclusive_relayer: Pubkey,
+    pub input_token: Pubkey,
+    pub output_token: Pubkey,
+    pub input_amount: [u8; 32],
+    pub output_amount: u64,
+    pub origin_chain_id: u64,
+    pub deposit_id: [u8; 32],
+    pub fill_deadline: u32,
+    pub exclusivity_deadline: u32,
+    pub message: Vec<u8>,
+}
+
+/// Emitted for every fill, carrying the depositor's message
+#[event]
+pub struct FilledRelay {
+    pub relay_hash: [u8; 32],
+    pub relayer: Pubkey,
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub output_token: Pubkey,
+    pub output_amount: u64,
+    pub origin_chain_id: u64,
+    pub deposit_id: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[derive(Accounts)]
+pub struct FillRelay<'info> {
+    pub relayer: Signer<'info>,
+
+    #[account(mut, token::authority = relayer)]
+    pub relayer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAsHandler<'info> {
+    /// CHECK: PDA signing as the Across handler
+    #[account(seeds = [HANDLER_SEED], bump)]
+    pub handler: UncheckedAccount<'info>,
+
+    /// CHECK: Program invoked with the handler's signature
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum MockAcrossError {
+    #[msg("Relay data must be passed in the instruction")]
+    RelayDataRequired,
+    #[msg("Recipient token account does not match the relay data")]
+    InvalidRecipientAccount,
+    #[msg("Fill deadline has passed")]
+    ExpiredFillDeadline,
+}
@@ -0,0 +1,514 @@
+//! Jupiter v6 stand-in
+//!
+//! `route` and `shared_accounts_route` take Jupiter's instruction names, and
+//! so its discriminators, along with its account order and argument layout,
+//! so the program's Jupiter CPI runs against real-shaped swap data. Each swap
+//! pulls `in_amount` from the source account into a pool the test funds up
+//! front and pays the quoted output from it, enforcing the route's slippage
+//! the way Jupiter does. The output may be a Token-2022 mint, paid with
+//! `transfer_checked` so its transfer fee applies. `configure` makes the pool
+//! short the output or fail every swap. Never deploy this outside test validators.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::{self, TransferChecked};
+use anchor_spl::token_interface;
+
+declare_id!("2oDMnRNddzhMiqoSsirR7vaH6eALqw64VUianLcoh1pN");
+
+/// Seed of the PDA owning the pool's token accounts
+pub const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority";
+
+/// Seed of the pool's `Behavior` account
+pub const BEHAVIOR_SEED: &[u8] = b"behavior";
+
+/// Jupiter v6 `route` discriminator
+pub const ROUTE_DISCRIMINATOR: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+
+/// Jupiter v6 `shared_accounts_route` discriminator
+pub const SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+
+#[program]
+pub mod mock_jupiter {
+    use super::*;
+
+    /// Swaps the user's source tokens through the pool, paying the destination
+    /// account when one is passed and the user's destination account otherwise
+    pub fn route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Route<'info>>,
+        route_plan: Vec<RoutePlanStep>,
+        in_amount: u64,
+        quoted_out_amount: u64,
+        slippage_bps: u16,
+        _platform_fee_bps: u8,
+    ) -> Result<u64> {
+        require!(!route_plan.is_empty(), MockJupiterError::EmptyRoute);
+        let destination = match &ctx.accounts.destination_token_account {
+            Some(destination) => destination.to_account_info(),
+            None => ctx.accounts.user_destination_token_account.to_account_info(),
+        };
+        swap(
+            &ctx.accounts.token_program,
+            ctx.accounts.user_transfer_authority.to_account_info(),
+            ctx.accounts.user_source_token_account.to_account_info(),
+            destination,
+            ctx.remaining_accounts,
+            in_amount,
+            quoted_out_amount,
+            slippage_bps,
+        )
+    }
+
+    /// Swaps through Jupiter's shared program accounts; the mock moves tokens
+    /// between the user's accounts and the pool directly
+    pub fn shared_accounts_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SharedAccountsRoute<'info>>,
+        _id: u8,
+        route_plan: Vec<RoutePlanStep>,
+        in_amount: u64,
+        quoted_out_amount: u64,
+        slippage_bps: u16,
+        _platform_fee_bps: u8,
+    ) -> Result<u64> {
+        require!(!route_plan.is_empty(), MockJupiterError::EmptyRoute);
+        swap(
+            &ctx.accounts.token_program,
+            ctx.accounts.user_transfer_authority.to_account_info(),
+            ctx.accounts.source_token_account.to_account_info(),
+            ctx.accounts.destination_token_account.to_account_info(),
+            ctx.remaining_accounts,
+            in_amount,
+            quoted_out_amount,
+            slippage_bps,
+        )
+    }
+
+    /// Sets the share of the quoted output the pool pays, and whether every swap fails
+    pub fn configure(ctx: Context<Configure>, output_bps: u16, fail: bool) -> Result<()> {
+        let behavior = &mut ctx.accounts.behavior;
+        behavior.output_bps = output_bps;
+        behavior.fail = fail;
+        behavior.bump = ctx.bumps.behavior;
+        Ok(())
+    }
+}
+
+/// Moves `in_amount` into the pool and pays out the pool's share of `quoted_out_amount`
+///
+/// `pool_accounts` are the route's remaining accounts: the pool authority,
+/// its input and output token accounts and the behavior account, which may
+/// be uninitialized. A Token-2022 output account is followed by the output
+/// mint and the Token-2022 program.
+#[allow(clippy::too_many_arguments)]
+fn swap<'info>(
+    token_program: &Program<'info, Token>,
+    authority: AccountInfo<'info>,
+    source: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    pool_accounts: &'info [AccountInfo<'info>],
+    in_amount: u64,
+    quoted_out_amount: u64,
+    slippage_bps: u16,
+) -> Result<u64> {
+    let [pool_authority, pool_input, pool_output, behavior, output_accounts @ ..] = pool_accounts else {
+        return err!(MockJupiterError::MissingPoolAccounts);
+    };
+    let (expected_authority, pool_bump) = Pubkey::find_program_address(&[POOL_AUTHORITY_SEED], &crate::ID);
+    require_keys_eq!(pool_authority.key(), expected_authority, MockJupiterError::InvalidPool);
+    require_keys_eq!(behavior.key(), behavior_address(), MockJupiterError::InvalidPool);
+    let behavior = if behavior.data_is_empty() {
+        Behavior::DEFAULT
+    } else {
+        Account::<Behavior>::try_from(behavior)?.into_inner()
+    };
+    require!(!behavior.fail, MockJupiterError::InjectedFailure);
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: source,
+                to: pool_input.clone(),
+                authority,
+            },
+        ),
+        in_amount,
+    )?;
+
+    let out_amount = share(quoted_out_amount, behavior.output_bps);
+    let min_out_amount = share(quoted_out_amount, 10_000u16.saturating_sub(slippage_bps));
+    require!(out_amount >= min_out_amount, MockJupiterError::SlippageToleranceExceeded);
+
+    let seeds = &[POOL_AUTHORITY_SEED, &[pool_bump]];
+    if *pool_output.owner == token_2022::ID {
+        let [output_mint, token_2022_program, ..] = output_accounts else {
+            return err!(MockJupiterError::MissingPoolAccounts);
+        };
+        let decimals = token_interface::Mint::try_deserialize(&mut &output_mint.try_borrow_data()?[..])?.decimals;
+        token_2022::transfer_checked(
+            CpiContext::new_with_signer(
+                token_2022_program.clone(),
+                TransferChecked {
+                    from: pool_output.clone(),
+                    mint: output_mint.clone(),
+                    to: destination,
+                    authority: pool_authority.clone(),
+                },
+                &[&seeds[..]],
+            ),
+            out_amount,
+            decimals,
+        )?;
+    } else {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: pool_output.clone(),
+                    to: destination,
+                    authority: pool_authority.clone(),
+                },
+                &[&seeds[..]],
+            ),
+            out_amount,
+        )?;
+    }
+
+    Ok(out_amount)
+}
+
+fn share(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+/// Address of the pool's behavior account
+pub fn behavior_address() -> Pubkey {
+    Pubkey::find_program_address(&[BEHAVIOR_SEED], &crate::ID).0
+}
+
+/// One hop of a route plan, in Jupiter's layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoutePlanStep {
+    pub swap: Swap,
+    pub percent: u8,
+    pub input_index: u8,
+    pub output_index: u8,
+}
+
+/// The leading unit variants of Jupiter's `Swap` enum; the mock ignores which AMM a step names
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum Swap {
+    Saber,
+    SaberAddDecimalsDeposit,
+    SaberAddDecimalsWithdraw,
+    TokenSwap,
+}
+
+/// How the pool answers swaps
+#[account]
+pub struct Behavior {
+    /// Share of the quoted output paid, in basis points
+    pub output_bps: u16,
+    /// Fail every swap with `InjectedFailure`
+    pub fail: bool,
+    pub bump: u8,
+}
+
+impl Behavior {
+    pub const LEN: usize = 8 + 2 + 1 + 1;
+
+    /// Pays exactly the quoted output
+    pub const DEFAULT: Self = Self {
+        output_bps: 10_000,
+        fail: false,
+        bump: 0,
+    };
+}
+
+#[derive(Accounts)]
+pub struct Route<'info> {
+    pub token_program: Program<'info, Token>,
+
+    /// Owner of the source account; a PDA of the calling program signs through CPI
+    pub user_transfer_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub user_source_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_destination_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(mut)]
+    pub destination_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Output mint, unused by the mock
+    pub destination_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Platform fee account, unused by the mock
+    #[account(mut)]
+    pub platform_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Jupiter's event authority, unused by the mock
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: This program
+    pub program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SharedAccountsRoute<'info> {
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Jupiter's shared program authority, unused by the mock
+    pub program_authority: UncheckedAccount<'info>,
+
+    /// Owner of the source account; a PDA of the calling program signs through CPI
+    pub user_transfer_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Shared source account, unused by the mock
+    #[account(mut)]
+    pub program_source_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Shared destination account, unused by the mock
+    #[account(mut)]
+    pub program_destination_token_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub destination_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// CHECK: Input mint, unused by the mock
+    pub source_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Output mint, unused by the mock
+    pub destination_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Platform fee account, unused by the mock
+    #[account(mut)]
+    pub platform_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token-2022 program, unused by the mock
+    pub token_2022_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Jupiter's event authority, unused by the mock
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: This program
+    pub program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Configure<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Behavior::LEN,
+        seeds = [BEHAVIOR_SEED],
+        bump,
+    )]
+    pub behavior: Account<'info, Behavior>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// The first two codes match Jupiter's own
+#[error_code]
+pub enum MockJupiterError {
+    #[msg("Empty route")]
+    EmptyRoute,
+    #[msg("Slippage tolerance exceeded")]
+    SlippageToleranceExceeded,
+    #[msg("Swap failed as configured")]
+    InjectedFailure,
+    #[msg("Route is missing the pool accounts")]
+    MissingPoolAccounts,
+    #[msg("Pool accounts do not belong to the mock pool")]
+    InvalidPool,
+}
+
+#[cfg(not(target_os = "solana"))]
+pub mod client {
+    //! Builds mock swaps shaped like quoted Jupiter routes
+
+    use anchor_lang::prelude::*;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use anchor_spl::associated_token::{get_associated_token_address, get_associated_token_address_with_program_id};
+    use super::{behavior_address, RoutePlanStep, Swap, POOL_AUTHORITY_SEED};
+
+    /// A swap of `in_amount` of `input_mint` quoted at `quoted_out_amount` of `output_mint`
+    #[derive(Debug, Clone)]
+    pub struct MockSwap {
+        /// Owner of `source`; a PDA of the calling program
+        pub authority: Pubkey,
+        pub source: Pubkey,
+        pub destination: Pubkey,
+        pub input_mint: Pubkey,
+        pub output_mint: Pubkey,
+        /// Token program owning `output_mint`: SPL Token, or Token-2022
+        pub output_token_program: Pubkey,
+        pub in_amount: u64,
+        pub quoted_out_amount: u64,
+        pub slippage_bps: u16,
+    }
+
+    impl MockSwap {
+        /// A `route` instruction paying `destination`
+        pub fn route(&self) -> Instruction {
+            let mut accounts = crate::accounts::Route {
+                token_program: anchor_spl::token::ID,
+                user_transfer_authority: self.authority,
+                user_source_token_account: self.source,
+                user_destination_token_account: self.destination,
+                destination_token_account: None,
+                destination_mint: self.output_mint,
+                platform_fee_account: None,
+                event_authority: event_authority(),
+                program: crate::ID,
+            }
+            .to_account_metas(None);
+            accounts.extend(self.pool_accounts());
+
+            Instruction {
+                program_id: crate::ID,
+                accounts,
+                data: crate::instruction::Route {
+                    route_plan: single_step_plan(),
+                    in_amount: self.in_amount,
+                    quoted_out_amount: self.quoted_out_amount,
+                    slippage_bps: self.slippage_bps,
+                    _platform_fee_bps: 0,
+                }
+                .data(),
+            }
+        }
+
+        /// A `shared_accounts_route` instruction paying `destination`
+        pub fn shared_accounts_route(&self) -> Instruction {
+            let program_authority = Pubkey::find_program_address(&[b"authority", &[0]], &crate::ID).0;
+            let mut accounts = crate::accounts::SharedAccountsRoute {
+                token_program: anchor_spl::token::ID,
+                program_authority,
+                user_transfer_authority: self.authority,
+                source_token_account: self.source,
+                program_source_token_account: get_associated_token_address(&program_authority, &self.input_mint),
+                program_destination_token_account: get_associated_token_address_with_program_id(
+                    &program_authority,
+                    &self.output_mint,
+                    &self.output_token_program,
+                ),
+                destination_token_account: self.destination,
+                source_mint: self.input_mint,
+                destination_mint: self.output_mint,
+                platform_fee_account: None,
+                token_2022_program: None,
+                event_authority: event_authority(),
+                program: crate::ID,
+            }
+            .to_account_metas(None);
+            accounts.extend(self.pool_accounts());
+
+            Instruction {
+                program_id: crate::ID,
+                accounts,
+                data: crate::instruction::SharedAccountsRoute {
+                    _id: 0,
+                    route_plan: single_step_plan(),
+                    in_amount: self.in_amount,
+                    quoted_out_amount: self.quoted_out_amount,
+                    slippage_bps: self.slippage_bps,
+                    _platform_fee_bps: 0,
+                }
+                .data(),
+            }
+        }
+
+        /// Output below which the route reverts
+        pub fn min_out_amount(&self) -> u64 {
+            super::share(self.quoted_out_amount, 10_000u16.saturating_sub(self.slippage_bps))
+        }
+
+        fn pool_accounts(&self) -> Vec<AccountMeta> {
+            let authority = pool_authority();
+            let pool_output =
+                get_associated_token_address_with_program_id(&authority, &self.output_mint, &self.output_token_program);
+            let mut accounts = vec![
+                AccountMeta::new_readonly(authority, false),
+                AccountMeta::new(get_associated_token_address(&authority, &self.input_mint), false),
+                AccountMeta::new(pool_output, false),
+                AccountMeta::new_readonly(behavior_address(), false),
+            ];
+            if self.output_token_program == anchor_spl::token_2022::ID {
+                accounts.push(AccountMeta::new_readonly(self.output_mint, false));
+                accounts.push(AccountMeta::new_readonly(self.output_token_program, false));
+            }
+            accounts
+        }
+    }
+
+    /// A `configure` instruction paid for by `payer`
+    pub fn configure(payer: &Pubkey, output_bps: u16, fail: bool) -> Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::Configure {
+                behavior: behavior_address(),
+                payer: *payer,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Configure { output_bps, fail }.data(),
+        }
+    }
+
+    /// PDA owning the pool's token accounts
+    pub fn pool_authority() -> Pubkey {
+        Pubkey::find_program_address(&[POOL_AUTHORITY_SEED], &crate::ID).0
+    }
+
+    fn event_authority() -> Pubkey {
+        Pubkey::find_program_address(&[b"__event_authority"], &crate::ID).0
+    }
+
+    fn single_step_plan() -> Vec<RoutePlanStep> {
+        vec![RoutePlanStep {
+            swap: Swap::TokenSwap,
+            percent: 100,
+            input_index: 0,
+            output_index: 1,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn swap_discriminators_match_jupiter() {
+        assert_eq!(instruction::Route::DISCRIMINATOR, ROUTE_DISCRIMINATOR);
+        assert_eq!(instruction::SharedAccountsRoute::DISCRIMINATOR, SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR);
+    }
+
+    #[test]
+    fn slippage_floor_rounds_down() {
+        let swap = client::MockSwap {
+            authority: Pubkey::new_unique(),
+            source: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+            input_mint: Pubkey::new_unique(),
+            output_mint: Pubkey::new_unique(),
+            output_token_program: anchor_spl::token::ID,
+            in_amount: 1_000,
+            quoted_out_amount: 999,
+            slippage_bps: 50,
+        };
+        assert_eq!(swap.min_out_amount(), 994);
+        assert_eq!(share(999, 10_000), 999);
+    }
+}
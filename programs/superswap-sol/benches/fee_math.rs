@@ -0,0 +1,19 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use superswap_sol::utils::{calculate_fee, calculate_net_amount};
+
+fn bench_fee_math(c: &mut Criterion) {
+    c.bench_function("calculate_fee", |b| {
+        b.iter(|| calculate_fee(black_box(1_234_567_890), black_box(30)))
+    });
+
+    c.bench_function("calculate_fee_max_amount", |b| {
+        b.iter(|| calculate_fee(black_box(u64::MAX), black_box(1000)))
+    });
+
+    c.bench_function("calculate_net_amount", |b| {
+        b.iter(|| calculate_net_amount(black_box(1_234_567_890), black_box(30)))
+    });
+}
+
+criterion_group!(benches, bench_fee_math);
+criterion_main!(benches);
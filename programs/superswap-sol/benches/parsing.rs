@@ -0,0 +1,47 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use superswap_sol::state::ProcessBridgeAndSwapParams;
+use superswap_sol::utils::{parse_jupiter_swap_data, JUPITER_ROUTE_DISCRIMINATOR};
+
+/// Roughly the size of a multi-hop Jupiter route payload
+const ROUTE_DATA_LEN: usize = 512;
+
+fn sample_params() -> ProcessBridgeAndSwapParams {
+    ProcessBridgeAndSwapParams {
+        order_id: 42,
+        recipient: Pubkey::new_unique(),
+        usdc_amount: 1_000_000_000,
+        min_output_amount: 950_000_000,
+        destination_mint: Pubkey::new_unique(),
+        deadline: 1_700_000_000,
+        jupiter_swap_data: vec![7u8; ROUTE_DATA_LEN],
+        post_swap_hook: None,
+        gas_top_up: None,
+        donation: None,
+        relayer_tip: 0,
+        allow_off_curve_recipient: false,
+        refund_failed_swap: false,
+        gas_drop_lamports: 0,
+    }
+}
+
+fn bench_message_parsing(c: &mut Criterion) {
+    let encoded = sample_params().try_to_vec().unwrap();
+
+    c.bench_function("deserialize_bridge_message", |b| {
+        b.iter(|| ProcessBridgeAndSwapParams::try_from_slice(black_box(&encoded)))
+    });
+}
+
+fn bench_route_validation(c: &mut Criterion) {
+    let mut route = JUPITER_ROUTE_DISCRIMINATOR.to_vec();
+    route.resize(ROUTE_DATA_LEN, 7);
+
+    c.bench_function("parse_jupiter_swap_data", |b| {
+        b.iter(|| parse_jupiter_swap_data(black_box(&route)))
+    });
+}
+
+criterion_group!(benches, bench_message_parsing, bench_route_validation);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+//! Bridge message decoding: arbitrary bytes must never panic the parser or
+//! allocate unboundedly, and whatever decodes must re-encode to the bytes read
+
+#![no_main]
+
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use libfuzzer_sys::fuzz_target;
+use superswap_sol::state::ProcessBridgeAndSwapParams;
+use superswap_sol::utils::calculate_net_amount;
+
+fuzz_target!(|data: &[u8]| {
+    let mut remaining = data;
+    let Ok(params) = ProcessBridgeAndSwapParams::deserialize(&mut remaining) else {
+        return;
+    };
+    let consumed = data.len() - remaining.len();
+    assert_eq!(params.try_to_vec().unwrap(), &data[..consumed]);
+
+    // The handler nets the protocol fee out of whatever amount the message carries
+    for fee_bps in [0, 10, 30, 10_000] {
+        let net = calculate_net_amount(params.usdc_amount, fee_bps).unwrap();
+        assert!(net <= params.usdc_amount);
+    }
+});
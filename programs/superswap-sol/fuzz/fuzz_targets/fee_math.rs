@@ -0,0 +1,58 @@
+//! Fee and rounding math: no overflow panics for any amount, fees never
+//! exceed the amount for valid basis points, and splits and DCA tranches
+//! never hand out more than they were given
+
+#![no_main]
+
+use anchor_lang::prelude::Pubkey;
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use superswap_sol::state::{split_weighted, DcaOrder};
+use superswap_sol::utils::{calculate_fee, calculate_net_amount, net_of_stream_fee, STREAMFLOW_FEE_BPS};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    amount: u64,
+    fee_bps: u16,
+    weights: Vec<u64>,
+    tranche_count: u8,
+    tranche_amount: u64,
+    min_output_per_tranche: u64,
+    remaining_amount: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let amount = input.amount;
+
+    let fee = calculate_fee(amount, input.fee_bps).unwrap();
+    match calculate_net_amount(amount, input.fee_bps) {
+        Ok(net) => assert_eq!(fee + net, amount),
+        Err(_) => assert!(fee > amount),
+    }
+    if input.fee_bps <= 10_000 {
+        assert!(fee <= amount);
+    }
+
+    let net = net_of_stream_fee(amount).unwrap();
+    assert!(net as u128 + net as u128 * STREAMFLOW_FEE_BPS as u128 / 10_000 <= amount as u128);
+
+    if let Ok(parts) = split_weighted(amount, &input.weights) {
+        assert_eq!(parts.len(), input.weights.len());
+        assert_eq!(parts.iter().map(|part| *part as u128).sum::<u128>(), amount as u128);
+    }
+
+    let dca_order = DcaOrder {
+        swap_order: Pubkey::default(),
+        tranche_count: input.tranche_count,
+        tranche_amount: input.tranche_amount,
+        interval_seconds: 0,
+        next_execution_at: 0,
+        min_output_per_tranche: input.min_output_per_tranche,
+        remaining_amount: input.remaining_amount,
+        fills: Vec::new(),
+        bump: 0,
+    };
+    let usdc_in = dca_order.next_tranche_amount();
+    assert!(usdc_in <= dca_order.remaining_amount);
+    let _ = dca_order.tranche_min_output(usdc_in);
+});
@@ -0,0 +1,29 @@
+//! Jupiter route validation: malformed or unlisted swap data is rejected
+//! rather than panicking, and the output check accepts exactly the outputs at
+//! or above the minimum
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use superswap_sol::utils::{parse_jupiter_swap_data, validate_swap_output, JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    swap_data: Vec<u8>,
+    actual_output: u64,
+    min_output: u64,
+}
+
+fuzz_target!(|input: Input| {
+    // Only whitelisted discriminators with a full route tail parse
+    if let Ok(route) = parse_jupiter_swap_data(&input.swap_data) {
+        assert!(input.swap_data.len() >= 8 + 4 + 19);
+        if route.kind.is_exact_out() {
+            assert_eq!(input.swap_data[..8], JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR);
+        }
+    }
+
+    let validated = validate_swap_output(input.actual_output, input.min_output);
+    assert_eq!(validated.is_ok(), input.actual_output >= input.min_output);
+});
@@ -58,5 +58,283 @@ pub enum SuperSwapError {
 
     #[msg("Fee calculation failed")]
     FeeCalculationFailed,
-}
 
+    #[msg("Rent vault has insufficient lamports")]
+    InsufficientRentVaultBalance,
+
+    #[msg("Order is not in a closable state")]
+    OrderNotClosable,
+
+    #[msg("Too many orders in batch")]
+    BatchTooLarge,
+
+    #[msg("Invalid Across spoke pool")]
+    InvalidAcrossSpokePool,
+
+    #[msg("Invalid destination chain")]
+    InvalidDestinationChain,
+
+    #[msg("Bridge output exceeds bridged amount")]
+    InvalidBridgeOutputAmount,
+
+    #[msg("Invalid CCTP TokenMessengerMinter program")]
+    InvalidCctpProgram,
+
+    #[msg("Invalid order status for this operation")]
+    InvalidOrderStatus,
+
+    #[msg("Outbound amount outside configured limits")]
+    OutboundAmountOutOfRange,
+
+    #[msg("Outbound escrow is empty")]
+    EmptyOutboundEscrow,
+
+    #[msg("Invalid onward messaging adapter")]
+    InvalidOnwardAdapter,
+
+    #[msg("Order has no onward destination")]
+    MissingOnwardDestination,
+
+    #[msg("Outbound volume cap for the current period exceeded")]
+    OutboundRateLimitExceeded,
+
+    #[msg("Post-swap hook program is not allowlisted")]
+    HookProgramNotAllowed,
+
+    #[msg("Order has no post-swap action")]
+    MissingPostSwapAction,
+
+    #[msg("Invalid liquid staking pool or accounts")]
+    InvalidStakePool,
+
+    #[msg("Invalid lending market or accounts")]
+    InvalidLendingMarket,
+
+    #[msg("Invalid liquidity pool or accounts")]
+    InvalidLiquidityPool,
+
+    #[msg("Invalid limit price")]
+    InvalidLimitPrice,
+
+    #[msg("Invalid DCA tranche schedule")]
+    InvalidTrancheSchedule,
+
+    #[msg("DCA tranche is not due yet")]
+    TrancheNotDue,
+
+    #[msg("Gas top-up is disabled or exceeds the configured cap")]
+    InvalidGasTopUp,
+
+    #[msg("Order extension sets more than one delivery mode")]
+    ConflictingDeliveryModes,
+
+    #[msg("Order has no claimant")]
+    MissingClaimant,
+
+    #[msg("Spend program is not allowlisted")]
+    SpendProgramNotAllowed,
+
+    #[msg("Spend program did not consume the full output")]
+    OutputNotConsumed,
+
+    #[msg("Invalid stream plan")]
+    InvalidStreamPlan,
+
+    #[msg("Donation is disabled, exceeds the configured cap or targets a recipient that is not allowlisted")]
+    InvalidDonation,
+
+    #[msg("Invalid split plan")]
+    InvalidSplitPlan,
+
+    #[msg("Vault program is not allowlisted")]
+    VaultProgramNotAllowed,
+
+    #[msg("Invalid vault")]
+    InvalidVault,
+
+    #[msg("Order is below the minimum order size")]
+    OrderBelowMinimum,
+
+    #[msg("Recipient has too many open orders")]
+    TooManyOpenOrders,
+
+    #[msg("Destination mint is disabled")]
+    DestinationMintDisabled,
+
+    #[msg("Swap output fell too far short of the route's quote")]
+    PriceImpactExceeded,
+
+    #[msg("Order is past its TTL and can only be refunded")]
+    OrderTtlExpired,
+
+    #[msg("Bridge is paused")]
+    BridgePaused,
+
+    #[msg("Recovery exceeds the timelock threshold and must be queued")]
+    RecoveryRequiresTimelock,
+
+    #[msg("Queued recovery is still timelocked")]
+    RecoveryTimelockActive,
+
+    #[msg("Recovery cap exceeded for the current period")]
+    RecoveryCapExceeded,
+
+    #[msg("Account is not the insurance fund's USDC account")]
+    InvalidInsuranceAccount,
+
+    #[msg("Order can still be refunded, so it is not eligible for an insurance claim")]
+    InsuranceClaimNotEligible,
+
+    #[msg("Insurance claim exceeds the per-claim cap")]
+    InsuranceClaimTooLarge,
+
+    #[msg("Invalid insurance claim status")]
+    InvalidClaimStatus,
+
+    #[msg("Order archive is full")]
+    OrderArchiveFull,
+
+    #[msg("Signature does not recover to the claim's EVM address")]
+    InvalidEvmSignature,
+
+    #[msg("Mint is neither the order's destination mint nor its configured fallback")]
+    InvalidFallbackOutput,
+
+    #[msg("Account to return the order's rent to is missing")]
+    InvalidRentDestination,
+
+    #[msg("Order is still within its retention period")]
+    RetentionPeriodActive,
+
+    #[msg("Payer is neither the Across handler nor a registered operator")]
+    PayerNotRegistered,
+
+    #[msg("Payer operator still has an unsettled balance")]
+    PayerLedgerNotSettled,
+
+    #[msg("Config account is not in the layout being migrated")]
+    ConfigNotMigratable,
+
+    #[msg("Bridge adapter is disabled")]
+    AdapterDisabled,
+
+    #[msg("Enabled adapter set has unknown bits")]
+    InvalidAdapterSet,
+
+    #[msg("Order amount exceeds the configured maximum")]
+    OrderAboveMaximum,
+
+    #[msg("Account belongs to another deployment instance")]
+    WrongInstance,
+
+    #[msg("Bridge adapter is not built into this program")]
+    AdapterNotBuilt,
+
+    #[msg("Account is not the order's escrow USDC account")]
+    InvalidEscrowAccount,
+
+    #[msg("Balances do not cover outstanding orders")]
+    Insolvent,
+
+    #[msg("Program only accepts refunds, claims and closures")]
+    RefundsOnly,
+
+    #[msg("Staged route data exceeds its declared or maximum length")]
+    RouteDataTooLarge,
+
+    #[msg("Route chunk does not continue the staged data")]
+    RouteChunkOutOfOrder,
+
+    #[msg("Staged route is missing chunks")]
+    RouteIncomplete,
+
+    #[msg("Staged route is already finalized")]
+    RouteAlreadyFinalized,
+
+    #[msg("Staged route has not been finalized")]
+    RouteNotFinalized,
+
+    #[msg("Route does not match the pair's registered template")]
+    RouteTemplateMismatch,
+
+    #[msg("Route template describes no accounts or too many")]
+    InvalidRouteTemplate,
+
+    #[msg("Relayer tip exceeds the configured cap or the order, or has no relayer USDC account")]
+    InvalidRelayerTip,
+
+    #[msg("Basket legs must name distinct mints with weights summing to 10000 bps")]
+    InvalidBasketPlan,
+
+    #[msg("Basket leg has already executed")]
+    BasketLegAlreadyExecuted,
+
+    #[msg("Recipient is an off-curve address and the order does not allow one")]
+    OffCurveRecipient,
+
+    #[msg("Delegate is not allowlisted")]
+    DelegateNotAllowed,
+
+    #[msg("Order's delegate has already been approved")]
+    DelegateAlreadyApproved,
+
+    #[msg("Lamports can only be recovered from the instance's own program-owned accounts, other than the rent vault")]
+    InvalidLamportSource,
+
+    #[msg("Amount exceeds the lamports held above the account's rent-exempt minimum")]
+    InsufficientStrayLamports,
+
+    #[msg("Route's token accounts are not the program's USDC account and the order's delivery account")]
+    RouteAccountMismatch,
+
+    #[msg("Gas drop is disabled, exceeds the configured cap or is below the rent-exempt minimum")]
+    InvalidGasDrop,
+
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+
+    #[msg("Config update delay is negative or lowered outside a proposed update")]
+    InvalidConfigUpdateDelay,
+
+    #[msg("Proposed config update is still timelocked")]
+    ConfigUpdateTimelockActive,
+
+    #[msg("Across handler is already registered or invalid")]
+    AcrossHandlerAlreadyRegistered,
+
+    #[msg("No room for another Across handler")]
+    AcrossHandlerLimitReached,
+
+    #[msg("Across handler is not an additional handler of this config")]
+    AcrossHandlerNotRegistered,
+
+    #[msg("Order would exceed today's fill volume cap")]
+    DailyVolumeCapExceeded,
+
+    #[msg("Daily volume cap is set but its volume tracker was not passed")]
+    MissingVolumeTracker,
+
+    #[msg("Minimum order size exceeds the maximum")]
+    InvalidOrderSizeRange,
+
+    #[msg("Surplus policy rebates the payer but the payer's account for the delivered mint was not passed")]
+    MissingSurplusRebateAccount,
+
+    #[msg("Account is not the order's escrow account for its destination mint")]
+    InvalidOutputEscrowAccount,
+
+    #[msg("Order's escrow still holds output")]
+    EscrowNotEmpty,
+
+    #[msg("Account is not the order's extension")]
+    InvalidOrderExtension,
+
+    #[msg("Config captures surplus but its surplus vault for the delivered mint was not passed")]
+    MissingSurplusVault,
+
+    #[msg("Surplus policy pays the treasury but the fee recipient's account for the delivered mint was not passed")]
+    MissingSurplusFeeAccount,
+
+    #[msg("Order is not past its deadline yet")]
+    OrderNotExpired,
+}
@@ -0,0 +1,533 @@
+use anchor_lang::prelude::*;
+use crate::state::{LendingMarket, LiquidStakingPool, LiquidityProtocol, OutboundBridge, RecoveryLimits, SurplusPolicy};
+
+/// Emitted when an outbound order is created
+#[event]
+pub struct OutboundCreated {
+    pub order_id: u64,
+    pub sender: Pubkey,
+    pub input_mint: Pubkey,
+    pub bridge: OutboundBridge,
+    pub destination_chain_id: u64,
+    pub recipient: [u8; 20],
+}
+
+/// Emitted when an outbound order's USDC is handed to the bridge
+#[event]
+pub struct OutboundBridged {
+    pub order_id: u64,
+    pub usdc_amount: u64,
+    pub fee_amount: u64,
+    pub bridged_amount: u64,
+}
+
+/// Emitted when delivery of an outbound order is confirmed on the destination chain
+#[event]
+pub struct OutboundSettled {
+    pub order_id: u64,
+    pub destination_tx_hash: [u8; 32],
+}
+
+/// Emitted when an outbound order's escrowed USDC is returned to the sender
+#[event]
+pub struct OutboundRefunded {
+    pub order_id: u64,
+    pub sender: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when protocol USDC is bridged back to the EVM treasury
+#[event]
+pub struct TreasuryRebalanced {
+    pub bridge: OutboundBridge,
+    pub amount: u64,
+    pub destination_chain_id: u64,
+    pub recipient: [u8; 20],
+}
+
+/// Emitted when a completed order's output is forwarded to another SVM chain
+#[event]
+pub struct OnwardForwarded {
+    pub order_id: u64,
+    pub domain: u32,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when an order's bridged USDC is placed as a Jupiter limit order
+#[event]
+pub struct LimitOrderPlaced {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub jupiter_order: Pubkey,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+}
+
+/// Emitted when a limit order is cancelled and its balances delivered to the recipient
+#[event]
+pub struct LimitOrderCancelled {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_returned: u64,
+    pub output_delivered: u64,
+    pub keeper_bounty: u64,
+}
+
+/// Emitted when an order's bridged USDC is scheduled as DCA tranches
+#[event]
+pub struct DcaOrderCreated {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub tranche_count: u8,
+    pub tranche_amount: u64,
+    pub interval_seconds: i64,
+}
+
+/// Emitted when a keeper executes a DCA tranche
+#[event]
+pub struct DcaTrancheExecuted {
+    pub order_id: u64,
+    pub tranche: u8,
+    pub usdc_in: u64,
+    pub output: u64,
+    pub remaining_amount: u64,
+    /// Output the tranche's Jupiter route was quoted at (0 when the route has no readable quote)
+    pub quoted_output: u64,
+}
+
+/// Emitted when part of an order's USDC is converted into SOL for the recipient's gas
+#[event]
+pub struct GasToppedUp {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+    pub lamports: u64,
+}
+
+/// Emitted when part of an order is routed to a donation recipient
+#[event]
+pub struct DonationRouted {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub donation_recipient: Pubkey,
+    pub usdc_amount: u64,
+}
+
+/// Emitted after a program-owned recipient's delivery callback returns
+#[event]
+pub struct RecipientNotified {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub recipient_program: Pubkey,
+    pub amount_delivered: u64,
+}
+
+/// Emitted when a claimant pulls an order's escrowed output
+#[event]
+pub struct OutputClaimed {
+    pub order_id: u64,
+    pub claimant: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when an order's SOL output is staked for its beneficiary
+#[event]
+pub struct OutputStaked {
+    pub order_id: u64,
+    pub pool: LiquidStakingPool,
+    pub beneficiary: Pubkey,
+    pub lamports: u64,
+}
+
+/// Emitted when an order's output is deposited into a lending reserve for its beneficiary
+#[event]
+pub struct OutputLent {
+    pub order_id: u64,
+    pub market: LendingMarket,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when an order's output is added as liquidity for its beneficiary
+#[event]
+pub struct LiquidityProvided {
+    pub order_id: u64,
+    pub protocol: LiquidityProtocol,
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub usdc_amount: u64,
+    pub pair_amount: u64,
+}
+
+/// Emitted when an order's output is deposited into a vault for its beneficiary
+#[event]
+pub struct OutputDepositedToVault {
+    pub order_id: u64,
+    pub vault: Pubkey,
+    pub beneficiary: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+}
+
+/// Emitted when an allowlisted spend program consumes an order's output
+#[event]
+pub struct OutputSpent {
+    pub order_id: u64,
+    pub spend_program: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when an order's output is delivered with an allowance left for its delegate
+#[event]
+pub struct DelegateApproved {
+    pub order_id: u64,
+    pub delegate: Pubkey,
+    pub beneficiary: Pubkey,
+    pub allowance: u64,
+    pub amount_delivered: u64,
+}
+
+/// Emitted when the beneficiary revokes an order's delegate and reclaims the rest
+#[event]
+pub struct DelegateRevoked {
+    pub order_id: u64,
+    pub delegate: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount_reclaimed: u64,
+}
+
+/// Emitted for each Streamflow stream funded from an order's output
+#[event]
+pub struct OutputStreamed {
+    pub order_id: u64,
+    pub beneficiary: Pubkey,
+    pub stream: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted for each leg of an order's output split
+#[event]
+pub struct SplitLegDelivered {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted for each leg of an order's basket once swapped and delivered
+#[event]
+pub struct BasketLegDelivered {
+    pub order_id: u64,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub usdc_amount: u64,
+    pub amount_delivered: u64,
+}
+
+/// Emitted when a fill beats its route's quote and its surplus share is split off the output
+#[event]
+pub struct SurplusCaptured {
+    pub order_id: u64,
+    /// Mint of the output, held in the config's vault for it
+    pub mint: Pubkey,
+    pub quoted_output: u64,
+    pub output: u64,
+    pub captured: u64,
+    /// Kept in the vault under `Treasury`, rebated to the fill's payer under `PayerRebate`
+    pub policy: SurplusPolicy,
+}
+
+/// Emitted when a fill below the minimum order size is delivered as USDC instead of swapped
+#[event]
+pub struct DustOrderDelivered {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+}
+
+/// Emitted when a fill arrives after the order's deadline and the bridged USDC is refunded in full
+#[event]
+pub struct ExpiredOrderRefunded {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub deadline: i64,
+    pub usdc_amount: u64,
+}
+
+/// Emitted when the handler settles an order whose swap failed by refunding the bridged USDC in full
+#[event]
+pub struct FailedSwapRefunded {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+}
+
+/// Emitted when a fill delivers the configured fallback asset because the order's
+/// destination mint was disabled after bridging
+#[event]
+pub struct FallbackOutputDelivered {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub requested_mint: Pubkey,
+    pub delivered_mint: Pubkey,
+    pub amount_delivered: u64,
+}
+
+/// Emitted when an operator disables or re-enables a destination mint
+#[event]
+pub struct DestinationMintUpdated {
+    pub mint: Pubkey,
+    pub disabled: bool,
+}
+
+/// Emitted when a DCA order is cancelled and its unswapped USDC refunded to the recipient
+#[event]
+pub struct DcaOrderCancelled {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_returned: u64,
+    pub keeper_bounty: u64,
+}
+
+/// Emitted when a keeper expires a stale escrowed order and refunds its USDC
+#[event]
+pub struct OrderExpired {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub keeper: Pubkey,
+    pub usdc_refunded: u64,
+    pub keeper_bounty: u64,
+}
+
+/// Emitted when an order escrow holds less USDC than its ledger and fills are paused
+#[event]
+pub struct SolvencyBreakerTripped {
+    pub order_id: u64,
+    pub escrow: Pubkey,
+    pub expected_usdc: u64,
+    pub actual_usdc: u64,
+}
+
+/// Emitted when tokens leave a config-owned account through `recover_funds` or a queued recovery
+#[event]
+pub struct FundsRecovered {
+    /// Set when the recovery was queued
+    pub recovery_id: Option<u64>,
+    pub token_mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a recovery above the timelock threshold is queued; a lamport
+/// recovery records `PendingRecovery::LAMPORTS_MINT` as its mint
+#[event]
+pub struct RecoveryQueued {
+    pub recovery_id: u64,
+    pub token_mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+}
+
+/// Emitted when the admin cancels a queued recovery
+#[event]
+pub struct RecoveryCancelled {
+    pub recovery_id: u64,
+    pub amount: u64,
+}
+
+/// Emitted when recovery limits change; looser limits only apply from `effective_at`
+#[event]
+pub struct RecoveryLimitsUpdated {
+    pub limits: RecoveryLimits,
+    pub effective_at: i64,
+}
+
+/// Emitted when the insurance fund compensates a user affected by an incident
+#[event]
+pub struct InsurancePaid {
+    pub incident_id: u64,
+    pub destination_token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a recipient files an insurance claim for an unrefundable order
+#[event]
+pub struct InsuranceClaimFiled {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub payout_token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when the admin approves an insurance claim
+#[event]
+pub struct InsuranceClaimApproved {
+    pub order_id: u64,
+    pub amount: u64,
+}
+
+/// Emitted when an approved insurance claim is paid; `usdc_recovered` is what
+/// the order's escrow still held and was swept back into the fund
+#[event]
+pub struct InsuranceClaimPaid {
+    pub order_id: u64,
+    pub payout_token_account: Pubkey,
+    pub amount: u64,
+    pub usdc_recovered: u64,
+}
+
+/// Emitted when a settled order's account is closed into the order archive;
+/// `record` is the order's serialized state, committed to by leaf `leaf_index`
+#[event]
+pub struct OrderCompressed {
+    pub order_id: u64,
+    pub swap_order: Pubkey,
+    pub leaf_index: u64,
+    pub root: [u8; 32],
+    pub record: Vec<u8>,
+}
+
+/// Emitted when funds held for an EVM address are released to the Solana wallet its key signed for
+#[event]
+pub struct EvmClaimReleased {
+    pub evm_address: [u8; 20],
+    pub destination: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a registered operator pays for a fill; `owed_usdc` is its ledger balance afterwards
+#[event]
+pub struct PayerExpenseRecorded {
+    pub order_id: u64,
+    pub operator: Pubkey,
+    pub cost_usdc: u64,
+    pub owed_usdc: u64,
+}
+
+/// Emitted when a payer operator's ledger is settled from collected fees
+#[event]
+pub struct PayerLedgerSettled {
+    pub operator: Pubkey,
+    pub usdc_amount: u64,
+}
+
+/// Emitted when the config account is migrated to a newer layout in place
+#[event]
+pub struct ConfigMigrated {
+    pub version: u8,
+    pub previous_len: u32,
+    pub new_len: u32,
+}
+
+/// Emitted when `assert_solvency` finds the checked orders covered
+#[event]
+pub struct SolvencyAsserted {
+    pub instance_id: u16,
+    pub vault_balance: u64,
+    pub vault_liabilities: u64,
+    pub orders_checked: u32,
+}
+
+/// Emitted when a staged Jupiter route is fully uploaded and locked
+#[event]
+pub struct RouteFinalized {
+    pub order_id: u64,
+    pub authority: Pubkey,
+    pub route_len: u32,
+}
+
+/// Emitted when an order tips the relayer that filled it
+#[event]
+pub struct RelayerTipped {
+    pub order_id: u64,
+    pub relayer: Pubkey,
+    pub usdc_amount: u64,
+}
+
+/// Emitted when stray lamports leave a program-owned account through
+/// `recover_lamports` or a queued lamport recovery
+#[event]
+pub struct LamportsRecovered {
+    /// Set when the recovery was queued
+    pub recovery_id: Option<u64>,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a fill's payer sends SOL to a recipient holding none
+#[event]
+pub struct GasDropped {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub payer: Pubkey,
+    pub lamports: u64,
+}
+
+/// Emitted when a pending admin accepts the transfer and takes over the config
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+/// Emitted when the admin proposes a timelocked config update
+#[event]
+pub struct ConfigUpdateProposed {
+    pub config: Pubkey,
+    pub across_handler: Option<Pubkey>,
+    pub jupiter_program: Option<Pubkey>,
+    pub fee_recipient: Option<Pubkey>,
+    pub config_update_delay_seconds: Option<i64>,
+    pub add_across_handler: Option<Pubkey>,
+    pub executable_at: i64,
+}
+
+/// Emitted when a proposed config update is applied, with the resulting values
+#[event]
+pub struct ConfigUpdateApplied {
+    pub config: Pubkey,
+    pub across_handler: Pubkey,
+    pub jupiter_program: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub config_update_delay_seconds: i64,
+    pub additional_across_handlers: Vec<Pubkey>,
+}
+
+/// Emitted when the admin discards a proposed config update
+#[event]
+pub struct ConfigUpdateCancelled {
+    pub config: Pubkey,
+}
+
+/// Emitted when the admin stops accepting an additional Across handler
+#[event]
+pub struct AcrossHandlerRemoved {
+    pub config: Pubkey,
+    pub handler: Pubkey,
+}
+
+/// Emitted when the admin creates or updates a destination mint's config
+#[event]
+pub struct MintConfigUpdated {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub fee_bps: Option<u16>,
+    pub max_order_usdc: u64,
+    pub max_slippage_bps: u16,
+    pub daily_volume_cap: u64,
+}
+
+/// Emitted when the admin deletes a destination mint's config
+#[event]
+pub struct MintConfigDeleted {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+}
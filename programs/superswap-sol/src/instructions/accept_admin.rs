@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::AdminTransferred;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.pending_admin != Pubkey::default() @ SuperSwapError::NoPendingAdmin,
+        constraint = config.pending_admin == new_admin.key() @ SuperSwapError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Pending admin proposed through update_config
+    pub new_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_admin = config.admin;
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            config.admin,
+            AdminAction::AcceptAdmin,
+            Clock::get()?.unix_timestamp,
+            Pubkey::default(),
+        )
+        .with_values(previous_admin, config.admin),
+    );
+
+    emit!(AdminTransferred {
+        previous_admin,
+        new_admin: config.admin,
+    });
+
+    msg!("Admin transferred from {} to {}", previous_admin, config.admin);
+
+    Ok(())
+}
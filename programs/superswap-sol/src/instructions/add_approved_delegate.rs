@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, APPROVED_DELEGATE_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ApprovedDelegateParams)]
+pub struct AddApprovedDelegate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ApprovedDelegate::LEN,
+        seeds = [APPROVED_DELEGATE_SEED, config.instance_seed().as_ref(), params.delegate.as_ref()],
+        bump
+    )]
+    pub approved_delegate: Account<'info, ApprovedDelegate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddApprovedDelegate>, params: ApprovedDelegateParams) -> Result<()> {
+    let approved_delegate = &mut ctx.accounts.approved_delegate;
+    approved_delegate.delegate = params.delegate;
+    approved_delegate.bump = ctx.bumps.approved_delegate;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::AddApprovedDelegate,
+        Clock::get()?.unix_timestamp,
+        params.delegate,
+    ));
+
+    msg!("Delegate allowlisted: {}", params.delegate);
+
+    Ok(())
+}
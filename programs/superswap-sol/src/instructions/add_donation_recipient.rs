@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, DONATION_RECIPIENT_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: DonationRecipientParams)]
+pub struct AddDonationRecipient<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = DonationRecipient::LEN,
+        seeds = [DONATION_RECIPIENT_SEED, config.instance_seed().as_ref(), params.recipient.as_ref()],
+        bump
+    )]
+    pub donation_recipient: Account<'info, DonationRecipient>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddDonationRecipient>, params: DonationRecipientParams) -> Result<()> {
+    let donation_recipient = &mut ctx.accounts.donation_recipient;
+    donation_recipient.recipient = params.recipient;
+    donation_recipient.bump = ctx.bumps.donation_recipient;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::AddDonationRecipient,
+        Clock::get()?.unix_timestamp,
+        params.recipient,
+    ));
+
+    msg!("Donation recipient allowlisted: {}", params.recipient);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PAYER_LEDGER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: PayerOperatorParams)]
+pub struct AddPayerOperator<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PayerLedger::LEN,
+        seeds = [PAYER_LEDGER_SEED, config.instance_seed().as_ref(), params.operator.as_ref()],
+        bump
+    )]
+    pub payer_ledger: Account<'info, PayerLedger>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddPayerOperator>, params: PayerOperatorParams) -> Result<()> {
+    let payer_ledger = &mut ctx.accounts.payer_ledger;
+    payer_ledger.operator = params.operator;
+    payer_ledger.owed_usdc = 0;
+    payer_ledger.fills_paid = 0;
+    payer_ledger.total_settled_usdc = 0;
+    payer_ledger.bump = ctx.bumps.payer_ledger;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::AddPayerOperator,
+        Clock::get()?.unix_timestamp,
+        params.operator,
+    ));
+
+    msg!("Payer operator registered: {}", params.operator);
+
+    Ok(())
+}
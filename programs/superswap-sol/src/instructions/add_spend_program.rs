@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, SPEND_PROGRAM_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: SpendProgramParams)]
+pub struct AddSpendProgram<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = SpendProgram::LEN,
+        seeds = [SPEND_PROGRAM_SEED, config.instance_seed().as_ref(), params.program_id.as_ref()],
+        bump
+    )]
+    pub spend_program: Account<'info, SpendProgram>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddSpendProgram>, params: SpendProgramParams) -> Result<()> {
+    let spend_program = &mut ctx.accounts.spend_program;
+    spend_program.program_id = params.program_id;
+    spend_program.bump = ctx.bumps.spend_program;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::AddSpendProgram,
+        Clock::get()?.unix_timestamp,
+        params.program_id,
+    ));
+
+    msg!("Spend program allowlisted: {}", params.program_id);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::ConfigUpdateApplied;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_CONFIG_UPDATE_SEED};
+
+#[derive(Accounts)]
+pub struct ApplyConfigUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording each changed setting
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Proposed change, closed to the admin once applied
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PENDING_CONFIG_UPDATE_SEED, config.instance_seed().as_ref()],
+        bump = pending_config_update.bump,
+        has_one = config,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pending_config_update = &ctx.accounts.pending_config_update;
+    require!(
+        now >= pending_config_update.executable_at,
+        SuperSwapError::ConfigUpdateTimelockActive
+    );
+
+    let config = &mut ctx.accounts.config;
+    let before = Config::clone(config);
+    pending_config_update.apply(config)?;
+
+    let changes = config.changes_since(&before);
+    let admin_log = &mut ctx.accounts.admin_log;
+    admin_log.record_changes(
+        ctx.accounts.admin.key(),
+        AdminAction::ApplyConfigUpdate,
+        now,
+        changes,
+    );
+    if let Some(handler) = pending_config_update.add_across_handler {
+        admin_log.record(AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::AddAcrossHandler,
+            now,
+            handler,
+        ));
+    }
+
+    emit!(ConfigUpdateApplied {
+        config: config.key(),
+        across_handler: config.across_handler,
+        jupiter_program: config.jupiter_program,
+        fee_recipient: config.fee_recipient,
+        config_update_delay_seconds: config.config_update_delay_seconds,
+        additional_across_handlers: config.registered_additional_across_handlers(),
+    });
+
+    msg!("Config update applied");
+
+    Ok(())
+}
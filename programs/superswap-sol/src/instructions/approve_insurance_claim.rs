@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::InsuranceClaimApproved;
+use superswap_pda::{CONFIG_SEED, INSURANCE_CLAIM_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: InsuranceClaimParams)]
+pub struct ApproveInsuranceClaim<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_CLAIM_SEED, swap_order.key().as_ref()],
+        bump = insurance_claim.bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveInsuranceClaim>, params: InsuranceClaimParams) -> Result<()> {
+    let insurance_claim = &mut ctx.accounts.insurance_claim;
+    require!(
+        insurance_claim.status == InsuranceClaimStatus::Filed,
+        SuperSwapError::InvalidClaimStatus
+    );
+    // The cap may have been lowered since the claim was filed
+    require!(
+        insurance_claim.amount <= ctx.accounts.config.max_insurance_claim_usdc,
+        SuperSwapError::InsuranceClaimTooLarge
+    );
+
+    insurance_claim.status = InsuranceClaimStatus::Approved;
+
+    emit!(InsuranceClaimApproved {
+        order_id: params.order_id,
+        amount: insurance_claim.amount,
+    });
+
+    msg!("Insurance claim for order {} approved", params.order_id);
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::SolvencyAsserted;
+use superswap_pda::{escrow_token_account, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct AssertSolvency<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Program's USDC vault, owing the USDC of pending and failed orders
+    #[account(
+        associated_token::mint = config.usdc_mint,
+        associated_token::authority = config
+    )]
+    pub program_usdc_account: Account<'info, TokenAccount>,
+
+    // Note: Swap orders to check are passed as remaining_accounts; each
+    // DcaActive order is followed by its DcaOrder and escrow USDC account
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AssertSolvency<'info>>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let instance_seed = config.instance_seed();
+    let vault_balance = ctx.accounts.program_usdc_account.amount;
+
+    let mut vault_liabilities: u64 = 0;
+    let mut orders_checked: u32 = 0;
+    let mut accounts = ctx.remaining_accounts.iter();
+    while let Some(order_info) = accounts.next() {
+        let swap_order = Account::<SwapOrder>::try_from(order_info)?;
+        require!(
+            swap_order.is_instance_order(order_info.key, &instance_seed),
+            SuperSwapError::WrongInstance
+        );
+        orders_checked += 1;
+
+        if swap_order.is_vault_liability() {
+            vault_liabilities = vault_liabilities
+                .checked_add(swap_order.usdc_amount)
+                .ok_or(SuperSwapError::MathOverflow)?;
+        } else if swap_order.status == OrderStatus::DcaActive {
+            // A DCA order's unswapped USDC sits in its own escrow
+            let (Some(dca_info), Some(escrow_info)) = (accounts.next(), accounts.next()) else {
+                return err!(SuperSwapError::InvalidInstructionData);
+            };
+            let dca_order = Account::<DcaOrder>::try_from(dca_info)?;
+            require!(dca_order.swap_order == *order_info.key, SuperSwapError::InvalidInstructionData);
+            require!(
+                *escrow_info.key == escrow_token_account(&crate::ID, order_info.key, &config.usdc_mint),
+                SuperSwapError::InvalidEscrowAccount
+            );
+            let escrow = Account::<TokenAccount>::try_from(escrow_info)?;
+            if escrow.amount < dca_order.remaining_amount {
+                msg!(
+                    "DCA order {} escrow holds {} of {} USDC",
+                    swap_order.order_id,
+                    escrow.amount,
+                    dca_order.remaining_amount
+                );
+                return err!(SuperSwapError::Insolvent);
+            }
+        }
+    }
+
+    if vault_balance < vault_liabilities {
+        msg!(
+            "USDC vault holds {} against {} owed, short {}",
+            vault_balance,
+            vault_liabilities,
+            vault_liabilities - vault_balance
+        );
+        return err!(SuperSwapError::Insolvent);
+    }
+
+    emit!(SolvencyAsserted {
+        instance_id: config.instance_id,
+        vault_balance,
+        vault_liabilities,
+        orders_checked,
+    });
+
+    msg!("Solvent: {} USDC held against {} owed", vault_balance, vault_liabilities);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::ConfigUpdateCancelled;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_CONFIG_UPDATE_SEED};
+
+#[derive(Accounts)]
+pub struct CancelConfigUpdate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PENDING_CONFIG_UPDATE_SEED, config.instance_seed().as_ref()],
+        bump = pending_config_update.bump,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelConfigUpdate>) -> Result<()> {
+    let pending_config_update_key = ctx.accounts.pending_config_update.key();
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::CancelConfigUpdate,
+        Clock::get()?.unix_timestamp,
+        pending_config_update_key,
+    ));
+
+    emit!(ConfigUpdateCancelled {
+        config: ctx.accounts.config.key(),
+    });
+
+    msg!("Pending config update cancelled");
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer as TokenTransfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::DcaOrderCancelled;
+use crate::utils::open_orders::release_open_order;
+use crate::utils::refund::calculate_fee;
+use crate::utils::rent_vault::create_ata_if_needed;
+use superswap_pda::{CONFIG_SEED, DCA_ORDER_SEED, ESCROW_SEED, RECIPIENT_ORDERS_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CancelDcaOrderParams)]
+pub struct CancelDcaOrder<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// Schedule being stopped, kept as the record of its fills
+    #[account(
+        mut,
+        seeds = [DCA_ORDER_SEED, swap_order.key().as_ref()],
+        bump = dca_order.bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    /// CHECK: Recipient's open order count, released by the cancellation;
+    /// absent when the order predates the count
+    #[account(
+        mut,
+        seeds = [RECIPIENT_ORDERS_SEED, swap_order.recipient.as_ref()],
+        bump
+    )]
+    pub recipient_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Per-order escrow PDA holding the unswapped USDC
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's USDC account, closed once emptied
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_usdc_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Order recipient (validated against the order)
+    #[account(address = swap_order.recipient @ SuperSwapError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// CHECK: Recipient's USDC account, created from the rent vault if missing
+    #[account(
+        mut,
+        address = get_associated_token_address(&recipient.key(), &usdc_mint.key()) @ SuperSwapError::UsdcTokenAccountNotFound
+    )]
+    pub recipient_usdc_account: UncheckedAccount<'info>,
+
+    /// Recipient or Across handler cancelling the order; once the order is past
+    /// the TTL any keeper may cancel it, validated in instruction
+    pub authority: Signer<'info>,
+
+    /// Keeper's USDC account collecting the refund bounty of an order past the TTL
+    #[account(
+        mut,
+        constraint = keeper_usdc_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub keeper_usdc_account: Option<Account<'info, TokenAccount>>,
+
+    /// Rent vault that funds the recipient's USDC account and is repaid for the escrow's
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Fronts the recipient's USDC account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelDcaOrder>, params: CancelDcaOrderParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    require!(
+        ctx.accounts.swap_order.status == OrderStatus::DcaActive,
+        SuperSwapError::InvalidOrderStatus
+    );
+
+    // Orders past the TTL are refundable by anyone so stale schedules never resume
+    let authority = ctx.accounts.authority.key();
+    let is_keeper = authority != ctx.accounts.swap_order.recipient
+        && !ctx.accounts.config.is_across_handler(&authority);
+    if is_keeper {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.swap_order.is_past_ttl(now, ctx.accounts.config.order_ttl_seconds),
+            SuperSwapError::Unauthorized
+        );
+    }
+
+    let swap_order_key = ctx.accounts.swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    // A keeper refunding a stale order earns a share of the returned USDC
+    let keeper_bounty = match ctx.accounts.keeper_usdc_account.as_ref() {
+        Some(keeper_usdc_account) if is_keeper => {
+            let bounty = calculate_fee(
+                ctx.accounts.escrow_usdc_account.amount,
+                ctx.accounts.config.refund_bounty_bps,
+            )?;
+            if bounty > 0 {
+                let bounty_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TokenTransfer {
+                        from: ctx.accounts.escrow_usdc_account.to_account_info(),
+                        to: keeper_usdc_account.to_account_info(),
+                        authority: ctx.accounts.escrow.to_account_info(),
+                    },
+                    signer,
+                );
+                token::transfer(bounty_ctx, bounty)?;
+                ctx.accounts.escrow_usdc_account.reload()?;
+            }
+            bounty
+        }
+        _ => 0,
+    };
+
+    let usdc_returned = ctx.accounts.escrow_usdc_account.amount;
+    if usdc_returned > 0 {
+        create_ata_if_needed(
+            &mut ctx.accounts.rent_vault,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.recipient_usdc_account.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.usdc_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+        )?;
+
+        let refund_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenTransfer {
+                from: ctx.accounts.escrow_usdc_account.to_account_info(),
+                to: ctx.accounts.recipient_usdc_account.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(refund_ctx, usdc_returned)?;
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_usdc_account.to_account_info(),
+            destination: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        signer,
+    );
+    token::close_account(close_ctx)?;
+
+    // The escrow's USDC account rent was fronted by the rent vault
+    let leftover = ctx.accounts.escrow.lamports();
+    if leftover > 0 {
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+    }
+
+    release_open_order(&ctx.accounts.recipient_orders.to_account_info())?;
+
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.remaining_amount = 0;
+    let swap_order = &mut ctx.accounts.swap_order;
+    swap_order.status = if dca_order.fills.is_empty() {
+        OrderStatus::Refunded
+    } else {
+        OrderStatus::Completed
+    };
+
+    emit!(DcaOrderCancelled {
+        order_id: params.order_id,
+        recipient: swap_order.recipient,
+        usdc_returned,
+        keeper_bounty,
+    });
+
+    msg!("DCA order {} cancelled", params.order_id);
+
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer as TokenTransfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::LimitOrderCancelled;
+use crate::utils::limit_order::{self, JUPITER_LIMIT_ORDER_PROGRAM_ID};
+use crate::utils::open_orders::release_open_order;
+use crate::utils::refund::calculate_fee;
+use crate::utils::rent_vault::create_ata_if_needed;
+use superswap_pda::{CONFIG_SEED, ESCROW_SEED, LIMIT_ORDER_SEED, RECIPIENT_ORDERS_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CancelLimitOrderParams)]
+pub struct CancelLimitOrder<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// Limit order record, closed into the rent vault
+    #[account(
+        mut,
+        close = rent_vault,
+        seeds = [LIMIT_ORDER_SEED, swap_order.key().as_ref()],
+        bump = limit_order.bump
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    /// CHECK: Recipient's open order count, released once the order finishes;
+    /// absent when the order predates the count
+    #[account(
+        mut,
+        seeds = [RECIPIENT_ORDERS_SEED, swap_order.recipient.as_ref()],
+        bump
+    )]
+    pub recipient_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Per-order escrow PDA that made the limit order
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's USDC account holding the unfilled input after cancellation
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_usdc_account: Account<'info, TokenAccount>,
+
+    /// Escrow's destination token account holding the fills
+    #[account(
+        mut,
+        associated_token::mint = destination_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_output_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Order recipient (validated against the order)
+    #[account(address = swap_order.recipient @ SuperSwapError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Destination token mint
+    #[account(address = swap_order.destination_mint @ SuperSwapError::InvalidTokenMint)]
+    pub destination_mint: Account<'info, Mint>,
+
+    /// CHECK: Recipient's USDC account, created from the rent vault if missing
+    #[account(
+        mut,
+        address = get_associated_token_address(&recipient.key(), &usdc_mint.key()) @ SuperSwapError::UsdcTokenAccountNotFound
+    )]
+    pub recipient_usdc_account: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient's destination token account, created from the rent vault if missing
+    #[account(
+        mut,
+        address = get_associated_token_address(&recipient.key(), &destination_mint.key()) @ SuperSwapError::DestinationTokenAccountNotFound
+    )]
+    pub recipient_destination_account: UncheckedAccount<'info>,
+
+    /// Recipient or Across handler cancelling the order; once the limit order has
+    /// expired any keeper may cancel it, validated in instruction
+    pub authority: Signer<'info>,
+
+    /// Keeper's USDC account collecting the refund bounty of an expired order
+    #[account(
+        mut,
+        constraint = keeper_usdc_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub keeper_usdc_account: Option<Account<'info, TokenAccount>>,
+
+    /// Rent vault that funds the recipient's token accounts and is repaid for the escrow's
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// CHECK: Jupiter Limit Order program
+    #[account(address = JUPITER_LIMIT_ORDER_PROGRAM_ID @ SuperSwapError::InvalidJupiterProgram)]
+    pub limit_order_program: UncheckedAccount<'info>,
+
+    /// Fronts the recipient's token account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Note: remaining_accounts holds the Jupiter `cancel_order` accounts; it is
+    // empty once the Jupiter order has been fully filled and closed
+}
+
+impl<'info> CancelLimitOrder<'info> {
+    /// Transfers an escrow token account's balance to the recipient and closes it
+    fn deliver_and_close(
+        &mut self,
+        escrow_account: &AccountInfo<'info>,
+        recipient_account: &AccountInfo<'info>,
+        mint: &AccountInfo<'info>,
+        amount: u64,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        if amount > 0 {
+            create_ata_if_needed(
+                &mut self.rent_vault,
+                &self.payer.to_account_info(),
+                recipient_account,
+                &self.recipient.to_account_info(),
+                mint,
+                &self.system_program.to_account_info(),
+                &self.token_program.to_account_info(),
+                &self.associated_token_program.to_account_info(),
+            )?;
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenTransfer {
+                    from: escrow_account.clone(),
+                    to: recipient_account.clone(),
+                    authority: self.escrow.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, amount)?;
+        }
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: escrow_account.clone(),
+                destination: self.escrow.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelLimitOrder<'info>>,
+    params: CancelLimitOrderParams,
+) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    require!(
+        ctx.accounts.swap_order.status == OrderStatus::LimitPlaced,
+        SuperSwapError::InvalidOrderStatus
+    );
+
+    // Expired orders, and orders past the TTL, are refundable by anyone so keepers can unstick them
+    let authority = ctx.accounts.authority.key();
+    let is_keeper = authority != ctx.accounts.swap_order.recipient
+        && !ctx.accounts.config.is_across_handler(&authority);
+    if is_keeper {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.limit_order.is_expired(now)
+                || ctx.accounts.swap_order.is_past_ttl(now, ctx.accounts.config.order_ttl_seconds),
+            SuperSwapError::Unauthorized
+        );
+    }
+
+    let swap_order_key = ctx.accounts.swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    // A fully filled order is closed by Jupiter, leaving nothing to cancel
+    let jupiter_order = ctx.accounts.limit_order.jupiter_order;
+    let order_open = ctx
+        .remaining_accounts
+        .first()
+        .map(|account| account.key() == jupiter_order && !account.data_is_empty())
+        .unwrap_or(false);
+    if order_open {
+        limit_order::cancel_order(
+            &ctx.accounts.limit_order_program.to_account_info(),
+            ctx.remaining_accounts,
+            &jupiter_order,
+            &ctx.accounts.escrow.key(),
+            &ctx.accounts.escrow_usdc_account.key(),
+            signer,
+        )?;
+    }
+
+    ctx.accounts.escrow_usdc_account.reload()?;
+    ctx.accounts.escrow_output_account.reload()?;
+
+    // A keeper cancelling an expired order earns a share of the returned USDC
+    let keeper_bounty = match ctx.accounts.keeper_usdc_account.as_ref() {
+        Some(keeper_usdc_account) if is_keeper => {
+            let bounty = calculate_fee(
+                ctx.accounts.escrow_usdc_account.amount,
+                ctx.accounts.config.refund_bounty_bps,
+            )?;
+            if bounty > 0 {
+                let bounty_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TokenTransfer {
+                        from: ctx.accounts.escrow_usdc_account.to_account_info(),
+                        to: keeper_usdc_account.to_account_info(),
+                        authority: ctx.accounts.escrow.to_account_info(),
+                    },
+                    signer,
+                );
+                token::transfer(bounty_ctx, bounty)?;
+                ctx.accounts.escrow_usdc_account.reload()?;
+            }
+            bounty
+        }
+        _ => 0,
+    };
+
+    let usdc_returned = ctx.accounts.escrow_usdc_account.amount;
+    let output_delivered = ctx.accounts.escrow_output_account.amount;
+
+    // Deliver the unfilled USDC and any fills to the recipient
+    let escrow_usdc = ctx.accounts.escrow_usdc_account.to_account_info();
+    let recipient_usdc = ctx.accounts.recipient_usdc_account.to_account_info();
+    let usdc_mint = ctx.accounts.usdc_mint.to_account_info();
+    ctx.accounts
+        .deliver_and_close(&escrow_usdc, &recipient_usdc, &usdc_mint, usdc_returned, signer)?;
+
+    let escrow_output = ctx.accounts.escrow_output_account.to_account_info();
+    let recipient_destination = ctx.accounts.recipient_destination_account.to_account_info();
+    let destination_mint = ctx.accounts.destination_mint.to_account_info();
+    ctx.accounts.deliver_and_close(
+        &escrow_output,
+        &recipient_destination,
+        &destination_mint,
+        output_delivered,
+        signer,
+    )?;
+
+    // The escrow's ATA and Jupiter order rent was fronted by the rent vault
+    let leftover = ctx.accounts.escrow.lamports();
+    if leftover > 0 {
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+    }
+
+    release_open_order(&ctx.accounts.recipient_orders.to_account_info())?;
+    let swap_order = &mut ctx.accounts.swap_order;
+    swap_order.status = if output_delivered > 0 {
+        OrderStatus::Completed
+    } else {
+        OrderStatus::Refunded
+    };
+
+    emit!(LimitOrderCancelled {
+        order_id: params.order_id,
+        recipient: swap_order.recipient,
+        usdc_returned,
+        output_delivered,
+        keeper_bounty,
+    });
+
+    msg!("Limit order for swap order {} cancelled", params.order_id);
+
+    Ok(())
+}
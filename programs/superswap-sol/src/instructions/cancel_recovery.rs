@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::RecoveryCancelled;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_RECOVERY_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: PendingRecoveryParams)]
+pub struct CancelRecovery<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PENDING_RECOVERY_SEED, config.instance_seed().as_ref(), &params.recovery_id.to_le_bytes()],
+        bump = pending_recovery.bump,
+    )]
+    pub pending_recovery: Account<'info, PendingRecovery>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelRecovery>, params: PendingRecoveryParams) -> Result<()> {
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::CancelRecovery,
+            Clock::get()?.unix_timestamp,
+            ctx.accounts.pending_recovery.key(),
+        )
+            .with_values(0u64, ctx.accounts.pending_recovery.amount),
+    );
+
+    emit!(RecoveryCancelled {
+        recovery_id: params.recovery_id,
+        amount: ctx.accounts.pending_recovery.amount,
+    });
+
+    msg!("Recovery {} cancelled", params.recovery_id);
+
+    Ok(())
+}
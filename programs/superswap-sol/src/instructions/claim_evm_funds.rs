@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer as TokenTransfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::EvmClaimReleased;
+use crate::utils::evm::{claim_message, personal_sign_hash, recover_evm_address};
+use superswap_pda::{CONFIG_SEED, EVM_CLAIM_SEED, RENT_VAULT_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ClaimEvmFundsParams)]
+pub struct ClaimEvmFunds<'info> {
+    /// Instance whose orders delivered to the claim PDA
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Claim PDA named as the recipient of orders from users without a Solana wallet
+    #[account(
+        mut,
+        seeds = [EVM_CLAIM_SEED, config.instance_seed().as_ref(), params.evm_address.as_ref()],
+        bump
+    )]
+    pub evm_claim: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Claim PDA's token account holding the delivered output or refunded USDC
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = evm_claim
+    )]
+    pub claim_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Solana wallet the EVM key signed for; bound by the signature
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = destination
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Rent vault repaid for the claim PDA's token account
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Anyone may submit the claim; the signature decides where the funds go
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimEvmFunds>, params: ClaimEvmFundsParams) -> Result<()> {
+    let destination = ctx.accounts.destination.key();
+
+    // The signature only names the destination, so replaying it can only pay the same wallet
+    let message = claim_message(&crate::ID, &destination);
+    let signer = recover_evm_address(
+        &personal_sign_hash(message.as_bytes()),
+        &params.signature,
+        params.recovery_id,
+    )?;
+    require!(signer == params.evm_address, SuperSwapError::InvalidEvmSignature);
+
+    let amount = ctx.accounts.claim_token_account.amount;
+    require!(amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let instance_seed = ctx.accounts.config.instance_seed();
+    let seeds = &[
+        EVM_CLAIM_SEED,
+        instance_seed.as_ref(),
+        params.evm_address.as_ref(),
+        &[ctx.bumps.evm_claim],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TokenTransfer {
+            from: ctx.accounts.claim_token_account.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.evm_claim.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.claim_token_account.to_account_info(),
+            destination: ctx.accounts.evm_claim.to_account_info(),
+            authority: ctx.accounts.evm_claim.to_account_info(),
+        },
+        signer,
+    );
+    token::close_account(close_ctx)?;
+
+    // The claim PDA's token accounts are created from the rent vault on delivery
+    let leftover = ctx.accounts.evm_claim.lamports();
+    if leftover > 0 {
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.evm_claim.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+    }
+
+    emit!(EvmClaimReleased {
+        evm_address: params.evm_address,
+        destination,
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    msg!("Released {} to {}", amount, destination);
+
+    Ok(())
+}
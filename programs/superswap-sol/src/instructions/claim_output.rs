@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer as TokenTransfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::OutputClaimed;
+use superswap_pda::{CONFIG_SEED, ESCROW_SEED, ORDER_EXTENSION_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ClaimOutputParams)]
+pub struct ClaimOutput<'info> {
+    /// Config of the instance the order belongs to
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        seeds = [ORDER_EXTENSION_SEED, swap_order.key().as_ref()],
+        bump = order_extension.bump
+    )]
+    pub order_extension: Account<'info, OrderExtension>,
+
+    /// CHECK: Per-order escrow PDA holding the output until it is claimed
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's token account holding the swapped output
+    #[account(
+        mut,
+        associated_token::mint = swap_order.destination_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// Any token account for the destination mint chosen by the claimant
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == swap_order.destination_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Claimant recorded on the order; smart wallets sign through CPI
+    pub claimant: Signer<'info>,
+
+    /// Rent vault repaid for the escrow's token account
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimOutput>, params: ClaimOutputParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    let swap_order = &ctx.accounts.swap_order;
+
+    require!(
+        swap_order.status == OrderStatus::Completed,
+        SuperSwapError::InvalidOrderStatus
+    );
+    require!(
+        swap_order.recipient == ctx.accounts.escrow.key(),
+        SuperSwapError::InvalidRecipient
+    );
+
+    let claimant = ctx
+        .accounts
+        .order_extension
+        .claimant
+        .ok_or(SuperSwapError::MissingClaimant)?;
+    require!(
+        ctx.accounts.claimant.key() == claimant,
+        SuperSwapError::Unauthorized
+    );
+
+    let amount = ctx.accounts.escrow_token_account.amount;
+    require!(amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let swap_order_key = swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TokenTransfer {
+            from: ctx.accounts.escrow_token_account.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        signer,
+    );
+    token::close_account(close_ctx)?;
+
+    // The escrow's token account rent was fronted by the rent vault
+    let leftover = ctx.accounts.escrow.lamports();
+    if leftover > 0 {
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+    }
+
+    emit!(OutputClaimed {
+        order_id: params.order_id,
+        claimant,
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+    });
+
+    msg!("Order {} output claimed by {}", params.order_id, claimant);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::utils::order_close::{close_order_extension, require_escrow_empty, tombstone_order};
+use crate::utils::rent_vault::order_rent_destination;
+use superswap_pda::{CONFIG_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CloseOrderParams)]
+pub struct CloseOrder<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Swap order to close, read in the handler since it is rewritten into a tombstone
+    #[account(
+        mut,
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub swap_order: UncheckedAccount<'info>,
+
+    /// CHECK: The order's extension PDA, closed with the order when it exists
+    #[account(mut)]
+    pub order_extension: UncheckedAccount<'info>,
+
+    /// CHECK: The order's destination mint, whose owner the escrow account is derived under
+    pub destination_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The order's escrow account for its destination mint, which must be empty
+    pub escrow_token_account: UncheckedAccount<'info>,
+
+    /// Rent vault that receives the rent it funded
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// CHECK: Rent treasury taking all reclaimed rent, required while the config sets one
+    #[account(
+        mut,
+        address = config.rent_treasury @ SuperSwapError::InvalidRentDestination
+    )]
+    pub rent_treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Payer recorded on the order, required when it rather than the rent vault funded the rent
+    #[account(mut)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+
+    /// Anyone may close a settled order; the rent only goes to where it came from
+    pub closer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseOrder>, params: CloseOrderParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    config.require_not_paused()?;
+
+    let order_info = ctx.accounts.swap_order.to_account_info();
+    let swap_order = SwapOrder::try_deserialize(&mut &order_info.try_borrow_data()?[..])?;
+    require!(swap_order.is_terminal(), SuperSwapError::OrderNotClosable);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        swap_order.is_retention_elapsed(now, config.min_retention_seconds),
+        SuperSwapError::RetentionPeriodActive
+    );
+    require_escrow_empty(
+        order_info.key,
+        &swap_order,
+        &ctx.accounts.destination_mint,
+        &ctx.accounts.escrow_token_account,
+    )?;
+
+    let rent_vault_info = ctx.accounts.rent_vault.to_account_info();
+    let rent_treasury_info = ctx.accounts.rent_treasury.as_ref().map(|treasury| treasury.to_account_info());
+    let rent_payers: Vec<AccountInfo> = ctx
+        .accounts
+        .rent_payer
+        .iter()
+        .map(|payer| payer.to_account_info())
+        .collect();
+    let destination = order_rent_destination(
+        config,
+        &swap_order,
+        &rent_vault_info,
+        rent_treasury_info.as_ref(),
+        &rent_payers,
+    )?;
+
+    let extension_info = ctx.accounts.order_extension.to_account_info();
+    let reclaimed = close_order_extension(order_info.key, &extension_info, destination)?
+        .checked_add(tombstone_order(&order_info, params.order_id, destination)?)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    msg!(
+        "Closed swap order {} into {}, {} lamports reclaimed",
+        params.order_id,
+        destination.key(),
+        reclaimed
+    );
+
+    Ok(())
+}
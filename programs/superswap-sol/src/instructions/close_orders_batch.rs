@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::utils::order_close::{close_order_extension, require_escrow_empty, split_order_accounts, tombstone_order};
+use crate::utils::rent_vault::order_rent_destination;
+use superswap_pda::{CONFIG_SEED, RENT_VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct CloseOrdersBatch<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Admin, or the config's operator when one is set
+    #[account(constraint = config.is_operator(&authority.key()) @ SuperSwapError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    /// Rent vault that receives the rent it funded
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// CHECK: Rent treasury taking all reclaimed rent, required while the config sets one
+    #[account(
+        mut,
+        address = config.rent_treasury @ SuperSwapError::InvalidRentDestination
+    )]
+    pub rent_treasury: Option<UncheckedAccount<'info>>,
+
+    // Note: Each swap order to close is passed in remaining_accounts as the
+    // writable order, its writable extension PDA, its destination mint and its
+    // escrow's account for that mint (see `close_order`), followed by the
+    // writable accounts of any payers that funded their rent
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseOrdersBatch<'info>>) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    let (orders, rent_payers) = split_order_accounts(ctx.remaining_accounts)?;
+    require!(!orders.is_empty(), SuperSwapError::InvalidInstructionData);
+    require!(
+        orders.len() <= SwapOrder::MAX_CLOSE_BATCH,
+        SuperSwapError::BatchTooLarge
+    );
+
+    let rent_vault_info = ctx.accounts.rent_vault.to_account_info();
+    let rent_treasury_info = ctx.accounts.rent_treasury.as_ref().map(|treasury| treasury.to_account_info());
+    let now = Clock::get()?.unix_timestamp;
+    let mut reclaimed: u64 = 0;
+
+    for [order_info, order_extension, destination_mint, escrow_token_account] in orders.iter() {
+        let swap_order = Account::<SwapOrder>::try_from(order_info)?;
+        require!(
+            swap_order.is_instance_order(order_info.key, &ctx.accounts.config.instance_seed()),
+            SuperSwapError::WrongInstance
+        );
+        require!(swap_order.is_terminal(), SuperSwapError::OrderNotClosable);
+        require!(
+            swap_order.is_retention_elapsed(now, ctx.accounts.config.min_retention_seconds),
+            SuperSwapError::RetentionPeriodActive
+        );
+        require_escrow_empty(order_info.key, &swap_order, destination_mint, escrow_token_account)?;
+
+        let destination = order_rent_destination(
+            &ctx.accounts.config,
+            &swap_order,
+            &rent_vault_info,
+            rent_treasury_info.as_ref(),
+            rent_payers,
+        )?;
+
+        msg!("Closing swap order {} into {}", swap_order.order_id, destination.key());
+        let order_reclaimed = close_order_extension(order_info.key, order_extension, destination)?
+            .checked_add(tombstone_order(order_info, swap_order.order_id, destination)?)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        reclaimed = reclaimed
+            .checked_add(order_reclaimed)
+            .ok_or(SuperSwapError::MathOverflow)?;
+    }
+
+    msg!("Closed {} orders", orders.len());
+    msg!("Rent reclaimed: {}", reclaimed);
+
+    Ok(())
+}
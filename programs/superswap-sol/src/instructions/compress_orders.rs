@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::OrderCompressed;
+use crate::utils::order_close::{close_order_extension, require_escrow_empty, split_order_accounts, tombstone_order};
+use crate::utils::rent_vault::order_rent_destination;
+use superswap_pda::{CONFIG_SEED, ORDER_ARCHIVE_SEED, RENT_VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct CompressOrders<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Admin, or the config's operator when one is set; pays for the archive on first use
+    #[account(
+        mut,
+        constraint = config.is_operator(&authority.key()) @ SuperSwapError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// Merkle commitment the instance's closed orders are appended to
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OrderArchive::LEN,
+        seeds = [ORDER_ARCHIVE_SEED, config.instance_seed().as_ref()],
+        bump
+    )]
+    pub order_archive: Box<Account<'info, OrderArchive>>,
+
+    /// Rent vault that receives the rent it funded
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// CHECK: Rent treasury taking all reclaimed rent, required while the config sets one
+    #[account(
+        mut,
+        address = config.rent_treasury @ SuperSwapError::InvalidRentDestination
+    )]
+    pub rent_treasury: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // Note: Each swap order to compress is passed in remaining_accounts as the
+    // writable order, its writable extension PDA, its destination mint and its
+    // escrow's account for that mint (see `close_order`), followed by the
+    // writable accounts of any payers that funded their rent
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CompressOrders<'info>>) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    let (orders, rent_payers) = split_order_accounts(ctx.remaining_accounts)?;
+    require!(!orders.is_empty(), SuperSwapError::InvalidInstructionData);
+    require!(
+        orders.len() <= SwapOrder::MAX_COMPRESS_BATCH,
+        SuperSwapError::BatchTooLarge
+    );
+
+    let order_archive = &mut ctx.accounts.order_archive;
+    order_archive.bump = ctx.bumps.order_archive;
+    let rent_vault_info = ctx.accounts.rent_vault.to_account_info();
+    let rent_treasury_info = ctx.accounts.rent_treasury.as_ref().map(|treasury| treasury.to_account_info());
+    let now = Clock::get()?.unix_timestamp;
+    let mut reclaimed: u64 = 0;
+
+    for [order_info, order_extension, destination_mint, escrow_token_account] in orders.iter() {
+        let swap_order = Account::<SwapOrder>::try_from(order_info)?;
+        require!(
+            swap_order.is_instance_order(order_info.key, &ctx.accounts.config.instance_seed()),
+            SuperSwapError::WrongInstance
+        );
+        require!(swap_order.is_terminal(), SuperSwapError::OrderNotClosable);
+        require!(
+            swap_order.is_retention_elapsed(now, ctx.accounts.config.min_retention_seconds),
+            SuperSwapError::RetentionPeriodActive
+        );
+        require_escrow_empty(order_info.key, &swap_order, destination_mint, escrow_token_account)?;
+
+        let record = swap_order.try_to_vec()?;
+        let leaf_index = order_archive.append(OrderArchive::leaf(order_info.key, &record))?;
+
+        emit!(OrderCompressed {
+            order_id: swap_order.order_id,
+            swap_order: *order_info.key,
+            leaf_index,
+            root: order_archive.root,
+            record,
+        });
+
+        let destination = order_rent_destination(
+            &ctx.accounts.config,
+            &swap_order,
+            &rent_vault_info,
+            rent_treasury_info.as_ref(),
+            rent_payers,
+        )?;
+        let order_reclaimed = close_order_extension(order_info.key, order_extension, destination)?
+            .checked_add(tombstone_order(order_info, swap_order.order_id, destination)?)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        reclaimed = reclaimed
+            .checked_add(order_reclaimed)
+            .ok_or(SuperSwapError::MathOverflow)?;
+    }
+
+    msg!("Compressed {} orders", orders.len());
+    msg!("Rent reclaimed: {}", reclaimed);
+
+    Ok(())
+}
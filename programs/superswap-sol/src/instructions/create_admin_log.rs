@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct CreateAdminLog<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AdminLog::LEN,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateAdminLog>) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    ctx.accounts.admin_log.init(config_key, ctx.bumps.admin_log);
+
+    msg!("Admin log created for config {}", config_key);
+
+    Ok(())
+}
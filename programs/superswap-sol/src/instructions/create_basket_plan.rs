@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::utils::rent_vault::reimburse_rent;
+use superswap_pda::{BASKET_PLAN_SEED, CONFIG_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CreateBasketPlanParams)]
+pub struct CreateBasketPlan<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = BasketPlan::LEN,
+        seeds = [BASKET_PLAN_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub basket_plan: Account<'info, BasketPlan>,
+
+    /// Across handler that relays the order payload
+    pub across_handler: Signer<'info>,
+
+    /// Rent vault that covers the plan account rent
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Fronts the plan account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateBasketPlan>, params: CreateBasketPlanParams) -> Result<()> {
+    let legs: Vec<BasketLeg> = params
+        .legs
+        .iter()
+        .map(|leg| BasketLeg {
+            mint: leg.mint,
+            weight_bps: leg.weight_bps,
+            min_output_amount: leg.min_output_amount,
+            delivered: 0,
+            executed: false,
+        })
+        .collect();
+    require!(
+        params.beneficiary != Pubkey::default() && BasketPlan::validate_legs(&legs),
+        SuperSwapError::InvalidBasketPlan
+    );
+
+    let plan_rent = ctx.accounts.basket_plan.get_lamports();
+    let payer = ctx.accounts.payer.to_account_info();
+    reimburse_rent(&mut ctx.accounts.rent_vault, &payer, plan_rent)?;
+
+    let basket_plan = &mut ctx.accounts.basket_plan;
+    basket_plan.swap_order = ctx.accounts.swap_order.key();
+    basket_plan.beneficiary = params.beneficiary;
+    basket_plan.usdc_amount = 0;
+    basket_plan.legs = legs;
+    basket_plan.bump = ctx.bumps.basket_plan;
+
+    msg!("Basket plan created for order {}", params.order_id);
+    msg!("Legs: {}", basket_plan.legs.len());
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::instructions::initialize::{init_config, init_instance_accounts};
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, OUTBOUND_RATE_LIMIT_SEED, RECOVERY_GUARD_SEED, RENT_VAULT_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CreateInstanceParams)]
+pub struct CreateInstance<'info> {
+    /// Primary instance's config; only its admin may add instances
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = primary_config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub primary_config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::LEN,
+        seeds = [CONFIG_SEED, params.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AdminLog::LEN,
+        seeds = [ADMIN_LOG_SEED, params.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
+    /// The instance's own rent vault, rate limit and recovery guard, scoped like its config
+    #[account(
+        init,
+        payer = admin,
+        space = RentVault::LEN,
+        seeds = [RENT_VAULT_SEED, params.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rent_vault: Box<Account<'info, RentVault>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = OutboundRateLimit::LEN,
+        seeds = [OUTBOUND_RATE_LIMIT_SEED, params.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub outbound_rate_limit: Box<Account<'info, OutboundRateLimit>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RecoveryGuard::LEN,
+        seeds = [RECOVERY_GUARD_SEED, params.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub recovery_guard: Box<Account<'info, RecoveryGuard>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateInstance>, params: CreateInstanceParams) -> Result<()> {
+    // Instance 0 is the primary config, created by `initialize`
+    require!(params.instance_id != 0, SuperSwapError::WrongInstance);
+
+    init_config(
+        &mut ctx.accounts.config,
+        params.admin,
+        &params.config,
+        params.instance_id,
+        ctx.bumps.config,
+    )?;
+    let config_key = ctx.accounts.config.key();
+    ctx.accounts.admin_log.init(config_key, ctx.bumps.admin_log);
+    init_instance_accounts(
+        &mut ctx.accounts.rent_vault,
+        ctx.bumps.rent_vault,
+        &mut ctx.accounts.outbound_rate_limit,
+        ctx.bumps.outbound_rate_limit,
+        &mut ctx.accounts.recovery_guard,
+        ctx.bumps.recovery_guard,
+    )?;
+
+    msg!("Instance {} created", params.instance_id);
+    msg!("Admin: {}", params.admin);
+    msg!("Fee recipient: {}", params.config.fee_recipient);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::MintConfigUpdated;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, MINT_CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: MintConfigParams)]
+pub struct CreateMintConfig<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = MintConfig::LEN,
+        seeds = [MINT_CONFIG_SEED, config.instance_seed().as_ref(), params.mint.as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateMintConfig>, params: MintConfigParams) -> Result<()> {
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.config = ctx.accounts.config.key();
+    mint_config.mint = params.mint;
+    mint_config.bump = ctx.bumps.mint_config;
+    mint_config.set(&params)?;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::CreateMintConfig,
+        Clock::get()?.unix_timestamp,
+        params.mint,
+    ));
+
+    emit!(MintConfigUpdated {
+        config: mint_config.config,
+        mint: params.mint,
+        enabled: params.enabled,
+        fee_bps: params.fee_bps,
+        max_order_usdc: params.max_order_usdc,
+        max_slippage_bps: params.max_slippage_bps,
+        daily_volume_cap: params.daily_volume_cap,
+    });
+
+    msg!("Mint config created for {}", params.mint);
+
+    Ok(())
+}
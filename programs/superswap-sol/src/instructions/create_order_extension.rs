@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::utils::rent_vault::reimburse_rent;
+use superswap_pda::{CONFIG_SEED, ORDER_EXTENSION_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CreateOrderExtensionParams)]
+pub struct CreateOrderExtension<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderExtension::LEN,
+        seeds = [ORDER_EXTENSION_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub order_extension: Account<'info, OrderExtension>,
+
+    /// Across handler that owns order metadata
+    pub across_handler: Signer<'info>,
+
+    /// Rent vault that covers the extension account rent
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Fronts the extension account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateOrderExtension>, params: CreateOrderExtensionParams) -> Result<()> {
+    // Escrowed output leaves through exactly one of onward forwarding, an action or a claim
+    let delivery_modes = [
+        params.onward_destination.is_some(),
+        params.post_swap_action.is_some(),
+        params.claimant.is_some(),
+    ];
+    require!(
+        delivery_modes.iter().filter(|mode| **mode).count() <= 1,
+        SuperSwapError::ConflictingDeliveryModes
+    );
+
+    let extension_rent = ctx.accounts.order_extension.get_lamports();
+    let payer = ctx.accounts.payer.to_account_info();
+    reimburse_rent(&mut ctx.accounts.rent_vault, &payer, extension_rent)?;
+
+    let order_extension = &mut ctx.accounts.order_extension;
+    order_extension.swap_order = ctx.accounts.swap_order.key();
+    order_extension.origin_chain_id = params.origin_chain_id;
+    order_extension.evm_origin = params.evm_origin;
+    order_extension.route_hash = params.route_hash;
+    order_extension.integrator_id = params.integrator_id;
+    order_extension.onward_destination = params.onward_destination;
+    order_extension.post_swap_action = params.post_swap_action;
+    order_extension.claimant = params.claimant;
+    order_extension.bump = ctx.bumps.order_extension;
+
+    msg!("Order extension created for order {}", params.order_id);
+    msg!("Origin chain: {}", params.origin_chain_id);
+    msg!("Integrator: {}", params.integrator_id);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::utils::rent_vault::reimburse_rent;
+use superswap_pda::{CONFIG_SEED, RENT_VAULT_SEED, SPLIT_PLAN_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CreateSplitPlanParams)]
+pub struct CreateSplitPlan<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SplitPlan::LEN,
+        seeds = [SPLIT_PLAN_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub split_plan: Account<'info, SplitPlan>,
+
+    /// Across handler that relays the order payload
+    pub across_handler: Signer<'info>,
+
+    /// Rent vault that covers the plan account rent
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Fronts the plan account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateSplitPlan>, params: CreateSplitPlanParams) -> Result<()> {
+    // Every leg needs a wallet and a non-zero weight
+    require!(
+        !params.recipients.is_empty()
+            && params.recipients.len() <= SplitPlan::MAX_RECIPIENTS
+            && params.recipients.iter().all(|leg| {
+                leg.weight > 0 && leg.recipient != Pubkey::default()
+            }),
+        SuperSwapError::InvalidSplitPlan
+    );
+
+    let plan_rent = ctx.accounts.split_plan.get_lamports();
+    let payer = ctx.accounts.payer.to_account_info();
+    reimburse_rent(&mut ctx.accounts.rent_vault, &payer, plan_rent)?;
+
+    let split_plan = &mut ctx.accounts.split_plan;
+    split_plan.swap_order = ctx.accounts.swap_order.key();
+    split_plan.legs = params
+        .recipients
+        .iter()
+        .map(|leg| SplitLeg {
+            recipient: leg.recipient,
+            weight: leg.weight,
+            delivered: 0,
+        })
+        .collect();
+    split_plan.bump = ctx.bumps.split_plan;
+
+    msg!("Split plan created for order {}", params.order_id);
+    msg!("Legs: {}", split_plan.legs.len());
+
+    Ok(())
+}
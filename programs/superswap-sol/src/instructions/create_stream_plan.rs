@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::utils::rent_vault::reimburse_rent;
+use superswap_pda::{CONFIG_SEED, RENT_VAULT_SEED, STREAM_PLAN_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CreateStreamPlanParams)]
+pub struct CreateStreamPlan<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = StreamPlan::LEN,
+        seeds = [STREAM_PLAN_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub stream_plan: Account<'info, StreamPlan>,
+
+    /// Across handler that relays the order payload
+    pub across_handler: Signer<'info>,
+
+    /// Rent vault that covers the plan account rent
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Fronts the plan account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateStreamPlan>, params: CreateStreamPlanParams) -> Result<()> {
+    // Shares must cover the whole output, and the schedule must unlock at least once
+    let total_bps = params
+        .recipients
+        .iter()
+        .map(|recipient| recipient.share_bps as u32)
+        .sum::<u32>();
+    require!(
+        !params.recipients.is_empty()
+            && params.recipients.len() <= StreamPlan::MAX_RECIPIENTS
+            && params.recipients.iter().all(|recipient| {
+                recipient.share_bps > 0 && recipient.beneficiary != Pubkey::default()
+            })
+            && total_bps == 10_000,
+        SuperSwapError::InvalidStreamPlan
+    );
+    require!(
+        params.period_seconds > 0
+            && params.period_seconds <= params.duration_seconds
+            && params.cliff_bps <= 10_000
+            && params.start_time >= 0,
+        SuperSwapError::InvalidStreamPlan
+    );
+
+    let plan_rent = ctx.accounts.stream_plan.get_lamports();
+    let payer = ctx.accounts.payer.to_account_info();
+    reimburse_rent(&mut ctx.accounts.rent_vault, &payer, plan_rent)?;
+
+    let stream_plan = &mut ctx.accounts.stream_plan;
+    stream_plan.swap_order = ctx.accounts.swap_order.key();
+    stream_plan.recipients = params.recipients;
+    stream_plan.start_time = params.start_time;
+    stream_plan.duration_seconds = params.duration_seconds;
+    stream_plan.period_seconds = params.period_seconds;
+    stream_plan.cliff_bps = params.cliff_bps;
+    stream_plan.bump = ctx.bumps.stream_plan;
+
+    msg!("Stream plan created for order {}", params.order_id);
+    msg!("Streams: {}", stream_plan.recipients.len());
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, VOLUME_TRACKER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: CreateVolumeTrackerParams)]
+pub struct CreateVolumeTracker<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = VolumeTracker::LEN,
+        seeds = [VOLUME_TRACKER_SEED, config.instance_seed().as_ref(), params.mint.as_ref()],
+        bump
+    )]
+    pub volume_tracker: Account<'info, VolumeTracker>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateVolumeTracker>, params: CreateVolumeTrackerParams) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let volume_tracker = &mut ctx.accounts.volume_tracker;
+    volume_tracker.config = ctx.accounts.config.key();
+    volume_tracker.mint = params.mint;
+    volume_tracker.day = now.div_euclid(VolumeTracker::SECONDS_PER_DAY);
+    volume_tracker.volume = 0;
+    volume_tracker.bump = ctx.bumps.volume_tracker;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::CreateVolumeTracker,
+        now,
+        params.mint,
+    ));
+
+    msg!("Volume tracker created for {}", params.mint);
+
+    Ok(())
+}
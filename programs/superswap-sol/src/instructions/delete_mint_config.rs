@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::MintConfigDeleted;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, MINT_CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: DeleteMintConfigParams)]
+pub struct DeleteMintConfig<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [MINT_CONFIG_SEED, config.instance_seed().as_ref(), params.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DeleteMintConfig>, params: DeleteMintConfigParams) -> Result<()> {
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::DeleteMintConfig,
+        Clock::get()?.unix_timestamp,
+        params.mint,
+    ));
+
+    emit!(MintConfigDeleted {
+        config: ctx.accounts.config.key(),
+        mint: params.mint,
+    });
+
+    msg!("Mint config deleted for {}", params.mint);
+
+    Ok(())
+}
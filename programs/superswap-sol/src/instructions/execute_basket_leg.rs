@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{self, accessor, CloseAccount, Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::BasketLegDelivered;
+use crate::utils::jupiter::{execute_jupiter_swap, parse_jupiter_swap_data, require_route_token_accounts, validate_swap_output};
+use crate::utils::rent_vault::create_ata_if_needed;
+use crate::utils::solvency::trip_on_escrow_shortfall;
+use superswap_pda::{BASKET_PLAN_SEED, CONFIG_SEED, ESCROW_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ExecuteBasketLegParams)]
+pub struct ExecuteBasketLeg<'info> {
+    /// Config, switched to refunds-only by the solvency breaker when the escrow is short
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// Output mints and weights of the basket; records each leg
+    #[account(
+        mut,
+        seeds = [BASKET_PLAN_SEED, swap_order.key().as_ref()],
+        bump = basket_plan.bump
+    )]
+    pub basket_plan: Account<'info, BasketPlan>,
+
+    /// CHECK: Per-order escrow authority PDA; basket orders are filled with it as recipient
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's USDC account the legs are swapped from
+    #[account(
+        mut,
+        associated_token::mint = config.usdc_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_usdc_account: Account<'info, TokenAccount>,
+
+    /// Output mint of the leg being executed
+    pub leg_mint: Account<'info, Mint>,
+
+    /// CHECK: Beneficiary recorded on the basket plan
+    #[account(address = basket_plan.beneficiary @ SuperSwapError::InvalidRecipient)]
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// CHECK: Beneficiary's ATA for the leg mint, created from the rent vault if missing
+    #[account(mut)]
+    pub beneficiary_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Jupiter program, matched against the config
+    #[account(address = config.jupiter_program @ SuperSwapError::InvalidJupiterProgram)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    /// Rent vault that funds the beneficiary's token account
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Across handler that relays orders
+    pub across_handler: Signer<'info>,
+
+    /// Fronts the beneficiary's token account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Note: remaining_accounts holds the Jupiter route swapping the leg's USDC
+    // from the escrow into the beneficiary's token account
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteBasketLeg<'info>>,
+    params: ExecuteBasketLegParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let swap_order = &ctx.accounts.swap_order;
+
+    config.require_accepting_fills()?;
+    require!(
+        swap_order.status == OrderStatus::Completed,
+        SuperSwapError::InvalidOrderStatus
+    );
+    require!(
+        swap_order.recipient == ctx.accounts.escrow.key(),
+        SuperSwapError::InvalidRecipient
+    );
+    require!(
+        swap_order.destination_mint == config.usdc_mint,
+        SuperSwapError::InvalidTokenMint
+    );
+
+    let leg_index = params.leg_index as usize;
+    let leg = *ctx
+        .accounts
+        .basket_plan
+        .legs
+        .get(leg_index)
+        .ok_or(SuperSwapError::InvalidBasketPlan)?;
+    require!(!leg.executed, SuperSwapError::BasketLegAlreadyExecuted);
+    require!(
+        ctx.accounts.leg_mint.key() == leg.mint,
+        SuperSwapError::InvalidTokenMint
+    );
+    require!(
+        ctx.accounts.beneficiary_token_account.key()
+            == get_associated_token_address(&ctx.accounts.beneficiary.key(), &leg.mint),
+        SuperSwapError::InvalidRecipient
+    );
+
+    // The first leg fixes the USDC every leg's share is computed from; after it
+    // the escrow must still hold the shares of the legs left to run
+    if ctx.accounts.basket_plan.usdc_amount == 0 {
+        let escrowed = ctx.accounts.escrow_usdc_account.amount;
+        require!(escrowed > 0, SuperSwapError::InvalidBridgeAmount);
+        ctx.accounts.basket_plan.usdc_amount = escrowed;
+    } else if trip_on_escrow_shortfall(
+        &mut ctx.accounts.config,
+        params.order_id,
+        ctx.accounts.escrow_usdc_account.key(),
+        ctx.accounts.basket_plan.unexecuted_usdc()?,
+        ctx.accounts.escrow_usdc_account.amount,
+    ) {
+        return Ok(());
+    }
+    let leg_amount = ctx.accounts.basket_plan.leg_amounts()?[leg_index];
+
+    create_ata_if_needed(
+        &mut ctx.accounts.rent_vault,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.beneficiary_token_account.to_account_info(),
+        &ctx.accounts.beneficiary.to_account_info(),
+        &ctx.accounts.leg_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
+    let beneficiary_token_info = ctx.accounts.beneficiary_token_account.to_account_info();
+
+    let swap_order_key = swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    // The route must spend from the escrow and deliver where the output is measured
+    let route = parse_jupiter_swap_data(&params.jupiter_swap_data)?;
+    require_route_token_accounts(
+        &route,
+        &ctx.accounts.jupiter_program.key(),
+        ctx.remaining_accounts,
+        &ctx.accounts.escrow_usdc_account.key(),
+        &beneficiary_token_info.key(),
+    )?;
+
+    // The output is measured from the beneficiary's balance rather than trusted from the route
+    let usdc_before = ctx.accounts.escrow_usdc_account.amount;
+    let output_before = accessor::amount(&beneficiary_token_info)?;
+    execute_jupiter_swap(
+        &ctx.accounts.jupiter_program.to_account_info(),
+        &params.jupiter_swap_data,
+        ctx.remaining_accounts,
+        signer,
+    )?;
+    let delivered = accessor::amount(&beneficiary_token_info)?
+        .checked_sub(output_before)
+        .ok_or(SuperSwapError::MathOverflow)?;
+    validate_swap_output(delivered, leg.min_output_amount)?;
+
+    // The route must spend exactly this leg's share of the USDC
+    ctx.accounts.escrow_usdc_account.reload()?;
+    let usdc_spent = usdc_before
+        .checked_sub(ctx.accounts.escrow_usdc_account.amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+    require!(usdc_spent == leg_amount, SuperSwapError::InvalidSwapCalldata);
+
+    let basket_plan = &mut ctx.accounts.basket_plan;
+    basket_plan.legs[leg_index].delivered = delivered;
+    basket_plan.legs[leg_index].executed = true;
+
+    emit!(BasketLegDelivered {
+        order_id: params.order_id,
+        beneficiary: basket_plan.beneficiary,
+        mint: leg.mint,
+        usdc_amount: leg_amount,
+        amount_delivered: delivered,
+    });
+
+    msg!(
+        "Basket leg {} of order {}: {} USDC -> {} of {}",
+        leg_index,
+        params.order_id,
+        leg_amount,
+        delivered,
+        leg.mint
+    );
+
+    // Once every leg has executed the escrow's USDC account is empty
+    if basket_plan.is_complete() {
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_usdc_account.to_account_info(),
+                destination: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)?;
+        msg!("Basket of order {} fully delivered", params.order_id);
+    }
+
+    Ok(())
+}
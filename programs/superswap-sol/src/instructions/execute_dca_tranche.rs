@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::DcaTrancheExecuted;
+use crate::utils::destination_mint::require_mint_enabled;
+use crate::utils::jupiter::{
+    execute_jupiter_swap_measured, parse_jupiter_swap_data, require_route_token_accounts, route_quoted_out_amount,
+    validate_price_impact, validate_swap_output,
+};
+use crate::utils::open_orders::release_open_order;
+use crate::utils::solvency::trip_on_escrow_shortfall;
+use superswap_pda::{CONFIG_SEED, DCA_ORDER_SEED, DESTINATION_MINT_SEED, ESCROW_SEED, RECIPIENT_ORDERS_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ExecuteDcaTrancheParams)]
+pub struct ExecuteDcaTranche<'info> {
+    /// Config, paused by the solvency breaker when the escrow is short
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        mut,
+        seeds = [DCA_ORDER_SEED, swap_order.key().as_ref()],
+        bump = dca_order.bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    /// CHECK: Operator switch for the destination mint, absent while the mint has no entry
+    #[account(seeds = [DESTINATION_MINT_SEED, config.instance_seed().as_ref(), swap_order.destination_mint.as_ref()], bump)]
+    pub destination_mint_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient's open order count, released once the order finishes;
+    /// absent when the order predates the count
+    #[account(
+        mut,
+        seeds = [RECIPIENT_ORDERS_SEED, swap_order.recipient.as_ref()],
+        bump
+    )]
+    pub recipient_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Per-order escrow PDA, signs the tranche swap
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's USDC account funding the tranches
+    #[account(
+        mut,
+        associated_token::mint = config.usdc_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_usdc_account: Account<'info, TokenAccount>,
+
+    /// Recipient's destination token account receiving each tranche
+    #[account(
+        mut,
+        associated_token::mint = swap_order.destination_mint,
+        associated_token::authority = swap_order.recipient
+    )]
+    pub recipient_destination_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Jupiter program (validated against config)
+    #[account(constraint = jupiter_program.key() == config.jupiter_program @ SuperSwapError::InvalidJupiterProgram)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    /// Rent vault repaid for the escrow's USDC account once the schedule completes
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Keeper executing the tranche; any signer may crank a due tranche
+    pub keeper: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    // Note: remaining_accounts holds the Jupiter route accounts
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteDcaTranche<'info>>,
+    params: ExecuteDcaTrancheParams,
+) -> Result<()> {
+    ctx.accounts.config.require_accepting_fills()?;
+
+    // The escrow must still hold what the schedule has left to swap
+    if trip_on_escrow_shortfall(
+        &mut ctx.accounts.config,
+        ctx.accounts.swap_order.order_id,
+        ctx.accounts.escrow_usdc_account.key(),
+        ctx.accounts.dca_order.remaining_amount,
+        ctx.accounts.escrow_usdc_account.amount,
+    ) {
+        return Ok(());
+    }
+
+    let config = &ctx.accounts.config;
+
+    // Hold further tranches while an operator has the mint disabled
+    require_mint_enabled(&ctx.accounts.destination_mint_entry.to_account_info())?;
+    require!(
+        ctx.accounts.swap_order.status == OrderStatus::DcaActive,
+        SuperSwapError::InvalidOrderStatus
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let dca_order = &ctx.accounts.dca_order;
+    require!(now >= dca_order.next_execution_at, SuperSwapError::TrancheNotDue);
+
+    // Past the TTL the schedule is refund-only, however much of it remains
+    require!(
+        !ctx.accounts.swap_order.is_past_ttl(now, config.order_ttl_seconds),
+        SuperSwapError::OrderTtlExpired
+    );
+
+    let usdc_in = dca_order.next_tranche_amount();
+    let min_output = dca_order.tranche_min_output(usdc_in)?;
+    require!(usdc_in > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let swap_order_key = ctx.accounts.swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    // The route must spend from the escrow and deliver where the output is measured
+    let route = parse_jupiter_swap_data(&params.jupiter_swap_data)?;
+    require_route_token_accounts(
+        &route,
+        &ctx.accounts.jupiter_program.key(),
+        ctx.remaining_accounts,
+        &ctx.accounts.escrow_usdc_account.key(),
+        &ctx.accounts.recipient_destination_account.key(),
+    )?;
+
+    // Swap the tranche from the escrow straight into the recipient's account
+    let usdc_before = ctx.accounts.escrow_usdc_account.amount;
+    let output = execute_jupiter_swap_measured(
+        &ctx.accounts.jupiter_program.to_account_info(),
+        &params.jupiter_swap_data,
+        ctx.remaining_accounts,
+        signer,
+        &mut ctx.accounts.recipient_destination_account,
+    )?;
+    validate_swap_output(output, min_output)?;
+
+    // A loose per-tranche minimum still can't hide a route filled far below its own quote
+    let quoted_output = route_quoted_out_amount(&params.jupiter_swap_data);
+    validate_price_impact(output, quoted_output, ctx.accounts.config.max_price_impact_bps)?;
+
+    // The route must spend exactly the tranche so the schedule tracks the escrow balance
+    ctx.accounts.escrow_usdc_account.reload()?;
+    let usdc_spent = usdc_before
+        .checked_sub(ctx.accounts.escrow_usdc_account.amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+    require!(usdc_spent == usdc_in, SuperSwapError::InvalidSwapCalldata);
+
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.record_fill(usdc_in, output, now)?;
+    let tranche = dca_order.fills.len() as u8;
+    let remaining_amount = dca_order.remaining_amount;
+    let complete = dca_order.is_complete();
+
+    emit!(DcaTrancheExecuted {
+        order_id: params.order_id,
+        tranche,
+        usdc_in,
+        output,
+        remaining_amount,
+        quoted_output: quoted_output.unwrap_or(0),
+    });
+
+    msg!("DCA tranche {} executed: {} USDC -> {}", tranche, usdc_in, output);
+
+    if complete {
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_usdc_account.to_account_info(),
+                destination: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)?;
+
+        // The escrow's USDC account rent was fronted by the rent vault
+        let leftover = ctx.accounts.escrow.lamports();
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+
+        release_open_order(&ctx.accounts.recipient_orders.to_account_info())?;
+        ctx.accounts.swap_order.status = OrderStatus::Completed;
+        msg!("DCA order {} completed", params.order_id);
+    }
+
+    Ok(())
+}
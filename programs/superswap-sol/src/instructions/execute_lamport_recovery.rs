@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::LamportsRecovered;
+use crate::utils::rent_vault::withdraw_stray_lamports;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_RECOVERY_SEED, RECOVERY_GUARD_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: PendingRecoveryParams)]
+pub struct ExecuteLamportRecovery<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    /// Queued lamport recovery, closed to the admin once executed
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PENDING_RECOVERY_SEED, config.instance_seed().as_ref(), &params.recovery_id.to_le_bytes()],
+        bump = pending_recovery.bump,
+        constraint = pending_recovery.is_lamports() @ SuperSwapError::InvalidTokenMint,
+        constraint = pending_recovery.source_token_account == source.key() @ SuperSwapError::InvalidLamportSource,
+        constraint = pending_recovery.destination_token_account == destination.key() @ SuperSwapError::InvalidRecipient,
+    )]
+    pub pending_recovery: Account<'info, PendingRecovery>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Account recorded on the queued recovery, checked against the instance when queued
+    #[account(mut)]
+    pub source: UncheckedAccount<'info>,
+
+    /// CHECK: Destination recorded on the queued recovery
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteLamportRecovery>, _params: PendingRecoveryParams) -> Result<()> {
+    let pending_recovery = &ctx.accounts.pending_recovery;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= pending_recovery.executable_at, SuperSwapError::RecoveryTimelockActive);
+
+    // Queued recoveries still count against the period cap when they land
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    recovery_guard.record(pending_recovery.amount, now)?;
+
+    withdraw_stray_lamports(
+        &ctx.accounts.source.to_account_info(),
+        &ctx.accounts.destination.to_account_info(),
+        pending_recovery.amount,
+    )?;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::ExecuteLamportRecovery,
+            now,
+            ctx.accounts.pending_recovery.key(),
+        )
+            .with_values(0u64, pending_recovery.amount),
+    );
+
+    emit!(LamportsRecovered {
+        recovery_id: Some(pending_recovery.recovery_id),
+        source: pending_recovery.source_token_account,
+        destination: pending_recovery.destination_token_account,
+        amount: pending_recovery.amount,
+    });
+
+    msg!("Lamport recovery {} executed", pending_recovery.recovery_id);
+
+    Ok(())
+}
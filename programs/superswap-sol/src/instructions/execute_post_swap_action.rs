@@ -0,0 +1,772 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{self, Approve, CloseAccount, Token, TokenAccount, Transfer as TokenTransfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::{
+    DelegateApproved, LiquidityProvided, OutputDepositedToVault, OutputLent, OutputSpent, OutputStaked,
+    OutputStreamed, SplitLegDelivered,
+};
+use crate::utils::jupiter::{execute_jupiter_swap_measured, parse_jupiter_swap_data, require_route_token_accounts, validate_swap_output};
+use crate::utils::lending::deposit_reserve_liquidity;
+use crate::utils::liquidity::{add_liquidity, LiquidityLeg};
+use crate::utils::rent_vault::{create_ata_if_needed, reimburse_rent};
+use crate::utils::spend::spend_output;
+use crate::utils::staking::liquid_stake;
+use crate::utils::streaming::{
+    create_stream, net_of_stream_fee, stream_recipient_accounts, CreateStreamArgs,
+    STREAMFLOW_CREATE_ACCOUNTS,
+};
+use crate::utils::vault::{deposit_to_vault, vault_share_accounts, VaultDepositArgs};
+use superswap_pda::{
+    APPROVED_DELEGATE_SEED, CONFIG_SEED, ESCROW_SEED, ORDER_EXTENSION_SEED, RENT_VAULT_SEED, SPEND_PROGRAM_SEED,
+    SPLIT_PLAN_SEED, STREAM_PLAN_SEED, SWAP_ORDER_SEED, VAULT_PROGRAM_SEED,
+};
+
+/// Accounts the provide-liquidity action expects ahead of the Jupiter route:
+/// jupiter_program, escrow_pair_account, escrow_position_account,
+/// beneficiary_usdc_account, beneficiary_pair_account, beneficiary_position_account
+const LIQUIDITY_PREFIX_ACCOUNTS: usize = 6;
+
+#[derive(Accounts)]
+#[instruction(params: ExecutePostSwapActionParams)]
+pub struct ExecutePostSwapAction<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        seeds = [ORDER_EXTENSION_SEED, swap_order.key().as_ref()],
+        bump = order_extension.bump
+    )]
+    pub order_extension: Account<'info, OrderExtension>,
+
+    /// CHECK: Per-order escrow authority PDA; orders with an action are filled with it as recipient
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's token account holding the swapped output
+    #[account(
+        mut,
+        associated_token::mint = swap_order.destination_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Beneficiary of the action, matched against the order's action
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Rent vault that funds the beneficiary's token account and is repaid for the escrow's
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Across handler that relays orders
+    pub across_handler: Signer<'info>,
+
+    /// Fronts the beneficiary's token account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Program the action calls into, or the approve-delegate action's delegate, validated per action
+    pub action_program: UncheckedAccount<'info>,
+
+    /// Allowlist entry for the action program, required by the spend action
+    #[account(
+        seeds = [SPEND_PROGRAM_SEED, config.instance_seed().as_ref(), action_program.key().as_ref()],
+        bump = spend_program_entry.bump
+    )]
+    pub spend_program_entry: Option<Account<'info, SpendProgram>>,
+
+    /// Allowlist entry for the action program, required by the vault deposit action
+    #[account(
+        seeds = [VAULT_PROGRAM_SEED, config.instance_seed().as_ref(), action_program.key().as_ref()],
+        bump = vault_program_entry.bump
+    )]
+    pub vault_program_entry: Option<Account<'info, VaultProgram>>,
+
+    /// Beneficiaries and schedule, required by the stream action
+    #[account(
+        seeds = [STREAM_PLAN_SEED, swap_order.key().as_ref()],
+        bump = stream_plan.bump
+    )]
+    pub stream_plan: Option<Account<'info, StreamPlan>>,
+
+    /// Recipients and weights, required by the split action; records each leg
+    #[account(
+        mut,
+        seeds = [SPLIT_PLAN_SEED, swap_order.key().as_ref()],
+        bump = split_plan.bump
+    )]
+    pub split_plan: Option<Account<'info, SplitPlan>>,
+
+    /// Allowlist entry for the delegate passed as `action_program`, required by the approve-delegate action
+    #[account(
+        seeds = [APPROVED_DELEGATE_SEED, config.instance_seed().as_ref(), action_program.key().as_ref()],
+        bump = approved_delegate_entry.bump
+    )]
+    pub approved_delegate_entry: Option<Account<'info, ApprovedDelegate>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Note: remaining_accounts holds the accounts of the action's CPI; the
+    // provide-liquidity action prepends its fixed accounts and the Jupiter route,
+    // the spend action passes the spend instruction's accounts, and the stream
+    // action passes the Streamflow `create` accounts of each stream in plan order;
+    // the split action passes the output mint, then each leg's wallet and token account,
+    // the vault deposit action passes the vault's `deposit` accounts, and the
+    // approve-delegate action passes the output mint and the beneficiary's token account
+}
+
+impl<'info> ExecutePostSwapAction<'info> {
+    /// Creates the beneficiary's token account for `mint` if it does not exist yet
+    fn create_beneficiary_account(
+        &mut self,
+        mint: &AccountInfo<'info>,
+        account: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let beneficiary = self.beneficiary.to_account_info();
+        self.create_token_account(&beneficiary, mint, account)
+    }
+
+    /// Creates `owner`'s token account for `mint` from the rent vault if it does not exist yet
+    fn create_token_account(
+        &mut self,
+        owner: &AccountInfo<'info>,
+        mint: &AccountInfo<'info>,
+        account: &AccountInfo<'info>,
+    ) -> Result<()> {
+        create_ata_if_needed(
+            &mut self.rent_vault,
+            &self.payer.to_account_info(),
+            account,
+            owner,
+            mint,
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            &self.associated_token_program.to_account_info(),
+        )
+    }
+
+    /// Closes the escrow token account into the escrow PDA
+    fn close_escrow_token_account(&self, signer: &[&[&[u8]]]) -> Result<()> {
+        self.close_escrow_owned_account(
+            &self.escrow_token_account.to_account_info(),
+            &self.escrow.to_account_info(),
+            signer,
+        )
+    }
+
+    /// Closes an empty token account owned by the escrow PDA
+    fn close_escrow_owned_account(
+        &self,
+        account: &AccountInfo<'info>,
+        destination: &AccountInfo<'info>,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: account.clone(),
+                destination: destination.clone(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)
+    }
+
+    /// Transfers tokens out of an account owned by the escrow PDA
+    fn transfer_from_escrow(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        amount: u64,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TokenTransfer {
+                from: from.clone(),
+                to: to.clone(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecutePostSwapAction<'info>>,
+    params: ExecutePostSwapActionParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let swap_order = &ctx.accounts.swap_order;
+
+    config.require_accepting_fills()?;
+    require!(
+        swap_order.status == OrderStatus::Completed,
+        SuperSwapError::InvalidOrderStatus
+    );
+    require!(
+        swap_order.recipient == ctx.accounts.escrow.key(),
+        SuperSwapError::InvalidRecipient
+    );
+
+    let action = ctx
+        .accounts
+        .order_extension
+        .post_swap_action
+        .ok_or(SuperSwapError::MissingPostSwapAction)?;
+
+    let amount = ctx.accounts.escrow_token_account.amount;
+    require!(amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let swap_order_key = swap_order.key();
+    let escrow_key = ctx.accounts.escrow.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    match action {
+        PostSwapAction::Stake { pool, beneficiary } => {
+            require!(
+                ctx.accounts.beneficiary.key() == beneficiary,
+                SuperSwapError::InvalidRecipient
+            );
+            require!(
+                ctx.accounts.escrow_token_account.is_native(),
+                SuperSwapError::InvalidTokenMint
+            );
+            let expected_pool = match pool {
+                LiquidStakingPool::Jito => config.jito_stake_pool,
+                LiquidStakingPool::Marinade => config.marinade_state,
+            };
+
+            let (lst_mint, lst_account) = pool.lst_accounts(ctx.remaining_accounts)?;
+            ctx.accounts.create_beneficiary_account(lst_mint, lst_account)?;
+
+            // Unwrap the WSOL output into the escrow PDA, which then deposits it
+            ctx.accounts.close_escrow_token_account(signer)?;
+
+            liquid_stake(
+                pool,
+                &ctx.accounts.action_program.to_account_info(),
+                &expected_pool,
+                ctx.remaining_accounts,
+                &escrow_key,
+                &beneficiary,
+                amount,
+                signer,
+            )?;
+
+            emit!(OutputStaked {
+                order_id: params.order_id,
+                pool,
+                beneficiary,
+                lamports: amount,
+            });
+        }
+        PostSwapAction::Lend { market, beneficiary } => {
+            require!(
+                ctx.accounts.beneficiary.key() == beneficiary,
+                SuperSwapError::InvalidRecipient
+            );
+            let expected_market = match market {
+                LendingMarket::Kamino => config.kamino_lending_market,
+                LendingMarket::Solend => config.solend_lending_market,
+            };
+
+            // The beneficiary receives the reserve's collateral tokens; obligations
+            // need the owner's signature, so depositing them is left to the beneficiary
+            let (collateral_mint, collateral_account) =
+                market.collateral_accounts(ctx.remaining_accounts)?;
+            ctx.accounts.create_beneficiary_account(collateral_mint, collateral_account)?;
+
+            deposit_reserve_liquidity(
+                market,
+                &ctx.accounts.action_program.to_account_info(),
+                &expected_market,
+                ctx.remaining_accounts,
+                &escrow_key,
+                &ctx.accounts.escrow_token_account.key(),
+                &beneficiary,
+                amount,
+                signer,
+            )?;
+
+            ctx.accounts.escrow_token_account.reload()?;
+            require!(
+                ctx.accounts.escrow_token_account.amount == 0,
+                SuperSwapError::InvalidLendingMarket
+            );
+            ctx.accounts.close_escrow_token_account(signer)?;
+
+            emit!(OutputLent {
+                order_id: params.order_id,
+                market,
+                beneficiary,
+                amount,
+            });
+        }
+        PostSwapAction::ProvideLiquidity { protocol, pool, beneficiary } => {
+            require!(
+                ctx.accounts.beneficiary.key() == beneficiary,
+                SuperSwapError::InvalidRecipient
+            );
+            require!(
+                ctx.accounts.escrow_token_account.mint == config.usdc_mint,
+                SuperSwapError::InvalidTokenMint
+            );
+            let liquidity = params
+                .liquidity
+                .as_ref()
+                .ok_or(SuperSwapError::InvalidInstructionData)?;
+
+            let jupiter_accounts_len = liquidity.jupiter_accounts_len as usize;
+            require!(
+                ctx.remaining_accounts.len() >= LIQUIDITY_PREFIX_ACCOUNTS + jupiter_accounts_len,
+                SuperSwapError::InvalidInstructionData
+            );
+            let (prefix, rest) = ctx.remaining_accounts.split_at(LIQUIDITY_PREFIX_ACCOUNTS);
+            let (route_accounts, pool_accounts) = rest.split_at(jupiter_accounts_len);
+            let [jupiter_program, escrow_pair, escrow_position, beneficiary_usdc, beneficiary_pair, beneficiary_position] =
+                prefix
+            else {
+                return err!(SuperSwapError::InvalidInstructionData);
+            };
+
+            require!(
+                jupiter_program.key() == config.jupiter_program,
+                SuperSwapError::InvalidJupiterProgram
+            );
+            let mut escrow_pair_account = Account::<TokenAccount>::try_from(escrow_pair)?;
+            let mut escrow_position_account = Account::<TokenAccount>::try_from(escrow_position)?;
+            require!(
+                escrow_pair_account.owner == escrow_key
+                    && escrow_position_account.owner == escrow_key
+                    && escrow_pair.key() != ctx.accounts.escrow_token_account.key(),
+                SuperSwapError::InvalidLiquidityPool
+            );
+
+            // Leftovers and the LP tokens or position NFT go to the beneficiary's ATAs
+            let beneficiary_accounts = [
+                (beneficiary_usdc, config.usdc_mint),
+                (beneficiary_pair, escrow_pair_account.mint),
+                (beneficiary_position, escrow_position_account.mint),
+            ];
+            for (account, mint) in beneficiary_accounts.iter() {
+                require!(
+                    account.key() == get_associated_token_address(&beneficiary, mint),
+                    SuperSwapError::InvalidRecipient
+                );
+            }
+
+            // Swap at most half of the USDC into the pool's other token, from the escrow
+            // into the escrow account it is measured in
+            let route = parse_jupiter_swap_data(&liquidity.jupiter_swap_data)?;
+            require_route_token_accounts(
+                &route,
+                &jupiter_program.key(),
+                route_accounts,
+                &ctx.accounts.escrow_token_account.key(),
+                &escrow_pair.key(),
+            )?;
+            let pair_out = execute_jupiter_swap_measured(
+                jupiter_program,
+                &liquidity.jupiter_swap_data,
+                route_accounts,
+                signer,
+                &mut escrow_pair_account,
+            )?;
+            validate_swap_output(pair_out, liquidity.min_pair_out)?;
+
+            ctx.accounts.escrow_token_account.reload()?;
+            let usdc_left = ctx.accounts.escrow_token_account.amount;
+            require!(
+                usdc_left >= amount / 2,
+                SuperSwapError::InvalidSwapCalldata
+            );
+
+            let usdc_leg = LiquidityLeg {
+                account: ctx.accounts.escrow_token_account.key(),
+                amount: usdc_left,
+            };
+            let pair_leg = LiquidityLeg {
+                account: escrow_pair.key(),
+                amount: escrow_pair_account.amount,
+            };
+            add_liquidity(
+                protocol,
+                &ctx.accounts.action_program.to_account_info(),
+                &pool,
+                pool_accounts,
+                &escrow_key,
+                &escrow_position.key(),
+                [&usdc_leg, &pair_leg],
+                liquidity.liquidity_amount,
+                signer,
+            )?;
+
+            ctx.accounts.escrow_token_account.reload()?;
+            escrow_pair_account.reload()?;
+            escrow_position_account.reload()?;
+            let usdc_dust = ctx.accounts.escrow_token_account.amount;
+            let pair_dust = escrow_pair_account.amount;
+
+            // Deliver the position and any unused leg amounts to the beneficiary
+            let escrow_usdc = ctx.accounts.escrow_token_account.to_account_info();
+            ctx.accounts.transfer_from_escrow(
+                escrow_position,
+                beneficiary_position,
+                escrow_position_account.amount,
+                signer,
+            )?;
+            ctx.accounts.transfer_from_escrow(&escrow_usdc, beneficiary_usdc, usdc_dust, signer)?;
+            ctx.accounts.transfer_from_escrow(escrow_pair, beneficiary_pair, pair_dust, signer)?;
+
+            // The relayer opened the pair and position accounts, so their rent returns to it
+            let payer = ctx.accounts.payer.to_account_info();
+            ctx.accounts.close_escrow_owned_account(escrow_pair, &payer, signer)?;
+            ctx.accounts.close_escrow_owned_account(escrow_position, &payer, signer)?;
+            ctx.accounts.close_escrow_token_account(signer)?;
+
+            emit!(LiquidityProvided {
+                order_id: params.order_id,
+                protocol,
+                pool,
+                beneficiary,
+                usdc_amount: usdc_left - usdc_dust,
+                pair_amount: pair_leg.amount - pair_dust,
+            });
+        }
+        PostSwapAction::Spend { program, beneficiary } => {
+            require!(
+                ctx.accounts.beneficiary.key() == beneficiary,
+                SuperSwapError::InvalidRecipient
+            );
+            let entry = ctx
+                .accounts
+                .spend_program_entry
+                .as_ref()
+                .ok_or(SuperSwapError::SpendProgramNotAllowed)?;
+            let spend_data = params
+                .spend_data
+                .as_ref()
+                .ok_or(SuperSwapError::InvalidInstructionData)?;
+
+            spend_output(
+                &ctx.accounts.action_program.to_account_info(),
+                entry,
+                &program,
+                ctx.remaining_accounts,
+                &escrow_key,
+                spend_data,
+                signer,
+            )?;
+
+            // Checkout settles in this transaction, so the spend must take everything
+            ctx.accounts.escrow_token_account.reload()?;
+            require!(
+                ctx.accounts.escrow_token_account.amount == 0,
+                SuperSwapError::OutputNotConsumed
+            );
+            ctx.accounts.close_escrow_token_account(signer)?;
+
+            emit!(OutputSpent {
+                order_id: params.order_id,
+                spend_program: program,
+                beneficiary,
+                amount,
+            });
+        }
+        PostSwapAction::Stream => {
+            let plan: StreamPlan = ctx
+                .accounts
+                .stream_plan
+                .as_deref()
+                .cloned()
+                .ok_or(SuperSwapError::InvalidStreamPlan)?;
+            require!(
+                ctx.remaining_accounts.len() == plan.recipients.len() * STREAMFLOW_CREATE_ACCOUNTS,
+                SuperSwapError::InvalidStreamPlan
+            );
+
+            // Streamflow charges its fee on top of each deposit, so split what is left after it
+            let amounts = plan.split(net_of_stream_fee(amount)?)?;
+            require!(amounts.iter().all(|amount| *amount > 0), SuperSwapError::InvalidStreamPlan);
+            let start_time = plan.start_time.max(Clock::get()?.unix_timestamp) as u64;
+
+            let streamflow_program = ctx.accounts.action_program.to_account_info();
+            let escrow_token_key = ctx.accounts.escrow_token_account.key();
+            let escrow_lamports = ctx.accounts.escrow.lamports();
+            let streams = plan
+                .recipients
+                .iter()
+                .zip(ctx.remaining_accounts.chunks(STREAMFLOW_CREATE_ACCOUNTS))
+                .zip(amounts);
+            for ((recipient, stream_accounts), net_amount) in streams {
+                let args = CreateStreamArgs::for_plan(&plan, start_time, net_amount, params.order_id)?;
+                let stream = create_stream(
+                    &streamflow_program,
+                    stream_accounts,
+                    &escrow_key,
+                    &escrow_token_key,
+                    &recipient.beneficiary,
+                    &args,
+                    signer,
+                )?;
+
+                emit!(OutputStreamed {
+                    order_id: params.order_id,
+                    beneficiary: recipient.beneficiary,
+                    stream,
+                    amount: net_amount,
+                });
+            }
+
+            // The escrow pays the stream accounts' rent; reimburse the relayer that funded it
+            let stream_rent = escrow_lamports.saturating_sub(ctx.accounts.escrow.lamports());
+            let payer = ctx.accounts.payer.to_account_info();
+            reimburse_rent(&mut ctx.accounts.rent_vault, &payer, stream_rent)?;
+
+            // Fee rounding dust goes to the first beneficiary's token account
+            ctx.accounts.escrow_token_account.reload()?;
+            let dust = ctx.accounts.escrow_token_account.amount;
+            let (_, first_recipient_tokens) = stream_recipient_accounts(ctx.remaining_accounts)?;
+            let escrow_token = ctx.accounts.escrow_token_account.to_account_info();
+            ctx.accounts.transfer_from_escrow(&escrow_token, first_recipient_tokens, dust, signer)?;
+            ctx.accounts.close_escrow_token_account(signer)?;
+        }
+        PostSwapAction::Split => {
+            let plan = ctx
+                .accounts
+                .split_plan
+                .as_deref()
+                .cloned()
+                .ok_or(SuperSwapError::InvalidSplitPlan)?;
+            let (mint, leg_accounts) = ctx
+                .remaining_accounts
+                .split_first()
+                .ok_or(SuperSwapError::InvalidSplitPlan)?;
+            require!(
+                mint.key() == ctx.accounts.escrow_token_account.mint
+                    && leg_accounts.len() == plan.legs.len() * 2,
+                SuperSwapError::InvalidSplitPlan
+            );
+
+            let amounts = plan.split(amount)?;
+            let escrow_token = ctx.accounts.escrow_token_account.to_account_info();
+            let legs = plan.legs.iter().zip(leg_accounts.chunks(2)).zip(&amounts);
+            for ((leg, accounts), leg_amount) in legs {
+                let [recipient, recipient_token_account] = accounts else {
+                    return err!(SuperSwapError::InvalidSplitPlan);
+                };
+                require!(
+                    recipient.key() == leg.recipient
+                        && recipient_token_account.key()
+                            == get_associated_token_address(&leg.recipient, &mint.key()),
+                    SuperSwapError::InvalidRecipient
+                );
+
+                ctx.accounts.create_token_account(recipient, mint, recipient_token_account)?;
+                ctx.accounts.transfer_from_escrow(
+                    &escrow_token,
+                    recipient_token_account,
+                    *leg_amount,
+                    signer,
+                )?;
+
+                emit!(SplitLegDelivered {
+                    order_id: params.order_id,
+                    recipient: leg.recipient,
+                    amount: *leg_amount,
+                });
+            }
+            ctx.accounts.close_escrow_token_account(signer)?;
+
+            // Record what each leg received on the plan
+            if let Some(split_plan) = ctx.accounts.split_plan.as_mut() {
+                for (leg, leg_amount) in split_plan.legs.iter_mut().zip(amounts) {
+                    leg.delivered = leg_amount;
+                }
+            }
+        }
+        PostSwapAction::DepositVault { program, vault, beneficiary } => {
+            require!(
+                ctx.accounts.beneficiary.key() == beneficiary,
+                SuperSwapError::InvalidRecipient
+            );
+            let entry = ctx
+                .accounts
+                .vault_program_entry
+                .as_deref()
+                .cloned()
+                .ok_or(SuperSwapError::VaultProgramNotAllowed)?;
+            let min_shares_out = params
+                .min_vault_shares
+                .ok_or(SuperSwapError::InvalidInstructionData)?;
+
+            // The beneficiary receives the share tokens in its ATA for the share mint
+            let (share_mint, receiver_shares) = vault_share_accounts(ctx.remaining_accounts)?;
+            require!(
+                receiver_shares.key() == get_associated_token_address(&beneficiary, &share_mint.key()),
+                SuperSwapError::InvalidRecipient
+            );
+            ctx.accounts.create_beneficiary_account(share_mint, receiver_shares)?;
+            let shares_before = Account::<TokenAccount>::try_from(receiver_shares)?.amount;
+
+            let args = VaultDepositArgs {
+                assets: amount,
+                min_shares_out,
+            };
+            deposit_to_vault(
+                &ctx.accounts.action_program.to_account_info(),
+                &entry,
+                &program,
+                &vault,
+                ctx.remaining_accounts,
+                &escrow_key,
+                &ctx.accounts.escrow_token_account.key(),
+                &receiver_shares.key(),
+                &args,
+                signer,
+            )?;
+
+            // Vault programs are third-party, so check the outcome rather than trust it
+            let shares = Account::<TokenAccount>::try_from(receiver_shares)?
+                .amount
+                .checked_sub(shares_before)
+                .ok_or(SuperSwapError::MathOverflow)?;
+            require!(shares >= min_shares_out, SuperSwapError::SlippageExceeded);
+            ctx.accounts.escrow_token_account.reload()?;
+            require!(
+                ctx.accounts.escrow_token_account.amount == 0,
+                SuperSwapError::InvalidVault
+            );
+            ctx.accounts.close_escrow_token_account(signer)?;
+
+            emit!(OutputDepositedToVault {
+                order_id: params.order_id,
+                vault,
+                beneficiary,
+                assets: amount,
+                shares,
+            });
+        }
+        PostSwapAction::ApproveDelegate { delegate, amount: cap, beneficiary } => {
+            require!(
+                ctx.accounts.beneficiary.key() == beneficiary,
+                SuperSwapError::InvalidRecipient
+            );
+            let entry = ctx
+                .accounts
+                .approved_delegate_entry
+                .as_ref()
+                .ok_or(SuperSwapError::DelegateNotAllowed)?;
+            require!(
+                entry.delegate == delegate && ctx.accounts.action_program.key() == delegate,
+                SuperSwapError::DelegateNotAllowed
+            );
+            // Running again would restore an allowance the delegate already pulled from
+            require!(
+                ctx.accounts.escrow_token_account.delegate.is_none(),
+                SuperSwapError::DelegateAlreadyApproved
+            );
+            let [mint, beneficiary_token_account] = ctx.remaining_accounts else {
+                return err!(SuperSwapError::InvalidInstructionData);
+            };
+            require!(
+                mint.key() == ctx.accounts.escrow_token_account.mint
+                    && beneficiary_token_account.key()
+                        == get_associated_token_address(&beneficiary, &mint.key()),
+                SuperSwapError::InvalidRecipient
+            );
+
+            // The recipient's own account can only be approved by its owner, so the
+            // allowance stays in the escrow, which approves the delegate on it
+            let allowance = cap.min(amount);
+            let delivered = amount - allowance;
+            if delivered > 0 {
+                ctx.accounts.create_beneficiary_account(mint, beneficiary_token_account)?;
+                ctx.accounts.transfer_from_escrow(
+                    &ctx.accounts.escrow_token_account.to_account_info(),
+                    beneficiary_token_account,
+                    delivered,
+                    signer,
+                )?;
+            }
+            if allowance > 0 {
+                let approve_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Approve {
+                        to: ctx.accounts.escrow_token_account.to_account_info(),
+                        delegate: ctx.accounts.action_program.to_account_info(),
+                        authority: ctx.accounts.escrow.to_account_info(),
+                    },
+                    signer,
+                );
+                token::approve(approve_ctx, allowance)?;
+            } else {
+                ctx.accounts.close_escrow_token_account(signer)?;
+            }
+
+            emit!(DelegateApproved {
+                order_id: params.order_id,
+                delegate,
+                beneficiary,
+                allowance,
+                amount_delivered: delivered,
+            });
+        }
+    }
+
+    // Whatever is left in the escrow is the closed account's rent; repay the vault
+    let leftover = ctx.accounts.escrow.lamports();
+    if leftover > 0 {
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+    }
+
+    msg!("Post-swap action executed for order {}", params.order_id);
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::FundsRecovered;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_RECOVERY_SEED, RECOVERY_GUARD_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: PendingRecoveryParams)]
+pub struct ExecuteRecovery<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    /// Queued recovery, closed to the admin once executed
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PENDING_RECOVERY_SEED, config.instance_seed().as_ref(), &params.recovery_id.to_le_bytes()],
+        bump = pending_recovery.bump,
+        has_one = source_token_account,
+        has_one = destination_token_account,
+    )]
+    pub pending_recovery: Account<'info, PendingRecovery>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ExecuteRecovery>, _params: PendingRecoveryParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let pending_recovery = &ctx.accounts.pending_recovery;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= pending_recovery.executable_at, SuperSwapError::RecoveryTimelockActive);
+
+    // Queued recoveries still count against the period cap when they land
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    recovery_guard.record(pending_recovery.amount, now)?;
+
+    let instance_seed = config.instance_seed();
+    let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source_token_account.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: config.to_account_info(),
+            },
+            signer,
+        ),
+        pending_recovery.amount,
+    )?;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::ExecuteRecovery,
+            now,
+            ctx.accounts.pending_recovery.key(),
+        )
+            .with_values(0u64, pending_recovery.amount),
+    );
+
+    emit!(FundsRecovered {
+        recovery_id: Some(pending_recovery.recovery_id),
+        token_mint: pending_recovery.token_mint,
+        source_token_account: pending_recovery.source_token_account,
+        destination_token_account: pending_recovery.destination_token_account,
+        amount: pending_recovery.amount,
+    });
+
+    msg!("Recovery {} executed", pending_recovery.recovery_id);
+
+    Ok(())
+}
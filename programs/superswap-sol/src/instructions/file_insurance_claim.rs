@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::InsuranceClaimFiled;
+use superswap_pda::{CONFIG_SEED, DCA_ORDER_SEED, INSURANCE_CLAIM_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: InsuranceClaimParams)]
+pub struct FileInsuranceClaim<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// Schedule whose unswapped USDC the claim covers
+    #[account(
+        seeds = [DCA_ORDER_SEED, swap_order.key().as_ref()],
+        bump = dca_order.bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    #[account(
+        init,
+        payer = recipient,
+        space = InsuranceClaim::LEN,
+        seeds = [INSURANCE_CLAIM_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+
+    #[account(
+        mut,
+        address = swap_order.recipient @ SuperSwapError::InvalidRecipient
+    )]
+    pub recipient: Signer<'info>,
+
+    /// Recipient's USDC account every refund goes to; frozen accounts make the refund impossible
+    #[account(
+        address = get_associated_token_address(&recipient.key(), &config.usdc_mint) @ SuperSwapError::UsdcTokenAccountNotFound
+    )]
+    pub recipient_usdc_account: Account<'info, TokenAccount>,
+
+    /// Where the recipient wants the payout
+    #[account(
+        constraint = payout_token_account.mint == config.usdc_mint @ SuperSwapError::InvalidTokenMint,
+        constraint = !payout_token_account.is_frozen() @ SuperSwapError::InsuranceClaimNotEligible,
+    )]
+    pub payout_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FileInsuranceClaim>, params: InsuranceClaimParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let swap_order = &ctx.accounts.swap_order;
+
+    // Provably unrefundable: a stale schedule whose only refund destination is frozen
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        swap_order.status == OrderStatus::DcaActive
+            && swap_order.is_past_ttl(now, config.order_ttl_seconds)
+            && ctx.accounts.recipient_usdc_account.is_frozen(),
+        SuperSwapError::InsuranceClaimNotEligible
+    );
+
+    let amount = ctx.accounts.dca_order.remaining_amount;
+    require!(amount > 0, SuperSwapError::InsuranceClaimNotEligible);
+    require!(
+        amount <= config.max_insurance_claim_usdc,
+        SuperSwapError::InsuranceClaimTooLarge
+    );
+
+    let insurance_claim = &mut ctx.accounts.insurance_claim;
+    insurance_claim.swap_order = swap_order.key();
+    insurance_claim.recipient = swap_order.recipient;
+    insurance_claim.payout_token_account = ctx.accounts.payout_token_account.key();
+    insurance_claim.amount = amount;
+    insurance_claim.status = InsuranceClaimStatus::Filed;
+    insurance_claim.filed_at = now;
+    insurance_claim.bump = ctx.bumps.insurance_claim;
+
+    emit!(InsuranceClaimFiled {
+        order_id: params.order_id,
+        recipient: swap_order.recipient,
+        payout_token_account: insurance_claim.payout_token_account,
+        amount,
+    });
+
+    msg!("Insurance claim filed for order {}: {} USDC", params.order_id, amount);
+
+    Ok(())
+}
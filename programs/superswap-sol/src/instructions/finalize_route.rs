@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::RouteFinalized;
+use superswap_pda::{CONFIG_SEED, ROUTE_STAGING_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: FinalizeRouteParams)]
+pub struct FinalizeRoute<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            ROUTE_STAGING_SEED,
+            config.instance_seed().as_ref(),
+            authority.key().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = route_staging.bump,
+    )]
+    pub route_staging: Account<'info, RouteStaging>,
+
+    /// Relayer that uploaded the route
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizeRoute>, params: FinalizeRouteParams) -> Result<()> {
+    let route_staging = &mut ctx.accounts.route_staging;
+    route_staging.finalize()?;
+
+    emit!(RouteFinalized {
+        order_id: params.order_id,
+        authority: route_staging.authority,
+        route_len: route_staging.total_len,
+    });
+
+    msg!("Route for order {} finalized ({} bytes)", params.order_id, route_staging.total_len);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::error::SuperSwapError;
+#[cfg(feature = "hyperlane")]
+use crate::events::OnwardForwarded;
+#[cfg(feature = "hyperlane")]
+use crate::utils::hyperlane::{self, TransferRemoteArgs};
+use superswap_pda::{CONFIG_SEED, ESCROW_SEED, ORDER_EXTENSION_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ForwardOnwardParams)]
+pub struct ForwardOnward<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        seeds = [ORDER_EXTENSION_SEED, swap_order.key().as_ref()],
+        bump = order_extension.bump
+    )]
+    pub order_extension: Account<'info, OrderExtension>,
+
+    /// CHECK: Per-order escrow authority PDA; onward orders are filled with it as recipient
+    #[account(
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's token account holding the swapped output
+    #[account(
+        mut,
+        associated_token::mint = swap_order.destination_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// Across handler that relays orders
+    pub across_handler: Signer<'info>,
+
+    /// CHECK: Onward messaging adapter (validated against config)
+    #[account(constraint = onward_adapter.key() == config.onward_adapter @ SuperSwapError::InvalidOnwardAdapter)]
+    pub onward_adapter: UncheckedAccount<'info>,
+
+    // Note: remaining_accounts holds the adapter's TransferRemote accounts
+}
+
+#[cfg(feature = "hyperlane")]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ForwardOnward<'info>>,
+    params: ForwardOnwardParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let swap_order = &ctx.accounts.swap_order;
+
+    config.require_accepting_fills()?;
+    require!(!config.across_paused, SuperSwapError::BridgePaused);
+    require!(config.is_adapter_enabled(Config::ADAPTER_HYPERLANE), SuperSwapError::AdapterDisabled);
+    require!(
+        config.onward_adapter != Pubkey::default(),
+        SuperSwapError::InvalidOnwardAdapter
+    );
+    require!(
+        swap_order.status == OrderStatus::Completed,
+        SuperSwapError::InvalidOrderStatus
+    );
+    require!(
+        swap_order.recipient == ctx.accounts.escrow.key(),
+        SuperSwapError::InvalidRecipient
+    );
+
+    let onward_destination = ctx
+        .accounts
+        .order_extension
+        .onward_destination
+        .ok_or(SuperSwapError::MissingOnwardDestination)?;
+
+    let amount = ctx.accounts.escrow_token_account.amount;
+    require!(amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let swap_order_key = swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_args = TransferRemoteArgs::new(
+        onward_destination.domain,
+        &onward_destination.recipient,
+        amount,
+    );
+    hyperlane::transfer_remote(
+        &ctx.accounts.onward_adapter.to_account_info(),
+        ctx.remaining_accounts,
+        &transfer_args,
+        &ctx.accounts.escrow.key(),
+        signer,
+    )?;
+
+    emit!(OnwardForwarded {
+        order_id: params.order_id,
+        domain: onward_destination.domain,
+        recipient: onward_destination.recipient,
+        amount,
+    });
+
+    msg!("Order {} forwarded onward: {}", params.order_id, amount);
+
+    Ok(())
+}
+
+/// Builds without the `hyperlane` feature keep the instruction in the interface but reject it
+#[cfg(not(feature = "hyperlane"))]
+pub fn handler<'info>(
+    _ctx: Context<'_, '_, 'info, 'info, ForwardOnward<'info>>,
+    _params: ForwardOnwardParams,
+) -> Result<()> {
+    err!(SuperSwapError::AdapterNotBuilt)
+}
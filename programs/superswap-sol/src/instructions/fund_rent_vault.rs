@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{CONFIG_SEED, RENT_VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct FundRentVault<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// Admin, or the config's operator when one is set
+    #[account(
+        mut,
+        constraint = config.is_operator(&authority.key()) @ SuperSwapError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundRentVault>, params: FundRentVaultParams) -> Result<()> {
+    require!(params.amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.rent_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(transfer_ctx, params.amount)?;
+
+    let rent_vault = &mut ctx.accounts.rent_vault;
+    rent_vault.total_deposited = rent_vault
+        .total_deposited
+        .checked_add(params.amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    msg!("Rent vault funded with {} lamports", params.amount);
+    msg!("Rent vault balance: {}", rent_vault.get_lamports());
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use crate::state::*;
+use superswap_pda::CONFIG_SEED;
+
+// The whole config must fit in return data; split the snapshot before it outgrows it
+const _: () = assert!(Config::LEN - 8 <= MAX_RETURN_DATA);
+
+#[derive(Accounts)]
+pub struct GetConfig<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<GetConfig>) -> Result<Config> {
+    Ok((*ctx.accounts.config).clone())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, OUTBOUND_RATE_LIMIT_SEED, RECOVERY_GUARD_SEED, RENT_VAULT_SEED};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -8,37 +9,196 @@ pub struct Initialize<'info> {
         init,
         payer = admin,
         space = Config::LEN,
-        seeds = [b"config"],
+        seeds = [CONFIG_SEED],
         bump
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init,
+        payer = admin,
+        space = RentVault::LEN,
+        seeds = [RENT_VAULT_SEED],
+        bump
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = OutboundRateLimit::LEN,
+        seeds = [OUTBOUND_RATE_LIMIT_SEED],
+        bump
+    )]
+    pub outbound_rate_limit: Account<'info, OutboundRateLimit>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RecoveryGuard::LEN,
+        seeds = [RECOVERY_GUARD_SEED],
+        bump
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AdminLog::LEN,
+        seeds = [ADMIN_LOG_SEED],
+        bump
+    )]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
-    let config = &mut ctx.accounts.config;
-
+/// Fills in a freshly created config, shared by `initialize` and `create_instance`
+pub(crate) fn init_config(
+    config: &mut Account<Config>,
+    admin: Pubkey,
+    params: &InitializeParams,
+    instance_id: u16,
+    bump: u8,
+) -> Result<()> {
     // Validate fee bps (max 10% = 1000 bps)
     require!(params.fee_bps <= 1000, SuperSwapError::InvalidFeeConfiguration);
+    require!(params.outbound_fee_bps <= 1000, SuperSwapError::InvalidFeeConfiguration);
+    require!(
+        params.max_outbound_amount == 0 || params.min_outbound_amount <= params.max_outbound_amount,
+        SuperSwapError::InvalidFeeConfiguration
+    );
 
-    config.admin = ctx.accounts.admin.key();
+    config.admin = admin;
     config.across_handler = params.across_handler;
     config.jupiter_program = params.jupiter_program;
+    config.across_spoke_pool = params.across_spoke_pool;
+    config.cctp_token_messenger_minter = params.cctp_token_messenger_minter;
     config.usdc_mint = params.usdc_mint;
     config.fee_recipient = params.fee_recipient;
     config.fee_bps = params.fee_bps;
+    config.outbound_fee_bps = params.outbound_fee_bps;
+    config.min_outbound_amount = params.min_outbound_amount;
+    config.max_outbound_amount = params.max_outbound_amount;
+    // Bridge fee estimates are tuned later through update_config
+    config.across_fee_bps_estimate = 0;
+    config.cctp_fee_bps_estimate = 0;
+    // Onward forwarding stays disabled until an adapter is configured
+    config.onward_adapter = Pubkey::default();
+    config.jito_stake_pool = Pubkey::default();
+    config.marinade_state = Pubkey::default();
+    config.kamino_lending_market = Pubkey::default();
+    config.solend_lending_market = Pubkey::default();
+    // Gas top-ups stay disabled until a cap is configured
+    config.max_gas_top_up_usdc = 0;
+    // Donation splits stay off until the admin sets a cap
+    config.max_donation_bps = 0;
+    // Keepers refunding expired orders go unpaid until the admin sets a bounty
+    config.refund_bounty_bps = 0;
+    // Dust orders are accepted until the admin sets a minimum
+    config.min_order_usdc = 0;
+    config.deliver_dust_as_usdc = false;
+    config.max_open_orders_per_recipient = 0;
+    config.max_price_impact_bps = 0;
+    config.order_ttl_seconds = 0;
+    config.escrow_shortfall_tolerance = 0;
+    config.across_paused = false;
+    config.cctp_paused = false;
+    config.insurance_fee_share_bps = 0;
+    config.max_insurance_claim_usdc = 0;
+    config.fallback_output_mint = Pubkey::default();
+    config.rent_treasury = Pubkey::default();
+    config.min_retention_seconds = 0;
+    // Operator reimbursement starts disabled until the admin prices a fill
+    config.operator_fill_cost_usdc = 0;
+    config.version = Config::VERSION;
+    config.guardian = Pubkey::default();
+    config.max_order_usdc = 0;
+    // Every bridge adapter starts enabled
+    config.enabled_adapters = Config::ADAPTERS_BUILT;
+    config.vault_authority = config.key();
+    config.instance_id = instance_id;
+    config.refunds_only = false;
+    // Relayers go untipped until the admin sets a cap
+    config.max_relayer_tip_usdc = 0;
+    // Positive slippage goes to the recipient until the admin sets a share
+    config.surplus_share_bps = 0;
+    config.max_gas_drop_lamports = 0;
+    config.pending_admin = Pubkey::default();
+    // No delay until the admin sets one
+    config.config_update_delay_seconds = 0;
+    config.operator = Pubkey::default();
+    config.additional_across_handlers = [Pubkey::default(); Config::MAX_ADDITIONAL_ACROSS_HANDLERS];
+    // No daily volume cap until the admin sets one
+    config.daily_volume_cap = 0;
+    // Surplus stays with the recipient until the admin picks where a share goes
+    config.surplus_policy = SurplusPolicy::Recipient;
     config.is_paused = false;
-    config.bump = ctx.bumps.config;
+    config.bump = bump;
+
+    Ok(())
+}
+
+/// Fills in the rent vault, outbound rate limit and recovery guard every instance
+/// owns, shared by `initialize` and `create_instance`
+pub(crate) fn init_instance_accounts(
+    rent_vault: &mut RentVault,
+    rent_vault_bump: u8,
+    outbound_rate_limit: &mut OutboundRateLimit,
+    outbound_rate_limit_bump: u8,
+    recovery_guard: &mut RecoveryGuard,
+    recovery_guard_bump: u8,
+) -> Result<()> {
+    rent_vault.total_deposited = 0;
+    rent_vault.total_subsidized = 0;
+    rent_vault.bump = rent_vault_bump;
+
+    // Outbound volume is tracked from the start but uncapped until configured
+    outbound_rate_limit.period_seconds = OutboundRateLimit::DEFAULT_PERIOD_SECONDS;
+    outbound_rate_limit.period_cap = 0;
+    outbound_rate_limit.period_start = Clock::get()?.unix_timestamp;
+    outbound_rate_limit.period_volume = 0;
+    outbound_rate_limit.bump = outbound_rate_limit_bump;
+
+    // Recoveries are likewise tracked but neither timelocked nor capped until configured
+    recovery_guard.limits = RecoveryLimits::DISABLED;
+    recovery_guard.pending_limits = RecoveryLimits::DISABLED;
+    recovery_guard.pending_effective_at = 0;
+    recovery_guard.period_start = outbound_rate_limit.period_start;
+    recovery_guard.period_volume = 0;
+    recovery_guard.bump = recovery_guard_bump;
+
+    Ok(())
+}
+
+pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+    init_config(
+        &mut ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &params,
+        0,
+        ctx.bumps.config,
+    )?;
+    let config = &ctx.accounts.config;
+    ctx.accounts.admin_log.init(config.key(), ctx.bumps.admin_log);
+    init_instance_accounts(
+        &mut ctx.accounts.rent_vault,
+        ctx.bumps.rent_vault,
+        &mut ctx.accounts.outbound_rate_limit,
+        ctx.bumps.outbound_rate_limit,
+        &mut ctx.accounts.recovery_guard,
+        ctx.bumps.recovery_guard,
+    )?;
 
     msg!("SuperSwap initialized successfully");
     msg!("Admin: {}", config.admin);
     msg!("Across Handler: {}", config.across_handler);
     msg!("Jupiter Program: {}", config.jupiter_program);
     msg!("Fee BPS: {}", config.fee_bps);
+    msg!("Outbound Fee BPS: {}", config.outbound_fee_bps);
 
     Ok(())
 }
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::ConfigMigrated;
+use superswap_pda::CONFIG_SEED;
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Config PDA still in an older layout, which `Account<Config>` cannot read; validated in instruction
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// Admin recorded in the old config; pays the rent for the larger layout
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    require_keys_eq!(*config_info.owner, crate::ID, SuperSwapError::ConfigNotMigratable);
+
+    // The account's length tells the layouts apart; the V1 vault is the config PDA's own USDC account
+    let previous_len = config_info.data_len();
+    let config = {
+        let data = config_info.try_borrow_data()?;
+        match previous_len {
+            ConfigV1::LEN => Config::from_v1(Config::v1_from_account_data(&data)?, config_info.key()),
+            ConfigV2::LEN => Config::from_v2(Config::v2_from_account_data(&data)?),
+            _ => return err!(SuperSwapError::ConfigNotMigratable),
+        }
+    };
+    require_keys_eq!(config.admin, ctx.accounts.admin.key(), SuperSwapError::Unauthorized);
+
+    // The PDA keeps its address; it only grows, with the admin covering the extra rent
+    let rent_due = Rent::get()?
+        .minimum_balance(Config::LEN)
+        .saturating_sub(config_info.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    config_info.realloc(Config::LEN, false)?;
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    emit!(ConfigMigrated {
+        version: Config::VERSION,
+        previous_len: previous_len as u32,
+        new_len: Config::LEN as u32,
+    });
+
+    msg!("Config migrated to version {}", Config::VERSION);
+
+    Ok(())
+}
@@ -1,14 +1,162 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize;
 pub mod update_config;
+pub mod accept_admin;
+pub mod propose_config_update;
+pub mod apply_config_update;
+pub mod cancel_config_update;
+pub mod remove_across_handler;
 pub mod process_bridge_and_swap;
-pub mod execute_jupiter_swap;
+pub mod create_admin_log;
+pub mod upload_route_chunk;
+pub mod finalize_route;
+pub mod register_route_template;
+pub mod remove_route_template;
+pub mod create_mint_config;
+pub mod update_mint_config;
+pub mod delete_mint_config;
+pub mod create_volume_tracker;
+pub mod create_order_extension;
+pub mod create_stream_plan;
+pub mod create_split_plan;
+pub mod create_basket_plan;
+pub mod execute_basket_leg;
+pub mod forward_onward;
+pub mod execute_post_swap_action;
+pub mod claim_output;
+pub mod claim_evm_funds;
+pub mod place_limit_order;
+pub mod cancel_limit_order;
+pub mod create_dca_order;
+pub mod execute_dca_tranche;
+pub mod cancel_dca_order;
+pub mod expire_and_refund;
+pub mod close_order;
+pub mod close_orders_batch;
+pub mod compress_orders;
+pub mod assert_solvency;
+pub mod swap_and_bridge;
+pub mod swap_and_burn;
+pub mod quote_outbound;
+pub mod get_config;
+pub mod settle_outbound_order;
+pub mod refund_outbound;
 pub mod recover_funds;
+pub mod queue_recovery;
+pub mod execute_recovery;
+pub mod cancel_recovery;
+pub mod set_recovery_limits;
+pub mod recover_lamports;
+pub mod queue_lamport_recovery;
+pub mod execute_lamport_recovery;
+pub mod pay_insurance;
+pub mod file_insurance_claim;
+pub mod approve_insurance_claim;
+pub mod pay_insurance_claim;
+pub mod rebalance_treasury;
+pub mod set_outbound_rate_limit;
+pub mod set_recipient_preferences;
+pub mod add_hook_program;
+pub mod remove_hook_program;
+pub mod add_spend_program;
+pub mod remove_spend_program;
+pub mod add_vault_program;
+pub mod remove_vault_program;
+pub mod add_approved_delegate;
+pub mod remove_approved_delegate;
+pub mod revoke_delegate;
+pub mod add_donation_recipient;
+pub mod remove_donation_recipient;
+pub mod add_payer_operator;
+pub mod remove_payer_operator;
+pub mod settle_payer_ledger;
+pub mod set_destination_mint;
+pub mod create_instance;
+pub mod migrate_config;
 pub mod pause;
+pub mod unpause;
+pub mod set_refunds_only;
+pub mod fund_rent_vault;
+pub mod withdraw_rent_vault;
 
 pub use initialize::*;
 pub use update_config::*;
+pub use accept_admin::*;
+pub use propose_config_update::*;
+pub use apply_config_update::*;
+pub use cancel_config_update::*;
+pub use remove_across_handler::*;
 pub use process_bridge_and_swap::*;
-pub use execute_jupiter_swap::*;
+pub use create_admin_log::*;
+pub use upload_route_chunk::*;
+pub use finalize_route::*;
+pub use register_route_template::*;
+pub use remove_route_template::*;
+pub use create_mint_config::*;
+pub use update_mint_config::*;
+pub use delete_mint_config::*;
+pub use create_volume_tracker::*;
+pub use create_order_extension::*;
+pub use create_stream_plan::*;
+pub use create_split_plan::*;
+pub use create_basket_plan::*;
+pub use execute_basket_leg::*;
+pub use forward_onward::*;
+pub use execute_post_swap_action::*;
+pub use claim_output::*;
+pub use claim_evm_funds::*;
+pub use place_limit_order::*;
+pub use cancel_limit_order::*;
+pub use create_dca_order::*;
+pub use execute_dca_tranche::*;
+pub use cancel_dca_order::*;
+pub use expire_and_refund::*;
+pub use close_order::*;
+pub use close_orders_batch::*;
+pub use compress_orders::*;
+pub use assert_solvency::*;
+pub use swap_and_bridge::*;
+pub use swap_and_burn::*;
+pub use quote_outbound::*;
+pub use get_config::*;
+pub use settle_outbound_order::*;
+pub use refund_outbound::*;
 pub use recover_funds::*;
+pub use queue_recovery::*;
+pub use execute_recovery::*;
+pub use cancel_recovery::*;
+pub use set_recovery_limits::*;
+pub use recover_lamports::*;
+pub use queue_lamport_recovery::*;
+pub use execute_lamport_recovery::*;
+pub use pay_insurance::*;
+pub use file_insurance_claim::*;
+pub use approve_insurance_claim::*;
+pub use pay_insurance_claim::*;
+pub use rebalance_treasury::*;
+pub use set_outbound_rate_limit::*;
+pub use set_recipient_preferences::*;
+pub use add_hook_program::*;
+pub use remove_hook_program::*;
+pub use add_spend_program::*;
+pub use remove_spend_program::*;
+pub use add_vault_program::*;
+pub use remove_vault_program::*;
+pub use add_approved_delegate::*;
+pub use remove_approved_delegate::*;
+pub use revoke_delegate::*;
+pub use add_donation_recipient::*;
+pub use remove_donation_recipient::*;
+pub use add_payer_operator::*;
+pub use remove_payer_operator::*;
+pub use settle_payer_ledger::*;
+pub use set_destination_mint::*;
+pub use create_instance::*;
+pub use migrate_config::*;
 pub use pause::*;
+pub use unpause::*;
+pub use set_refunds_only::*;
+pub use fund_rent_vault::*;
+pub use withdraw_rent_vault::*;
 
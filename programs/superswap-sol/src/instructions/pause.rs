@@ -1,48 +1,48 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
 
 #[derive(Accounts)]
 pub struct Pause<'info> {
     #[account(
         mut,
-        seeds = [b"config"],
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
         bump = config.bump,
-        has_one = admin @ SuperSwapError::Unauthorized
     )]
     pub config: Account<'info, Config>,
 
-    pub admin: Signer<'info>,
-}
-
-pub fn handler(ctx: Context<Pause>) -> Result<()> {
-    let config = &mut ctx.accounts.config;
-    config.is_paused = true;
-
-    msg!("Program paused");
-
-    Ok(())
-}
-
-#[derive(Accounts)]
-pub struct Unpause<'info> {
+    /// Audit log recording the action
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        has_one = admin @ SuperSwapError::Unauthorized
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
     )]
-    pub config: Account<'info, Config>,
+    pub admin_log: Account<'info, AdminLog>,
 
-    pub admin: Signer<'info>,
+    /// Admin, or the config's guardian when one is set
+    #[account(
+        constraint = config.is_guardian(&authority.key()) @ SuperSwapError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<Unpause>) -> Result<()> {
+pub fn handler(ctx: Context<Pause>) -> Result<()> {
     let config = &mut ctx.accounts.config;
-    config.is_paused = false;
+    let was_paused = config.is_paused;
+    config.is_paused = true;
 
-    msg!("Program unpaused");
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.authority.key(),
+            AdminAction::Pause,
+            Clock::get()?.unix_timestamp,
+            Pubkey::default(),
+        )
+        .with_values(was_paused, true),
+    );
+
+    msg!("Program paused");
 
     Ok(())
 }
-
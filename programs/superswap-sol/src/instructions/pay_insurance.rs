@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::InsurancePaid;
+use superswap_pda::{CONFIG_SEED, INSURANCE_VAULT_SEED, RECOVERY_GUARD_SEED};
+
+#[derive(Accounts)]
+pub struct PayInsurance<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payouts count against the recovery cap like any other admin outflow
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    /// CHECK: Insurance vault PDA, owner of the fund's USDC
+    #[account(seeds = [INSURANCE_VAULT_SEED, config.instance_seed().as_ref()], bump)]
+    pub insurance_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.usdc_mint,
+        associated_token::authority = insurance_vault
+    )]
+    pub insurance_usdc_account: Account<'info, TokenAccount>,
+
+    /// Affected user's USDC account
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == config.usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<PayInsurance>, params: PayInsuranceParams) -> Result<()> {
+    require!(params.amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    recovery_guard.record(params.amount, now)?;
+
+    let instance_seed = ctx.accounts.config.instance_seed();
+    let seeds = &[INSURANCE_VAULT_SEED, instance_seed.as_ref(), &[ctx.bumps.insurance_vault]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.insurance_usdc_account.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.insurance_vault.to_account_info(),
+            },
+            signer,
+        ),
+        params.amount,
+    )?;
+
+    emit!(InsurancePaid {
+        incident_id: params.incident_id,
+        destination_token_account: ctx.accounts.destination_token_account.key(),
+        amount: params.amount,
+    });
+
+    msg!("Insurance paid {} USDC for incident {}", params.amount, params.incident_id);
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::InsuranceClaimPaid;
+use crate::utils::open_orders::release_open_order;
+use superswap_pda::{
+    CONFIG_SEED, DCA_ORDER_SEED, ESCROW_SEED, INSURANCE_CLAIM_SEED, INSURANCE_VAULT_SEED,
+    RECIPIENT_ORDERS_SEED, RECOVERY_GUARD_SEED, SWAP_ORDER_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(params: InsuranceClaimParams)]
+pub struct PayInsuranceClaim<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payouts count against the recovery cap like any other outflow the admin authorizes
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    #[account(
+        mut,
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// Schedule the claim settles, kept as the record of its fills
+    #[account(
+        mut,
+        seeds = [DCA_ORDER_SEED, swap_order.key().as_ref()],
+        bump = dca_order.bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_CLAIM_SEED, swap_order.key().as_ref()],
+        bump = insurance_claim.bump,
+        has_one = payout_token_account,
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+
+    /// CHECK: Recipient's open order count, released once the claim settles the order;
+    /// absent when the order predates the count
+    #[account(
+        mut,
+        seeds = [RECIPIENT_ORDERS_SEED, swap_order.recipient.as_ref()],
+        bump
+    )]
+    pub recipient_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Per-order escrow PDA holding the unrefundable USDC
+    #[account(
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's USDC account, swept into the fund unless it is frozen too
+    #[account(
+        mut,
+        associated_token::mint = config.usdc_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_usdc_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Insurance vault PDA, owner of the fund's USDC
+    #[account(seeds = [INSURANCE_VAULT_SEED, config.instance_seed().as_ref()], bump)]
+    pub insurance_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.usdc_mint,
+        associated_token::authority = insurance_vault
+    )]
+    pub insurance_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payout_token_account: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<PayInsuranceClaim>, params: InsuranceClaimParams) -> Result<()> {
+    require!(
+        ctx.accounts.insurance_claim.status == InsuranceClaimStatus::Approved,
+        SuperSwapError::InvalidClaimStatus
+    );
+    require!(
+        ctx.accounts.swap_order.status == OrderStatus::DcaActive,
+        SuperSwapError::InvalidOrderStatus
+    );
+    let amount = ctx.accounts.insurance_claim.amount;
+
+    let now = Clock::get()?.unix_timestamp;
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    recovery_guard.record(amount, now)?;
+
+    // Whatever the escrow still holds replenishes the fund, so the order can never
+    // be refunded on top of the payout
+    let usdc_recovered = if ctx.accounts.escrow_usdc_account.is_frozen() {
+        0
+    } else {
+        ctx.accounts.escrow_usdc_account.amount
+    };
+    if usdc_recovered > 0 {
+        let swap_order_key = ctx.accounts.swap_order.key();
+        let seeds = &[ESCROW_SEED, swap_order_key.as_ref(), &[ctx.bumps.escrow]];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_usdc_account.to_account_info(),
+                    to: ctx.accounts.insurance_usdc_account.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                signer,
+            ),
+            usdc_recovered,
+        )?;
+    }
+
+    let instance_seed = ctx.accounts.config.instance_seed();
+    let seeds = &[INSURANCE_VAULT_SEED, instance_seed.as_ref(), &[ctx.bumps.insurance_vault]];
+    let signer = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.insurance_usdc_account.to_account_info(),
+                to: ctx.accounts.payout_token_account.to_account_info(),
+                authority: ctx.accounts.insurance_vault.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    release_open_order(&ctx.accounts.recipient_orders.to_account_info())?;
+
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.remaining_amount = 0;
+    let swap_order = &mut ctx.accounts.swap_order;
+    swap_order.status = if dca_order.fills.is_empty() {
+        OrderStatus::Refunded
+    } else {
+        OrderStatus::Completed
+    };
+    ctx.accounts.insurance_claim.status = InsuranceClaimStatus::Paid;
+
+    emit!(InsuranceClaimPaid {
+        order_id: params.order_id,
+        payout_token_account: ctx.accounts.payout_token_account.key(),
+        amount,
+        usdc_recovered,
+    });
+
+    msg!("Insurance claim for order {} paid: {} USDC", params.order_id, amount);
+
+    Ok(())
+}
@@ -0,0 +1,329 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::LimitOrderPlaced;
+use crate::utils::destination_mint::require_mint_enabled;
+use crate::utils::limit_order::{self, InitializeOrderArgs, LimitOrderAccounts, JUPITER_LIMIT_ORDER_PROGRAM_ID};
+use crate::utils::insurance::pay_protocol_fee;
+use crate::utils::refund::calculate_fee;
+use crate::utils::payer_ledger::record_payer_expense;
+use crate::utils::rent_vault::{create_ata_if_needed, fund_order_rent, reimburse_rent};
+use superswap_pda::{CONFIG_SEED, DESTINATION_MINT_SEED, ESCROW_SEED, LIMIT_ORDER_SEED, RECIPIENT_ORDERS_SEED, PAYER_LEDGER_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: PlaceLimitOrderParams)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+        has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SwapOrder::LEN,
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = LimitOrder::LEN,
+        seeds = [LIMIT_ORDER_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    /// Recipient's open order count, created with their first escrowed order
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientOrders::LEN,
+        seeds = [RECIPIENT_ORDERS_SEED, params.recipient.as_ref()],
+        bump
+    )]
+    pub recipient_orders: Account<'info, RecipientOrders>,
+
+    /// CHECK: Per-order escrow PDA acting as the limit order maker; the relayer
+    /// funds it with the Jupiter order rent ahead of this instruction
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Across handler that relays the bridged USDC
+    pub across_handler: Signer<'info>,
+
+    /// CHECK: Recipient of fills and of the USDC returned on cancellation
+    #[account(constraint = params.recipient != Pubkey::default() @ SuperSwapError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Source USDC token account (receives bridged USDC from Across)
+    #[account(
+        mut,
+        constraint = source_usdc_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub source_usdc_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Escrow's USDC account funding the order, created from the rent vault if missing
+    #[account(
+        mut,
+        address = get_associated_token_address(&escrow.key(), &usdc_mint.key()) @ SuperSwapError::UsdcTokenAccountNotFound
+    )]
+    pub escrow_usdc_account: UncheckedAccount<'info>,
+
+    /// Destination token mint (the token the order buys)
+    pub destination_mint: Account<'info, Mint>,
+
+    /// CHECK: Operator switch for the destination mint, absent while the mint has no entry
+    #[account(seeds = [DESTINATION_MINT_SEED, config.instance_seed().as_ref(), destination_mint.key().as_ref()], bump)]
+    pub destination_mint_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Escrow's destination token account receiving fills, created from the rent vault if missing
+    #[account(
+        mut,
+        address = get_associated_token_address(&escrow.key(), &destination_mint.key()) @ SuperSwapError::DestinationTokenAccountNotFound
+    )]
+    pub escrow_output_account: UncheckedAccount<'info>,
+
+    /// Fee recipient's USDC account
+    #[account(
+        mut,
+        constraint = fee_recipient_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+        constraint = fee_recipient_account.owner == config.fee_recipient @ SuperSwapError::Unauthorized,
+    )]
+    pub fee_recipient_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Insurance fund's USDC account, checked by address when a fee share is paid
+    #[account(mut)]
+    pub insurance_usdc_account: UncheckedAccount<'info>,
+
+    /// Rent vault that covers order and ATA creation
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// CHECK: Jupiter Limit Order program
+    #[account(address = JUPITER_LIMIT_ORDER_PROGRAM_ID @ SuperSwapError::InvalidJupiterProgram)]
+    pub limit_order_program: UncheckedAccount<'info>,
+
+    /// Payer's reimbursement ledger, required when the payer is not the Across handler
+    #[account(
+        mut,
+        seeds = [PAYER_LEDGER_SEED, config.instance_seed().as_ref(), payer.key().as_ref()],
+        bump = payer_ledger.bump
+    )]
+    pub payer_ledger: Option<Account<'info, PayerLedger>>,
+
+    /// Fronts the order account rent, reimbursed from the rent vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Note: remaining_accounts holds the Jupiter `initialize_order` accounts
+}
+
+impl<'info> PlaceLimitOrder<'info> {
+    /// Covers rent for the order accounts and the escrow's ATAs from the rent vault,
+    /// returning the account that funded the swap order's rent
+    fn subsidize_account_creation(&mut self) -> Result<Pubkey> {
+        let payer = self.payer.to_account_info();
+        let rent_payer = fund_order_rent(&mut self.rent_vault, &payer, self.swap_order.get_lamports())?;
+        let mut order_rent = self.limit_order.get_lamports();
+        // The count is only new on the recipient's first escrowed order
+        if self.recipient_orders.recipient == Pubkey::default() {
+            order_rent += self.recipient_orders.get_lamports();
+        }
+        reimburse_rent(&mut self.rent_vault, &payer, order_rent)?;
+
+        let escrow = self.escrow.to_account_info();
+        let usdc_mint = self.usdc_mint.to_account_info();
+        let destination_mint = self.destination_mint.to_account_info();
+        let atas = [
+            (self.escrow_usdc_account.to_account_info(), &usdc_mint),
+            (self.escrow_output_account.to_account_info(), &destination_mint),
+        ];
+
+        for (ata, mint) in atas.iter() {
+            create_ata_if_needed(
+                &mut self.rent_vault,
+                &payer,
+                ata,
+                &escrow,
+                mint,
+                &self.system_program.to_account_info(),
+                &self.token_program.to_account_info(),
+                &self.associated_token_program.to_account_info(),
+            )?;
+        }
+
+        Ok(rent_payer)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceLimitOrder<'info>>,
+    params: PlaceLimitOrderParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    // Check if program is paused
+    config.require_accepting_fills()?;
+    require!(!config.across_paused, SuperSwapError::BridgePaused);
+    require!(config.is_adapter_enabled(Config::ADAPTER_ACROSS), SuperSwapError::AdapterDisabled);
+
+    // Reject new orders into a mint an operator has disabled
+    require_mint_enabled(&ctx.accounts.destination_mint_entry.to_account_info())?;
+
+    // Anyone but the Across handler paying for the transaction must be a registered operator
+    record_payer_expense(
+        config,
+        &ctx.accounts.payer.key(),
+        ctx.accounts.payer_ledger.as_mut(),
+        params.order_id,
+    )?;
+
+    // Validate amounts and expiry
+    require!(params.usdc_amount > 0, SuperSwapError::InvalidBridgeAmount);
+    require!(!config.exceeds_max_order(params.usdc_amount), SuperSwapError::OrderAboveMaximum);
+    require!(!config.is_dust_order(params.usdc_amount), SuperSwapError::OrderBelowMinimum);
+    require!(params.taking_amount > 0, SuperSwapError::InvalidLimitPrice);
+    let current_time = Clock::get()?.unix_timestamp;
+    if let Some(expired_at) = params.expired_at {
+        require!(expired_at > current_time, SuperSwapError::DeadlineExceeded);
+    }
+
+    // Rent for the order accounts and the escrow's ATAs comes from the rent vault
+    let rent_payer = ctx.accounts.subsidize_account_creation()?;
+
+    // Bound how much escrow a single recipient can tie up in open orders
+    let max_open_orders = ctx.accounts.config.max_open_orders_per_recipient;
+    let recipient_orders = &mut ctx.accounts.recipient_orders;
+    recipient_orders.recipient = params.recipient;
+    recipient_orders.bump = ctx.bumps.recipient_orders;
+    recipient_orders.open_order(max_open_orders)?;
+    let config = &ctx.accounts.config;
+
+    let fee_amount = calculate_fee(params.usdc_amount, config.fee_bps)?;
+    let making_amount = params.usdc_amount
+        .checked_sub(fee_amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+    require!(making_amount > 0, SuperSwapError::InvalidBridgeAmount);
+
+    let swap_order = &mut ctx.accounts.swap_order;
+    swap_order.order_id = params.order_id;
+    swap_order.recipient = params.recipient;
+    swap_order.usdc_amount = params.usdc_amount;
+    swap_order.min_output_amount = params.taking_amount;
+    swap_order.destination_mint = params.destination_mint;
+    swap_order.deadline = params.expired_at.unwrap_or(0);
+    swap_order.created_at = current_time;
+    swap_order.status = OrderStatus::LimitPlaced;
+    swap_order.bump = ctx.bumps.swap_order;
+    swap_order.rent_payer = rent_payer;
+    swap_order.surplus = 0;
+
+    msg!("Placing limit order for swap order: {}", params.order_id);
+    msg!("Recipient: {}", params.recipient);
+    msg!("Fee Amount: {}", fee_amount);
+
+    // Move the bridged USDC into the escrow, less the protocol fee
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.source_usdc_account.to_account_info(),
+            to: ctx.accounts.escrow_usdc_account.to_account_info(),
+            authority: ctx.accounts.across_handler.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, making_amount)?;
+
+    if fee_amount > 0 {
+        pay_protocol_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.source_usdc_account.to_account_info(),
+            &ctx.accounts.across_handler.to_account_info(),
+            &ctx.accounts.fee_recipient_account.to_account_info(),
+            &ctx.accounts.insurance_usdc_account.to_account_info(),
+            fee_amount,
+            &[],
+        )?;
+    }
+
+    let swap_order_key = ctx.accounts.swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    // The escrow is the maker, so fills and refunds stay under program control
+    let order_accounts = LimitOrderAccounts {
+        maker: ctx.accounts.escrow.key(),
+        maker_input: ctx.accounts.escrow_usdc_account.key(),
+        maker_output: ctx.accounts.escrow_output_account.key(),
+    };
+    let args = InitializeOrderArgs {
+        making_amount,
+        taking_amount: params.taking_amount,
+        expired_at: params.expired_at,
+    };
+    let jupiter_order = limit_order::order_account(ctx.remaining_accounts)?.key();
+
+    // The escrow pays the Jupiter order rent; reimburse the relayer that funded it
+    let escrow_lamports = ctx.accounts.escrow.lamports();
+    limit_order::initialize_order(
+        &ctx.accounts.limit_order_program.to_account_info(),
+        ctx.remaining_accounts,
+        &order_accounts,
+        &args,
+        signer,
+    )?;
+    let order_rent = escrow_lamports.saturating_sub(ctx.accounts.escrow.lamports());
+    let payer = ctx.accounts.payer.to_account_info();
+    reimburse_rent(&mut ctx.accounts.rent_vault, &payer, order_rent)?;
+
+    let limit_order = &mut ctx.accounts.limit_order;
+    limit_order.swap_order = swap_order_key;
+    limit_order.jupiter_order = jupiter_order;
+    limit_order.making_amount = making_amount;
+    limit_order.taking_amount = params.taking_amount;
+    limit_order.expired_at = params.expired_at.unwrap_or(0);
+    limit_order.bump = ctx.bumps.limit_order;
+
+    emit!(LimitOrderPlaced {
+        order_id: params.order_id,
+        recipient: params.recipient,
+        jupiter_order,
+        making_amount,
+        taking_amount: params.taking_amount,
+    });
+
+    msg!("Limit order {} placed", jupiter_order);
+
+    Ok(())
+}
@@ -1,16 +1,30 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, spl_token, CloseAccount, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::associated_token::{get_associated_token_address, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, TransferChecked};
 use crate::state::*;
 use crate::error::SuperSwapError;
+use crate::events::{DonationRouted, DustOrderDelivered, ExpiredOrderRefunded, FailedSwapRefunded, FallbackOutputDelivered, GasDropped, GasToppedUp, RecipientNotified, RelayerTipped, SurplusCaptured};
+use crate::utils::callback::{notify_delivery, DeliveryCallbackArgs};
+use crate::utils::curve::is_on_curve;
+use crate::utils::destination_mint::{is_mint_disabled, load_mint_config};
+use crate::utils::hooks::execute_post_swap_hook;
+use crate::utils::insurance::pay_protocol_fee;
+use crate::utils::jupiter::{captured_surplus, execute_jupiter_swap, parse_jupiter_swap_data, realized_surplus, require_route_token_accounts, route_quoted_out_amount, validate_price_impact, validate_route_params, validate_swap_output};
+use crate::utils::refund::calculate_fee;
+use crate::utils::payer_ledger::record_payer_expense;
+use crate::utils::rent_vault::{create_ata_if_needed, fund_order_rent};
+use superswap_pda::{CALLBACK_AUTHORITY_SEED, CONFIG_SEED, DESTINATION_MINT_SEED, DONATION_RECIPIENT_SEED, ESCROW_SEED, HOOK_PROGRAM_SEED, MINT_CONFIG_SEED, RECIPIENT_PREFERENCES_SEED, PAYER_LEDGER_SEED, RENT_VAULT_SEED, ROUTE_STAGING_SEED, ROUTE_TEMPLATE_SEED, SWAP_ORDER_SEED, VOLUME_TRACKER_SEED};
 
 #[derive(Accounts)]
 #[instruction(params: ProcessBridgeAndSwapParams)]
 pub struct ProcessBridgeAndSwap<'info> {
     #[account(
-        seeds = [b"config"],
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
         bump = config.bump,
-        has_one = across_handler @ SuperSwapError::InvalidAcrossHandler,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
         has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
     )]
     pub config: Account<'info, Config>,
@@ -20,7 +34,8 @@ pub struct ProcessBridgeAndSwap<'info> {
         payer = payer,
         space = SwapOrder::LEN,
         seeds = [
-            b"swap_order",
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
             params.order_id.to_le_bytes().as_ref()
         ],
         bump
@@ -30,8 +45,8 @@ pub struct ProcessBridgeAndSwap<'info> {
     /// Across handler that triggers the swap (Across program account)
     pub across_handler: Signer<'info>,
 
-    /// CHECK: Recipient address validated in instruction
-    #[account(constraint = params.recipient != Pubkey::default() @ SuperSwapError::InvalidRecipient)]
+    /// CHECK: Recipient address validated in instruction; receives the gas top-up lamports
+    #[account(mut, constraint = params.recipient != Pubkey::default() @ SuperSwapError::InvalidRecipient)]
     pub recipient: UncheckedAccount<'info>,
 
     /// USDC mint
@@ -44,95 +59,817 @@ pub struct ProcessBridgeAndSwap<'info> {
     )]
     pub source_usdc_account: Account<'info, TokenAccount>,
 
-    /// Program's USDC token account
+    /// CHECK: Program's USDC token account, created from the rent vault if missing
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = config
+        mut,
+        address = get_associated_token_address(&config.key(), &usdc_mint.key()) @ SuperSwapError::UsdcTokenAccountNotFound
+    )]
+    pub program_usdc_account: UncheckedAccount<'info>,
+
+    /// Destination token mint (the token user wants to receive), or the configured
+    /// fallback mint when the order's mint has been disabled; SPL Token or Token-2022
+    #[account(
+        constraint = *destination_mint.to_account_info().owner
+            == destination_token_program.as_ref().map_or(token_program.key(), |program| program.key())
+            @ SuperSwapError::InvalidTokenMint
     )]
-    pub program_usdc_account: Account<'info, TokenAccount>,
+    pub destination_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// CHECK: Operator switch for the order's destination mint, absent while the mint has no entry
+    #[account(seeds = [DESTINATION_MINT_SEED, config.instance_seed().as_ref(), params.destination_mint.as_ref()], bump)]
+    pub destination_mint_entry: UncheckedAccount<'info>,
 
-    /// Destination token mint (the token user wants to receive)
-    pub destination_mint: Account<'info, Mint>,
+    /// CHECK: Admin overrides for the order's destination mint, absent while the mint has none
+    #[account(seeds = [MINT_CONFIG_SEED, config.instance_seed().as_ref(), params.destination_mint.as_ref()], bump)]
+    pub mint_config: UncheckedAccount<'info>,
 
-    /// Recipient's destination token account
+    /// CHECK: Recipient's destination token account (the ATA or the preferred account),
+    /// the ATA is created from the rent vault if missing
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = destination_mint,
-        associated_token::authority = recipient
+        mut,
+        constraint = recipient_destination_account.key() == get_associated_token_address_with_program_id(
+                &recipient.key(),
+                &destination_mint.key(),
+                destination_mint.to_account_info().owner,
+            )
+            || recipient_preferences.as_ref().is_some_and(|preferences| {
+                preferences.is_preferred_destination(&recipient_destination_account.key())
+            }) @ SuperSwapError::DestinationTokenAccountNotFound
     )]
-    pub recipient_destination_account: Account<'info, TokenAccount>,
+    pub recipient_destination_account: UncheckedAccount<'info>,
 
-    /// Recipient's USDC account (for refunds)
+    /// CHECK: Recipient's USDC account for refunds (the ATA or the preferred refund address),
+    /// the ATA is created from the rent vault if missing
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = recipient
+        mut,
+        constraint = recipient_usdc_account.key() == get_associated_token_address(&recipient.key(), &usdc_mint.key())
+            || recipient_preferences.as_ref().is_some_and(|preferences| {
+                preferences.is_refund_address(&recipient_usdc_account.key())
+            }) @ SuperSwapError::UsdcTokenAccountNotFound
     )]
-    pub recipient_usdc_account: Account<'info, TokenAccount>,
+    pub recipient_usdc_account: UncheckedAccount<'info>,
 
-    /// Fee recipient's USDC account
+    /// CHECK: Fee recipient's USDC account, created from the rent vault if missing
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = config.fee_recipient
+        mut,
+        address = get_associated_token_address(&fee_recipient.key(), &usdc_mint.key()) @ SuperSwapError::UsdcTokenAccountNotFound
     )]
-    pub fee_recipient_account: Account<'info, TokenAccount>,
+    pub fee_recipient_account: UncheckedAccount<'info>,
+
+    /// CHECK: Fee recipient (validated against config)
+    #[account(address = config.fee_recipient @ SuperSwapError::Unauthorized)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Insurance fund's USDC account, checked by address when a fee share is paid
+    #[account(mut)]
+    pub insurance_usdc_account: UncheckedAccount<'info>,
 
     /// CHECK: Jupiter program (validated against config)
     #[account(constraint = jupiter_program.key() == config.jupiter_program @ SuperSwapError::InvalidJupiterProgram)]
     pub jupiter_program: UncheckedAccount<'info>,
 
+    /// Rent vault that covers order and ATA creation
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    /// CHECK: Post-swap hook program, matched against the hook params and allowlist entry
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Allowlist entry for the post-swap hook program, from this instance's allowlist
+    #[account(
+        seeds = [HOOK_PROGRAM_SEED, config.instance_seed().as_ref(), hook_program_entry.program_id.as_ref()],
+        bump = hook_program_entry.bump,
+    )]
+    pub hook_program_entry: Option<Account<'info, HookProgram>>,
+
+    /// Allowlist entry for the donation recipient, matched against the donation params
+    #[account(
+        seeds = [DONATION_RECIPIENT_SEED, config.instance_seed().as_ref(), donation_recipient_entry.recipient.as_ref()],
+        bump = donation_recipient_entry.bump,
+    )]
+    pub donation_recipient_entry: Option<Account<'info, DonationRecipient>>,
+
+    /// Donation recipient's USDC account
+    #[account(
+        mut,
+        constraint = donation_usdc_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub donation_usdc_account: Option<Account<'info, TokenAccount>>,
+
+    /// Recipient's delivery preferences, when registered
+    #[account(
+        seeds = [RECIPIENT_PREFERENCES_SEED, recipient.key().as_ref()],
+        bump = recipient_preferences.bump
+    )]
+    pub recipient_preferences: Option<Account<'info, RecipientPreferences>>,
+
+    /// CHECK: Per-order escrow PDA that owns WSOL while it is unwrapped for the recipient
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub gas_escrow: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Escrow's WSOL account receiving the gas top-up or auto-unwrapped output, validated in instruction
+    #[account(mut)]
+    pub gas_wsol_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Program owning a program-owned recipient; when passed, its delivery
+    /// callback is invoked after the output is delivered
+    #[account(
+        executable,
+        constraint = *recipient.owner == recipient_program.key() @ SuperSwapError::InvalidRecipient
+    )]
+    pub recipient_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA signing delivery callbacks so recipient programs can authenticate them
+    #[account(seeds = [CALLBACK_AUTHORITY_SEED], bump)]
+    pub callback_authority: Option<UncheckedAccount<'info>>,
+
+    /// Native SOL mint, required with a gas top-up or auto-unwrap
+    #[account(address = spl_token::native_mint::ID @ SuperSwapError::InvalidTokenMint)]
+    pub native_mint: Option<Account<'info, Mint>>,
+
+    /// Payer's reimbursement ledger, required when the payer is not the Across handler
+    #[account(
+        mut,
+        seeds = [PAYER_LEDGER_SEED, config.instance_seed().as_ref(), payer.key().as_ref()],
+        bump = payer_ledger.bump
+    )]
+    pub payer_ledger: Option<Account<'info, PayerLedger>>,
+
+    /// Fronts the order account rent, reimbursed from the rent vault
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// Route staged by the payer when it does not fit in the params; closed to the payer
+    #[account(
+        mut,
+        seeds = [
+            ROUTE_STAGING_SEED,
+            config.instance_seed().as_ref(),
+            payer.key().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = route_staging.bump,
+        close = payer,
+    )]
+    pub route_staging: Option<Box<Account<'info, RouteStaging>>>,
+
+    /// Registered template for the USDC -> delivered mint pair, which the route must then match
+    #[account(
+        seeds = [
+            ROUTE_TEMPLATE_SEED,
+            config.instance_seed().as_ref(),
+            usdc_mint.key().as_ref(),
+            destination_mint.key().as_ref()
+        ],
+        bump = route_template.bump,
+    )]
+    pub route_template: Option<Box<Account<'info, RouteTemplate>>>,
+
+    /// Payer's USDC account receiving the order's relayer tip
+    #[account(
+        mut,
+        constraint = relayer_usdc_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+        constraint = relayer_usdc_account.owner == payer.key() @ SuperSwapError::InvalidRelayerTip,
+    )]
+    pub relayer_usdc_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Config's account for the delivered mint; the route delivers here and `surplus_share_bps`
+    /// of the output above the quote goes where `surplus_policy` says. Required for swapped
+    /// fills while the config captures surplus, and refused otherwise
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &config.key(),
+            &destination_mint.key(),
+            destination_mint.to_account_info().owner,
+        ) @ SuperSwapError::InvalidTokenMint
+    )]
+    pub surplus_vault: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    /// Payer's account for the delivered mint, receiving the surplus share under `SurplusPolicy::PayerRebate`
+    #[account(
+        mut,
+        constraint = payer_surplus_account.mint == destination_mint.key() @ SuperSwapError::InvalidTokenMint,
+        constraint = payer_surplus_account.owner == payer.key() @ SuperSwapError::InvalidRecipient,
+    )]
+    pub payer_surplus_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    /// Fee recipient's account for the delivered mint, receiving the surplus share under `SurplusPolicy::Treasury`
+    #[account(
+        mut,
+        constraint = fee_recipient_surplus_account.mint == destination_mint.key() @ SuperSwapError::InvalidTokenMint,
+        constraint = fee_recipient_surplus_account.owner == config.fee_recipient @ SuperSwapError::InvalidRecipient,
+    )]
+    pub fee_recipient_surplus_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    /// Token-2022 program, required when the delivered mint belongs to it
+    pub destination_token_program: Option<Program<'info, Token2022>>,
+
+    /// Config-wide daily fill volume, required while `daily_volume_cap` is set
+    #[account(
+        mut,
+        seeds = [VOLUME_TRACKER_SEED, config.instance_seed().as_ref(), Pubkey::default().as_ref()],
+        bump = global_volume_tracker.bump,
+    )]
+    pub global_volume_tracker: Option<Box<Account<'info, VolumeTracker>>>,
+
+    /// Daily fill volume into the order's destination mint, required while its mint config caps it
+    #[account(
+        mut,
+        seeds = [VOLUME_TRACKER_SEED, config.instance_seed().as_ref(), params.destination_mint.as_ref()],
+        bump = mint_volume_tracker.bump,
+    )]
+    pub mint_volume_tracker: Option<Box<Account<'info, VolumeTracker>>>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    // Note: remaining_accounts starts with the gas top-up route accounts
+    // (gas_top_up.jupiter_accounts_len of them) and ends with the post-swap
+    // hook accounts (post_swap_hook.accounts_len of them) when those are set
 }
 
-pub fn handler(ctx: Context<ProcessBridgeAndSwap>, params: ProcessBridgeAndSwapParams) -> Result<()> {
-    let config = &ctx.accounts.config;
+impl<'info> ProcessBridgeAndSwap<'info> {
+    /// Covers rent for the order account and any missing ATAs from the rent vault,
+    /// returning the account that funded the order's rent
+    ///
+    /// Accounts taken from the recipient's preferences instead of the ATA must
+    /// already exist; with `unwrap_sol` the output lands in the escrow's WSOL account.
+    /// Dust delivered as USDC has no output, so its destination account is left alone.
+    fn subsidize_account_creation(&mut self, unwrap_sol: bool, deliver_as_usdc: bool) -> Result<Pubkey> {
+        let payer = self.payer.to_account_info();
+        let order_rent = self.swap_order.get_lamports();
+        let rent_payer = fund_order_rent(&mut self.rent_vault, &payer, order_rent)?;
 
-    // Check if program is paused
-    require!(!config.is_paused, SuperSwapError::ProgramPaused);
+        let config = self.config.to_account_info();
+        let recipient = self.recipient.to_account_info();
+        let fee_recipient = self.fee_recipient.to_account_info();
+        let usdc_mint = self.usdc_mint.to_account_info();
+        let destination_mint = self.destination_mint.to_account_info();
+        let token_program = self.token_program.to_account_info();
+        let destination_token_program = self.destination_token_program();
+        let (escrow, escrow_wsol_account, _) = if unwrap_sol {
+            self.escrow_wsol_accounts()?
+        } else {
+            let recipient_destination_account = self.recipient_destination_account.to_account_info();
+            (recipient.clone(), recipient_destination_account, destination_mint.clone())
+        };
+        let mut atas = vec![
+            (self.program_usdc_account.to_account_info(), &config, &usdc_mint, &token_program),
+            (self.recipient_usdc_account.to_account_info(), &recipient, &usdc_mint, &token_program),
+            (self.fee_recipient_account.to_account_info(), &fee_recipient, &usdc_mint, &token_program),
+        ];
+        if !deliver_as_usdc {
+            atas.push((escrow_wsol_account, &escrow, &destination_mint, &destination_token_program));
+        }
 
-    // Validate deadline
-    let current_time = Clock::get()?.unix_timestamp;
-    require!(current_time <= params.deadline, SuperSwapError::DeadlineExceeded);
+        for (ata, authority, mint, mint_token_program) in atas.iter() {
+            if ata.key() != get_associated_token_address_with_program_id(&authority.key(), &mint.key(), &mint_token_program.key()) {
+                require!(
+                    read_token_account(ata)?.mint == mint.key(),
+                    SuperSwapError::InvalidTokenMint
+                );
+                continue;
+            }
+
+            create_ata_if_needed(
+                &mut self.rent_vault,
+                &payer,
+                ata,
+                authority,
+                mint,
+                &self.system_program.to_account_info(),
+                mint_token_program,
+                &self.associated_token_program.to_account_info(),
+            )?;
+        }
+
+        Ok(rent_payer)
+    }
+
+    /// Token program owning the delivered mint
+    fn destination_token_program(&self) -> AccountInfo<'info> {
+        match self.destination_token_program.as_ref() {
+            Some(token_2022_program) => token_2022_program.to_account_info(),
+            None => self.token_program.to_account_info(),
+        }
+    }
+
+    /// Forwards the route's output from the surplus vault to the recipient, splitting off
+    /// the surplus share of whatever exceeds the quote as the config's policy says; returns
+    /// the amount the recipient received, net of any Token-2022 transfer fee, and the surplus
+    fn capture_surplus(&self, order_id: u64, swap_data: &[u8], vault_before: u64) -> Result<(u64, u64)> {
+        let Some(surplus_vault) = self.surplus_vault.as_ref() else {
+            return err!(SuperSwapError::InvalidInstructionData);
+        };
+        let output = read_token_account(&surplus_vault.to_account_info())?
+            .amount
+            .checked_sub(vault_before)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        let quoted_output = route_quoted_out_amount(swap_data);
+        let policy = self.config.surplus_policy;
+        let captured = match policy {
+            SurplusPolicy::Recipient => 0,
+            SurplusPolicy::Treasury | SurplusPolicy::PayerRebate => {
+                captured_surplus(output, quoted_output, self.config.surplus_share_bps)?
+            }
+        };
+        let forwarded = output - captured;
+
+        let instance_seed = self.config.instance_seed();
+        let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[self.config.bump]];
+        let signer = &[&seeds[..]];
+        let recipient_destination = self.recipient_destination_account.to_account_info();
+        let received_before = read_token_account(&recipient_destination)?.amount;
+        let forward_ctx = CpiContext::new_with_signer(
+            self.destination_token_program(),
+            TransferChecked {
+                from: surplus_vault.to_account_info(),
+                mint: self.destination_mint.to_account_info(),
+                to: recipient_destination.clone(),
+                authority: self.config.to_account_info(),
+            },
+            signer,
+        );
+        token_interface::transfer_checked(forward_ctx, forwarded, self.destination_mint.decimals)?;
+        let received = read_token_account(&recipient_destination)?
+            .amount
+            .checked_sub(received_before)
+            .ok_or(SuperSwapError::MathOverflow)?;
+
+        if captured > 0 {
+            // The share leaves the vault at once, as fees or as the payer's rebate
+            let share_account = match policy {
+                SurplusPolicy::Treasury => self
+                    .fee_recipient_surplus_account
+                    .as_ref()
+                    .ok_or(SuperSwapError::MissingSurplusFeeAccount)?,
+                _ => self
+                    .payer_surplus_account
+                    .as_ref()
+                    .ok_or(SuperSwapError::MissingSurplusRebateAccount)?,
+            };
+            let share_ctx = CpiContext::new_with_signer(
+                self.destination_token_program(),
+                TransferChecked {
+                    from: surplus_vault.to_account_info(),
+                    mint: self.destination_mint.to_account_info(),
+                    to: share_account.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer,
+            );
+            token_interface::transfer_checked(share_ctx, captured, self.destination_mint.decimals)?;
+
+            emit!(SurplusCaptured {
+                order_id,
+                mint: self.destination_mint.key(),
+                quoted_output: quoted_output.unwrap_or(0),
+                output,
+                captured,
+                policy,
+            });
+            msg!("Captured {} of {} above the quote ({:?})", captured, output, policy);
+        }
+
+        Ok((received, realized_surplus(output, quoted_output)))
+    }
+
+    /// Transfers an order's net USDC from the program's vault to the recipient's
+    /// destination account, for orders whose destination mint is USDC itself
+    fn pass_usdc_through(&self, amount: u64) -> Result<()> {
+        let instance_seed = self.config.instance_seed();
+        let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[self.config.bump]];
+        let signer = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.program_usdc_account.to_account_info(),
+                to: self.recipient_destination_account.to_account_info(),
+                authority: self.config.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)
+    }
+
+    /// Sends `lamports` from the payer to a recipient that holds no SOL yet
+    ///
+    /// Recipients already holding SOL, or about to receive their output as
+    /// native SOL, are skipped.
+    fn drop_gas(&self, order_id: u64, lamports: u64, unwrap_sol: bool) -> Result<()> {
+        let max_gas_drop_lamports = self.config.max_gas_drop_lamports;
+        require!(
+            max_gas_drop_lamports > 0
+                && lamports <= max_gas_drop_lamports
+                && lamports >= Rent::get()?.minimum_balance(0),
+            SuperSwapError::InvalidGasDrop
+        );
+        if unwrap_sol || self.recipient.lamports() > 0 {
+            msg!("Recipient already has SOL, gas drop skipped");
+            return Ok(());
+        }
+
+        let drop_ctx = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.payer.to_account_info(),
+                to: self.recipient.to_account_info(),
+            },
+        );
+        system_program::transfer(drop_ctx, lamports)?;
+
+        emit!(GasDropped {
+            order_id,
+            recipient: self.recipient.key(),
+            payer: self.payer.key(),
+            lamports,
+        });
+        msg!("Gas drop: {} lamports", lamports);
+
+        Ok(())
+    }
+
+    /// Escrow PDA, its WSOL account and the native mint, used to deliver native SOL
+    fn escrow_wsol_accounts(&self) -> Result<(AccountInfo<'info>, AccountInfo<'info>, AccountInfo<'info>)> {
+        let (Some(escrow), Some(wsol_account), Some(native_mint)) = (
+            self.gas_escrow.as_ref(),
+            self.gas_wsol_account.as_ref(),
+            self.native_mint.as_ref(),
+        ) else {
+            return err!(SuperSwapError::InvalidInstructionData);
+        };
+        let escrow = escrow.to_account_info();
+        let wsol_account = wsol_account.to_account_info();
+        let native_mint = native_mint.to_account_info();
+        require!(
+            self.recipient.key() != escrow.key()
+                && wsol_account.key() == get_associated_token_address(&escrow.key(), &native_mint.key()),
+            SuperSwapError::InvalidRecipient
+        );
+
+        Ok((escrow, wsol_account, native_mint))
+    }
+
+    /// Swaps part of the program's USDC to WSOL and delivers it to the recipient as native SOL
+    ///
+    /// Returns the USDC the route spent and the lamports delivered.
+    fn top_up_gas(
+        &mut self,
+        top_up: &GasTopUp,
+        route_accounts: &[AccountInfo<'info>],
+        escrow_bump: Option<u8>,
+    ) -> Result<(u64, u64)> {
+        let (escrow, wsol_account, native_mint) = self.escrow_wsol_accounts()?;
+
+        // The config PDA signs this route too, so it gets the main route's checks: it may
+        // spend no more than the top-up from the program's vault, and must deliver into
+        // the escrow's WSOL account where the output is measured
+        let route = parse_jupiter_swap_data(&top_up.jupiter_swap_data)?;
+        require!(
+            route.amount_in <= top_up.usdc_amount && route.minimum_amount_out >= top_up.min_lamports_out,
+            SuperSwapError::InvalidSwapCalldata
+        );
+        require_route_token_accounts(
+            &route,
+            &self.jupiter_program.key(),
+            route_accounts,
+            &self.program_usdc_account.key(),
+            &wsol_account.key(),
+        )?;
+
+        create_ata_if_needed(
+            &mut self.rent_vault,
+            &self.payer.to_account_info(),
+            &wsol_account,
+            &escrow,
+            &native_mint,
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            &self.associated_token_program.to_account_info(),
+        )?;
+
+        // Wrapped SOL moves lamports with the tokens, so the balance delta is the output
+        let program_usdc = self.program_usdc_account.to_account_info();
+        let usdc_before = read_token_account(&program_usdc)?.amount;
+        let lamports_before = wsol_account.lamports();
+
+        let instance_seed = self.config.instance_seed();
+        let config_seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[self.config.bump]];
+        execute_jupiter_swap(
+            &self.jupiter_program.to_account_info(),
+            &top_up.jupiter_swap_data,
+            route_accounts,
+            &[&config_seeds[..]],
+        )?;
+
+        let lamports = wsol_account
+            .lamports()
+            .checked_sub(lamports_before)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        let usdc_spent = usdc_before
+            .checked_sub(read_token_account(&program_usdc)?.amount)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        require!(usdc_spent <= top_up.usdc_amount, SuperSwapError::InvalidSwapCalldata);
+        validate_swap_output(lamports, top_up.min_lamports_out)?;
+
+        self.deliver_unwrapped_sol(escrow, wsol_account, lamports, escrow_bump)?;
+
+        Ok((usdc_spent, lamports))
+    }
+
+    /// Unwraps the escrow's WSOL account, pays `lamports` to the recipient and
+    /// returns the account rent to the rent vault
+    fn deliver_unwrapped_sol(
+        &self,
+        escrow: AccountInfo<'info>,
+        wsol_account: AccountInfo<'info>,
+        lamports: u64,
+        escrow_bump: Option<u8>,
+    ) -> Result<()> {
+        let escrow_bump = escrow_bump.ok_or(SuperSwapError::InvalidInstructionData)?;
+        let swap_order_key = self.swap_order.key();
+        let escrow_seeds = &[ESCROW_SEED, swap_order_key.as_ref(), &[escrow_bump]];
+        let signer = &[&escrow_seeds[..]];
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: wsol_account,
+                destination: escrow.clone(),
+                authority: escrow.clone(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)?;
+
+        let deliver_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: escrow.clone(),
+                to: self.recipient.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(deliver_ctx, lamports)?;
+
+        let leftover = escrow.lamports();
+        if leftover > 0 {
+            let repay_ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: escrow,
+                    to: self.rent_vault.to_account_info(),
+                },
+                signer,
+            );
+            system_program::transfer(repay_ctx, leftover)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Settles a fill without swapping: the whole bridged amount, fee included, goes
+/// to the recipient's USDC account and the order is recorded as Refunded
+fn refund_bridged_usdc<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessBridgeAndSwap<'info>>,
+    params: &ProcessBridgeAndSwapParams,
+    current_time: i64,
+) -> Result<FillResult> {
+    let accounts = ctx.accounts;
+    let rent_payer = accounts.subsidize_account_creation(false, true)?;
+
+    let swap_order = &mut accounts.swap_order;
+    swap_order.order_id = params.order_id;
+    swap_order.recipient = params.recipient;
+    swap_order.usdc_amount = params.usdc_amount;
+    swap_order.min_output_amount = params.min_output_amount;
+    swap_order.destination_mint = params.destination_mint;
+    swap_order.deadline = params.deadline;
+    swap_order.created_at = current_time;
+    swap_order.status = OrderStatus::Refunded;
+    swap_order.bump = ctx.bumps.swap_order;
+    swap_order.rent_payer = rent_payer;
+    swap_order.surplus = 0;
+
+    let refund_ctx = CpiContext::new(
+        accounts.token_program.to_account_info(),
+        Transfer {
+            from: accounts.source_usdc_account.to_account_info(),
+            to: accounts.recipient_usdc_account.to_account_info(),
+            authority: accounts.across_handler.to_account_info(),
+        },
+    );
+    token::transfer(refund_ctx, params.usdc_amount)?;
+
+    Ok(FillResult {
+        order_id: params.order_id,
+        fee_amount: 0,
+        swap_amount: 0,
+        relayer_tip: 0,
+        amount_delivered: params.usdc_amount,
+    })
+}
+
+/// Adds a fill to `volume_tracker`, which must be passed while `cap` is set
+fn record_daily_volume(
+    volume_tracker: Option<&mut Account<VolumeTracker>>,
+    usdc_amount: u64,
+    now: i64,
+    cap: u64,
+) -> Result<()> {
+    match volume_tracker {
+        Some(volume_tracker) => volume_tracker.record(usdc_amount, now, cap),
+        None => {
+            require!(cap == 0, SuperSwapError::MissingVolumeTracker);
+            Ok(())
+        }
+    }
+}
+
+/// Reads an SPL token or Token-2022 account, extensions included
+fn read_token_account(account: &AccountInfo) -> Result<token_interface::TokenAccount> {
+    let data = account.try_borrow_data()?;
+    token_interface::TokenAccount::try_deserialize(&mut &data[..])
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessBridgeAndSwap<'info>>,
+    params: ProcessBridgeAndSwapParams,
+) -> Result<FillResult> {
+    let config = &ctx.accounts.config;
+
+    // Check if program is paused; refunds-only mode is checked past the refund paths below
+    config.require_not_paused()?;
+    require!(!config.across_paused, SuperSwapError::BridgePaused);
+    require!(config.is_adapter_enabled(Config::ADAPTER_ACROSS), SuperSwapError::AdapterDisabled);
 
     // Validate amounts
     require!(params.usdc_amount > 0, SuperSwapError::InvalidBridgeAmount);
 
+    // An off-curve recipient has no private key, so it must be flagged by the order
+    // unless it is the order's own escrow or a program-owned recipient checked above
+    if !params.allow_off_curve_recipient
+        && ctx.accounts.recipient_program.is_none()
+        && !is_on_curve(&params.recipient)
+    {
+        let (escrow, _) = Pubkey::find_program_address(
+            &[ESCROW_SEED, ctx.accounts.swap_order.key().as_ref()],
+            &crate::ID,
+        );
+        require!(params.recipient == escrow, SuperSwapError::OffCurveRecipient);
+    }
+
+    // Anyone but the Across handler paying for the transaction must be a registered operator
+    record_payer_expense(
+        config,
+        &ctx.accounts.payer.key(),
+        ctx.accounts.payer_ledger.as_mut(),
+        params.order_id,
+    )?;
+
+    // Rejecting a late fill would strand the bridged USDC with the handler, so it
+    // is refunded to the recipient in full instead
+    let current_time = Clock::get()?.unix_timestamp;
+    if current_time > params.deadline {
+        let result = refund_bridged_usdc(ctx, &params, current_time)?;
+        emit!(ExpiredOrderRefunded {
+            order_id: params.order_id,
+            recipient: params.recipient,
+            deadline: params.deadline,
+            usdc_amount: params.usdc_amount,
+        });
+        msg!("Order {} filled after its deadline, refunded {} USDC", params.order_id, params.usdc_amount);
+        return Ok(result);
+    }
+
+    // A failing Jupiter CPI or a short output reverts the whole fill, USDC included,
+    // and leaves nothing on-chain to refund from. The handler must then send a second
+    // transaction, this refund fill, which pays the bridged USDC straight out of its
+    // own account
+    if params.refund_failed_swap {
+        let result = refund_bridged_usdc(ctx, &params, current_time)?;
+        emit!(FailedSwapRefunded {
+            order_id: params.order_id,
+            recipient: params.recipient,
+            usdc_amount: params.usdc_amount,
+        });
+        msg!("Order {} swap failed, refunded {} USDC", params.order_id, params.usdc_amount);
+        return Ok(result);
+    }
+
+    // Refunds-only mode still lets the refunds above through, but takes no new swaps
+    config.require_accepting_fills()?;
+
+    // A disabled destination mint takes no fills until an operator re-enables it,
+    // unless the config names a fallback asset to deliver instead
+    let mint_config = load_mint_config(&ctx.accounts.mint_config.to_account_info())?;
+    let requested_mint_disabled = is_mint_disabled(&ctx.accounts.destination_mint_entry.to_account_info())?
+        || mint_config.as_ref().is_some_and(|mint_config| !mint_config.enabled);
+    let delivered_mint = ctx.accounts.destination_mint.key();
+    let substituted = delivered_mint != params.destination_mint;
+    if substituted {
+        require!(
+            config.fallback_output_mint != Pubkey::default()
+                && delivered_mint == config.fallback_output_mint
+                && requested_mint_disabled,
+            SuperSwapError::InvalidFallbackOutput
+        );
+    } else {
+        require!(!requested_mint_disabled, SuperSwapError::DestinationMintDisabled);
+    }
+
+    // The requested mint's overrides only govern fills delivering it. Order size caps are
+    // checked after the refund paths above so an oversized order can still be refunded
+    let mint_config = mint_config.filter(|_| !substituted);
+    require!(
+        !config.exceeds_max_order(params.usdc_amount)
+            && !mint_config.as_ref().is_some_and(|mint_config| mint_config.exceeds_max_order(params.usdc_amount)),
+        SuperSwapError::OrderAboveMaximum
+    );
+    let max_price_impact_bps = config.max_price_impact_bps_with(
+        mint_config.as_ref().map_or(0, |mint_config| mint_config.max_slippage_bps),
+    );
+
+    // Daily volume caps count the bridged USDC of every order that goes on to be delivered
+    record_daily_volume(
+        ctx.accounts.global_volume_tracker.as_deref_mut(),
+        params.usdc_amount,
+        current_time,
+        config.daily_volume_cap,
+    )?;
+    if !substituted {
+        record_daily_volume(
+            ctx.accounts.mint_volume_tracker.as_deref_mut(),
+            params.usdc_amount,
+            current_time,
+            mint_config.as_ref().map_or(0, |mint_config| mint_config.daily_volume_cap),
+        )?;
+    }
+
+    // Dust costs more in rent and compute than its swap delivers
+    let is_dust = config.is_dust_order(params.usdc_amount);
+    require!(
+        !is_dust || config.deliver_dust_as_usdc,
+        SuperSwapError::OrderBelowMinimum
+    );
+    let deliver_as_usdc = is_dust || (substituted && delivered_mint == config.usdc_mint);
+
+    // WSOL output is delivered as native SOL, since most bridged recipients hold no SOL
+    // to use a WSOL account with; registered preferences may opt out, and decide
+    // whether a gas top-up may run
+    let preferences = ctx.accounts.recipient_preferences.as_deref();
+    let unwrap_sol = !deliver_as_usdc
+        && delivered_mint == spl_token::native_mint::ID
+        && preferences.map(|preferences| preferences.auto_unwrap_sol).unwrap_or(true);
+    let gas_top_up_allowed = preferences.map(|preferences| preferences.gas_top_up).unwrap_or(true);
+    // Unwrapped output already leaves the recipient SOL for gas
+    let gas_top_up = params.gas_top_up.as_ref().filter(|_| gas_top_up_allowed && !unwrap_sol);
+
+    // Rent for the order account and any missing ATAs comes from the rent vault
+    let rent_payer = ctx.accounts.subsidize_account_creation(unwrap_sol, deliver_as_usdc)?;
+    let config = &ctx.accounts.config;
+
     // Initialize swap order
     let swap_order = &mut ctx.accounts.swap_order;
     swap_order.order_id = params.order_id;
     swap_order.recipient = params.recipient;
     swap_order.usdc_amount = params.usdc_amount;
-    swap_order.min_output_amount = params.min_output_amount;
-    swap_order.destination_mint = params.destination_mint;
+    // A substituted order records the mint actually delivered; the minimum was
+    // quoted in the requested mint, so it no longer applies
+    swap_order.min_output_amount = if substituted { 0 } else { params.min_output_amount };
+    swap_order.destination_mint = delivered_mint;
     swap_order.deadline = params.deadline;
+    swap_order.created_at = current_time;
     swap_order.status = OrderStatus::Pending;
     swap_order.bump = ctx.bumps.swap_order;
+    swap_order.rent_payer = rent_payer;
+    swap_order.surplus = 0;
 
     msg!("Processing swap order: {}", params.order_id);
     msg!("Recipient: {}", params.recipient);
     msg!("USDC Amount: {}", params.usdc_amount);
     msg!("Min Output: {}", params.min_output_amount);
 
-    // Calculate swap fee
-    let fee_amount = (params.usdc_amount as u128)
-        .checked_mul(config.fee_bps as u128)
-        .ok_or(SuperSwapError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SuperSwapError::MathOverflow)? as u64;
+    // Calculate swap fee, at the mint's rate when it overrides the config's
+    let fee_bps = mint_config
+        .as_ref()
+        .and_then(|mint_config| mint_config.fee_bps)
+        .unwrap_or(config.fee_bps);
+    let fee_amount = calculate_fee(params.usdc_amount, fee_bps)?;
 
-    let swap_amount = params.usdc_amount
+    let mut swap_amount = params.usdc_amount
         .checked_sub(fee_amount)
         .ok_or(SuperSwapError::MathOverflow)?;
 
@@ -150,64 +887,386 @@ pub fn handler(ctx: Context<ProcessBridgeAndSwap>, params: ProcessBridgeAndSwapP
     );
     token::transfer(transfer_ctx, params.usdc_amount)?;
 
-    // Transfer fee to fee recipient if fee > 0
+    // Transfer fee to fee recipient and insurance fund if fee > 0
     if fee_amount > 0 {
-        let config_key = config.key();
-        let seeds = &[b"config".as_ref(), &[config.bump]];
+        let instance_seed = config.instance_seed();
+        let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+        let signer = &[&seeds[..]];
+
+        pay_protocol_fee(
+            config,
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.program_usdc_account.to_account_info(),
+            &config.to_account_info(),
+            &ctx.accounts.fee_recipient_account.to_account_info(),
+            &ctx.accounts.insurance_usdc_account.to_account_info(),
+            fee_amount,
+            signer,
+        )?;
+    }
+
+    // Pay the relayer the tip the user attached for faster execution
+    let relayer_tip = params.relayer_tip;
+    if relayer_tip > 0 {
+        let max_relayer_tip_usdc = ctx.accounts.config.max_relayer_tip_usdc;
+        let Some(relayer_usdc_account) = ctx.accounts.relayer_usdc_account.as_ref() else {
+            return err!(SuperSwapError::InvalidRelayerTip);
+        };
+        require!(
+            relayer_tip <= max_relayer_tip_usdc && relayer_tip < swap_amount,
+            SuperSwapError::InvalidRelayerTip
+        );
+
+        let config = &ctx.accounts.config;
+        let instance_seed = config.instance_seed();
+        let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
         let signer = &[&seeds[..]];
 
-        let fee_transfer_ctx = CpiContext::new_with_signer(
+        let tip_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.program_usdc_account.to_account_info(),
-                to: ctx.accounts.fee_recipient_account.to_account_info(),
+                to: relayer_usdc_account.to_account_info(),
                 authority: config.to_account_info(),
             },
             signer,
         );
-        token::transfer(fee_transfer_ctx, fee_amount)?;
-    }
-
-    // Execute Jupiter swap
-    // Note: The actual Jupiter swap execution will be done via CPI
-    // The jupiter_swap_data contains the serialized instruction data
-    // This is a complex operation that requires deserializing Jupiter instructions
-    // and executing them via CPI
-    
-    // For now, we'll add a placeholder that needs to be implemented
-    // based on Jupiter's exact CPI interface
-    msg!("Executing Jupiter swap with {} USDC", swap_amount);
-    msg!("Jupiter swap data length: {}", params.jupiter_swap_data.len());
-
-    // TODO: Implement actual Jupiter CPI call
-    // This will involve:
-    // 1. Deserializing the Jupiter swap instruction
-    // 2. Building the accounts vector from the instruction
-    // 3. Executing the CPI call
-    // 4. Verifying the output amount meets minimum requirements
-    
-    // For now, mark as completed (this should be conditional on successful swap)
-    swap_order.status = OrderStatus::Completed;
+        token::transfer(tip_ctx, relayer_tip)?;
+        swap_amount = swap_amount
+            .checked_sub(relayer_tip)
+            .ok_or(SuperSwapError::MathOverflow)?;
 
-    msg!("Swap order {} processed successfully", params.order_id);
+        emit!(RelayerTipped {
+            order_id: params.order_id,
+            relayer: ctx.accounts.payer.key(),
+            usdc_amount: relayer_tip,
+        });
+        msg!("Relayer Tip: {}", relayer_tip);
+    }
 
-    Ok(())
-}
+    // A recipient without SOL gets a gas drop funded by the payer, typically out of the relayer tip
+    if params.gas_drop_lamports > 0 {
+        ctx.accounts.drop_gas(params.order_id, params.gas_drop_lamports, unwrap_sol)?;
+    }
 
-// Helper function to execute Jupiter swap (to be implemented)
-fn execute_jupiter_swap_cpi(
-    jupiter_program: AccountInfo,
-    swap_data: &[u8],
-    accounts: Vec<AccountInfo>,
-    config: &Account<Config>,
-    config_bump: u8,
-) -> Result<u64> {
-    // This function will:
-    // 1. Deserialize Jupiter instruction data
-    // 2. Execute CPI to Jupiter
-    // 3. Return the output amount
-    
-    msg!("Jupiter swap CPI execution (placeholder)");
-    Ok(0) // Placeholder return
-}
+    // Dust skips the swap: the recipient gets the USDC left after the fee
+    if deliver_as_usdc {
+        let config = &ctx.accounts.config;
+        let instance_seed = config.instance_seed();
+        let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+        let signer = &[&seeds[..]];
+
+        let deliver_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.program_usdc_account.to_account_info(),
+                to: ctx.accounts.recipient_usdc_account.to_account_info(),
+                authority: config.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(deliver_ctx, swap_amount)?;
+        ctx.accounts.swap_order.status = OrderStatus::Completed;
+
+        if substituted {
+            emit!(FallbackOutputDelivered {
+                order_id: params.order_id,
+                recipient: params.recipient,
+                requested_mint: params.destination_mint,
+                delivered_mint,
+                amount_delivered: swap_amount,
+            });
+            msg!("Delivered order {} as {} USDC in place of its disabled mint", params.order_id, swap_amount);
+        } else {
+            emit!(DustOrderDelivered {
+                order_id: params.order_id,
+                recipient: params.recipient,
+                usdc_amount: swap_amount,
+            });
+            msg!("Delivered dust order {} as {} USDC", params.order_id, swap_amount);
+        }
+
+        return Ok(FillResult {
+            order_id: params.order_id,
+            fee_amount,
+            swap_amount: 0,
+            relayer_tip,
+            amount_delivered: swap_amount,
+        });
+    }
+
+    // Route the order's donation split to its allowlisted recipient before the main swap
+    if let Some(donation) = &params.donation {
+        let (Some(entry), Some(donation_usdc_account)) = (
+            ctx.accounts.donation_recipient_entry.as_ref(),
+            ctx.accounts.donation_usdc_account.as_ref(),
+        ) else {
+            return err!(SuperSwapError::InvalidDonation);
+        };
+        let max_donation_bps = ctx.accounts.config.max_donation_bps;
+        require!(
+            donation.bps > 0
+                && donation.bps <= max_donation_bps
+                && entry.recipient == donation.recipient
+                && donation_usdc_account.owner == donation.recipient,
+            SuperSwapError::InvalidDonation
+        );
+
+        let donation_amount = calculate_fee(swap_amount, donation.bps)?;
+        if donation_amount > 0 {
+            let config = &ctx.accounts.config;
+            let instance_seed = config.instance_seed();
+            let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+            let signer = &[&seeds[..]];
+
+            let donation_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.program_usdc_account.to_account_info(),
+                    to: donation_usdc_account.to_account_info(),
+                    authority: config.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(donation_ctx, donation_amount)?;
+            swap_amount = swap_amount
+                .checked_sub(donation_amount)
+                .ok_or(SuperSwapError::MathOverflow)?;
+        }
+
+        emit!(DonationRouted {
+            order_id: params.order_id,
+            recipient: params.recipient,
+            donation_recipient: donation.recipient,
+            usdc_amount: donation_amount,
+        });
+    }
 
+    // Carve the recipient's gas top-up out of the USDC before the main swap
+    if let Some(top_up) = gas_top_up {
+        let max_gas_top_up_usdc = ctx.accounts.config.max_gas_top_up_usdc;
+        require!(
+            max_gas_top_up_usdc > 0
+                && top_up.usdc_amount <= max_gas_top_up_usdc
+                && top_up.usdc_amount < swap_amount,
+            SuperSwapError::InvalidGasTopUp
+        );
+
+        let route_accounts_len = top_up.jupiter_accounts_len as usize;
+        require!(
+            route_accounts_len <= ctx.remaining_accounts.len(),
+            SuperSwapError::InvalidInstructionData
+        );
+        let route_accounts = &ctx.remaining_accounts[..route_accounts_len];
+
+        let (usdc_spent, lamports) =
+            ctx.accounts
+                .top_up_gas(top_up, route_accounts, ctx.bumps.gas_escrow)?;
+        swap_amount = swap_amount
+            .checked_sub(usdc_spent)
+            .ok_or(SuperSwapError::MathOverflow)?;
+
+        emit!(GasToppedUp {
+            order_id: params.order_id,
+            recipient: params.recipient,
+            usdc_amount: usdc_spent,
+            lamports,
+        });
+    }
+
+    // Output is measured at the delivery account for the recipient's callback
+    let output_before = if unwrap_sol {
+        0
+    } else {
+        read_token_account(&ctx.accounts.recipient_destination_account)?.amount
+    };
+
+    // The config alone decides whether a swap's output is routed through its vault for
+    // the mint to split off the surplus share; the vault is required then and refused otherwise
+    let capture_surplus = !unwrap_sol
+        && delivered_mint != ctx.accounts.config.usdc_mint
+        && ctx.accounts.config.captures_surplus();
+    let surplus_vault_before = match ctx.accounts.surplus_vault.as_ref() {
+        Some(surplus_vault) => {
+            require!(capture_surplus, SuperSwapError::InvalidInstructionData);
+            surplus_vault.amount
+        }
+        None => {
+            require!(!capture_surplus, SuperSwapError::MissingSurplusVault);
+            0
+        }
+    };
+
+    // An order for USDC itself needs no swap: the net USDC goes straight to the recipient
+    let amount_delivered = if delivered_mint == ctx.accounts.config.usdc_mint {
+        ctx.accounts.pass_usdc_through(swap_amount)?;
+        msg!("Delivered {} USDC without a swap", swap_amount);
+        swap_amount
+    } else {
+        let jupiter_swap_data = match ctx.accounts.route_staging.as_ref() {
+            Some(route_staging) => {
+                require!(route_staging.finalized, SuperSwapError::RouteNotFinalized);
+                require!(params.jupiter_swap_data.is_empty(), SuperSwapError::InvalidInstructionData);
+                &route_staging.data
+            }
+            None => &params.jupiter_swap_data,
+        };
+
+        // The swap route sits between the gas top-up route and the hook accounts
+        let top_up_len = params.gas_top_up.as_ref().map_or(0, |top_up| top_up.jupiter_accounts_len as usize);
+        let hook_len = params.post_swap_hook.as_ref().map_or(0, |hook| hook.accounts_len as usize);
+        let route_accounts = ctx
+            .remaining_accounts
+            .get(top_up_len..ctx.remaining_accounts.len().saturating_sub(hook_len))
+            .ok_or(SuperSwapError::InvalidInstructionData)?;
+        if let Some(route_template) = ctx.accounts.route_template.as_ref() {
+            route_template.validate(
+                jupiter_swap_data,
+                route_accounts.iter().map(|account| (account.key(), account.is_writable)),
+            )?;
+            msg!("Route matches the registered template");
+        }
+
+        // The route's own amounts must match the order before the config PDA signs it
+        let route = parse_jupiter_swap_data(jupiter_swap_data)?;
+        validate_route_params(&route, swap_amount, ctx.accounts.swap_order.min_output_amount)?;
+
+        // The route must spend from the program's vault and deliver where the order's output is measured
+        let delivery_account = if unwrap_sol {
+            ctx.accounts.escrow_wsol_accounts()?.1.key()
+        } else if capture_surplus {
+            ctx.accounts
+                .surplus_vault
+                .as_ref()
+                .ok_or(SuperSwapError::MissingSurplusVault)?
+                .key()
+        } else {
+            ctx.accounts.recipient_destination_account.key()
+        };
+        require_route_token_accounts(
+            &route,
+            &ctx.accounts.jupiter_program.key(),
+            route_accounts,
+            &ctx.accounts.program_usdc_account.key(),
+            &delivery_account,
+        )?;
+        msg!("Executing Jupiter swap with {} USDC", swap_amount);
+
+        // The config PDA signs for the program's USDC account as the route's source
+        let program_usdc = ctx.accounts.program_usdc_account.to_account_info();
+        let usdc_before = read_token_account(&program_usdc)?.amount;
+        let config = &ctx.accounts.config;
+        let instance_seed = config.instance_seed();
+        let config_seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+        execute_jupiter_swap(
+            &ctx.accounts.jupiter_program.to_account_info(),
+            jupiter_swap_data,
+            route_accounts,
+            &[&config_seeds[..]],
+        )?;
+
+        // The vault also holds other orders' USDC, so the route may not spend past this order's share
+        let usdc_spent = usdc_before
+            .checked_sub(read_token_account(&program_usdc)?.amount)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        require!(usdc_spent <= swap_amount, SuperSwapError::InvalidSwapCalldata);
+
+        // Deliver WSOL output as native SOL when the recipient asked for it
+        let quoted_output = route_quoted_out_amount(jupiter_swap_data);
+        let (delivered, surplus) = if unwrap_sol {
+            let (escrow, wsol_account, _) = ctx.accounts.escrow_wsol_accounts()?;
+            let lamports = read_token_account(&wsol_account)?.amount;
+            ctx.accounts
+                .deliver_unwrapped_sol(escrow, wsol_account, lamports, ctx.bumps.gas_escrow)?;
+            msg!("Delivered {} lamports unwrapped", lamports);
+            (lamports, realized_surplus(lamports, quoted_output))
+        } else if capture_surplus {
+            ctx.accounts
+                .capture_surplus(params.order_id, jupiter_swap_data, surplus_vault_before)?
+        } else {
+            let delivered = read_token_account(&ctx.accounts.recipient_destination_account)?
+                .amount
+                .saturating_sub(output_before);
+            (delivered, realized_surplus(delivered, quoted_output))
+        };
+        ctx.accounts.swap_order.surplus = surplus;
+
+        // The config's and the mint's bounds hold however loosely the order set its minimum
+        validate_price_impact(delivered, quoted_output, max_price_impact_bps)?;
+        delivered
+    };
+
+    // The order only completes once the measured output meets its minimum
+    validate_swap_output(amount_delivered, ctx.accounts.swap_order.min_output_amount)?;
+    ctx.accounts.swap_order.status = OrderStatus::Completed;
+
+    if substituted {
+        emit!(FallbackOutputDelivered {
+            order_id: params.order_id,
+            recipient: params.recipient,
+            requested_mint: params.destination_mint,
+            delivered_mint,
+            amount_delivered,
+        });
+    }
+
+    // Let a program-owned recipient react to the delivery atomically
+    if let (Some(recipient_program), Some(callback_authority)) = (
+        ctx.accounts.recipient_program.as_ref(),
+        ctx.accounts.callback_authority.as_ref(),
+    ) {
+        let callback_bump = ctx.bumps.callback_authority.ok_or(SuperSwapError::InvalidInstructionData)?;
+        let seeds = &[CALLBACK_AUTHORITY_SEED, &[callback_bump]];
+        let args = DeliveryCallbackArgs {
+            order_id: params.order_id,
+            usdc_amount: params.usdc_amount,
+            destination_mint: delivered_mint,
+            amount_delivered,
+        };
+        notify_delivery(
+            &recipient_program.to_account_info(),
+            &callback_authority.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.recipient_destination_account.to_account_info(),
+            &ctx.accounts.swap_order.to_account_info(),
+            &args,
+            &[&seeds[..]],
+        )?;
+
+        emit!(RecipientNotified {
+            order_id: params.order_id,
+            recipient: params.recipient,
+            recipient_program: recipient_program.key(),
+            amount_delivered,
+        });
+    }
+
+    // Run the integrator's post-swap hook once the output is delivered
+    if let Some(hook) = &params.post_swap_hook {
+        let (Some(hook_program), Some(entry)) = (
+            ctx.accounts.hook_program.as_ref(),
+            ctx.accounts.hook_program_entry.as_ref(),
+        ) else {
+            return err!(SuperSwapError::HookProgramNotAllowed);
+        };
+
+        let hook_accounts_len = hook.accounts_len as usize;
+        require!(
+            hook_accounts_len <= ctx.remaining_accounts.len(),
+            SuperSwapError::InvalidInstructionData
+        );
+        let hook_accounts = &ctx.remaining_accounts[ctx.remaining_accounts.len() - hook_accounts_len..];
+
+        execute_post_swap_hook(hook, entry, &hook_program.to_account_info(), hook_accounts)?;
+    }
+
+    msg!("Swap order {} processed successfully", params.order_id);
+
+    Ok(FillResult {
+        order_id: params.order_id,
+        fee_amount,
+        swap_amount,
+        relayer_tip,
+        amount_delivered,
+    })
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::ConfigUpdateProposed;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_CONFIG_UPDATE_SEED};
+
+#[derive(Accounts)]
+pub struct ProposeConfigUpdate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Proposed change; only one can wait at a time
+    #[account(
+        init,
+        payer = admin,
+        space = PendingConfigUpdate::LEN,
+        seeds = [PENDING_CONFIG_UPDATE_SEED, config.instance_seed().as_ref()],
+        bump
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeConfigUpdate>, params: ProposeConfigUpdateParams) -> Result<()> {
+    require!(
+        params.new_across_handler.is_some()
+            || params.new_jupiter_program.is_some()
+            || params.new_fee_recipient.is_some()
+            || params.new_config_update_delay_seconds.is_some()
+            || params.add_across_handler.is_some(),
+        SuperSwapError::InvalidInstructionData
+    );
+    require!(
+        params.new_config_update_delay_seconds.map(|delay| delay >= 0).unwrap_or(true),
+        SuperSwapError::InvalidConfigUpdateDelay
+    );
+
+    let config = &ctx.accounts.config;
+    if let Some(handler) = params.add_across_handler {
+        config.require_can_add_across_handler(&handler)?;
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let executable_at = now
+        .checked_add(config.config_update_delay_seconds)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    let pending_config_update = &mut ctx.accounts.pending_config_update;
+    pending_config_update.config = config.key();
+    pending_config_update.across_handler = params.new_across_handler;
+    pending_config_update.jupiter_program = params.new_jupiter_program;
+    pending_config_update.fee_recipient = params.new_fee_recipient;
+    pending_config_update.config_update_delay_seconds = params.new_config_update_delay_seconds;
+    pending_config_update.add_across_handler = params.add_across_handler;
+    pending_config_update.executable_at = executable_at;
+    pending_config_update.bump = ctx.bumps.pending_config_update;
+    let pending_config_update_key = pending_config_update.key();
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(ctx.accounts.admin.key(), AdminAction::ProposeConfigUpdate, now, pending_config_update_key)
+            .with_values(0u64, executable_at),
+    );
+
+    emit!(ConfigUpdateProposed {
+        config: config.key(),
+        across_handler: params.new_across_handler,
+        jupiter_program: params.new_jupiter_program,
+        fee_recipient: params.new_fee_recipient,
+        config_update_delay_seconds: params.new_config_update_delay_seconds,
+        add_across_handler: params.add_across_handler,
+        executable_at,
+    });
+
+    msg!("Config update proposed, applicable from {}", executable_at);
+
+    Ok(())
+}
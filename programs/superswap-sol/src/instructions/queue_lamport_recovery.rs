@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::RecoveryQueued;
+use crate::utils::rent_vault::require_stray_lamport_source;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PENDING_RECOVERY_SEED, RECOVERY_GUARD_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: QueueLamportRecoveryParams)]
+pub struct QueueLamportRecovery<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PendingRecovery::LEN,
+        seeds = [PENDING_RECOVERY_SEED, config.instance_seed().as_ref(), &params.recovery_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_recovery: Account<'info, PendingRecovery>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: The instance's account named by `params.source`, checked in the handler
+    pub source: UncheckedAccount<'info>,
+
+    /// CHECK: Any account can receive lamports
+    pub destination: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueLamportRecovery>, params: QueueLamportRecoveryParams) -> Result<()> {
+    require_stray_lamport_source(&ctx.accounts.source.to_account_info(), &params.source, &ctx.accounts.config)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    let executable_at = now
+        .checked_add(recovery_guard.limits.delay_seconds)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    let pending_recovery = &mut ctx.accounts.pending_recovery;
+    pending_recovery.recovery_id = params.recovery_id;
+    pending_recovery.token_mint = PendingRecovery::LAMPORTS_MINT;
+    pending_recovery.source_token_account = ctx.accounts.source.key();
+    pending_recovery.destination_token_account = ctx.accounts.destination.key();
+    pending_recovery.amount = params.amount;
+    pending_recovery.executable_at = executable_at;
+    pending_recovery.bump = ctx.bumps.pending_recovery;
+    let pending_recovery_key = pending_recovery.key();
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(ctx.accounts.admin.key(), AdminAction::QueueLamportRecovery, now, pending_recovery_key)
+            .with_values(0u64, params.amount),
+    );
+
+    emit!(RecoveryQueued {
+        recovery_id: params.recovery_id,
+        token_mint: PendingRecovery::LAMPORTS_MINT,
+        source_token_account: pending_recovery.source_token_account,
+        destination_token_account: pending_recovery.destination_token_account,
+        amount: params.amount,
+        executable_at,
+    });
+
+    msg!("Recovery {} of {} lamports queued until {}", params.recovery_id, params.amount, executable_at);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::outbound::quote_outbound_amounts;
+use superswap_pda::CONFIG_SEED;
+
+#[derive(Accounts)]
+pub struct QuoteOutbound<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<QuoteOutbound>, params: QuoteOutboundParams) -> Result<OutboundQuote> {
+    let quote = quote_outbound_amounts(&ctx.accounts.config, params.usdc_amount, params.bridge)?;
+
+    msg!("Protocol fee: {}", quote.protocol_fee);
+    msg!("Bridge fee estimate: {}", quote.bridge_fee_estimate);
+    msg!("Min received: {}", quote.min_received);
+
+    Ok(quote)
+}
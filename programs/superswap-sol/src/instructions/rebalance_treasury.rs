@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{TokenAccount, Mint};
+use crate::state::*;
+use crate::error::SuperSwapError;
+#[cfg(any(feature = "across", feature = "cctp"))]
+use crate::events::TreasuryRebalanced;
+#[cfg(feature = "across")]
+use crate::utils::across::{self, AcrossDepositArgs};
+#[cfg(feature = "cctp")]
+use crate::utils::cctp::{self, cctp_domain_for_chain, DepositForBurnArgs};
+#[cfg(any(feature = "across", feature = "cctp"))]
+use crate::utils::evm::{evm_address_to_bytes32, is_zero_evm_address};
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct RebalanceTreasury<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized,
+        has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    pub admin: Signer<'info>,
+
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Program's USDC token account holding accumulated protocol USDC
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = config
+    )]
+    pub program_usdc_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Across spoke pool or CCTP TokenMessengerMinter, validated against config in the handler
+    pub bridge_program: UncheckedAccount<'info>,
+
+    // Note: remaining_accounts holds the Across deposit or deposit_for_burn accounts
+}
+
+#[cfg(any(feature = "across", feature = "cctp"))]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RebalanceTreasury<'info>>,
+    params: RebalanceTreasuryParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    require!(params.amount > 0, SuperSwapError::InvalidBridgeAmount);
+    require!(
+        params.amount <= ctx.accounts.program_usdc_account.amount,
+        SuperSwapError::InvalidBridgeAmount
+    );
+    require!(params.destination_chain_id != 0, SuperSwapError::InvalidDestinationChain);
+    require!(!is_zero_evm_address(&params.recipient), SuperSwapError::InvalidRecipient);
+
+    msg!("Rebalancing {} USDC to treasury", params.amount);
+
+    let instance_seed = config.instance_seed();
+    let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+    let signer = &[&seeds[..]];
+
+    let bridge_program = ctx.accounts.bridge_program.to_account_info();
+
+    // The config PDA owns the program's USDC account, so it is the depositor
+    match params.bridge {
+        #[cfg(feature = "across")]
+        OutboundBridge::Across => {
+            require!(
+                bridge_program.key() == config.across_spoke_pool,
+                SuperSwapError::InvalidAcrossSpokePool
+            );
+            require!(
+                params.output_amount <= params.amount,
+                SuperSwapError::InvalidBridgeOutputAmount
+            );
+
+            let deposit_args = AcrossDepositArgs {
+                depositor: config.key(),
+                recipient: evm_address_to_bytes32(&params.recipient),
+                input_token: config.usdc_mint,
+                output_token: evm_address_to_bytes32(&params.output_token),
+                input_amount: params.amount,
+                output_amount: params.output_amount,
+                destination_chain_id: params.destination_chain_id,
+                exclusive_relayer: Pubkey::default(),
+                quote_timestamp: params.quote_timestamp,
+                fill_deadline: params.fill_deadline,
+                exclusivity_parameter: 0,
+                message: Vec::new(),
+            };
+            across::deposit(&bridge_program, ctx.remaining_accounts, &deposit_args, signer)?;
+        }
+        #[cfg(feature = "cctp")]
+        OutboundBridge::Cctp => {
+            require!(
+                bridge_program.key() == config.cctp_token_messenger_minter,
+                SuperSwapError::InvalidCctpProgram
+            );
+            require!(
+                cctp_domain_for_chain(params.destination_chain_id) == Some(params.destination_domain),
+                SuperSwapError::InvalidDestinationChain
+            );
+
+            let burn_args = DepositForBurnArgs {
+                amount: params.amount,
+                destination_domain: params.destination_domain,
+                mint_recipient: evm_address_to_bytes32(&params.recipient),
+            };
+            cctp::deposit_for_burn(
+                &bridge_program,
+                ctx.remaining_accounts,
+                &burn_args,
+                &config.key(),
+                signer,
+            )?;
+        }
+        #[allow(unreachable_patterns)]
+        _ => return err!(SuperSwapError::AdapterNotBuilt),
+    }
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::RebalanceTreasury,
+            Clock::get()?.unix_timestamp,
+            Pubkey::default(),
+        )
+            .with_values(0u64, params.amount),
+    );
+
+    emit!(TreasuryRebalanced {
+        bridge: params.bridge,
+        amount: params.amount,
+        destination_chain_id: params.destination_chain_id,
+        recipient: params.recipient,
+    });
+
+    msg!("Treasury rebalance submitted");
+
+    Ok(())
+}
+
+/// Builds with neither the `across` nor the `cctp` feature have no bridge to rebalance through
+#[cfg(not(any(feature = "across", feature = "cctp")))]
+pub fn handler<'info>(
+    _ctx: Context<'_, '_, 'info, 'info, RebalanceTreasury<'info>>,
+    _params: RebalanceTreasuryParams,
+) -> Result<()> {
+    err!(SuperSwapError::AdapterNotBuilt)
+}
@@ -2,16 +2,34 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::error::SuperSwapError;
+use crate::events::FundsRecovered;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, RECOVERY_GUARD_SEED};
 
 #[derive(Accounts)]
 pub struct RecoverFunds<'info> {
     #[account(
-        seeds = [b"config"],
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
         bump = config.bump,
         has_one = admin @ SuperSwapError::Unauthorized
     )]
     pub config: Account<'info, Config>,
 
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Timelock threshold and per-period cap on recoveries
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
     pub admin: Signer<'info>,
 
     #[account(mut)]
@@ -36,11 +54,21 @@ pub fn handler(ctx: Context<RecoverFunds>, params: RecoverFundsParams) -> Result
         SuperSwapError::InvalidTokenMint
     );
 
+    // Large recoveries go through queue_recovery; all of them count against the cap
+    let now = Clock::get()?.unix_timestamp;
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    require!(
+        !recovery_guard.requires_timelock(params.amount),
+        SuperSwapError::RecoveryRequiresTimelock
+    );
+    recovery_guard.record(params.amount, now)?;
+
     msg!("Recovering {} tokens", params.amount);
     msg!("Token mint: {}", params.token_mint);
 
-    let config_key = config.key();
-    let seeds = &[b"config".as_ref(), &[config.bump]];
+    let instance_seed = config.instance_seed();
+    let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
     let signer = &[&seeds[..]];
 
     let transfer_ctx = CpiContext::new_with_signer(
@@ -55,8 +83,25 @@ pub fn handler(ctx: Context<RecoverFunds>, params: RecoverFundsParams) -> Result
 
     token::transfer(transfer_ctx, params.amount)?;
 
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::RecoverFunds,
+            now,
+            ctx.accounts.destination_token_account.key(),
+        )
+            .with_values(0u64, params.amount),
+    );
+
+    emit!(FundsRecovered {
+        recovery_id: None,
+        token_mint: params.token_mint,
+        source_token_account: ctx.accounts.source_token_account.key(),
+        destination_token_account: ctx.accounts.destination_token_account.key(),
+        amount: params.amount,
+    });
+
     msg!("Funds recovered successfully");
 
     Ok(())
 }
-
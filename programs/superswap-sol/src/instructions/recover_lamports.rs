@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::LamportsRecovered;
+use crate::utils::rent_vault::{require_stray_lamport_source, withdraw_stray_lamports};
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, RECOVERY_GUARD_SEED};
+
+#[derive(Accounts)]
+pub struct RecoverLamports<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Timelock threshold and per-period cap on recoveries
+    #[account(
+        mut,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump = recovery_guard.bump,
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: The instance's account named by `params.source`, checked in the handler
+    #[account(mut)]
+    pub source: UncheckedAccount<'info>,
+
+    /// CHECK: Any account can receive lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RecoverLamports>, params: RecoverLamportsParams) -> Result<()> {
+    require_stray_lamport_source(&ctx.accounts.source.to_account_info(), &params.source, &ctx.accounts.config)?;
+
+    // Same guard as token recoveries, counted in lamports
+    let now = Clock::get()?.unix_timestamp;
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    recovery_guard.refresh(now);
+    require!(
+        !recovery_guard.requires_timelock(params.amount),
+        SuperSwapError::RecoveryRequiresTimelock
+    );
+    recovery_guard.record(params.amount, now)?;
+
+    withdraw_stray_lamports(
+        &ctx.accounts.source.to_account_info(),
+        &ctx.accounts.destination.to_account_info(),
+        params.amount,
+    )?;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::RecoverLamports,
+            now,
+            ctx.accounts.source.key(),
+        )
+            .with_values(0u64, params.amount),
+    );
+
+    emit!(LamportsRecovered {
+        recovery_id: None,
+        source: ctx.accounts.source.key(),
+        destination: ctx.accounts.destination.key(),
+        amount: params.amount,
+    });
+
+    msg!("Recovered {} lamports from {}", params.amount, ctx.accounts.source.key());
+
+    Ok(())
+}
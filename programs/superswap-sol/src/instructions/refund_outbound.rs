@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::OutboundRefunded;
+use superswap_pda::{CONFIG_SEED, OUTBOUND_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: RefundOutboundParams)]
+pub struct RefundOutbound<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            OUTBOUND_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = outbound_order.bump,
+        has_one = sender @ SuperSwapError::Unauthorized,
+    )]
+    pub outbound_order: Account<'info, OutboundOrder>,
+
+    /// Original sender of the outbound order
+    pub sender: Signer<'info>,
+
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Outbound order's USDC escrow
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = outbound_order
+    )]
+    pub outbound_escrow: Account<'info, TokenAccount>,
+
+    /// Sender's USDC account receiving the refund
+    #[account(
+        mut,
+        constraint = sender_usdc_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+        constraint = sender_usdc_account.owner == sender.key() @ SuperSwapError::Unauthorized,
+    )]
+    pub sender_usdc_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RefundOutbound>, params: RefundOutboundParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    let outbound_order = &ctx.accounts.outbound_order;
+
+    // Settled orders were delivered; refunded orders were already paid out
+    require!(
+        matches!(
+            outbound_order.status,
+            OutboundStatus::Created | OutboundStatus::Bridged
+        ),
+        SuperSwapError::InvalidOrderStatus
+    );
+
+    // Only USDC the bridge never took (or returned) can be refunded
+    let amount = ctx.accounts.outbound_escrow.amount;
+    require!(amount > 0, SuperSwapError::EmptyOutboundEscrow);
+
+    let instance_seed = ctx.accounts.config.instance_seed();
+    let order_id_bytes = params.order_id.to_le_bytes();
+    let seeds = &[
+        OUTBOUND_ORDER_SEED,
+        instance_seed.as_ref(),
+        order_id_bytes.as_ref(),
+        &[outbound_order.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let refund_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.outbound_escrow.to_account_info(),
+            to: ctx.accounts.sender_usdc_account.to_account_info(),
+            authority: ctx.accounts.outbound_order.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(refund_ctx, amount)?;
+
+    let outbound_order = &mut ctx.accounts.outbound_order;
+    outbound_order.status = OutboundStatus::Refunded;
+
+    emit!(OutboundRefunded {
+        order_id: params.order_id,
+        sender: outbound_order.sender,
+        amount,
+    });
+
+    msg!("Outbound order {} refunded: {} USDC", params.order_id, amount);
+
+    Ok(())
+}
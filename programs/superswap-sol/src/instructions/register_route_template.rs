@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, ROUTE_TEMPLATE_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: RegisterRouteTemplateParams)]
+pub struct RegisterRouteTemplate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RouteTemplate::space(params.accounts.len()),
+        seeds = [
+            ROUTE_TEMPLATE_SEED,
+            config.instance_seed().as_ref(),
+            params.input_mint.as_ref(),
+            params.output_mint.as_ref()
+        ],
+        bump
+    )]
+    pub route_template: Account<'info, RouteTemplate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterRouteTemplate>, params: RegisterRouteTemplateParams) -> Result<()> {
+    require!(
+        !params.accounts.is_empty() && params.accounts.len() <= RouteTemplate::MAX_ACCOUNTS,
+        SuperSwapError::InvalidRouteTemplate
+    );
+
+    let route_template = &mut ctx.accounts.route_template;
+    route_template.config = ctx.accounts.config.key();
+    route_template.input_mint = params.input_mint;
+    route_template.output_mint = params.output_mint;
+    route_template.discriminator = params.discriminator;
+    route_template.accounts = params.accounts;
+    route_template.bump = ctx.bumps.route_template;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RegisterRouteTemplate,
+        Clock::get()?.unix_timestamp,
+        route_template.key(),
+    ));
+
+    msg!(
+        "Route template registered for {} -> {} ({} accounts)",
+        params.input_mint,
+        params.output_mint,
+        route_template.accounts.len()
+    );
+
+    Ok(())
+}
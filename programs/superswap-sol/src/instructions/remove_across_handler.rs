@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::AcrossHandlerRemoved;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct RemoveAcrossHandler<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveAcrossHandler>, params: RemoveAcrossHandlerParams) -> Result<()> {
+    // Revoking a handler is never timelocked, so a compromised key can be cut off at once
+    let config = &mut ctx.accounts.config;
+    config.remove_across_handler(&params.handler)?;
+
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RemoveAcrossHandler,
+        Clock::get()?.unix_timestamp,
+        params.handler,
+    ));
+
+    emit!(AcrossHandlerRemoved {
+        config: config.key(),
+        handler: params.handler,
+    });
+
+    msg!("Across handler {} removed", params.handler);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, APPROVED_DELEGATE_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: ApprovedDelegateParams)]
+pub struct RemoveApprovedDelegate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [APPROVED_DELEGATE_SEED, config.instance_seed().as_ref(), params.delegate.as_ref()],
+        bump = approved_delegate.bump
+    )]
+    pub approved_delegate: Account<'info, ApprovedDelegate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveApprovedDelegate>, params: ApprovedDelegateParams) -> Result<()> {
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RemoveApprovedDelegate,
+        Clock::get()?.unix_timestamp,
+        params.delegate,
+    ));
+
+    msg!("Delegate removed from allowlist: {}", params.delegate);
+
+    Ok(())
+}
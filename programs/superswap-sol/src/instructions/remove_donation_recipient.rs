@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, DONATION_RECIPIENT_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: DonationRecipientParams)]
+pub struct RemoveDonationRecipient<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [DONATION_RECIPIENT_SEED, config.instance_seed().as_ref(), params.recipient.as_ref()],
+        bump = donation_recipient.bump
+    )]
+    pub donation_recipient: Account<'info, DonationRecipient>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveDonationRecipient>, params: DonationRecipientParams) -> Result<()> {
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RemoveDonationRecipient,
+        Clock::get()?.unix_timestamp,
+        params.recipient,
+    ));
+
+    msg!("Donation recipient removed from allowlist: {}", params.recipient);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, HOOK_PROGRAM_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: HookProgramParams)]
+pub struct RemoveHookProgram<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [HOOK_PROGRAM_SEED, config.instance_seed().as_ref(), params.program_id.as_ref()],
+        bump = hook_program.bump
+    )]
+    pub hook_program: Account<'info, HookProgram>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveHookProgram>, params: HookProgramParams) -> Result<()> {
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RemoveHookProgram,
+        Clock::get()?.unix_timestamp,
+        params.program_id,
+    ));
+
+    msg!("Hook program removed from allowlist: {}", params.program_id);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, PAYER_LEDGER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: PayerOperatorParams)]
+pub struct RemovePayerOperator<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Closing the ledger would forget what the operator is owed, so it must be settled first
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PAYER_LEDGER_SEED, config.instance_seed().as_ref(), params.operator.as_ref()],
+        bump = payer_ledger.bump,
+        constraint = payer_ledger.owed_usdc == 0 @ SuperSwapError::PayerLedgerNotSettled
+    )]
+    pub payer_ledger: Account<'info, PayerLedger>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemovePayerOperator>, params: PayerOperatorParams) -> Result<()> {
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RemovePayerOperator,
+        Clock::get()?.unix_timestamp,
+        params.operator,
+    ));
+
+    msg!("Payer operator removed: {}", params.operator);
+
+    Ok(())
+}
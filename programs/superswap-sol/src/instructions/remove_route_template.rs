@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, ROUTE_TEMPLATE_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: RemoveRouteTemplateParams)]
+pub struct RemoveRouteTemplate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            ROUTE_TEMPLATE_SEED,
+            config.instance_seed().as_ref(),
+            params.input_mint.as_ref(),
+            params.output_mint.as_ref()
+        ],
+        bump = route_template.bump
+    )]
+    pub route_template: Account<'info, RouteTemplate>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveRouteTemplate>, params: RemoveRouteTemplateParams) -> Result<()> {
+    ctx.accounts.admin_log.record(AdminLogEntry::new(
+        ctx.accounts.admin.key(),
+        AdminAction::RemoveRouteTemplate,
+        Clock::get()?.unix_timestamp,
+        ctx.accounts.route_template.key(),
+    ));
+
+    msg!("Route template removed for {} -> {}", params.input_mint, params.output_mint);
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, CloseAccount, Revoke, Token, TokenAccount, Transfer as TokenTransfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::DelegateRevoked;
+use superswap_pda::{CONFIG_SEED, ESCROW_SEED, ORDER_EXTENSION_SEED, RENT_VAULT_SEED, SWAP_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: RevokeDelegateParams)]
+pub struct RevokeDelegate<'info> {
+    /// Config of the instance the order belongs to
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SWAP_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    #[account(
+        seeds = [ORDER_EXTENSION_SEED, swap_order.key().as_ref()],
+        bump = order_extension.bump
+    )]
+    pub order_extension: Account<'info, OrderExtension>,
+
+    /// CHECK: Per-order escrow PDA holding the delegate's allowance
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, swap_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Escrow's token account the delegate pulls from
+    #[account(
+        mut,
+        associated_token::mint = swap_order.destination_mint,
+        associated_token::authority = escrow
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// Beneficiary's token account receiving what the delegate left
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == swap_order.destination_mint @ SuperSwapError::InvalidTokenMint,
+        constraint = beneficiary_token_account.owner == beneficiary.key() @ SuperSwapError::InvalidRecipient,
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    /// Beneficiary named by the order's approve-delegate action
+    pub beneficiary: Signer<'info>,
+
+    /// Rent vault repaid for the escrow's token account
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RevokeDelegate>, params: RevokeDelegateParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    let swap_order = &ctx.accounts.swap_order;
+    require!(
+        swap_order.status == OrderStatus::Completed,
+        SuperSwapError::InvalidOrderStatus
+    );
+    require!(
+        swap_order.recipient == ctx.accounts.escrow.key(),
+        SuperSwapError::InvalidRecipient
+    );
+
+    let Some(PostSwapAction::ApproveDelegate { delegate, beneficiary, .. }) =
+        ctx.accounts.order_extension.post_swap_action
+    else {
+        return err!(SuperSwapError::MissingPostSwapAction);
+    };
+    require!(
+        ctx.accounts.beneficiary.key() == beneficiary,
+        SuperSwapError::Unauthorized
+    );
+
+    // Until the action runs the escrow holds the whole output, undelegated
+    let escrow_token_account = &ctx.accounts.escrow_token_account;
+    require!(
+        escrow_token_account.delegate.is_some() || escrow_token_account.amount == 0,
+        SuperSwapError::InvalidOrderStatus
+    );
+    let amount = escrow_token_account.amount;
+
+    let swap_order_key = swap_order.key();
+    let seeds = &[
+        ESCROW_SEED,
+        swap_order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let signer = &[&seeds[..]];
+
+    if escrow_token_account.delegate.is_some() {
+        let revoke_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::revoke(revoke_ctx)?;
+    }
+
+    if amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenTransfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        signer,
+    );
+    token::close_account(close_ctx)?;
+
+    // The escrow's token account rent was fronted by the rent vault
+    let leftover = ctx.accounts.escrow.lamports();
+    if leftover > 0 {
+        let repay_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.rent_vault.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(repay_ctx, leftover)?;
+    }
+
+    emit!(DelegateRevoked {
+        order_id: params.order_id,
+        delegate,
+        beneficiary,
+        amount_reclaimed: amount,
+    });
+
+    msg!("Order {} delegate {} revoked, {} reclaimed", params.order_id, delegate, amount);
+
+    Ok(())
+}
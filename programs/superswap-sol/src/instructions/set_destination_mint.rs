@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::DestinationMintUpdated;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, DESTINATION_MINT_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: SetDestinationMintParams)]
+pub struct SetDestinationMint<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = DestinationMint::LEN,
+        seeds = [DESTINATION_MINT_SEED, config.instance_seed().as_ref(), params.mint.as_ref()],
+        bump
+    )]
+    pub destination_mint: Account<'info, DestinationMint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetDestinationMint>, params: SetDestinationMintParams) -> Result<()> {
+    let destination_mint = &mut ctx.accounts.destination_mint;
+    let was_disabled = destination_mint.disabled;
+    destination_mint.mint = params.mint;
+    destination_mint.disabled = params.disabled;
+    destination_mint.bump = ctx.bumps.destination_mint;
+
+    emit!(DestinationMintUpdated {
+        mint: params.mint,
+        disabled: params.disabled,
+    });
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::SetDestinationMint,
+            Clock::get()?.unix_timestamp,
+            params.mint,
+        )
+        .with_values(was_disabled, params.disabled),
+    );
+
+    msg!("Destination mint {} disabled: {}", params.mint, params.disabled);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, OUTBOUND_RATE_LIMIT_SEED};
+
+#[derive(Accounts)]
+pub struct SetOutboundRateLimit<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        seeds = [OUTBOUND_RATE_LIMIT_SEED, config.instance_seed().as_ref()],
+        bump = outbound_rate_limit.bump,
+    )]
+    pub outbound_rate_limit: Account<'info, OutboundRateLimit>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOutboundRateLimit>, params: SetOutboundRateLimitParams) -> Result<()> {
+    require!(params.period_seconds > 0, SuperSwapError::InvalidFeeConfiguration);
+
+    let outbound_rate_limit = &mut ctx.accounts.outbound_rate_limit;
+    let changes = setting_changes!(outbound_rate_limit, params; period_seconds, period_cap);
+    outbound_rate_limit.period_seconds = params.period_seconds;
+    outbound_rate_limit.period_cap = params.period_cap;
+
+    ctx.accounts.admin_log.record_changes(
+        ctx.accounts.admin.key(),
+        AdminAction::SetOutboundRateLimit,
+        Clock::get()?.unix_timestamp,
+        changes,
+    );
+
+    msg!("Outbound period: {} seconds", params.period_seconds);
+    msg!("Outbound period cap: {}", params.period_cap);
+
+    Ok(())
+}
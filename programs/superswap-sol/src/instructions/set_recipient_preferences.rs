@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use superswap_pda::RECIPIENT_PREFERENCES_SEED;
+
+#[derive(Accounts)]
+pub struct SetRecipientPreferences<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = RecipientPreferences::LEN,
+        seeds = [RECIPIENT_PREFERENCES_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub recipient_preferences: Account<'info, RecipientPreferences>,
+
+    /// Recipient registering the preferences
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetRecipientPreferences>, params: SetRecipientPreferencesParams) -> Result<()> {
+    let preferences = &mut ctx.accounts.recipient_preferences;
+    preferences.owner = ctx.accounts.owner.key();
+    preferences.preferred_destination_account = params.preferred_destination_account;
+    preferences.auto_unwrap_sol = params.auto_unwrap_sol;
+    preferences.gas_top_up = params.gas_top_up;
+    preferences.refund_address = params.refund_address;
+    preferences.bump = ctx.bumps.recipient_preferences;
+
+    msg!("Recipient preferences set for {}", preferences.owner);
+
+    Ok(())
+}
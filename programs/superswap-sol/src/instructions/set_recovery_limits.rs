@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::RecoveryLimitsUpdated;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, RECOVERY_GUARD_SEED};
+
+#[derive(Accounts)]
+pub struct SetRecoveryLimits<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Created here for deployments initialized before the guard existed
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RecoveryGuard::LEN,
+        seeds = [RECOVERY_GUARD_SEED, config.instance_seed().as_ref()],
+        bump
+    )]
+    pub recovery_guard: Account<'info, RecoveryGuard>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetRecoveryLimits>, params: SetRecoveryLimitsParams) -> Result<()> {
+    let limits = params.limits;
+    require!(
+        limits.period_seconds > 0 && limits.delay_seconds >= 0,
+        SuperSwapError::InvalidFeeConfiguration
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let recovery_guard = &mut ctx.accounts.recovery_guard;
+    let changes = limits.changes_since(&recovery_guard.limits);
+    if recovery_guard.limits.period_seconds == 0 {
+        // A fresh guard has nothing to loosen, so its first limits apply at once
+        recovery_guard.limits = limits;
+        recovery_guard.period_start = now;
+        recovery_guard.bump = ctx.bumps.recovery_guard;
+    }
+    let effective_at = recovery_guard.set_limits(limits, now)?.unwrap_or(now);
+
+    ctx.accounts.admin_log.record_changes(
+        ctx.accounts.admin.key(),
+        AdminAction::SetRecoveryLimits,
+        now,
+        changes,
+    );
+
+    emit!(RecoveryLimitsUpdated { limits, effective_at });
+
+    msg!("Recovery limits take effect at {}", effective_at);
+
+    Ok(())
+}
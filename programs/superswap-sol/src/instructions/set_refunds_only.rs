@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct SetRefundsOnly<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    /// Admin, or the config's guardian when one is set; only the admin may leave the mode
+    #[account(
+        constraint = config.is_guardian(&authority.key()) @ SuperSwapError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRefundsOnly>, params: SetRefundsOnlyParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        params.enabled || ctx.accounts.authority.key() == config.admin,
+        SuperSwapError::Unauthorized
+    );
+    let was_refunds_only = config.refunds_only;
+    config.refunds_only = params.enabled;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.authority.key(),
+            AdminAction::SetRefundsOnly,
+            Clock::get()?.unix_timestamp,
+            Pubkey::default(),
+        )
+        .with_values(was_refunds_only, params.enabled),
+    );
+
+    if params.enabled {
+        msg!("Refunds-only mode entered; new fills are rejected");
+    } else {
+        msg!("Refunds-only mode left");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::OutboundSettled;
+use superswap_pda::{CONFIG_SEED, OUTBOUND_ORDER_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: SettleOutboundOrderParams)]
+pub struct SettleOutboundOrder<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        constraint = config.is_across_handler(&across_handler.key()) @ SuperSwapError::InvalidAcrossHandler,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            OUTBOUND_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump = outbound_order.bump
+    )]
+    pub outbound_order: Account<'info, OutboundOrder>,
+
+    /// Across handler that observes destination chain deliveries
+    pub across_handler: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SettleOutboundOrder>, params: SettleOutboundOrderParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused()?;
+
+    let outbound_order = &mut ctx.accounts.outbound_order;
+
+    require!(
+        outbound_order.status == OutboundStatus::Bridged,
+        SuperSwapError::InvalidOrderStatus
+    );
+
+    outbound_order.status = OutboundStatus::Settled;
+
+    emit!(OutboundSettled {
+        order_id: params.order_id,
+        destination_tx_hash: params.destination_tx_hash,
+    });
+
+    msg!("Outbound order {} settled", params.order_id);
+
+    Ok(())
+}
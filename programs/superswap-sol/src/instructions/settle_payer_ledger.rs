@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::PayerLedgerSettled;
+use superswap_pda::{CONFIG_SEED, PAYER_LEDGER_SEED};
+
+#[derive(Accounts)]
+pub struct SettlePayerLedger<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = fee_recipient @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [PAYER_LEDGER_SEED, config.instance_seed().as_ref(), payer_ledger.operator.as_ref()],
+        bump = payer_ledger.bump
+    )]
+    pub payer_ledger: Account<'info, PayerLedger>,
+
+    /// Fee recipient's USDC account the settlement is paid from
+    #[account(
+        mut,
+        constraint = fee_recipient_account.owner == fee_recipient.key() @ SuperSwapError::Unauthorized,
+        constraint = fee_recipient_account.mint == config.usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub fee_recipient_account: Account<'info, TokenAccount>,
+
+    /// Operator's USDC account
+    #[account(
+        mut,
+        constraint = operator_usdc_account.owner == payer_ledger.operator @ SuperSwapError::Unauthorized,
+        constraint = operator_usdc_account.mint == config.usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub operator_usdc_account: Account<'info, TokenAccount>,
+
+    pub fee_recipient: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SettlePayerLedger>) -> Result<()> {
+    let usdc_amount = ctx.accounts.payer_ledger.settle()?;
+    if usdc_amount == 0 {
+        msg!("Nothing owed to payer operator");
+        return Ok(());
+    }
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_recipient_account.to_account_info(),
+                to: ctx.accounts.operator_usdc_account.to_account_info(),
+                authority: ctx.accounts.fee_recipient.to_account_info(),
+            },
+        ),
+        usdc_amount,
+    )?;
+
+    emit!(PayerLedgerSettled {
+        operator: ctx.accounts.payer_ledger.operator,
+        usdc_amount,
+    });
+
+    msg!("Settled {} USDC to payer operator {}", usdc_amount, ctx.accounts.payer_ledger.operator);
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::*;
+use crate::error::SuperSwapError;
+#[cfg(feature = "cctp")]
+use crate::events::{OutboundBridged, OutboundCreated};
+#[cfg(feature = "cctp")]
+use crate::utils::cctp::{self, cctp_domain_for_chain, DepositForBurnArgs};
+#[cfg(feature = "cctp")]
+use crate::utils::evm::{evm_address_to_bytes32, is_zero_evm_address};
+#[cfg(feature = "cctp")]
+use crate::utils::outbound::{fund_outbound_escrow, swap_to_usdc_net_of_fee};
+use superswap_pda::{CONFIG_SEED, OUTBOUND_ORDER_SEED, OUTBOUND_RATE_LIMIT_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: SwapAndBurnParams)]
+pub struct SwapAndBurn<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = usdc_mint @ SuperSwapError::InvalidTokenMint,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = user,
+        space = OutboundOrder::LEN,
+        seeds = [
+            OUTBOUND_ORDER_SEED,
+            config.instance_seed().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub outbound_order: Account<'info, OutboundOrder>,
+
+    #[account(
+        mut,
+        seeds = [OUTBOUND_RATE_LIMIT_SEED, config.instance_seed().as_ref()],
+        bump = outbound_rate_limit.bump,
+    )]
+    pub outbound_rate_limit: Account<'info, OutboundRateLimit>,
+
+    /// User swapping their tokens and burning the proceeds
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Mint of the token being swapped
+    pub input_mint: Account<'info, Mint>,
+
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Program's USDC token account (receives the swap output)
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = config
+    )]
+    pub program_usdc_account: Account<'info, TokenAccount>,
+
+    /// Fee recipient's USDC account
+    #[account(
+        mut,
+        constraint = fee_recipient_account.mint == usdc_mint.key() @ SuperSwapError::InvalidTokenMint,
+        constraint = fee_recipient_account.owner == config.fee_recipient @ SuperSwapError::Unauthorized,
+    )]
+    pub fee_recipient_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Insurance fund's USDC account, checked by address when a fee share is paid
+    #[account(mut)]
+    pub insurance_usdc_account: UncheckedAccount<'info>,
+
+    /// Outbound order's USDC escrow, burned from by the order PDA;
+    /// anything left unburned stays claimable through `refund_outbound`
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = outbound_order
+    )]
+    pub outbound_escrow: Account<'info, TokenAccount>,
+
+    /// CHECK: Jupiter program (validated against config)
+    #[account(constraint = jupiter_program.key() == config.jupiter_program @ SuperSwapError::InvalidJupiterProgram)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    /// CHECK: CCTP TokenMessengerMinter program (validated against config)
+    #[account(constraint = token_messenger_minter.key() == config.cctp_token_messenger_minter @ SuperSwapError::InvalidCctpProgram)]
+    pub token_messenger_minter: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Note: remaining_accounts holds the Jupiter route accounts first
+    // (jupiter_accounts_len of them), followed by the deposit_for_burn accounts
+}
+
+#[cfg(feature = "cctp")]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapAndBurn<'info>>,
+    params: SwapAndBurnParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    // Check if program is paused
+    config.require_accepting_fills()?;
+    require!(!config.cctp_paused, SuperSwapError::BridgePaused);
+    require!(config.is_adapter_enabled(Config::ADAPTER_CCTP), SuperSwapError::AdapterDisabled);
+
+    // Validate destination
+    require!(
+        cctp_domain_for_chain(params.destination_chain_id) == Some(params.destination_domain),
+        SuperSwapError::InvalidDestinationChain
+    );
+    require!(!is_zero_evm_address(&params.recipient), SuperSwapError::InvalidRecipient);
+
+    let jupiter_accounts_len = params.jupiter_accounts_len as usize;
+    require!(
+        jupiter_accounts_len <= ctx.remaining_accounts.len(),
+        SuperSwapError::InvalidInstructionData
+    );
+    let (route_accounts, burn_accounts) = ctx.remaining_accounts.split_at(jupiter_accounts_len);
+
+    // Initialize outbound order
+    let outbound_order = &mut ctx.accounts.outbound_order;
+    outbound_order.order_id = params.order_id;
+    outbound_order.sender = ctx.accounts.user.key();
+    outbound_order.input_mint = ctx.accounts.input_mint.key();
+    outbound_order.bridge = OutboundBridge::Cctp;
+    outbound_order.destination_chain_id = params.destination_chain_id;
+    outbound_order.recipient = params.recipient;
+    outbound_order.status = OutboundStatus::Created;
+    outbound_order.created_at = Clock::get()?.unix_timestamp;
+    outbound_order.bump = ctx.bumps.outbound_order;
+
+    emit!(OutboundCreated {
+        order_id: params.order_id,
+        sender: outbound_order.sender,
+        input_mint: outbound_order.input_mint,
+        bridge: OutboundBridge::Cctp,
+        destination_chain_id: params.destination_chain_id,
+        recipient: params.recipient,
+    });
+
+    msg!("Swap and burn order: {}", params.order_id);
+    msg!("Destination domain: {}", params.destination_domain);
+
+    // Swap the user's tokens into the program's USDC account; the user signs the route
+    let amounts = swap_to_usdc_net_of_fee(
+        config,
+        &ctx.accounts.jupiter_program.to_account_info(),
+        &params.jupiter_swap_data,
+        route_accounts,
+        &ctx.accounts.user.key(),
+        &mut ctx.accounts.program_usdc_account,
+        &ctx.accounts.fee_recipient_account.to_account_info(),
+        &ctx.accounts.insurance_usdc_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        params.min_usdc_out,
+    )?;
+
+    // Bridged USDC leaves protocol custody, so it counts against the period cap
+    ctx.accounts
+        .outbound_rate_limit
+        .record(amounts.net_amount, Clock::get()?.unix_timestamp)?;
+
+    fund_outbound_escrow(
+        config,
+        &ctx.accounts.program_usdc_account.to_account_info(),
+        &ctx.accounts.outbound_escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        amounts.net_amount,
+    )?;
+
+    let instance_seed = ctx.accounts.config.instance_seed();
+    let order_id_bytes = params.order_id.to_le_bytes();
+    let seeds = &[
+        OUTBOUND_ORDER_SEED,
+        instance_seed.as_ref(),
+        order_id_bytes.as_ref(),
+        &[ctx.bumps.outbound_order],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Burn the escrowed USDC with the order PDA as owner of the burned account
+    let burn_args = DepositForBurnArgs {
+        amount: amounts.net_amount,
+        destination_domain: params.destination_domain,
+        mint_recipient: evm_address_to_bytes32(&params.recipient),
+    };
+    cctp::deposit_for_burn(
+        &ctx.accounts.token_messenger_minter.to_account_info(),
+        burn_accounts,
+        &burn_args,
+        &ctx.accounts.outbound_order.key(),
+        signer,
+    )?;
+
+    let outbound_order = &mut ctx.accounts.outbound_order;
+    outbound_order.usdc_amount = amounts.usdc_out;
+    outbound_order.fee_amount = amounts.fee_amount;
+    outbound_order.bridged_amount = amounts.net_amount;
+    outbound_order.status = OutboundStatus::Bridged;
+
+    emit!(OutboundBridged {
+        order_id: params.order_id,
+        usdc_amount: amounts.usdc_out,
+        fee_amount: amounts.fee_amount,
+        bridged_amount: amounts.net_amount,
+    });
+
+    msg!("Swap and burn completed");
+
+    Ok(())
+}
+
+/// Builds without the `cctp` feature keep the instruction in the interface but reject it
+#[cfg(not(feature = "cctp"))]
+pub fn handler<'info>(
+    _ctx: Context<'_, '_, 'info, 'info, SwapAndBurn<'info>>,
+    _params: SwapAndBurnParams,
+) -> Result<()> {
+    err!(SuperSwapError::AdapterNotBuilt)
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Unpause>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let was_paused = config.is_paused;
+    config.is_paused = false;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::Unpause,
+            Clock::get()?.unix_timestamp,
+            Pubkey::default(),
+        )
+        .with_values(was_paused, false),
+    );
+
+    msg!("Program unpaused");
+
+    Ok(())
+}
+
@@ -1,41 +1,47 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED};
 
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(
         mut,
-        seeds = [b"config"],
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
         bump = config.bump,
         has_one = admin @ SuperSwapError::Unauthorized
     )]
     pub config: Account<'info, Config>,
 
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
     pub admin: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
     let config = &mut ctx.accounts.config;
+    let before = Config::clone(config);
 
+    // The new admin only takes over once it signs accept_admin; the default key cancels a proposal
     if let Some(new_admin) = params.new_admin {
-        config.admin = new_admin;
-        msg!("Admin updated to: {}", new_admin);
-    }
-
-    if let Some(new_across_handler) = params.new_across_handler {
-        config.across_handler = new_across_handler;
-        msg!("Across handler updated to: {}", new_across_handler);
+        config.pending_admin = new_admin;
+        msg!("Admin transfer proposed to: {}", new_admin);
     }
 
-    if let Some(new_jupiter_program) = params.new_jupiter_program {
-        config.jupiter_program = new_jupiter_program;
-        msg!("Jupiter program updated to: {}", new_jupiter_program);
+    if let Some(new_across_spoke_pool) = params.new_across_spoke_pool {
+        config.across_spoke_pool = new_across_spoke_pool;
+        msg!("Across spoke pool updated to: {}", new_across_spoke_pool);
     }
 
-    if let Some(new_fee_recipient) = params.new_fee_recipient {
-        config.fee_recipient = new_fee_recipient;
-        msg!("Fee recipient updated to: {}", new_fee_recipient);
+    if let Some(new_cctp_token_messenger_minter) = params.new_cctp_token_messenger_minter {
+        config.cctp_token_messenger_minter = new_cctp_token_messenger_minter;
+        msg!("CCTP TokenMessengerMinter updated to: {}", new_cctp_token_messenger_minter);
     }
 
     if let Some(new_fee_bps) = params.new_fee_bps {
@@ -44,6 +50,230 @@ pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result
         msg!("Fee BPS updated to: {}", new_fee_bps);
     }
 
+    if let Some(new_outbound_fee_bps) = params.new_outbound_fee_bps {
+        require!(new_outbound_fee_bps <= 1000, SuperSwapError::InvalidFeeConfiguration);
+        config.outbound_fee_bps = new_outbound_fee_bps;
+        msg!("Outbound fee BPS updated to: {}", new_outbound_fee_bps);
+    }
+
+    if let Some(new_min_outbound_amount) = params.new_min_outbound_amount {
+        config.min_outbound_amount = new_min_outbound_amount;
+        msg!("Min outbound amount updated to: {}", new_min_outbound_amount);
+    }
+
+    if let Some(new_max_outbound_amount) = params.new_max_outbound_amount {
+        config.max_outbound_amount = new_max_outbound_amount;
+        msg!("Max outbound amount updated to: {}", new_max_outbound_amount);
+    }
+
+    if let Some(new_across_fee_bps_estimate) = params.new_across_fee_bps_estimate {
+        require!(new_across_fee_bps_estimate <= 1000, SuperSwapError::InvalidFeeConfiguration);
+        config.across_fee_bps_estimate = new_across_fee_bps_estimate;
+        msg!("Across fee estimate updated to: {} bps", new_across_fee_bps_estimate);
+    }
+
+    if let Some(new_cctp_fee_bps_estimate) = params.new_cctp_fee_bps_estimate {
+        require!(new_cctp_fee_bps_estimate <= 1000, SuperSwapError::InvalidFeeConfiguration);
+        config.cctp_fee_bps_estimate = new_cctp_fee_bps_estimate;
+        msg!("CCTP fee estimate updated to: {} bps", new_cctp_fee_bps_estimate);
+    }
+
+    if let Some(new_onward_adapter) = params.new_onward_adapter {
+        config.onward_adapter = new_onward_adapter;
+        msg!("Onward adapter updated to: {}", new_onward_adapter);
+    }
+
+    if let Some(new_jito_stake_pool) = params.new_jito_stake_pool {
+        config.jito_stake_pool = new_jito_stake_pool;
+        msg!("Jito stake pool updated to: {}", new_jito_stake_pool);
+    }
+
+    if let Some(new_marinade_state) = params.new_marinade_state {
+        config.marinade_state = new_marinade_state;
+        msg!("Marinade state updated to: {}", new_marinade_state);
+    }
+
+    if let Some(new_kamino_lending_market) = params.new_kamino_lending_market {
+        config.kamino_lending_market = new_kamino_lending_market;
+        msg!("Kamino lending market updated to: {}", new_kamino_lending_market);
+    }
+
+    if let Some(new_solend_lending_market) = params.new_solend_lending_market {
+        config.solend_lending_market = new_solend_lending_market;
+        msg!("Solend lending market updated to: {}", new_solend_lending_market);
+    }
+
+    if let Some(new_max_gas_top_up_usdc) = params.new_max_gas_top_up_usdc {
+        config.max_gas_top_up_usdc = new_max_gas_top_up_usdc;
+        msg!("Max gas top-up updated to: {}", new_max_gas_top_up_usdc);
+    }
+
+    if let Some(new_max_donation_bps) = params.new_max_donation_bps {
+        require!(new_max_donation_bps <= 10_000, SuperSwapError::InvalidFeeConfiguration);
+        config.max_donation_bps = new_max_donation_bps;
+        msg!("Max donation BPS updated to: {}", new_max_donation_bps);
+    }
+
+    if let Some(new_refund_bounty_bps) = params.new_refund_bounty_bps {
+        require!(
+            new_refund_bounty_bps <= Config::MAX_REFUND_BOUNTY_BPS,
+            SuperSwapError::InvalidFeeConfiguration
+        );
+        config.refund_bounty_bps = new_refund_bounty_bps;
+        msg!("Refund bounty BPS updated to: {}", new_refund_bounty_bps);
+    }
+
+    if let Some(new_min_order_usdc) = params.new_min_order_usdc {
+        config.min_order_usdc = new_min_order_usdc;
+        msg!("Min order USDC updated to: {}", new_min_order_usdc);
+    }
+
+    if let Some(new_deliver_dust_as_usdc) = params.new_deliver_dust_as_usdc {
+        config.deliver_dust_as_usdc = new_deliver_dust_as_usdc;
+        msg!("Deliver dust as USDC updated to: {}", new_deliver_dust_as_usdc);
+    }
+
+    if let Some(new_max_open_orders_per_recipient) = params.new_max_open_orders_per_recipient {
+        config.max_open_orders_per_recipient = new_max_open_orders_per_recipient;
+        msg!("Max open orders per recipient updated: {}", new_max_open_orders_per_recipient);
+    }
+
+    if let Some(new_max_price_impact_bps) = params.new_max_price_impact_bps {
+        require!(new_max_price_impact_bps <= 10_000, SuperSwapError::InvalidFeeConfiguration);
+        config.max_price_impact_bps = new_max_price_impact_bps;
+        msg!("Max price impact BPS updated to: {}", new_max_price_impact_bps);
+    }
+
+    if let Some(new_order_ttl_seconds) = params.new_order_ttl_seconds {
+        config.order_ttl_seconds = new_order_ttl_seconds;
+        msg!("Order TTL updated to: {}", new_order_ttl_seconds);
+    }
+
+    if let Some(new_escrow_shortfall_tolerance) = params.new_escrow_shortfall_tolerance {
+        config.escrow_shortfall_tolerance = new_escrow_shortfall_tolerance;
+        msg!("Escrow shortfall tolerance updated to: {}", new_escrow_shortfall_tolerance);
+    }
+
+    if let Some(new_across_paused) = params.new_across_paused {
+        config.across_paused = new_across_paused;
+        msg!("Across paused: {}", new_across_paused);
+    }
+
+    if let Some(new_cctp_paused) = params.new_cctp_paused {
+        config.cctp_paused = new_cctp_paused;
+        msg!("CCTP paused: {}", new_cctp_paused);
+    }
+
+    if let Some(new_insurance_fee_share_bps) = params.new_insurance_fee_share_bps {
+        require!(new_insurance_fee_share_bps <= 10_000, SuperSwapError::InvalidFeeConfiguration);
+        config.insurance_fee_share_bps = new_insurance_fee_share_bps;
+        msg!("Insurance fee share updated to: {}", new_insurance_fee_share_bps);
+    }
+
+    if let Some(new_max_insurance_claim_usdc) = params.new_max_insurance_claim_usdc {
+        config.max_insurance_claim_usdc = new_max_insurance_claim_usdc;
+        msg!("Max insurance claim updated to: {}", new_max_insurance_claim_usdc);
+    }
+
+    if let Some(new_fallback_output_mint) = params.new_fallback_output_mint {
+        require!(
+            config.is_valid_fallback_output_mint(&new_fallback_output_mint),
+            SuperSwapError::InvalidFallbackOutput
+        );
+        config.fallback_output_mint = new_fallback_output_mint;
+        msg!("Fallback output mint updated: {}", new_fallback_output_mint);
+    }
+
+    if let Some(new_rent_treasury) = params.new_rent_treasury {
+        config.rent_treasury = new_rent_treasury;
+        msg!("Rent treasury updated: {}", new_rent_treasury);
+    }
+
+    if let Some(new_min_retention_seconds) = params.new_min_retention_seconds {
+        config.min_retention_seconds = new_min_retention_seconds;
+        msg!("Min retention updated: {}", new_min_retention_seconds);
+    }
+
+    if let Some(new_operator_fill_cost_usdc) = params.new_operator_fill_cost_usdc {
+        config.operator_fill_cost_usdc = new_operator_fill_cost_usdc;
+        msg!("Operator fill cost updated: {}", new_operator_fill_cost_usdc);
+    }
+
+    if let Some(new_guardian) = params.new_guardian {
+        config.guardian = new_guardian;
+        msg!("Guardian updated: {}", new_guardian);
+    }
+
+    if let Some(new_max_order_usdc) = params.new_max_order_usdc {
+        config.max_order_usdc = new_max_order_usdc;
+        msg!("Max order updated: {}", new_max_order_usdc);
+    }
+
+    if let Some(new_enabled_adapters) = params.new_enabled_adapters {
+        require!(
+            new_enabled_adapters & !Config::ADAPTERS_BUILT == 0,
+            SuperSwapError::InvalidAdapterSet
+        );
+        config.enabled_adapters = new_enabled_adapters;
+        msg!("Enabled adapters updated: {}", new_enabled_adapters);
+    }
+
+    if let Some(new_max_relayer_tip_usdc) = params.new_max_relayer_tip_usdc {
+        config.max_relayer_tip_usdc = new_max_relayer_tip_usdc;
+        msg!("Max relayer tip (USDC) updated to: {}", new_max_relayer_tip_usdc);
+    }
+
+    if let Some(new_surplus_share_bps) = params.new_surplus_share_bps {
+        require!(new_surplus_share_bps <= 10_000, SuperSwapError::InvalidFeeConfiguration);
+        config.surplus_share_bps = new_surplus_share_bps;
+        msg!("Surplus share BPS updated to: {}", new_surplus_share_bps);
+    }
+
+    if let Some(new_max_gas_drop_lamports) = params.new_max_gas_drop_lamports {
+        config.max_gas_drop_lamports = new_max_gas_drop_lamports;
+        msg!("Max gas drop (lamports) updated to: {}", new_max_gas_drop_lamports);
+    }
+
+    // The delay can only grow here; shortening it goes through propose_config_update
+    if let Some(new_config_update_delay_seconds) = params.new_config_update_delay_seconds {
+        require!(
+            new_config_update_delay_seconds >= config.config_update_delay_seconds,
+            SuperSwapError::InvalidConfigUpdateDelay
+        );
+        config.config_update_delay_seconds = new_config_update_delay_seconds;
+        msg!("Config update delay updated: {}", new_config_update_delay_seconds);
+    }
+
+    if let Some(new_operator) = params.new_operator {
+        config.operator = new_operator;
+        msg!("Operator updated: {}", new_operator);
+    }
+
+    if let Some(new_daily_volume_cap) = params.new_daily_volume_cap {
+        config.daily_volume_cap = new_daily_volume_cap;
+        msg!("Daily volume cap updated to: {}", new_daily_volume_cap);
+    }
+
+    if let Some(new_surplus_policy) = params.new_surplus_policy {
+        config.surplus_policy = new_surplus_policy;
+        msg!("Surplus policy updated to: {:?}", new_surplus_policy);
+    }
+
+    // Outbound limits must stay consistent (max of 0 disables the cap)
+    require!(
+        config.max_outbound_amount == 0 || config.min_outbound_amount <= config.max_outbound_amount,
+        SuperSwapError::InvalidFeeConfiguration
+    );
+    require!(config.order_size_range_valid(), SuperSwapError::InvalidOrderSizeRange);
+
+    let changes = config.changes_since(&before);
+    ctx.accounts.admin_log.record_changes(
+        ctx.accounts.admin.key(),
+        AdminAction::UpdateConfig,
+        Clock::get()?.unix_timestamp,
+        changes,
+    );
+
     Ok(())
 }
 
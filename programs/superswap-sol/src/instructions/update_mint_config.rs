@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use crate::events::MintConfigUpdated;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, MINT_CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: MintConfigParams)]
+pub struct UpdateMintConfig<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_SEED, config.instance_seed().as_ref(), params.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateMintConfig>, params: MintConfigParams) -> Result<()> {
+    let mint_config = &mut ctx.accounts.mint_config;
+    let was_enabled = mint_config.enabled;
+    mint_config.set(&params)?;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::UpdateMintConfig,
+            Clock::get()?.unix_timestamp,
+            params.mint,
+        )
+        .with_values(was_enabled, params.enabled),
+    );
+
+    emit!(MintConfigUpdated {
+        config: mint_config.config,
+        mint: params.mint,
+        enabled: params.enabled,
+        fee_bps: params.fee_bps,
+        max_order_usdc: params.max_order_usdc,
+        max_slippage_bps: params.max_slippage_bps,
+        daily_volume_cap: params.daily_volume_cap,
+    });
+
+    msg!("Mint config updated for {}", params.mint);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{CONFIG_SEED, ROUTE_STAGING_SEED};
+
+#[derive(Accounts)]
+#[instruction(params: UploadRouteChunkParams)]
+pub struct UploadRouteChunk<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Staging account, created with room for the whole route by the first chunk
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RouteStaging::space(params.total_len),
+        seeds = [
+            ROUTE_STAGING_SEED,
+            config.instance_seed().as_ref(),
+            authority.key().as_ref(),
+            params.order_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub route_staging: Account<'info, RouteStaging>,
+
+    /// Relayer that will fill the order, paying for the staging account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UploadRouteChunk>, params: UploadRouteChunkParams) -> Result<()> {
+    ctx.accounts.config.require_accepting_fills()?;
+
+    let route_staging = &mut ctx.accounts.route_staging;
+    if route_staging.authority == Pubkey::default() {
+        require!(
+            params.total_len > 0 && params.total_len <= RouteStaging::MAX_DATA_LEN,
+            SuperSwapError::RouteDataTooLarge
+        );
+        route_staging.config = ctx.accounts.config.key();
+        route_staging.order_id = params.order_id;
+        route_staging.authority = ctx.accounts.authority.key();
+        route_staging.total_len = params.total_len;
+        route_staging.finalized = false;
+        route_staging.data = Vec::with_capacity(params.total_len as usize);
+        route_staging.bump = ctx.bumps.route_staging;
+    } else {
+        require!(params.total_len == route_staging.total_len, SuperSwapError::RouteDataTooLarge);
+    }
+
+    route_staging.append(params.offset, &params.data)?;
+
+    msg!(
+        "Staged {} of {} route bytes for order {}",
+        route_staging.data.len(),
+        route_staging.total_len,
+        params.order_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SuperSwapError;
+use superswap_pda::{ADMIN_LOG_SEED, CONFIG_SEED, RENT_VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct WithdrawRentVault<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.instance_seed().as_ref()],
+        bump = config.bump,
+        has_one = admin @ SuperSwapError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Audit log recording the action
+    #[account(
+        mut,
+        seeds = [ADMIN_LOG_SEED, config.instance_seed().as_ref()],
+        bump = admin_log.bump,
+    )]
+    pub admin_log: Account<'info, AdminLog>,
+
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, config.instance_seed().as_ref()],
+        bump = rent_vault.bump,
+    )]
+    pub rent_vault: Account<'info, RentVault>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawRentVault>, params: WithdrawRentVaultParams) -> Result<()> {
+    let rent_vault = &ctx.accounts.rent_vault;
+
+    // The vault must stay rent exempt after the withdrawal
+    let min_balance = Rent::get()?.minimum_balance(RentVault::LEN);
+    let available = rent_vault
+        .get_lamports()
+        .checked_sub(min_balance)
+        .ok_or(SuperSwapError::InsufficientRentVaultBalance)?;
+    require!(
+        params.amount <= available,
+        SuperSwapError::InsufficientRentVaultBalance
+    );
+
+    rent_vault.sub_lamports(params.amount)?;
+    ctx.accounts.admin.add_lamports(params.amount)?;
+
+    ctx.accounts.admin_log.record(
+        AdminLogEntry::new(
+            ctx.accounts.admin.key(),
+            AdminAction::WithdrawRentVault,
+            Clock::get()?.unix_timestamp,
+            ctx.accounts.admin.key(),
+        )
+            .with_values(0u64, params.amount),
+    );
+
+    msg!("Withdrew {} lamports from rent vault", params.amount);
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::*;
 
 declare_id!("EzUq3vK7g8JvTLQzKvNAzBCjRz6wNJaZMWZPQVRz7nJq");
 
@@ -23,30 +25,499 @@ pub mod superswap_sol {
         instructions::update_config::handler(ctx, params)
     }
 
+    /// Take over as admin after being proposed through update_config (pending admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    /// Propose a change to the across handler, Jupiter program or fee recipient, or an
+    /// additional across handler, applicable once the config's update delay has passed (admin only)
+    pub fn propose_config_update(ctx: Context<ProposeConfigUpdate>, params: ProposeConfigUpdateParams) -> Result<()> {
+        instructions::propose_config_update::handler(ctx, params)
+    }
+
+    /// Apply a proposed config update after its delay (admin only)
+    pub fn apply_config_update(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+        instructions::apply_config_update::handler(ctx)
+    }
+
+    /// Discard a proposed config update (admin only)
+    pub fn cancel_config_update(ctx: Context<CancelConfigUpdate>) -> Result<()> {
+        instructions::cancel_config_update::handler(ctx)
+    }
+
+    /// Stop accepting an additional across handler, effective immediately (admin only)
+    pub fn remove_across_handler(ctx: Context<RemoveAcrossHandler>, params: RemoveAcrossHandlerParams) -> Result<()> {
+        instructions::remove_across_handler::handler(ctx, params)
+    }
+
     /// Process bridged USDC from Across and execute Jupiter swap
-    /// This is called by the Across handler account
-    pub fn process_bridge_and_swap(
-        ctx: Context<ProcessBridgeAndSwap>,
+    /// This is called by the Across handler account; program-owned recipients can be
+    /// notified through their `on_superswap_delivery` callback
+    /// Returns the fill's fee and delivered output, readable through simulation
+    pub fn process_bridge_and_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessBridgeAndSwap<'info>>,
         params: ProcessBridgeAndSwapParams,
-    ) -> Result<()> {
+    ) -> Result<FillResult> {
         instructions::process_bridge_and_swap::handler(ctx, params)
     }
 
-    /// Execute a Jupiter swap using provided instructions
-    /// Internal instruction used by process_bridge_and_swap
-    pub fn execute_jupiter_swap(
-        ctx: Context<ExecuteJupiterSwap>,
-        params: ExecuteJupiterSwapParams,
+    /// Upload one chunk of a Jupiter route too large for the fill transaction
+    /// The route is staged per relayer and order, and used by the fill once finalized
+    pub fn upload_route_chunk(ctx: Context<UploadRouteChunk>, params: UploadRouteChunkParams) -> Result<()> {
+        instructions::upload_route_chunk::handler(ctx, params)
+    }
+
+    /// Lock a fully uploaded route so the order's fill can execute from it
+    pub fn finalize_route(ctx: Context<FinalizeRoute>, params: FinalizeRouteParams) -> Result<()> {
+        instructions::finalize_route::handler(ctx, params)
+    }
+
+    /// Attach optional metadata (EVM origin, route hash, integrator, delivery mode) to an order
+    /// Called by the Across handler only when the bridge message carries it
+    pub fn create_order_extension(
+        ctx: Context<CreateOrderExtension>,
+        params: CreateOrderExtensionParams,
+    ) -> Result<()> {
+        instructions::create_order_extension::handler(ctx, params)
+    }
+
+    /// Attach the beneficiaries and schedule of an order's stream action (Across handler only)
+    pub fn create_stream_plan(ctx: Context<CreateStreamPlan>, params: CreateStreamPlanParams) -> Result<()> {
+        instructions::create_stream_plan::handler(ctx, params)
+    }
+
+    /// Attach the recipients and weights of an order's split action (Across handler only)
+    pub fn create_split_plan(ctx: Context<CreateSplitPlan>, params: CreateSplitPlanParams) -> Result<()> {
+        instructions::create_split_plan::handler(ctx, params)
+    }
+
+    /// Attach the output mints, weights and per-leg minimums of an order's basket (Across handler only)
+    pub fn create_basket_plan(ctx: Context<CreateBasketPlan>, params: CreateBasketPlanParams) -> Result<()> {
+        instructions::create_basket_plan::handler(ctx, params)
+    }
+
+    /// Swap one leg of a completed basket order's escrowed USDC into its mint for the beneficiary
+    /// Called by the Across handler; basket orders are filled as USDC with the escrow PDA as recipient
+    pub fn execute_basket_leg<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBasketLeg<'info>>,
+        params: ExecuteBasketLegParams,
+    ) -> Result<()> {
+        instructions::execute_basket_leg::handler(ctx, params)
+    }
+
+    /// Forward a completed order's output to its onward SVM destination (Across handler only)
+    /// Onward orders are filled with the order's escrow PDA as recipient
+    pub fn forward_onward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForwardOnward<'info>>,
+        params: ForwardOnwardParams,
+    ) -> Result<()> {
+        instructions::forward_onward::handler(ctx, params)
+    }
+
+    /// Apply a completed order's post-swap action (staking, lending, liquidity, spending, streaming, splitting, vault deposits or delegate approvals) to its escrowed output
+    /// Called by the Across handler; orders with an action are filled with the escrow PDA as recipient
+    pub fn execute_post_swap_action<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecutePostSwapAction<'info>>,
+        params: ExecutePostSwapActionParams,
+    ) -> Result<()> {
+        instructions::execute_post_swap_action::handler(ctx, params)
+    }
+
+    /// Pull a completed order's escrowed output into a token account of the claimant's choice
+    /// For smart wallets and program recipients; such orders are filled with the escrow PDA as recipient
+    pub fn claim_output(ctx: Context<ClaimOutput>, params: ClaimOutputParams) -> Result<()> {
+        instructions::claim_output::handler(ctx, params)
+    }
+
+    /// Revoke an approve-delegate order's delegate, moving what it has not pulled to the beneficiary
+    /// Signed by the beneficiary; closes the escrow's token account
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>, params: RevokeDelegateParams) -> Result<()> {
+        instructions::revoke_delegate::handler(ctx, params)
+    }
+
+    /// Release tokens held for an EVM address to the Solana wallet its key signed for
+    /// Orders from users without a Solana wallet are filled with the EVM claim PDA as recipient
+    pub fn claim_evm_funds(ctx: Context<ClaimEvmFunds>, params: ClaimEvmFundsParams) -> Result<()> {
+        instructions::claim_evm_funds::handler(ctx, params)
+    }
+
+    /// Place the bridged USDC as a Jupiter limit order at the user's price instead of swapping
+    /// Called by the Across handler; the order's escrow PDA is the maker
+    pub fn place_limit_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceLimitOrder<'info>>,
+        params: PlaceLimitOrderParams,
+    ) -> Result<()> {
+        instructions::place_limit_order::handler(ctx, params)
+    }
+
+    /// Cancel an order's Jupiter limit order and deliver its USDC and fills to the recipient
+    /// Callable by the recipient or the Across handler, and by any keeper once the order
+    /// has expired, who earns the configured refund bounty
+    pub fn cancel_limit_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelLimitOrder<'info>>,
+        params: CancelLimitOrderParams,
+    ) -> Result<()> {
+        instructions::cancel_limit_order::handler(ctx, params)
+    }
+
+    /// Schedule the bridged USDC as tranches swapped over time instead of at once
+    /// Called by the Across handler; the USDC waits in the order's escrow
+    pub fn create_dca_order(
+        ctx: Context<CreateDcaOrder>,
+        params: CreateDcaOrderParams,
+    ) -> Result<()> {
+        instructions::create_dca_order::handler(ctx, params)
+    }
+
+    /// Swap the next due DCA tranche into the recipient's account (any keeper)
+    pub fn execute_dca_tranche<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteDcaTranche<'info>>,
+        params: ExecuteDcaTrancheParams,
+    ) -> Result<()> {
+        instructions::execute_dca_tranche::handler(ctx, params)
+    }
+
+    /// Stop a DCA schedule and refund its unswapped USDC to the recipient
+    /// Callable by the recipient or the Across handler, and by any keeper once the order
+    /// is past the configured TTL, who earns the configured refund bounty
+    pub fn cancel_dca_order(ctx: Context<CancelDcaOrder>, params: CancelDcaOrderParams) -> Result<()> {
+        instructions::cancel_dca_order::handler(ctx, params)
+    }
+
+    /// Refund a DCA order's unswapped USDC to the recipient once it is past the TTL
+    /// Callable by anyone; a keeper passing its USDC account earns the configured refund bounty
+    pub fn expire_and_refund(ctx: Context<ExpireAndRefund>, params: ExpireAndRefundParams) -> Result<()> {
+        instructions::expire_and_refund::handler(ctx, params)
+    }
+
+    /// Swap any SPL token to USDC via Jupiter and bridge it to an EVM chain via Across
+    pub fn swap_and_bridge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapAndBridge<'info>>,
+        params: SwapAndBridgeParams,
+    ) -> Result<()> {
+        instructions::swap_and_bridge::handler(ctx, params)
+    }
+
+    /// Swap any SPL token to USDC via Jupiter and burn it via CCTP to an EVM domain
+    pub fn swap_and_burn<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapAndBurn<'info>>,
+        params: SwapAndBurnParams,
+    ) -> Result<()> {
+        instructions::swap_and_burn::handler(ctx, params)
+    }
+
+    /// Quote fees and the minimum receivable amount for an outbound swap
+    /// Read-only; wallets call it through transaction simulation
+    pub fn quote_outbound(
+        ctx: Context<QuoteOutbound>,
+        params: QuoteOutboundParams,
+    ) -> Result<OutboundQuote> {
+        instructions::quote_outbound::handler(ctx, params)
+    }
+
+    /// Return a snapshot of the full config as return data
+    /// Read-only; lets other programs and simulations read the config atomically
+    pub fn get_config(ctx: Context<GetConfig>) -> Result<Config> {
+        instructions::get_config::handler(ctx)
+    }
+
+    /// Mark an outbound order as delivered on the destination chain
+    /// Called by the Across handler once the fill or mint is observed
+    pub fn settle_outbound_order(
+        ctx: Context<SettleOutboundOrder>,
+        params: SettleOutboundOrderParams,
+    ) -> Result<()> {
+        instructions::settle_outbound_order::handler(ctx, params)
+    }
+
+    /// Return an outbound order's escrowed USDC to its sender
+    /// Covers USDC left behind by a failed bridge step or returned by Across
+    pub fn refund_outbound(
+        ctx: Context<RefundOutbound>,
+        params: RefundOutboundParams,
+    ) -> Result<()> {
+        instructions::refund_outbound::handler(ctx, params)
+    }
+
+    /// Close one settled order once its retention period has elapsed
+    /// Callable by anyone; the rent returns to the rent vault or payer that funded it
+    pub fn close_order(ctx: Context<CloseOrder>, params: CloseOrderParams) -> Result<()> {
+        instructions::close_order::handler(ctx, params)
+    }
+
+    /// Close Completed/Refunded orders passed as remaining accounts (admin or operator)
+    /// Reclaimed rent returns to the rent vault
+    pub fn close_orders_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseOrdersBatch<'info>>,
+    ) -> Result<()> {
+        instructions::close_orders_batch::handler(ctx)
+    }
+
+    /// Check that the USDC vault and DCA escrows cover the orders passed as remaining accounts
+    /// Fails with `Insolvent` otherwise; anyone may call it, e.g. from monitoring or ahead of a fill
+    pub fn assert_solvency<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssertSolvency<'info>>,
+    ) -> Result<()> {
+        instructions::assert_solvency::handler(ctx)
+    }
+
+    /// Close Completed/Refunded orders into the order archive (admin or operator)
+    /// Each order's record is emitted and committed to the archive's Merkle root;
+    /// reclaimed rent returns to the rent vault
+    pub fn compress_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompressOrders<'info>>,
     ) -> Result<()> {
-        instructions::execute_jupiter_swap::handler(ctx, params)
+        instructions::compress_orders::handler(ctx)
     }
 
     /// Emergency function to recover stuck funds (admin only)
+    ///
+    /// Amounts above the recovery guard's timelock threshold must go through
+    /// `queue_recovery`, and every recovery counts against its per-period cap
     pub fn recover_funds(ctx: Context<RecoverFunds>, params: RecoverFundsParams) -> Result<()> {
         instructions::recover_funds::handler(ctx, params)
     }
 
-    /// Pause the program (admin only)
+    /// Queue a recovery above the timelock threshold, executable after the guard's delay (admin only)
+    pub fn queue_recovery(ctx: Context<QueueRecovery>, params: QueueRecoveryParams) -> Result<()> {
+        instructions::queue_recovery::handler(ctx, params)
+    }
+
+    /// Execute a queued recovery once its delay has passed (admin only)
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>, params: PendingRecoveryParams) -> Result<()> {
+        instructions::execute_recovery::handler(ctx, params)
+    }
+
+    /// Drop a queued recovery (admin only)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>, params: PendingRecoveryParams) -> Result<()> {
+        instructions::cancel_recovery::handler(ctx, params)
+    }
+
+    /// Set the recovery timelock threshold, delay and per-period cap (admin only)
+    ///
+    /// Tighter limits apply at once; looser ones wait out the current delay
+    pub fn set_recovery_limits(
+        ctx: Context<SetRecoveryLimits>,
+        params: SetRecoveryLimitsParams,
+    ) -> Result<()> {
+        instructions::set_recovery_limits::handler(ctx, params)
+    }
+
+    /// Withdraw stray lamports above a program-owned account's rent-exempt minimum (admin only)
+    ///
+    /// Counted in lamports against the same recovery guard as `recover_funds`;
+    /// amounts above its timelock threshold go through `queue_lamport_recovery`
+    pub fn recover_lamports(ctx: Context<RecoverLamports>, params: RecoverLamportsParams) -> Result<()> {
+        instructions::recover_lamports::handler(ctx, params)
+    }
+
+    /// Queue a lamport recovery above the timelock threshold (admin only)
+    ///
+    /// Dropped with `cancel_recovery` like a queued token recovery
+    pub fn queue_lamport_recovery(
+        ctx: Context<QueueLamportRecovery>,
+        params: QueueLamportRecoveryParams,
+    ) -> Result<()> {
+        instructions::queue_lamport_recovery::handler(ctx, params)
+    }
+
+    /// Execute a queued lamport recovery once its delay has passed (admin only)
+    pub fn execute_lamport_recovery(
+        ctx: Context<ExecuteLamportRecovery>,
+        params: PendingRecoveryParams,
+    ) -> Result<()> {
+        instructions::execute_lamport_recovery::handler(ctx, params)
+    }
+
+    /// Pay USDC from the insurance fund to a user affected by an incident (admin only)
+    pub fn pay_insurance(ctx: Context<PayInsurance>, params: PayInsuranceParams) -> Result<()> {
+        instructions::pay_insurance::handler(ctx, params)
+    }
+
+    /// File an insurance claim for a DCA order that cannot be refunded: past the TTL,
+    /// with the recipient's USDC account frozen (recipient only)
+    pub fn file_insurance_claim(
+        ctx: Context<FileInsuranceClaim>,
+        params: InsuranceClaimParams,
+    ) -> Result<()> {
+        instructions::file_insurance_claim::handler(ctx, params)
+    }
+
+    /// Approve a filed insurance claim (admin only)
+    pub fn approve_insurance_claim(
+        ctx: Context<ApproveInsuranceClaim>,
+        params: InsuranceClaimParams,
+    ) -> Result<()> {
+        instructions::approve_insurance_claim::handler(ctx, params)
+    }
+
+    /// Pay an approved insurance claim from the fund, sweeping the order's escrow back into it (admin only)
+    pub fn pay_insurance_claim(
+        ctx: Context<PayInsuranceClaim>,
+        params: InsuranceClaimParams,
+    ) -> Result<()> {
+        instructions::pay_insurance_claim::handler(ctx, params)
+    }
+
+    /// Bridge accumulated protocol USDC to an EVM treasury via Across or CCTP (admin only)
+    pub fn rebalance_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RebalanceTreasury<'info>>,
+        params: RebalanceTreasuryParams,
+    ) -> Result<()> {
+        instructions::rebalance_treasury::handler(ctx, params)
+    }
+
+    /// Set the outbound per-period volume cap (admin only)
+    pub fn set_outbound_rate_limit(
+        ctx: Context<SetOutboundRateLimit>,
+        params: SetOutboundRateLimitParams,
+    ) -> Result<()> {
+        instructions::set_outbound_rate_limit::handler(ctx, params)
+    }
+
+    /// Register or update the signer's delivery preferences, consulted by fills addressed to them
+    pub fn set_recipient_preferences(
+        ctx: Context<SetRecipientPreferences>,
+        params: SetRecipientPreferencesParams,
+    ) -> Result<()> {
+        instructions::set_recipient_preferences::handler(ctx, params)
+    }
+
+    /// Allowlist a post-swap hook program (admin only)
+    pub fn add_hook_program(ctx: Context<AddHookProgram>, params: HookProgramParams) -> Result<()> {
+        instructions::add_hook_program::handler(ctx, params)
+    }
+
+    /// Remove a post-swap hook program from the allowlist (admin only)
+    pub fn remove_hook_program(ctx: Context<RemoveHookProgram>, params: HookProgramParams) -> Result<()> {
+        instructions::remove_hook_program::handler(ctx, params)
+    }
+
+    /// Allowlist a spend program that post-swap spend actions may hand output to (admin only)
+    pub fn add_spend_program(ctx: Context<AddSpendProgram>, params: SpendProgramParams) -> Result<()> {
+        instructions::add_spend_program::handler(ctx, params)
+    }
+
+    /// Remove a spend program from the allowlist (admin only)
+    pub fn remove_spend_program(ctx: Context<RemoveSpendProgram>, params: SpendProgramParams) -> Result<()> {
+        instructions::remove_spend_program::handler(ctx, params)
+    }
+
+    /// Allowlist a 4626-style vault program that vault deposit actions may use (admin only)
+    pub fn add_vault_program(ctx: Context<AddVaultProgram>, params: VaultProgramParams) -> Result<()> {
+        instructions::add_vault_program::handler(ctx, params)
+    }
+
+    /// Remove a vault program from the allowlist (admin only)
+    pub fn remove_vault_program(ctx: Context<RemoveVaultProgram>, params: VaultProgramParams) -> Result<()> {
+        instructions::remove_vault_program::handler(ctx, params)
+    }
+
+    /// Allowlist a delegate that approve-delegate actions may grant an allowance to (admin only)
+    pub fn add_approved_delegate(ctx: Context<AddApprovedDelegate>, params: ApprovedDelegateParams) -> Result<()> {
+        instructions::add_approved_delegate::handler(ctx, params)
+    }
+
+    /// Remove a delegate from the allowlist (admin only)
+    pub fn remove_approved_delegate(
+        ctx: Context<RemoveApprovedDelegate>,
+        params: ApprovedDelegateParams,
+    ) -> Result<()> {
+        instructions::remove_approved_delegate::handler(ctx, params)
+    }
+
+    /// Allowlist an address that orders may route a donation split to (admin only)
+    pub fn add_donation_recipient(
+        ctx: Context<AddDonationRecipient>,
+        params: DonationRecipientParams,
+    ) -> Result<()> {
+        instructions::add_donation_recipient::handler(ctx, params)
+    }
+
+    /// Remove a donation recipient from the allowlist (admin only)
+    pub fn remove_donation_recipient(
+        ctx: Context<RemoveDonationRecipient>,
+        params: DonationRecipientParams,
+    ) -> Result<()> {
+        instructions::remove_donation_recipient::handler(ctx, params)
+    }
+
+    /// Register an operator that may pay for fills the Across handler signs (admin only)
+    pub fn add_payer_operator(ctx: Context<AddPayerOperator>, params: PayerOperatorParams) -> Result<()> {
+        instructions::add_payer_operator::handler(ctx, params)
+    }
+
+    /// Remove a settled payer operator (admin only)
+    pub fn remove_payer_operator(
+        ctx: Context<RemovePayerOperator>,
+        params: PayerOperatorParams,
+    ) -> Result<()> {
+        instructions::remove_payer_operator::handler(ctx, params)
+    }
+
+    /// Pay a payer operator what its ledger is owed out of collected fees (fee recipient only)
+    pub fn settle_payer_ledger(ctx: Context<SettlePayerLedger>) -> Result<()> {
+        instructions::settle_payer_ledger::handler(ctx)
+    }
+
+    /// Disable or re-enable fills into a destination mint without pausing the program (admin only)
+    pub fn set_destination_mint(
+        ctx: Context<SetDestinationMint>,
+        params: SetDestinationMintParams,
+    ) -> Result<()> {
+        instructions::set_destination_mint::handler(ctx, params)
+    }
+
+    /// Create an independent deployment instance with its own config and orders (primary admin only)
+    pub fn create_instance(ctx: Context<CreateInstance>, params: CreateInstanceParams) -> Result<()> {
+        instructions::create_instance::handler(ctx, params)
+    }
+
+    /// Create the admin log of a config initialized before the log existed (admin only)
+    pub fn create_admin_log(ctx: Context<CreateAdminLog>) -> Result<()> {
+        instructions::create_admin_log::handler(ctx)
+    }
+
+    /// Register the vetted route shape for a high-volume pair (admin only)
+    pub fn register_route_template(
+        ctx: Context<RegisterRouteTemplate>,
+        params: RegisterRouteTemplateParams,
+    ) -> Result<()> {
+        instructions::register_route_template::handler(ctx, params)
+    }
+
+    /// Remove a pair's route template (admin only)
+    pub fn remove_route_template(ctx: Context<RemoveRouteTemplate>, params: RemoveRouteTemplateParams) -> Result<()> {
+        instructions::remove_route_template::handler(ctx, params)
+    }
+
+    /// Create the overrides applied to fills into a destination mint (admin only)
+    pub fn create_mint_config(ctx: Context<CreateMintConfig>, params: MintConfigParams) -> Result<()> {
+        instructions::create_mint_config::handler(ctx, params)
+    }
+
+    /// Update a destination mint's overrides (admin only)
+    pub fn update_mint_config(ctx: Context<UpdateMintConfig>, params: MintConfigParams) -> Result<()> {
+        instructions::update_mint_config::handler(ctx, params)
+    }
+
+    /// Delete a destination mint's overrides, returning it to the config's settings (admin only)
+    pub fn delete_mint_config(ctx: Context<DeleteMintConfig>, params: DeleteMintConfigParams) -> Result<()> {
+        instructions::delete_mint_config::handler(ctx, params)
+    }
+
+    /// Create the tracker counting daily fill volume into a mint, or across all mints (admin only)
+    pub fn create_volume_tracker(ctx: Context<CreateVolumeTracker>, params: CreateVolumeTrackerParams) -> Result<()> {
+        instructions::create_volume_tracker::handler(ctx, params)
+    }
+
+    /// Copy a V1 or version 2 config into the current layout, keeping its PDA (admin only)
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
+    }
+
+    /// Pause the program (admin or guardian)
     pub fn pause(ctx: Context<Pause>) -> Result<()> {
         instructions::pause::handler(ctx)
     }
@@ -55,4 +526,23 @@ pub mod superswap_sol {
     pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
         instructions::unpause::handler(ctx)
     }
+
+    /// Enter (admin or guardian) or leave (admin only) refunds-only mode, in which
+    /// new fills are rejected while refunds, claims and closures keep working
+    pub fn set_refunds_only(ctx: Context<SetRefundsOnly>, params: SetRefundsOnlyParams) -> Result<()> {
+        instructions::set_refunds_only::handler(ctx, params)
+    }
+
+    /// Top up the rent vault that pays for order and ATA creation (admin or operator)
+    pub fn fund_rent_vault(ctx: Context<FundRentVault>, params: FundRentVaultParams) -> Result<()> {
+        instructions::fund_rent_vault::handler(ctx, params)
+    }
+
+    /// Withdraw surplus lamports from the rent vault (admin only)
+    pub fn withdraw_rent_vault(
+        ctx: Context<WithdrawRentVault>,
+        params: WithdrawRentVaultParams,
+    ) -> Result<()> {
+        instructions::withdraw_rent_vault::handler(ctx, params)
+    }
 }
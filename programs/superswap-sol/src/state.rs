@@ -1,4 +1,25 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::error::SuperSwapError;
+
+/// `(name, old, new)` of every listed field that differs between `$before` and `$after`,
+/// for `AdminLog::record_changes`
+macro_rules! setting_changes {
+    ($before:expr, $after:expr; $($field:ident),* $(,)?) => {{
+        let mut changes: Vec<(&str, [u8; 32], [u8; 32])> = Vec::new();
+        $(
+            if $before.$field != $after.$field {
+                changes.push((
+                    stringify!($field),
+                    $crate::state::AdminLogValue::to_log_value(&$before.$field),
+                    $crate::state::AdminLogValue::to_log_value(&$after.$field),
+                ));
+            }
+        )*
+        changes
+    }};
+}
+pub(crate) use setting_changes;
 
 /// Global configuration for the SuperSwap program
 #[account]
@@ -12,6 +33,12 @@ pub struct Config {
     /// Jupiter program ID for swaps
     pub jupiter_program: Pubkey,
     
+    /// Across spoke pool program ID for outbound deposits
+    pub across_spoke_pool: Pubkey,
+    
+    /// CCTP TokenMessengerMinter program ID for outbound burns
+    pub cctp_token_messenger_minter: Pubkey,
+    
     /// USDC mint address on Solana
     pub usdc_mint: Pubkey,
     
@@ -21,6 +48,138 @@ pub struct Config {
     /// Fee in basis points (1 bp = 0.01%)
     pub fee_bps: u16,
     
+    /// Fee in basis points charged on outbound swaps
+    pub outbound_fee_bps: u16,
+    
+    /// Minimum USDC an outbound swap must produce
+    pub min_outbound_amount: u64,
+    
+    /// Maximum USDC an outbound swap may produce (0 = no cap)
+    pub max_outbound_amount: u64,
+    
+    /// Estimated Across relayer fee in basis points, used for quotes only
+    pub across_fee_bps_estimate: u16,
+    
+    /// Estimated CCTP fee in basis points, used for quotes only
+    pub cctp_fee_bps_estimate: u16,
+    
+    /// Messaging adapter (Hyperlane warp route program) for onward SVM bridging;
+    /// the default pubkey disables onward forwarding
+    pub onward_adapter: Pubkey,
+    
+    /// Jito stake pool used by the stake post-swap action (default = disabled)
+    pub jito_stake_pool: Pubkey,
+    
+    /// Marinade state account used by the stake post-swap action (default = disabled)
+    pub marinade_state: Pubkey,
+    
+    /// Kamino lending market used by the lend post-swap action (default = disabled)
+    pub kamino_lending_market: Pubkey,
+    
+    /// Solend lending market used by the lend post-swap action (default = disabled)
+    pub solend_lending_market: Pubkey,
+    
+    /// Maximum USDC an order may convert into SOL for the recipient's gas (0 = disabled)
+    pub max_gas_top_up_usdc: u64,
+    
+    /// Cap on the share of an order routed to a donation recipient, in basis points (0 = disabled)
+    pub max_donation_bps: u16,
+    
+    /// Share of an expired order's returned USDC paid to the keeper that refunds it,
+    /// in basis points (0 = no bounty)
+    pub refund_bounty_bps: u16,
+    
+    /// Smallest USDC amount an inbound order may carry (0 = no minimum)
+    pub min_order_usdc: u64,
+    
+    /// Deliver fills below `min_order_usdc` as USDC instead of rejecting them
+    pub deliver_dust_as_usdc: bool,
+    
+    /// Most DCA and limit orders a recipient may hold in escrow at once (0 = no cap)
+    pub max_open_orders_per_recipient: u16,
+    
+    /// Largest shortfall of realized output against the route's quoted output, in basis points (0 = disabled)
+    pub max_price_impact_bps: u16,
+    
+    /// Age in seconds after which DCA and limit orders can only be refunded, whatever their own deadline (0 = no TTL)
+    pub order_ttl_seconds: u32,
+    
+    /// USDC an order escrow may hold below its ledger before fills auto-pause
+    pub escrow_shortfall_tolerance: u64,
+    
+    /// Whether Across flows are paused, inbound and outbound, while the rest keep running
+    pub across_paused: bool,
+    
+    /// Whether CCTP burns are paused while the rest keep running
+    pub cctp_paused: bool,
+    
+    /// Share of every protocol fee paid into the insurance fund, in basis points of the fee
+    pub insurance_fee_share_bps: u16,
+    
+    /// Largest insurance claim a recipient can file for an unrefundable order (0 = claims disabled)
+    pub max_insurance_claim_usdc: u64,
+    
+    /// Asset delivered when an order's destination mint is disabled before its fill: the USDC mint, the native mint, or default for none
+    pub fallback_output_mint: Pubkey,
+    
+    /// Receives the rent reclaimed from closed orders instead of whoever funded it (default = none)
+    pub rent_treasury: Pubkey,
+    
+    /// Seconds after creation before a settled order may be closed, its dispute window (0 = none)
+    pub min_retention_seconds: u32,
+    
+    /// USDC credited to a registered payer operator's ledger for each fill it pays for
+    pub operator_fill_cost_usdc: u64,
+    
+    /// Layout version, `Config::VERSION` once created or migrated by this program
+    pub version: u8,
+    
+    /// Account that may pause the program, but not unpause it, besides the admin (default = admin only)
+    pub guardian: Pubkey,
+    
+    /// Largest USDC amount an inbound order may carry (0 = no cap)
+    pub max_order_usdc: u64,
+    
+    /// Bridge adapters taking new orders, a bitmask of `Config::ADAPTER_*`
+    pub enabled_adapters: u8,
+    
+    /// Authority owning the program's USDC vault, the config PDA itself
+    pub vault_authority: Pubkey,
+    
+    /// Deployment this config belongs to; 0 is the primary one at `["config"]`
+    pub instance_id: u16,
+    
+    /// Whether only refunds, claims and closures are accepted, with new fills rejected
+    pub refunds_only: bool,
+    
+    /// Largest tip an order may pay its filling relayer, in USDC base units; 0 disables tips
+    pub max_relayer_tip_usdc: u64,
+    
+    /// Share of output above the route's quote kept by the protocol, in basis points (0 = all to the recipient)
+    pub surplus_share_bps: u16,
+    
+    /// Largest gas drop a fill may send a fresh recipient, in lamports; 0 disables gas drops
+    pub max_gas_drop_lamports: u64,
+    
+    /// Admin proposed through update_config, taking over once it signs accept_admin; default when none is pending
+    pub pending_admin: Pubkey,
+    
+    /// Seconds a proposed change to the across handler, Jupiter program or fee recipient waits before it can be applied
+    pub config_update_delay_seconds: i64,
+    
+    /// Account that may run operational sweeps (order closing and compression, rent vault top-ups) besides the admin (default = admin only)
+    pub operator: Pubkey,
+    
+    /// Handlers accepted besides `across_handler`, e.g. the next key during a handler upgrade;
+    /// free slots hold the default pubkey
+    pub additional_across_handlers: [Pubkey; Config::MAX_ADDITIONAL_ACROSS_HANDLERS],
+    
+    /// Most USDC filled inbound per UTC day across all mints, tracked by the global volume tracker (0 = no cap)
+    pub daily_volume_cap: u64,
+    
+    /// Where the `surplus_share_bps` share of output above the route's quote goes
+    pub surplus_policy: SurplusPolicy,
+    
     /// Whether the program is paused
     pub is_paused: bool,
     
@@ -29,18 +188,537 @@ pub struct Config {
 }
 
 impl Config {
+    /// Upper bound on `refund_bounty_bps`
+    pub const MAX_REFUND_BOUNTY_BPS: u16 = 100;
+
+    /// Capacity of `additional_across_handlers`
+    pub const MAX_ADDITIONAL_ACROSS_HANDLERS: usize = 4;
+
+    /// Current layout version
+    pub const VERSION: u8 = 3;
+
+    /// `enabled_adapters` bit for Across, inbound fills and outbound deposits
+    pub const ADAPTER_ACROSS: u8 = 1 << 0;
+    /// `enabled_adapters` bit for outbound CCTP burns
+    pub const ADAPTER_CCTP: u8 = 1 << 1;
+    /// `enabled_adapters` bit for onward Hyperlane forwarding
+    pub const ADAPTER_HYPERLANE: u8 = 1 << 2;
+    pub const ADAPTER_ALL: u8 = Self::ADAPTER_ACROSS | Self::ADAPTER_CCTP | Self::ADAPTER_HYPERLANE;
+    /// Adapters built into this binary by the crate's adapter features; Across
+    /// inbound fills are always built, so its bit is always set
+    pub const ADAPTERS_BUILT: u8 = Self::ADAPTER_ACROSS
+        | if cfg!(feature = "cctp") { Self::ADAPTER_CCTP } else { 0 }
+        | if cfg!(feature = "hyperlane") { Self::ADAPTER_HYPERLANE } else { 0 };
+
+    /// Whether `key` may relay as an Across handler: the config's handler or an additional one
+    pub fn is_across_handler(&self, key: &Pubkey) -> bool {
+        *key == self.across_handler
+            || (*key != Pubkey::default() && self.additional_across_handlers.contains(key))
+    }
+
+    /// Checks `handler` can be registered as an additional Across handler
+    pub fn require_can_add_across_handler(&self, handler: &Pubkey) -> Result<()> {
+        require!(
+            *handler != Pubkey::default() && !self.is_across_handler(handler),
+            SuperSwapError::AcrossHandlerAlreadyRegistered
+        );
+        require!(
+            self.additional_across_handlers.contains(&Pubkey::default()),
+            SuperSwapError::AcrossHandlerLimitReached
+        );
+        Ok(())
+    }
+
+    /// Registers `handler` in the first free additional handler slot
+    pub fn add_across_handler(&mut self, handler: Pubkey) -> Result<()> {
+        self.require_can_add_across_handler(&handler)?;
+        if let Some(slot) = self
+            .additional_across_handlers
+            .iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+        {
+            *slot = handler;
+        }
+        Ok(())
+    }
+
+    /// Frees the additional handler slot holding `handler`
+    pub fn remove_across_handler(&mut self, handler: &Pubkey) -> Result<()> {
+        let slot = self
+            .additional_across_handlers
+            .iter_mut()
+            .find(|slot| *slot == handler && *handler != Pubkey::default())
+            .ok_or(SuperSwapError::AcrossHandlerNotRegistered)?;
+        *slot = Pubkey::default();
+        Ok(())
+    }
+
+    /// Additional Across handlers currently registered
+    pub fn registered_additional_across_handlers(&self) -> Vec<Pubkey> {
+        self.additional_across_handlers
+            .iter()
+            .filter(|handler| **handler != Pubkey::default())
+            .copied()
+            .collect()
+    }
+
+    /// Whether `key` may pause the program or enter refunds-only mode: the admin,
+    /// or the guardian when one is set
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        *key == self.admin || (self.guardian != Pubkey::default() && *key == self.guardian)
+    }
+
+    /// Whether `key` may run operational sweeps: the admin, or the operator when one is set
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        *key == self.admin || (self.operator != Pubkey::default() && *key == self.operator)
+    }
+
+    /// Settings `update_config` changed relative to `before`, for the admin log
+    pub fn changes_since(&self, before: &Config) -> Vec<(&'static str, [u8; 32], [u8; 32])> {
+        setting_changes!(before, self;
+            admin,
+            pending_admin,
+            across_handler,
+            jupiter_program,
+            across_spoke_pool,
+            cctp_token_messenger_minter,
+            fee_recipient,
+            fee_bps,
+            outbound_fee_bps,
+            min_outbound_amount,
+            max_outbound_amount,
+            across_fee_bps_estimate,
+            cctp_fee_bps_estimate,
+            onward_adapter,
+            jito_stake_pool,
+            marinade_state,
+            kamino_lending_market,
+            solend_lending_market,
+            max_gas_top_up_usdc,
+            max_donation_bps,
+            refund_bounty_bps,
+            min_order_usdc,
+            deliver_dust_as_usdc,
+            max_open_orders_per_recipient,
+            max_price_impact_bps,
+            order_ttl_seconds,
+            escrow_shortfall_tolerance,
+            across_paused,
+            cctp_paused,
+            insurance_fee_share_bps,
+            max_insurance_claim_usdc,
+            fallback_output_mint,
+            rent_treasury,
+            min_retention_seconds,
+            operator_fill_cost_usdc,
+            guardian,
+            max_order_usdc,
+            enabled_adapters,
+            max_relayer_tip_usdc,
+            surplus_share_bps,
+            max_gas_drop_lamports,
+            config_update_delay_seconds,
+            operator,
+            daily_volume_cap,
+            surplus_policy,
+        )
+    }
+
+    /// Whether every adapter in `adapters` takes new orders
+    pub fn is_adapter_enabled(&self, adapters: u8) -> bool {
+        self.enabled_adapters & adapters == adapters
+    }
+
+    /// Seed scoping this instance's config and order PDAs, empty for the primary instance
+    pub fn instance_seed(&self) -> Vec<u8> {
+        superswap_pda::instance_seed(self.instance_id)
+    }
+
+    /// Whether an inbound order of `usdc_amount` exceeds the configured maximum
+    pub fn exceeds_max_order(&self, usdc_amount: u64) -> bool {
+        self.max_order_usdc != 0 && usdc_amount > self.max_order_usdc
+    }
+
+    /// Price impact bound for a fill under a mint limit of `mint_max_slippage_bps`
+    ///
+    /// The stricter of the two applies; either set to 0 leaves the other alone.
+    pub fn max_price_impact_bps_with(&self, mint_max_slippage_bps: u16) -> u16 {
+        match (self.max_price_impact_bps, mint_max_slippage_bps) {
+            (0, bps) | (bps, 0) => bps,
+            (config_bps, mint_bps) => config_bps.min(mint_bps),
+        }
+    }
+
+    /// Whether swapped fills split off a share of their output above the quote
+    pub fn captures_surplus(&self) -> bool {
+        self.surplus_share_bps > 0 && self.surplus_policy != SurplusPolicy::Recipient
+    }
+
+    /// Fails unless new fills are accepted: the program is neither paused nor refunds-only
+    pub fn require_accepting_fills(&self) -> Result<()> {
+        require!(!self.is_paused, SuperSwapError::ProgramPaused);
+        require!(!self.refunds_only, SuperSwapError::RefundsOnly);
+        Ok(())
+    }
+
+    /// Fails while paused; refunds, claims and closures stay open in refunds-only mode
+    pub fn require_not_paused(&self) -> Result<()> {
+        require!(!self.is_paused, SuperSwapError::ProgramPaused);
+        Ok(())
+    }
+
+    /// Whether an escrow holding `actual` USDC against a ledger of `expected` is short
+    /// by more than the tolerance, a sign of an accounting bug or exploit
+    pub fn escrow_shortfall_exceeded(&self, expected: u64, actual: u64) -> bool {
+        expected.saturating_sub(actual) > self.escrow_shortfall_tolerance
+    }
+
+    /// Whether `mint` may be configured as the fallback output: USDC, the native mint, or default for none
+    pub fn is_valid_fallback_output_mint(&self, mint: &Pubkey) -> bool {
+        *mint == Pubkey::default()
+            || *mint == self.usdc_mint
+            || *mint == anchor_spl::token::spl_token::native_mint::ID
+    }
+
+    /// Whether an inbound order of `usdc_amount` falls below the configured minimum
+    pub fn is_dust_order(&self, usdc_amount: u64) -> bool {
+        usdc_amount < self.min_order_usdc
+    }
+
+    /// Whether the inbound order size bounds are consistent (a maximum of 0 disables the cap)
+    pub fn order_size_range_valid(&self) -> bool {
+        self.max_order_usdc == 0 || self.min_order_usdc <= self.max_order_usdc
+    }
+
+    /// Checks an outbound swap output against the configured limits
+    pub fn outbound_amount_in_range(&self, amount: u64) -> bool {
+        amount >= self.min_outbound_amount
+            && (self.max_outbound_amount == 0 || amount <= self.max_outbound_amount)
+    }
+
+    /// Copies a V1 config into the current layout; settings V1 lacked start at their
+    /// `initialize` defaults, so behaviour is unchanged until the admin sets them
+    pub fn from_v1(v1: ConfigV1, vault_authority: Pubkey) -> Self {
+        Self {
+            admin: v1.admin,
+            across_handler: v1.across_handler,
+            jupiter_program: v1.jupiter_program,
+            across_spoke_pool: Pubkey::default(),
+            cctp_token_messenger_minter: Pubkey::default(),
+            usdc_mint: v1.usdc_mint,
+            fee_recipient: v1.fee_recipient,
+            fee_bps: v1.fee_bps,
+            outbound_fee_bps: 0,
+            min_outbound_amount: 0,
+            max_outbound_amount: 0,
+            across_fee_bps_estimate: 0,
+            cctp_fee_bps_estimate: 0,
+            onward_adapter: Pubkey::default(),
+            jito_stake_pool: Pubkey::default(),
+            marinade_state: Pubkey::default(),
+            kamino_lending_market: Pubkey::default(),
+            solend_lending_market: Pubkey::default(),
+            max_gas_top_up_usdc: 0,
+            max_donation_bps: 0,
+            refund_bounty_bps: 0,
+            min_order_usdc: 0,
+            deliver_dust_as_usdc: false,
+            max_open_orders_per_recipient: 0,
+            max_price_impact_bps: 0,
+            order_ttl_seconds: 0,
+            escrow_shortfall_tolerance: 0,
+            across_paused: false,
+            cctp_paused: false,
+            insurance_fee_share_bps: 0,
+            max_insurance_claim_usdc: 0,
+            fallback_output_mint: Pubkey::default(),
+            rent_treasury: Pubkey::default(),
+            min_retention_seconds: 0,
+            operator_fill_cost_usdc: 0,
+            version: Config::VERSION,
+            guardian: Pubkey::default(),
+            max_order_usdc: 0,
+            enabled_adapters: Config::ADAPTERS_BUILT,
+            vault_authority,
+            instance_id: 0,
+            refunds_only: false,
+            max_relayer_tip_usdc: 0,
+            surplus_share_bps: 0,
+            max_gas_drop_lamports: 0,
+            pending_admin: Pubkey::default(),
+            config_update_delay_seconds: 0,
+            operator: Pubkey::default(),
+            additional_across_handlers: [Pubkey::default(); Config::MAX_ADDITIONAL_ACROSS_HANDLERS],
+            daily_volume_cap: 0,
+            surplus_policy: SurplusPolicy::Recipient,
+            is_paused: v1.is_paused,
+            bump: v1.bump,
+        }
+    }
+
+    /// Reads a V1 config account's data, discriminator included
+    pub fn v1_from_account_data(data: &[u8]) -> Result<ConfigV1> {
+        require!(data.len() == ConfigV1::LEN, SuperSwapError::ConfigNotMigratable);
+        require!(
+            data[..8] == <Config as anchor_lang::Discriminator>::DISCRIMINATOR,
+            SuperSwapError::ConfigNotMigratable
+        );
+        Ok(ConfigV1::deserialize(&mut &data[8..])?)
+    }
+
+    /// Copies a version 2 config into the current layout; the settings version 3 added
+    /// start at their `initialize` defaults
+    pub fn from_v2(v2: ConfigV2) -> Self {
+        Self {
+            admin: v2.admin,
+            across_handler: v2.across_handler,
+            jupiter_program: v2.jupiter_program,
+            across_spoke_pool: v2.across_spoke_pool,
+            cctp_token_messenger_minter: v2.cctp_token_messenger_minter,
+            usdc_mint: v2.usdc_mint,
+            fee_recipient: v2.fee_recipient,
+            fee_bps: v2.fee_bps,
+            outbound_fee_bps: v2.outbound_fee_bps,
+            min_outbound_amount: v2.min_outbound_amount,
+            max_outbound_amount: v2.max_outbound_amount,
+            across_fee_bps_estimate: v2.across_fee_bps_estimate,
+            cctp_fee_bps_estimate: v2.cctp_fee_bps_estimate,
+            onward_adapter: v2.onward_adapter,
+            jito_stake_pool: v2.jito_stake_pool,
+            marinade_state: v2.marinade_state,
+            kamino_lending_market: v2.kamino_lending_market,
+            solend_lending_market: v2.solend_lending_market,
+            max_gas_top_up_usdc: v2.max_gas_top_up_usdc,
+            max_donation_bps: v2.max_donation_bps,
+            refund_bounty_bps: v2.refund_bounty_bps,
+            min_order_usdc: v2.min_order_usdc,
+            deliver_dust_as_usdc: v2.deliver_dust_as_usdc,
+            max_open_orders_per_recipient: v2.max_open_orders_per_recipient,
+            max_price_impact_bps: v2.max_price_impact_bps,
+            order_ttl_seconds: v2.order_ttl_seconds,
+            escrow_shortfall_tolerance: v2.escrow_shortfall_tolerance,
+            across_paused: v2.across_paused,
+            cctp_paused: v2.cctp_paused,
+            insurance_fee_share_bps: v2.insurance_fee_share_bps,
+            max_insurance_claim_usdc: v2.max_insurance_claim_usdc,
+            fallback_output_mint: v2.fallback_output_mint,
+            rent_treasury: v2.rent_treasury,
+            min_retention_seconds: v2.min_retention_seconds,
+            operator_fill_cost_usdc: v2.operator_fill_cost_usdc,
+            version: Config::VERSION,
+            guardian: v2.guardian,
+            max_order_usdc: v2.max_order_usdc,
+            enabled_adapters: v2.enabled_adapters,
+            vault_authority: v2.vault_authority,
+            instance_id: 0,
+            refunds_only: false,
+            max_relayer_tip_usdc: 0,
+            surplus_share_bps: 0,
+            max_gas_drop_lamports: 0,
+            pending_admin: Pubkey::default(),
+            config_update_delay_seconds: 0,
+            operator: Pubkey::default(),
+            additional_across_handlers: [Pubkey::default(); Config::MAX_ADDITIONAL_ACROSS_HANDLERS],
+            daily_volume_cap: 0,
+            // Version 2 had no surplus capture, so the recipient kept all of it
+            surplus_policy: SurplusPolicy::Recipient,
+            is_paused: v2.is_paused,
+            bump: v2.bump,
+        }
+    }
+
+    /// Reads a version 2 config account's data, discriminator included
+    pub fn v2_from_account_data(data: &[u8]) -> Result<ConfigV2> {
+        require!(data.len() == ConfigV2::LEN, SuperSwapError::ConfigNotMigratable);
+        require!(
+            data[..8] == <Config as anchor_lang::Discriminator>::DISCRIMINATOR,
+            SuperSwapError::ConfigNotMigratable
+        );
+        let v2 = ConfigV2::deserialize(&mut &data[8..])?;
+        require!(v2.version == 2, SuperSwapError::ConfigNotMigratable);
+        Ok(v2)
+    }
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // across_handler
+        32 + // jupiter_program
+        32 + // across_spoke_pool
+        32 + // cctp_token_messenger_minter
+        32 + // usdc_mint
+        32 + // fee_recipient
+        2 + // fee_bps
+        2 + // outbound_fee_bps
+        8 + // min_outbound_amount
+        8 + // max_outbound_amount
+        2 + // across_fee_bps_estimate
+        2 + // cctp_fee_bps_estimate
+        32 + // onward_adapter
+        32 + // jito_stake_pool
+        32 + // marinade_state
+        32 + // kamino_lending_market
+        32 + // solend_lending_market
+        8 + // max_gas_top_up_usdc
+        2 + // max_donation_bps
+        2 + // refund_bounty_bps
+        8 + // min_order_usdc
+        1 + // deliver_dust_as_usdc
+        2 + // max_open_orders_per_recipient
+        2 + // max_price_impact_bps
+        4 + // order_ttl_seconds
+        8 + // escrow_shortfall_tolerance
+        1 + // across_paused
+        1 + // cctp_paused
+        2 + // insurance_fee_share_bps
+        8 + // max_insurance_claim_usdc
+        32 + // fallback_output_mint
+        32 + // rent_treasury
+        4 + // min_retention_seconds
+        8 + // operator_fill_cost_usdc
+        1 + // version
+        32 + // guardian
+        8 + // max_order_usdc
+        1 + // enabled_adapters
+        32 + // vault_authority
+        2 + // instance_id
+        1 + // refunds_only
+        8 + // max_relayer_tip_usdc
+        2 + // surplus_share_bps
+        8 + // max_gas_drop_lamports
+        32 + // pending_admin
+        8 + // config_update_delay_seconds
+        32 + // operator
+        32 * Config::MAX_ADDITIONAL_ACROSS_HANDLERS + // additional_across_handlers
+        8 + // daily_volume_cap
+        1 + // surplus_policy
+        1 + // is_paused
+        1; // bump
+}
+
+/// Config layout of the first deployment, before `version` was added, kept to read
+/// accounts `migrate_config` upgrades
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigV1 {
+    pub admin: Pubkey,
+    pub across_handler: Pubkey,
+    pub jupiter_program: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fee_bps: u16,
+    pub is_paused: bool,
+    pub bump: u8,
+}
+
+impl ConfigV1 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // across_handler
+        32 + // jupiter_program
+        32 + // usdc_mint
+        32 + // fee_recipient
+        2 + // fee_bps
+        1 + // is_paused
+        1; // bump
+}
+
+/// Config layout at version 2, before the settings version 3 added, kept to read
+/// accounts `migrate_config` upgrades
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigV2 {
+    pub admin: Pubkey,
+    pub across_handler: Pubkey,
+    pub jupiter_program: Pubkey,
+    pub across_spoke_pool: Pubkey,
+    pub cctp_token_messenger_minter: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fee_bps: u16,
+    pub outbound_fee_bps: u16,
+    pub min_outbound_amount: u64,
+    pub max_outbound_amount: u64,
+    pub across_fee_bps_estimate: u16,
+    pub cctp_fee_bps_estimate: u16,
+    pub onward_adapter: Pubkey,
+    pub jito_stake_pool: Pubkey,
+    pub marinade_state: Pubkey,
+    pub kamino_lending_market: Pubkey,
+    pub solend_lending_market: Pubkey,
+    pub max_gas_top_up_usdc: u64,
+    pub max_donation_bps: u16,
+    pub refund_bounty_bps: u16,
+    pub min_order_usdc: u64,
+    pub deliver_dust_as_usdc: bool,
+    pub max_open_orders_per_recipient: u16,
+    pub max_price_impact_bps: u16,
+    pub order_ttl_seconds: u32,
+    pub escrow_shortfall_tolerance: u64,
+    pub across_paused: bool,
+    pub cctp_paused: bool,
+    pub insurance_fee_share_bps: u16,
+    pub max_insurance_claim_usdc: u64,
+    pub fallback_output_mint: Pubkey,
+    pub rent_treasury: Pubkey,
+    pub min_retention_seconds: u32,
+    pub operator_fill_cost_usdc: u64,
+    pub version: u8,
+    pub guardian: Pubkey,
+    pub max_order_usdc: u64,
+    pub enabled_adapters: u8,
+    pub vault_authority: Pubkey,
+    pub is_paused: bool,
+    pub bump: u8,
+}
+
+impl ConfigV2 {
     pub const LEN: usize = 8 + // discriminator
         32 + // admin
         32 + // across_handler
         32 + // jupiter_program
+        32 + // across_spoke_pool
+        32 + // cctp_token_messenger_minter
         32 + // usdc_mint
         32 + // fee_recipient
         2 + // fee_bps
+        2 + // outbound_fee_bps
+        8 + // min_outbound_amount
+        8 + // max_outbound_amount
+        2 + // across_fee_bps_estimate
+        2 + // cctp_fee_bps_estimate
+        32 + // onward_adapter
+        32 + // jito_stake_pool
+        32 + // marinade_state
+        32 + // kamino_lending_market
+        32 + // solend_lending_market
+        8 + // max_gas_top_up_usdc
+        2 + // max_donation_bps
+        2 + // refund_bounty_bps
+        8 + // min_order_usdc
+        1 + // deliver_dust_as_usdc
+        2 + // max_open_orders_per_recipient
+        2 + // max_price_impact_bps
+        4 + // order_ttl_seconds
+        8 + // escrow_shortfall_tolerance
+        1 + // across_paused
+        1 + // cctp_paused
+        2 + // insurance_fee_share_bps
+        8 + // max_insurance_claim_usdc
+        32 + // fallback_output_mint
+        32 + // rent_treasury
+        4 + // min_retention_seconds
+        8 + // operator_fill_cost_usdc
+        1 + // version
+        32 + // guardian
+        8 + // max_order_usdc
+        1 + // enabled_adapters
+        32 + // vault_authority
         1 + // is_paused
         1; // bump
 }
 
 /// Represents a swap order being processed
+///
+/// Only the fields needed to execute or refund the order live here;
+/// optional metadata goes in an `OrderExtension`.
 #[account]
 pub struct SwapOrder {
     /// Order ID (derived from Across message)
@@ -61,11 +739,21 @@ pub struct SwapOrder {
     /// Deadline timestamp
     pub deadline: i64,
     
+    /// When the order was created, the start of its TTL
+    pub created_at: i64,
+    
     /// Status of the order
     pub status: OrderStatus,
     
     /// Bump seed for PDA derivation
     pub bump: u8,
+    
+    /// Account that funded the order's rent and gets it back on close:
+    /// the rent vault, or the creator when the vault could not cover it
+    pub rent_payer: Pubkey,
+    
+    /// Output the swap delivered above its route's quote, wherever the policy sent it
+    pub surplus: u64,
 }
 
 impl SwapOrder {
@@ -76,64 +764,3313 @@ impl SwapOrder {
         8 + // min_output_amount
         32 + // destination_mint
         8 + // deadline
+        8 + // created_at
+        1 + // status
+        1 + // bump
+        32 + // rent_payer
+        8; // surplus
+
+    /// Maximum number of orders closed by a single close_orders_batch call
+    pub const MAX_CLOSE_BATCH: usize = 20;
+
+    /// Maximum number of orders compressed by a single compress_orders call,
+    /// lower than `MAX_CLOSE_BATCH` since each append hashes the full tree height
+    pub const MAX_COMPRESS_BATCH: usize = 8;
+
+    /// Whether the order's USDC is still owed out of the program's USDC vault
+    pub fn is_vault_liability(&self) -> bool {
+        self.status == OrderStatus::Pending || self.status == OrderStatus::Failed
+    }
+
+    /// Whether the order reached a final state and can be closed
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status,
+            OrderStatus::Completed | OrderStatus::Refunded | OrderStatus::Expired
+        )
+    }
+
+    /// Whether `address` is this order's PDA within the instance with `instance_seed`
+    pub fn is_instance_order(&self, address: &Pubkey, instance_seed: &[u8]) -> bool {
+        let order_id = self.order_id.to_le_bytes();
+        Pubkey::create_program_address(
+            &[superswap_pda::SWAP_ORDER_SEED, instance_seed, &order_id, &[self.bump]],
+            &crate::ID,
+        )
+        .is_ok_and(|derived| derived == *address)
+    }
+
+    /// Whether the order's dispute window of `retention_seconds` has elapsed, so closing it is allowed
+    pub fn is_retention_elapsed(&self, now: i64, retention_seconds: u32) -> bool {
+        now >= self.created_at.saturating_add(retention_seconds as i64)
+    }
+
+    /// Whether the order has outlived `ttl_seconds` and may only be refunded (0 = no TTL)
+    pub fn is_past_ttl(&self, now: i64, ttl_seconds: u32) -> bool {
+        ttl_seconds > 0 && now >= self.created_at.saturating_add(ttl_seconds as i64)
+    }
+}
+
+/// What a closed swap order leaves at its address
+///
+/// The order's PDA stays allocated and program-owned, so its `order_id` can never
+/// be initialized and filled again; closing reclaims the rent above this.
+#[account]
+pub struct OrderTombstone {
+    /// Order ID of the closed order
+    pub order_id: u64,
+}
+
+impl OrderTombstone {
+    pub const LEN: usize = 8 + // discriminator
+        8; // order_id
+}
+
+/// Represents an outbound (Solana to EVM) swap and bridge
+#[account]
+pub struct OutboundOrder {
+    /// Order ID chosen by the sender
+    pub order_id: u64,
+    
+    /// User who swapped and bridged
+    pub sender: Pubkey,
+    
+    /// Token the sender swapped from
+    pub input_mint: Pubkey,
+    
+    /// USDC produced by the swap
+    pub usdc_amount: u64,
+    
+    /// Protocol fee taken from the swap output
+    pub fee_amount: u64,
+    
+    /// USDC handed to the bridge
+    pub bridged_amount: u64,
+    
+    /// Bridge used for the transfer
+    pub bridge: OutboundBridge,
+    
+    /// Destination EVM chain id
+    pub destination_chain_id: u64,
+    
+    /// Recipient on the destination chain (20-byte EVM address)
+    pub recipient: [u8; 20],
+    
+    /// Status of the order
+    pub status: OutboundStatus,
+    
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OutboundOrder {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // order_id
+        32 + // sender
+        32 + // input_mint
+        8 + // usdc_amount
+        8 + // fee_amount
+        8 + // bridged_amount
+        1 + // bridge
+        8 + // destination_chain_id
+        20 + // recipient
         1 + // status
+        8 + // created_at
         1; // bump
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum OrderStatus {
-    /// Order is being processed
-    Pending,
-    /// Swap completed successfully
-    Completed,
-    /// Swap failed, USDC refunded
+pub enum OutboundBridge {
+    /// Across spoke pool deposit
+    Across,
+    /// CCTP burn
+    Cctp,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundStatus {
+    /// Order created, bridge not yet called
+    Created,
+    /// Funds handed to the bridge
+    Bridged,
+    /// Delivery confirmed on the destination chain
+    Settled,
+    /// Escrowed USDC returned to the sender
     Refunded,
-    /// Order failed with error
-    Failed,
 }
 
-/// Parameters for initialization
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct InitializeParams {
-    pub across_handler: Pubkey,
-    pub jupiter_program: Pubkey,
-    pub usdc_mint: Pubkey,
-    pub fee_recipient: Pubkey,
-    pub fee_bps: u16,
+/// Optional metadata for a swap order, created only when the bridge message carries it
+#[account]
+pub struct OrderExtension {
+    /// Swap order this extension belongs to
+    pub swap_order: Pubkey,
+    
+    /// EVM chain id the deposit originated from
+    pub origin_chain_id: u64,
+    
+    /// EVM address of the depositor
+    pub evm_origin: [u8; 20],
+    
+    /// Hash of the quoted Jupiter route
+    pub route_hash: [u8; 32],
+    
+    /// Integrator that originated the order
+    pub integrator_id: u32,
+    
+    /// Optional onward destination on another SVM chain
+    pub onward_destination: Option<OnwardDestination>,
+    
+    /// Optional action applied to the swap output after delivery to the escrow
+    pub post_swap_action: Option<PostSwapAction>,
+    
+    /// Optional claimant that pulls the escrowed output with `claim_output`
+    pub claimant: Option<Pubkey>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
 }
 
-/// Parameters for updating configuration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct UpdateConfigParams {
-    pub new_admin: Option<Pubkey>,
-    pub new_across_handler: Option<Pubkey>,
-    pub new_jupiter_program: Option<Pubkey>,
-    pub new_fee_recipient: Option<Pubkey>,
-    pub new_fee_bps: Option<u16>,
+impl OrderExtension {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        8 + // origin_chain_id
+        20 + // evm_origin
+        32 + // route_hash
+        4 + // integrator_id
+        1 + OnwardDestination::LEN + // onward_destination
+        1 + PostSwapAction::MAX_LEN + // post_swap_action
+        1 + 32 + // claimant
+        1; // bump
 }
 
-/// Parameters for processing bridge and swap
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct ProcessBridgeAndSwapParams {
-    pub order_id: u64,
+/// Destination on another SVM chain (e.g. Eclipse, SOON) reached through the onward adapter
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct OnwardDestination {
+    /// Messaging domain of the destination chain
+    pub domain: u32,
+    
+    /// Recipient on the destination chain
     pub recipient: Pubkey,
-    pub usdc_amount: u64,
-    pub min_output_amount: u64,
-    pub destination_mint: Pubkey,
-    pub deadline: i64,
-    pub jupiter_swap_data: Vec<u8>,
 }
 
-/// Parameters for executing Jupiter swap
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct ExecuteJupiterSwapParams {
-    pub swap_data: Vec<u8>,
+impl OnwardDestination {
+    pub const LEN: usize = 4 + // domain
+        32; // recipient
 }
 
-/// Parameters for recovering funds
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct RecoverFundsParams {
-    pub token_mint: Pubkey,
-    pub amount: u64,
+/// Action applied to an order's swap output held in its escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PostSwapAction {
+    /// Stake the SOL output in a liquid staking pool and deliver the LST to the beneficiary
+    Stake {
+        pool: LiquidStakingPool,
+        beneficiary: Pubkey,
+    },
+    /// Deposit the output into a lending reserve and deliver the collateral to the beneficiary
+    Lend {
+        market: LendingMarket,
+        beneficiary: Pubkey,
+    },
+    /// Swap half of the USDC output and add both legs as liquidity to a pool,
+    /// delivering the LP tokens or position NFT to the beneficiary
+    ProvideLiquidity {
+        protocol: LiquidityProtocol,
+        pool: Pubkey,
+        beneficiary: Pubkey,
+    },
+    /// Hand the output to an allowlisted spend program (e.g. a marketplace or
+    /// payments program) that consumes all of it on behalf of the beneficiary
+    Spend {
+        program: Pubkey,
+        beneficiary: Pubkey,
+    },
+    /// Deposit the output into Streamflow streams to the beneficiaries of the
+    /// order's stream plan
+    Stream,
+    /// Deliver the output to the recipients of the order's split plan by weight
+    Split,
+    /// Deposit the output into a 4626-style vault of an allowlisted vault program,
+    /// delivering the share tokens to the beneficiary
+    DepositVault {
+        program: Pubkey,
+        vault: Pubkey,
+        beneficiary: Pubkey,
+    },
+    /// Deliver the output to the beneficiary except for up to `amount`, which stays
+    /// in the escrow with an allowlisted delegate approved to pull it
+    ApproveDelegate {
+        delegate: Pubkey,
+        amount: u64,
+        beneficiary: Pubkey,
+    },
+}
+
+impl PostSwapAction {
+    /// Size of the largest variant, including the enum tag
+    pub const MAX_LEN: usize = 1 + // tag
+        32 + // program
+        32 + // vault
+        32; // beneficiary
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidStakingPool {
+    /// JitoSOL via the SPL stake pool program
+    Jito,
+    /// mSOL via Marinade
+    Marinade,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityProtocol {
+    /// Raydium constant-product pool (LP tokens)
+    RaydiumCpmm,
+    /// Orca Whirlpool position (position NFT)
+    OrcaWhirlpool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LendingMarket {
+    /// Kamino Lend
+    Kamino,
+    /// Solend (SPL token-lending)
+    Solend,
+}
+
+/// Program-owned lamport vault that pays rent for order and ATA creation
+#[account]
+pub struct RentVault {
+    /// Total lamports deposited by the admin
+    pub total_deposited: u64,
+    
+    /// Total lamports spent on account creation
+    pub total_subsidized: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RentVault {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // total_deposited
+        8 + // total_subsidized
+        1; // bump
+}
+
+/// Per-period volume cap on outbound swaps, independent from inbound limits
+#[account]
+pub struct OutboundRateLimit {
+    /// Length of a rate limit period in seconds
+    pub period_seconds: i64,
+    
+    /// Maximum USDC bridged out per period (0 = no cap)
+    pub period_cap: u64,
+    
+    /// Start timestamp of the current period
+    pub period_start: i64,
+    
+    /// USDC bridged out in the current period
+    pub period_volume: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OutboundRateLimit {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // period_seconds
+        8 + // period_cap
+        8 + // period_start
+        8 + // period_volume
+        1; // bump
+
+    /// Default period length (one day)
+    pub const DEFAULT_PERIOD_SECONDS: i64 = 86_400;
+
+    /// Adds `amount` to the current period's volume, rolling the period over
+    /// when it has elapsed, and fails if the cap would be exceeded
+    pub fn record(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now >= self.period_start.saturating_add(self.period_seconds) {
+            self.period_start = now;
+            self.period_volume = 0;
+        }
+
+        let period_volume = self
+            .period_volume
+            .checked_add(amount)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        require!(
+            self.period_cap == 0 || period_volume <= self.period_cap,
+            SuperSwapError::OutboundRateLimitExceeded
+        );
+
+        self.period_volume = period_volume;
+
+        Ok(())
+    }
+}
+
+/// Thresholds and caps on recoveries, in the recovered token's base units
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RecoveryLimits {
+    /// Recoveries above this amount must be queued (0 = no timelock)
+    pub timelock_threshold: u64,
+    /// Delay before a queued recovery, or a loosening of these limits, takes effect
+    pub delay_seconds: i64,
+    /// Length of a recovery cap period in seconds
+    pub period_seconds: i64,
+    /// Maximum recovered per period, queued or not (0 = no cap)
+    pub period_cap: u64,
+}
+
+impl RecoveryLimits {
+    pub const LEN: usize = 8 + // timelock_threshold
+        8 + // delay_seconds
+        8 + // period_seconds
+        8; // period_cap
+
+    /// Default delay (two days)
+    pub const DEFAULT_DELAY_SECONDS: i64 = 172_800;
+
+    /// Limits that neither timelock nor cap anything
+    pub const DISABLED: Self = Self {
+        timelock_threshold: 0,
+        delay_seconds: Self::DEFAULT_DELAY_SECONDS,
+        period_seconds: OutboundRateLimit::DEFAULT_PERIOD_SECONDS,
+        period_cap: 0,
+    };
+
+    /// Limits changed relative to `before`, for the admin log
+    pub fn changes_since(&self, before: &RecoveryLimits) -> Vec<(&'static str, [u8; 32], [u8; 32])> {
+        setting_changes!(before, self; timelock_threshold, delay_seconds, period_seconds, period_cap)
+    }
+
+    /// Whether moving from `current` to these limits lets more out, or out sooner
+    pub fn loosens(&self, current: &RecoveryLimits) -> bool {
+        let raises = |new: u64, old: u64| old != 0 && (new == 0 || new > old);
+        raises(self.timelock_threshold, current.timelock_threshold)
+            || raises(self.period_cap, current.period_cap)
+            || self.delay_seconds < current.delay_seconds
+            || self.period_seconds < current.period_seconds
+    }
+}
+
+/// Limits on `recover_funds` so a compromised admin key cannot drain the
+/// program at once: large recoveries wait out a public delay and every
+/// recovery counts against a per-period cap
+#[account]
+pub struct RecoveryGuard {
+    /// Limits in force
+    pub limits: RecoveryLimits,
+    
+    /// Looser limits waiting out the current delay
+    pub pending_limits: RecoveryLimits,
+    
+    /// When `pending_limits` take over (0 = none pending)
+    pub pending_effective_at: i64,
+    
+    /// Start timestamp of the current period
+    pub period_start: i64,
+    
+    /// Amount recovered in the current period
+    pub period_volume: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RecoveryGuard {
+    pub const LEN: usize = 8 + // discriminator
+        RecoveryLimits::LEN + // limits
+        RecoveryLimits::LEN + // pending_limits
+        8 + // pending_effective_at
+        8 + // period_start
+        8 + // period_volume
+        1; // bump
+
+    /// Puts pending limits in force once their delay has passed
+    pub fn refresh(&mut self, now: i64) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.limits = self.pending_limits;
+            self.pending_effective_at = 0;
+        }
+    }
+
+    /// Applies tighter limits at once and queues looser ones behind the current
+    /// delay, dropping any earlier pending change; returns when queued limits apply
+    pub fn set_limits(&mut self, limits: RecoveryLimits, now: i64) -> Result<Option<i64>> {
+        self.refresh(now);
+        self.pending_effective_at = 0;
+        if !limits.loosens(&self.limits) {
+            self.limits = limits;
+            return Ok(None);
+        }
+
+        let effective_at = now
+            .checked_add(self.limits.delay_seconds)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        self.pending_limits = limits;
+        self.pending_effective_at = effective_at;
+
+        Ok(Some(effective_at))
+    }
+
+    /// Whether recovering `amount` has to be queued
+    pub fn requires_timelock(&self, amount: u64) -> bool {
+        self.limits.timelock_threshold != 0 && amount > self.limits.timelock_threshold
+    }
+
+    /// Adds `amount` to the current period's recoveries, rolling the period
+    /// over when it has elapsed, and fails if the cap would be exceeded
+    pub fn record(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now >= self.period_start.saturating_add(self.limits.period_seconds) {
+            self.period_start = now;
+            self.period_volume = 0;
+        }
+
+        let period_volume = self
+            .period_volume
+            .checked_add(amount)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        require!(
+            self.limits.period_cap == 0 || period_volume <= self.limits.period_cap,
+            SuperSwapError::RecoveryCapExceeded
+        );
+
+        self.period_volume = period_volume;
+
+        Ok(())
+    }
+}
+
+/// A recovery above the timelock threshold, one PDA per recovery id
+#[account]
+pub struct PendingRecovery {
+    pub recovery_id: u64,
+    
+    /// Recovered mint, or `LAMPORTS_MINT` for a recovery of native lamports
+    pub token_mint: Pubkey,
+    
+    /// Config-owned token account the tokens leave, or the program-owned
+    /// account holding the lamports
+    pub source_token_account: Pubkey,
+    
+    pub destination_token_account: Pubkey,
+    
+    pub amount: u64,
+    
+    /// Earliest time the recovery can be executed
+    pub executable_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PendingRecovery {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // recovery_id
+        32 + // token_mint
+        32 + // source_token_account
+        32 + // destination_token_account
+        8 + // amount
+        8 + // executable_at
+        1; // bump
+
+    /// `token_mint` recorded for recoveries of native lamports
+    pub const LAMPORTS_MINT: Pubkey = anchor_lang::system_program::ID;
+
+    pub fn is_lamports(&self) -> bool {
+        self.token_mint == Self::LAMPORTS_MINT
+    }
+}
+
+/// A timelocked change to the config's routing-critical addresses, one PDA per config
+#[account]
+pub struct PendingConfigUpdate {
+    /// Config the change applies to
+    pub config: Pubkey,
+
+    pub across_handler: Option<Pubkey>,
+
+    pub jupiter_program: Option<Pubkey>,
+
+    pub fee_recipient: Option<Pubkey>,
+
+    /// Shorter `config_update_delay_seconds`, which only takes effect through the timelock
+    pub config_update_delay_seconds: Option<i64>,
+
+    /// Handler to register alongside `across_handler`
+    pub add_across_handler: Option<Pubkey>,
+
+    /// Earliest time the change can be applied
+    pub executable_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PendingConfigUpdate {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // config
+        (1 + 32) + // across_handler
+        (1 + 32) + // jupiter_program
+        (1 + 32) + // fee_recipient
+        (1 + 8) + // config_update_delay_seconds
+        (1 + 32) + // add_across_handler
+        8 + // executable_at
+        1; // bump
+
+    /// Writes the proposed values into `config`
+    pub fn apply(&self, config: &mut Config) -> Result<()> {
+        if let Some(across_handler) = self.across_handler {
+            config.across_handler = across_handler;
+        }
+        if let Some(jupiter_program) = self.jupiter_program {
+            config.jupiter_program = jupiter_program;
+        }
+        if let Some(fee_recipient) = self.fee_recipient {
+            config.fee_recipient = fee_recipient;
+        }
+        if let Some(config_update_delay_seconds) = self.config_update_delay_seconds {
+            config.config_update_delay_seconds = config_update_delay_seconds;
+        }
+        // Rechecked since the handler may have become the main one in the meantime
+        if let Some(handler) = self.add_across_handler {
+            config.add_across_handler(handler)?;
+        }
+        Ok(())
+    }
+}
+
+/// A recipient's claim on the insurance fund for an order that cannot be refunded,
+/// one PDA per swap order
+#[account]
+pub struct InsuranceClaim {
+    /// Swap order the claim compensates
+    pub swap_order: Pubkey,
+    
+    pub recipient: Pubkey,
+    
+    /// USDC account, chosen by the recipient, the payout goes to
+    pub payout_token_account: Pubkey,
+    
+    /// USDC owed, fixed when the claim is filed
+    pub amount: u64,
+    
+    pub status: InsuranceClaimStatus,
+    
+    pub filed_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl InsuranceClaim {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        32 + // recipient
+        32 + // payout_token_account
+        8 + // amount
+        1 + // status
+        8 + // filed_at
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InsuranceClaimStatus {
+    /// Filed by the recipient, awaiting review
+    Filed,
+    /// Approved by the admin, awaiting its payout
+    Approved,
+    /// Paid out of the insurance fund
+    Paid,
+}
+
+/// Depth of the order archive tree, which holds up to 2^24 records
+pub const ORDER_ARCHIVE_DEPTH: usize = 24;
+
+/// Append-only Merkle commitment to settled swap orders whose accounts were closed
+///
+/// Each leaf commits to one order's final record, which is emitted in full in
+/// `OrderCompressed`; an indexer replays the events to rebuild the tree and
+/// serve proofs against `root`.
+#[account]
+pub struct OrderArchive {
+    /// Root after the last append; all zeroes while the archive is empty
+    pub root: [u8; 32],
+
+    /// Index the next record is appended at
+    pub next_index: u64,
+
+    /// Rightmost left child at each level, enough to append without the leaves
+    pub filled_subtrees: [[u8; 32]; ORDER_ARCHIVE_DEPTH],
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OrderArchive {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // root
+        8 + // next_index
+        32 * ORDER_ARCHIVE_DEPTH + // filled_subtrees
+        1; // bump
+
+    /// Leaf committing to `record`, a closed order's serialized state
+    pub fn leaf(swap_order: &Pubkey, record: &[u8]) -> [u8; 32] {
+        hashv(&[b"superswap_order", swap_order.as_ref(), record]).to_bytes()
+    }
+
+    /// Appends `leaf`, returning its index
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<u64> {
+        let index = self.next_index;
+        require!(index < 1 << ORDER_ARCHIVE_DEPTH, SuperSwapError::OrderArchiveFull);
+
+        let mut node = leaf;
+        let mut zero = [0u8; 32];
+        for (level, filled) in self.filled_subtrees.iter_mut().enumerate() {
+            node = if (index >> level) & 1 == 0 {
+                *filled = node;
+                hashv(&[&node, &zero]).to_bytes()
+            } else {
+                hashv(&[filled.as_ref(), &node]).to_bytes()
+            };
+            zero = hashv(&[&zero, &zero]).to_bytes();
+        }
+
+        self.root = node;
+        self.next_index = index + 1;
+        Ok(index)
+    }
+
+    /// Whether `proof`, the sibling at each level from the leaf up, places `leaf` at `index` under `root`
+    pub fn verify(root: &[u8; 32], leaf: [u8; 32], index: u64, proof: &[[u8; 32]]) -> bool {
+        if proof.len() != ORDER_ARCHIVE_DEPTH {
+            return false;
+        }
+        let node = proof.iter().enumerate().fold(leaf, |node, (level, sibling)| {
+            if (index >> level) & 1 == 0 {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+        node == *root
+    }
+}
+
+/// Allowlist entry for a post-swap hook program, one PDA per allowed program
+#[account]
+pub struct HookProgram {
+    /// Allowed hook program
+    pub program_id: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl HookProgram {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // program_id
+        1; // bump
+}
+
+/// Allowlisted spend program that may consume escrowed output, one PDA per program
+#[account]
+pub struct SpendProgram {
+    /// Allowed spend program
+    pub program_id: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl SpendProgram {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // program_id
+        1; // bump
+}
+
+/// Registered payer operator and the fill costs owed to it, one PDA per operator
+///
+/// Its presence is what lets the operator pay for fills the Across handler signs.
+#[account]
+pub struct PayerLedger {
+    /// Operator paying transaction costs for fills
+    pub operator: Pubkey,
+    
+    /// USDC owed for fills paid since the last settlement
+    pub owed_usdc: u64,
+    
+    /// Fills paid since the operator was registered
+    pub fills_paid: u64,
+    
+    /// USDC settled to the operator over its lifetime
+    pub total_settled_usdc: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PayerLedger {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // operator
+        8 + // owed_usdc
+        8 + // fills_paid
+        8 + // total_settled_usdc
+        1; // bump
+
+    /// Records one paid fill costing `cost_usdc`
+    pub fn record_fill(&mut self, cost_usdc: u64) -> Result<()> {
+        self.owed_usdc = self
+            .owed_usdc
+            .checked_add(cost_usdc)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        self.fills_paid = self
+            .fills_paid
+            .checked_add(1)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Clears the owed balance, returning the USDC to pay the operator
+    pub fn settle(&mut self) -> Result<u64> {
+        let amount = self.owed_usdc;
+        self.total_settled_usdc = self
+            .total_settled_usdc
+            .checked_add(amount)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        self.owed_usdc = 0;
+        Ok(amount)
+    }
+}
+
+/// Ring buffer of the last `AdminLog::CAPACITY` privileged actions on a config, one PDA per config
+///
+/// Kept in account data so the trail outlives RPC log retention.
+#[account]
+pub struct AdminLog {
+    /// Config whose actions are recorded
+    pub config: Pubkey,
+    
+    /// Actions recorded over the log's lifetime; the next entry goes to `count % CAPACITY`
+    pub count: u64,
+    
+    /// Recorded actions, oldest overwritten first once full
+    pub entries: Vec<AdminLogEntry>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl AdminLog {
+    /// Entries kept before the oldest is overwritten
+    pub const CAPACITY: usize = 32;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // config
+        8 + // count
+        4 + AdminLogEntry::LEN * Self::CAPACITY + // entries
+        1; // bump
+
+    /// Sets up an empty log for `config`
+    pub fn init(&mut self, config: Pubkey, bump: u8) {
+        self.config = config;
+        self.count = 0;
+        self.entries = Vec::new();
+        self.bump = bump;
+    }
+
+    /// Appends an entry, overwriting the oldest once the log is full
+    pub fn record(&mut self, entry: AdminLogEntry) {
+        let slot = (self.count % Self::CAPACITY as u64) as usize;
+        if slot < self.entries.len() {
+            self.entries[slot] = entry;
+        } else {
+            self.entries.push(entry);
+        }
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// Records one `action` entry per changed setting, as listed by `setting_changes!`
+    pub fn record_changes(
+        &mut self,
+        actor: Pubkey,
+        action: AdminAction,
+        timestamp: i64,
+        changes: Vec<(&str, [u8; 32], [u8; 32])>,
+    ) {
+        for (field, old_value, new_value) in changes {
+            self.record(AdminLogEntry {
+                field: AdminLogEntry::field_name(field),
+                old_value,
+                new_value,
+                ..AdminLogEntry::new(actor, action, timestamp, Pubkey::default())
+            });
+        }
+    }
+
+    /// Entries from oldest to newest
+    pub fn chronological(&self) -> impl Iterator<Item = &AdminLogEntry> {
+        let start = if self.entries.len() < Self::CAPACITY {
+            0
+        } else {
+            (self.count % Self::CAPACITY as u64) as usize
+        };
+        self.entries[start..].iter().chain(self.entries[..start].iter())
+    }
+}
+
+/// One privileged action in an `AdminLog`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdminLogEntry {
+    /// Signer who performed the action
+    pub actor: Pubkey,
+    
+    pub action: AdminAction,
+    
+    /// Unix timestamp of the action
+    pub timestamp: i64,
+    
+    /// Account the action applied to (allowlisted address, mint, destination), default when none
+    pub subject: Pubkey,
+    
+    /// Name of the setting changed, zero-padded ASCII; empty when the action changes none
+    pub field: [u8; 32],
+    
+    /// Value before the action, encoded by `AdminLogValue`
+    pub old_value: [u8; 32],
+    
+    /// Value after the action, encoded by `AdminLogValue`
+    pub new_value: [u8; 32],
+}
+
+impl AdminLogEntry {
+    pub const LEN: usize = 32 + // actor
+        1 + // action
+        8 + // timestamp
+        32 + // subject
+        32 + // field
+        32 + // old_value
+        32; // new_value
+
+    /// Entry for `action` by `actor` on `subject`, with no field or values
+    pub fn new(actor: Pubkey, action: AdminAction, timestamp: i64, subject: Pubkey) -> Self {
+        Self {
+            actor,
+            action,
+            timestamp,
+            subject,
+            field: [0; 32],
+            old_value: [0; 32],
+            new_value: [0; 32],
+        }
+    }
+
+    /// Sets the values before and after the action
+    pub fn with_values(mut self, old_value: impl AdminLogValue, new_value: impl AdminLogValue) -> Self {
+        self.old_value = old_value.to_log_value();
+        self.new_value = new_value.to_log_value();
+        self
+    }
+
+    /// `field` as stored in an entry, truncated to 32 bytes
+    pub fn field_name(field: &str) -> [u8; 32] {
+        let mut name = [0; 32];
+        let len = field.len().min(32);
+        name[..len].copy_from_slice(&field.as_bytes()[..len]);
+        name
+    }
+}
+
+/// Privileged action recorded in the admin log
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdminAction {
+    UpdateConfig,
+    Pause,
+    Unpause,
+    SetRefundsOnly,
+    SetDestinationMint,
+    AddHookProgram,
+    RemoveHookProgram,
+    AddSpendProgram,
+    RemoveSpendProgram,
+    AddVaultProgram,
+    RemoveVaultProgram,
+    AddDonationRecipient,
+    RemoveDonationRecipient,
+    AddPayerOperator,
+    RemovePayerOperator,
+    SetOutboundRateLimit,
+    SetRecoveryLimits,
+    RecoverFunds,
+    QueueRecovery,
+    ExecuteRecovery,
+    CancelRecovery,
+    WithdrawRentVault,
+    RebalanceTreasury,
+    RegisterRouteTemplate,
+    RemoveRouteTemplate,
+    AddApprovedDelegate,
+    RemoveApprovedDelegate,
+    RecoverLamports,
+    QueueLamportRecovery,
+    ExecuteLamportRecovery,
+    AcceptAdmin,
+    ProposeConfigUpdate,
+    ApplyConfigUpdate,
+    CancelConfigUpdate,
+    AddAcrossHandler,
+    RemoveAcrossHandler,
+    CreateMintConfig,
+    UpdateMintConfig,
+    DeleteMintConfig,
+    CreateVolumeTracker,
+}
+
+/// Encoding of a logged value into 32 bytes: addresses as their bytes,
+/// integers and flags little-endian and zero-padded
+pub trait AdminLogValue {
+    fn to_log_value(&self) -> [u8; 32];
+}
+
+impl AdminLogValue for Pubkey {
+    fn to_log_value(&self) -> [u8; 32] {
+        self.to_bytes()
+    }
+}
+
+impl AdminLogValue for bool {
+    fn to_log_value(&self) -> [u8; 32] {
+        (*self as u64).to_log_value()
+    }
+}
+
+macro_rules! admin_log_value_le {
+    ($($ty:ty),*) => {$(
+        impl AdminLogValue for $ty {
+            fn to_log_value(&self) -> [u8; 32] {
+                let bytes = self.to_le_bytes();
+                let mut value = [0; 32];
+                value[..bytes.len()].copy_from_slice(&bytes);
+                value
+            }
+        }
+    )*};
+}
+
+admin_log_value_le!(u8, u16, u32, u64, i64);
+
+impl AdminLogValue for SurplusPolicy {
+    fn to_log_value(&self) -> [u8; 32] {
+        (*self as u8).to_log_value()
+    }
+}
+
+/// Admin-vetted shape of the Jupiter route for a high-volume pair, one PDA per pair and config
+///
+/// A fill naming the template must use exactly its instruction and account
+/// list, which is cheaper to check than decoding an arbitrary route.
+#[account]
+pub struct RouteTemplate {
+    /// Config the template was registered on
+    pub config: Pubkey,
+    
+    /// Mint the route swaps from
+    pub input_mint: Pubkey,
+    
+    /// Mint the route swaps to
+    pub output_mint: Pubkey,
+    
+    /// Jupiter instruction discriminator the route data must start with
+    pub discriminator: [u8; 8],
+    
+    /// Route accounts in order; a default address matches any account in that position
+    pub accounts: Vec<TemplateAccount>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RouteTemplate {
+    /// Most route accounts a template can describe
+    pub const MAX_ACCOUNTS: usize = 48;
+
+    /// Space for a template describing `accounts_len` route accounts
+    pub fn space(accounts_len: usize) -> usize {
+        8 + // discriminator
+        32 + // config
+        32 + // input_mint
+        32 + // output_mint
+        8 + // discriminator
+        4 + TemplateAccount::LEN * accounts_len + // accounts
+        1 // bump
+    }
+
+    /// Checks route data and accounts, given as (address, writable) pairs, against the template
+    pub fn validate(&self, swap_data: &[u8], accounts: impl ExactSizeIterator<Item = (Pubkey, bool)>) -> Result<()> {
+        require!(
+            swap_data.len() >= 8 && swap_data[..8] == self.discriminator,
+            SuperSwapError::RouteTemplateMismatch
+        );
+        require!(accounts.len() == self.accounts.len(), SuperSwapError::RouteTemplateMismatch);
+        for (expected, (pubkey, is_writable)) in self.accounts.iter().zip(accounts) {
+            require!(
+                (expected.pubkey == Pubkey::default() || expected.pubkey == pubkey)
+                    && expected.is_writable == is_writable,
+                SuperSwapError::RouteTemplateMismatch
+            );
+        }
+        Ok(())
+    }
+}
+
+/// One route account position in a `RouteTemplate`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TemplateAccount {
+    /// Required address, or the default address for an order-specific account
+    pub pubkey: Pubkey,
+    
+    /// Whether the route passes the account writable
+    pub is_writable: bool,
+}
+
+impl TemplateAccount {
+    pub const LEN: usize = 32 + // pubkey
+        1; // is_writable
+}
+
+/// Jupiter route data too large for one transaction, assembled chunk by chunk
+/// and consumed (then closed) by the order's fill
+#[account]
+pub struct RouteStaging {
+    /// Config of the instance the order is filled on
+    pub config: Pubkey,
+    
+    /// Order the route is staged for
+    pub order_id: u64,
+    
+    /// Relayer uploading the route; only its fills may use it
+    pub authority: Pubkey,
+    
+    /// Length of the complete route data
+    pub total_len: u32,
+    
+    /// Set once every chunk is uploaded; no more chunks are accepted after
+    pub finalized: bool,
+    
+    /// Route data uploaded so far
+    pub data: Vec<u8>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RouteStaging {
+    /// Largest route that can be staged, keeping the account within the size a CPI can create
+    pub const MAX_DATA_LEN: u32 = 8192;
+
+    /// Space for a staging account holding `total_len` bytes of route data
+    pub fn space(total_len: u32) -> usize {
+        8 + // discriminator
+        32 + // config
+        8 + // order_id
+        32 + // authority
+        4 + // total_len
+        1 + // finalized
+        4 + total_len as usize + // data
+        1 // bump
+    }
+
+    /// Appends a chunk, which must start where the previous one ended
+    pub fn append(&mut self, offset: u32, chunk: &[u8]) -> Result<()> {
+        require!(!self.finalized, SuperSwapError::RouteAlreadyFinalized);
+        require!(offset as usize == self.data.len(), SuperSwapError::RouteChunkOutOfOrder);
+        require!(
+            self.data.len() + chunk.len() <= self.total_len as usize,
+            SuperSwapError::RouteDataTooLarge
+        );
+        self.data.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    /// Locks the route once all `total_len` bytes are uploaded
+    pub fn finalize(&mut self) -> Result<()> {
+        require!(!self.finalized, SuperSwapError::RouteAlreadyFinalized);
+        require!(self.data.len() == self.total_len as usize, SuperSwapError::RouteIncomplete);
+        self.finalized = true;
+        Ok(())
+    }
+}
+
+/// Allowlisted 4626-style vault program that escrowed output may be deposited into, one PDA per program
+#[account]
+pub struct VaultProgram {
+    /// Allowed vault program
+    pub program_id: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl VaultProgram {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // program_id
+        1; // bump
+}
+
+/// Allowlisted delegate that orders may approve on their escrowed output, one PDA per delegate
+#[account]
+pub struct ApprovedDelegate {
+    /// Allowed delegate (e.g. a subscription program's pull authority)
+    pub delegate: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ApprovedDelegate {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // delegate
+        1; // bump
+}
+
+/// Allowlisted address that orders may route a donation split to, one PDA per address
+#[account]
+pub struct DonationRecipient {
+    /// Allowed donation recipient (owner of the USDC account donations go to)
+    pub recipient: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl DonationRecipient {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // recipient
+        1; // bump
+}
+
+/// Operator switch for a destination mint, one PDA per mint; mints without an entry are enabled
+#[account]
+pub struct DestinationMint {
+    /// Destination mint this entry controls
+    pub mint: Pubkey,
+    
+    /// Rejects new orders and DCA tranches into the mint while set
+    pub disabled: bool,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl DestinationMint {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        1 + // disabled
+        1; // bump
+}
+
+/// Admin overrides for fills into a destination mint, one PDA per config and mint;
+/// mints without one follow the config alone
+#[account]
+pub struct MintConfig {
+    /// Config the overrides belong to
+    pub config: Pubkey,
+
+    /// Destination mint the overrides apply to
+    pub mint: Pubkey,
+
+    /// Accepts fills into the mint; when cleared the mint is treated as disabled
+    pub enabled: bool,
+
+    /// Fee charged instead of `Config::fee_bps`
+    pub fee_bps: Option<u16>,
+
+    /// Largest order into the mint, in USDC base units (0 = no cap beyond the config's)
+    pub max_order_usdc: u64,
+
+    /// Furthest the delivered output may fall below the route's quote (0 = unchecked)
+    pub max_slippage_bps: u16,
+
+    /// Most USDC filled into the mint per UTC day, tracked by the mint's volume tracker (0 = no cap)
+    pub daily_volume_cap: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl MintConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // config
+        32 + // mint
+        1 + // enabled
+        (1 + 2) + // fee_bps
+        8 + // max_order_usdc
+        2 + // max_slippage_bps
+        8 + // daily_volume_cap
+        1; // bump
+
+    /// Upper bound on the `fee_bps` override, matching `Config::fee_bps`
+    pub const MAX_FEE_BPS: u16 = 1_000;
+
+    /// Validates `params` and writes them into the entry
+    pub fn set(&mut self, params: &MintConfigParams) -> Result<()> {
+        require!(
+            params.fee_bps.map(|fee_bps| fee_bps <= Self::MAX_FEE_BPS).unwrap_or(true),
+            SuperSwapError::InvalidFeeConfiguration
+        );
+        require!(params.max_slippage_bps <= 10_000, SuperSwapError::InvalidFeeConfiguration);
+        self.enabled = params.enabled;
+        self.fee_bps = params.fee_bps;
+        self.max_order_usdc = params.max_order_usdc;
+        self.max_slippage_bps = params.max_slippage_bps;
+        self.daily_volume_cap = params.daily_volume_cap;
+        Ok(())
+    }
+
+    /// Whether an order of `usdc_amount` exceeds the mint's cap
+    pub fn exceeds_max_order(&self, usdc_amount: u64) -> bool {
+        self.max_order_usdc > 0 && usdc_amount > self.max_order_usdc
+    }
+}
+
+/// USDC filled inbound during the current UTC day, one PDA per config and
+/// destination mint plus a config-wide one keyed by the default pubkey
+#[account]
+pub struct VolumeTracker {
+    /// Config the volume is filled on
+    pub config: Pubkey,
+
+    /// Destination mint tracked, or the default pubkey for all mints
+    pub mint: Pubkey,
+
+    /// UTC day, counted from the Unix epoch, the volume belongs to
+    pub day: i64,
+
+    /// USDC filled during `day`
+    pub volume: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl VolumeTracker {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // config
+        32 + // mint
+        8 + // day
+        8 + // volume
+        1; // bump
+
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+
+    /// Adds `amount` to the volume of the UTC day containing `now`, starting
+    /// the day from zero when it has rolled over, and fails if `cap` would be exceeded
+    pub fn record(&mut self, amount: u64, now: i64, cap: u64) -> Result<()> {
+        let day = now.div_euclid(Self::SECONDS_PER_DAY);
+        if day != self.day {
+            self.day = day;
+            self.volume = 0;
+        }
+
+        let volume = self.volume.checked_add(amount).ok_or(SuperSwapError::MathOverflow)?;
+        require!(cap == 0 || volume <= cap, SuperSwapError::DailyVolumeCapExceeded);
+        self.volume = volume;
+
+        Ok(())
+    }
+}
+
+/// Streams an order's output is split into, one PDA per swap order
+#[account]
+pub struct StreamPlan {
+    /// Swap order this plan belongs to
+    pub swap_order: Pubkey,
+    
+    /// Beneficiaries and their share of the output
+    pub recipients: Vec<StreamRecipient>,
+    
+    /// Unix timestamp streams start at (0 = when the action executes)
+    pub start_time: i64,
+    
+    /// Seconds over which each stream unlocks
+    pub duration_seconds: u64,
+    
+    /// Seconds between unlocks
+    pub period_seconds: u64,
+    
+    /// Share of each stream unlocked at the start, in basis points
+    pub cliff_bps: u16,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl StreamPlan {
+    pub const MAX_RECIPIENTS: usize = 5;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        4 + Self::MAX_RECIPIENTS * StreamRecipient::LEN + // recipients
+        8 + // start_time
+        8 + // duration_seconds
+        8 + // period_seconds
+        2 + // cliff_bps
+        1; // bump
+
+    /// Splits `amount` by the recipients' shares; the last stream takes the rounding remainder
+    pub fn split(&self, amount: u64) -> Result<Vec<u64>> {
+        let shares: Vec<u64> = self
+            .recipients
+            .iter()
+            .map(|recipient| recipient.share_bps as u64)
+            .collect();
+        split_weighted(amount, &shares)
+    }
+}
+
+/// Splits `amount` proportionally to `weights`; the last part takes the rounding remainder
+pub fn split_weighted(amount: u64, weights: &[u64]) -> Result<Vec<u64>> {
+    let total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+    require!(total_weight > 0, SuperSwapError::MathOverflow);
+
+    let mut remaining = amount;
+    let mut amounts = Vec::with_capacity(weights.len());
+    for (index, weight) in weights.iter().enumerate() {
+        let part = if index + 1 == weights.len() {
+            remaining
+        } else {
+            let part = (amount as u128)
+                .checked_mul(*weight as u128)
+                .ok_or(SuperSwapError::MathOverflow)?
+                / total_weight;
+            part as u64
+        };
+        remaining = remaining
+            .checked_sub(part)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        amounts.push(part);
+    }
+    Ok(amounts)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct StreamRecipient {
+    /// Beneficiary of the stream
+    pub beneficiary: Pubkey,
+    /// Share of the output, in basis points
+    pub share_bps: u16,
+}
+
+impl StreamRecipient {
+    pub const LEN: usize = 32 + // beneficiary
+        2; // share_bps
+}
+
+/// Recipients an order's output is split between, one PDA per swap order
+#[account]
+pub struct SplitPlan {
+    /// Swap order this plan belongs to
+    pub swap_order: Pubkey,
+    
+    /// Recipients, their weights and what each was delivered
+    pub legs: Vec<SplitLeg>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl SplitPlan {
+    pub const MAX_RECIPIENTS: usize = 8;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        4 + Self::MAX_RECIPIENTS * SplitLeg::LEN + // legs
+        1; // bump
+
+    /// Splits `amount` by the legs' weights; the last leg takes the rounding remainder
+    pub fn split(&self, amount: u64) -> Result<Vec<u64>> {
+        let weights: Vec<u64> = self.legs.iter().map(|leg| leg.weight as u64).collect();
+        split_weighted(amount, &weights)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SplitLeg {
+    /// Wallet receiving this leg
+    pub recipient: Pubkey,
+    /// Relative weight of this leg
+    pub weight: u16,
+    /// Output delivered to this leg once the split executes
+    pub delivered: u64,
+}
+
+impl SplitLeg {
+    pub const LEN: usize = 32 + // recipient
+        2 + // weight
+        8; // delivered
+}
+
+/// Output tokens an order's USDC is spread across, one PDA per swap order
+#[account]
+pub struct BasketPlan {
+    /// Swap order this plan belongs to
+    pub swap_order: Pubkey,
+    
+    /// Wallet receiving every leg's output
+    pub beneficiary: Pubkey,
+    
+    /// Escrowed USDC the legs split, recorded when the first leg executes
+    pub usdc_amount: u64,
+    
+    /// Output mints, their weights and what each delivered
+    pub legs: Vec<BasketLeg>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl BasketPlan {
+    pub const MAX_LEGS: usize = 4;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        32 + // beneficiary
+        8 + // usdc_amount
+        4 + Self::MAX_LEGS * BasketLeg::LEN + // legs
+        1; // bump
+
+    /// Checks the legs name distinct mints and their weights cover the whole order
+    pub fn validate_legs(legs: &[BasketLeg]) -> bool {
+        let total_bps: u32 = legs.iter().map(|leg| leg.weight_bps as u32).sum();
+        !legs.is_empty()
+            && legs.len() <= Self::MAX_LEGS
+            && total_bps == 10_000
+            && legs.iter().enumerate().all(|(i, leg)| {
+                leg.weight_bps > 0
+                    && leg.mint != Pubkey::default()
+                    && legs[..i].iter().all(|other| other.mint != leg.mint)
+            })
+    }
+
+    /// USDC each leg swaps; the last leg takes the rounding remainder
+    pub fn leg_amounts(&self) -> Result<Vec<u64>> {
+        let weights: Vec<u64> = self.legs.iter().map(|leg| leg.weight_bps as u64).collect();
+        split_weighted(self.usdc_amount, &weights)
+    }
+
+    /// Whether every leg has been swapped and delivered
+    pub fn is_complete(&self) -> bool {
+        self.legs.iter().all(|leg| leg.executed)
+    }
+
+    /// USDC the legs not yet executed still have to swap
+    pub fn unexecuted_usdc(&self) -> Result<u64> {
+        Ok(self
+            .leg_amounts()?
+            .iter()
+            .zip(&self.legs)
+            .filter(|(_, leg)| !leg.executed)
+            .map(|(amount, _)| amount)
+            .sum())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BasketLeg {
+    /// Output mint of this leg
+    pub mint: Pubkey,
+    /// Share of the order's USDC in basis points
+    pub weight_bps: u16,
+    /// Minimum output this leg must deliver
+    pub min_output_amount: u64,
+    /// Output delivered once the leg executes
+    pub delivered: u64,
+    /// Whether the leg has executed
+    pub executed: bool,
+}
+
+impl BasketLeg {
+    pub const LEN: usize = 32 + // mint
+        2 + // weight_bps
+        8 + // min_output_amount
+        8 + // delivered
+        1; // executed
+}
+
+/// Jupiter limit order placed with an order's bridged USDC, one PDA per swap order
+#[account]
+pub struct LimitOrder {
+    /// Swap order this limit order belongs to
+    pub swap_order: Pubkey,
+    
+    /// Jupiter Limit Order account
+    pub jupiter_order: Pubkey,
+    
+    /// USDC offered by the order
+    pub making_amount: u64,
+    
+    /// Destination tokens asked for the full making amount
+    pub taking_amount: u64,
+    
+    /// Expiry timestamp (0 = none)
+    pub expired_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl LimitOrder {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        32 + // jupiter_order
+        8 + // making_amount
+        8 + // taking_amount
+        8 + // expired_at
+        1; // bump
+
+    /// Whether the order carries an expiry that has passed, letting any keeper refund it
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expired_at != 0 && now >= self.expired_at
+    }
+}
+
+/// Schedule splitting an order's bridged USDC into tranches swapped by keepers over time
+#[account]
+pub struct DcaOrder {
+    /// Swap order this schedule belongs to
+    pub swap_order: Pubkey,
+    
+    /// Number of tranches the USDC is split into
+    pub tranche_count: u8,
+    
+    /// USDC swapped per tranche (the last tranche takes the remainder)
+    pub tranche_amount: u64,
+    
+    /// Minimum seconds between tranches
+    pub interval_seconds: i64,
+    
+    /// Earliest time the next tranche may execute
+    pub next_execution_at: i64,
+    
+    /// Minimum output for a full tranche, scaled for the last one
+    pub min_output_per_tranche: u64,
+    
+    /// USDC not yet swapped
+    pub remaining_amount: u64,
+    
+    /// Executed tranches, in order
+    pub fills: Vec<TrancheFill>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl DcaOrder {
+    /// Maximum number of tranches per order
+    pub const MAX_TRANCHES: u8 = 24;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // swap_order
+        1 + // tranche_count
+        8 + // tranche_amount
+        8 + // interval_seconds
+        8 + // next_execution_at
+        8 + // min_output_per_tranche
+        8 + // remaining_amount
+        4 + TrancheFill::LEN * Self::MAX_TRANCHES as usize + // fills
+        1; // bump
+
+    /// USDC the next tranche swaps
+    pub fn next_tranche_amount(&self) -> u64 {
+        if self.fills.len() + 1 >= self.tranche_count as usize {
+            self.remaining_amount
+        } else {
+            self.tranche_amount.min(self.remaining_amount)
+        }
+    }
+
+    /// Minimum output for a tranche swapping `usdc_in`, pro rata to a full tranche
+    pub fn tranche_min_output(&self, usdc_in: u64) -> Result<u64> {
+        let min_output = (self.min_output_per_tranche as u128)
+            .checked_mul(usdc_in as u128)
+            .and_then(|value| value.checked_div(self.tranche_amount as u128))
+            .ok_or(SuperSwapError::MathOverflow)?;
+        u64::try_from(min_output).map_err(|_| error!(SuperSwapError::MathOverflow))
+    }
+
+    /// Records an executed tranche and schedules the next one
+    pub fn record_fill(&mut self, usdc_in: u64, output: u64, now: i64) -> Result<()> {
+        self.remaining_amount = self
+            .remaining_amount
+            .checked_sub(usdc_in)
+            .ok_or(SuperSwapError::MathOverflow)?;
+        self.next_execution_at = now.saturating_add(self.interval_seconds);
+        self.fills.push(TrancheFill {
+            usdc_in,
+            output,
+            executed_at: now,
+        });
+
+        Ok(())
+    }
+
+    /// Whether every tranche has executed
+    pub fn is_complete(&self) -> bool {
+        self.remaining_amount == 0 || self.fills.len() >= self.tranche_count as usize
+    }
+}
+
+/// Fill of a single DCA tranche
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TrancheFill {
+    /// USDC swapped
+    pub usdc_in: u64,
+    
+    /// Destination tokens delivered to the recipient
+    pub output: u64,
+    
+    /// Execution timestamp
+    pub executed_at: i64,
+}
+
+impl TrancheFill {
+    pub const LEN: usize = 8 + // usdc_in
+        8 + // output
+        8; // executed_at
+}
+
+/// Delivery defaults a Solana user registers for fills addressed to them
+#[account]
+pub struct RecipientPreferences {
+    /// Recipient these preferences belong to
+    pub owner: Pubkey,
+    
+    /// Token account used instead of the ATA when its mint matches the
+    /// order's destination mint (default = none)
+    pub preferred_destination_account: Pubkey,
+    
+    /// Deliver native SOL instead of WSOL when the destination mint is WSOL;
+    /// recipients without preferences always get native SOL
+    pub auto_unwrap_sol: bool,
+    
+    /// Whether fills may carve a gas top-up from the recipient's USDC
+    pub gas_top_up: bool,
+    
+    /// USDC token account refunds are sent to (default = recipient's USDC ATA)
+    pub refund_address: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RecipientPreferences {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // preferred_destination_account
+        1 + // auto_unwrap_sol
+        1 + // gas_top_up
+        32 + // refund_address
+        1; // bump
+
+    /// Whether `account` is the registered preferred destination account
+    pub fn is_preferred_destination(&self, account: &Pubkey) -> bool {
+        self.preferred_destination_account != Pubkey::default()
+            && self.preferred_destination_account == *account
+    }
+
+    /// Whether `account` is the registered refund address
+    pub fn is_refund_address(&self, account: &Pubkey) -> bool {
+        self.refund_address != Pubkey::default() && self.refund_address == *account
+    }
+}
+
+/// Count of a recipient's DCA and limit orders still holding escrowed USDC
+#[account]
+pub struct RecipientOrders {
+    /// Recipient the orders deliver to
+    pub recipient: Pubkey,
+    
+    /// Orders opened and not yet completed or refunded
+    pub open_orders: u16,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RecipientOrders {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // recipient
+        2 + // open_orders
+        1; // bump
+
+    /// Counts a newly escrowed order, failing once `max_open_orders` are open (0 = no cap)
+    pub fn open_order(&mut self, max_open_orders: u16) -> Result<()> {
+        require!(
+            max_open_orders == 0 || self.open_orders < max_open_orders,
+            SuperSwapError::TooManyOpenOrders
+        );
+        self.open_orders = self.open_orders.checked_add(1).ok_or(SuperSwapError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Releases an order that completed or was refunded
+    ///
+    /// Saturates because orders escrowed before the count existed were never counted.
+    pub fn release_order(&mut self) {
+        self.open_orders = self.open_orders.saturating_sub(1);
+    }
+}
+
+/// Where a fill's share of output above the route's quote goes, set by `surplus_share_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurplusPolicy {
+    /// Nothing is kept: the recipient gets the whole output
+    Recipient,
+    /// The share is paid as fees to the fee recipient's account for the mint
+    Treasury,
+    /// The share is rebated to the fill's payer
+    PayerRebate,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderStatus {
+    /// Order is being processed
+    Pending,
+    /// Swap completed successfully
+    Completed,
+    /// Swap failed, USDC refunded
+    Refunded,
+    /// Order failed with error
+    Failed,
+    /// Bridged USDC is resting in a Jupiter limit order
+    LimitPlaced,
+    /// Bridged USDC is being swapped in scheduled tranches
+    DcaActive,
+    /// Left past its deadline with USDC in escrow; refunded by a keeper
+    Expired,
+}
+
+/// Parameters for initialization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeParams {
+    pub across_handler: Pubkey,
+    pub jupiter_program: Pubkey,
+    pub across_spoke_pool: Pubkey,
+    pub cctp_token_messenger_minter: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fee_bps: u16,
+    pub outbound_fee_bps: u16,
+    pub min_outbound_amount: u64,
+    pub max_outbound_amount: u64,
+}
+
+/// Parameters for creating an additional deployment instance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateInstanceParams {
+    /// Nonzero id scoping the instance's config and order PDAs
+    pub instance_id: u16,
+    /// Admin of the new instance
+    pub admin: Pubkey,
+    pub config: InitializeParams,
+}
+
+/// Parameters for uploading one chunk of a staged Jupiter route
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UploadRouteChunkParams {
+    pub order_id: u64,
+    /// Length of the complete route; fixed by the first chunk
+    pub total_len: u32,
+    /// Position of this chunk in the route, equal to the bytes uploaded so far
+    pub offset: u32,
+    pub data: Vec<u8>,
+}
+
+/// Parameters for locking a fully uploaded route
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FinalizeRouteParams {
+    pub order_id: u64,
+}
+
+/// Parameters for entering or leaving refunds-only mode
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRefundsOnlyParams {
+    pub enabled: bool,
+}
+
+/// Parameters for proposing a timelocked config update; omitted fields are left unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProposeConfigUpdateParams {
+    pub new_across_handler: Option<Pubkey>,
+    pub new_jupiter_program: Option<Pubkey>,
+    pub new_fee_recipient: Option<Pubkey>,
+    pub new_config_update_delay_seconds: Option<i64>,
+    /// Handler to accept alongside the current one, e.g. ahead of a handler upgrade
+    pub add_across_handler: Option<Pubkey>,
+}
+
+/// Parameters for removing an additional Across handler
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveAcrossHandlerParams {
+    pub handler: Pubkey,
+}
+
+/// Parameters for updating configuration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigParams {
+    /// Proposed admin, recorded as the config's pending admin until it calls `accept_admin`
+    pub new_admin: Option<Pubkey>,
+    pub new_across_spoke_pool: Option<Pubkey>,
+    pub new_cctp_token_messenger_minter: Option<Pubkey>,
+    pub new_fee_bps: Option<u16>,
+    pub new_outbound_fee_bps: Option<u16>,
+    pub new_min_outbound_amount: Option<u64>,
+    pub new_max_outbound_amount: Option<u64>,
+    pub new_across_fee_bps_estimate: Option<u16>,
+    pub new_cctp_fee_bps_estimate: Option<u16>,
+    pub new_onward_adapter: Option<Pubkey>,
+    pub new_jito_stake_pool: Option<Pubkey>,
+    pub new_marinade_state: Option<Pubkey>,
+    pub new_kamino_lending_market: Option<Pubkey>,
+    pub new_solend_lending_market: Option<Pubkey>,
+    pub new_max_gas_top_up_usdc: Option<u64>,
+    pub new_max_donation_bps: Option<u16>,
+    pub new_refund_bounty_bps: Option<u16>,
+    pub new_min_order_usdc: Option<u64>,
+    pub new_deliver_dust_as_usdc: Option<bool>,
+    pub new_max_open_orders_per_recipient: Option<u16>,
+    pub new_max_price_impact_bps: Option<u16>,
+    pub new_order_ttl_seconds: Option<u32>,
+    pub new_escrow_shortfall_tolerance: Option<u64>,
+    pub new_across_paused: Option<bool>,
+    pub new_cctp_paused: Option<bool>,
+    pub new_insurance_fee_share_bps: Option<u16>,
+    pub new_max_insurance_claim_usdc: Option<u64>,
+    pub new_fallback_output_mint: Option<Pubkey>,
+    pub new_rent_treasury: Option<Pubkey>,
+    pub new_min_retention_seconds: Option<u32>,
+    pub new_operator_fill_cost_usdc: Option<u64>,
+    pub new_guardian: Option<Pubkey>,
+    pub new_max_order_usdc: Option<u64>,
+    pub new_enabled_adapters: Option<u8>,
+    pub new_max_relayer_tip_usdc: Option<u64>,
+    pub new_surplus_share_bps: Option<u16>,
+    pub new_max_gas_drop_lamports: Option<u64>,
+    pub new_config_update_delay_seconds: Option<i64>,
+    pub new_operator: Option<Pubkey>,
+    pub new_daily_volume_cap: Option<u64>,
+    pub new_surplus_policy: Option<SurplusPolicy>,
+}
+
+/// Parameters for processing bridge and swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProcessBridgeAndSwapParams {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+    pub min_output_amount: u64,
+    pub destination_mint: Pubkey,
+    pub deadline: i64,
+    pub jupiter_swap_data: Vec<u8>,
+    pub post_swap_hook: Option<PostSwapHook>,
+    pub gas_top_up: Option<GasTopUp>,
+    pub donation: Option<DonationSplit>,
+    /// USDC paid from the order to the relayer paying for the fill, capped by
+    /// `Config::max_relayer_tip_usdc`; 0 for none
+    pub relayer_tip: u64,
+    /// Whether the recipient may be an off-curve address such as a PDA-based
+    /// treasury or smart wallet; off-curve recipients are rejected otherwise
+    pub allow_off_curve_recipient: bool,
+    /// Set by the handler once the order's swap has failed or fallen short of
+    /// `min_output_amount`: the fill skips the swap and refunds the bridged USDC
+    pub refund_failed_swap: bool,
+    /// Lamports the payer sends a recipient holding no SOL, capped by
+    /// `Config::max_gas_drop_lamports`; 0 for none
+    pub gas_drop_lamports: u64,
+}
+
+/// Share of an order routed to an allowlisted donation recipient
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DonationSplit {
+    /// Allowlisted donation recipient
+    pub recipient: Pubkey,
+    /// Share of the swap amount donated, capped by `Config::max_donation_bps`
+    pub bps: u16,
+}
+
+/// Portion of the bridged USDC swapped to SOL and delivered to the recipient for gas
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GasTopUp {
+    /// USDC to convert, capped by `Config::max_gas_top_up_usdc`
+    pub usdc_amount: u64,
+    /// Minimum lamports the conversion must produce
+    pub min_lamports_out: u64,
+    /// Jupiter USDC -> WSOL route
+    pub jupiter_swap_data: Vec<u8>,
+    /// Number of leading remaining accounts used by the route
+    pub jupiter_accounts_len: u8,
+}
+
+/// Follow-on CPI executed after the swap output is delivered
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PostSwapHook {
+    /// Hook program (must be allowlisted)
+    pub program_id: Pubkey,
+    /// Number of trailing remaining accounts passed to the hook
+    pub accounts_len: u8,
+    /// Instruction data for the hook
+    pub data: Vec<u8>,
+}
+
+/// Parameters for swapping an SPL token to USDC and bridging it to an EVM chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwapAndBridgeParams {
+    /// Outbound order ID
+    pub order_id: u64,
+    /// Minimum USDC the Jupiter swap must produce
+    pub min_usdc_out: u64,
+    /// Recipient on the destination chain (20-byte EVM address)
+    pub recipient: [u8; 20],
+    /// Destination EVM chain id
+    pub destination_chain_id: u64,
+    /// Token to receive on the destination chain (20-byte EVM address)
+    pub output_token: [u8; 20],
+    /// Amount the relayer delivers on the destination chain
+    pub output_amount: u64,
+    /// Across quote timestamp
+    pub quote_timestamp: u32,
+    /// Across fill deadline
+    pub fill_deadline: u32,
+    /// Serialized Jupiter instruction data
+    pub jupiter_swap_data: Vec<u8>,
+    /// Number of remaining accounts belonging to the Jupiter route;
+    /// the rest are passed to the Across deposit
+    pub jupiter_accounts_len: u8,
+}
+
+/// Parameters for swapping an SPL token to USDC and burning it via CCTP
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwapAndBurnParams {
+    /// Outbound order ID
+    pub order_id: u64,
+    /// Minimum USDC the Jupiter swap must produce
+    pub min_usdc_out: u64,
+    /// Recipient of the minted USDC (20-byte EVM address)
+    pub recipient: [u8; 20],
+    /// Destination EVM chain id
+    pub destination_chain_id: u64,
+    /// CCTP domain of the destination chain (must match destination_chain_id)
+    pub destination_domain: u32,
+    /// Serialized Jupiter instruction data
+    pub jupiter_swap_data: Vec<u8>,
+    /// Number of remaining accounts belonging to the Jupiter route;
+    /// the rest are passed to deposit_for_burn
+    pub jupiter_accounts_len: u8,
+}
+
+/// Parameters for quoting an outbound swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QuoteOutboundParams {
+    /// USDC the Jupiter swap is expected to produce
+    pub usdc_amount: u64,
+    /// Bridge the quote is for
+    pub bridge: OutboundBridge,
+}
+
+/// Expected amounts for an outbound swap, returned by `quote_outbound`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OutboundQuote {
+    /// USDC the swap is expected to produce
+    pub usdc_amount: u64,
+    /// Protocol fee taken on Solana
+    pub protocol_fee: u64,
+    /// USDC handed to the bridge
+    pub bridged_amount: u64,
+    /// Estimated bridge fee from the configured estimate
+    pub bridge_fee_estimate: u64,
+    /// Minimum USDC receivable on the destination chain
+    pub min_received: u64,
+    /// Whether the amount is within the configured outbound limits
+    pub within_limits: bool,
+}
+
+/// Amounts of an inbound fill, returned by `process_bridge_and_swap`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FillResult {
+    pub order_id: u64,
+    /// Protocol fee taken from the bridged USDC
+    pub fee_amount: u64,
+    /// USDC routed into the main swap, after the fee, relayer tip, donation and gas top-up
+    pub swap_amount: u64,
+    /// USDC paid to the relayer that filled the order
+    pub relayer_tip: u64,
+    /// Output delivered to the recipient (lamports when unwrapped, USDC for dust and late fills)
+    pub amount_delivered: u64,
+}
+
+/// Parameters for forwarding a completed order's output to its onward destination
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ForwardOnwardParams {
+    pub order_id: u64,
+}
+
+/// Parameters for executing a completed order's post-swap action
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExecutePostSwapActionParams {
+    pub order_id: u64,
+    /// Required by the provide-liquidity action, ignored by the others
+    pub liquidity: Option<ProvideLiquidityParams>,
+    /// Instruction data for the spend program, required by the spend action
+    pub spend_data: Option<Vec<u8>>,
+    /// Minimum vault shares to mint, required by the vault deposit action
+    pub min_vault_shares: Option<u64>,
+}
+
+/// Relayer-computed inputs for the provide-liquidity action
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProvideLiquidityParams {
+    /// Serialized Jupiter instruction swapping half of the USDC into the pool's other token
+    pub jupiter_swap_data: Vec<u8>,
+    /// Number of remaining accounts belonging to the Jupiter route
+    pub jupiter_accounts_len: u8,
+    /// Minimum output of the half swap
+    pub min_pair_out: u64,
+    /// LP tokens (Raydium) or liquidity (Whirlpool) to mint
+    pub liquidity_amount: u128,
+}
+
+/// Parameters for refunding an outbound order's escrow to its sender
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RefundOutboundParams {
+    pub order_id: u64,
+}
+
+/// Parameters for settling an outbound order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SettleOutboundOrderParams {
+    pub order_id: u64,
+    /// Destination chain transaction hash of the fill or mint
+    pub destination_tx_hash: [u8; 32],
+}
+
+/// Parameters for attaching metadata to a swap order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateOrderExtensionParams {
+    pub order_id: u64,
+    pub origin_chain_id: u64,
+    pub evm_origin: [u8; 20],
+    pub route_hash: [u8; 32],
+    pub integrator_id: u32,
+    pub onward_destination: Option<OnwardDestination>,
+    pub post_swap_action: Option<PostSwapAction>,
+    pub claimant: Option<Pubkey>,
+}
+
+/// Parameters for attaching a stream plan to an order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateStreamPlanParams {
+    pub order_id: u64,
+    pub recipients: Vec<StreamRecipient>,
+    pub start_time: i64,
+    pub duration_seconds: u64,
+    pub period_seconds: u64,
+    pub cliff_bps: u16,
+}
+
+/// Parameters for attaching a split plan to an order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateSplitPlanParams {
+    pub order_id: u64,
+    pub recipients: Vec<SplitRecipient>,
+}
+
+/// Parameters for attaching a basket plan to an order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateBasketPlanParams {
+    pub order_id: u64,
+    pub beneficiary: Pubkey,
+    pub legs: Vec<BasketLegParams>,
+}
+
+/// Output mint, weight and slippage bound of one leg of a basket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BasketLegParams {
+    pub mint: Pubkey,
+    pub weight_bps: u16,
+    pub min_output_amount: u64,
+}
+
+/// Parameters for swapping one leg of an order's basket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExecuteBasketLegParams {
+    pub order_id: u64,
+    pub leg_index: u8,
+    /// Serialized Jupiter instruction swapping the leg's USDC into its mint
+    pub jupiter_swap_data: Vec<u8>,
+}
+
+/// Recipient and weight of one leg of a split
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SplitRecipient {
+    pub recipient: Pubkey,
+    pub weight: u16,
+}
+
+/// Parameters for recovering funds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecoverFundsParams {
+    pub token_mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Parameters for queueing a recovery above the timelock threshold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QueueRecoveryParams {
+    /// Caller-chosen id, unique among queued recoveries
+    pub recovery_id: u64,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Parameters for setting the recovery guard's limits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRecoveryLimitsParams {
+    pub limits: RecoveryLimits,
+}
+
+/// Parameters for paying out of the insurance fund
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PayInsuranceParams {
+    /// Operator-assigned incident the payout compensates, recorded in the event
+    pub incident_id: u64,
+    /// USDC to pay
+    pub amount: u64,
+}
+
+/// Parameters for filing, approving or paying an insurance claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InsuranceClaimParams {
+    pub order_id: u64,
+}
+
+/// Program-owned account stray lamports can be recovered from, derived within the config's instance
+///
+/// The rent vault is left out: its surplus goes through `withdraw_rent_vault`,
+/// which keeps its accounting.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LamportSource {
+    Config,
+    OutboundRateLimit,
+    RecoveryGuard,
+    AdminLog,
+    OrderArchive,
+    SwapOrder { order_id: u64 },
+    OutboundOrder { order_id: u64 },
+}
+
+impl LamportSource {
+    /// Address of the source within instance `instance_id` of `program_id`
+    pub fn address(&self, program_id: &Pubkey, instance_id: u16) -> Pubkey {
+        match *self {
+            Self::Config => superswap_pda::find_instance_config(program_id, instance_id).0,
+            Self::OutboundRateLimit => superswap_pda::find_instance_outbound_rate_limit(program_id, instance_id).0,
+            Self::RecoveryGuard => superswap_pda::find_instance_recovery_guard(program_id, instance_id).0,
+            Self::AdminLog => superswap_pda::find_instance_admin_log(program_id, instance_id).0,
+            Self::OrderArchive => superswap_pda::find_instance_order_archive(program_id, instance_id).0,
+            Self::SwapOrder { order_id } => superswap_pda::find_instance_swap_order(program_id, instance_id, order_id).0,
+            Self::OutboundOrder { order_id } => {
+                superswap_pda::find_instance_outbound_order(program_id, instance_id, order_id).0
+            }
+        }
+    }
+}
+
+/// Parameters for recovering stray lamports from a program-owned account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecoverLamportsParams {
+    /// Account the lamports leave, which must be the `source` account passed
+    pub source: LamportSource,
+    pub amount: u64,
+}
+
+/// Parameters for queueing a lamport recovery above the timelock threshold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QueueLamportRecoveryParams {
+    /// Caller-chosen id, unique among queued recoveries
+    pub recovery_id: u64,
+    /// Account the lamports will leave, which must be the `source` account passed
+    pub source: LamportSource,
+    pub amount: u64,
+}
+
+/// Parameters for executing or cancelling a queued recovery
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingRecoveryParams {
+    pub recovery_id: u64,
+}
+
+/// Parameters for bridging protocol USDC back to an EVM treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RebalanceTreasuryParams {
+    /// USDC to bridge out of the program's USDC account
+    pub amount: u64,
+    /// Bridge to use
+    pub bridge: OutboundBridge,
+    /// Treasury address on the destination chain (20-byte EVM address)
+    pub recipient: [u8; 20],
+    /// Destination EVM chain id
+    pub destination_chain_id: u64,
+    /// CCTP domain of the destination chain (CCTP only)
+    pub destination_domain: u32,
+    /// Token to receive on the destination chain (Across only)
+    pub output_token: [u8; 20],
+    /// Amount the relayer delivers on the destination chain (Across only)
+    pub output_amount: u64,
+    /// Across quote timestamp (Across only)
+    pub quote_timestamp: u32,
+    /// Across fill deadline (Across only)
+    pub fill_deadline: u32,
+}
+
+
+/// Parameters for disabling or re-enabling a destination mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetDestinationMintParams {
+    pub mint: Pubkey,
+    pub disabled: bool,
+}
+
+/// Parameters for creating or updating a destination mint's config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintConfigParams {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    /// Fee charged instead of the config's; None keeps the config's
+    pub fee_bps: Option<u16>,
+    /// Largest order into the mint in USDC base units; 0 for no cap
+    pub max_order_usdc: u64,
+    /// Furthest the output may fall below the route's quote; 0 to leave it unchecked
+    pub max_slippage_bps: u16,
+    /// Most USDC filled into the mint per UTC day; 0 for no cap
+    pub daily_volume_cap: u64,
+}
+
+/// Parameters for creating a daily volume tracker
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateVolumeTrackerParams {
+    /// Destination mint to track, or the default pubkey for the config-wide tracker
+    pub mint: Pubkey,
+}
+
+/// Parameters for deleting a destination mint's config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DeleteMintConfigParams {
+    pub mint: Pubkey,
+}
+
+/// Parameters for registering a route template
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegisterRouteTemplateParams {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub discriminator: [u8; 8],
+    pub accounts: Vec<TemplateAccount>,
+}
+
+/// Parameters for removing a route template
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveRouteTemplateParams {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+}
+
+/// Parameters for adding or removing an allowlisted hook program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HookProgramParams {
+    pub program_id: Pubkey,
+}
+
+/// Parameters for registering or removing a payer operator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PayerOperatorParams {
+    pub operator: Pubkey,
+}
+
+/// Parameters for adding or removing an allowlisted vault program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VaultProgramParams {
+    pub program_id: Pubkey,
+}
+
+/// Parameters for adding or removing an allowlisted donation recipient
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DonationRecipientParams {
+    pub recipient: Pubkey,
+}
+
+/// Parameters for adding or removing an allowlisted spend program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SpendProgramParams {
+    pub program_id: Pubkey,
+}
+
+/// Parameters for adding or removing an allowlisted delegate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ApprovedDelegateParams {
+    pub delegate: Pubkey,
+}
+
+/// Parameters for revoking an order's delegate and reclaiming its unpulled allowance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RevokeDelegateParams {
+    pub order_id: u64,
+}
+
+/// Parameters for registering a recipient's delivery preferences
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRecipientPreferencesParams {
+    pub preferred_destination_account: Pubkey,
+    pub auto_unwrap_sol: bool,
+    pub gas_top_up: bool,
+    pub refund_address: Pubkey,
+}
+
+/// Parameters for configuring the outbound rate limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetOutboundRateLimitParams {
+    pub period_seconds: i64,
+    pub period_cap: u64,
+}
+
+/// Parameters for placing a Jupiter limit order with bridged USDC
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PlaceLimitOrderParams {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+    pub destination_mint: Pubkey,
+    /// Destination tokens asked for the USDC left after the fee; sets the limit price
+    pub taking_amount: u64,
+    /// Optional expiry of the limit order
+    pub expired_at: Option<i64>,
+}
+
+/// Parameters for cancelling an order's Jupiter limit order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CancelLimitOrderParams {
+    pub order_id: u64,
+}
+
+/// Parameters for closing a settled order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CloseOrderParams {
+    pub order_id: u64,
+}
+
+/// Parameters for scheduling bridged USDC as DCA tranches
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateDcaOrderParams {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub usdc_amount: u64,
+    pub destination_mint: Pubkey,
+    pub tranche_count: u8,
+    pub interval_seconds: i64,
+    /// Minimum output for a full tranche
+    pub min_output_per_tranche: u64,
+}
+
+/// Parameters for executing the next DCA tranche
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExecuteDcaTrancheParams {
+    pub order_id: u64,
+    pub jupiter_swap_data: Vec<u8>,
+}
+
+/// Parameters for cancelling a DCA order and refunding its unswapped USDC
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CancelDcaOrderParams {
+    pub order_id: u64,
+}
+
+/// Parameters for expiring a stale escrowed order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExpireAndRefundParams {
+    pub order_id: u64,
+}
+
+/// Parameters for claiming an order's escrowed output
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimOutputParams {
+    pub order_id: u64,
+}
+
+/// Parameters for releasing an EVM claim PDA's tokens to a Solana wallet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimEvmFundsParams {
+    pub evm_address: [u8; 20],
+    /// `personal_sign` signature over `utils::evm::claim_message` for the destination
+    pub signature: [u8; 64],
+    pub recovery_id: u8,
+}
+
+/// Parameters for funding the rent vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FundRentVaultParams {
+    pub amount: u64,
+}
+
+/// Parameters for withdrawing from the rent vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawRentVaultParams {
+    pub amount: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit(period_cap: u64) -> OutboundRateLimit {
+        OutboundRateLimit {
+            period_seconds: 100,
+            period_cap,
+            period_start: 0,
+            period_volume: 0,
+            bump: 255,
+        }
+    }
+
+    fn dca_order(tranche_count: u8, remaining_amount: u64) -> DcaOrder {
+        DcaOrder {
+            swap_order: Pubkey::default(),
+            tranche_count,
+            tranche_amount: 300,
+            interval_seconds: 60,
+            next_execution_at: 0,
+            min_output_per_tranche: 600,
+            remaining_amount,
+            fills: Vec::new(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn dca_last_tranche_takes_remainder() {
+        let mut dca = dca_order(3, 1_000);
+
+        assert_eq!(dca.next_tranche_amount(), 300);
+        dca.record_fill(300, 600, 10).unwrap();
+        dca.record_fill(300, 600, 70).unwrap();
+        assert_eq!(dca.next_tranche_amount(), 400);
+        assert_eq!(dca.tranche_min_output(400).unwrap(), 800);
+        assert_eq!(dca.next_execution_at, 130);
+
+        dca.record_fill(400, 800, 130).unwrap();
+        assert!(dca.is_complete());
+        assert_eq!(dca.fills.len(), 3);
+    }
+
+    #[test]
+    fn spend_action_fits_extension_space() {
+        let action = PostSwapAction::Spend {
+            program: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+        };
+        assert!(action.try_to_vec().unwrap().len() <= PostSwapAction::MAX_LEN);
+    }
+
+    #[test]
+    fn vault_action_is_largest_variant() {
+        let action = PostSwapAction::DepositVault {
+            program: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+        };
+        assert_eq!(action.try_to_vec().unwrap().len(), PostSwapAction::MAX_LEN);
+    }
+
+    #[test]
+    fn delegate_action_fits_extension() {
+        let action = PostSwapAction::ApproveDelegate {
+            delegate: Pubkey::new_unique(),
+            amount: u64::MAX,
+            beneficiary: Pubkey::new_unique(),
+        };
+        assert!(action.try_to_vec().unwrap().len() <= PostSwapAction::MAX_LEN);
+    }
+
+    #[test]
+    fn stream_split_gives_remainder_to_last() {
+        let recipient = |share_bps| StreamRecipient {
+            beneficiary: Pubkey::new_unique(),
+            share_bps,
+        };
+        let plan = StreamPlan {
+            swap_order: Pubkey::default(),
+            recipients: vec![recipient(3_333), recipient(3_333), recipient(3_334)],
+            start_time: 0,
+            duration_seconds: 60,
+            period_seconds: 60,
+            cliff_bps: 0,
+            bump: 255,
+        };
+
+        assert_eq!(plan.split(1_000).unwrap(), vec![333, 333, 334]);
+        assert_eq!(plan.split(7).unwrap(), vec![2, 2, 3]);
+    }
+
+    #[test]
+    fn weighted_split_is_proportional() {
+        assert_eq!(split_weighted(100, &[1, 1, 2]).unwrap(), vec![25, 25, 50]);
+        assert_eq!(split_weighted(10, &[3, 3, 3]).unwrap(), vec![3, 3, 4]);
+        assert!(split_weighted(10, &[0]).is_err());
+    }
+
+    #[test]
+    fn basket_legs_cover_the_whole_order() {
+        let leg = |mint: Pubkey, weight_bps: u16| BasketLeg {
+            mint,
+            weight_bps,
+            min_output_amount: 0,
+            delivered: 0,
+            executed: false,
+        };
+        let (sol, jito_sol) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let legs = vec![leg(sol, 5_000), leg(jito_sol, 5_000)];
+        assert!(BasketPlan::validate_legs(&legs));
+        assert!(!BasketPlan::validate_legs(&[leg(sol, 5_000), leg(jito_sol, 4_999)]));
+        assert!(!BasketPlan::validate_legs(&[leg(sol, 5_000), leg(sol, 5_000)]));
+        assert!(!BasketPlan::validate_legs(&[leg(sol, 10_000), leg(jito_sol, 0)]));
+
+        let mut plan = BasketPlan {
+            swap_order: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            usdc_amount: 1_000_001,
+            legs,
+            bump: 255,
+        };
+        assert_eq!(plan.leg_amounts().unwrap(), vec![500_000, 500_001]);
+        assert_eq!(plan.unexecuted_usdc().unwrap(), 1_000_001);
+        assert!(!plan.is_complete());
+
+        plan.legs[0].executed = true;
+        assert_eq!(plan.unexecuted_usdc().unwrap(), 500_001);
+    }
+
+    #[test]
+    fn unset_preferences_match_no_account() {
+        let mut preferences = RecipientPreferences {
+            owner: Pubkey::new_unique(),
+            preferred_destination_account: Pubkey::default(),
+            auto_unwrap_sol: false,
+            gas_top_up: true,
+            refund_address: Pubkey::default(),
+            bump: 255,
+        };
+        assert!(!preferences.is_preferred_destination(&Pubkey::default()));
+        assert!(!preferences.is_refund_address(&Pubkey::default()));
+
+        let account = Pubkey::new_unique();
+        preferences.preferred_destination_account = account;
+        assert!(preferences.is_preferred_destination(&account));
+        assert!(!preferences.is_refund_address(&account));
+    }
+
+    #[test]
+    fn ttl_starts_at_creation_and_zero_disables_it() {
+        let order = SwapOrder {
+            order_id: 1,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000,
+            min_output_amount: 0,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 0,
+            created_at: 1_000,
+            status: OrderStatus::DcaActive,
+            bump: 255,
+            rent_payer: Pubkey::new_unique(),
+            surplus: 0,
+        };
+        assert!(!order.is_past_ttl(1_059, 60));
+        assert!(order.is_past_ttl(1_060, 60));
+        assert!(!order.is_past_ttl(i64::MAX, 0));
+
+        assert!(!order.is_retention_elapsed(1_059, 60));
+        assert!(order.is_retention_elapsed(1_060, 60));
+        assert!(order.is_retention_elapsed(1_000, 0));
+    }
+
+    #[test]
+    fn only_pending_and_failed_orders_are_owed_from_the_vault() {
+        let mut order = SwapOrder {
+            order_id: 1,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000,
+            min_output_amount: 0,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 0,
+            created_at: 0,
+            status: OrderStatus::Pending,
+            bump: 255,
+            rent_payer: Pubkey::new_unique(),
+            surplus: 0,
+        };
+        for (status, owed) in [
+            (OrderStatus::Pending, true),
+            (OrderStatus::Failed, true),
+            (OrderStatus::Completed, false),
+            (OrderStatus::Refunded, false),
+            (OrderStatus::LimitPlaced, false),
+            (OrderStatus::DcaActive, false),
+            (OrderStatus::Expired, false),
+        ] {
+            order.status = status;
+            assert_eq!(order.is_vault_liability(), owed, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn orders_are_scoped_to_their_instance() {
+        use superswap_pda::{find_instance_swap_order, find_swap_order, instance_seed};
+        assert_eq!(find_instance_swap_order(&crate::ID, 0, 7), find_swap_order(&crate::ID, 7));
+
+        let (address, bump) = find_instance_swap_order(&crate::ID, 3, 7);
+        let order = SwapOrder {
+            order_id: 7,
+            recipient: Pubkey::new_unique(),
+            usdc_amount: 1_000,
+            min_output_amount: 0,
+            destination_mint: Pubkey::new_unique(),
+            deadline: 0,
+            created_at: 0,
+            status: OrderStatus::Completed,
+            bump,
+            rent_payer: Pubkey::new_unique(),
+            surplus: 0,
+        };
+        assert!(order.is_instance_order(&address, &instance_seed(3)));
+        assert!(!order.is_instance_order(&address, &instance_seed(0)));
+        assert!(!order.is_instance_order(&address, &instance_seed(4)));
+    }
+
+    /// Config account data as the first deployment wrote it on mainnet, discriminator included
+    const MAINNET_CONFIG_V1: [u8; ConfigV1::LEN] = [
+        // discriminator
+        0x9b, 0x0c, 0xaa, 0xe0, 0x1e, 0xfa, 0xcc, 0x82,
+        // admin
+        0x7e, 0x8c, 0x08, 0x87, 0x60, 0xbf, 0xde, 0x1d, 0xdd, 0xcf, 0x32, 0xc1, 0x7f, 0x20, 0x9b, 0x82,
+        0x42, 0xee, 0x52, 0xaa, 0xf1, 0x31, 0xfa, 0xcd, 0x88, 0xd0, 0xea, 0x2c, 0x6d, 0x0b, 0x06, 0xf2,
+        // across_handler
+        0x41, 0x57, 0xb0, 0x58, 0x0f, 0x31, 0xc5, 0xfc, 0xe4, 0x4a, 0x62, 0x58, 0x2d, 0xbc, 0xf9, 0xd7,
+        0x8e, 0xe7, 0x59, 0x43, 0xa0, 0x84, 0xa3, 0x93, 0xb3, 0x50, 0x36, 0x8d, 0x22, 0x89, 0x93, 0x08,
+        // jupiter_program
+        0x04, 0x79, 0xd5, 0x5b, 0xf2, 0x31, 0xc0, 0x6e, 0xee, 0x74, 0xc5, 0x6e, 0xce, 0x68, 0x15, 0x07,
+        0xfd, 0xb1, 0xb2, 0xde, 0xa3, 0xf4, 0x8e, 0x51, 0x02, 0xb1, 0xcd, 0xa2, 0x56, 0xbc, 0x13, 0x8f,
+        // usdc_mint
+        0xc6, 0xfa, 0x7a, 0xf3, 0xbe, 0xdb, 0xad, 0x3a, 0x3d, 0x65, 0xf3, 0x6a, 0xab, 0xc9, 0x74, 0x31,
+        0xb1, 0xbb, 0xe4, 0xc2, 0xd2, 0xf6, 0xe0, 0xe4, 0x7c, 0xa6, 0x02, 0x03, 0x45, 0x2f, 0x5d, 0x61,
+        // fee_recipient
+        0x62, 0x71, 0xcb, 0x71, 0x19, 0x47, 0x6b, 0x9d, 0xce, 0x00, 0xd8, 0x15, 0xc8, 0xff, 0x31, 0x5f,
+        0xc8, 0xbf, 0x7d, 0x28, 0x48, 0x63, 0x3d, 0x34, 0x94, 0x2a, 0xdf, 0xd5, 0x35, 0xf2, 0xde, 0xfe,
+        // fee_bps
+        0x1e, 0x00,
+        // is_paused
+        0x00,
+        // bump
+        0xfe,
+    ];
+
+    /// The mainnet config after `migrate_config`, with the settings V1 lacked configured
+    /// the way the admin set them afterwards through `update_config`
+    fn mainnet_config() -> Config {
+        use std::str::FromStr;
+        let key = |address: &str| Pubkey::from_str(address).unwrap();
+        let v1 = Config::v1_from_account_data(&MAINNET_CONFIG_V1).unwrap();
+        Config {
+            across_spoke_pool: key("DLv3NggMiSaef97YCkew5xKUHDh13tVGZ7tydt3ZeAru"),
+            cctp_token_messenger_minter: key("CCTPiPYPc6AsJuwueEnWgSgucamXDZwBd53dQ11YiKX3"),
+            outbound_fee_bps: 25,
+            min_outbound_amount: 1_000_000,
+            max_outbound_amount: 250_000_000_000,
+            across_fee_bps_estimate: 8,
+            jito_stake_pool: key("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb"),
+            marinade_state: key("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC"),
+            kamino_lending_market: key("7u3HeHxYDLhnCoErrtycNokbQYbWGzLs6JSDqGAv5PfF"),
+            max_gas_top_up_usdc: 2_000_000,
+            max_donation_bps: 500,
+            refund_bounty_bps: 10,
+            min_order_usdc: 500_000,
+            deliver_dust_as_usdc: true,
+            max_open_orders_per_recipient: 16,
+            max_price_impact_bps: 150,
+            order_ttl_seconds: 2_592_000,
+            escrow_shortfall_tolerance: 1,
+            cctp_paused: true,
+            insurance_fee_share_bps: 1_000,
+            max_insurance_claim_usdc: 10_000_000_000,
+            fallback_output_mint: v1.usdc_mint,
+            min_retention_seconds: 604_800,
+            operator_fill_cost_usdc: 2_500,
+            ..Config::from_v1(v1, Pubkey::new_unique())
+        }
+    }
+
+    #[test]
+    fn fills_take_the_stricter_price_impact_bound() {
+        let mut config = mainnet_config();
+        assert_eq!(config.max_price_impact_bps_with(0), 150);
+        assert_eq!(config.max_price_impact_bps_with(50), 50);
+        assert_eq!(config.max_price_impact_bps_with(300), 150);
+
+        config.max_price_impact_bps = 0;
+        assert_eq!(config.max_price_impact_bps_with(0), 0);
+        assert_eq!(config.max_price_impact_bps_with(300), 300);
+    }
+
+    #[test]
+    fn refunds_only_rejects_fills_but_not_refunds() {
+        let mut config = mainnet_config();
+        assert!(config.require_accepting_fills().is_ok());
+        assert!(config.require_not_paused().is_ok());
+
+        config.refunds_only = true;
+        assert_eq!(
+            config.require_accepting_fills().unwrap_err(),
+            SuperSwapError::RefundsOnly.into()
+        );
+        assert!(config.require_not_paused().is_ok());
+
+        // A pause halts refunds too, whatever the mode
+        config.is_paused = true;
+        assert_eq!(
+            config.require_accepting_fills().unwrap_err(),
+            SuperSwapError::ProgramPaused.into()
+        );
+        assert_eq!(config.require_not_paused().unwrap_err(), SuperSwapError::ProgramPaused.into());
+    }
+
+    #[test]
+    fn admin_log_records_each_changed_setting() {
+        let before = mainnet_config();
+        let mut config = before.clone();
+        config.fee_bps = before.fee_bps + 5;
+        config.guardian = Pubkey::new_unique();
+        config.refunds_only = true; // not an update_config setting
+
+        let changes = config.changes_since(&before);
+        let fields: Vec<&str> = changes.iter().map(|(field, _, _)| *field).collect();
+        assert_eq!(fields, ["fee_bps", "guardian"]);
+        assert_eq!(changes[0].1, before.fee_bps.to_log_value());
+        assert_eq!(changes[0].2, config.fee_bps.to_log_value());
+        assert_eq!(changes[1].2, config.guardian.to_bytes());
+
+        let mut admin_log = AdminLog { config: Pubkey::new_unique(), count: 0, entries: Vec::new(), bump: 255 };
+        admin_log.record_changes(before.admin, AdminAction::UpdateConfig, 1_700_000_000, changes);
+        assert_eq!(admin_log.count, 2);
+        assert_eq!(admin_log.entries[1].field, AdminLogEntry::field_name("guardian"));
+        assert_eq!(admin_log.entries[1].actor, before.admin);
+    }
+
+    #[test]
+    fn roles_extend_the_admin_only_once_set() {
+        let mut config = mainnet_config();
+        let operator = Pubkey::new_unique();
+        let guardian = Pubkey::new_unique();
+        assert!(config.is_operator(&config.admin) && config.is_guardian(&config.admin));
+        assert!(!config.is_operator(&Pubkey::default()) && !config.is_guardian(&Pubkey::default()));
+
+        config.operator = operator;
+        config.guardian = guardian;
+        assert!(config.is_operator(&operator) && !config.is_guardian(&operator));
+        assert!(config.is_guardian(&guardian) && !config.is_operator(&guardian));
+    }
+
+    #[test]
+    fn pending_config_update_applies_only_proposed_fields() {
+        let before = mainnet_config();
+        let mut config = before.clone();
+        let pending = PendingConfigUpdate {
+            config: Pubkey::new_unique(),
+            across_handler: None,
+            jupiter_program: Some(Pubkey::new_unique()),
+            fee_recipient: None,
+            config_update_delay_seconds: Some(3_600),
+            add_across_handler: None,
+            executable_at: 0,
+            bump: 255,
+        };
+        pending.apply(&mut config).unwrap();
+
+        let changes = config.changes_since(&before);
+        let fields: Vec<&str> = changes.iter().map(|(field, _, _)| *field).collect();
+        assert_eq!(fields, ["jupiter_program", "config_update_delay_seconds"]);
+        assert_eq!(config.jupiter_program, pending.jupiter_program.unwrap());
+    }
+
+    #[test]
+    fn additional_across_handlers_are_bounded_and_unique() {
+        let mut config = mainnet_config();
+        let main_handler = config.across_handler;
+        assert_eq!(
+            config.require_can_add_across_handler(&main_handler).unwrap_err(),
+            SuperSwapError::AcrossHandlerAlreadyRegistered.into()
+        );
+
+        for _ in 0..Config::MAX_ADDITIONAL_ACROSS_HANDLERS {
+            let pending = PendingConfigUpdate {
+                config: Pubkey::new_unique(),
+                across_handler: None,
+                jupiter_program: None,
+                fee_recipient: None,
+                config_update_delay_seconds: None,
+                add_across_handler: Some(Pubkey::new_unique()),
+                executable_at: 0,
+                bump: 255,
+            };
+            pending.apply(&mut config).unwrap();
+            assert!(config.is_across_handler(&pending.add_across_handler.unwrap()));
+        }
+        assert!(config.is_across_handler(&main_handler));
+        assert_eq!(
+            config.require_can_add_across_handler(&Pubkey::new_unique()).unwrap_err(),
+            SuperSwapError::AcrossHandlerLimitReached.into()
+        );
+
+        // A removed handler frees its slot, and the empty slots never match
+        let removed = config.additional_across_handlers[1];
+        config.remove_across_handler(&removed).unwrap();
+        assert!(!config.is_across_handler(&removed) && !config.is_across_handler(&Pubkey::default()));
+        assert_eq!(config.registered_additional_across_handlers().len(), Config::MAX_ADDITIONAL_ACROSS_HANDLERS - 1);
+        assert!(config.require_can_add_across_handler(&removed).is_ok());
+    }
+
+    #[test]
+    fn order_size_bounds_are_inclusive_and_consistent() {
+        let mut config = mainnet_config();
+        assert!(config.is_dust_order(499_999) && !config.is_dust_order(500_000));
+        assert!(config.order_size_range_valid());
+
+        config.max_order_usdc = 500_000;
+        assert!(!config.exceeds_max_order(500_000) && config.exceeds_max_order(500_001));
+        assert!(config.order_size_range_valid());
+
+        config.max_order_usdc = 499_999;
+        assert!(!config.order_size_range_valid());
+    }
+
+    #[test]
+    fn mint_config_validates_overrides_and_caps_orders() {
+        let mut mint_config = MintConfig {
+            config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            enabled: false,
+            fee_bps: None,
+            max_order_usdc: 0,
+            max_slippage_bps: 0,
+            daily_volume_cap: 0,
+            bump: 255,
+        };
+        assert!(!mint_config.exceeds_max_order(u64::MAX));
+
+        let params = MintConfigParams {
+            mint: mint_config.mint,
+            enabled: true,
+            fee_bps: Some(MintConfig::MAX_FEE_BPS + 1),
+            max_order_usdc: 1_000,
+            max_slippage_bps: 50,
+            daily_volume_cap: 0,
+        };
+        assert_eq!(
+            mint_config.set(&params).unwrap_err(),
+            SuperSwapError::InvalidFeeConfiguration.into()
+        );
+        mint_config.set(&MintConfigParams { fee_bps: Some(25), ..params }).unwrap();
+        assert!(mint_config.enabled && mint_config.fee_bps == Some(25));
+        assert!(!mint_config.exceeds_max_order(1_000) && mint_config.exceeds_max_order(1_001));
+    }
+
+    #[test]
+    fn volume_tracker_caps_each_utc_day() {
+        let mut tracker = VolumeTracker {
+            config: Pubkey::new_unique(),
+            mint: Pubkey::default(),
+            day: 0,
+            volume: 0,
+            bump: 255,
+        };
+        let day = VolumeTracker::SECONDS_PER_DAY;
+
+        tracker.record(600, 10 * day + 5, 1_000).unwrap();
+        tracker.record(400, 11 * day - 1, 1_000).unwrap();
+        assert_eq!(
+            tracker.record(1, 11 * day - 1, 1_000).unwrap_err(),
+            SuperSwapError::DailyVolumeCapExceeded.into()
+        );
+        assert_eq!(tracker.volume, 1_000);
+
+        // Midnight UTC starts a fresh day, and no cap only counts
+        tracker.record(1, 11 * day, 1_000).unwrap();
+        assert_eq!((tracker.day, tracker.volume), (11, 1));
+        tracker.record(5_000, 11 * day, 0).unwrap();
+        assert_eq!(tracker.volume, 5_001);
+    }
+
+    #[test]
+    fn route_staging_assembles_chunks_in_order() {
+        let route: Vec<u8> = (0..=255).cycle().take(2_000).collect();
+        let mut route_staging = RouteStaging {
+            config: Pubkey::new_unique(),
+            order_id: 7,
+            authority: Pubkey::new_unique(),
+            total_len: route.len() as u32,
+            finalized: false,
+            data: Vec::new(),
+            bump: 255,
+        };
+
+        route_staging.append(0, &route[..900]).unwrap();
+        // A replayed or skipped chunk is rejected
+        assert_eq!(
+            route_staging.append(0, &route[..900]).unwrap_err(),
+            SuperSwapError::RouteChunkOutOfOrder.into()
+        );
+        route_staging.append(900, &route[900..1_800]).unwrap();
+        assert_eq!(route_staging.finalize().unwrap_err(), SuperSwapError::RouteIncomplete.into());
+        assert_eq!(
+            route_staging.append(1_800, &route[1_800..]).and(route_staging.append(2_000, &[0])).unwrap_err(),
+            SuperSwapError::RouteDataTooLarge.into()
+        );
+
+        route_staging.finalize().unwrap();
+        assert_eq!(route_staging.data, route);
+        assert_eq!(
+            route_staging.append(2_000, &[]).unwrap_err(),
+            SuperSwapError::RouteAlreadyFinalized.into()
+        );
+        assert_eq!(
+            8 + route_staging.try_to_vec().unwrap().len(),
+            RouteStaging::space(route_staging.total_len)
+        );
+    }
+
+    #[test]
+    fn route_must_match_its_template() {
+        let pool = Pubkey::new_unique();
+        let template = RouteTemplate {
+            config: Pubkey::new_unique(),
+            input_mint: Pubkey::new_unique(),
+            output_mint: Pubkey::new_unique(),
+            discriminator: [229, 23, 203, 151, 122, 227, 173, 42],
+            accounts: vec![
+                TemplateAccount { pubkey: Pubkey::default(), is_writable: true },
+                TemplateAccount { pubkey: pool, is_writable: true },
+            ],
+            bump: 255,
+        };
+        let data = [template.discriminator.as_slice(), &[1, 2, 3]].concat();
+        let source = Pubkey::new_unique();
+
+        assert!(template.validate(&data, [(source, true), (pool, true)].into_iter()).is_ok());
+        // Another pool, a read-only pool, a missing account or another instruction is rejected
+        for (data, accounts) in [
+            (data.clone(), vec![(source, true), (Pubkey::new_unique(), true)]),
+            (data.clone(), vec![(source, true), (pool, false)]),
+            (data.clone(), vec![(source, true)]),
+            ([0u8; 11].to_vec(), vec![(source, true), (pool, true)]),
+        ] {
+            assert_eq!(
+                template.validate(&data, accounts.into_iter()).unwrap_err(),
+                SuperSwapError::RouteTemplateMismatch.into()
+            );
+        }
+    }
+
+    #[test]
+    fn admin_log_overwrites_oldest_entries_once_full() {
+        let actor = Pubkey::new_unique();
+        let mut admin_log = AdminLog { config: Pubkey::new_unique(), count: 0, entries: Vec::new(), bump: 255 };
+        let total = AdminLog::CAPACITY as i64 + 5;
+        for timestamp in 0..total {
+            admin_log.record(AdminLogEntry::new(actor, AdminAction::Pause, timestamp, Pubkey::default()));
+        }
+
+        assert_eq!(admin_log.count, total as u64);
+        assert_eq!(admin_log.entries.len(), AdminLog::CAPACITY);
+        let timestamps: Vec<i64> = admin_log.chronological().map(|entry| entry.timestamp).collect();
+        assert_eq!(timestamps, (5..total).collect::<Vec<_>>());
+
+        // A full log still fits the space allocated for it
+        assert!(8 + admin_log.try_to_vec().unwrap().len() <= AdminLog::LEN);
+    }
+
+    #[test]
+    fn v1_config_migrates_with_settings_preserved() {
+        use std::str::FromStr;
+        let mut data = MAINNET_CONFIG_V1.to_vec();
+        let v1 = Config::v1_from_account_data(&data).unwrap();
+        assert_eq!(
+            v1.usdc_mint,
+            Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap()
+        );
+        assert_eq!((v1.fee_bps, v1.is_paused, v1.bump), (30, false, 254));
+        // The layout accounts for every byte the account holds
+        assert_eq!(v1.try_to_vec().unwrap(), data[8..]);
+
+        let config_key = Pubkey::new_unique();
+        let config = Config::from_v1(v1.clone(), config_key);
+        assert_eq!(config.admin, v1.admin);
+        assert_eq!(config.across_handler, v1.across_handler);
+        assert_eq!(config.jupiter_program, v1.jupiter_program);
+        assert_eq!(config.usdc_mint, v1.usdc_mint);
+        assert_eq!(config.fee_bps, 30);
+        assert!(!config.is_paused);
+        assert_eq!(config.bump, 254);
+
+        // Settings V1 lacked start at their defaults, so outbound and the integrations wait on the admin
+        assert_eq!(config.across_spoke_pool, Pubkey::default());
+        assert_eq!((config.outbound_fee_bps, config.max_outbound_amount), (0, 0));
+        assert!(!config.is_dust_order(1));
+        assert_eq!(config.fallback_output_mint, Pubkey::default());
+        assert_eq!(config.version, Config::VERSION);
+        assert_eq!(config.guardian, Pubkey::default());
+        assert!(!config.exceeds_max_order(u64::MAX));
+        assert!(config.is_adapter_enabled(Config::ADAPTER_ALL));
+        assert_eq!(config.vault_authority, config_key);
+        assert_eq!(config.surplus_policy, SurplusPolicy::Recipient);
+
+        // The migrated account reads back as a current config
+        let mut migrated = Vec::new();
+        config.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), Config::LEN);
+        let read = Config::try_deserialize(&mut migrated.as_slice()).unwrap();
+        assert_eq!(read.fee_recipient, v1.fee_recipient);
+        assert_eq!(read.vault_authority, config_key);
+
+        // Already migrated or foreign accounts are rejected
+        assert!(Config::v1_from_account_data(&migrated).is_err());
+        data[0] ^= 1;
+        assert!(Config::v1_from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn v2_config_migrates_with_settings_preserved() {
+        let mut config = mainnet_config();
+        config.version = 2;
+        config.guardian = Pubkey::new_unique();
+        config.max_order_usdc = 1_000_000_000_000;
+        config.is_paused = true;
+        let mut current = Vec::new();
+        config.try_serialize(&mut current).unwrap();
+
+        // Version 2 ended at `vault_authority`, followed by the pause flag and bump
+        assert_eq!(ConfigV2::LEN, 617);
+        let mut data = current[..ConfigV2::LEN - 2].to_vec();
+        data.extend_from_slice(&current[Config::LEN - 2..]);
+
+        let migrated = Config::from_v2(Config::v2_from_account_data(&data).unwrap());
+        assert_eq!(migrated.version, Config::VERSION);
+        assert_eq!((migrated.admin, migrated.guardian), (config.admin, config.guardian));
+        assert_eq!((migrated.fee_bps, migrated.outbound_fee_bps), (30, 25));
+        assert_eq!(migrated.max_order_usdc, config.max_order_usdc);
+        assert_eq!(migrated.vault_authority, config.vault_authority);
+        assert_eq!(migrated.operator_fill_cost_usdc, config.operator_fill_cost_usdc);
+        assert!(migrated.is_paused && migrated.cctp_paused);
+        assert_eq!(migrated.bump, 254);
+
+        // Settings version 3 added start out inert
+        assert_eq!(migrated.instance_id, 0);
+        assert!(!migrated.refunds_only);
+        assert_eq!((migrated.max_relayer_tip_usdc, migrated.surplus_share_bps), (0, 0));
+        assert_eq!(migrated.pending_admin, Pubkey::default());
+        assert_eq!(migrated.config_update_delay_seconds, 0);
+        assert!(migrated.additional_across_handlers.iter().all(|handler| *handler == Pubkey::default()));
+        assert_eq!(migrated.surplus_policy, SurplusPolicy::Recipient);
+        assert!(!migrated.captures_surplus());
+
+        // Neither a V1 nor an already migrated account reads as version 2
+        assert!(Config::v2_from_account_data(&MAINNET_CONFIG_V1).is_err());
+        let mut reserialized = Vec::new();
+        migrated.try_serialize(&mut reserialized).unwrap();
+        assert!(Config::v2_from_account_data(&reserialized).is_err());
+    }
+
+    #[test]
+    fn payer_ledger_settles_what_fills_accrued() {
+        let mut ledger = PayerLedger {
+            operator: Pubkey::new_unique(),
+            owed_usdc: 0,
+            fills_paid: 0,
+            total_settled_usdc: 0,
+            bump: 255,
+        };
+        ledger.record_fill(1_500).unwrap();
+        ledger.record_fill(1_500).unwrap();
+        assert_eq!((ledger.owed_usdc, ledger.fills_paid), (3_000, 2));
+
+        assert_eq!(ledger.settle().unwrap(), 3_000);
+        assert_eq!((ledger.owed_usdc, ledger.total_settled_usdc), (0, 3_000));
+        assert_eq!(ledger.settle().unwrap(), 0);
+    }
+
+    #[test]
+    fn open_orders_respect_cap() {
+        let mut orders = RecipientOrders {
+            recipient: Pubkey::new_unique(),
+            open_orders: 0,
+            bump: 255,
+        };
+        orders.open_order(2).unwrap();
+        orders.open_order(2).unwrap();
+        assert!(orders.open_order(2).is_err());
+        assert_eq!(orders.open_orders, 2);
+
+        orders.release_order();
+        orders.open_order(2).unwrap();
+        orders.open_order(0).unwrap();
+        assert_eq!(orders.open_orders, 3);
+
+        orders.open_orders = 0;
+        orders.release_order();
+        assert_eq!(orders.open_orders, 0);
+    }
+
+    #[test]
+    fn limit_order_without_expiry_never_expires() {
+        let mut limit_order = LimitOrder {
+            swap_order: Pubkey::default(),
+            jupiter_order: Pubkey::default(),
+            making_amount: 1_000,
+            taking_amount: 10,
+            expired_at: 0,
+            bump: 255,
+        };
+        assert!(!limit_order.is_expired(i64::MAX));
+
+        limit_order.expired_at = 100;
+        assert!(!limit_order.is_expired(99));
+        assert!(limit_order.is_expired(100));
+    }
+
+    #[test]
+    fn rate_limit_enforces_cap_within_period() {
+        let mut limit = rate_limit(1_000);
+
+        limit.record(600, 10).unwrap();
+        limit.record(400, 20).unwrap();
+        assert!(limit.record(1, 30).is_err());
+        assert_eq!(limit.period_volume, 1_000);
+    }
+
+    #[test]
+    fn rate_limit_resets_after_period() {
+        let mut limit = rate_limit(1_000);
+
+        limit.record(1_000, 10).unwrap();
+        limit.record(1_000, 100).unwrap();
+        assert_eq!(limit.period_start, 100);
+        assert_eq!(limit.period_volume, 1_000);
+    }
+
+    #[test]
+    fn zero_cap_tracks_volume_without_limit() {
+        let mut limit = rate_limit(0);
+
+        limit.record(u64::MAX / 2, 10).unwrap();
+        assert_eq!(limit.period_volume, u64::MAX / 2);
+    }
+
+    fn recovery_guard(timelock_threshold: u64, period_cap: u64) -> RecoveryGuard {
+        let limits = RecoveryLimits {
+            timelock_threshold,
+            delay_seconds: 50,
+            period_seconds: 100,
+            period_cap,
+        };
+        RecoveryGuard {
+            limits,
+            pending_limits: limits,
+            pending_effective_at: 0,
+            period_start: 0,
+            period_volume: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn recovery_guard_timelocks_and_caps() {
+        let mut guard = recovery_guard(500, 1_000);
+
+        assert!(!guard.requires_timelock(500));
+        assert!(guard.requires_timelock(501));
+        guard.record(500, 10).unwrap();
+        guard.record(500, 20).unwrap();
+        assert!(guard.record(1, 30).is_err());
+        guard.record(1, 100).unwrap();
+        assert!(!recovery_guard(0, 0).requires_timelock(u64::MAX));
+    }
+
+    #[test]
+    fn looser_recovery_limits_wait_out_the_delay() {
+        let mut guard = recovery_guard(500, 1_000);
+        let looser = RecoveryLimits {
+            period_cap: 0,
+            ..guard.limits
+        };
+        let tighter = RecoveryLimits {
+            timelock_threshold: 100,
+            ..guard.limits
+        };
+
+        assert_eq!(guard.set_limits(looser, 10).unwrap(), Some(60));
+        assert_eq!(guard.limits.period_cap, 1_000);
+        guard.refresh(60);
+        assert_eq!(guard.limits, looser);
+
+        // Tightening applies at once and drops a pending loosening
+        guard.set_limits(RecoveryLimits { period_cap: 1_000, ..looser }, 70).unwrap();
+        assert_eq!(guard.set_limits(looser, 80).unwrap(), Some(130));
+        assert_eq!(guard.set_limits(tighter, 90).unwrap(), None);
+        guard.refresh(200);
+        assert_eq!(guard.limits, tighter);
+    }
+
+    #[test]
+    fn order_archive_roots_prove_every_leaf() {
+        let mut archive = OrderArchive {
+            root: [0; 32],
+            next_index: 0,
+            filled_subtrees: [[0; 32]; ORDER_ARCHIVE_DEPTH],
+            bump: 255,
+        };
+        let leaves: Vec<[u8; 32]> = (0..5u8)
+            .map(|i| OrderArchive::leaf(&Pubkey::new_unique(), &[i]))
+            .collect();
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(archive.append(*leaf).unwrap(), i as u64);
+        }
+
+        // Rebuild the tree level by level to collect each leaf's siblings
+        for (index, leaf) in leaves.iter().enumerate() {
+            let mut level = leaves.clone();
+            let mut zero = [0u8; 32];
+            let mut proof = Vec::new();
+            let mut position = index;
+            for _ in 0..ORDER_ARCHIVE_DEPTH {
+                proof.push(*level.get(position ^ 1).unwrap_or(&zero));
+                level = level
+                    .chunks(2)
+                    .map(|pair| hashv(&[&pair[0], pair.get(1).unwrap_or(&zero)]).to_bytes())
+                    .collect();
+                zero = hashv(&[&zero, &zero]).to_bytes();
+                position /= 2;
+            }
+            assert!(OrderArchive::verify(&archive.root, *leaf, index as u64, &proof));
+            assert!(!OrderArchive::verify(&archive.root, *leaf, index as u64 ^ 1, &proof));
+        }
+    }
+}
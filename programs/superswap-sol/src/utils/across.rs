@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+
+/// Anchor discriminator of the Across spoke pool `deposit` instruction
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
+/// Arguments of the Across spoke pool `deposit` instruction
+#[derive(AnchorSerialize, Clone)]
+pub struct AcrossDepositArgs {
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub input_token: Pubkey,
+    pub output_token: Pubkey,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub destination_chain_id: u64,
+    pub exclusive_relayer: Pubkey,
+    pub quote_timestamp: u32,
+    pub fill_deadline: u32,
+    pub exclusivity_parameter: u32,
+    pub message: Vec<u8>,
+}
+
+/// Deposits tokens into the Across spoke pool via CPI
+///
+/// # Arguments
+/// * `spoke_pool` - Across spoke pool program account
+/// * `accounts` - Accounts required by the deposit, in spoke pool order
+/// * `args` - Deposit arguments
+/// * `signer_seeds` - Seeds for the depositor PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn deposit(
+    spoke_pool: &AccountInfo,
+    accounts: &[AccountInfo],
+    args: &AcrossDepositArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    msg!("Depositing {} into Across spoke pool", args.input_amount);
+    msg!("Destination chain: {}", args.destination_chain_id);
+
+    let mut data = DEPOSIT_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    // The depositor PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key() == args.depositor,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let deposit_instruction = Instruction {
+        program_id: spoke_pool.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    invoke_signed(&deposit_instruction, accounts, signer_seeds)?;
+
+    msg!("Across deposit submitted");
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+
+/// Anchor discriminator of the `on_superswap_delivery` callback recipient programs implement
+pub const DELIVERY_CALLBACK_DISCRIMINATOR: [u8; 8] = [152, 235, 225, 213, 66, 40, 86, 46];
+
+/// Order details passed to a recipient program's delivery callback
+#[derive(AnchorSerialize, Clone)]
+pub struct DeliveryCallbackArgs {
+    pub order_id: u64,
+    pub usdc_amount: u64,
+    pub destination_mint: Pubkey,
+    pub amount_delivered: u64,
+}
+
+/// Notifies a program-owned recipient that an order's output was delivered
+///
+/// The callback receives, in order: the callback authority PDA (signer, proving
+/// the call comes from SuperSwap), the recipient, the account the output was
+/// delivered to and the swap order.
+///
+/// # Arguments
+/// * `recipient_program` - Program owning the recipient account
+/// * `callback_authority` - SuperSwap PDA signing the callback, holds no funds
+/// * `recipient` - Program-owned recipient of the order
+/// * `destination` - Account the output was delivered to
+/// * `swap_order` - The delivered swap order
+/// * `args` - Order details
+/// * `signer_seeds` - Seeds for the callback authority
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn notify_delivery<'info>(
+    recipient_program: &AccountInfo<'info>,
+    callback_authority: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    swap_order: &AccountInfo<'info>,
+    args: &DeliveryCallbackArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    msg!("Notifying recipient program {}", recipient_program.key());
+
+    let mut data = DELIVERY_CALLBACK_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    let callback_instruction = Instruction {
+        program_id: recipient_program.key(),
+        accounts: vec![
+            AccountMeta::new_readonly(callback_authority.key(), true),
+            AccountMeta::new(recipient.key(), false),
+            AccountMeta::new(destination.key(), false),
+            AccountMeta::new_readonly(swap_order.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &callback_instruction,
+        &[
+            callback_authority.clone(),
+            recipient.clone(),
+            destination.clone(),
+            swap_order.clone(),
+            recipient_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    fn discriminator_matches_anchor_sighash() {
+        let sighash = hash(b"global:on_superswap_delivery");
+        assert_eq!(DELIVERY_CALLBACK_DISCRIMINATOR, sighash.to_bytes()[..8]);
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+
+/// Anchor discriminator of the CCTP TokenMessengerMinter `deposit_for_burn` instruction
+pub const DEPOSIT_FOR_BURN_DISCRIMINATOR: [u8; 8] = [215, 60, 61, 46, 114, 55, 128, 176];
+
+/// Arguments of the CCTP `deposit_for_burn` instruction
+#[derive(AnchorSerialize, Clone)]
+pub struct DepositForBurnArgs {
+    pub amount: u64,
+    pub destination_domain: u32,
+    pub mint_recipient: Pubkey,
+}
+
+/// Maps an EVM chain id to its CCTP domain
+///
+/// # Arguments
+/// * `chain_id` - EVM chain id
+///
+/// # Returns
+/// * `Option<u32>` - CCTP domain, or None if CCTP does not serve the chain
+pub fn cctp_domain_for_chain(chain_id: u64) -> Option<u32> {
+    match chain_id {
+        1 => Some(0),     // Ethereum
+        43114 => Some(1), // Avalanche
+        10 => Some(2),    // Optimism
+        42161 => Some(3), // Arbitrum
+        8453 => Some(6),  // Base
+        137 => Some(7),   // Polygon PoS
+        _ => None,
+    }
+}
+
+/// Burns USDC through CCTP's TokenMessengerMinter via CPI
+///
+/// # Arguments
+/// * `token_messenger_minter` - CCTP TokenMessengerMinter program account
+/// * `accounts` - Accounts required by deposit_for_burn, in program order
+/// * `args` - Burn arguments
+/// * `owner` - PDA owning the burned token account, signing via seeds
+/// * `signer_seeds` - Seeds for the owner PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn deposit_for_burn(
+    token_messenger_minter: &AccountInfo,
+    accounts: &[AccountInfo],
+    args: &DepositForBurnArgs,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    msg!("Burning {} USDC via CCTP", args.amount);
+    msg!("Destination domain: {}", args.destination_domain);
+
+    let mut data = DEPOSIT_FOR_BURN_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    // The owner PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == owner,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let burn_instruction = Instruction {
+        program_id: token_messenger_minter.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    invoke_signed(&burn_instruction, accounts, signer_seeds)?;
+
+    msg!("CCTP burn submitted");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cctp_domain_for_chain() {
+        assert_eq!(cctp_domain_for_chain(1), Some(0));
+        assert_eq!(cctp_domain_for_chain(8453), Some(6));
+        assert_eq!(cctp_domain_for_chain(56), None);
+    }
+}
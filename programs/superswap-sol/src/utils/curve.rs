@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+/// ed25519 curve id of the `sol_curve_validate_point` syscall
+#[cfg(target_os = "solana")]
+const CURVE25519_EDWARDS: u64 = 0;
+
+/// Whether `key` is a point on the ed25519 curve, i.e. could have a private key
+///
+/// Program-derived addresses are off-curve by construction; wallets are on it.
+/// On-chain this goes through the curve25519 syscall, since `Pubkey::is_on_curve`
+/// is only implemented off-chain.
+pub fn is_on_curve(key: &Pubkey) -> bool {
+    #[cfg(target_os = "solana")]
+    {
+        let mut result = 0u8;
+        // SAFETY: both pointers reference live locals for the duration of the call
+        let status = unsafe {
+            anchor_lang::solana_program::syscalls::sol_curve_validate_point(
+                CURVE25519_EDWARDS,
+                key.as_ref().as_ptr(),
+                &mut result,
+            )
+        };
+        status == 0
+    }
+    #[cfg(not(target_os = "solana"))]
+    {
+        key.is_on_curve()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn wallets_are_on_curve_and_pdas_are_not() {
+        assert!(is_on_curve(&Keypair::new().pubkey()));
+
+        let (escrow, _) = Pubkey::find_program_address(&[b"escrow"], &crate::ID);
+        assert!(!is_on_curve(&escrow));
+    }
+}
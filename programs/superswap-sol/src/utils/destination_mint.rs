@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::error::SuperSwapError;
+use crate::state::{DestinationMint, MintConfig};
+
+/// Fails when an operator has disabled the destination mint behind `destination_mint_entry`
+///
+/// Mints without an entry are enabled.
+pub fn require_mint_enabled(destination_mint_entry: &AccountInfo) -> Result<()> {
+    require!(
+        !is_mint_disabled(destination_mint_entry)?,
+        SuperSwapError::DestinationMintDisabled
+    );
+    Ok(())
+}
+
+/// Whether an operator has disabled the destination mint behind `destination_mint_entry`
+pub fn is_mint_disabled(destination_mint_entry: &AccountInfo) -> Result<bool> {
+    if destination_mint_entry.owner != &crate::ID || destination_mint_entry.data_is_empty() {
+        return Ok(false);
+    }
+    let data = destination_mint_entry.try_borrow_data()?;
+    let entry = DestinationMint::try_deserialize(&mut &data[..])?;
+    Ok(entry.disabled)
+}
+
+/// Reads the admin overrides behind `mint_config_entry`, None while the mint has none
+pub fn load_mint_config(mint_config_entry: &AccountInfo) -> Result<Option<MintConfig>> {
+    if mint_config_entry.owner != &crate::ID || mint_config_entry.data_is_empty() {
+        return Ok(None);
+    }
+    let data = mint_config_entry.try_borrow_data()?;
+    Ok(Some(MintConfig::try_deserialize(&mut &data[..])?))
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::secp256k1_recover::secp256k1_recover;
+use crate::error::SuperSwapError;
+
+/// Left-pads a 20-byte EVM address to the 32-byte form bridges use on Solana
+///
+/// # Arguments
+/// * `address` - EVM address
+///
+/// # Returns
+/// * `Pubkey` - Address with 12 leading zero bytes
+pub fn evm_address_to_bytes32(address: &[u8; 20]) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes[12..].copy_from_slice(address);
+    Pubkey::new_from_array(bytes)
+}
+
+/// Returns true if the EVM address is the zero address
+pub fn is_zero_evm_address(address: &[u8; 20]) -> bool {
+    address.iter().all(|byte| *byte == 0)
+}
+
+/// Message an EVM key signs to release its claim PDA's funds to `destination`
+pub fn claim_message(program_id: &Pubkey, destination: &Pubkey) -> String {
+    format!("SuperSwap claim on {} to {}", program_id, destination)
+}
+
+/// EIP-191 `personal_sign` hash of `message`, what EVM wallets actually sign
+pub fn personal_sign_hash(message: &[u8]) -> [u8; 32] {
+    keccak::hashv(&[
+        b"\x19Ethereum Signed Message:\n",
+        message.len().to_string().as_bytes(),
+        message,
+    ])
+    .to_bytes()
+}
+
+/// Recovers the EVM address whose key produced `signature` over `hash`
+///
+/// # Arguments
+/// * `hash` - Signed 32-byte digest
+/// * `signature` - Compact `r || s` signature
+/// * `recovery_id` - 0 or 1, or Ethereum's 27 or 28
+///
+/// # Returns
+/// * `[u8; 20]` - Last 20 bytes of the keccak hash of the recovered public key
+pub fn recover_evm_address(hash: &[u8; 32], signature: &[u8; 64], recovery_id: u8) -> Result<[u8; 20]> {
+    let recovery_id = if recovery_id >= 27 { recovery_id - 27 } else { recovery_id };
+    let public_key = secp256k1_recover(hash, recovery_id, signature)
+        .map_err(|_| error!(SuperSwapError::InvalidEvmSignature))?;
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&keccak::hash(&public_key.to_bytes()).to_bytes()[12..]);
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evm_address_is_left_padded() {
+        let address = [0xabu8; 20];
+        let padded = evm_address_to_bytes32(&address).to_bytes();
+
+        assert_eq!(padded[..12], [0u8; 12]);
+        assert_eq!(padded[12..], address);
+    }
+
+    #[test]
+    fn test_zero_evm_address() {
+        assert!(is_zero_evm_address(&[0u8; 20]));
+
+        let mut address = [0u8; 20];
+        address[19] = 1;
+        assert!(!is_zero_evm_address(&address));
+    }
+
+    #[test]
+    fn test_claim_signature_recovers_signer() {
+        let secret_key = libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+        let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key).serialize();
+        let mut expected = [0u8; 20];
+        expected.copy_from_slice(&keccak::hash(&public_key[1..]).to_bytes()[12..]);
+
+        let message = claim_message(&crate::ID, &Pubkey::new_unique());
+        let hash = personal_sign_hash(message.as_bytes());
+        let (signature, recovery_id) =
+            libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), &secret_key);
+        let signature = signature.serialize();
+
+        assert_eq!(recover_evm_address(&hash, &signature, recovery_id.serialize() + 27).unwrap(), expected);
+
+        let other = personal_sign_hash(claim_message(&crate::ID, &Pubkey::new_unique()).as_bytes());
+        assert_ne!(recover_evm_address(&other, &signature, recovery_id.serialize()).unwrap(), expected);
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke,
+};
+use crate::state::{HookProgram, PostSwapHook};
+use crate::error::SuperSwapError;
+
+/// Executes an allowlisted post-swap hook via CPI
+///
+/// Hooks run without any program signature and with every signer flag
+/// cleared, so a hook can never act as the config PDA or spend the payer's
+/// lamports.
+///
+/// # Arguments
+/// * `hook` - Hook program, accounts and data from the order params
+/// * `entry` - Allowlist entry for the hook program
+/// * `hook_program` - Hook program account
+/// * `accounts` - Accounts passed to the hook
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn execute_post_swap_hook<'info>(
+    hook: &PostSwapHook,
+    entry: &HookProgram,
+    hook_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        entry.program_id == hook.program_id && hook_program.key() == hook.program_id,
+        SuperSwapError::HookProgramNotAllowed
+    );
+
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: false,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let hook_instruction = Instruction {
+        program_id: hook.program_id,
+        accounts: account_metas,
+        data: hook.data.clone(),
+    };
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(hook_program.clone());
+    invoke(&hook_instruction, &account_infos)?;
+
+    msg!("Post-swap hook executed: {}", hook.program_id);
+
+    Ok(())
+}
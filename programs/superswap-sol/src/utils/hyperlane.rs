@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+
+/// Instruction prefix shared by Hyperlane Sealevel program instructions
+pub const HYPERLANE_INSTRUCTION_DISCRIMINATOR: [u8; 8] = [1, 1, 1, 1, 1, 1, 1, 1];
+
+/// Index of `TransferRemote` in the warp route instruction enum
+pub const TRANSFER_REMOTE_VARIANT: u8 = 1;
+
+/// Arguments of the Hyperlane warp route `TransferRemote` instruction
+#[derive(AnchorSerialize, Clone)]
+pub struct TransferRemoteArgs {
+    pub destination_domain: u32,
+    pub recipient: [u8; 32],
+    /// U256 amount, little endian
+    pub amount_or_id: [u8; 32],
+}
+
+impl TransferRemoteArgs {
+    /// Builds transfer arguments for a u64 token amount
+    pub fn new(destination_domain: u32, recipient: &Pubkey, amount: u64) -> Self {
+        let mut amount_or_id = [0u8; 32];
+        amount_or_id[..8].copy_from_slice(&amount.to_le_bytes());
+
+        Self {
+            destination_domain,
+            recipient: recipient.to_bytes(),
+            amount_or_id,
+        }
+    }
+}
+
+/// Sends tokens to another SVM chain through a Hyperlane warp route via CPI
+///
+/// # Arguments
+/// * `warp_route` - Hyperlane warp route program account
+/// * `accounts` - Accounts required by TransferRemote, in program order
+/// * `args` - Transfer arguments
+/// * `sender` - PDA owning the sent tokens, signing via seeds
+/// * `signer_seeds` - Seeds for the sender PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn transfer_remote(
+    warp_route: &AccountInfo,
+    accounts: &[AccountInfo],
+    args: &TransferRemoteArgs,
+    sender: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    msg!("Forwarding tokens to domain {}", args.destination_domain);
+
+    let mut data = HYPERLANE_INSTRUCTION_DISCRIMINATOR.to_vec();
+    data.push(TRANSFER_REMOTE_VARIANT);
+    args.serialize(&mut data)?;
+
+    // The sender PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == sender,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let transfer_instruction = Instruction {
+        program_id: warp_route.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    invoke_signed(&transfer_instruction, accounts, signer_seeds)?;
+
+    msg!("Hyperlane transfer submitted");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amount_is_encoded_as_little_endian_u256() {
+        let args = TransferRemoteArgs::new(1408864445, &Pubkey::default(), 0x0102);
+
+        assert_eq!(args.amount_or_id[0], 0x02);
+        assert_eq!(args.amount_or_id[1], 0x01);
+        assert_eq!(args.amount_or_id[2..], [0u8; 30]);
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::error::SuperSwapError;
+use crate::state::Config;
+use crate::utils::refund::calculate_fee;
+use superswap_pda::instance_insurance_usdc_account;
+
+/// Splits a protocol fee into the insurance fund's share and the fee recipient's rest
+pub fn split_fee(fee_amount: u64, insurance_fee_share_bps: u16) -> Result<(u64, u64)> {
+    let insurance_amount = calculate_fee(fee_amount, insurance_fee_share_bps)?;
+    let recipient_amount = fee_amount
+        .checked_sub(insurance_amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+    Ok((insurance_amount, recipient_amount))
+}
+
+/// Fails unless `account` is the USDC account of the config's insurance fund
+///
+/// Checked only when a fee share is actually paid, so instructions keep working
+/// on deployments that have not set up the fund.
+pub fn require_insurance_account(account: &AccountInfo, config: &Config) -> Result<()> {
+    require_keys_eq!(
+        account.key(),
+        instance_insurance_usdc_account(&crate::ID, config.instance_id, &config.usdc_mint),
+        SuperSwapError::InvalidInsuranceAccount
+    );
+    Ok(())
+}
+
+/// Pays a protocol fee out of `from`, routing the insurance share to the fund
+/// and the rest to the fee recipient; returns the insurance share
+///
+/// `signer_seeds` are the config PDA's when it owns `from`, and empty when
+/// `authority` signs the transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_protocol_fee<'info>(
+    config: &Config,
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    fee_recipient_account: &AccountInfo<'info>,
+    insurance_usdc_account: &AccountInfo<'info>,
+    fee_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let (insurance_amount, recipient_amount) = split_fee(fee_amount, config.insurance_fee_share_bps)?;
+
+    let transfer = |to: &AccountInfo<'info>, amount: u64| {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    };
+
+    if recipient_amount > 0 {
+        transfer(fee_recipient_account, recipient_amount)?;
+    }
+    if insurance_amount > 0 {
+        require_insurance_account(insurance_usdc_account, config)?;
+        transfer(insurance_usdc_account, insurance_amount)?;
+        msg!("Insurance Share: {}", insurance_amount);
+    }
+
+    Ok(insurance_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insurance_share_comes_out_of_the_fee() {
+        assert_eq!(split_fee(1_000, 2_500).unwrap(), (250, 750));
+        assert_eq!(split_fee(1_000, 0).unwrap(), (0, 1_000));
+        assert_eq!(split_fee(999, 10_000).unwrap(), (999, 0));
+    }
+}
@@ -3,6 +3,7 @@ use anchor_lang::solana_program::{
     instruction::Instruction,
     program::invoke_signed,
 };
+use anchor_spl::token::TokenAccount;
 
 /// Executes a Jupiter swap via CPI
 /// 
@@ -10,7 +11,7 @@ use anchor_lang::solana_program::{
 /// * `jupiter_program` - Jupiter program account
 /// * `swap_data` - Serialized Jupiter instruction data
 /// * `accounts` - Accounts required for the swap
-/// * `signer_seeds` - Seeds for PDA signing
+/// * `signer_seeds` - Seeds for PDA signing; the derived PDAs are marked as signers
 ///
 /// # Returns
 /// * `Result<()>` - Success or error
@@ -25,19 +26,26 @@ pub fn execute_jupiter_swap(
     msg!("Number of accounts: {}", accounts.len());
     msg!("Swap data length: {}", swap_data.len());
 
+    // PDAs signing through invoke_signed must be marked as signers
+    let pda_signers = signer_seeds
+        .iter()
+        .map(|seeds| Pubkey::create_program_address(seeds, &crate::ID))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| crate::error::SuperSwapError::InvalidInstructionData)?;
+
     // Build account metas for the instruction
     let account_metas: Vec<AccountMeta> = accounts
         .iter()
         .map(|account| AccountMeta {
             pubkey: account.key(),
-            is_signer: account.is_signer,
+            is_signer: account.is_signer || pda_signers.contains(account.key),
             is_writable: account.is_writable,
         })
         .collect();
 
     // Create the Jupiter instruction
     let jupiter_instruction = Instruction {
-        program_id: *jupiter_program.key(),
+        program_id: *jupiter_program.key,
         accounts: account_metas,
         data: swap_data.to_vec(),
     };
@@ -54,6 +62,32 @@ pub fn execute_jupiter_swap(
     Ok(())
 }
 
+/// Executes a Jupiter swap via CPI and returns the amount credited to `destination`
+///
+/// The output is measured from the destination balance rather than trusted
+/// from the route, so any route shape is accounted correctly.
+pub fn execute_jupiter_swap_measured<'info>(
+    jupiter_program: &AccountInfo<'info>,
+    swap_data: &[u8],
+    accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+    destination: &mut Account<'info, TokenAccount>,
+) -> Result<u64> {
+    let balance_before = destination.amount;
+
+    execute_jupiter_swap(jupiter_program, swap_data, accounts, signer_seeds)?;
+
+    destination.reload()?;
+    let output = destination
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(crate::error::SuperSwapError::MathOverflow)?;
+
+    msg!("Measured swap output: {}", output);
+
+    Ok(output)
+}
+
 /// Validates Jupiter swap output meets minimum requirements
 pub fn validate_swap_output(
     actual_output: u64,
@@ -69,27 +103,338 @@ pub fn validate_swap_output(
     Ok(())
 }
 
+/// Jupiter V6 `route` instruction discriminator
+pub const JUPITER_ROUTE_DISCRIMINATOR: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+
+/// Jupiter V6 `shared_accounts_route` instruction discriminator
+pub const JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+
+/// Jupiter V6 `exact_out_route` instruction discriminator
+pub const JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+
+/// Reads the quoted output a Jupiter `route` or `shared_accounts_route` was built with
+///
+/// Both instructions end with `in_amount: u64, quoted_out_amount: u64,
+/// slippage_bps: u16, platform_fee_bps: u8`, so the quote is read from the
+/// tail without decoding the variable-length route plan. Returns `None` for
+/// any other instruction.
+pub fn route_quoted_out_amount(data: &[u8]) -> Option<u64> {
+    // Discriminator, route plan length prefix and the fixed tail
+    const MIN_LEN: usize = 8 + 4 + 8 + 8 + 2 + 1;
+    if data.len() < MIN_LEN
+        || (data[..8] != JUPITER_ROUTE_DISCRIMINATOR
+            && data[..8] != JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR)
+    {
+        return None;
+    }
+    let quoted = &data[data.len() - 11..data.len() - 3];
+    Some(u64::from_le_bytes(quoted.try_into().ok()?))
+}
+
+/// Rejects output that falls more than `max_impact_bps` short of the route's quote
+///
+/// Catches sandwiches and stale quotes that a loosely set minimum output lets
+/// through. A `max_impact_bps` of 0 disables the check; with it enabled, a
+/// route whose quote cannot be read is rejected.
+pub fn validate_price_impact(
+    actual_output: u64,
+    quoted_output: Option<u64>,
+    max_impact_bps: u16,
+) -> Result<()> {
+    if max_impact_bps == 0 {
+        return Ok(());
+    }
+    let quoted_output = quoted_output.ok_or(crate::error::SuperSwapError::InvalidSwapCalldata)?;
+    let floor = (quoted_output as u128)
+        .checked_mul(10_000u128.saturating_sub(max_impact_bps as u128))
+        .ok_or(crate::error::SuperSwapError::MathOverflow)?
+        / 10_000;
+    require!(
+        actual_output as u128 >= floor,
+        crate::error::SuperSwapError::PriceImpactExceeded
+    );
+
+    msg!("Price impact validated: {} against quote {}", actual_output, quoted_output);
+
+    Ok(())
+}
+
+/// Output a route delivered above its quote; a route without a readable quote has none
+pub fn realized_surplus(actual_output: u64, quoted_output: Option<u64>) -> u64 {
+    quoted_output.map_or(0, |quoted_output| actual_output.saturating_sub(quoted_output))
+}
+
+/// Protocol's share of the output a route delivered above its quote
+///
+/// Positive slippage is split by `share_bps`, rounding in the recipient's
+/// favor; a route without a readable quote has no surplus to share.
+pub fn captured_surplus(actual_output: u64, quoted_output: Option<u64>, share_bps: u16) -> Result<u64> {
+    let Some(quoted_output) = quoted_output else {
+        return Ok(0);
+    };
+    let surplus = actual_output.saturating_sub(quoted_output);
+    let captured = (surplus as u128)
+        .checked_mul(share_bps as u128)
+        .ok_or(crate::error::SuperSwapError::MathOverflow)?
+        / 10_000;
+    Ok(captured as u64)
+}
+
 /// Parse Jupiter V6 swap instruction data
-/// 
-/// Jupiter V6 uses the following instruction format:
-/// - First 8 bytes: Instruction discriminator
-/// - Following bytes: Instruction parameters
 ///
-/// This function helps parse and validate the instruction data
+/// Only `route`, `shared_accounts_route` and `exact_out_route` are accepted.
+/// All three end with two amounts, `slippage_bps: u16` and
+/// `platform_fee_bps: u8` after the variable-length route plan, so the
+/// amounts are read from the tail:
+/// - exact-in routes carry `in_amount` and `quoted_out_amount`; the minimum
+///   output is the quote less the slippage, as Jupiter enforces it
+/// - `exact_out_route` carries `out_amount` and `quoted_in_amount`; the input
+///   is capped at the quote plus the slippage
 pub fn parse_jupiter_swap_data(data: &[u8]) -> Result<JupiterSwapParams> {
-    require!(data.len() >= 8, crate::error::SuperSwapError::InvalidSwapCalldata);
-    
-    // In production, you would deserialize the full instruction here
-    // For now, we return a placeholder
+    // Discriminator, route plan length prefix and the fixed tail
+    const MIN_LEN: usize = 8 + 4 + 8 + 8 + 2 + 1;
+    require!(data.len() >= MIN_LEN, crate::error::SuperSwapError::InvalidSwapCalldata);
+
+    let kind = match data[..8].try_into().unwrap() {
+        JUPITER_ROUTE_DISCRIMINATOR => JupiterRouteKind::Route,
+        JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR => JupiterRouteKind::SharedAccountsRoute,
+        JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR => JupiterRouteKind::ExactOutRoute,
+        _ => return err!(crate::error::SuperSwapError::InvalidSwapCalldata),
+    };
+
+    let tail = &data[data.len() - 19..];
+    let first = u64::from_le_bytes(tail[..8].try_into().unwrap());
+    let second = u64::from_le_bytes(tail[8..16].try_into().unwrap());
+    let slippage_bps = u16::from_le_bytes(tail[16..18].try_into().unwrap());
+    require!(slippage_bps <= 10_000, crate::error::SuperSwapError::InvalidSwapCalldata);
+
+    let (amount_in, minimum_amount_out) = if kind.is_exact_out() {
+        let max_in = (second as u128) * (10_000 + slippage_bps as u128) / 10_000;
+        (u64::try_from(max_in).map_err(|_| crate::error::SuperSwapError::MathOverflow)?, first)
+    } else {
+        let min_out = (second as u128) * (10_000 - slippage_bps as u128) / 10_000;
+        (first, min_out as u64)
+    };
+
     Ok(JupiterSwapParams {
-        amount_in: 0,
-        minimum_amount_out: 0,
+        kind,
+        amount_in,
+        minimum_amount_out,
     })
 }
 
+/// Jupiter V6 instructions a route may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JupiterRouteKind {
+    Route,
+    SharedAccountsRoute,
+    ExactOutRoute,
+}
+
+impl JupiterRouteKind {
+    /// Whether the route fixes the output rather than the input
+    pub fn is_exact_out(&self) -> bool {
+        *self == JupiterRouteKind::ExactOutRoute
+    }
+}
+
 #[derive(Debug)]
 pub struct JupiterSwapParams {
+    /// Instruction the route data encodes
+    pub kind: JupiterRouteKind,
+    /// Input the route spends; the most it may spend for an exact-out route
     pub amount_in: u64,
+    /// Output below which Jupiter reverts the route
     pub minimum_amount_out: u64,
 }
 
+/// Checks a route's own amounts against the order it fills
+///
+/// An exact-in route must spend exactly `swap_amount`, an exact-out route at
+/// most that, and either must enforce at least the order's `min_output_amount`.
+pub fn validate_route_params(
+    route: &JupiterSwapParams,
+    swap_amount: u64,
+    min_output_amount: u64,
+) -> Result<()> {
+    let amount_in_ok = if route.kind.is_exact_out() {
+        route.amount_in <= swap_amount
+    } else {
+        route.amount_in == swap_amount
+    };
+    require!(
+        amount_in_ok && route.minimum_amount_out >= min_output_amount,
+        crate::error::SuperSwapError::InvalidSwapCalldata
+    );
+    Ok(())
+}
+
+/// Source and destination token accounts of a route, read from its accounts
+///
+/// `route` and `exact_out_route` take the user's source account third and
+/// deliver to their optional `destination_token_account` (fifth), falling back
+/// to the user's destination account (fourth) when it is left unset, i.e. set
+/// to the Jupiter program itself. `shared_accounts_route` takes the source
+/// account fourth and always delivers to its seventh account.
+pub fn route_token_accounts(
+    route: &JupiterSwapParams,
+    jupiter_program: &Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<(Pubkey, Pubkey)> {
+    let key = |index: usize| {
+        accounts
+            .get(index)
+            .map(|account| account.key())
+            .ok_or(error!(crate::error::SuperSwapError::InvalidSwapCalldata))
+    };
+    match route.kind {
+        JupiterRouteKind::SharedAccountsRoute => Ok((key(3)?, key(6)?)),
+        JupiterRouteKind::Route | JupiterRouteKind::ExactOutRoute => {
+            let destination = key(4)?;
+            let destination = if destination == *jupiter_program { key(3)? } else { destination };
+            Ok((key(2)?, destination))
+        }
+    }
+}
+
+/// Rejects a route that does not spend from `source` and deliver to `destination`
+///
+/// Without this a relayer could route the output to an account of its own.
+pub fn require_route_token_accounts(
+    route: &JupiterSwapParams,
+    jupiter_program: &Pubkey,
+    accounts: &[AccountInfo],
+    source: &Pubkey,
+    destination: &Pubkey,
+) -> Result<()> {
+    let (route_source, route_destination) = route_token_accounts(route, jupiter_program, accounts)?;
+    require!(
+        route_source == *source && route_destination == *destination,
+        crate::error::SuperSwapError::RouteAccountMismatch
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_data_shorter_than_route_tail() {
+        assert!(parse_jupiter_swap_data(&[]).is_err());
+        assert!(parse_jupiter_swap_data(&[0u8; 7]).is_err());
+        assert!(parse_jupiter_swap_data(&JUPITER_ROUTE_DISCRIMINATOR).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_unlisted_discriminators() {
+        assert!(parse_jupiter_swap_data(&route_data([0; 8], 777)).is_err());
+        assert!(parse_jupiter_swap_data(&route_data(JUPITER_ROUTE_DISCRIMINATOR, 777)).is_ok());
+    }
+
+    #[test]
+    fn parse_reads_route_amounts() {
+        // 1_000 in, 1_000 quoted out at 50 bps slippage
+        let route = parse_jupiter_swap_data(&route_data(JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR, 1_000)).unwrap();
+        assert_eq!(route.kind, JupiterRouteKind::SharedAccountsRoute);
+        assert_eq!(route.amount_in, 1_000);
+        assert_eq!(route.minimum_amount_out, 995);
+
+        // 1_000 out, 1_000 quoted in at 50 bps slippage
+        let route = parse_jupiter_swap_data(&route_data(JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR, 1_000)).unwrap();
+        assert_eq!(route.kind, JupiterRouteKind::ExactOutRoute);
+        assert_eq!(route.amount_in, 1_005);
+        assert_eq!(route.minimum_amount_out, 1_000);
+    }
+
+    #[test]
+    fn route_params_must_match_the_order() {
+        let exact_in = JupiterSwapParams { kind: JupiterRouteKind::Route, amount_in: 1_000, minimum_amount_out: 500 };
+        assert!(validate_route_params(&exact_in, 1_000, 500).is_ok());
+        assert!(validate_route_params(&exact_in, 1_001, 500).is_err());
+        assert!(validate_route_params(&exact_in, 999, 500).is_err());
+        assert!(validate_route_params(&exact_in, 1_000, 501).is_err());
+
+        let exact_out = JupiterSwapParams { kind: JupiterRouteKind::ExactOutRoute, amount_in: 900, minimum_amount_out: 500 };
+        assert!(validate_route_params(&exact_out, 1_000, 500).is_ok());
+        assert!(validate_route_params(&exact_out, 899, 500).is_err());
+    }
+
+    #[test]
+    fn output_below_minimum_is_rejected() {
+        assert!(validate_swap_output(99, 100).is_err());
+        assert!(validate_swap_output(100, 100).is_ok());
+    }
+
+    fn route_data(discriminator: [u8; 8], quoted_out_amount: u64) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&1u32.to_le_bytes()); // one route plan step
+        data.extend_from_slice(&[7, 100, 0, 1]);
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&quoted_out_amount.to_le_bytes());
+        data.extend_from_slice(&50u16.to_le_bytes());
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn quoted_output_is_read_from_route_tail() {
+        assert_eq!(route_quoted_out_amount(&route_data(JUPITER_ROUTE_DISCRIMINATOR, 777)), Some(777));
+        assert_eq!(
+            route_quoted_out_amount(&route_data(JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR, 5)),
+            Some(5)
+        );
+        assert_eq!(route_quoted_out_amount(&route_data([0; 8], 777)), None);
+        assert_eq!(route_quoted_out_amount(&JUPITER_ROUTE_DISCRIMINATOR), None);
+    }
+
+    #[test]
+    fn route_token_accounts_follow_the_instruction_layout() {
+        let jupiter = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports: Vec<u64> = vec![0; keys.len()];
+        let mut data: Vec<Vec<u8>> = vec![Vec::new(); keys.len()];
+        let owner = Pubkey::default();
+        let mut infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| AccountInfo::new(key, false, false, lamports, data, &owner, false, 0))
+            .collect();
+        let params = |kind| JupiterSwapParams { kind, amount_in: 0, minimum_amount_out: 0 };
+
+        let shared = route_token_accounts(&params(JupiterRouteKind::SharedAccountsRoute), &jupiter, &infos).unwrap();
+        assert_eq!(shared, (keys[3], keys[6]));
+        let route = route_token_accounts(&params(JupiterRouteKind::Route), &jupiter, &infos).unwrap();
+        assert_eq!(route, (keys[2], keys[4]));
+
+        // An unset destination_token_account falls back to the user's destination account
+        infos[4].key = &jupiter;
+        let exact_out = route_token_accounts(&params(JupiterRouteKind::ExactOutRoute), &jupiter, &infos).unwrap();
+        assert_eq!(exact_out, (keys[2], keys[3]));
+
+        assert!(route_token_accounts(&params(JupiterRouteKind::SharedAccountsRoute), &jupiter, &infos[..6]).is_err());
+    }
+
+    #[test]
+    fn price_impact_beyond_threshold_is_rejected() {
+        assert!(validate_price_impact(989, Some(1_000), 100).is_err());
+        assert!(validate_price_impact(990, Some(1_000), 100).is_ok());
+        assert!(validate_price_impact(1_200, Some(1_000), 100).is_ok());
+        assert!(validate_price_impact(0, None, 0).is_ok());
+        assert!(validate_price_impact(1_000, None, 100).is_err());
+    }
+
+    #[test]
+    fn surplus_above_quote_is_split_by_share() {
+        assert_eq!(captured_surplus(1_100, Some(1_000), 5_000).unwrap(), 50);
+        assert_eq!(captured_surplus(1_003, Some(1_000), 5_000).unwrap(), 1);
+        assert_eq!(captured_surplus(1_100, Some(1_000), 0).unwrap(), 0);
+        assert_eq!(captured_surplus(900, Some(1_000), 10_000).unwrap(), 0);
+        assert_eq!(captured_surplus(1_100, None, 10_000).unwrap(), 0);
+
+        assert_eq!(realized_surplus(1_100, Some(1_000)), 100);
+        assert_eq!(realized_surplus(900, Some(1_000)), 0);
+        assert_eq!(realized_surplus(1_100, None), 0);
+    }
+}
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+    pubkey,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::LendingMarket;
+use crate::error::SuperSwapError;
+
+/// Kamino Lend program
+pub const KAMINO_LEND_PROGRAM_ID: Pubkey = pubkey!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+
+/// Solend program
+pub const SOLEND_PROGRAM_ID: Pubkey = pubkey!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+
+/// Anchor discriminator of the Kamino `deposit_reserve_liquidity` instruction
+pub const KAMINO_DEPOSIT_RESERVE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [169, 201, 30, 126, 6, 205, 102, 68];
+
+/// Index of `DepositReserveLiquidity` in the SPL token-lending instruction enum
+pub const SOLEND_DEPOSIT_RESERVE_LIQUIDITY: u8 = 4;
+
+/// Positions of the accounts the program validates, per market
+struct LendingAccountLayout {
+    lending_market: usize,
+    owner: usize,
+    source_liquidity: usize,
+    collateral_mint: usize,
+    destination_collateral: usize,
+}
+
+impl LendingMarket {
+    /// Lending program for this market
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            LendingMarket::Kamino => KAMINO_LEND_PROGRAM_ID,
+            LendingMarket::Solend => SOLEND_PROGRAM_ID,
+        }
+    }
+
+    fn layout(&self) -> LendingAccountLayout {
+        match self {
+            // owner, reserve, lending_market, lending_market_authority, reserve_liquidity_mint,
+            // reserve_liquidity_supply, reserve_collateral_mint, user_source_liquidity,
+            // user_destination_collateral, ...
+            LendingMarket::Kamino => LendingAccountLayout {
+                lending_market: 2,
+                owner: 0,
+                source_liquidity: 7,
+                collateral_mint: 6,
+                destination_collateral: 8,
+            },
+            // source_liquidity, destination_collateral, reserve, reserve_liquidity_supply,
+            // reserve_collateral_mint, lending_market, lending_market_authority,
+            // user_transfer_authority, ...
+            LendingMarket::Solend => LendingAccountLayout {
+                lending_market: 5,
+                owner: 7,
+                source_liquidity: 0,
+                collateral_mint: 4,
+                destination_collateral: 1,
+            },
+        }
+    }
+
+    /// Collateral mint and beneficiary collateral account within the deposit accounts
+    pub fn collateral_accounts<'a, 'info>(
+        &self,
+        accounts: &'a [AccountInfo<'info>],
+    ) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)> {
+        let layout = self.layout();
+        let mint = accounts
+            .get(layout.collateral_mint)
+            .ok_or(SuperSwapError::InvalidLendingMarket)?;
+        let destination = accounts
+            .get(layout.destination_collateral)
+            .ok_or(SuperSwapError::InvalidLendingMarket)?;
+        Ok((mint, destination))
+    }
+
+    fn instruction_data(&self, amount: u64) -> Vec<u8> {
+        let mut data = match self {
+            LendingMarket::Kamino => KAMINO_DEPOSIT_RESERVE_LIQUIDITY_DISCRIMINATOR.to_vec(),
+            LendingMarket::Solend => vec![SOLEND_DEPOSIT_RESERVE_LIQUIDITY],
+        };
+        data.extend_from_slice(&amount.to_le_bytes());
+        data
+    }
+}
+
+/// Deposits liquidity into a lending reserve via CPI, minting the collateral to the beneficiary
+///
+/// # Arguments
+/// * `market` - Lending market to deposit into
+/// * `lending_program` - Kamino or Solend program account
+/// * `expected_market` - Lending market configured for this kind
+/// * `accounts` - Accounts required by the deposit, in program order
+/// * `owner` - PDA owning the source liquidity, signing via seeds
+/// * `source_liquidity` - Token account holding the liquidity to deposit
+/// * `beneficiary` - Owner of the collateral account receiving the minted collateral
+/// * `amount` - Liquidity to deposit
+/// * `signer_seeds` - Seeds for the owner PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_reserve_liquidity(
+    market: LendingMarket,
+    lending_program: &AccountInfo,
+    expected_market: &Pubkey,
+    accounts: &[AccountInfo],
+    owner: &Pubkey,
+    source_liquidity: &Pubkey,
+    beneficiary: &Pubkey,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let layout = market.layout();
+    require!(
+        lending_program.key() == market.program_id()
+            && *expected_market != Pubkey::default()
+            && accounts.len() > layout.lending_market.max(layout.destination_collateral),
+        SuperSwapError::InvalidLendingMarket
+    );
+
+    // The configured market, our escrow and the beneficiary's collateral account must be in place
+    let collateral_mint = accounts[layout.collateral_mint].key();
+    require!(
+        accounts[layout.lending_market].key() == *expected_market
+            && accounts[layout.owner].key() == *owner
+            && accounts[layout.source_liquidity].key() == *source_liquidity
+            && accounts[layout.destination_collateral].key()
+                == get_associated_token_address(beneficiary, &collateral_mint),
+        SuperSwapError::InvalidLendingMarket
+    );
+
+    msg!("Depositing {} into lending reserve for {}", amount, beneficiary);
+
+    // The owner PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == owner,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let deposit_instruction = Instruction {
+        program_id: lending_program.key(),
+        accounts: account_metas,
+        data: market.instruction_data(amount),
+    };
+
+    invoke_signed(&deposit_instruction, accounts, signer_seeds)?;
+
+    msg!("Lending deposit submitted");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deposit_data_encodes_amount() {
+        let kamino = LendingMarket::Kamino.instruction_data(7);
+        assert_eq!(kamino[..8], KAMINO_DEPOSIT_RESERVE_LIQUIDITY_DISCRIMINATOR);
+        assert_eq!(kamino[8..], 7u64.to_le_bytes());
+
+        let solend = LendingMarket::Solend.instruction_data(7);
+        assert_eq!(solend, [SOLEND_DEPOSIT_RESERVE_LIQUIDITY, 7, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
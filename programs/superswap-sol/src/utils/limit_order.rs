@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+    pubkey,
+};
+use crate::error::SuperSwapError;
+
+/// Jupiter Limit Order program
+pub const JUPITER_LIMIT_ORDER_PROGRAM_ID: Pubkey = pubkey!("jupoNjAxXgZ4rjzxzPMP4oxduvQsQtZzyknqvzYNrNu");
+
+/// Anchor discriminator of the Jupiter Limit Order `initialize_order` instruction
+pub const INITIALIZE_ORDER_DISCRIMINATOR: [u8; 8] = [133, 110, 74, 175, 112, 159, 245, 159];
+
+/// Anchor discriminator of the Jupiter Limit Order `cancel_order` instruction
+pub const CANCEL_ORDER_DISCRIMINATOR: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+
+// initialize_order accounts: base, maker, order, reserve, maker_input_account,
+// input_mint, maker_output_account, referral, output_mint, ...
+const INITIALIZE_MAKER: usize = 1;
+const INITIALIZE_ORDER: usize = 2;
+const INITIALIZE_MAKER_INPUT: usize = 4;
+const INITIALIZE_MAKER_OUTPUT: usize = 6;
+
+// cancel_order accounts: order, maker, reserve, maker_input_account, input_mint, ...
+const CANCEL_ORDER: usize = 0;
+const CANCEL_MAKER: usize = 1;
+const CANCEL_MAKER_INPUT: usize = 3;
+
+/// Arguments of the Jupiter Limit Order `initialize_order` instruction
+#[derive(AnchorSerialize, Clone)]
+pub struct InitializeOrderArgs {
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub expired_at: Option<i64>,
+}
+
+/// Escrow-owned token accounts an order trades between
+pub struct LimitOrderAccounts {
+    /// Maker PDA signing via seeds
+    pub maker: Pubkey,
+    /// Maker's USDC account the order is funded from and refunded to
+    pub maker_input: Pubkey,
+    /// Maker's account that receives fills
+    pub maker_output: Pubkey,
+}
+
+/// Jupiter order account within the `initialize_order` accounts
+pub fn order_account<'a, 'info>(accounts: &'a [AccountInfo<'info>]) -> Result<&'a AccountInfo<'info>> {
+    accounts
+        .get(INITIALIZE_ORDER)
+        .ok_or_else(|| error!(SuperSwapError::InvalidInstructionData))
+}
+
+/// Places a Jupiter limit order via CPI
+///
+/// # Arguments
+/// * `limit_order_program` - Jupiter Limit Order program account
+/// * `accounts` - Accounts required by `initialize_order`, in program order
+/// * `order_accounts` - Maker and token accounts the order must use
+/// * `args` - Order amounts and expiry
+/// * `signer_seeds` - Seeds for the maker PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn initialize_order(
+    limit_order_program: &AccountInfo,
+    accounts: &[AccountInfo],
+    order_accounts: &LimitOrderAccounts,
+    args: &InitializeOrderArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        limit_order_program.key() == JUPITER_LIMIT_ORDER_PROGRAM_ID
+            && accounts.len() > INITIALIZE_MAKER_OUTPUT,
+        SuperSwapError::InvalidInstructionData
+    );
+    require!(
+        accounts[INITIALIZE_MAKER].key() == order_accounts.maker
+            && accounts[INITIALIZE_MAKER_INPUT].key() == order_accounts.maker_input
+            && accounts[INITIALIZE_MAKER_OUTPUT].key() == order_accounts.maker_output,
+        SuperSwapError::InvalidRecipient
+    );
+
+    msg!(
+        "Placing limit order: {} in for {} out",
+        args.making_amount,
+        args.taking_amount
+    );
+
+    let mut data = INITIALIZE_ORDER_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    invoke_limit_order(limit_order_program, accounts, &order_accounts.maker, data, signer_seeds)
+}
+
+/// Cancels a Jupiter limit order via CPI, returning the unfilled input to the maker
+///
+/// # Arguments
+/// * `limit_order_program` - Jupiter Limit Order program account
+/// * `accounts` - Accounts required by `cancel_order`, in program order
+/// * `order` - Jupiter order account recorded when the order was placed
+/// * `maker` - Maker PDA, signing via seeds
+/// * `maker_input` - Maker's account receiving the unfilled input
+/// * `signer_seeds` - Seeds for the maker PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn cancel_order(
+    limit_order_program: &AccountInfo,
+    accounts: &[AccountInfo],
+    order: &Pubkey,
+    maker: &Pubkey,
+    maker_input: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        limit_order_program.key() == JUPITER_LIMIT_ORDER_PROGRAM_ID
+            && accounts.len() > CANCEL_MAKER_INPUT,
+        SuperSwapError::InvalidInstructionData
+    );
+    require!(
+        accounts[CANCEL_ORDER].key() == *order
+            && accounts[CANCEL_MAKER].key() == *maker
+            && accounts[CANCEL_MAKER_INPUT].key() == *maker_input,
+        SuperSwapError::InvalidRecipient
+    );
+
+    msg!("Cancelling limit order {}", order);
+
+    invoke_limit_order(
+        limit_order_program,
+        accounts,
+        maker,
+        CANCEL_ORDER_DISCRIMINATOR.to_vec(),
+        signer_seeds,
+    )
+}
+
+fn invoke_limit_order(
+    limit_order_program: &AccountInfo,
+    accounts: &[AccountInfo],
+    maker: &Pubkey,
+    data: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // The maker PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == maker,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let instruction = Instruction {
+        program_id: limit_order_program.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    invoke_signed(&instruction, accounts, signer_seeds)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize_args_encode_optional_expiry() {
+        let mut data = Vec::new();
+        InitializeOrderArgs {
+            making_amount: 5,
+            taking_amount: 7,
+            expired_at: None,
+        }
+        .serialize(&mut data)
+        .unwrap();
+        assert_eq!(data.len(), 17);
+        assert_eq!(data[16], 0);
+
+        data.clear();
+        InitializeOrderArgs {
+            making_amount: 5,
+            taking_amount: 7,
+            expired_at: Some(1),
+        }
+        .serialize(&mut data)
+        .unwrap();
+        assert_eq!(data.len(), 25);
+        assert_eq!(data[16..], [1, 1, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+    pubkey,
+};
+use crate::state::LiquidityProtocol;
+use crate::error::SuperSwapError;
+
+/// Raydium CPMM program
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
+/// Orca Whirlpool program
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+/// Anchor discriminator of the Raydium CPMM `deposit` instruction
+pub const RAYDIUM_CPMM_DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
+/// Anchor discriminator of the Orca Whirlpool `increase_liquidity` instruction
+pub const WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+
+/// Positions of the accounts the program validates, per protocol
+struct LiquidityAccountLayout {
+    pool: usize,
+    owner: usize,
+    position: usize,
+    token_a: usize,
+    token_b: usize,
+}
+
+impl LiquidityProtocol {
+    /// Program that accepts liquidity deposits for this protocol
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            LiquidityProtocol::RaydiumCpmm => RAYDIUM_CPMM_PROGRAM_ID,
+            LiquidityProtocol::OrcaWhirlpool => ORCA_WHIRLPOOL_PROGRAM_ID,
+        }
+    }
+
+    fn layout(&self) -> LiquidityAccountLayout {
+        match self {
+            // owner, authority, pool_state, owner_lp_token, token_0_account, token_1_account, ...
+            LiquidityProtocol::RaydiumCpmm => LiquidityAccountLayout {
+                pool: 2,
+                owner: 0,
+                position: 3,
+                token_a: 4,
+                token_b: 5,
+            },
+            // whirlpool, token_program, position_authority, position, position_token_account,
+            // token_owner_account_a, token_owner_account_b, ...
+            LiquidityProtocol::OrcaWhirlpool => LiquidityAccountLayout {
+                pool: 0,
+                owner: 2,
+                position: 4,
+                token_a: 5,
+                token_b: 6,
+            },
+        }
+    }
+
+    fn instruction_data(&self, liquidity: u128, max_a: u64, max_b: u64) -> Result<Vec<u8>> {
+        let mut data = match self {
+            LiquidityProtocol::RaydiumCpmm => {
+                // CPMM mints LP tokens, so the liquidity amount is a u64
+                let lp_amount = u64::try_from(liquidity).map_err(|_| SuperSwapError::MathOverflow)?;
+                let mut data = RAYDIUM_CPMM_DEPOSIT_DISCRIMINATOR.to_vec();
+                data.extend_from_slice(&lp_amount.to_le_bytes());
+                data
+            }
+            LiquidityProtocol::OrcaWhirlpool => {
+                let mut data = WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR.to_vec();
+                data.extend_from_slice(&liquidity.to_le_bytes());
+                data
+            }
+        };
+        data.extend_from_slice(&max_a.to_le_bytes());
+        data.extend_from_slice(&max_b.to_le_bytes());
+        Ok(data)
+    }
+}
+
+/// One side of a liquidity deposit: a token account owned by the depositor and its balance
+pub struct LiquidityLeg {
+    pub account: Pubkey,
+    pub amount: u64,
+}
+
+/// Deposits two token legs into a Raydium CPMM pool or Orca Whirlpool position via CPI
+///
+/// The legs may be given in either order; they are matched to the pool's
+/// token A/B accounts and the balances are used as the maximum amounts.
+///
+/// # Arguments
+/// * `protocol` - Liquidity protocol
+/// * `liquidity_program` - Raydium CPMM or Orca Whirlpool program account
+/// * `pool` - Pool the order designated
+/// * `accounts` - Accounts required by the deposit, in program order
+/// * `owner` - PDA owning the legs and the LP tokens or position, signing via seeds
+/// * `position_account` - Owner's LP token account or position token account
+/// * `legs` - The two token legs to deposit
+/// * `liquidity` - LP tokens (Raydium) or liquidity (Whirlpool) to mint, computed off-chain
+/// * `signer_seeds` - Seeds for the owner PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity(
+    protocol: LiquidityProtocol,
+    liquidity_program: &AccountInfo,
+    pool: &Pubkey,
+    accounts: &[AccountInfo],
+    owner: &Pubkey,
+    position_account: &Pubkey,
+    legs: [&LiquidityLeg; 2],
+    liquidity: u128,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let layout = protocol.layout();
+    require!(
+        liquidity_program.key() == protocol.program_id()
+            && accounts.len() > layout.token_b.max(layout.token_a),
+        SuperSwapError::InvalidLiquidityPool
+    );
+    require!(
+        accounts[layout.pool].key() == *pool
+            && accounts[layout.owner].key() == *owner
+            && accounts[layout.position].key() == *position_account,
+        SuperSwapError::InvalidLiquidityPool
+    );
+
+    // Match the legs to the pool's token order
+    let token_a = accounts[layout.token_a].key();
+    let token_b = accounts[layout.token_b].key();
+    let (leg_a, leg_b) = if legs[0].account == token_a && legs[1].account == token_b {
+        (legs[0], legs[1])
+    } else if legs[1].account == token_a && legs[0].account == token_b {
+        (legs[1], legs[0])
+    } else {
+        return err!(SuperSwapError::InvalidLiquidityPool);
+    };
+
+    msg!("Adding liquidity: {} / {}", leg_a.amount, leg_b.amount);
+
+    // The owner PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == owner,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let deposit_instruction = Instruction {
+        program_id: liquidity_program.key(),
+        accounts: account_metas,
+        data: protocol.instruction_data(liquidity, leg_a.amount, leg_b.amount)?,
+    };
+
+    invoke_signed(&deposit_instruction, accounts, signer_seeds)?;
+
+    msg!("Liquidity deposit submitted");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raydium_data_rejects_oversized_lp_amount() {
+        let protocol = LiquidityProtocol::RaydiumCpmm;
+
+        let data = protocol.instruction_data(10, 1, 2).unwrap();
+        assert_eq!(data.len(), 8 + 8 * 3);
+        assert!(protocol.instruction_data(u128::from(u64::MAX) + 1, 1, 2).is_err());
+    }
+
+    #[test]
+    fn whirlpool_data_encodes_u128_liquidity() {
+        let data = LiquidityProtocol::OrcaWhirlpool.instruction_data(1 << 70, 1, 2).unwrap();
+
+        assert_eq!(data[..8], WHIRLPOOL_INCREASE_LIQUIDITY_DISCRIMINATOR);
+        assert_eq!(data[8..24], (1u128 << 70).to_le_bytes());
+        assert_eq!(data[24..32], 1u64.to_le_bytes());
+        assert_eq!(data[32..], 2u64.to_le_bytes());
+    }
+}
@@ -1,6 +1,56 @@
+#[cfg(feature = "across")]
+pub mod across;
+pub mod callback;
+pub mod curve;
+#[cfg(feature = "cctp")]
+pub mod cctp;
+pub mod destination_mint;
+pub mod evm;
+pub mod hooks;
+#[cfg(feature = "hyperlane")]
+pub mod hyperlane;
+pub mod insurance;
 pub mod jupiter;
+pub mod lending;
+pub mod limit_order;
+pub mod liquidity;
+pub mod open_orders;
+pub mod order_close;
+pub mod outbound;
+pub mod payer_ledger;
 pub mod refund;
+pub mod rent_vault;
+pub mod solvency;
+pub mod spend;
+pub mod staking;
+pub mod streaming;
+pub mod vault;
 
+#[cfg(feature = "across")]
+pub use across::*;
+pub use callback::*;
+pub use curve::*;
+#[cfg(feature = "cctp")]
+pub use cctp::*;
+pub use destination_mint::*;
+pub use evm::*;
+pub use hooks::*;
+#[cfg(feature = "hyperlane")]
+pub use hyperlane::*;
+pub use insurance::*;
 pub use jupiter::*;
+pub use lending::*;
+pub use limit_order::*;
+pub use liquidity::*;
+pub use open_orders::*;
+pub use order_close::*;
+pub use outbound::*;
+pub use payer_ledger::*;
 pub use refund::*;
+pub use rent_vault::*;
+pub use solvency::*;
+pub use spend::*;
+pub use staking::*;
+pub use streaming::*;
+pub use vault::*;
 
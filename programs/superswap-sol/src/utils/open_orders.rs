@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::state::RecipientOrders;
+
+/// Releases a completed or refunded order from its recipient's open order count
+///
+/// Recipients whose orders were all escrowed before counts were kept have no
+/// account yet, so there is nothing to release.
+pub fn release_open_order(recipient_orders: &AccountInfo) -> Result<()> {
+    if recipient_orders.owner != &crate::ID || recipient_orders.data_is_empty() {
+        return Ok(());
+    }
+    let mut data = recipient_orders.try_borrow_mut_data()?;
+    let mut orders = RecipientOrders::try_deserialize(&mut &data[..])?;
+    orders.release_order();
+    orders.try_serialize(&mut &mut data[..])
+}
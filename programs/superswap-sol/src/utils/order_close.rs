@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{OrderExtension, OrderTombstone, SwapOrder};
+use crate::error::SuperSwapError;
+use superswap_pda::{ESCROW_SEED, ORDER_EXTENSION_SEED};
+
+/// Splits a batch's remaining accounts into per-order groups and the rent payers
+///
+/// Each order comes as its writable account, its writable extension's address,
+/// its destination mint and its escrow's account for that mint; the
+/// program-owned order account starts each group, and the first account after
+/// a group that is not program-owned starts the rent payers.
+///
+/// # Returns
+/// * `Result<(Vec<&[AccountInfo; 4]>, &[AccountInfo])>` - Order groups and rent payers
+pub fn split_order_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(Vec<&'a [AccountInfo<'info>; 4]>, &'a [AccountInfo<'info>])> {
+    let mut orders = Vec::new();
+    let mut rest = accounts;
+    while rest.first().is_some_and(|account| account.owner == &crate::ID) {
+        let group = rest
+            .get(..4)
+            .and_then(|group| <&[AccountInfo; 4]>::try_from(group).ok())
+            .ok_or(SuperSwapError::InvalidInstructionData)?;
+        orders.push(group);
+        rest = &rest[4..];
+    }
+
+    Ok((orders, rest))
+}
+
+/// Fails while a settled order's escrow still holds output for a claimant
+///
+/// `escrow_token_account` must be the escrow's ATA for the order's destination
+/// mint, derived under the token program owning `destination_mint`; an account
+/// that was never created, or was closed by the claim, holds nothing.
+///
+/// # Arguments
+/// * `swap_order_key` - Address of the order being closed
+/// * `swap_order` - Order being closed
+/// * `destination_mint` - The order's destination mint
+/// * `escrow_token_account` - The escrow's account for the destination mint
+///
+/// # Returns
+/// * `Result<()>` - Success, or `EscrowNotEmpty` while output is unclaimed
+pub fn require_escrow_empty(
+    swap_order_key: &Pubkey,
+    swap_order: &SwapOrder,
+    destination_mint: &AccountInfo,
+    escrow_token_account: &AccountInfo,
+) -> Result<()> {
+    require!(
+        destination_mint.key() == swap_order.destination_mint,
+        SuperSwapError::InvalidTokenMint
+    );
+    let (escrow, _) = Pubkey::find_program_address(&[ESCROW_SEED, swap_order_key.as_ref()], &crate::ID);
+    let expected = get_associated_token_address_with_program_id(&escrow, &destination_mint.key(), destination_mint.owner);
+    require!(
+        escrow_token_account.key() == expected,
+        SuperSwapError::InvalidOutputEscrowAccount
+    );
+    if escrow_token_account.data_is_empty() {
+        return Ok(());
+    }
+
+    let data = escrow_token_account.try_borrow_data()?;
+    let escrowed = TokenAccount::try_deserialize(&mut &data[..])?.amount;
+    require!(escrowed == 0, SuperSwapError::EscrowNotEmpty);
+
+    Ok(())
+}
+
+/// Shrinks a settled order to its tombstone and moves the rest of its rent to `destination`
+///
+/// # Arguments
+/// * `order_info` - The order's account, program-owned and writable
+/// * `order_id` - Order ID recorded in the tombstone
+/// * `destination` - Account receiving the reclaimed rent
+///
+/// # Returns
+/// * `Result<u64>` - Lamports reclaimed
+pub fn tombstone_order<'info>(
+    order_info: &AccountInfo<'info>,
+    order_id: u64,
+    destination: &AccountInfo<'info>,
+) -> Result<u64> {
+    {
+        let mut data = order_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        OrderTombstone { order_id }.try_serialize(&mut writer)?;
+    }
+    order_info.realloc(OrderTombstone::LEN, false)?;
+
+    let tombstone_rent = Rent::get()?.minimum_balance(OrderTombstone::LEN);
+    let reclaimed = order_info
+        .lamports()
+        .checked_sub(tombstone_rent)
+        .ok_or(SuperSwapError::MathOverflow)?;
+    order_info.sub_lamports(reclaimed)?;
+    destination.add_lamports(reclaimed)?;
+
+    Ok(reclaimed)
+}
+
+/// Closes an order's extension, when it has one, into the account its order's rent goes to
+///
+/// `order_extension` must be the order's extension PDA even when no extension
+/// was created, so an order can never be closed while leaving its extension behind.
+///
+/// # Arguments
+/// * `swap_order_key` - Address of the order being closed
+/// * `order_extension` - The order's extension PDA, writable
+/// * `destination` - Account receiving the extension's rent
+///
+/// # Returns
+/// * `Result<u64>` - Lamports reclaimed, 0 for an order without an extension
+pub fn close_order_extension<'info>(
+    swap_order_key: &Pubkey,
+    order_extension: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<u64> {
+    let (expected, _) =
+        Pubkey::find_program_address(&[ORDER_EXTENSION_SEED, swap_order_key.as_ref()], &crate::ID);
+    require!(
+        order_extension.key() == expected,
+        SuperSwapError::InvalidOrderExtension
+    );
+    if order_extension.data_is_empty() {
+        return Ok(0);
+    }
+
+    require!(order_extension.owner == &crate::ID, SuperSwapError::InvalidOrderExtension);
+    OrderExtension::try_deserialize(&mut &order_extension.try_borrow_data()?[..])?;
+
+    let reclaimed = order_extension.lamports();
+    order_extension.sub_lamports(reclaimed)?;
+    destination.add_lamports(reclaimed)?;
+    order_extension.assign(&System::id());
+    order_extension.realloc(0, false)?;
+
+    Ok(reclaimed)
+}
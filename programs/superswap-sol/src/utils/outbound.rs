@@ -0,0 +1,271 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+use crate::state::{Config, OutboundBridge, OutboundQuote};
+use crate::error::SuperSwapError;
+use crate::utils::insurance::pay_protocol_fee;
+use crate::utils::jupiter::{execute_jupiter_swap_measured, parse_jupiter_swap_data, route_token_accounts, validate_swap_output};
+use crate::utils::refund::calculate_fee;
+use superswap_pda::CONFIG_SEED;
+
+/// USDC amounts produced by an outbound swap
+#[derive(Debug, Clone, Copy)]
+pub struct OutboundSwapAmounts {
+    /// USDC produced by the swap
+    pub usdc_out: u64,
+    /// Protocol fee taken from the output
+    pub fee_amount: u64,
+    /// USDC left to bridge
+    pub net_amount: u64,
+}
+
+/// Computes the expected amounts for an outbound swap of `usdc_amount`
+///
+/// # Arguments
+/// * `config` - Program configuration (outbound fee, limits and bridge fee estimates)
+/// * `usdc_amount` - USDC the swap is expected to produce
+/// * `bridge` - Bridge the quote is for
+///
+/// # Returns
+/// * `Result<OutboundQuote>` - Fees and minimum receivable amount
+pub fn quote_outbound_amounts(
+    config: &Config,
+    usdc_amount: u64,
+    bridge: OutboundBridge,
+) -> Result<OutboundQuote> {
+    let protocol_fee = calculate_fee(usdc_amount, config.outbound_fee_bps)?;
+    let bridged_amount = usdc_amount
+        .checked_sub(protocol_fee)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    let bridge_fee_bps = match bridge {
+        OutboundBridge::Across => config.across_fee_bps_estimate,
+        OutboundBridge::Cctp => config.cctp_fee_bps_estimate,
+    };
+    let bridge_fee_estimate = calculate_fee(bridged_amount, bridge_fee_bps)?;
+    let min_received = bridged_amount
+        .checked_sub(bridge_fee_estimate)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    Ok(OutboundQuote {
+        usdc_amount,
+        protocol_fee,
+        bridged_amount,
+        bridge_fee_estimate,
+        min_received,
+        within_limits: config.outbound_amount_in_range(usdc_amount),
+    })
+}
+
+/// Swaps the user's tokens into the program's USDC account and takes the outbound fee
+///
+/// # Arguments
+/// * `config` - Program configuration account
+/// * `jupiter_program` - Jupiter program account
+/// * `swap_data` - Serialized Jupiter instruction data
+/// * `route_accounts` - Jupiter route accounts (the user signs the route)
+/// * `user` - User whose token account the route must spend from
+/// * `program_usdc_account` - Program's USDC account receiving the swap output
+/// * `fee_recipient_account` - Fee recipient's USDC account
+/// * `insurance_usdc_account` - Insurance fund's USDC account, paid the configured fee share
+/// * `token_program` - SPL Token program
+/// * `min_usdc_out` - Minimum USDC the swap must produce
+///
+/// # Returns
+/// * `Result<OutboundSwapAmounts>` - Swap output, fee and USDC left to bridge
+#[allow(clippy::too_many_arguments)]
+pub fn swap_to_usdc_net_of_fee<'info>(
+    config: &Account<'info, Config>,
+    jupiter_program: &AccountInfo<'info>,
+    swap_data: &[u8],
+    route_accounts: &[AccountInfo<'info>],
+    user: &Pubkey,
+    program_usdc_account: &mut Account<'info, TokenAccount>,
+    fee_recipient_account: &AccountInfo<'info>,
+    insurance_usdc_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    min_usdc_out: u64,
+) -> Result<OutboundSwapAmounts> {
+    // The route must spend the user's own tokens and deliver into the program's USDC account
+    let route = parse_jupiter_swap_data(swap_data)?;
+    let (route_source, route_destination) = route_token_accounts(&route, &jupiter_program.key(), route_accounts)?;
+    let source = route_accounts
+        .iter()
+        .find(|account| account.key() == route_source)
+        .ok_or(SuperSwapError::InvalidSwapCalldata)?;
+    require_keys_eq!(*source.owner, token::ID, SuperSwapError::RouteAccountMismatch);
+    let source_owner = TokenAccount::try_deserialize(&mut &source.try_borrow_data()?[..])?.owner;
+    require!(
+        source_owner == *user && route_destination == program_usdc_account.key(),
+        SuperSwapError::RouteAccountMismatch
+    );
+
+    let usdc_out = execute_jupiter_swap_measured(
+        jupiter_program,
+        swap_data,
+        route_accounts,
+        &[],
+        program_usdc_account,
+    )?;
+    validate_swap_output(usdc_out, min_usdc_out)?;
+    require!(
+        config.outbound_amount_in_range(usdc_out),
+        SuperSwapError::OutboundAmountOutOfRange
+    );
+
+    // Outbound swaps carry their own fee, separate from the inbound fee
+    let fee_amount = calculate_fee(usdc_out, config.outbound_fee_bps)?;
+    let net_amount = usdc_out
+        .checked_sub(fee_amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    msg!("Fee Amount: {}", fee_amount);
+    msg!("Bridge Amount: {}", net_amount);
+
+    // Transfer fee to fee recipient and insurance fund if fee > 0
+    if fee_amount > 0 {
+        let instance_seed = config.instance_seed();
+        let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+        let signer = &[&seeds[..]];
+
+        pay_protocol_fee(
+            config,
+            token_program,
+            &program_usdc_account.to_account_info(),
+            &config.to_account_info(),
+            fee_recipient_account,
+            insurance_usdc_account,
+            fee_amount,
+            signer,
+        )?;
+    }
+
+    Ok(OutboundSwapAmounts {
+        usdc_out,
+        fee_amount,
+        net_amount,
+    })
+}
+
+/// Moves an outbound order's USDC from the program's USDC account into its escrow
+///
+/// The escrow is owned by the outbound order PDA, which then acts as the
+/// bridge depositor, so anything the bridge returns lands back in the escrow.
+///
+/// # Arguments
+/// * `config` - Program configuration account (authority of the program's USDC account)
+/// * `program_usdc_account` - Program's USDC account (source)
+/// * `outbound_escrow` - Outbound order's escrow (destination)
+/// * `token_program` - SPL Token program
+/// * `amount` - USDC to move
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn fund_outbound_escrow<'info>(
+    config: &Account<'info, Config>,
+    program_usdc_account: &AccountInfo<'info>,
+    outbound_escrow: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let instance_seed = config.instance_seed();
+    let seeds = &[CONFIG_SEED, instance_seed.as_ref(), &[config.bump]];
+    let signer = &[&seeds[..]];
+
+    let escrow_transfer_ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        Transfer {
+            from: program_usdc_account.clone(),
+            to: outbound_escrow.clone(),
+            authority: config.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(escrow_transfer_ctx, amount)?;
+
+    msg!("Escrowed {} USDC for outbound order", amount);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::SurplusPolicy;
+
+    fn test_config() -> Config {
+        Config {
+            admin: Pubkey::default(),
+            across_handler: Pubkey::default(),
+            jupiter_program: Pubkey::default(),
+            across_spoke_pool: Pubkey::default(),
+            cctp_token_messenger_minter: Pubkey::default(),
+            usdc_mint: Pubkey::default(),
+            fee_recipient: Pubkey::default(),
+            fee_bps: 30,
+            outbound_fee_bps: 10,
+            min_outbound_amount: 1_000_000,
+            max_outbound_amount: 0,
+            across_fee_bps_estimate: 5,
+            cctp_fee_bps_estimate: 0,
+            onward_adapter: Pubkey::default(),
+            jito_stake_pool: Pubkey::default(),
+            marinade_state: Pubkey::default(),
+            kamino_lending_market: Pubkey::default(),
+            solend_lending_market: Pubkey::default(),
+            max_gas_top_up_usdc: 0,
+            max_donation_bps: 0,
+            refund_bounty_bps: 0,
+            min_order_usdc: 0,
+            deliver_dust_as_usdc: false,
+            max_open_orders_per_recipient: 0,
+            max_price_impact_bps: 0,
+            order_ttl_seconds: 0,
+            escrow_shortfall_tolerance: 0,
+            across_paused: false,
+            cctp_paused: false,
+            insurance_fee_share_bps: 0,
+            max_insurance_claim_usdc: 0,
+            fallback_output_mint: Pubkey::default(),
+            rent_treasury: Pubkey::default(),
+            min_retention_seconds: 0,
+            operator_fill_cost_usdc: 0,
+            version: Config::VERSION,
+            guardian: Pubkey::default(),
+            max_order_usdc: 0,
+            enabled_adapters: Config::ADAPTER_ALL,
+            vault_authority: Pubkey::default(),
+            instance_id: 0,
+            refunds_only: false,
+            max_relayer_tip_usdc: 0,
+            surplus_share_bps: 0,
+            max_gas_drop_lamports: 0,
+            pending_admin: Pubkey::default(),
+            config_update_delay_seconds: 0,
+            operator: Pubkey::default(),
+            additional_across_handlers: [Pubkey::default(); Config::MAX_ADDITIONAL_ACROSS_HANDLERS],
+            daily_volume_cap: 0,
+            surplus_policy: SurplusPolicy::Treasury,
+            is_paused: false,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn quote_deducts_protocol_and_bridge_fees() {
+        let quote = quote_outbound_amounts(&test_config(), 10_000_000, OutboundBridge::Across).unwrap();
+
+        assert_eq!(quote.protocol_fee, 10_000);
+        assert_eq!(quote.bridged_amount, 9_990_000);
+        assert_eq!(quote.bridge_fee_estimate, 4_995);
+        assert_eq!(quote.min_received, 9_985_005);
+        assert!(quote.within_limits);
+    }
+
+    #[test]
+    fn quote_flags_amounts_below_minimum() {
+        let quote = quote_outbound_amounts(&test_config(), 999_999, OutboundBridge::Cctp).unwrap();
+
+        assert_eq!(quote.bridge_fee_estimate, 0);
+        assert!(!quote.within_limits);
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::error::SuperSwapError;
+use crate::events::PayerExpenseRecorded;
+use crate::state::{Config, PayerLedger};
+
+/// Checks who paid for an order's transaction and records the cost against their ledger
+///
+/// Across handlers pay for their own fills and keep no ledger. Any other payer
+/// must be a registered operator, whose ledger is credited the config's fill cost
+/// to be settled later from collected fees.
+///
+/// # Arguments
+/// * `config` - Program configuration
+/// * `payer` - Account paying for the transaction
+/// * `payer_ledger` - The payer's ledger, required unless the payer is an Across handler
+/// * `order_id` - Order the payer paid for
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn record_payer_expense(
+    config: &Config,
+    payer: &Pubkey,
+    payer_ledger: Option<&mut Account<PayerLedger>>,
+    order_id: u64,
+) -> Result<()> {
+    let Some(ledger) = payer_ledger else {
+        require!(config.is_across_handler(payer), SuperSwapError::PayerNotRegistered);
+        return Ok(());
+    };
+    if config.is_across_handler(payer) {
+        return Ok(());
+    }
+
+    ledger.record_fill(config.operator_fill_cost_usdc)?;
+
+    emit!(PayerExpenseRecorded {
+        order_id,
+        operator: *payer,
+        cost_usdc: config.operator_fill_cost_usdc,
+        owed_usdc: ledger.owed_usdc,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token::TokenAccount;
+use crate::state::{Config, LamportSource, RentVault, SwapOrder};
+use crate::error::SuperSwapError;
+
+/// Reimburses the payer from the rent vault for rent it fronted
+///
+/// # Arguments
+/// * `rent_vault` - Program-owned rent vault (source)
+/// * `payer` - Account that paid for the account creation (destination)
+/// * `amount` - Lamports to reimburse
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn reimburse_rent<'info>(
+    rent_vault: &mut Account<'info, RentVault>,
+    payer: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // The vault must stay rent exempt after paying out
+    let min_balance = Rent::get()?.minimum_balance(RentVault::LEN);
+    let available = rent_vault
+        .get_lamports()
+        .checked_sub(min_balance)
+        .ok_or(SuperSwapError::InsufficientRentVaultBalance)?;
+    require!(available >= amount, SuperSwapError::InsufficientRentVaultBalance);
+
+    rent_vault.sub_lamports(amount)?;
+    payer.add_lamports(amount)?;
+
+    rent_vault.total_subsidized = rent_vault
+        .total_subsidized
+        .checked_add(amount)
+        .ok_or(SuperSwapError::MathOverflow)?;
+
+    msg!("Rent vault reimbursed {} lamports", amount);
+
+    Ok(())
+}
+
+/// Reimburses the payer from the rent vault when the vault can spare it
+///
+/// Unlike `reimburse_rent`, a vault running low does not fail the instruction:
+/// the payer keeps fronting the rent instead.
+///
+/// # Arguments
+/// * `rent_vault` - Program-owned rent vault (source)
+/// * `payer` - Account that paid for the account creation (destination)
+/// * `amount` - Lamports to reimburse
+///
+/// # Returns
+/// * `Result<bool>` - Whether the vault reimbursed the payer
+pub fn reimburse_rent_if_available<'info>(
+    rent_vault: &mut Account<'info, RentVault>,
+    payer: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<bool> {
+    let min_balance = Rent::get()?.minimum_balance(RentVault::LEN);
+    if rent_vault.get_lamports().saturating_sub(min_balance) < amount {
+        return Ok(false);
+    }
+
+    reimburse_rent(rent_vault, payer, amount)?;
+    Ok(true)
+}
+
+/// Reimburses the payer for an order account's rent when the vault can spare it
+///
+/// A vault running low leaves the payer fronting the rent, which it gets back
+/// when the order is closed.
+///
+/// # Arguments
+/// * `rent_vault` - Program-owned rent vault (source)
+/// * `payer` - Account that paid for the order account
+/// * `amount` - Lamports of rent the order account holds
+///
+/// # Returns
+/// * `Result<Pubkey>` - Account that ended up funding the rent, to record as the order's `rent_payer`
+pub fn fund_order_rent<'info>(
+    rent_vault: &mut Account<'info, RentVault>,
+    payer: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<Pubkey> {
+    if !reimburse_rent_if_available(rent_vault, payer, amount)? {
+        msg!("Rent vault low, payer fronts {} lamports until the order is closed", amount);
+        return Ok(payer.key());
+    }
+
+    Ok(rent_vault.key())
+}
+
+/// Picks the account a closed order's rent goes to
+///
+/// The config's rent treasury takes everything when set; otherwise the rent
+/// returns to whoever funded it. Orders funded by a payer need that payer's
+/// account among `rent_payers`.
+///
+/// # Arguments
+/// * `config` - Program configuration
+/// * `swap_order` - Order being closed
+/// * `rent_vault` - Program-owned rent vault
+/// * `rent_treasury` - The configured rent treasury, when passed
+/// * `rent_payers` - Payer accounts passed alongside the orders
+///
+/// # Returns
+/// * `Result<&AccountInfo>` - Account to close the order into
+pub fn order_rent_destination<'a, 'info>(
+    config: &Config,
+    swap_order: &SwapOrder,
+    rent_vault: &'a AccountInfo<'info>,
+    rent_treasury: Option<&'a AccountInfo<'info>>,
+    rent_payers: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>> {
+    if config.rent_treasury != Pubkey::default() {
+        return rent_treasury.ok_or(error!(SuperSwapError::InvalidRentDestination));
+    }
+    if swap_order.rent_payer == rent_vault.key() {
+        return Ok(rent_vault);
+    }
+    rent_payers
+        .iter()
+        .find(|account| account.key() == swap_order.rent_payer)
+        .ok_or(error!(SuperSwapError::InvalidRentDestination))
+}
+
+/// Creates an associated token account if it does not exist yet,
+/// with the rent covered by the rent vault
+///
+/// A vault running low does not fail the instruction: the payer funds the
+/// rent itself, as it would have without the vault.
+///
+/// # Arguments
+/// * `rent_vault` - Program-owned rent vault covering the rent
+/// * `payer` - Signer funding the creation CPI (reimbursed up front when the vault can spare it)
+/// * `associated_token` - The ATA to create
+/// * `authority` - Owner of the ATA
+/// * `mint` - Mint of the ATA
+/// * `system_program` - System program
+/// * `token_program` - SPL Token program
+/// * `associated_token_program` - Associated Token program
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+#[allow(clippy::too_many_arguments)]
+pub fn create_ata_if_needed<'info>(
+    rent_vault: &mut Account<'info, RentVault>,
+    payer: &AccountInfo<'info>,
+    associated_token: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if !associated_token.data_is_empty() {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+    let subsidized = reimburse_rent_if_available(rent_vault, payer, rent)?;
+    if !subsidized {
+        msg!("Rent vault low, payer funds {} lamports of token account rent", rent);
+    }
+
+    let create_ctx = CpiContext::new(
+        associated_token_program.clone(),
+        Create {
+            payer: payer.clone(),
+            associated_token: associated_token.clone(),
+            authority: authority.clone(),
+            mint: mint.clone(),
+            system_program: system_program.clone(),
+            token_program: token_program.clone(),
+        },
+    );
+    associated_token::create(create_ctx)?;
+
+    // Token-2022 accounts carrying extensions need more rent than a plain token account
+    let extra_rent = associated_token.lamports().saturating_sub(rent);
+    if subsidized && extra_rent > 0 && !reimburse_rent_if_available(rent_vault, payer, extra_rent)? {
+        msg!("Rent vault low, payer funds {} lamports of extension rent", extra_rent);
+    }
+
+    msg!("Created associated token account: {}", associated_token.key());
+
+    Ok(())
+}
+
+/// Checks that lamports may be recovered from `source`
+///
+/// The account must be the config instance's `named` account, so an admin can
+/// only reach accounts of its own instance, and must be owned by this program.
+pub fn require_stray_lamport_source(source: &AccountInfo, named: &LamportSource, config: &Config) -> Result<()> {
+    require_keys_eq!(
+        source.key(),
+        named.address(&crate::ID, config.instance_id),
+        SuperSwapError::InvalidLamportSource
+    );
+    require!(source.owner == &crate::ID, SuperSwapError::InvalidLamportSource);
+    Ok(())
+}
+
+/// Moves lamports a program-owned account holds above its rent-exempt minimum
+///
+/// # Arguments
+/// * `source` - Program-owned account holding the stray lamports, checked by `require_stray_lamport_source`
+/// * `destination` - Account receiving them
+/// * `amount` - Lamports to move
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn withdraw_stray_lamports(
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    // The source must stay rent exempt for the data it holds
+    let min_balance = Rent::get()?.minimum_balance(source.data_len());
+    let available = source.lamports().saturating_sub(min_balance);
+    require!(amount <= available, SuperSwapError::InsufficientStrayLamports);
+
+    source.sub_lamports(amount)?;
+    destination.add_lamports(amount)?;
+    Ok(())
+}
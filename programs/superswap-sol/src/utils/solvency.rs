@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::events::SolvencyBreakerTripped;
+use crate::state::Config;
+
+/// Solvency breaker for an escrow holding `actual_usdc` against a ledger of `expected_usdc`
+///
+/// A shortfall beyond the config's tolerance switches the program to refunds-only
+/// until an operator investigates, rejecting fills but leaving refunds open.
+/// Returns whether it tripped, in which case the caller must return `Ok`: an
+/// error would roll the switch back.
+pub fn trip_on_escrow_shortfall(
+    config: &mut Config,
+    order_id: u64,
+    escrow_usdc_account: Pubkey,
+    expected_usdc: u64,
+    actual_usdc: u64,
+) -> bool {
+    if !config.escrow_shortfall_exceeded(expected_usdc, actual_usdc) {
+        return false;
+    }
+    config.refunds_only = true;
+    msg!(
+        "Escrow {} holds {} USDC against {} owed; switching to refunds-only",
+        escrow_usdc_account,
+        actual_usdc,
+        expected_usdc
+    );
+    emit!(SolvencyBreakerTripped {
+        order_id,
+        escrow: escrow_usdc_account,
+        expected_usdc,
+        actual_usdc,
+    });
+    true
+}
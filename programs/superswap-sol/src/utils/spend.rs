@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+use crate::state::SpendProgram;
+use crate::error::SuperSwapError;
+
+/// Hands escrowed output to an allowlisted spend program via CPI
+///
+/// The escrow PDA signs, so the spend program can pull the output from the
+/// escrow's token account; it only ever holds this order's funds.
+///
+/// # Arguments
+/// * `spend_program` - Spend program account
+/// * `entry` - Allowlist entry for the spend program
+/// * `expected_program` - Spend program recorded in the order's action
+/// * `accounts` - Accounts required by the spend instruction, in program order
+/// * `escrow` - Escrow PDA, signing via seeds
+/// * `data` - Instruction data for the spend program
+/// * `signer_seeds` - Seeds for the escrow PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn spend_output<'info>(
+    spend_program: &AccountInfo<'info>,
+    entry: &SpendProgram,
+    expected_program: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    escrow: &Pubkey,
+    data: &[u8],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        spend_program.key() == *expected_program && entry.program_id == *expected_program,
+        SuperSwapError::SpendProgramNotAllowed
+    );
+
+    // The escrow PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == escrow,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let spend_instruction = Instruction {
+        program_id: spend_program.key(),
+        accounts: account_metas,
+        data: data.to_vec(),
+    };
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(spend_program.clone());
+    invoke_signed(&spend_instruction, &account_infos, signer_seeds)?;
+
+    msg!("Output spent through {}", spend_program.key());
+
+    Ok(())
+}
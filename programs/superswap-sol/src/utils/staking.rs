@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+    pubkey,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::LiquidStakingPool;
+use crate::error::SuperSwapError;
+
+/// SPL stake pool program (JitoSOL)
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Marinade liquid staking program (mSOL)
+pub const MARINADE_PROGRAM_ID: Pubkey = pubkey!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+
+/// Index of `DepositSol` in the SPL stake pool instruction enum
+pub const STAKE_POOL_DEPOSIT_SOL: u8 = 14;
+
+/// Anchor discriminator of the Marinade `deposit` instruction
+pub const MARINADE_DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
+/// Positions of the accounts the program validates, per pool
+struct StakeAccountLayout {
+    pool: usize,
+    lst_mint: usize,
+    lamports_from: usize,
+    lst_destination: usize,
+}
+
+impl LiquidStakingPool {
+    /// Program that accepts SOL deposits for this pool
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            LiquidStakingPool::Jito => SPL_STAKE_POOL_PROGRAM_ID,
+            LiquidStakingPool::Marinade => MARINADE_PROGRAM_ID,
+        }
+    }
+
+    fn layout(&self) -> StakeAccountLayout {
+        match self {
+            // stake_pool, withdraw_authority, reserve_stake, lamports_from,
+            // pool_tokens_to, manager_fee_account, referrer_fee_account, pool_mint, ...
+            LiquidStakingPool::Jito => StakeAccountLayout {
+                pool: 0,
+                lst_mint: 7,
+                lamports_from: 3,
+                lst_destination: 4,
+            },
+            // state, msol_mint, liq_pool_sol_leg, liq_pool_msol_leg, liq_pool_msol_leg_authority,
+            // reserve, transfer_from, mint_to, ...
+            LiquidStakingPool::Marinade => StakeAccountLayout {
+                pool: 0,
+                lst_mint: 1,
+                lamports_from: 6,
+                lst_destination: 7,
+            },
+        }
+    }
+
+    /// LST mint and beneficiary LST account within the deposit accounts
+    pub fn lst_accounts<'a, 'info>(
+        &self,
+        accounts: &'a [AccountInfo<'info>],
+    ) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)> {
+        let layout = self.layout();
+        let mint = accounts.get(layout.lst_mint).ok_or(SuperSwapError::InvalidStakePool)?;
+        let destination = accounts
+            .get(layout.lst_destination)
+            .ok_or(SuperSwapError::InvalidStakePool)?;
+        Ok((mint, destination))
+    }
+
+    fn instruction_data(&self, lamports: u64) -> Vec<u8> {
+        let mut data = match self {
+            LiquidStakingPool::Jito => vec![STAKE_POOL_DEPOSIT_SOL],
+            LiquidStakingPool::Marinade => MARINADE_DEPOSIT_DISCRIMINATOR.to_vec(),
+        };
+        data.extend_from_slice(&lamports.to_le_bytes());
+        data
+    }
+}
+
+/// Deposits SOL into a liquid staking pool via CPI, minting the LST to the beneficiary
+///
+/// # Arguments
+/// * `pool` - Liquid staking pool to deposit into
+/// * `stake_program` - Stake pool or Marinade program account
+/// * `expected_pool` - Pool (stake pool or Marinade state) configured for this kind
+/// * `accounts` - Accounts required by the deposit, in program order
+/// * `depositor` - PDA holding the lamports, signing via seeds
+/// * `beneficiary` - Owner of the LST account receiving the minted tokens
+/// * `lamports` - SOL to stake
+/// * `signer_seeds` - Seeds for the depositor PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+#[allow(clippy::too_many_arguments)]
+pub fn liquid_stake(
+    pool: LiquidStakingPool,
+    stake_program: &AccountInfo,
+    expected_pool: &Pubkey,
+    accounts: &[AccountInfo],
+    depositor: &Pubkey,
+    beneficiary: &Pubkey,
+    lamports: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let layout = pool.layout();
+    require!(
+        stake_program.key() == pool.program_id()
+            && *expected_pool != Pubkey::default()
+            && accounts.len() > layout.lst_mint.max(layout.lst_destination),
+        SuperSwapError::InvalidStakePool
+    );
+
+    // The configured pool, our depositor and the beneficiary's LST account must be in place
+    let lst_mint = accounts[layout.lst_mint].key();
+    require!(
+        accounts[layout.pool].key() == *expected_pool
+            && accounts[layout.lamports_from].key() == *depositor
+            && accounts[layout.lst_destination].key()
+                == get_associated_token_address(beneficiary, &lst_mint),
+        SuperSwapError::InvalidStakePool
+    );
+
+    msg!("Staking {} lamports for {}", lamports, beneficiary);
+
+    // The depositor PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == depositor,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let deposit_instruction = Instruction {
+        program_id: stake_program.key(),
+        accounts: account_metas,
+        data: pool.instruction_data(lamports),
+    };
+
+    invoke_signed(&deposit_instruction, accounts, signer_seeds)?;
+
+    msg!("Liquid stake deposit submitted");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deposit_data_encodes_lamports() {
+        let jito = LiquidStakingPool::Jito.instruction_data(5);
+        assert_eq!(jito, [STAKE_POOL_DEPOSIT_SOL, 5, 0, 0, 0, 0, 0, 0, 0]);
+
+        let marinade = LiquidStakingPool::Marinade.instruction_data(5);
+        assert_eq!(marinade[..8], MARINADE_DEPOSIT_DISCRIMINATOR);
+        assert_eq!(marinade[8..], 5u64.to_le_bytes());
+    }
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+    pubkey,
+};
+use crate::state::StreamPlan;
+use crate::error::SuperSwapError;
+
+/// Streamflow protocol program
+pub const STREAMFLOW_PROGRAM_ID: Pubkey = pubkey!("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m");
+
+/// Anchor discriminator of the Streamflow `create` instruction
+pub const STREAMFLOW_CREATE_DISCRIMINATOR: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+
+/// Streamflow fee charged on top of the deposit, in basis points
+pub const STREAMFLOW_FEE_BPS: u64 = 25;
+
+/// Accounts per stream: sender, sender_tokens, recipient, metadata, escrow_tokens,
+/// recipient_tokens, streamflow_treasury, streamflow_treasury_tokens, withdrawor,
+/// partner, partner_tokens, mint, fee_oracle, rent, timelock_program,
+/// token_program, associated_token_program, system_program
+pub const STREAMFLOW_CREATE_ACCOUNTS: usize = 18;
+
+const CREATE_SENDER: usize = 0;
+const CREATE_SENDER_TOKENS: usize = 1;
+const CREATE_RECIPIENT: usize = 2;
+const CREATE_METADATA: usize = 3;
+const CREATE_RECIPIENT_TOKENS: usize = 5;
+
+/// Arguments of the Streamflow `create` instruction
+#[derive(AnchorSerialize, Clone)]
+pub struct CreateStreamArgs {
+    pub start_time: u64,
+    pub net_amount_deposited: u64,
+    pub period: u64,
+    pub amount_per_period: u64,
+    pub cliff: u64,
+    pub cliff_amount: u64,
+    pub cancelable_by_sender: bool,
+    pub cancelable_by_recipient: bool,
+    pub automatic_withdrawal: bool,
+    pub transferable_by_sender: bool,
+    pub transferable_by_recipient: bool,
+    pub can_topup: bool,
+    pub stream_name: [u8; 64],
+    pub withdraw_frequency: u64,
+}
+
+impl CreateStreamArgs {
+    /// Builds the stream arguments for one beneficiary of a plan
+    ///
+    /// The escrow PDA is the sender, so streams are never cancelable or
+    /// transferable by it; the beneficiary may transfer its stream.
+    pub fn for_plan(plan: &StreamPlan, start_time: u64, net_amount: u64, order_id: u64) -> Result<Self> {
+        let cliff_amount = (net_amount as u128)
+            .checked_mul(plan.cliff_bps as u128)
+            .ok_or(SuperSwapError::MathOverflow)?
+            / 10_000;
+        let cliff_amount = cliff_amount as u64;
+        let streamed = net_amount - cliff_amount;
+
+        let periods = (plan.duration_seconds / plan.period_seconds).max(1);
+        let amount_per_period = streamed.div_ceil(periods).max(1);
+
+        let mut stream_name = [0u8; 64];
+        let name = format!("superswap-{}", order_id);
+        stream_name[..name.len()].copy_from_slice(name.as_bytes());
+
+        Ok(Self {
+            start_time,
+            net_amount_deposited: net_amount,
+            period: plan.period_seconds,
+            amount_per_period,
+            cliff: start_time,
+            cliff_amount,
+            cancelable_by_sender: false,
+            cancelable_by_recipient: false,
+            automatic_withdrawal: false,
+            transferable_by_sender: false,
+            transferable_by_recipient: true,
+            can_topup: false,
+            stream_name,
+            withdraw_frequency: plan.period_seconds,
+        })
+    }
+}
+
+/// Largest deposit whose Streamflow fee still fits in `amount`
+pub fn net_of_stream_fee(amount: u64) -> Result<u64> {
+    let net = (amount as u128)
+        .checked_mul(10_000)
+        .ok_or(SuperSwapError::MathOverflow)?
+        / (10_000 + STREAMFLOW_FEE_BPS as u128);
+    Ok(net as u64)
+}
+
+/// Beneficiary and its token account within one stream's `create` accounts
+pub fn stream_recipient_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)> {
+    let recipient = accounts.get(CREATE_RECIPIENT).ok_or(SuperSwapError::InvalidStreamPlan)?;
+    let recipient_tokens = accounts
+        .get(CREATE_RECIPIENT_TOKENS)
+        .ok_or(SuperSwapError::InvalidStreamPlan)?;
+    Ok((recipient, recipient_tokens))
+}
+
+/// Creates a Streamflow stream funded by the escrow via CPI
+///
+/// # Arguments
+/// * `streamflow_program` - Streamflow program account
+/// * `accounts` - Accounts required by `create`, in program order
+/// * `sender` - Escrow PDA funding the stream, signing via seeds
+/// * `sender_tokens` - Escrow's token account holding the output
+/// * `beneficiary` - Stream recipient from the order's plan
+/// * `args` - Stream schedule and amount
+/// * `signer_seeds` - Seeds for the escrow PDA
+///
+/// # Returns
+/// * `Result<Pubkey>` - The stream's metadata account
+pub fn create_stream<'info>(
+    streamflow_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    sender: &Pubkey,
+    sender_tokens: &Pubkey,
+    beneficiary: &Pubkey,
+    args: &CreateStreamArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<Pubkey> {
+    require!(
+        streamflow_program.key() == STREAMFLOW_PROGRAM_ID
+            && accounts.len() == STREAMFLOW_CREATE_ACCOUNTS,
+        SuperSwapError::InvalidStreamPlan
+    );
+    require!(
+        accounts[CREATE_SENDER].key() == *sender
+            && accounts[CREATE_SENDER_TOKENS].key() == *sender_tokens
+            && accounts[CREATE_RECIPIENT].key() == *beneficiary,
+        SuperSwapError::InvalidRecipient
+    );
+
+    msg!("Streaming {} to {}", args.net_amount_deposited, beneficiary);
+
+    let mut data = STREAMFLOW_CREATE_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    // The escrow PDA signs through invoke_signed; the metadata keypair signs the transaction
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == sender,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let stream_instruction = Instruction {
+        program_id: streamflow_program.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(streamflow_program.clone());
+    invoke_signed(&stream_instruction, &account_infos, signer_seeds)?;
+
+    Ok(accounts[CREATE_METADATA].key())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StreamRecipient;
+
+    fn plan(cliff_bps: u16) -> StreamPlan {
+        StreamPlan {
+            swap_order: Pubkey::default(),
+            recipients: vec![StreamRecipient {
+                beneficiary: Pubkey::new_unique(),
+                share_bps: 10_000,
+            }],
+            start_time: 0,
+            duration_seconds: 30 * 86_400,
+            period_seconds: 86_400,
+            cliff_bps,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn stream_args_unlock_full_amount_by_end() {
+        let args = CreateStreamArgs::for_plan(&plan(1_000), 100, 3_000_001, 7).unwrap();
+        assert_eq!(args.cliff_amount, 300_000);
+        assert!(args.cliff_amount + args.amount_per_period * 30 >= args.net_amount_deposited);
+        assert_eq!(&args.stream_name[..11], b"superswap-7");
+    }
+
+    #[test]
+    fn stream_fee_fits_in_amount() {
+        let net = net_of_stream_fee(1_000_000).unwrap();
+        assert!(net + net * STREAMFLOW_FEE_BPS / 10_000 <= 1_000_000);
+    }
+}
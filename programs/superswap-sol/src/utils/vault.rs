@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::invoke_signed,
+};
+use crate::state::VaultProgram;
+use crate::error::SuperSwapError;
+
+/// Anchor discriminator of the 4626-style vault `deposit` instruction
+pub const VAULT_DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
+// deposit accounts: vault, depositor, depositor_asset_account, share_mint,
+// receiver_share_account, vault_asset_account, ...
+const DEPOSIT_VAULT: usize = 0;
+const DEPOSIT_DEPOSITOR: usize = 1;
+const DEPOSIT_DEPOSITOR_ASSETS: usize = 2;
+const DEPOSIT_SHARE_MINT: usize = 3;
+const DEPOSIT_RECEIVER_SHARES: usize = 4;
+
+/// Arguments of the 4626-style vault `deposit` instruction
+#[derive(AnchorSerialize, Clone)]
+pub struct VaultDepositArgs {
+    /// Assets to deposit
+    pub assets: u64,
+    /// Minimum shares minted to the receiver
+    pub min_shares_out: u64,
+}
+
+/// Share mint and receiver share account within the `deposit` accounts
+pub fn vault_share_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)> {
+    let share_mint = accounts
+        .get(DEPOSIT_SHARE_MINT)
+        .ok_or(SuperSwapError::InvalidVault)?;
+    let receiver_shares = accounts
+        .get(DEPOSIT_RECEIVER_SHARES)
+        .ok_or(SuperSwapError::InvalidVault)?;
+    Ok((share_mint, receiver_shares))
+}
+
+/// Deposits escrowed output into an allowlisted 4626-style vault via CPI,
+/// minting the shares to the beneficiary
+///
+/// # Arguments
+/// * `vault_program` - Vault program account
+/// * `entry` - Allowlist entry for the vault program
+/// * `expected_program` - Vault program recorded in the order's action
+/// * `expected_vault` - Vault recorded in the order's action
+/// * `accounts` - Accounts required by `deposit`, in program order
+/// * `depositor` - Escrow PDA, signing via seeds
+/// * `depositor_assets` - Escrow token account holding the output
+/// * `receiver_shares` - Beneficiary's share token account
+/// * `args` - Deposit amount and minimum shares
+/// * `signer_seeds` - Seeds for the escrow PDA
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_to_vault<'info>(
+    vault_program: &AccountInfo<'info>,
+    entry: &VaultProgram,
+    expected_program: &Pubkey,
+    expected_vault: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    depositor: &Pubkey,
+    depositor_assets: &Pubkey,
+    receiver_shares: &Pubkey,
+    args: &VaultDepositArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        vault_program.key() == *expected_program && entry.program_id == *expected_program,
+        SuperSwapError::VaultProgramNotAllowed
+    );
+    require!(
+        accounts.len() > DEPOSIT_RECEIVER_SHARES
+            && accounts[DEPOSIT_VAULT].key() == *expected_vault,
+        SuperSwapError::InvalidVault
+    );
+    require!(
+        accounts[DEPOSIT_DEPOSITOR].key() == *depositor
+            && accounts[DEPOSIT_DEPOSITOR_ASSETS].key() == *depositor_assets
+            && accounts[DEPOSIT_RECEIVER_SHARES].key() == *receiver_shares,
+        SuperSwapError::InvalidRecipient
+    );
+
+    msg!("Depositing {} into vault {}", args.assets, expected_vault);
+
+    let mut data = VAULT_DEPOSIT_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    // The escrow PDA signs through invoke_signed, so mark it as a signer
+    let account_metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == depositor,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let deposit_instruction = Instruction {
+        program_id: vault_program.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(vault_program.clone());
+    invoke_signed(&deposit_instruction, &account_infos, signer_seeds)?;
+
+    Ok(())
+}
@@ -28,6 +28,12 @@ async function main() {
     // Jupiter V6 program ID (mainnet)
     jupiterProgram: new PublicKey("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"),
     
+    // Across spoke pool program ID (mainnet)
+    acrossSpokePool: new PublicKey("DLv3NggMiSaef97YCkew5xKUHDh13tVGZ7tydt3ZeAru"),
+    
+    // CCTP TokenMessengerMinter program ID (mainnet)
+    cctpTokenMessengerMinter: new PublicKey("CCTPiPYPc6AsJuwueEnWgSgucamXDZwBd53dQ11YiKX3"),
+    
     // USDC mint (mainnet)
     usdcMint: new PublicKey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
     
@@ -36,6 +42,13 @@ async function main() {
     
     // Fee in basis points (30 = 0.3%)
     feeBps: 30,
+
+    // Outbound fee in basis points (10 = 0.1%)
+    outboundFeeBps: 10,
+
+    // Outbound swap output limits in USDC base units (max 0 = no cap)
+    minOutboundAmount: new anchor.BN(1_000_000),
+    maxOutboundAmount: new anchor.BN(0),
   };
 
   // Derive config PDA
@@ -47,6 +60,18 @@ async function main() {
   console.log("\nConfig PDA:", configPda.toString());
   console.log("Config Bump:", configBump);
 
+  // Derive rent vault PDA
+  const [rentVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("rent_vault")],
+    program.programId
+  );
+
+  // Derive outbound rate limit PDA
+  const [outboundRateLimitPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("outbound_rate_limit")],
+    program.programId
+  );
+
   // Check if already initialized
   try {
     const existingConfig = await program.account.config.fetch(configPda);
@@ -55,9 +80,11 @@ async function main() {
       admin: existingConfig.admin.toString(),
       acrossHandler: existingConfig.acrossHandler.toString(),
       jupiterProgram: existingConfig.jupiterProgram.toString(),
+      acrossSpokePool: existingConfig.acrossSpokePool.toString(),
       usdcMint: existingConfig.usdcMint.toString(),
       feeRecipient: existingConfig.feeRecipient.toString(),
       feeBps: existingConfig.feeBps,
+      outboundFeeBps: existingConfig.outboundFeeBps,
       isPaused: existingConfig.isPaused,
     });
     return;
@@ -71,6 +98,8 @@ async function main() {
     .initialize(config)
     .accounts({
       config: configPda,
+      rentVault: rentVaultPda,
+      outboundRateLimit: outboundRateLimitPda,
       admin: provider.wallet.publicKey,
       systemProgram: SystemProgram.programId,
     })
@@ -88,6 +117,7 @@ async function main() {
   console.log("  USDC Mint:", configAccount.usdcMint.toString());
   console.log("  Fee Recipient:", configAccount.feeRecipient.toString());
   console.log("  Fee BPS:", configAccount.feeBps);
+  console.log("  Outbound Fee BPS:", configAccount.outboundFeeBps);
   console.log("  Is Paused:", configAccount.isPaused);
 }
 
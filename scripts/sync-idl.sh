@@ -0,0 +1,21 @@
+#!/bin/bash
+
+# Regenerates the program IDL committed to the SDK
+#
+# Usage:
+#   ./scripts/sync-idl.sh
+#
+# The SDK's build script generates its event and account decoders from
+# crates/superswap-sdk/idl/superswap_sol.json and fails to compile when the
+# IDL's discriminators no longer match the program. Run this after adding or
+# renaming a program event or account, and commit the result.
+
+set -e
+
+IDL_OUT="crates/superswap-sdk/idl/superswap_sol.json"
+
+cd "$(dirname "$0")/.."
+
+anchor idl build -p superswap_sol -o "$IDL_OUT"
+
+echo "Wrote $IDL_OUT"
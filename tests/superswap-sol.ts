@@ -29,10 +29,20 @@ describe("superswap-sol", () => {
   let destinationMint: PublicKey;
   let configPda: PublicKey;
   let configBump: number;
+  let rentVaultPda: PublicKey;
+  let outboundRateLimitPda: PublicKey;
+  let recoveryGuardPda: PublicKey;
+  let adminLogPda: PublicKey;
 
   // Jupiter program ID (mainnet)
   const jupiterProgramId = new PublicKey("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
 
+  // Across spoke pool program ID
+  const acrossSpokePoolId = new PublicKey("DLv3NggMiSaef97YCkew5xKUHDh13tVGZ7tydt3ZeAru");
+
+  // CCTP TokenMessengerMinter program ID
+  const cctpTokenMessengerMinterId = new PublicKey("CCTPiPYPc6AsJuwueEnWgSgucamXDZwBd53dQ11YiKX3");
+
   before(async () => {
     // Airdrop SOL to test accounts
     await provider.connection.requestAirdrop(
@@ -76,6 +86,30 @@ describe("superswap-sol", () => {
       [Buffer.from("config")],
       program.programId
     );
+
+    // Derive rent vault PDA
+    [rentVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rent_vault")],
+      program.programId
+    );
+
+    // Derive outbound rate limit PDA
+    [outboundRateLimitPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("outbound_rate_limit")],
+      program.programId
+    );
+
+    // Derive recovery guard PDA
+    [recoveryGuardPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recovery_guard")],
+      program.programId
+    );
+
+    // Derive admin log PDA
+    [adminLogPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_log"), configPda.toBuffer()],
+      program.programId
+    );
   });
 
   it("Initializes the program", async () => {
@@ -83,12 +117,21 @@ describe("superswap-sol", () => {
       .initialize({
         acrossHandler: acrossHandler.publicKey,
         jupiterProgram: jupiterProgramId,
+        acrossSpokePool: acrossSpokePoolId,
+        cctpTokenMessengerMinter: cctpTokenMessengerMinterId,
         usdcMint: usdcMint,
         feeRecipient: feeRecipient.publicKey,
         feeBps: 30, // 0.3% fee
+        outboundFeeBps: 10, // 0.1% fee on outbound swaps
+        minOutboundAmount: new anchor.BN(1_000_000), // 1 USDC
+        maxOutboundAmount: new anchor.BN(0), // no cap
       })
       .accounts({
         config: configPda,
+        rentVault: rentVaultPda,
+        outboundRateLimit: outboundRateLimitPda,
+        recoveryGuard: recoveryGuardPda,
+        adminLog: adminLogPda,
         admin: admin.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -101,32 +144,142 @@ describe("superswap-sol", () => {
     assert.ok(config.admin.equals(admin.publicKey));
     assert.ok(config.acrossHandler.equals(acrossHandler.publicKey));
     assert.ok(config.jupiterProgram.equals(jupiterProgramId));
+    assert.ok(config.acrossSpokePool.equals(acrossSpokePoolId));
     assert.ok(config.usdcMint.equals(usdcMint));
     assert.ok(config.feeRecipient.equals(feeRecipient.publicKey));
     assert.equal(config.feeBps, 30);
+    assert.equal(config.outboundFeeBps, 10);
+    assert.equal(config.minOutboundAmount.toNumber(), 1_000_000);
+    assert.equal(config.maxOutboundAmount.toNumber(), 0);
     assert.equal(config.isPaused, false);
   });
 
-  it("Updates config", async () => {
-    const newFeeRecipient = Keypair.generate().publicKey;
+  it("Funds the rent vault", async () => {
+    const amount = LAMPORTS_PER_SOL / 2;
+    const balanceBefore = await provider.connection.getBalance(rentVaultPda);
+
+    await program.methods
+      .fundRentVault({ amount: new anchor.BN(amount) })
+      .accounts({
+        config: configPda,
+        rentVault: rentVaultPda,
+        authority: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(rentVaultPda);
+    assert.equal(balanceAfter - balanceBefore, amount);
 
+    const rentVault = await program.account.rentVault.fetch(rentVaultPda);
+    assert.equal(rentVault.totalDeposited.toNumber(), amount);
+  });
+
+  it("Updates config", async () => {
     await program.methods
       .updateConfig({
         newAdmin: null,
+        newAcrossSpokePool: null,
+        newCctpTokenMessengerMinter: null,
+        newFeeBps: 50,
+        newOutboundFeeBps: 20,
+        newMinOutboundAmount: null,
+        newMaxOutboundAmount: new anchor.BN(100_000_000_000), // 100k USDC
+        newAcrossFeeBpsEstimate: null,
+        newCctpFeeBpsEstimate: null,
+        newOnwardAdapter: null,
+        newJitoStakePool: null,
+        newMarinadeState: null,
+        newKaminoLendingMarket: null,
+        newSolendLendingMarket: null,
+        newMaxGasTopUpUsdc: null,
+        maxDonationBps: null,
+        newRefundBountyBps: null,
+        newMinOrderUsdc: null,
+        newDeliverDustAsUsdc: null,
+        newMaxOpenOrdersPerRecipient: null,
+        newMaxPriceImpactBps: null,
+        newOrderTtlSeconds: null,
+        newEscrowShortfallTolerance: null,
+        newAcrossPaused: null,
+        newCctpPaused: null,
+        newInsuranceFeeShareBps: null,
+        newMaxInsuranceClaimUsdc: null,
+        newFallbackOutputMint: null,
+        newRentTreasury: null,
+        newMinRetentionSeconds: null,
+        newOperatorFillCostUsdc: null,
+        newGuardian: null,
+        newMaxOrderUsdc: null,
+        newEnabledAdapters: null,
+        newMaxRelayerTipUsdc: null,
+        surplusShareBps: null,
+        newMaxGasDropLamports: null,
+        newConfigUpdateDelaySeconds: null,
+        newOperator: null,
+        newDailyVolumeCap: null,
+        newSurplusPolicy: null,
+      })
+      .accounts({
+        config: configPda,
+        adminLog: adminLogPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    const config = await program.account.config.fetch(configPda);
+    assert.equal(config.feeBps, 50);
+    assert.equal(config.outboundFeeBps, 20);
+    assert.equal(config.maxOutboundAmount.toNumber(), 100_000_000_000);
+
+    // One entry per changed setting
+    const adminLog = await program.account.adminLog.fetch(adminLogPda);
+    assert.equal(adminLog.count.toNumber(), 3);
+    assert.ok(adminLog.entries.every((entry) => entry.actor.equals(admin.publicKey)));
+  });
+
+  it("Changes the fee recipient through a timelocked update", async () => {
+    const newFeeRecipient = Keypair.generate().publicKey;
+    const [pendingConfigUpdatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_config_update"), configPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .proposeConfigUpdate({
         newAcrossHandler: null,
         newJupiterProgram: null,
         newFeeRecipient: newFeeRecipient,
-        newFeeBps: 50,
+        newConfigUpdateDelaySeconds: null,
+        addAcrossHandler: null,
       })
       .accounts({
         config: configPda,
+        adminLog: adminLogPda,
+        pendingConfigUpdate: pendingConfigUpdatePda,
         admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .rpc();
 
-    const config = await program.account.config.fetch(configPda);
+    // Proposed, not yet applied
+    let config = await program.account.config.fetch(configPda);
+    assert.ok(config.feeRecipient.equals(feeRecipient.publicKey));
+
+    // The config's update delay is 0, so the change applies right away
+    await program.methods
+      .applyConfigUpdate()
+      .accounts({
+        config: configPda,
+        adminLog: adminLogPda,
+        pendingConfigUpdate: pendingConfigUpdatePda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    config = await program.account.config.fetch(configPda);
     assert.ok(config.feeRecipient.equals(newFeeRecipient));
-    assert.equal(config.feeBps, 50);
+    assert.isNull(await provider.connection.getAccountInfo(pendingConfigUpdatePda));
   });
 
   it("Pauses the program", async () => {
@@ -134,7 +287,8 @@ describe("superswap-sol", () => {
       .pause()
       .accounts({
         config: configPda,
-        admin: admin.publicKey,
+        adminLog: adminLogPda,
+        authority: admin.publicKey,
       })
       .rpc();
 
@@ -147,6 +301,7 @@ describe("superswap-sol", () => {
       .unpause()
       .accounts({
         config: configPda,
+        adminLog: adminLogPda,
         admin: admin.publicKey,
       })
       .rpc();
@@ -155,12 +310,38 @@ describe("superswap-sol", () => {
     assert.equal(config.isPaused, false);
   });
 
+  it("Enters and leaves refunds-only mode", async () => {
+    await program.methods
+      .setRefundsOnly({ enabled: true })
+      .accounts({
+        config: configPda,
+        adminLog: adminLogPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+    let config = await program.account.config.fetch(configPda);
+    assert.equal(config.refundsOnly, true);
+    assert.equal(config.isPaused, false);
+
+    await program.methods
+      .setRefundsOnly({ enabled: false })
+      .accounts({
+        config: configPda,
+        adminLog: adminLogPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+    config = await program.account.config.fetch(configPda);
+    assert.equal(config.refundsOnly, false);
+  });
+
   describe("Process bridge and swap", () => {
     let sourceUsdcAccount: PublicKey;
     let programUsdcAccount: PublicKey;
     let recipientUsdcAccount: PublicKey;
     let recipientDestinationAccount: PublicKey;
     let feeRecipientAccount: PublicKey;
+    let insuranceUsdcAccount: PublicKey;
     let swapOrderPda: PublicKey;
 
     const orderId = Date.now();
@@ -202,9 +383,19 @@ describe("superswap-sol", () => {
         owner: user.publicKey,
       });
 
+      const config = await program.account.config.fetch(configPda);
       feeRecipientAccount = await anchor.utils.token.associatedAddress({
         mint: usdcMint,
-        owner: feeRecipient.publicKey,
+        owner: config.feeRecipient,
+      });
+
+      const [insuranceVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("insurance_vault")],
+        program.programId
+      );
+      insuranceUsdcAccount = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: insuranceVaultPda,
       });
 
       // Derive swap order PDA
@@ -229,6 +420,15 @@ describe("superswap-sol", () => {
 
       const config = await program.account.config.fetch(configPda);
 
+      const [destinationMintEntry] = PublicKey.findProgramAddressSync(
+        [Buffer.from("destination_mint"), destinationMint.toBuffer()],
+        program.programId
+      );
+      const [mintConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint_config"), configPda.toBuffer(), destinationMint.toBuffer()],
+        program.programId
+      );
+
       const tx = await program.methods
         .processBridgeAndSwap({
           orderId: new anchor.BN(orderId),
@@ -238,6 +438,13 @@ describe("superswap-sol", () => {
           destinationMint: destinationMint,
           deadline: new anchor.BN(deadline),
           jupiterSwapData: jupiterSwapData,
+          postSwapHook: null,
+          gasTopUp: null,
+          donation: null,
+          relayerTip: new anchor.BN(0),
+          allowOffCurveRecipient: false,
+          refundFailedSwap: false,
+          gasDropLamports: new anchor.BN(0),
         })
         .accounts({
           config: configPda,
@@ -248,11 +455,32 @@ describe("superswap-sol", () => {
           sourceUsdcAccount: sourceUsdcAccount,
           programUsdcAccount: programUsdcAccount,
           destinationMint: destinationMint,
+          destinationMintEntry: destinationMintEntry,
+          mintConfig: mintConfigPda,
           recipientDestinationAccount: recipientDestinationAccount,
           recipientUsdcAccount: recipientUsdcAccount,
           feeRecipientAccount: feeRecipientAccount,
+          feeRecipient: config.feeRecipient,
+          insuranceUsdcAccount: insuranceUsdcAccount,
           jupiterProgram: config.jupiterProgram,
+          rentVault: rentVaultPda,
+          hookProgram: null,
+          hookProgramEntry: null,
+          donationRecipientEntry: null,
+          donationUsdcAccount: null,
+          recipientPreferences: null,
+          gasEscrow: null,
+          gasWsolAccount: null,
+          recipientProgram: null,
+          callbackAuthority: null,
+          nativeMint: null,
+          payerLedger: null,
           payer: admin.publicKey,
+          routeStaging: null,
+          routeTemplate: null,
+          relayerUsdcAccount: null,
+          surplusVault: null,
+          destinationTokenProgram: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,